@@ -0,0 +1,555 @@
+//! User-facing actions, decoupled from the keys that trigger them.
+
+use super::state::Panel;
+
+/// Where an action's key binding is active.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyContext {
+    /// Active regardless of the focused panel.
+    Global,
+    /// Active while the remotes panel is focused.
+    Remotes,
+    /// Active while the files panel is focused.
+    Files,
+    /// Active while the log pane is focused.
+    Logs,
+}
+
+impl KeyContext {
+    /// Returns whether bindings in this context fire for `panel`.
+    pub fn applies_to(self, panel: Panel) -> bool {
+        match self {
+            Self::Global => true,
+            Self::Remotes => panel == Panel::Remotes,
+            Self::Files => panel == Panel::Files,
+            Self::Logs => panel == Panel::Logs,
+        }
+    }
+
+    /// Returns whether a key could reach actions in both contexts.
+    pub fn overlaps(self, other: Self) -> bool {
+        self == Self::Global || other == Self::Global || self == other
+    }
+
+    /// Returns the heading used when listing bindings.
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Global => "Global",
+            Self::Remotes => "Remotes",
+            Self::Files => "Files",
+            Self::Logs => "Logs",
+        }
+    }
+}
+
+/// An action the user can trigger from a key binding.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    /// Quit the application.
+    Quit,
+    /// Move the selection down one row.
+    NavigateDown,
+    /// Move the selection up one row.
+    NavigateUp,
+    /// Jump to the first item.
+    JumpTop,
+    /// Jump to the last item.
+    JumpBottom,
+    /// Move the selection down half a screen.
+    HalfPageDown,
+    /// Move the selection up half a screen.
+    HalfPageUp,
+    /// Select the first visible row.
+    ScreenTop,
+    /// Select the middle visible row.
+    ScreenMiddle,
+    /// Select the last visible row.
+    ScreenBottom,
+    /// Toggle focus between the remotes and files panels.
+    SwitchPanel,
+    /// Open the selected remote or directory.
+    Open,
+    /// Go to the parent directory or back to the remotes panel.
+    Back,
+    /// Abandon the directory listing in flight.
+    Cancel,
+    /// Open the help overlay listing every key binding.
+    ShowHelp,
+    /// Open the command palette.
+    CommandPalette,
+    /// Jump to a typed `remote:path`.
+    GoToPath,
+    /// Pick a recently visited location to jump to.
+    RecentLocations,
+    /// Open the mounts view.
+    ShowMounts,
+    /// Open the jobs view.
+    ShowJobs,
+    /// Open the transfer history.
+    TransferHistory,
+    /// Open the audit log.
+    AuditLog,
+    /// Show or hide the log pane.
+    ToggleLogs,
+    /// Pin the log pane to the newest line, or release it.
+    ToggleLogFollow,
+    /// Switch logging to debug level and back.
+    ToggleDebugLogging,
+    /// Show or hide the last RC calls and how long they took.
+    ToggleCalls,
+    /// Move the split between the panels right, widening the remotes.
+    WidenRemotes,
+    /// Move the split between the panels left, widening the files.
+    NarrowRemotes,
+    /// Collapse the remotes panel to a strip, hide it, or show it again.
+    ToggleSidebar,
+    /// Let the focused panel fill the screen, or restore the layout.
+    ToggleZoom,
+    /// Open the create remote form.
+    AddRemote,
+    /// Open the edit form for the selected remote.
+    EditRemote,
+    /// Ask to delete the selected remote.
+    DeleteRemote,
+    /// Show the selected remote's settings and features.
+    ShowRemoteInfo,
+    /// Rename the selected remote.
+    RenameRemote,
+    /// Copy the selected remote under a new name.
+    DuplicateRemote,
+    /// Star the selected remote, or unstar it.
+    ToggleFavorite,
+    /// Move the selected remote up in the panel.
+    MoveRemoteUp,
+    /// Move the selected remote down in the panel.
+    MoveRemoteDown,
+    /// Mark the selected entry for bulk operations, or unmark it.
+    ToggleMark,
+    /// Mark every entry, or unmark all if every entry is marked.
+    MarkAll,
+    /// Mark the unmarked entries and unmark the marked ones.
+    InvertMarks,
+    /// Mark the entries whose names match a glob.
+    MarkGlob,
+    /// Limit listings by file size and age.
+    Filter,
+    /// Switch the files panel between its columns and names only.
+    ToggleColumns,
+    /// Switch sizes and times between readable and exact.
+    ToggleExact,
+    /// Ask to delete the selected file or directory.
+    DeleteFile,
+    /// Create a directory in the current path.
+    Mkdir,
+    /// Copy the selected file.
+    CopyFile,
+    /// Move the selected file.
+    MoveFile,
+    /// Rename the marked entries, or the selected one, by pattern.
+    BatchRename,
+    /// Move the marked entries, or the selected one, to another
+    /// storage tier or class.
+    ChangeTier,
+    /// Show the selected file's backend metadata.
+    ShowMetadata,
+    /// Browse the prior versions of the selected file.
+    ShowVersions,
+    /// Have the daemon download a URL into the current directory.
+    CopyUrl,
+    /// Show the selected file's content.
+    Preview,
+    /// Open the selected file in `$EDITOR` and upload it if changed.
+    EditFile,
+    /// Open the selected file with the system's default application.
+    OpenFile,
+    /// Put the marked entries, or the selected one, on the internal
+    /// clipboard to be copied.
+    Yank,
+    /// Put the marked entries, or the selected one, on the internal
+    /// clipboard to be moved.
+    Cut,
+    /// Copy or move the clipboard's entries into the current
+    /// directory.
+    Put,
+    /// Copy the selected entry's `remote:path` to the clipboard.
+    CopyPath,
+    /// Copy an `rclone copy` command for the selected entry to the
+    /// clipboard.
+    CopyCommand,
+    /// Show what takes up space below the current directory.
+    ShowUsage,
+    /// Compare the current directory with another one.
+    Compare,
+    /// Switch the files panel between a flat listing and a tree.
+    ToggleTree,
+    /// Expand the selected subdirectory in the tree.
+    ExpandDir,
+    /// Collapse the selected subdirectory in the tree, or the one
+    /// holding the selected entry.
+    CollapseDir,
+    /// Switch the files panel between the current directory and
+    /// every file below it.
+    ToggleFlat,
+    /// Sort the recursive listing by path, size or time.
+    SortFlat,
+    /// Narrow the recursive listing to the files a glob matches.
+    NarrowFlat,
+    /// Find the largest files below the current directory.
+    LargestFiles,
+}
+
+impl Action {
+    /// Every action, in the order bindings are listed.
+    pub const ALL: [Self; 72] = [
+        Self::Quit,
+        Self::NavigateDown,
+        Self::NavigateUp,
+        Self::JumpTop,
+        Self::JumpBottom,
+        Self::HalfPageDown,
+        Self::HalfPageUp,
+        Self::ScreenTop,
+        Self::ScreenMiddle,
+        Self::ScreenBottom,
+        Self::SwitchPanel,
+        Self::Open,
+        Self::Back,
+        Self::Cancel,
+        Self::ShowHelp,
+        Self::CommandPalette,
+        Self::GoToPath,
+        Self::RecentLocations,
+        Self::ShowMounts,
+        Self::ShowJobs,
+        Self::TransferHistory,
+        Self::AuditLog,
+        Self::ToggleLogs,
+        Self::ToggleLogFollow,
+        Self::ToggleDebugLogging,
+        Self::ToggleCalls,
+        Self::WidenRemotes,
+        Self::NarrowRemotes,
+        Self::ToggleSidebar,
+        Self::ToggleZoom,
+        Self::AddRemote,
+        Self::EditRemote,
+        Self::DeleteRemote,
+        Self::ShowRemoteInfo,
+        Self::RenameRemote,
+        Self::DuplicateRemote,
+        Self::ToggleFavorite,
+        Self::MoveRemoteUp,
+        Self::MoveRemoteDown,
+        Self::ToggleMark,
+        Self::MarkAll,
+        Self::InvertMarks,
+        Self::MarkGlob,
+        Self::Filter,
+        Self::ToggleColumns,
+        Self::ToggleExact,
+        Self::DeleteFile,
+        Self::Mkdir,
+        Self::CopyFile,
+        Self::MoveFile,
+        Self::BatchRename,
+        Self::ChangeTier,
+        Self::ShowMetadata,
+        Self::ShowVersions,
+        Self::CopyUrl,
+        Self::Preview,
+        Self::EditFile,
+        Self::OpenFile,
+        Self::Yank,
+        Self::Cut,
+        Self::Put,
+        Self::CopyPath,
+        Self::CopyCommand,
+        Self::ShowUsage,
+        Self::Compare,
+        Self::ToggleTree,
+        Self::ExpandDir,
+        Self::CollapseDir,
+        Self::ToggleFlat,
+        Self::SortFlat,
+        Self::NarrowFlat,
+        Self::LargestFiles,
+    ];
+
+    /// Returns the identifier used for this action in the config
+    /// file's `[keys]` table.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Quit => "quit",
+            Self::NavigateDown => "down",
+            Self::NavigateUp => "up",
+            Self::JumpTop => "top",
+            Self::JumpBottom => "bottom",
+            Self::HalfPageDown => "half_page_down",
+            Self::HalfPageUp => "half_page_up",
+            Self::ScreenTop => "screen_top",
+            Self::ScreenMiddle => "screen_middle",
+            Self::ScreenBottom => "screen_bottom",
+            Self::SwitchPanel => "switch_panel",
+            Self::Open => "open",
+            Self::Back => "back",
+            Self::Cancel => "cancel",
+            Self::ShowHelp => "help",
+            Self::CommandPalette => "command_palette",
+            Self::GoToPath => "go_to_path",
+            Self::RecentLocations => "recent_locations",
+            Self::ShowMounts => "mounts",
+            Self::ShowJobs => "jobs",
+            Self::TransferHistory => "transfer_history",
+            Self::AuditLog => "audit_log",
+            Self::ToggleLogs => "toggle_logs",
+            Self::ToggleLogFollow => "toggle_log_follow",
+            Self::ToggleDebugLogging => "debug_logging",
+            Self::ToggleCalls => "rc_calls",
+            Self::WidenRemotes => "widen_remotes",
+            Self::NarrowRemotes => "narrow_remotes",
+            Self::ToggleSidebar => "toggle_sidebar",
+            Self::ToggleZoom => "zoom",
+            Self::AddRemote => "add_remote",
+            Self::EditRemote => "edit_remote",
+            Self::DeleteRemote => "delete_remote",
+            Self::ShowRemoteInfo => "remote_info",
+            Self::RenameRemote => "rename_remote",
+            Self::DuplicateRemote => "duplicate_remote",
+            Self::ToggleFavorite => "toggle_favorite",
+            Self::MoveRemoteUp => "move_remote_up",
+            Self::MoveRemoteDown => "move_remote_down",
+            Self::ToggleMark => "toggle_mark",
+            Self::MarkAll => "mark_all",
+            Self::InvertMarks => "invert_marks",
+            Self::MarkGlob => "mark_glob",
+            Self::Filter => "filter",
+            Self::ToggleColumns => "toggle_columns",
+            Self::ToggleExact => "toggle_exact",
+            Self::ToggleTree => "toggle_tree",
+            Self::ExpandDir => "expand_dir",
+            Self::CollapseDir => "collapse_dir",
+            Self::ToggleFlat => "toggle_flat",
+            Self::SortFlat => "sort_flat",
+            Self::NarrowFlat => "narrow_flat",
+            Self::LargestFiles => "largest_files",
+            Self::DeleteFile => "delete_file",
+            Self::Mkdir => "mkdir",
+            Self::CopyFile => "copy_file",
+            Self::MoveFile => "move_file",
+            Self::BatchRename => "batch_rename",
+            Self::ChangeTier => "change_tier",
+            Self::ShowMetadata => "show_metadata",
+            Self::ShowVersions => "show_versions",
+            Self::CopyUrl => "copy_url",
+            Self::Preview => "preview",
+            Self::EditFile => "edit_file",
+            Self::OpenFile => "open_file",
+            Self::Yank => "yank",
+            Self::Cut => "cut",
+            Self::Put => "put",
+            Self::CopyPath => "copy_path",
+            Self::CopyCommand => "copy_command",
+            Self::ShowUsage => "show_usage",
+            Self::Compare => "compare",
+        }
+    }
+
+    /// Returns the action with the given config name.
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|action| action.name() == name)
+    }
+
+    /// Returns a short human-readable description.
+    pub fn description(self) -> &'static str {
+        match self {
+            Self::Quit => "Quit",
+            Self::NavigateDown => "Move down",
+            Self::NavigateUp => "Move up",
+            Self::JumpTop => "Jump to top",
+            Self::JumpBottom => "Jump to bottom",
+            Self::HalfPageDown => "Half page down",
+            Self::HalfPageUp => "Half page up",
+            Self::ScreenTop => "Top of screen",
+            Self::ScreenMiddle => "Middle of screen",
+            Self::ScreenBottom => "Bottom of screen",
+            Self::SwitchPanel => "Switch panel",
+            Self::Open => "Open remote or directory",
+            Self::Back => "Go back",
+            Self::Cancel => "Cancel loading",
+            Self::ShowHelp => "Help",
+            Self::CommandPalette => "Command palette",
+            Self::GoToPath => "Go to remote:path",
+            Self::RecentLocations => "Recent locations",
+            Self::ShowMounts => "Mounts",
+            Self::ShowJobs => "Jobs",
+            Self::TransferHistory => "Transfer history",
+            Self::AuditLog => "Audit log",
+            Self::ToggleLogs => "Toggle log pane",
+            Self::ToggleLogFollow => "Follow newest log line",
+            Self::ToggleDebugLogging => "Toggle debug logging",
+            Self::ToggleCalls => "RC call latency overlay",
+            Self::WidenRemotes => "Widen remotes panel",
+            Self::NarrowRemotes => "Widen files panel",
+            Self::ToggleSidebar => "Collapse remotes panel",
+            Self::ToggleZoom => "Zoom focused panel",
+            Self::AddRemote => "Add remote",
+            Self::EditRemote => "Edit remote",
+            Self::DeleteRemote => "Delete remote",
+            Self::ShowRemoteInfo => "Show remote details",
+            Self::RenameRemote => "Rename remote",
+            Self::DuplicateRemote => "Duplicate remote",
+            Self::ToggleFavorite => "Star remote",
+            Self::MoveRemoteUp => "Move remote up",
+            Self::MoveRemoteDown => "Move remote down",
+            Self::ToggleMark => "Mark / unmark entry",
+            Self::MarkAll => "Mark / unmark all",
+            Self::InvertMarks => "Invert marks",
+            Self::MarkGlob => "Mark entries matching a glob",
+            Self::Filter => "Filter by size and age",
+            Self::ToggleColumns => "Toggle columns / names only",
+            Self::ToggleExact => "Toggle exact sizes and times",
+            Self::ToggleTree => "Toggle tree view",
+            Self::ExpandDir => "Expand directory in tree",
+            Self::CollapseDir => "Collapse directory in tree",
+            Self::ToggleFlat => "Toggle recursive listing",
+            Self::SortFlat => "Sort recursive listing",
+            Self::NarrowFlat => "Narrow recursive listing",
+            Self::LargestFiles => "Largest files",
+            Self::DeleteFile => "Delete file or directory",
+            Self::Mkdir => "New directory",
+            Self::CopyFile => "Copy file",
+            Self::MoveFile => "Move file",
+            Self::BatchRename => "Batch rename selection",
+            Self::ChangeTier => "Change storage tier",
+            Self::ShowMetadata => "Show object metadata",
+            Self::ShowVersions => "Browse file versions",
+            Self::CopyUrl => "Copy URL into remote",
+            Self::Preview => "Preview file",
+            Self::EditFile => "Edit file in $EDITOR",
+            Self::OpenFile => "Open with default app",
+            Self::Yank => "Yank selection",
+            Self::Cut => "Cut selection",
+            Self::Put => "Put yanked entries here",
+            Self::CopyPath => "Copy remote:path",
+            Self::CopyCommand => "Copy rclone copy command",
+            Self::ShowUsage => "Show disk usage",
+            Self::Compare => "Compare with another directory",
+        }
+    }
+
+    /// Returns the context in which this action's keys are active.
+    pub fn context(self) -> KeyContext {
+        match self {
+            Self::AddRemote
+            | Self::EditRemote
+            | Self::DeleteRemote
+            | Self::ShowRemoteInfo
+            | Self::RenameRemote
+            | Self::DuplicateRemote
+            | Self::ToggleFavorite
+            | Self::MoveRemoteUp
+            | Self::MoveRemoteDown => KeyContext::Remotes,
+            Self::ToggleMark
+            | Self::MarkAll
+            | Self::InvertMarks
+            | Self::MarkGlob
+            | Self::Filter
+            | Self::ToggleColumns
+            | Self::ToggleExact
+            | Self::ToggleTree
+            | Self::ExpandDir
+            | Self::CollapseDir
+            | Self::ToggleFlat
+            | Self::SortFlat
+            | Self::NarrowFlat
+            | Self::LargestFiles
+            | Self::DeleteFile
+            | Self::Mkdir
+            | Self::CopyFile
+            | Self::MoveFile
+            | Self::BatchRename
+            | Self::ChangeTier
+            | Self::ShowMetadata
+            | Self::ShowVersions
+            | Self::CopyUrl
+            | Self::Preview
+            | Self::EditFile
+            | Self::OpenFile
+            | Self::Yank
+            | Self::Cut
+            | Self::Put
+            | Self::CopyPath
+            | Self::CopyCommand
+            | Self::ShowUsage
+            | Self::Compare => KeyContext::Files,
+            Self::ToggleLogFollow | Self::ToggleDebugLogging => KeyContext::Logs,
+            Self::Quit
+            | Self::NavigateDown
+            | Self::NavigateUp
+            | Self::JumpTop
+            | Self::JumpBottom
+            | Self::HalfPageDown
+            | Self::HalfPageUp
+            | Self::ScreenTop
+            | Self::ScreenMiddle
+            | Self::ScreenBottom
+            | Self::SwitchPanel
+            | Self::Open
+            | Self::Back
+            | Self::Cancel
+            | Self::ShowHelp
+            | Self::CommandPalette
+            | Self::GoToPath
+            | Self::RecentLocations
+            | Self::ShowMounts
+            | Self::ShowJobs
+            | Self::TransferHistory
+            | Self::AuditLog
+            | Self::ToggleLogs
+            | Self::ToggleCalls
+            | Self::WidenRemotes
+            | Self::NarrowRemotes
+            | Self::ToggleSidebar
+            | Self::ToggleZoom => KeyContext::Global,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_names_round_trip() {
+        for action in Action::ALL {
+            assert_eq!(Action::from_name(action.name()), Some(action));
+        }
+    }
+
+    #[test]
+    fn test_names_are_unique() {
+        let mut names: Vec<_> = Action::ALL.iter().map(|a| a.name()).collect();
+        names.sort_unstable();
+        names.dedup();
+        assert_eq!(names.len(), Action::ALL.len());
+    }
+
+    #[test]
+    fn test_unknown_name_is_none() {
+        assert_eq!(Action::from_name("launch_rockets"), None);
+    }
+
+    #[test]
+    fn test_panel_contexts_do_not_overlap() {
+        assert!(!KeyContext::Remotes.overlaps(KeyContext::Files));
+        assert!(KeyContext::Global.overlaps(KeyContext::Files));
+        assert!(KeyContext::Remotes.overlaps(KeyContext::Remotes));
+    }
+
+    #[test]
+    fn test_context_applies_to_matching_panel() {
+        assert!(KeyContext::Global.applies_to(Panel::Files));
+        assert!(KeyContext::Remotes.applies_to(Panel::Remotes));
+        assert!(!KeyContext::Files.applies_to(Panel::Remotes));
+    }
+}
@@ -0,0 +1,127 @@
+//! Daemon activity for the status bar: how many jobs run and how fast
+//! files move. Polled only while a status bar segment shows either.
+
+use super::event::AppEvent;
+use super::state::App;
+use crate::error::Result;
+use crate::rclone::{Job, JobState, Transfer};
+use std::time::{Duration, Instant};
+use tracing::debug;
+
+/// Time between polls.
+pub const ACTIVITY_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Poll schedule and what the last poll found.
+#[derive(Debug, Clone)]
+pub struct Activity {
+    /// When the next poll is due.
+    pub next_poll: Instant,
+    /// Whether a poll is in flight.
+    pub in_flight: bool,
+    /// Jobs running on the daemon; None until known.
+    pub jobs: Option<usize>,
+    /// Combined speed of the daemon's transfers in bytes per second;
+    /// None until known.
+    pub speed: Option<f64>,
+}
+
+impl Activity {
+    /// Schedules the first poll for `now`.
+    pub fn new(now: Instant) -> Self {
+        Self {
+            next_poll: now,
+            in_flight: false,
+            jobs: None,
+            speed: None,
+        }
+    }
+}
+
+impl App {
+    /// Lists the daemon's jobs and transfers on a background task if
+    /// the status bar shows them and a poll is due. The answer arrives
+    /// as [`AppEvent::ActivityLoaded`].
+    pub fn tick_activity(&mut self, now: Instant) {
+        if !self.status_bar.polls()
+            || self.offline()
+            || self.activity.in_flight
+            || now < self.activity.next_poll
+        {
+            return;
+        }
+        self.activity.in_flight = true;
+        let client = self.client.clone();
+        self.workers.spawn(async move {
+            let (jobs, transfers) = tokio::join!(client.list_jobs(), client.transfers());
+            AppEvent::ActivityLoaded { jobs, transfers }
+        });
+    }
+
+    /// Records a poll's answer and schedules the next one. What failed
+    /// to load is shown as unknown.
+    pub fn apply_activity(
+        &mut self,
+        jobs: Result<Vec<Job>>,
+        transfers: Result<Vec<Transfer>>,
+        now: Instant,
+    ) {
+        self.activity.in_flight = false;
+        self.activity.next_poll = now + ACTIVITY_INTERVAL;
+        self.activity.jobs = match jobs {
+            Ok(jobs) => Some(
+                jobs.iter()
+                    .filter(|job| job.state() == JobState::Running)
+                    .count(),
+            ),
+            Err(e) => {
+                debug!(error = %e, "listing jobs for the status bar failed");
+                None
+            }
+        };
+        self.activity.speed = match transfers {
+            Ok(transfers) => Some(transfers.iter().map(|transfer| transfer.speed).sum()),
+            Err(e) => {
+                debug!(error = %e, "listing transfers for the status bar failed");
+                None
+            }
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::handler::test_support::unreachable_app;
+    use crate::config::Segment;
+
+    #[tokio::test]
+    async fn test_polls_only_for_activity_segments() {
+        let mut app = unreachable_app();
+        let now = Instant::now();
+
+        app.tick_activity(now);
+        assert!(!app.activity.in_flight);
+
+        app.status_bar.segments.push(Segment::Jobs);
+        app.tick_activity(now);
+        assert!(app.activity.in_flight);
+        let Some(AppEvent::ActivityLoaded { jobs, transfers }) = app.wait_event().await else {
+            panic!("no activity");
+        };
+        app.apply_activity(jobs, transfers, now);
+        assert!(!app.activity.in_flight);
+        assert_eq!(app.activity.jobs, None);
+
+        let running = Job {
+            id: 1,
+            ..Job::default()
+        };
+        let transfer = Transfer {
+            speed: 1024.0,
+            ..Transfer::default()
+        };
+        app.apply_activity(Ok(vec![running]), Ok(vec![transfer.clone(), transfer]), now);
+        assert_eq!(app.activity.jobs, Some(1));
+        assert_eq!(app.activity.speed, Some(2048.0));
+    }
+}
@@ -0,0 +1,271 @@
+//! Turning finished changes into audit log records: which events
+//! report a change, and how each is described.
+
+use super::command::rclone_path;
+use super::event::AppEvent;
+use super::state::App;
+use crate::config::AuditRecord;
+use crate::error::Result;
+use crate::ui::FileOperationType;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tracing::warn;
+
+impl App {
+    /// Appends `event` to the audit log, if there is one and the event
+    /// reports a change, failed ones included. A log that cannot be
+    /// written is logged; it never fails the change itself.
+    pub(crate) fn audit(&self, event: &AppEvent) {
+        let Some(ref audit) = self.audit else {
+            return;
+        };
+        let Some(mut record) = record(event) else {
+            return;
+        };
+        record.at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |elapsed| elapsed.as_secs() as i64);
+        if let Err(e) = audit.append(&record) {
+            warn!(error = %e, "could not record change");
+        }
+    }
+}
+
+/// Returns the name a file operation is recorded under.
+fn operation_name(operation: FileOperationType) -> &'static str {
+    match operation {
+        FileOperationType::DeleteFile => "delete",
+        FileOperationType::DeleteDirectory => "purge",
+        FileOperationType::Mkdir => "mkdir",
+        FileOperationType::Copy => "copy",
+        FileOperationType::Move => "move",
+    }
+}
+
+/// Describes the change `event` reports, by the current user and with
+/// no time set, or None if it reports none.
+fn record(event: &AppEvent) -> Option<AuditRecord> {
+    let entry = |action: &str, target: String, detail: Option<String>, error| AuditRecord {
+        at: 0,
+        user: AuditRecord::current_user(),
+        action: action.to_string(),
+        target,
+        detail,
+        error,
+    };
+    let record = match event {
+        AppEvent::Uploaded {
+            remote,
+            path,
+            result,
+            ..
+        } => entry("upload", rclone_path(remote, path), None, failure(result)),
+        AppEvent::FileOperationFinished {
+            operation,
+            target,
+            message,
+            result,
+            ..
+        } => entry(
+            operation_name(*operation),
+            target.clone(),
+            result.is_ok().then(|| message.clone()),
+            failure(result),
+        ),
+        AppEvent::Put {
+            done,
+            total,
+            source,
+            destination,
+            cut,
+            result,
+            ..
+        } => entry(
+            if *cut { "move" } else { "copy" },
+            source.clone(),
+            Some(format!("{} of {} entries to {}", done, total, destination)),
+            failure(result),
+        ),
+        AppEvent::BatchRenamed {
+            renamed,
+            total,
+            location,
+            result,
+        } => entry(
+            "rename",
+            location.clone(),
+            Some(format!("{} of {} entries", renamed, total)),
+            failure(result),
+        ),
+        AppEvent::TierChanged {
+            changed,
+            total,
+            location,
+            tier,
+            result,
+        } => entry(
+            "change tier",
+            location.clone(),
+            Some(format!("{} of {} entries to {}", changed, total, tier)),
+            failure(result),
+        ),
+        AppEvent::UrlCopied {
+            remote,
+            path,
+            url,
+            result,
+        } => entry(
+            "copy url",
+            rclone_path(remote, path),
+            // Query strings of signed URLs carry credentials.
+            url.split(['?', '#']).next().map(str::to_string),
+            failure(result),
+        ),
+        AppEvent::VersionRestored {
+            remote,
+            path,
+            version,
+            result,
+        } => entry(
+            "restore version",
+            rclone_path(remote, path),
+            Some(version.clone()),
+            failure(result),
+        ),
+        AppEvent::MetadataSaved {
+            remote,
+            path,
+            keys,
+            result,
+        } => entry(
+            "set metadata",
+            rclone_path(remote, path),
+            Some(keys.join(", ")),
+            failure(result),
+        ),
+        AppEvent::RemoteUpdated { name, result } => {
+            entry("update remote", name.clone(), None, failure(result))
+        }
+        AppEvent::RemoteCopied {
+            from,
+            to,
+            result,
+            removed,
+        } => {
+            let (action, result) = match (result, removed) {
+                (Ok(()), Some(removed)) => ("rename remote", removed),
+                _ => ("copy remote", result),
+            };
+            entry(
+                action,
+                from.clone(),
+                Some(format!("as {}", to)),
+                failure(result),
+            )
+        }
+        AppEvent::RemoteDeleted {
+            name,
+            discard: false,
+            result,
+        } => entry("delete remote", name.clone(), None, failure(result)),
+        // Each answered question is a step; only the last one, or a
+        // failed one, finishes creating the remote.
+        AppEvent::RemoteConfigStep {
+            result: Ok(Some(_)),
+            ..
+        } => return None,
+        AppEvent::RemoteConfigStep { name, result } => {
+            entry("create remote", name.clone(), None, failure(result))
+        }
+        AppEvent::ScriptFinished { name, result } => {
+            entry("run script", name.clone(), None, failure(result))
+        }
+        AppEvent::Mounted {
+            source,
+            mount_point,
+            result,
+        } => entry(
+            "mount",
+            source.clone(),
+            Some(format!("on {}", mount_point)),
+            failure(result),
+        ),
+        AppEvent::Unmounted {
+            mount_point,
+            result,
+        } => entry("unmount", mount_point.clone(), None, failure(result)),
+        AppEvent::JobStopped { id, result } => {
+            entry("stop job", format!("job {}", id), None, failure(result))
+        }
+        _ => return None,
+    };
+    Some(record)
+}
+
+/// Returns why `result` failed, if it did.
+fn failure<T>(result: &Result<T>) -> Option<String> {
+    result.as_ref().err().map(ToString::to_string)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::handler::test_support::unreachable_app;
+    use crate::config::AuditLog;
+    use crate::error::LazyFileError;
+    use std::time::Duration;
+
+    #[test]
+    fn test_records_changes_only() {
+        let event = AppEvent::FileOperationFinished {
+            operation: FileOperationType::DeleteDirectory,
+            target: "s3:team/old".to_string(),
+            message: "Deleted directory 'old'".to_string(),
+            elapsed: Duration::ZERO,
+            result: Err(LazyFileError::Cancelled {
+                endpoint: "operations/purge",
+            }),
+        };
+        let purge = record(&event).unwrap();
+        assert_eq!(purge.action, "purge");
+        assert_eq!(purge.target, "s3:team/old");
+        assert_eq!(purge.detail, None);
+        assert!(purge.error.is_some());
+
+        let event = AppEvent::RemoteCopied {
+            from: "old".to_string(),
+            to: "new".to_string(),
+            result: Ok(()),
+            removed: Some(Ok(())),
+        };
+        let rename = record(&event).unwrap();
+        assert_eq!(rename.action, "rename remote");
+        assert_eq!(rename.detail.as_deref(), Some("as new"));
+
+        let discarded = AppEvent::RemoteDeleted {
+            name: "half".to_string(),
+            discard: true,
+            result: Ok(()),
+        };
+        assert!(record(&discarded).is_none());
+        assert!(record(&AppEvent::Pinged(Ok("1.66".to_string()))).is_none());
+    }
+
+    #[test]
+    fn test_appends_to_the_audit_file() {
+        let dir = std::env::temp_dir().join(format!("lazyfile-app-audit-{}", std::process::id()));
+        let mut app = unreachable_app();
+        app.audit = Some(AuditLog::new(dir.join("audit.jsonl")));
+
+        app.audit(&AppEvent::JobStopped {
+            id: 7,
+            result: Ok(()),
+        });
+        app.audit(&AppEvent::JobsLoaded(Ok(Vec::new())));
+
+        let records = app.audit.as_ref().unwrap().load().unwrap();
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].target, "job 7");
+        assert!(records[0].at > 0);
+        let _ = std::fs::remove_dir_all(dir);
+    }
+}
@@ -0,0 +1,45 @@
+//! Columns of the files panel.
+
+use super::state::App;
+use tracing::debug;
+
+impl App {
+    /// Switches the files panel between its configured columns and
+    /// names only. Showing a hash column again lists the directory
+    /// again, since compact listings leave hashes out.
+    pub fn toggle_compact(&mut self) {
+        self.compact = !self.compact;
+        debug!(compact = self.compact, "toggled compact file list");
+        if !self.compact && self.file_list.hash_type().is_some() {
+            self.reload_files();
+        }
+    }
+
+    /// Returns the hash type listings ask for: the hash column's,
+    /// unless the panel is compact or has no such column.
+    pub fn hash_column(&self) -> Option<&str> {
+        if self.compact {
+            None
+        } else {
+            self.file_list.hash_type()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::app::handler::test_support::unreachable_app;
+    use crate::config::Column;
+
+    #[test]
+    fn test_compact_view_skips_hashes() {
+        let mut app = unreachable_app();
+        assert_eq!(app.hash_column(), None);
+
+        app.file_list.columns.push(Column::Hash);
+        assert_eq!(app.hash_column(), Some("md5"));
+        app.toggle_compact();
+        assert_eq!(app.hash_column(), None);
+    }
+}
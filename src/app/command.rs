@@ -0,0 +1,167 @@
+//! User-defined shell commands from the config file's `[commands]`
+//! table.
+//!
+//! A command is a shell template whose placeholders are replaced with
+//! shell-quoted values from the selection:
+//!
+//! - `%paths%`: the selected entries as `remote:path`
+//! - `%dir%`: the current directory as `remote:path`
+//! - `%remote%`: the remote's name
+//!
+//! The launcher runs the result through the shell with the TUI
+//! suspended, so its output stays on screen until the user returns.
+//! The quoting is POSIX only: `cmd` has none that keeps a file named
+//! `a&calc.exe` from running as a command, so commands are refused on
+//! Windows (see [`SUPPORTED`]).
+
+use super::state::App;
+use tracing::{debug, warn};
+
+/// A command ready to run.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ShellCommand {
+    /// Name from the `[commands]` table.
+    pub name: String,
+    /// Command line with placeholders filled in.
+    pub line: String,
+}
+
+/// Fills in the placeholders of `template` in one pass, so values
+/// that happen to contain a placeholder are left alone. Without a
+/// remote, every placeholder is empty.
+pub fn expand(template: &str, remote: Option<&str>, dir: &str, paths: &[String]) -> String {
+    let values = match remote {
+        Some(remote) => [
+            (
+                "%paths%",
+                paths
+                    .iter()
+                    .map(|path| shell_quote(&rclone_path(remote, path)))
+                    .collect::<Vec<_>>()
+                    .join(" "),
+            ),
+            ("%dir%", shell_quote(&rclone_path(remote, dir))),
+            ("%remote%", shell_quote(remote)),
+        ],
+        None => [
+            ("%paths%", String::new()),
+            ("%dir%", String::new()),
+            ("%remote%", String::new()),
+        ],
+    };
+
+    let mut line = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find('%') {
+        line.push_str(&rest[..start]);
+        rest = &rest[start..];
+        match values.iter().find(|(key, _)| rest.starts_with(key)) {
+            Some((key, value)) => {
+                line.push_str(value);
+                rest = &rest[key.len()..];
+            }
+            None => {
+                line.push('%');
+                rest = &rest[1..];
+            }
+        }
+    }
+    line.push_str(rest);
+    line
+}
+
+/// Whether the `[commands]` table is used on this platform.
+pub const SUPPORTED: bool = !cfg!(windows);
+
+/// Returns the shell and the flag that makes it run one command line.
+pub fn shell() -> (&'static str, &'static str) {
+    ("sh", "-c")
+}
+
+/// Formats a path the way the rclone CLI accepts it.
+pub(crate) fn rclone_path(remote: &str, path: &str) -> String {
+    format!("{}:{}", remote, path.trim_start_matches('/'))
+}
+
+/// Quotes `word` for POSIX shells when it contains anything beyond
+/// characters that are always safe.
+pub(crate) fn shell_quote(word: &str) -> String {
+    let safe = |c: char| c.is_ascii_alphanumeric() || "-_./:@%+=,".contains(c);
+    if !word.is_empty() && word.chars().all(safe) {
+        word.to_string()
+    } else {
+        format!("'{}'", word.replace('\'', r"'\''"))
+    }
+}
+
+impl App {
+    /// Expands the command `name` for the current selection and
+    /// leaves it for the launcher to run before the next frame.
+    ///
+    /// Returns `false` if no command has that name.
+    pub fn request_command(&mut self, name: &str) -> bool {
+        let Some(template) = self.commands.get(name) else {
+            warn!(command = name, "unknown command");
+            return false;
+        };
+        let paths = self.selected_paths();
+        let line = expand(
+            template,
+            self.current_remote.as_deref(),
+            &self.current_path,
+            &paths,
+        );
+        debug!(command = name, line = %line, "command expanded");
+        self.pending_command = Some(ShellCommand {
+            name: name.to_string(),
+            line,
+        });
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_expands_placeholders_quoted() {
+        let paths = vec!["docs/my file.txt".to_string(), "docs/b.txt".to_string()];
+        assert_eq!(
+            expand(
+                "rclone check %paths% --dir %dir% on %remote%",
+                Some("gdrive"),
+                "docs",
+                &paths
+            ),
+            "rclone check 'gdrive:docs/my file.txt' gdrive:docs/b.txt --dir gdrive:docs on gdrive"
+        );
+    }
+
+    #[test]
+    fn test_values_are_not_expanded_again() {
+        let paths = vec!["%remote%.txt".to_string()];
+        assert_eq!(
+            expand("echo %paths% 100%", Some("s3"), "", &paths),
+            "echo s3:%remote%.txt 100%"
+        );
+    }
+
+    #[test]
+    fn test_placeholders_are_empty_without_remote() {
+        assert_eq!(expand("ls %paths% %remote%", None, "", &[]), "ls  ");
+    }
+
+    #[test]
+    fn test_rclone_path_joins_remote_and_path() {
+        assert_eq!(rclone_path("gdrive", "docs/a.txt"), "gdrive:docs/a.txt");
+        assert_eq!(rclone_path("gdrive", ""), "gdrive:");
+    }
+
+    #[test]
+    fn test_shell_quote_escapes_spaces_and_quotes() {
+        assert_eq!(shell_quote("gdrive:my docs"), "'gdrive:my docs'");
+        assert_eq!(shell_quote("it's"), r"'it'\''s'");
+        assert_eq!(shell_quote(""), "''");
+    }
+}
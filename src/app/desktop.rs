@@ -0,0 +1,89 @@
+//! Desktop notifications for operations that finish while the user
+//! looks at another window.
+//!
+//! They go through the platform's own tool, `notify-send` or
+//! `osascript`, rather than a library; a system without one simply
+//! gets no notification.
+
+use super::state::App;
+use std::process::Stdio;
+use std::time::Duration;
+use tracing::debug;
+
+/// Returns the program and arguments that show a notification, or
+/// `None` where there is no tool to ask. `notify-send` gets `--`
+/// first, so a summary or body starting with `-`, as a file or
+/// remote name can, is not read as an option.
+fn command(summary: &str, body: &str) -> Option<(&'static str, Vec<String>)> {
+    if cfg!(target_os = "macos") {
+        let script = format!(
+            "display notification {} with title {}",
+            applescript_string(body),
+            applescript_string(summary)
+        );
+        Some(("osascript", vec!["-e".to_string(), script]))
+    } else if cfg!(windows) {
+        None
+    } else {
+        Some((
+            "notify-send",
+            vec![
+                "--app-name=LazyFile".to_string(),
+                "--".to_string(),
+                summary.to_string(),
+                body.to_string(),
+            ],
+        ))
+    }
+}
+
+/// Quotes `text` as an AppleScript string literal.
+fn applescript_string(text: &str) -> String {
+    format!("\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+impl App {
+    /// Raises a desktop notification if an operation that took
+    /// `elapsed` ran past [`App::notify_after`]. The notifier is not
+    /// waited for, and a failure to start it is only logged.
+    pub fn notify_if_slow(&self, elapsed: Duration, summary: &str, body: &str) {
+        if self.notify_after.is_none_or(|after| elapsed < after) {
+            return;
+        }
+        let Some((program, args)) = command(summary, body) else {
+            debug!("no desktop notifier on this platform");
+            return;
+        };
+        let spawned = tokio::process::Command::new(program)
+            .args(args)
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn();
+        match spawned {
+            Ok(_) => debug!(program, summary, "raised desktop notification"),
+            Err(e) => debug!(program, error = %e, "could not raise desktop notification"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_quotes_applescript_strings() {
+        assert_eq!(
+            applescript_string(r#"Copied "a\b""#),
+            r#""Copied \"a\\b\"""#
+        );
+    }
+
+    #[cfg(not(any(target_os = "macos", windows)))]
+    #[test]
+    fn test_leading_dash_names_are_not_options() {
+        let (program, args) = command("-rf copied", "-v.txt").unwrap();
+        assert_eq!(program, "notify-send");
+        assert_eq!(args, ["--app-name=LazyFile", "--", "-rf copied", "-v.txt"]);
+    }
+}
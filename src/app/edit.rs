@@ -0,0 +1,311 @@
+//! Working on remote files with local programs.
+//!
+//! Files are downloaded into a private scratch directory. For editing,
+//! the launcher suspends the TUI while the editor runs and the copy is
+//! uploaded back if its content changed. Opening hands the copy to the
+//! desktop's default application instead.
+
+use super::event::AppEvent;
+use super::state::App;
+use crate::error::{LazyFileError, Result};
+use std::fs::{self, DirBuilder};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::sync::atomic::{AtomicU64, Ordering};
+use tracing::{debug, warn};
+
+/// Editor used when neither `$VISUAL` nor `$EDITOR` is set.
+const FALLBACK_EDITOR: &str = "vi";
+
+/// Distinguishes scratch directories created by one process.
+static SCRATCH_COUNT: AtomicU64 = AtomicU64::new(0);
+
+/// Private temporary directory holding downloaded copies. Removed,
+/// with everything in it, when dropped.
+#[derive(Debug)]
+pub struct Scratch {
+    root: PathBuf,
+    files: u64,
+}
+
+impl Scratch {
+    /// Creates a new directory under the system temp directory,
+    /// readable only by the current user.
+    ///
+    /// # Errors
+    /// Returns error if the directory cannot be created.
+    pub fn create() -> io::Result<Self> {
+        let root = std::env::temp_dir().join(format!(
+            "lazyfile-{}-{}",
+            std::process::id(),
+            SCRATCH_COUNT.fetch_add(1, Ordering::Relaxed)
+        ));
+        create_private_dir(&root)?;
+        debug!(root = %root.display(), "created scratch directory");
+        Ok(Self { root, files: 0 })
+    }
+
+    /// Returns a fresh local path for the remote file at `path`.
+    ///
+    /// Each file gets its own subdirectory so it keeps its real name,
+    /// which editors use to pick a file type.
+    ///
+    /// # Errors
+    /// Returns error if the subdirectory cannot be created.
+    pub fn file_for(&mut self, path: &str) -> io::Result<PathBuf> {
+        self.files += 1;
+        let dir = self.root.join(self.files.to_string());
+        create_private_dir(&dir)?;
+        let name = path
+            .rsplit('/')
+            .find(|segment| !segment.is_empty())
+            .unwrap_or("file");
+        Ok(dir.join(name))
+    }
+
+    /// Returns the directory everything is stored in.
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+}
+
+impl Drop for Scratch {
+    fn drop(&mut self) {
+        if let Err(e) = fs::remove_dir_all(&self.root) {
+            warn!(root = %self.root.display(), error = %e, "could not remove scratch directory");
+        }
+    }
+}
+
+/// Creates `path` and its parents, with owner-only permissions on
+/// Unix.
+fn create_private_dir(path: &Path) -> io::Result<()> {
+    let mut builder = DirBuilder::new();
+    builder.recursive(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::DirBuilderExt;
+        builder.mode(0o700);
+    }
+    builder.create(path)
+}
+
+/// Removes the per-file directory holding a local copy once it is no
+/// longer needed.
+pub fn discard_copy(local: &Path) {
+    if let Some(dir) = local.parent()
+        && let Err(e) = fs::remove_dir_all(dir)
+    {
+        warn!(dir = %dir.display(), error = %e, "could not remove local copy");
+    }
+}
+
+/// A downloaded file waiting for, or returned from, the editor.
+#[derive(Debug, Clone)]
+pub struct EditSession {
+    /// Remote the file lives on.
+    pub remote: String,
+    /// Path of the file within the remote.
+    pub path: String,
+    /// Local copy handed to the editor.
+    pub local: PathBuf,
+    /// Content as downloaded, compared against the copy afterwards to
+    /// decide whether to upload.
+    pub original: Vec<u8>,
+}
+
+/// Returns the editor program and its arguments, from `$VISUAL`, then
+/// `$EDITOR`, then `vi`.
+pub fn editor_command() -> (String, Vec<String>) {
+    editor_from(std::env::var("VISUAL").ok(), std::env::var("EDITOR").ok())
+}
+
+/// Splits the first non-blank command on whitespace. Quoting is not
+/// supported, as with most tools that read `$EDITOR`.
+fn editor_from(visual: Option<String>, editor: Option<String>) -> (String, Vec<String>) {
+    let command = [visual, editor]
+        .into_iter()
+        .flatten()
+        .find(|command| !command.trim().is_empty())
+        .unwrap_or_else(|| FALLBACK_EDITOR.to_string());
+    let mut words = command.split_whitespace().map(str::to_string);
+    let program = words.next().unwrap_or_else(|| FALLBACK_EDITOR.to_string());
+    (program, words.collect())
+}
+
+/// Returns the program and leading arguments that open a file with
+/// the desktop's default application.
+fn opener() -> (&'static str, &'static [&'static str]) {
+    if cfg!(target_os = "macos") {
+        ("open", &[])
+    } else if cfg!(windows) {
+        // `start` is a shell builtin; the empty argument is the window
+        // title, without which a quoted path would be taken as one.
+        ("cmd", &["/C", "start", ""])
+    } else {
+        ("xdg-open", &[])
+    }
+}
+
+/// Opens `local` with the default application. Returns once the
+/// opener exits, which for all supported openers is right after the
+/// application was launched.
+async fn open_with_default_app(local: &Path) -> Result<()> {
+    let (program, args) = opener();
+    let status = tokio::process::Command::new(program)
+        .args(args)
+        .arg(local)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .await?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(LazyFileError::Io(io::Error::other(format!(
+            "{} exited with {}",
+            program, status
+        ))))
+    }
+}
+
+impl App {
+    /// Returns a fresh path in the scratch directory for the remote
+    /// file at `path`, creating the directory on first use.
+    fn local_copy(&mut self, path: &str) -> io::Result<PathBuf> {
+        let scratch = match self.scratch {
+            Some(ref mut scratch) => scratch,
+            None => self.scratch.insert(Scratch::create()?),
+        };
+        scratch.file_for(path)
+    }
+
+    /// Starts downloading a file into the scratch directory on a
+    /// background task. The result arrives as [`AppEvent::EditReady`].
+    ///
+    /// # Errors
+    /// Returns error if the scratch directory cannot be created.
+    pub fn request_edit(&mut self, remote: String, path: String) -> io::Result<()> {
+        let local = self.local_copy(&path)?;
+        debug!(remote = %remote, path = %path, local = %local.display(), "downloading for edit");
+
+        let client = self.client.clone();
+        self.workers.spawn(async move {
+            let result = match client.download_file(&remote, &path).await {
+                Ok(original) => match tokio::fs::write(&local, &original).await {
+                    Ok(()) => Ok(EditSession {
+                        remote,
+                        path: path.clone(),
+                        local,
+                        original,
+                    }),
+                    Err(e) => Err(e.into()),
+                },
+                Err(e) => Err(e),
+            };
+            AppEvent::EditReady { path, result }
+        });
+        Ok(())
+    }
+
+    /// Starts downloading a file into the scratch directory and
+    /// opening it with the default application on a background task.
+    /// The copy stays until the app exits, since there is no telling
+    /// when the application is done with it. The result arrives as
+    /// [`AppEvent::FileOpened`].
+    ///
+    /// # Errors
+    /// Returns error if the scratch directory cannot be created.
+    pub fn request_open(&mut self, remote: String, path: String) -> io::Result<()> {
+        let local = self.local_copy(&path)?;
+        debug!(remote = %remote, path = %path, local = %local.display(), "downloading to open");
+
+        let client = self.client.clone();
+        self.workers.spawn(async move {
+            let result = async {
+                let contents = client.download_file(&remote, &path).await?;
+                tokio::fs::write(&local, &contents).await?;
+                open_with_default_app(&local).await
+            }
+            .await;
+            AppEvent::FileOpened { path, result }
+        });
+        Ok(())
+    }
+
+    /// Starts uploading an edited copy on a background task. The
+    /// result arrives as [`AppEvent::Uploaded`].
+    pub fn request_upload(
+        &mut self,
+        remote: String,
+        path: String,
+        local: PathBuf,
+        contents: Vec<u8>,
+    ) {
+        if self.offline() {
+            self.queue(format!("uploading {}", path), move |app| {
+                app.request_upload(remote, path, local, contents);
+            });
+            return;
+        }
+        debug!(remote = %remote, path = %path, len = contents.len(), "uploading edit");
+        let client = self.client.clone();
+        self.workers.spawn_transfer(async move {
+            let result = client.upload_file(&remote, &path, contents).await;
+            AppEvent::Uploaded {
+                remote,
+                path,
+                local,
+                result,
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn some(command: &str) -> Option<String> {
+        Some(command.to_string())
+    }
+
+    #[test]
+    fn test_visual_wins_over_editor() {
+        let (program, args) = editor_from(some("code --wait"), some("nano"));
+        assert_eq!(program, "code");
+        assert_eq!(args, ["--wait"]);
+    }
+
+    #[test]
+    fn test_blank_variables_fall_through() {
+        assert_eq!(editor_from(some("  "), some("nano")).0, "nano");
+        assert_eq!(editor_from(None, None).0, FALLBACK_EDITOR);
+    }
+
+    #[test]
+    fn test_scratch_keeps_file_names_and_cleans_up() {
+        let mut scratch = Scratch::create().unwrap();
+        let root = scratch.root().to_path_buf();
+        let first = scratch.file_for("docs/notes.md").unwrap();
+        let second = scratch.file_for("other/notes.md").unwrap();
+
+        assert_eq!(first.file_name(), second.file_name());
+        assert_ne!(first, second);
+        assert!(first.starts_with(&root));
+
+        drop(scratch);
+        assert!(!root.exists());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_scratch_is_private() {
+        use std::os::unix::fs::PermissionsExt;
+        let scratch = Scratch::create().unwrap();
+        let mode = fs::metadata(scratch.root()).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o700);
+    }
+}
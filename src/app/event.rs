@@ -0,0 +1,406 @@
+//! Results that background tasks send back to the event loop.
+
+use super::edit::EditSession;
+use crate::daemon::Daemon;
+use crate::error::Result;
+use crate::rclone::{
+    ConfigQuestion, FileItem, FsInfo, Job, Mount, ObjectInfo, Provider, Quota, Transfer, TreeEntry,
+    Version,
+};
+use crate::ui::{DiffRow, FileOperationType, FileOperationsModal, PreviewContent};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::time::Duration;
+
+/// A message from a background task, applied by
+/// [`Handler::handle_app_event`](super::Handler::handle_app_event).
+#[derive(Debug)]
+pub enum AppEvent {
+    /// A directory listing finished.
+    FilesLoaded(Listing),
+    /// A directory under the cursor was listed ahead of time.
+    Prefetched {
+        /// Remote that was listed.
+        remote: String,
+        /// Path within the remote that was listed.
+        path: String,
+        /// Listing cache generation the request was made in.
+        generation: u64,
+        /// Entries, or why the listing failed.
+        result: Result<Vec<FileItem>>,
+    },
+    /// A subdirectory expanded in the tree view was listed.
+    TreeListed {
+        /// Remote that was listed.
+        remote: String,
+        /// Directory the files panel showed when it was expanded.
+        dir: String,
+        /// Path of the subdirectory relative to `dir`.
+        path: String,
+        /// Whether the listing came from the cache.
+        cached: bool,
+        /// Listing cache generation the request was made in.
+        generation: u64,
+        /// Entries, or why the listing failed.
+        result: Result<Vec<FileItem>>,
+    },
+    /// The start of a previewed file arrived.
+    PreviewLoaded {
+        /// Remote the file lives on.
+        remote: String,
+        /// Path of the file within the remote.
+        path: String,
+        /// Classified and highlighted content, or why it could not
+        /// be read.
+        content: PreviewContent,
+    },
+    /// A file to edit finished downloading.
+    EditReady {
+        /// Path of the file within the remote.
+        path: String,
+        /// The local copy, or why it could not be made.
+        result: Result<EditSession>,
+    },
+    /// A file was downloaded and handed to the default application.
+    FileOpened {
+        /// Path of the file within the remote.
+        path: String,
+        /// Whether the download and the opener succeeded.
+        result: Result<()>,
+    },
+    /// An edited file finished uploading.
+    Uploaded {
+        /// Remote the file was written to.
+        remote: String,
+        /// Path of the file within the remote.
+        path: String,
+        /// Local copy that was uploaded.
+        local: PathBuf,
+        /// Whether the upload succeeded.
+        result: Result<()>,
+    },
+    /// A file operation started from the file operations modal
+    /// finished.
+    FileOperationFinished {
+        /// Which operation it was.
+        operation: FileOperationType,
+        /// What it worked on, as `remote:path`: the entry deleted,
+        /// copied or moved, or the directory created.
+        target: String,
+        /// What to report once it succeeded.
+        message: String,
+        /// How long it ran.
+        elapsed: Duration,
+        /// Whether it succeeded.
+        result: Result<()>,
+    },
+    /// A copy or move found its destination taken and waits for the
+    /// user to choose what to do.
+    TransferConflict {
+        /// Remote the copy or move runs on.
+        remote: String,
+        /// The copy or move to resume.
+        operation: FileOperationsModal,
+        /// What exists at the destination.
+        existing: FileItem,
+    },
+    /// A batch rename stopped, after renaming everything or at the
+    /// first failure.
+    BatchRenamed {
+        /// Entries renamed before it stopped.
+        renamed: usize,
+        /// Entries it was asked to rename.
+        total: usize,
+        /// Directory they are in, as `remote:path`.
+        location: String,
+        /// Whether every rename succeeded.
+        result: Result<()>,
+    },
+    /// Changing the storage tier of entries stopped, after changing
+    /// every one or at the first failure.
+    TierChanged {
+        /// Entries changed before it stopped.
+        changed: usize,
+        /// Entries it was asked to change.
+        total: usize,
+        /// Directory they are in, as `remote:path`.
+        location: String,
+        /// Tier they were moved to.
+        tier: String,
+        /// Whether every change succeeded.
+        result: Result<()>,
+    },
+    /// Putting the clipboard's entries stopped, after putting every
+    /// one or at the first failure.
+    Put {
+        /// Entries copied or moved before it stopped.
+        done: usize,
+        /// Entries it was asked to put.
+        total: usize,
+        /// Directory they came from, as `remote:path`.
+        source: String,
+        /// Directory they were put in, as `remote:path`.
+        destination: String,
+        /// Entries left alone because their destination exists.
+        skipped: Vec<String>,
+        /// Whether the entries were moved rather than copied.
+        cut: bool,
+        /// How long putting them took.
+        elapsed: Duration,
+        /// Whether every entry was put or skipped.
+        result: Result<()>,
+    },
+    /// A location typed in the go-to prompt was looked up.
+    GoToResolved {
+        /// Remote of the location.
+        remote: String,
+        /// Path within the remote; empty for its root.
+        path: String,
+        /// What is there, `None` for nothing, or why the lookup
+        /// failed.
+        result: Result<Option<FileItem>>,
+    },
+    /// A user script finished.
+    ScriptFinished {
+        /// Name of the script.
+        name: String,
+        /// Lines the script printed, or why it failed.
+        result: Result<Vec<String>>,
+    },
+    /// A step of configuring a new remote finished.
+    RemoteConfigStep {
+        /// Remote being configured.
+        name: String,
+        /// The next question, `None` once the remote is complete, or
+        /// why the step failed.
+        result: Result<Option<ConfigQuestion>>,
+    },
+    /// Starting `rclone rcd` for the session finished, with the
+    /// listening daemon or why it did not start.
+    DaemonStarted(Result<Daemon>),
+    /// An attempt to unlock the daemon's encrypted config finished.
+    ConfigUnlocked(Result<()>),
+    /// A health ping finished, with the daemon's version or why it
+    /// did not answer.
+    Pinged(Result<String>),
+    /// The daemon's jobs and transfers were polled for the status bar.
+    ActivityLoaded {
+        /// The daemon's jobs, or why listing them failed.
+        jobs: Result<Vec<Job>>,
+        /// Files in flight, or why listing them failed.
+        transfers: Result<Vec<Transfer>>,
+    },
+    /// The list of remotes was fetched again.
+    RemotesLoaded(Result<Vec<String>>),
+    /// The backend type of each remote arrived, keyed by name.
+    RemoteTypesLoaded(Result<HashMap<String, String>>),
+    /// A remote's quota arrived.
+    QuotaLoaded {
+        /// Remote the quota is of.
+        remote: String,
+        /// Its space, or why it could not be had.
+        result: Result<Quota>,
+    },
+    /// The catalogue of backends arrived.
+    ProvidersLoaded(Result<Vec<Provider>>),
+    /// Saving a remote's changed settings finished.
+    RemoteUpdated {
+        /// Remote that was edited.
+        name: String,
+        /// Whether saving succeeded.
+        result: Result<()>,
+    },
+    /// Copying a remote under a new name finished.
+    RemoteCopied {
+        /// Remote that was copied.
+        from: String,
+        /// Name of the copy.
+        to: String,
+        /// Whether copying succeeded.
+        result: Result<()>,
+        /// Whether deleting the original succeeded, for a rename;
+        /// `None` when it was kept or the copy failed.
+        removed: Option<Result<()>>,
+    },
+    /// Deleting a remote finished.
+    RemoteDeleted {
+        /// Remote that was deleted.
+        name: String,
+        /// Whether it was the leftover of a cancelled config wizard,
+        /// which is deleted without a word.
+        discard: bool,
+        /// Whether deleting succeeded.
+        result: Result<()>,
+    },
+    /// A remote's settings arrived for the edit form.
+    RemoteSettingsLoaded {
+        /// Remote being edited.
+        name: String,
+        /// Its settings, `type` included, or why fetching them failed.
+        result: Result<HashMap<String, String>>,
+    },
+    /// A remote's settings and backend features arrived for the
+    /// details view.
+    RemoteInfoLoaded {
+        /// Remote shown.
+        name: String,
+        /// Its settings, `type` included, or why fetching them failed.
+        config: Result<HashMap<String, String>>,
+        /// What its backend supports, or why fetching that failed.
+        info: Result<FsInfo>,
+    },
+    /// The daemon finished downloading a URL into a file.
+    UrlCopied {
+        /// Remote the file is on.
+        remote: String,
+        /// Path of the file within the remote.
+        path: String,
+        /// URL downloaded.
+        url: String,
+        /// Whether the download succeeded.
+        result: Result<()>,
+    },
+    /// Prior versions of a file arrived for the versions view.
+    VersionsLoaded {
+        /// Remote the file is on.
+        remote: String,
+        /// Path of the file within the remote.
+        path: String,
+        /// The versions, newest first, or why listing them failed.
+        result: Result<Vec<Version>>,
+    },
+    /// A version of a file finished downloading.
+    VersionDownloaded {
+        /// Name of the version.
+        version: String,
+        /// Where it was saved, or why it was not.
+        result: Result<PathBuf>,
+    },
+    /// Restoring a version of a file finished.
+    VersionRestored {
+        /// Remote the file is on.
+        remote: String,
+        /// Path of the file within the remote.
+        path: String,
+        /// Name of the version copied over the file.
+        version: String,
+        /// Whether the copy succeeded.
+        result: Result<()>,
+    },
+    /// A file's details and metadata arrived for the metadata view.
+    MetadataLoaded {
+        /// Remote the file is on.
+        remote: String,
+        /// Path of the file within the remote.
+        path: String,
+        /// The file with its metadata, `None` if it is gone, or why
+        /// fetching it failed.
+        info: Result<Option<ObjectInfo>>,
+        /// What its backend supports, or why fetching that failed.
+        features: Result<FsInfo>,
+    },
+    /// Setting metadata keys on a file finished.
+    MetadataSaved {
+        /// Remote the file is on.
+        remote: String,
+        /// Path of the file within the remote.
+        path: String,
+        /// Keys that were set.
+        keys: Vec<String>,
+        /// Whether setting them succeeded.
+        result: Result<()>,
+    },
+    /// Folders for the upstream picker of the create form arrived.
+    UpstreamDirsLoaded {
+        /// Directory that was listed, as `remote:path/`.
+        location: String,
+        /// Names of its folders, or why the listing failed.
+        result: Result<Vec<String>>,
+    },
+    /// The daemon's active mounts arrived.
+    MountsLoaded(Result<Vec<Mount>>),
+    /// A mount request finished.
+    Mounted {
+        /// What was mounted, as `remote:path`.
+        source: String,
+        /// Local directory it was mounted on.
+        mount_point: String,
+        /// Whether mounting succeeded.
+        result: Result<()>,
+    },
+    /// A recursive listing for the usage view finished.
+    UsageLoaded {
+        /// Remote that was listed.
+        remote: String,
+        /// Directory that was listed.
+        path: String,
+        /// Everything below it, or why listing failed.
+        result: Result<Vec<TreeEntry>>,
+    },
+    /// A scan for the largest files view finished.
+    LargestLoaded {
+        /// Remote that was scanned.
+        remote: String,
+        /// Directory that was scanned.
+        path: String,
+        /// Every file below it, named by path relative to it, or why
+        /// listing failed.
+        result: Result<Vec<FileItem>>,
+    },
+    /// Listing both directories of the compare view finished.
+    Compared {
+        /// Left directory, as `remote:path`.
+        left: String,
+        /// Right directory, as `remote:path`.
+        right: String,
+        /// Hash type compared, if both backends share one.
+        hash: Option<String>,
+        /// How their files compare, or why listing failed.
+        result: Result<Vec<DiffRow>>,
+    },
+    /// The daemon's jobs arrived.
+    JobsLoaded(Result<Vec<Job>>),
+    /// The jobs listed before quitting arrived.
+    QuitChecked {
+        /// Whether the quit dialog asked, waiting for the jobs to
+        /// finish, rather than the quit key.
+        waiting: bool,
+        /// The daemon's jobs, or why listing them failed.
+        result: Result<Vec<Job>>,
+    },
+    /// The files the daemon is transferring arrived.
+    TransfersLoaded(Result<Vec<Transfer>>),
+    /// A request to stop a job finished.
+    JobStopped {
+        /// Job that was stopped.
+        id: i64,
+        /// Whether stopping it succeeded.
+        result: Result<()>,
+    },
+    /// An unmount request finished.
+    Unmounted {
+        /// Directory that was unmounted.
+        mount_point: String,
+        /// Whether unmounting succeeded.
+        result: Result<()>,
+    },
+}
+
+/// Outcome of a directory listing.
+#[derive(Debug)]
+pub struct Listing {
+    /// Request this answers; stale answers are dropped.
+    pub id: u64,
+    /// Remote that was listed.
+    pub remote: String,
+    /// Path within the remote that was listed.
+    pub path: String,
+    /// Whether the entries came from the listing cache rather than
+    /// the daemon.
+    pub cached: bool,
+    /// Whether every file below the path was listed, for the
+    /// recursive listing; such listings are not cached.
+    pub recursive: bool,
+    /// Entries, or why the listing failed.
+    pub result: Result<Vec<FileItem>>,
+}
@@ -0,0 +1,198 @@
+//! Recursive listing of the files panel: every file below the current
+//! directory in one flat list, as `rclone ls` prints them, for finding
+//! files when it is unknown where they are.
+//!
+//! While it is on, every listing of the panel is recursive. Files are
+//! named by their path relative to the current directory, like nested
+//! rows of the tree view, so operations reach them as usual. Rows can
+//! be sorted by path, size or time, and narrowed by a glob; the size
+//! and age filter applies as to any listing.
+
+use super::state::App;
+use crate::rclone::FileItem;
+use crate::ui::widgets::mark_glob::path_match;
+use std::cmp::Reverse;
+use tracing::debug;
+
+/// Order of the rows of the recursive listing.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum FlatSort {
+    /// By path.
+    #[default]
+    Path,
+    /// Largest first.
+    Size,
+    /// Most recently modified first.
+    Modified,
+}
+
+impl FlatSort {
+    /// Returns the order after this one.
+    fn next(self) -> Self {
+        match self {
+            Self::Path => Self::Size,
+            Self::Size => Self::Modified,
+            Self::Modified => Self::Path,
+        }
+    }
+
+    /// Returns the name of the order.
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Path => "path",
+            Self::Size => "size",
+            Self::Modified => "modified",
+        }
+    }
+}
+
+/// Every file below the current directory, and how they are shown.
+#[derive(Debug, Clone, Default)]
+pub struct FlatListing {
+    /// The files in listing order, named by relative path.
+    all: Vec<FileItem>,
+    /// Order of the rows.
+    pub sort: FlatSort,
+    /// Glob the rows match, if narrowed.
+    pub pattern: Option<String>,
+}
+
+impl FlatListing {
+    /// Returns the rows: the files the pattern matches, in order.
+    fn rows(&self) -> Vec<FileItem> {
+        let mut rows: Vec<FileItem> = self
+            .all
+            .iter()
+            .filter(|item| {
+                self.pattern
+                    .as_deref()
+                    .is_none_or(|pattern| path_match(pattern, item.name()))
+            })
+            .cloned()
+            .collect();
+        match self.sort {
+            FlatSort::Path => rows.sort_by(|a, b| a.name().cmp(b.name())),
+            FlatSort::Size => rows.sort_by_key(|item| Reverse(item.size)),
+            // RFC 3339 times of one daemon share an offset, so they
+            // sort as text.
+            FlatSort::Modified => rows.sort_by(|a, b| b.mod_time.cmp(&a.mod_time)),
+        }
+        rows
+    }
+
+    /// Returns every file listed, whether the pattern matches it or
+    /// not.
+    pub fn all(&self) -> &[FileItem] {
+        &self.all
+    }
+
+    /// Describes the order and pattern for the panel's title, e.g.
+    /// `all files by size, *.jpg`.
+    pub fn describe(&self) -> String {
+        match self.pattern {
+            Some(ref pattern) => format!("all files by {}, {}", self.sort.label(), pattern),
+            None => format!("all files by {}", self.sort.label()),
+        }
+    }
+}
+
+impl App {
+    /// Switches the files panel between the current directory and
+    /// every file below it, and lists it again. The tree view and the
+    /// recursive listing exclude each other.
+    pub fn toggle_flat(&mut self) {
+        self.flat = match self.flat {
+            Some(_) => None,
+            None => Some(FlatListing::default()),
+        };
+        debug!(flat = self.flat.is_some(), "toggled recursive listing");
+        if self.flat.is_some() && self.tree.enabled {
+            self.toggle_tree();
+        }
+        self.reload_files();
+    }
+
+    /// Shows the recursive listing in the next order.
+    pub fn cycle_flat_sort(&mut self) {
+        let Some(ref mut flat) = self.flat else {
+            return;
+        };
+        flat.sort = flat.sort.next();
+        debug!(sort = flat.sort.label(), "sorting recursive listing");
+        self.show_flat();
+    }
+
+    /// Narrows the recursive listing to the files `pattern` matches,
+    /// or shows every file again for an empty pattern.
+    pub fn narrow_flat(&mut self, pattern: &str) {
+        let Some(ref mut flat) = self.flat else {
+            return;
+        };
+        flat.pattern = (!pattern.is_empty()).then(|| pattern.to_string());
+        self.show_flat();
+    }
+
+    /// Keeps a recursive listing that just arrived and shows its rows.
+    pub(super) fn apply_flat(&mut self, files: Vec<FileItem>) {
+        let Some(ref mut flat) = self.flat else {
+            self.files = files;
+            return;
+        };
+        flat.all = files;
+        self.files = flat.rows();
+    }
+
+    /// Lays out the rows again, from the top.
+    fn show_flat(&mut self) {
+        let Some(ref flat) = self.flat else {
+            return;
+        };
+        self.files = flat.rows();
+        self.retain_marks(true);
+        self.files_selected = 0;
+        self.files_offset = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn file(path: &str, size: i64, mod_time: &str) -> FileItem {
+        FileItem {
+            name: path.to_string(),
+            size,
+            mod_time: mod_time.to_string(),
+            ..FileItem::default()
+        }
+    }
+
+    #[test]
+    fn test_rows_follow_sort_and_pattern() {
+        let mut flat = FlatListing {
+            all: vec![
+                file("b/old.jpg", 30, "2024-01-01T00:00:00Z"),
+                file("a.txt", 10, "2024-03-01T00:00:00Z"),
+                file("b/c/new.jpg", 20, "2024-02-01T00:00:00Z"),
+            ],
+            ..FlatListing::default()
+        };
+        let names = |flat: &FlatListing| {
+            flat.rows()
+                .into_iter()
+                .map(|item| item.name)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(names(&flat), ["a.txt", "b/c/new.jpg", "b/old.jpg"]);
+
+        flat.sort = FlatSort::Size;
+        assert_eq!(names(&flat), ["b/old.jpg", "b/c/new.jpg", "a.txt"]);
+        flat.sort = FlatSort::Modified;
+        flat.pattern = Some("*.jpg".to_string());
+        assert_eq!(names(&flat), ["b/c/new.jpg", "b/old.jpg"]);
+
+        flat.pattern = Some("b/c/*".to_string());
+        assert_eq!(names(&flat), ["b/c/new.jpg"]);
+        assert!(!path_match("c/*", "b/c/new.jpg"));
+    }
+}
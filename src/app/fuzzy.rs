@@ -0,0 +1,87 @@
+//! Subsequence fuzzy matching for pickers.
+
+/// Bonus for a match immediately after the previous one.
+const CONSECUTIVE_BONUS: i64 = 8;
+/// Bonus for a match at the start of a word.
+const WORD_START_BONUS: i64 = 6;
+/// Penalty per skipped candidate character.
+const GAP_PENALTY: i64 = 1;
+
+/// Scores `candidate` against `query`, case-insensitively.
+///
+/// Every query character must appear in the candidate in order.
+/// Returns `None` when it does not; otherwise higher is better.
+/// An empty query matches everything with score zero.
+pub fn score(query: &str, candidate: &str) -> Option<i64> {
+    let mut total = 0;
+    let mut matched_any = false;
+    let mut chars = candidate.chars();
+    let mut before: Option<char> = None;
+
+    for wanted in query.chars().flat_map(char::to_lowercase) {
+        if wanted.is_whitespace() {
+            continue;
+        }
+        let mut skipped = 0;
+        loop {
+            let c = chars.next()?;
+            let at_word_start = before.is_none_or(|b| !b.is_alphanumeric());
+            before = Some(c);
+            if c.to_lowercase().eq(std::iter::once(wanted)) {
+                total += 1;
+                if matched_any && skipped == 0 {
+                    total += CONSECUTIVE_BONUS;
+                }
+                if at_word_start {
+                    total += WORD_START_BONUS;
+                }
+                total -= skipped * GAP_PENALTY;
+                matched_any = true;
+                break;
+            }
+            skipped += 1;
+        }
+    }
+    Some(total)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_empty_query_matches_everything() {
+        assert_eq!(score("", "anything"), Some(0));
+    }
+
+    #[test]
+    fn test_requires_all_chars_in_order() {
+        assert!(score("adr", "Add remote").is_some());
+        assert!(score("rda", "Add remote").is_none());
+        assert!(score("xyz", "Add remote").is_none());
+    }
+
+    #[test]
+    fn test_is_case_insensitive() {
+        assert_eq!(score("ADD", "add"), score("add", "add"));
+    }
+
+    #[test]
+    fn test_prefers_consecutive_matches() {
+        let tight = score("mov", "Move file").unwrap();
+        let loose = score("mov", "Make new overlay").unwrap();
+        assert!(tight > loose);
+    }
+
+    #[test]
+    fn test_prefers_word_starts() {
+        let initials = score("nd", "New directory").unwrap();
+        let inner = score("nd", "Opened").unwrap();
+        assert!(initials > inner);
+    }
+
+    #[test]
+    fn test_ignores_spaces_in_query() {
+        assert!(score("del rem", "Delete remote").is_some());
+    }
+}
@@ -0,0 +1,95 @@
+//! Audit log view handling: reading the audit file and browsing it.
+
+use super::Handler;
+use crate::app::state::{ActiveModal, App};
+use crate::config::AuditRecord;
+use crate::error::Result;
+use crate::ui::AuditView;
+use crossterm::event::{KeyCode, KeyEvent};
+use tracing::{debug, warn};
+
+impl Handler {
+    /// Opens the audit log view.
+    pub(super) fn handle_show_audit(app: &mut App) {
+        if app.audit.is_none() {
+            app.notifications
+                .warning("No audit log: there is nowhere to keep one on this system");
+            return;
+        }
+        debug!("opening audit log");
+        let records = Self::load_audit(app);
+        app.modal = Some(ActiveModal::Audit(Box::new(AuditView::new(records))));
+    }
+
+    /// Handles keys while the audit log view is open.
+    pub(super) fn handle_audit_key(app: &mut App, key: KeyEvent) {
+        let Some(ActiveModal::Audit(ref mut view)) = app.modal else {
+            return;
+        };
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => {
+                debug!("closing audit log");
+                app.modal = None;
+            }
+            KeyCode::Char('j') | KeyCode::Down => view.select_next(),
+            KeyCode::Char('k') | KeyCode::Up => view.select_prev(),
+            KeyCode::Char('f') => view.toggle_failed_only(),
+            KeyCode::Char('r') => {
+                let records = Self::load_audit(app);
+                if let Some(ActiveModal::Audit(ref mut view)) = app.modal {
+                    view.set_records(records);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Reads the audit file, newest record first.
+    fn load_audit(app: &App) -> Result<Vec<AuditRecord>> {
+        let records = app.audit.as_ref().map_or(Ok(Vec::new()), |a| a.load());
+        if let Err(ref e) = records {
+            warn!(error = %e, "reading audit log failed");
+        }
+        records
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::handler::test_support::{key, unreachable_app};
+    use crate::config::AuditLog;
+
+    #[tokio::test]
+    async fn test_ga_shows_recorded_changes() {
+        let mut app = unreachable_app();
+        let dir = std::env::temp_dir().join(format!("lazyfile-ga-{}", std::process::id()));
+        let audit = AuditLog::new(dir.join("audit.jsonl"));
+        audit
+            .append(&AuditRecord {
+                at: 1,
+                user: "ana".to_string(),
+                action: "delete remote".to_string(),
+                target: "old-s3".to_string(),
+                detail: None,
+                error: None,
+            })
+            .unwrap();
+        app.audit = Some(audit);
+        for c in ['g', 'a'] {
+            Handler::handle_key(&mut app, key(KeyCode::Char(c)))
+                .await
+                .unwrap();
+        }
+        let Some(ActiveModal::Audit(ref view)) = app.modal else {
+            panic!("audit log not open");
+        };
+        assert_eq!(view.selected_record().unwrap().target, "old-s3");
+
+        Handler::handle_key(&mut app, key(KeyCode::Esc))
+            .await
+            .unwrap();
+        assert!(app.modal.is_none());
+        let _ = std::fs::remove_dir_all(dir);
+    }
+}
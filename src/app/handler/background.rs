@@ -0,0 +1,307 @@
+//! Results arriving from background tasks.
+
+use super::Handler;
+use crate::app::event::AppEvent;
+use crate::app::state::{ActiveModal, App, Retry};
+use crate::ui::ErrorModal;
+use std::time::{Duration, Instant};
+use tracing::warn;
+
+/// How often [`Handler::settle`] checks for requests that finished.
+const SETTLE_POLL: Duration = Duration::from_millis(10);
+
+impl Handler {
+    /// Applies a message from a background task, recording it in the
+    /// audit log first if it reports a change.
+    ///
+    /// A failed listing opens the error modal with a retry, unless
+    /// another modal is open; then it becomes an error toast so the
+    /// user's form is not thrown away.
+    pub fn handle_app_event(app: &mut App, event: AppEvent) {
+        app.audit(&event);
+        match event {
+            AppEvent::FilesLoaded(listing) => {
+                let (remote, path) = (listing.remote.clone(), listing.path.clone());
+                let Err(err) = app.apply_listing(listing) else {
+                    return;
+                };
+                warn!(remote = %remote, path = %path, error = %err, "listing failed");
+                if app.modal.is_some() {
+                    app.notifications.error(err.to_string());
+                } else {
+                    app.modal = Some(ActiveModal::Error {
+                        modal: ErrorModal::new(&err),
+                        retry: Box::new(Retry::ListFiles { remote, path }),
+                    });
+                }
+            }
+            AppEvent::Prefetched {
+                remote,
+                path,
+                generation,
+                result,
+            } => app.apply_prefetch(remote, path, generation, result),
+            AppEvent::TreeListed {
+                remote,
+                dir,
+                path,
+                cached,
+                generation,
+                result,
+            } => app.apply_tree_listing(remote, dir, path, cached, generation, result),
+            AppEvent::PreviewLoaded {
+                remote,
+                path,
+                content,
+            } => {
+                // The user may have closed the preview or opened another.
+                if let Some(ActiveModal::Preview(ref mut preview)) = app.modal
+                    && preview.remote == remote
+                    && preview.path == path
+                {
+                    preview.content = content;
+                }
+            }
+            AppEvent::EditReady { path, result } => Self::apply_edit_ready(app, &path, result),
+            AppEvent::FileOpened { path, result } => Self::apply_file_opened(app, &path, result),
+            AppEvent::Uploaded {
+                remote,
+                path,
+                local,
+                result,
+            } => Self::apply_uploaded(app, remote, path, local, result),
+            AppEvent::FileOperationFinished {
+                target,
+                message,
+                elapsed,
+                result,
+                ..
+            } => {
+                Self::apply_largest_operation(app, &target, result.is_ok());
+                Self::apply_file_operation(app, message, elapsed, result);
+            }
+            AppEvent::Put {
+                done,
+                total,
+                skipped,
+                cut,
+                elapsed,
+                result,
+                ..
+            } => Self::apply_put(app, (done, total), &skipped, cut, elapsed, result),
+            AppEvent::BatchRenamed {
+                renamed,
+                total,
+                result,
+                ..
+            } => Self::apply_batch_renamed(app, renamed, total, result),
+            AppEvent::UrlCopied {
+                remote,
+                path,
+                result,
+                ..
+            } => Self::apply_url_copied(app, &remote, &path, result),
+            AppEvent::VersionsLoaded {
+                remote,
+                path,
+                result,
+            } => Self::apply_versions_loaded(app, &remote, &path, result),
+            AppEvent::VersionDownloaded { version, result } => {
+                Self::apply_version_downloaded(app, &version, result)
+            }
+            AppEvent::VersionRestored {
+                remote,
+                path,
+                version,
+                result,
+            } => Self::apply_version_restored(app, remote, path, &version, result),
+            AppEvent::MetadataLoaded {
+                remote,
+                path,
+                info,
+                features,
+            } => Self::apply_metadata_loaded(app, &remote, &path, info, features),
+            AppEvent::MetadataSaved {
+                remote,
+                path,
+                result,
+                ..
+            } => Self::apply_metadata_saved(app, remote, path, result),
+            AppEvent::TierChanged {
+                changed,
+                total,
+                tier,
+                result,
+                ..
+            } => Self::apply_tier_changed(app, changed, total, &tier, result),
+            AppEvent::GoToResolved {
+                remote,
+                path,
+                result,
+            } => Self::apply_go_to(app, remote, path, result),
+            AppEvent::TransferConflict {
+                remote,
+                operation,
+                existing,
+            } => Self::apply_transfer_conflict(app, remote, operation, existing),
+            AppEvent::RemoteUpdated { name, result } => {
+                Self::apply_remote_updated(app, &name, result)
+            }
+            AppEvent::RemoteCopied {
+                from,
+                to,
+                result,
+                removed,
+            } => Self::apply_remote_copied(app, &from, &to, result, removed),
+            AppEvent::RemoteDeleted {
+                name,
+                discard,
+                result,
+            } => Self::apply_remote_deleted(app, &name, discard, result),
+            AppEvent::ScriptFinished { name, result } => {
+                Self::apply_script_finished(app, &name, result)
+            }
+            AppEvent::RemoteConfigStep { name, result } => {
+                Self::apply_config_step(app, &name, result)
+            }
+            AppEvent::ProvidersLoaded(result) => Self::apply_providers(app, result),
+            AppEvent::RemoteTypesLoaded(result) => app.apply_remote_types(result),
+            AppEvent::QuotaLoaded { remote, result } => app.apply_quota(remote, result),
+            AppEvent::MountsLoaded(result) => Self::apply_mounts(app, result),
+            AppEvent::UsageLoaded {
+                remote,
+                path,
+                result,
+            } => Self::apply_usage(app, &remote, &path, result),
+            AppEvent::LargestLoaded {
+                remote,
+                path,
+                result,
+            } => Self::apply_largest(app, &remote, &path, result),
+            AppEvent::Compared {
+                left,
+                right,
+                hash,
+                result,
+            } => Self::apply_compared(app, &left, &right, hash, result),
+            AppEvent::JobsLoaded(result) => Self::apply_jobs(app, result),
+            AppEvent::QuitChecked { waiting, result } => {
+                Self::apply_quit_check(app, waiting, result);
+            }
+            AppEvent::TransfersLoaded(result) => Self::apply_transfers(app, result),
+            AppEvent::JobStopped { id, result } => Self::apply_job_stopped(app, id, result),
+            AppEvent::Mounted {
+                source,
+                mount_point,
+                result,
+            } => Self::apply_mounted(app, &source, &mount_point, result),
+            AppEvent::Unmounted {
+                mount_point,
+                result,
+            } => Self::apply_unmounted(app, &mount_point, result),
+            AppEvent::RemoteSettingsLoaded { name, result } => {
+                Self::apply_remote_settings(app, name, result)
+            }
+            AppEvent::RemoteInfoLoaded { name, config, info } => {
+                Self::apply_remote_info(app, name, config, info)
+            }
+            AppEvent::UpstreamDirsLoaded { location, result } => {
+                Self::apply_upstream_dirs(app, &location, result)
+            }
+            AppEvent::RemotesLoaded(result) => match app.apply_remotes(result) {
+                Err(err) if err.is_config_locked() => Self::prompt_config_password(app),
+                Err(err) => {
+                    warn!(error = %err, "loading remotes failed");
+                    app.notifications.error(err.to_string());
+                }
+                // A daemon answered, so there is nothing left to set up.
+                Ok(()) => {
+                    if matches!(app.modal, Some(ActiveModal::DaemonSetup(_))) {
+                        app.modal = None;
+                    }
+                }
+            },
+            AppEvent::ConfigUnlocked(result) => Self::apply_config_unlocked(app, result),
+            AppEvent::DaemonStarted(result) => Self::apply_daemon_started(app, result),
+            AppEvent::Pinged(result) => app.apply_ping(result, Instant::now()),
+            AppEvent::ActivityLoaded { jobs, transfers } => {
+                app.apply_activity(jobs, transfers, Instant::now());
+            }
+        }
+    }
+
+    /// Waits until no request is queued or running, applying results
+    /// as the event loop would. Lets callers without an event loop,
+    /// such as tests, observe the outcome of a key press.
+    pub async fn settle(app: &mut App) {
+        loop {
+            while let Some(event) = app.next_event() {
+                Self::handle_app_event(app, event);
+            }
+            if app.loading.is_none() && app.workers.busy() == 0 {
+                // A request can finish between the drain and the
+                // check, so one last look catches its result.
+                match app.next_event() {
+                    Some(event) => Self::handle_app_event(app, event),
+                    None => break,
+                }
+                continue;
+            }
+            // Requests may finish without anything left to receive,
+            // so wake up now and then to check.
+            if let Ok(Some(event)) = tokio::time::timeout(SETTLE_POLL, app.wait_event()).await {
+                Self::handle_app_event(app, event);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::event::Listing;
+    use crate::app::handler::test_support::unreachable_app;
+    use crate::error::LazyFileError;
+
+    fn failed_listing(app: &mut App) -> AppEvent {
+        app.request_files("gdrive".to_string(), "docs".to_string());
+        AppEvent::FilesLoaded(Listing {
+            id: app.loading.as_ref().map_or(0, |l| l.id),
+            remote: "gdrive".to_string(),
+            path: "docs".to_string(),
+            cached: false,
+            recursive: false,
+            result: Err(LazyFileError::InvalidInput {
+                field: "path",
+                reason: "test",
+            }),
+        })
+    }
+
+    #[tokio::test]
+    async fn test_failed_listing_opens_error_modal() {
+        let mut app = unreachable_app();
+        let event = failed_listing(&mut app);
+
+        Handler::handle_app_event(&mut app, event);
+
+        match app.modal {
+            Some(ActiveModal::Error { ref retry, .. }) => {
+                assert!(matches!(**retry, Retry::ListFiles { ref path, .. } if path == "docs"));
+            }
+            ref other => panic!("expected error modal, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_failed_listing_behind_modal_is_a_toast() {
+        let mut app = unreachable_app();
+        app.modal = Some(ActiveModal::Help { scroll: 0 });
+        let event = failed_listing(&mut app);
+
+        Handler::handle_app_event(&mut app, event);
+
+        assert!(matches!(app.modal, Some(ActiveModal::Help { .. })));
+        assert!(!app.notifications.is_empty());
+    }
+}
@@ -0,0 +1,198 @@
+//! Batch rename of the marked entries, or the selected one.
+
+use super::Handler;
+use crate::app::state::{ActiveModal, App};
+use crate::error::Result;
+use crate::ui::BatchRename;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use tracing::{debug, info, warn};
+
+impl Handler {
+    /// Opens the batch rename form for the selection.
+    pub(super) fn handle_batch_rename(app: &mut App) {
+        let Some(remote) = app.current_remote.clone() else {
+            return;
+        };
+        let selection = app.selection();
+        let names: Vec<String> = selection
+            .iter()
+            .map(|item| item.name().to_string())
+            .collect();
+        if names.is_empty() {
+            return;
+        }
+        let dirs: Vec<String> = selection
+            .iter()
+            .filter(|item| item.is_dir())
+            .map(|item| item.name().to_string())
+            .collect();
+        let others = app
+            .files
+            .iter()
+            .map(|item| item.name().to_string())
+            .filter(|name| !names.contains(name))
+            .collect();
+        debug!(count = names.len(), "opening batch rename");
+        app.modal = Some(ActiveModal::BatchRename(Box::new(
+            BatchRename::new(remote, app.current_path.clone(), names, others).with_dirs(dirs),
+        )));
+    }
+
+    /// Handles keyboard input in the batch rename form. Only `Esc`
+    /// works while the renames are in flight; it closes the form and
+    /// the outcome arrives as a notification.
+    pub(super) fn handle_batch_rename_key(app: &mut App, key: KeyEvent) {
+        let Some(ActiveModal::BatchRename(ref mut view)) = app.modal else {
+            return;
+        };
+        match key.code {
+            KeyCode::Esc => {
+                debug!("closing batch rename");
+                app.modal = None;
+            }
+            _ if view.waiting => {}
+            KeyCode::Char('r') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                view.toggle_regex();
+            }
+            KeyCode::Tab | KeyCode::BackTab => view.toggle_focus(),
+            KeyCode::Up => view.scroll_by(-1),
+            KeyCode::Down => view.scroll_by(1),
+            KeyCode::Char(c) if !key.modifiers.contains(KeyModifiers::CONTROL) => {
+                view.input_char(c);
+            }
+            KeyCode::Backspace => view.backspace(),
+            KeyCode::Enter => match view.renames() {
+                Ok(renames) => {
+                    view.waiting = true;
+                    view.error = None;
+                    let (remote, dir) = (view.remote.clone(), view.dir.clone());
+                    app.request_batch_rename(remote, dir, renames);
+                }
+                Err(message) => view.error = Some(message),
+            },
+            _ => {}
+        }
+    }
+
+    /// Closes the form, clears the marks and lists the directory
+    /// again, whether or not every rename went through; a failure
+    /// says how many did.
+    pub(super) fn apply_batch_renamed(
+        app: &mut App,
+        renamed: usize,
+        total: usize,
+        result: Result<()>,
+    ) {
+        if matches!(app.modal, Some(ActiveModal::BatchRename(_))) {
+            app.modal = None;
+        }
+        match result {
+            Ok(()) => {
+                info!(renamed, "batch rename finished");
+                app.notifications
+                    .success(format!("Renamed {} entries", renamed));
+            }
+            Err(e) => {
+                warn!(error = %e, renamed, total, "batch rename failed");
+                app.notifications
+                    .error(format!("Renamed {} of {}, then: {}", renamed, total, e));
+            }
+        }
+        app.marked.clear();
+        app.reload_files();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::AppEvent;
+    use crate::app::handler::test_support::{files_app, key};
+    use crate::rclone::{FileItem, commands};
+
+    async fn type_text(app: &mut App, text: &str) {
+        for c in text.chars() {
+            Handler::handle_key(app, key(KeyCode::Char(c)))
+                .await
+                .unwrap();
+        }
+    }
+
+    #[tokio::test]
+    async fn test_opens_for_marked_entries() {
+        let mut app = files_app("", &["a.txt", "b.txt", "c.md"]);
+        app.marked.extend(["a.txt".to_string(), "c.md".to_string()]);
+
+        Handler::handle_key(&mut app, key(KeyCode::Char('R')))
+            .await
+            .unwrap();
+
+        let Some(ActiveModal::BatchRename(ref view)) = app.modal else {
+            panic!("batch rename not open");
+        };
+        assert_eq!(view.names, ["a.txt", "c.md"]);
+        assert_eq!(view.others, ["b.txt"]);
+    }
+
+    #[tokio::test]
+    async fn test_directories_are_renamed_with_a_directory_move() {
+        let mut app = files_app("", &["a.txt", "b.txt", "c.md"]);
+        app.files.push(FileItem {
+            name: "a-photos".to_string(),
+            is_dir: true,
+            ..Default::default()
+        });
+        app.marked.insert("a-photos".to_string());
+        Handler::handle_key(&mut app, key(KeyCode::Char('R')))
+            .await
+            .unwrap();
+        type_text(&mut app, "a-").await;
+        Handler::handle_key(&mut app, key(KeyCode::Tab))
+            .await
+            .unwrap();
+        type_text(&mut app, "b-").await;
+        Handler::handle_key(&mut app, key(KeyCode::Enter))
+            .await
+            .unwrap();
+
+        let event = app.wait_event().await.unwrap();
+        assert!(matches!(event, AppEvent::BatchRenamed { .. }));
+        let endpoints: Vec<_> = app
+            .client
+            .calls()
+            .iter()
+            .map(|call| call.endpoint)
+            .collect();
+        assert_eq!(endpoints, [commands::MOVE_DIR]);
+    }
+
+    #[tokio::test]
+    async fn test_rename_reports_failure_and_clears_marks() {
+        let mut app = files_app("", &["a.txt", "b.txt", "c.md"]);
+        app.marked.insert("a.txt".to_string());
+        Handler::handle_key(&mut app, key(KeyCode::Char('R')))
+            .await
+            .unwrap();
+        type_text(&mut app, "a.").await;
+        Handler::handle_key(&mut app, key(KeyCode::Tab))
+            .await
+            .unwrap();
+        type_text(&mut app, "z.").await;
+        Handler::handle_key(&mut app, key(KeyCode::Enter))
+            .await
+            .unwrap();
+
+        let event = app.wait_event().await.unwrap();
+        Handler::handle_app_event(&mut app, event);
+        assert!(app.modal.is_none());
+        assert!(app.marked.is_empty());
+        assert!(
+            app.notifications
+                .toasts()
+                .last()
+                .unwrap()
+                .message
+                .starts_with("Renamed 0 of 1")
+        );
+    }
+}
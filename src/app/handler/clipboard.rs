@@ -0,0 +1,96 @@
+//! Copying paths of remote files, and other text, to the clipboard.
+
+use super::Handler;
+use crate::app::command::{rclone_path, shell_quote};
+use crate::app::state::App;
+use crate::clipboard::CopiedVia;
+use crate::rclone::Filter;
+use tracing::{info, warn};
+
+impl Handler {
+    /// Copies the selected entry as `remote:path`, or as an
+    /// `rclone copy` command line when `as_command` is set.
+    pub(super) fn handle_copy_path(app: &mut App, as_command: bool) {
+        let (Some(remote), Some(item), Some(path)) = (
+            app.current_remote.as_deref(),
+            app.files.get(app.files_selected),
+            app.selected_path(),
+        ) else {
+            return;
+        };
+        let source = rclone_path(remote, &path);
+        let text = if as_command {
+            copy_command(&source, item.is_dir().then(|| item.name()), &app.filter)
+        } else {
+            source
+        };
+        Self::copy_text(app, &text);
+    }
+
+    /// Copies `text` and reports where it went in a toast.
+    pub(super) fn copy_text(app: &mut App, text: &str) {
+        match app.clipboard.copy(text) {
+            Ok(via) => {
+                info!(via = ?via, "copied to clipboard");
+                let suffix = match via {
+                    CopiedVia::System => "",
+                    CopiedVia::Terminal => " (via terminal)",
+                };
+                app.notifications
+                    .success(format!("Copied {}{}", text, suffix));
+            }
+            Err(e) => {
+                warn!(error = %e, "clipboard copy failed");
+                app.notifications.error(format!("Could not copy: {}", e));
+            }
+        }
+    }
+}
+
+/// Builds an `rclone copy` command that downloads `source` into the
+/// current directory. A directory's content goes into a directory of
+/// the same name, since `rclone copy` copies contents, not the
+/// directory itself. The size and age limits of `filter` follow as
+/// flags.
+fn copy_command(source: &str, dir_name: Option<&str>, filter: &Filter) -> String {
+    let destination = match dir_name {
+        Some(name) => shell_quote(&format!("./{}", name)),
+        None => ".".to_string(),
+    };
+    let mut command = format!("rclone copy {} {}", shell_quote(source), destination);
+    for word in filter.flags() {
+        command.push(' ');
+        command.push_str(&shell_quote(&word));
+    }
+    command
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_copy_command_for_file() {
+        assert_eq!(
+            copy_command("gdrive:docs/a.txt", None, &Filter::default()),
+            "rclone copy gdrive:docs/a.txt ."
+        );
+    }
+
+    #[test]
+    fn test_copy_command_keeps_directory_name() {
+        assert_eq!(
+            copy_command("gdrive:photos", Some("photos"), &Filter::default()),
+            "rclone copy gdrive:photos ./photos"
+        );
+    }
+
+    #[test]
+    fn test_copy_command_adds_filter_flags() {
+        let filter = Filter::new("10M", "", "2024-01-31 12:00:00", "").unwrap();
+        assert_eq!(
+            copy_command("gdrive:photos", Some("photos"), &filter),
+            "rclone copy gdrive:photos ./photos --min-size 10M --max-age '2024-01-31 12:00:00'"
+        );
+    }
+}
@@ -0,0 +1,169 @@
+//! Compare view handling: choosing two directories and moving
+//! through how their files compare.
+
+use super::Handler;
+use crate::app::state::{ActiveModal, App};
+use crate::error::Result;
+use crate::ui::widgets::compare::split_location;
+use crate::ui::{CompareForm, CompareView, DiffRow};
+use crossterm::event::{KeyCode, KeyEvent};
+use tracing::{debug, info, warn};
+
+impl Handler {
+    /// Opens the compare view with the current directory on the left,
+    /// asking for the right one.
+    pub(super) fn handle_compare(app: &mut App) {
+        let Some(ref remote) = app.current_remote else {
+            return;
+        };
+        let left = format!("{}:{}", remote, app.current_path);
+        debug!(left = %left, "opening compare");
+        app.modal = Some(ActiveModal::Compare(Box::new(CompareView::new(left))));
+    }
+
+    /// Handles keys while the compare view is open: its form while
+    /// choosing directories, the result otherwise.
+    pub(super) fn handle_compare_key(app: &mut App, key: KeyEvent) {
+        let Some(ActiveModal::Compare(ref mut view)) = app.modal else {
+            return;
+        };
+        if let Some(ref mut form) = view.form {
+            match key.code {
+                KeyCode::Esc => app.modal = None,
+                KeyCode::Tab | KeyCode::BackTab | KeyCode::Up | KeyCode::Down => {
+                    form.toggle_focus();
+                }
+                KeyCode::Char(c) => form.input_char(c),
+                KeyCode::Backspace => form.backspace(),
+                KeyCode::Enter => {
+                    let valid = split_location(&form.left).and(split_location(&form.right));
+                    match valid {
+                        Ok(_) => {
+                            let (left, right) = (form.left.trim(), form.right.trim());
+                            let (left, right) = (left.to_string(), right.to_string());
+                            info!(left = %left, right = %right, "comparing");
+                            view.start(left.clone(), right.clone());
+                            app.request_compare(left, right);
+                        }
+                        Err(reason) => form.error = Some(reason.to_string()),
+                    }
+                }
+                _ => {}
+            }
+            return;
+        }
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q' | 'C') => {
+                debug!("closing compare");
+                app.modal = None;
+            }
+            KeyCode::Char('j') | KeyCode::Down => view.select_next(),
+            KeyCode::Char('k') | KeyCode::Up => view.select_prev(),
+            KeyCode::Char('a') => view.toggle_same(),
+            KeyCode::Char('n') => {
+                view.form = Some(CompareForm::new(view.left.clone(), view.right.clone()));
+            }
+            KeyCode::Char('r') if !view.loading => {
+                let (left, right) = (view.left.clone(), view.right.clone());
+                view.start(left.clone(), right.clone());
+                app.request_compare(left, right);
+            }
+            _ => {}
+        }
+    }
+
+    /// Shows a finished comparison if the compare view still waits for
+    /// those two directories.
+    pub(super) fn apply_compared(
+        app: &mut App,
+        left: &str,
+        right: &str,
+        hash: Option<String>,
+        result: Result<Vec<DiffRow>>,
+    ) {
+        if let Err(ref e) = result {
+            warn!(left, right, error = %e, "comparing directories failed");
+        }
+        if let Some(ActiveModal::Compare(ref mut view)) = app.modal
+            && view.loading
+            && view.left == left
+            && view.right == right
+        {
+            view.set_rows(hash, result);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::event::AppEvent;
+    use crate::app::handler::test_support::{key, unreachable_app};
+    use crate::app::state::Panel;
+
+    async fn type_text(app: &mut App, text: &str) {
+        for c in text.chars() {
+            Handler::handle_key(app, key(KeyCode::Char(c)))
+                .await
+                .unwrap();
+        }
+    }
+
+    #[tokio::test]
+    async fn test_compare_asks_for_right_side() {
+        let mut app = unreachable_app();
+        app.focused_panel = Panel::Files;
+        app.current_remote = Some("gdrive".to_string());
+        app.current_path = "docs".to_string();
+        Handler::handle_key(&mut app, key(KeyCode::Char('C')))
+            .await
+            .unwrap();
+
+        Handler::handle_key(&mut app, key(KeyCode::Enter))
+            .await
+            .unwrap();
+        let Some(ActiveModal::Compare(ref view)) = app.modal else {
+            panic!("compare closed");
+        };
+        let form = view.form.as_ref().unwrap();
+        assert_eq!(form.left, "gdrive:docs");
+        assert!(form.error.is_some());
+
+        type_text(&mut app, "s3:backup").await;
+        Handler::handle_key(&mut app, key(KeyCode::Enter))
+            .await
+            .unwrap();
+        let Some(ActiveModal::Compare(ref view)) = app.modal else {
+            panic!("compare closed");
+        };
+        assert!(view.form.is_none());
+        assert!(view.loading);
+
+        let event = app.wait_event().await.unwrap();
+        assert!(matches!(
+            event,
+            AppEvent::Compared { ref right, result: Err(_), .. } if right == "s3:backup"
+        ));
+        Handler::handle_app_event(&mut app, event);
+        let Some(ActiveModal::Compare(ref view)) = app.modal else {
+            panic!("compare closed");
+        };
+        assert!(!view.loading);
+        assert!(view.error.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_stale_comparison_is_ignored() {
+        let mut app = unreachable_app();
+        let mut view = CompareView::new("gdrive:".to_string());
+        view.start("gdrive:".to_string(), "s3:new".to_string());
+        app.modal = Some(ActiveModal::Compare(Box::new(view)));
+
+        Handler::apply_compared(&mut app, "gdrive:", "s3:old", None, Ok(Vec::new()));
+
+        let Some(ActiveModal::Compare(ref view)) = app.modal else {
+            panic!("compare closed");
+        };
+        assert!(view.loading);
+    }
+}
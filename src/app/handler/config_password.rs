@@ -0,0 +1,148 @@
+//! Config password handling: unlocking the daemon's encrypted rclone
+//! config.
+
+use super::Handler;
+use crate::app::state::{ActiveModal, App};
+use crate::error::Result;
+use crate::ui::ConfigPasswordModal;
+use crossterm::event::{KeyCode, KeyEvent};
+use tracing::{info, warn};
+
+impl Handler {
+    /// Handles keys while the config password prompt is open. Only
+    /// `Esc` works while an attempt is in flight.
+    pub(super) fn handle_config_password_key(app: &mut App, key: KeyEvent) {
+        let Some(ActiveModal::ConfigPassword(ref mut modal)) = app.modal else {
+            return;
+        };
+        match key.code {
+            KeyCode::Esc => {
+                warn!("config password prompt skipped");
+                app.modal = None;
+                app.notifications
+                    .warning("Remotes cannot load until the config is unlocked");
+            }
+            _ if modal.waiting => {}
+            KeyCode::Enter if modal.input.is_empty() => {
+                modal.error = Some("Enter the password".to_string());
+            }
+            KeyCode::Enter => {
+                modal.waiting = true;
+                modal.error = None;
+                let password = std::mem::take(&mut modal.input);
+                modal.cursor.end();
+                app.request_unlock_config(password);
+            }
+            _ => modal.edit(key),
+        }
+    }
+
+    /// Loads the remotes once the config is unlocked, or asks for the
+    /// password again.
+    pub(super) fn apply_config_unlocked(app: &mut App, result: Result<()>) {
+        match result {
+            Ok(()) => {
+                info!("config unlocked");
+                if matches!(app.modal, Some(ActiveModal::ConfigPassword(_))) {
+                    app.modal = None;
+                }
+                app.notifications.success("Config unlocked");
+                app.request_remotes();
+            }
+            Err(e) => {
+                warn!(error = %e, "unlocking config failed");
+                match app.modal {
+                    Some(ActiveModal::ConfigPassword(ref mut modal)) => {
+                        modal.waiting = false;
+                        modal.error = Some(format!("Could not unlock: {}", e));
+                    }
+                    _ => app
+                        .notifications
+                        .error(format!("Could not unlock config: {}", e)),
+                }
+            }
+        }
+    }
+
+    /// Asks for the config password, unless another modal is open;
+    /// then a toast says why remotes did not load.
+    pub(super) fn prompt_config_password(app: &mut App) {
+        if app.modal.is_none() {
+            app.modal = Some(ActiveModal::ConfigPassword(ConfigPasswordModal::default()));
+        } else if !matches!(app.modal, Some(ActiveModal::ConfigPassword(_))) {
+            app.notifications
+                .warning("The daemon's config is encrypted; close this to unlock it");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::event::AppEvent;
+    use crate::app::handler::test_support::{key, unreachable_app};
+    use crate::error::LazyFileError;
+
+    fn locked() -> LazyFileError {
+        LazyFileError::RcloneStatus {
+            endpoint: "config/listremotes",
+            status: reqwest::StatusCode::INTERNAL_SERVER_ERROR,
+            body: r#"{"error": "unable to decrypt configuration and not allowed to ask for \
+                   password: set RCLONE_CONFIG_PASS to your configuration password"}"#
+                .to_string(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_locked_config_opens_prompt() {
+        let mut app = unreachable_app();
+        assert!(locked().is_config_locked());
+
+        Handler::handle_app_event(&mut app, AppEvent::RemotesLoaded(Err(locked())));
+
+        assert!(matches!(app.modal, Some(ActiveModal::ConfigPassword(_))));
+    }
+
+    #[tokio::test]
+    async fn test_password_is_sent_and_failure_shown() {
+        let mut app = unreachable_app();
+        app.modal = Some(ActiveModal::ConfigPassword(ConfigPasswordModal::default()));
+        for c in "secret".chars() {
+            Handler::handle_key(&mut app, key(KeyCode::Char(c)))
+                .await
+                .unwrap();
+        }
+        Handler::handle_key(&mut app, key(KeyCode::Enter))
+            .await
+            .unwrap();
+
+        let Some(ActiveModal::ConfigPassword(ref modal)) = app.modal else {
+            panic!("prompt closed");
+        };
+        assert!(modal.waiting);
+        assert!(modal.input.is_empty());
+
+        let event = app.wait_event().await.unwrap();
+        assert!(matches!(event, AppEvent::ConfigUnlocked(Err(_))));
+        Handler::handle_app_event(&mut app, event);
+        let Some(ActiveModal::ConfigPassword(ref modal)) = app.modal else {
+            panic!("prompt closed");
+        };
+        assert!(!modal.waiting);
+        assert!(modal.error.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_unlock_loads_remotes() {
+        let mut app = unreachable_app();
+        app.modal = Some(ActiveModal::ConfigPassword(ConfigPasswordModal::default()));
+
+        Handler::handle_app_event(&mut app, AppEvent::ConfigUnlocked(Ok(())));
+
+        assert!(app.modal.is_none());
+        let event = app
+            .wait_event_matching(|event| matches!(event, AppEvent::RemotesLoaded(_)))
+            .await;
+        assert!(event.is_some());
+    }
+}
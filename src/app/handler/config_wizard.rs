@@ -0,0 +1,288 @@
+//! Config wizard handling: answering the questions rclone asks while
+//! a remote is created.
+
+use super::Handler;
+use crate::app::state::{ActiveModal, App};
+use crate::error::Result;
+use crate::rclone::ConfigQuestion;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use tracing::{debug, info, warn};
+
+impl Handler {
+    /// Handles keyboard input while the config wizard is open. Only
+    /// cancelling and copying the link work while a step is in
+    /// flight.
+    pub(super) async fn handle_wizard_key(app: &mut App, key: KeyEvent) -> Result<()> {
+        let Some(ActiveModal::ConfigWizard(ref mut wizard)) = app.modal else {
+            return Ok(());
+        };
+        match key.code {
+            KeyCode::Esc => Self::cancel_wizard(app),
+            KeyCode::Char('y') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                if let Some(link) = wizard.link().map(str::to_string) {
+                    Self::copy_text(app, &link);
+                }
+            }
+            _ if wizard.waiting => {}
+            KeyCode::Down | KeyCode::Tab => wizard.select_next(),
+            KeyCode::Up | KeyCode::BackTab => wizard.select_prev(),
+            KeyCode::Char(c) => wizard.input_char(c),
+            KeyCode::Backspace => wizard.backspace(),
+            KeyCode::Enter => Self::submit_answer(app),
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Sends the answer to the current question, unless rclone would
+    /// reject it anyway.
+    fn submit_answer(app: &mut App) {
+        let Some(ActiveModal::ConfigWizard(ref mut wizard)) = app.modal else {
+            return;
+        };
+        let Some(ref question) = wizard.question else {
+            return;
+        };
+        let option = &question.option;
+        if option.required && wizard.answer.is_empty() {
+            wizard.error = Some("An answer is required".to_string());
+            return;
+        }
+        if option.exclusive && !option.examples().is_empty() && wizard.selected.is_none() {
+            wizard.error = Some("Choose one of the listed values".to_string());
+            return;
+        }
+
+        let (name, state) = (wizard.name.clone(), question.state.clone());
+        let answer = wizard.answer.clone();
+        wizard.waiting = true;
+        wizard.error = None;
+        app.request_config_answer(name, state, answer);
+    }
+
+    /// Closes the wizard and deletes the half-configured remote, which
+    /// rclone created when the wizard opened.
+    fn cancel_wizard(app: &mut App) {
+        let Some(ActiveModal::ConfigWizard(wizard)) = app.modal.take() else {
+            return;
+        };
+        info!(remote = %wizard.name, "cancelling remote config");
+        app.request_delete_remote(wizard.name, true);
+    }
+
+    /// Shows the next question, or closes the wizard once the remote
+    /// is complete. Results for a wizard that was closed are dropped.
+    pub(super) fn apply_config_step(
+        app: &mut App,
+        name: &str,
+        result: Result<Option<ConfigQuestion>>,
+    ) {
+        let Some(ActiveModal::ConfigWizard(ref mut wizard)) = app.modal else {
+            debug!(remote = name, "config wizard closed, dropping step");
+            return;
+        };
+        if wizard.name != name {
+            return;
+        }
+        match result {
+            Ok(Some(question)) => wizard.ask(question),
+            Ok(None) => {
+                info!(remote = name, "remote created");
+                app.modal = None;
+                app.notifications
+                    .success(format!("Created remote '{}'", name));
+                app.request_remotes();
+            }
+            Err(e) => {
+                warn!(remote = name, error = %e, "remote config step failed");
+                wizard.waiting = false;
+                wizard.error = Some(format!("Error: {}", e));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::event::AppEvent;
+    use crate::app::handler::test_support::{key, unreachable_app};
+    use crate::rclone::{ConfigExample, ConfigOption};
+    use crate::ui::ConfigWizard;
+
+    fn is_local_question() -> ConfigQuestion {
+        ConfigQuestion {
+            state: "*oauth-islocal".to_string(),
+            option: ConfigOption {
+                name: "config_is_local".to_string(),
+                default: "true".to_string(),
+                examples: Some(vec![
+                    ConfigExample {
+                        value: "true".to_string(),
+                        help: "Yes".to_string(),
+                        ..ConfigExample::default()
+                    },
+                    ConfigExample {
+                        value: "false".to_string(),
+                        help: "No".to_string(),
+                        ..ConfigExample::default()
+                    },
+                ]),
+                exclusive: true,
+                ..ConfigOption::default()
+            },
+            error: None,
+        }
+    }
+
+    fn wizard(app: &App) -> &ConfigWizard {
+        match app.modal {
+            Some(ActiveModal::ConfigWizard(ref wizard)) => wizard,
+            ref other => panic!("expected config wizard, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_create_submit_opens_wizard() {
+        let mut app = unreachable_app();
+        app.modal = Some(ActiveModal::CreateRemote(Box::new(
+            crate::ui::CreateRemoteModal::new(crate::ui::CreateRemoteMode::Create)
+                .with_name("gdrive".to_string())
+                .with_type("drive".to_string()),
+        )));
+
+        Handler::handle_key(&mut app, key(KeyCode::Enter))
+            .await
+            .unwrap();
+        assert!(wizard(&app).waiting);
+
+        let event = app.wait_event().await.unwrap();
+        assert!(matches!(event, AppEvent::RemoteConfigStep { ref name, .. } if name == "gdrive"));
+        Handler::handle_app_event(&mut app, event);
+        let wizard = wizard(&app);
+        assert!(!wizard.waiting);
+        assert!(wizard.error.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_existing_remote_is_not_recreated() {
+        let mut app = unreachable_app();
+        app.remotes = vec!["gdrive".to_string()];
+        app.modal = Some(ActiveModal::CreateRemote(Box::new(
+            crate::ui::CreateRemoteModal::new(crate::ui::CreateRemoteMode::Create)
+                .with_name("gdrive".to_string()),
+        )));
+
+        Handler::handle_key(&mut app, key(KeyCode::Enter))
+            .await
+            .unwrap();
+
+        let modal = app.create_remote_modal().unwrap();
+        assert!(modal.error.as_deref().unwrap().contains("already exists"));
+    }
+
+    #[tokio::test]
+    async fn test_question_is_answered_in_background() {
+        let mut app = unreachable_app();
+        app.modal = Some(ActiveModal::ConfigWizard(Box::new(ConfigWizard::new(
+            "gdrive", "drive",
+        ))));
+        Handler::handle_app_event(
+            &mut app,
+            AppEvent::RemoteConfigStep {
+                name: "gdrive".to_string(),
+                result: Ok(Some(is_local_question())),
+            },
+        );
+        assert_eq!(wizard(&app).answer, "true");
+
+        Handler::handle_key(&mut app, key(KeyCode::Down))
+            .await
+            .unwrap();
+        Handler::handle_key(&mut app, key(KeyCode::Enter))
+            .await
+            .unwrap();
+        assert!(wizard(&app).waiting);
+        assert_eq!(wizard(&app).answer, "false");
+
+        // Typing is ignored until the daemon answers.
+        Handler::handle_key(&mut app, key(KeyCode::Char('x')))
+            .await
+            .unwrap();
+        assert_eq!(wizard(&app).answer, "false");
+    }
+
+    #[tokio::test]
+    async fn test_exclusive_question_rejects_free_text() {
+        let mut app = unreachable_app();
+        let mut wizard_state = ConfigWizard::new("gdrive", "drive");
+        wizard_state.ask(is_local_question());
+        app.modal = Some(ActiveModal::ConfigWizard(Box::new(wizard_state)));
+
+        Handler::handle_key(&mut app, key(KeyCode::Char('x')))
+            .await
+            .unwrap();
+        Handler::handle_key(&mut app, key(KeyCode::Enter))
+            .await
+            .unwrap();
+
+        let wizard = wizard(&app);
+        assert!(!wizard.waiting);
+        assert!(wizard.error.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_finished_config_closes_wizard() {
+        let mut app = unreachable_app();
+        app.modal = Some(ActiveModal::ConfigWizard(Box::new(ConfigWizard::new(
+            "gdrive", "drive",
+        ))));
+
+        Handler::handle_app_event(
+            &mut app,
+            AppEvent::RemoteConfigStep {
+                name: "gdrive".to_string(),
+                result: Ok(None),
+            },
+        );
+
+        assert!(app.modal.is_none());
+        assert!(
+            app.notifications
+                .toasts()
+                .last()
+                .unwrap()
+                .message
+                .contains("gdrive")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_step_for_closed_wizard_is_dropped() {
+        let mut app = unreachable_app();
+
+        Handler::handle_app_event(
+            &mut app,
+            AppEvent::RemoteConfigStep {
+                name: "gdrive".to_string(),
+                result: Ok(Some(is_local_question())),
+            },
+        );
+
+        assert!(app.modal.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_escape_closes_wizard() {
+        let mut app = unreachable_app();
+        app.modal = Some(ActiveModal::ConfigWizard(Box::new(ConfigWizard::new(
+            "gdrive", "drive",
+        ))));
+
+        Handler::handle_key(&mut app, key(KeyCode::Esc))
+            .await
+            .unwrap();
+
+        assert!(app.modal.is_none());
+    }
+}
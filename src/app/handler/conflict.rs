@@ -0,0 +1,178 @@
+//! Conflict dialog for a copy or move whose destination exists.
+
+use super::Handler;
+use crate::app::state::{ActiveModal, App};
+use crate::config::ConflictPolicy;
+use crate::rclone::FileItem;
+use crate::ui::{ConflictModal, FileOperationType, FileOperationsModal};
+use crossterm::event::{KeyCode, KeyEvent};
+use tracing::{debug, info};
+
+impl Handler {
+    /// Asks what to do about the taken destination of `operation`.
+    /// If its modal was closed meanwhile, a toast says that nothing
+    /// was transferred instead.
+    pub(super) fn apply_transfer_conflict(
+        app: &mut App,
+        remote: String,
+        operation: FileOperationsModal,
+        existing: FileItem,
+    ) {
+        let waiting = matches!(
+            app.modal,
+            Some(ActiveModal::FileOperation(ref modal)) if modal.waiting
+        );
+        if !waiting {
+            let verb = match operation.operation {
+                FileOperationType::Move => "moved",
+                _ => "copied",
+            };
+            app.notifications.warning(format!(
+                "'{}' already exists; '{}' was not {}",
+                operation.input, operation.file_name, verb
+            ));
+            return;
+        }
+        debug!(dst = %operation.input, "opening conflict dialog");
+        app.modal = Some(ActiveModal::Conflict(Box::new(ConflictModal::new(
+            remote, operation, existing,
+        ))));
+    }
+
+    /// Handles keyboard input in the conflict dialog.
+    pub(super) fn handle_conflict_key(app: &mut App, key: KeyEvent) {
+        let Some(ActiveModal::Conflict(ref mut modal)) = app.modal else {
+            return;
+        };
+        let choice = match key.code {
+            KeyCode::Esc => {
+                debug!("conflict dialog cancelled");
+                app.modal = None;
+                return;
+            }
+            KeyCode::Left | KeyCode::BackTab | KeyCode::Char('h') => {
+                modal.step(-1);
+                return;
+            }
+            KeyCode::Right | KeyCode::Tab | KeyCode::Char('l') => {
+                modal.step(1);
+                return;
+            }
+            KeyCode::Char('a') => {
+                modal.toggle_apply_to_all();
+                return;
+            }
+            KeyCode::Char('s') => ConflictPolicy::Skip,
+            KeyCode::Char('o') => ConflictPolicy::Overwrite,
+            KeyCode::Char('r') => ConflictPolicy::Rename,
+            KeyCode::Enter => modal.selected,
+            _ => return,
+        };
+        Self::resolve_conflict(app, choice);
+    }
+
+    /// Runs the copy or move again with `choice`, back in its modal,
+    /// and keeps `choice` for later conflicts if "apply to all" is
+    /// ticked.
+    fn resolve_conflict(app: &mut App, choice: ConflictPolicy) {
+        let Some(ActiveModal::Conflict(modal)) = app.modal.take() else {
+            return;
+        };
+        let ConflictModal {
+            remote,
+            mut operation,
+            apply_to_all,
+            ..
+        } = *modal;
+        if apply_to_all {
+            info!(policy = ?choice, "applying conflict choice to the rest of the session");
+            app.conflict_policy = choice;
+        }
+        operation.waiting = true;
+        operation.error = None;
+        app.request_file_operation(remote, &operation, choice);
+        app.modal = Some(ActiveModal::FileOperation(operation));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::event::AppEvent;
+    use crate::app::handler::test_support::{key, unreachable_app};
+
+    fn copy_waiting() -> FileOperationsModal {
+        let mut modal = FileOperationsModal::copy("a.txt".to_string(), "/".to_string());
+        modal.input = "b.txt".to_string();
+        modal.waiting = true;
+        modal
+    }
+
+    fn existing() -> FileItem {
+        FileItem {
+            name: "b.txt".to_string(),
+            size: 10,
+            mod_time: "2024-01-01T00:00:00Z".to_string(),
+            is_dir: false,
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_conflict_opens_dialog_only_while_waiting() {
+        let mut app = unreachable_app();
+        app.modal = Some(ActiveModal::FileOperation(copy_waiting()));
+        Handler::apply_transfer_conflict(
+            &mut app,
+            "gdrive".to_string(),
+            copy_waiting(),
+            existing(),
+        );
+        assert!(matches!(app.modal, Some(ActiveModal::Conflict(_))));
+
+        app.modal = None;
+        Handler::apply_transfer_conflict(
+            &mut app,
+            "gdrive".to_string(),
+            copy_waiting(),
+            existing(),
+        );
+        assert!(app.modal.is_none());
+        assert!(
+            app.notifications
+                .toasts()
+                .last()
+                .unwrap()
+                .message
+                .contains("was not copied")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_choice_retries_and_applies_to_all() {
+        let mut app = unreachable_app();
+        app.modal = Some(ActiveModal::FileOperation(copy_waiting()));
+        Handler::apply_transfer_conflict(
+            &mut app,
+            "gdrive".to_string(),
+            copy_waiting(),
+            existing(),
+        );
+
+        Handler::handle_key(&mut app, key(KeyCode::Char('a')))
+            .await
+            .unwrap();
+        Handler::handle_key(&mut app, key(KeyCode::Char('o')))
+            .await
+            .unwrap();
+
+        assert_eq!(app.conflict_policy, ConflictPolicy::Overwrite);
+        let modal = app.file_operations_modal().unwrap();
+        assert!(modal.waiting);
+        let event = app.wait_event().await.unwrap();
+        assert!(matches!(
+            event,
+            AppEvent::FileOperationFinished { result: Err(_), .. }
+        ));
+    }
+}
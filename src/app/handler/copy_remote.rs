@@ -0,0 +1,196 @@
+//! Remote rename/duplicate handling.
+//!
+//! rclone has no rename, so both copy the remote's config under the
+//! new name; a rename then deletes the original and moves its star
+//! and place in the list to the copy.
+
+use super::Handler;
+use crate::app::state::{ActiveModal, App};
+use crate::error::Result;
+use crate::ui::CopyRemoteModal;
+use crossterm::event::{KeyCode, KeyEvent};
+use tracing::{debug, info, warn};
+
+impl Handler {
+    /// Opens the rename/duplicate prompt made by `open` for the
+    /// selected remote.
+    pub(super) fn handle_copy_remote(app: &mut App, open: fn(String) -> CopyRemoteModal) {
+        if let Some(remote) = app.remotes.get(app.remotes_selected) {
+            debug!(remote = %remote, "opening copy remote prompt");
+            app.modal = Some(ActiveModal::CopyRemote(open(remote.clone())));
+        }
+    }
+
+    /// Handles keys while the rename/duplicate prompt is open. Only
+    /// `Esc` works while the copy is in flight.
+    pub(super) fn handle_copy_remote_key(app: &mut App, key: KeyEvent) {
+        let Some(ActiveModal::CopyRemote(ref mut modal)) = app.modal else {
+            return;
+        };
+        match key.code {
+            KeyCode::Esc => app.modal = None,
+            _ if modal.waiting => {}
+            KeyCode::Tab => modal.toggle_remove(),
+            KeyCode::Char(c) => modal.input_char(c),
+            KeyCode::Backspace => modal.backspace(),
+            KeyCode::Enter => match modal.target(&app.remotes) {
+                Ok(target) => {
+                    let target = target.to_string();
+                    modal.waiting = true;
+                    modal.error = None;
+                    let (source, remove_source) = (modal.source.clone(), modal.remove_source);
+                    app.request_copy_remote(source, target, remove_source);
+                }
+                Err(reason) => modal.error = Some(reason.to_string()),
+            },
+            _ => {}
+        }
+    }
+
+    /// Closes the prompt once the copy exists and reports the outcome,
+    /// or shows why copying failed.
+    pub(super) fn apply_remote_copied(
+        app: &mut App,
+        from: &str,
+        to: &str,
+        result: Result<()>,
+        removed: Option<Result<()>>,
+    ) {
+        if let Err(e) = result {
+            warn!(from, to, error = %e, "copying remote failed");
+            match app.modal {
+                Some(ActiveModal::CopyRemote(ref mut modal)) => {
+                    modal.waiting = false;
+                    modal.error = Some(format!("Could not copy: {}", e));
+                }
+                _ => app
+                    .notifications
+                    .error(format!("Could not copy remote '{}': {}", from, e)),
+            }
+            return;
+        }
+
+        if matches!(app.modal, Some(ActiveModal::CopyRemote(_))) {
+            app.modal = None;
+        }
+        match removed {
+            Some(Ok(())) => {
+                info!(from, to, "remote renamed");
+                if let Err(e) = app.rename_in_layout(from, to) {
+                    warn!(error = %e, "could not save remote layout");
+                    app.notifications.error(e.to_string());
+                }
+                app.notifications
+                    .success(format!("Renamed '{}' to '{}'", from, to));
+            }
+            Some(Err(e)) => {
+                warn!(from, error = %e, "deleting renamed remote failed");
+                app.notifications.warning(format!(
+                    "Copied '{}' to '{}' but could not delete it: {}",
+                    from, to, e
+                ));
+            }
+            None => {
+                info!(from, to, "remote duplicated");
+                app.notifications
+                    .success(format!("Duplicated '{}' as '{}'", from, to));
+            }
+        }
+        app.request_remotes();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::event::AppEvent;
+    use crate::app::handler::test_support::{key, unreachable_app};
+    use crate::error::LazyFileError;
+
+    #[tokio::test]
+    async fn test_taken_name_is_rejected_locally() {
+        let mut app = unreachable_app();
+        app.remotes = vec!["gdrive".to_string(), "s3".to_string()];
+        app.modal = Some(ActiveModal::CopyRemote(CopyRemoteModal::rename(
+            "gdrive".to_string(),
+        )));
+        for _ in 0.."gdrive".len() {
+            Handler::handle_key(&mut app, key(KeyCode::Backspace))
+                .await
+                .unwrap();
+        }
+        for c in "s3".chars() {
+            Handler::handle_key(&mut app, key(KeyCode::Char(c)))
+                .await
+                .unwrap();
+        }
+        Handler::handle_key(&mut app, key(KeyCode::Enter))
+            .await
+            .unwrap();
+
+        let Some(ActiveModal::CopyRemote(ref modal)) = app.modal else {
+            panic!("prompt closed");
+        };
+        assert!(!modal.waiting);
+        assert!(modal.error.is_some());
+        assert_eq!(app.workers.busy(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_failed_copy_keeps_prompt_open() {
+        let mut app = unreachable_app();
+        app.remotes = vec!["gdrive".to_string()];
+        app.modal = Some(ActiveModal::CopyRemote(CopyRemoteModal::duplicate(
+            "gdrive".to_string(),
+        )));
+        Handler::handle_key(&mut app, key(KeyCode::Enter))
+            .await
+            .unwrap();
+
+        let event = app.wait_event().await.unwrap();
+        assert!(matches!(
+            event,
+            AppEvent::RemoteCopied {
+                result: Err(_),
+                removed: None,
+                ..
+            }
+        ));
+        Handler::handle_app_event(&mut app, event);
+        let Some(ActiveModal::CopyRemote(ref modal)) = app.modal else {
+            panic!("prompt closed");
+        };
+        assert!(!modal.waiting);
+        assert!(modal.error.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_undeleted_original_is_reported() {
+        let mut app = unreachable_app();
+        app.modal = Some(ActiveModal::CopyRemote(CopyRemoteModal::rename(
+            "gdrive".to_string(),
+        )));
+        let removed = Err(LazyFileError::RcloneApi {
+            endpoint: "config/delete",
+            message: "connection refused".to_string(),
+        });
+
+        Handler::handle_app_event(
+            &mut app,
+            AppEvent::RemoteCopied {
+                from: "gdrive".to_string(),
+                to: "work".to_string(),
+                result: Ok(()),
+                removed: Some(removed),
+            },
+        );
+
+        assert!(app.modal.is_none());
+        let toast = app.notifications.toasts().last().unwrap();
+        assert!(toast.message.contains("could not delete"));
+        let event = app
+            .wait_event_matching(|event| matches!(event, AppEvent::RemotesLoaded(_)))
+            .await;
+        assert!(event.is_some());
+    }
+}
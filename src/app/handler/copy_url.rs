@@ -0,0 +1,116 @@
+//! Copying a URL into the current directory: the daemon downloads it
+//! itself, so the data never passes through this machine.
+
+use super::Handler;
+use crate::app::command::rclone_path;
+use crate::app::state::{ActiveModal, App};
+use crate::error::Result;
+use crate::ui::CopyUrlPrompt;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use tracing::{debug, info, warn};
+
+impl Handler {
+    /// Opens the copy URL prompt for the current directory.
+    pub(super) fn handle_copy_url(app: &mut App) {
+        let Some(remote) = app.current_remote.clone() else {
+            return;
+        };
+        debug!("opening copy URL prompt");
+        app.modal = Some(ActiveModal::CopyUrl(CopyUrlPrompt::new(
+            remote,
+            app.current_path.clone(),
+        )));
+    }
+
+    /// Handles keyboard input in the copy URL prompt. `Enter` starts
+    /// the download and closes the prompt.
+    pub(super) fn handle_copy_url_key(app: &mut App, key: KeyEvent) {
+        let Some(ActiveModal::CopyUrl(ref mut prompt)) = app.modal else {
+            return;
+        };
+        match key.code {
+            KeyCode::Esc => {
+                debug!("closing copy URL prompt");
+                app.modal = None;
+            }
+            KeyCode::Tab | KeyCode::BackTab => prompt.toggle_field(),
+            KeyCode::Char(c) if !key.modifiers.contains(KeyModifiers::CONTROL) => {
+                prompt.input_char(c);
+            }
+            KeyCode::Backspace => prompt.backspace(),
+            KeyCode::Enter => match prompt.target() {
+                Ok((url, name)) => {
+                    let remote = prompt.remote.clone();
+                    let path = app.child_path(&name);
+                    app.modal = None;
+                    app.notifications
+                        .info(format!("Downloading {} into {}", name, remote));
+                    app.request_copy_url(remote, path, url);
+                }
+                Err(reason) => prompt.error = Some(reason),
+            },
+            _ => {}
+        }
+    }
+
+    /// Reports a finished download and lists the directory again.
+    pub(super) fn apply_url_copied(app: &mut App, remote: &str, path: &str, result: Result<()>) {
+        let location = rclone_path(remote, path);
+        match result {
+            Ok(()) => {
+                info!(location = %location, "URL copied");
+                app.notifications.success(format!("Saved {}", location));
+                app.reload_files();
+            }
+            Err(e) => {
+                warn!(location = %location, error = %e, "copying URL failed");
+                app.notifications
+                    .error(format!("Could not download into {}: {}", location, e));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::handler::test_support::{key, unreachable_app};
+    use crate::app::state::Panel;
+
+    #[tokio::test]
+    async fn test_enter_downloads_into_the_current_directory() {
+        let mut app = unreachable_app();
+        app.focused_panel = Panel::Files;
+        app.current_remote = Some("s3".to_string());
+        app.current_path = "releases".to_string();
+
+        Handler::handle_key(&mut app, key(KeyCode::Char('U')))
+            .await
+            .unwrap();
+        Handler::handle_key(&mut app, key(KeyCode::Enter))
+            .await
+            .unwrap();
+        let Some(ActiveModal::CopyUrl(ref prompt)) = app.modal else {
+            panic!("copy URL prompt closed");
+        };
+        assert!(prompt.error.is_some());
+
+        for c in "https://example.com/app.tar.gz".chars() {
+            Handler::handle_key(&mut app, key(KeyCode::Char(c)))
+                .await
+                .unwrap();
+        }
+        Handler::handle_key(&mut app, key(KeyCode::Enter))
+            .await
+            .unwrap();
+        assert!(app.modal.is_none());
+
+        let event = app.wait_event().await.unwrap();
+        Handler::handle_app_event(&mut app, event);
+        let last = app.notifications.toasts().last().unwrap();
+        assert!(
+            last.message
+                .starts_with("Could not download into s3:releases/app.tar.gz")
+        );
+    }
+}
@@ -0,0 +1,125 @@
+//! Daemon setup dialog handling: starting `rclone rcd` for the session
+//! when no daemon answers, or checking again once rclone is installed.
+
+use super::Handler;
+use crate::app::state::{ActiveModal, App};
+use crate::daemon::{self, Daemon};
+use crate::error::Result;
+use crate::rclone::RcloneClient;
+use crossterm::event::{KeyCode, KeyEvent};
+use std::time::Instant;
+use tracing::{debug, info, warn};
+
+impl Handler {
+    /// Handles keys while the daemon setup dialog is open. Only `Esc`
+    /// and `q` work while a daemon is starting.
+    pub(super) fn handle_daemon_setup_key(app: &mut App, key: KeyEvent) {
+        let Some(ActiveModal::DaemonSetup(ref mut setup)) = app.modal else {
+            return;
+        };
+        match key.code {
+            KeyCode::Esc => {
+                debug!("daemon setup dismissed");
+                app.modal = None;
+                app.notifications
+                    .warning("Remotes load once an rclone daemon answers");
+            }
+            KeyCode::Char('q') => app.running = false,
+            _ if setup.starting => {}
+            KeyCode::Char('s') | KeyCode::Enter => {
+                let Some(program) = setup.rclone.clone() else {
+                    return;
+                };
+                setup.starting = true;
+                setup.error = None;
+                app.request_start_daemon(program);
+            }
+            KeyCode::Char('r') => {
+                setup.rclone = daemon::find_rclone();
+                setup.error = None;
+                debug!(found = setup.rclone.is_some(), "looked for rclone again");
+                app.request_remotes();
+            }
+            _ => {}
+        }
+    }
+
+    /// Switches to the daemon started for the session and loads its
+    /// remotes, or shows why it did not start. A daemon restarted after
+    /// it died needs no reload: the next ping reconnects and runs what
+    /// was queued meanwhile.
+    pub(super) fn apply_daemon_started(app: &mut App, result: Result<Daemon>) {
+        let result = result.and_then(|daemon| {
+            let client = RcloneClient::connect(daemon.connection())?;
+            Ok((daemon, client))
+        });
+        match result {
+            Ok((daemon, client)) => {
+                info!("connected to the rclone daemon started for the session");
+                app.client = client.with_policy(app.client.policy().clone());
+                app.daemon = Some(daemon);
+                app.owns_daemon = true;
+                app.profile = None;
+                app.reset_health(Instant::now());
+                if matches!(app.modal, Some(ActiveModal::DaemonSetup(_))) {
+                    app.modal = None;
+                    app.notifications
+                        .success("Started rclone rcd for this session");
+                    app.request_remotes();
+                    app.request_providers();
+                }
+            }
+            Err(e) => {
+                warn!(error = %e, "starting rclone daemon failed");
+                match app.modal {
+                    Some(ActiveModal::DaemonSetup(ref mut setup)) => {
+                        setup.starting = false;
+                        setup.error = Some(e.to_string());
+                    }
+                    _ => app
+                        .notifications
+                        .error(format!("Could not start rclone rcd: {}", e)),
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::handler::test_support::{key, unreachable_app};
+    use crate::ui::DaemonSetup;
+    use std::path::PathBuf;
+
+    #[tokio::test]
+    async fn test_start_failure_stays_in_the_dialog() {
+        let mut app = unreachable_app();
+        app.modal = Some(ActiveModal::DaemonSetup(Box::new(DaemonSetup::new(
+            "localhost:1".to_string(),
+            Some(PathBuf::from("/nonexistent/rclone")),
+        ))));
+        Handler::handle_key(&mut app, key(KeyCode::Char('s')))
+            .await
+            .unwrap();
+        let Some(ActiveModal::DaemonSetup(ref setup)) = app.modal else {
+            panic!("daemon setup closed");
+        };
+        assert!(setup.starting);
+
+        let event = app.wait_event().await.unwrap();
+        Handler::handle_app_event(&mut app, event);
+        let Some(ActiveModal::DaemonSetup(ref setup)) = app.modal else {
+            panic!("daemon setup closed");
+        };
+        assert!(!setup.starting);
+        assert!(
+            setup
+                .error
+                .as_deref()
+                .unwrap()
+                .contains("/nonexistent/rclone")
+        );
+        assert!(app.daemon.is_none());
+    }
+}
@@ -0,0 +1,299 @@
+//! Deleting a remote: the confirmation modal and its outcome.
+
+use super::Handler;
+use crate::app::state::{ActiveModal, App};
+use crate::config::ConfirmStyle;
+use crate::error::Result;
+use crate::ui::ConfirmModal;
+use crossterm::event::{KeyCode, KeyEvent};
+use tracing::{debug, info, warn};
+
+impl Handler {
+    /// Opens the delete remote confirmation modal.
+    pub(super) fn handle_delete_remote(app: &mut App) {
+        if let Some(remote) = app.remotes.get(app.remotes_selected) {
+            debug!(remote = %remote, "opening delete confirmation");
+            let mut modal = ConfirmModal::new("Delete Remote", format!("Delete '{}'?", remote));
+            if app.confirm.delete_remote == ConfirmStyle::Type {
+                modal = modal.with_expected(remote.clone());
+            }
+            app.modal = Some(ActiveModal::ConfirmDeleteRemote {
+                remote: remote.clone(),
+                modal,
+            });
+        }
+    }
+
+    /// Handles confirmation modal input. While a name must be typed,
+    /// keys other than `Tab`, `Enter` and `Esc` edit it.
+    pub(super) async fn handle_confirm_key(app: &mut App, key: KeyEvent) -> Result<()> {
+        if let Some(ActiveModal::ConfirmDeleteRemote { ref mut modal, .. }) = app.modal {
+            match key.code {
+                KeyCode::Esc => {
+                    debug!("cancelling delete");
+                    app.modal = None;
+                }
+                KeyCode::Tab | KeyCode::BackTab => modal.toggle(),
+                KeyCode::Enter => Self::handle_confirm_submit(app).await?,
+                _ if modal.expected.is_some() => {
+                    modal.edit(key);
+                }
+                KeyCode::Right | KeyCode::Left => {
+                    modal.toggle();
+                }
+                KeyCode::Char(c) if c == 'y' || c == 'n' => {
+                    let confirmed = c == 'y';
+                    if confirmed != modal.is_confirmed() {
+                        modal.toggle();
+                    }
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    /// Closes the confirmation modal, deleting the remote only when
+    /// "Yes" is selected.
+    pub(super) async fn handle_confirm_submit(app: &mut App) -> Result<()> {
+        if let Some(ActiveModal::ConfirmDeleteRemote { remote, modal }) = app.modal.take()
+            && modal.is_confirmed()
+        {
+            app.request_delete_remote(remote, false);
+        }
+        Ok(())
+    }
+
+    /// Reports a deleted remote and reloads the remotes. The leftover
+    /// of a cancelled wizard is deleted quietly; it may never have
+    /// been created.
+    pub(super) fn apply_remote_deleted(
+        app: &mut App,
+        name: &str,
+        discard: bool,
+        result: Result<()>,
+    ) {
+        match (result, discard) {
+            (Ok(()), _) => {
+                info!(remote = name, "remote deleted");
+                if !discard {
+                    app.notifications
+                        .success(format!("Deleted remote '{}'", name));
+                }
+                app.request_remotes();
+            }
+            (Err(e), true) => {
+                debug!(remote = name, error = %e, "could not delete cancelled remote");
+            }
+            (Err(e), false) => {
+                warn!(remote = name, error = %e, "deleting remote failed");
+                app.notifications
+                    .error(format!("Could not delete remote '{}': {}", name, e));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::handler::test_support::{key, unreachable_app};
+    use crate::rclone::RcloneClient;
+    use crossterm::event::{KeyCode, KeyEvent, KeyEventKind, KeyEventState, KeyModifiers};
+
+    fn create_test_client() -> RcloneClient {
+        RcloneClient::new("localhost", 5572).expect("default reqwest client config is valid")
+    }
+
+    fn create_key_event(code: KeyCode) -> KeyEvent {
+        KeyEvent {
+            code,
+            modifiers: KeyModifiers::NONE,
+            kind: KeyEventKind::Press,
+            state: KeyEventState::NONE,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_open_delete_remote_modal() {
+        let client = create_test_client();
+        let mut app = App::new(client);
+        app.focused_panel = crate::app::state::Panel::Remotes;
+        app.remotes = vec!["test_remote".to_string()];
+        app.remotes_selected = 0;
+        assert!(app.confirm_modal().is_none());
+
+        let key = create_key_event(KeyCode::Char('d'));
+        Handler::handle_key(&mut app, key).await.unwrap();
+
+        assert!(app.confirm_modal().is_some());
+        assert_eq!(app.pending_delete_remote(), Some("test_remote"));
+    }
+
+    #[tokio::test]
+    async fn test_typed_delete_remote_needs_the_name() {
+        let mut app = unreachable_app();
+        app.focused_panel = crate::app::state::Panel::Remotes;
+        app.remotes = vec!["nas".to_string()];
+        app.confirm.delete_remote = ConfirmStyle::Type;
+
+        for code in [KeyCode::Char('d'), KeyCode::Char('y'), KeyCode::Tab] {
+            Handler::handle_key(&mut app, key(code)).await.unwrap();
+        }
+        let modal = app.confirm_modal().unwrap();
+        assert_eq!(modal.typed, "y");
+        assert!(!modal.is_confirmed());
+
+        Handler::handle_key(&mut app, key(KeyCode::Backspace))
+            .await
+            .unwrap();
+        for c in "nas".chars() {
+            Handler::handle_key(&mut app, key(KeyCode::Char(c)))
+                .await
+                .unwrap();
+        }
+        assert!(app.confirm_modal().unwrap().is_confirmed());
+    }
+
+    #[tokio::test]
+    async fn test_delete_remote_no_remotes() {
+        let client = create_test_client();
+        let mut app = App::new(client);
+        app.focused_panel = crate::app::state::Panel::Remotes;
+        app.remotes = vec![];
+
+        let key = create_key_event(KeyCode::Char('d'));
+        Handler::handle_key(&mut app, key).await.unwrap();
+
+        assert!(app.confirm_modal().is_none());
+        assert!(app.pending_delete_remote().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_delete_remote_key_ignored_in_files_panel() {
+        let client = create_test_client();
+        let mut app = App::new(client);
+        app.focused_panel = crate::app::state::Panel::Files;
+        app.remotes = vec!["test_remote".to_string()];
+
+        let key = create_key_event(KeyCode::Char('d'));
+        Handler::handle_key(&mut app, key).await.unwrap();
+
+        assert!(app.confirm_modal().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_confirm_modal_escape_closes() {
+        let client = create_test_client();
+        let mut app = App::new(client);
+        app.modal = Some(ActiveModal::ConfirmDeleteRemote {
+            remote: "test".to_string(),
+            modal: ConfirmModal::new("Test", "Test message".to_string()),
+        });
+
+        let key = create_key_event(KeyCode::Esc);
+        Handler::handle_key(&mut app, key).await.unwrap();
+
+        assert!(app.confirm_modal().is_none());
+        assert!(app.pending_delete_remote().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_confirm_modal_tab_toggles() {
+        let client = create_test_client();
+        let mut app = App::new(client);
+        app.modal = Some(ActiveModal::ConfirmDeleteRemote {
+            remote: "test".to_string(),
+            modal: ConfirmModal::new("Test", "Test message".to_string()),
+        });
+
+        assert!(!app.confirm_modal().unwrap().is_confirmed());
+
+        let key = create_key_event(KeyCode::Tab);
+        Handler::handle_key(&mut app, key).await.unwrap();
+
+        assert!(app.confirm_modal().unwrap().is_confirmed());
+    }
+
+    #[tokio::test]
+    async fn test_confirm_modal_left_right_toggle() {
+        let client = create_test_client();
+        let mut app = App::new(client);
+        app.modal = Some(ActiveModal::ConfirmDeleteRemote {
+            remote: "test".to_string(),
+            modal: ConfirmModal::new("Test", "Test message".to_string()),
+        });
+
+        let key = create_key_event(KeyCode::Right);
+        Handler::handle_key(&mut app, key).await.unwrap();
+        assert!(app.confirm_modal().unwrap().is_confirmed());
+
+        let key = create_key_event(KeyCode::Left);
+        Handler::handle_key(&mut app, key).await.unwrap();
+        assert!(!app.confirm_modal().unwrap().is_confirmed());
+    }
+
+    #[tokio::test]
+    async fn test_confirm_modal_y_key() {
+        let client = create_test_client();
+        let mut app = App::new(client);
+        app.modal = Some(ActiveModal::ConfirmDeleteRemote {
+            remote: "test".to_string(),
+            modal: ConfirmModal::new("Test", "Test message".to_string()),
+        });
+
+        let key = create_key_event(KeyCode::Char('y'));
+        Handler::handle_key(&mut app, key).await.unwrap();
+
+        assert!(app.confirm_modal().unwrap().is_confirmed());
+    }
+
+    #[tokio::test]
+    async fn test_confirm_modal_n_key() {
+        let client = create_test_client();
+        let mut app = App::new(client);
+        let mut modal = ConfirmModal::new("Test", "Test message".to_string());
+        modal.toggle();
+        app.modal = Some(ActiveModal::ConfirmDeleteRemote {
+            remote: "test".to_string(),
+            modal,
+        });
+
+        let key = create_key_event(KeyCode::Char('n'));
+        Handler::handle_key(&mut app, key).await.unwrap();
+
+        assert!(!app.confirm_modal().unwrap().is_confirmed());
+    }
+
+    #[tokio::test]
+    async fn test_open_modal_keys_ignored_while_confirm_open() {
+        // 'a' opens the create-remote modal only when no modal is open;
+        // with the confirmation active the key must not replace it.
+        let client = create_test_client();
+        let mut app = App::new(client);
+        app.focused_panel = crate::app::state::Panel::Remotes;
+        app.modal = Some(ActiveModal::ConfirmDeleteRemote {
+            remote: "test".to_string(),
+            modal: ConfirmModal::new("Test", "Test message".to_string()),
+        });
+
+        let key = create_key_event(KeyCode::Char('a'));
+        Handler::handle_key(&mut app, key).await.unwrap();
+
+        assert!(app.confirm_modal().is_some());
+        assert!(app.create_remote_modal().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_discarded_remote_is_deleted_quietly() {
+        let mut app = unreachable_app();
+        let err = crate::error::LazyFileError::RcloneApi {
+            endpoint: "config/delete",
+            message: "not found".to_string(),
+        };
+
+        Handler::apply_remote_deleted(&mut app, "half", true, Err(err));
+
+        assert_eq!(app.notifications.toasts().count(), 0);
+    }
+}
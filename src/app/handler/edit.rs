@@ -0,0 +1,292 @@
+//! Editing remote files in `$EDITOR` and opening them with the
+//! default application.
+
+use super::Handler;
+use crate::app::edit::{self, EditSession};
+use crate::app::state::{ActiveModal, App, Retry};
+use crate::error::Result;
+use crate::ui::ErrorModal;
+use std::io;
+use std::path::PathBuf;
+use std::process::ExitStatus;
+use tracing::{debug, info, warn};
+
+impl Handler {
+    /// Starts downloading the selected file for editing. Directories
+    /// are not edited.
+    pub(super) fn handle_edit_file(app: &mut App) {
+        if let Some((remote, path, name)) = selected_file(app) {
+            let requested = app.request_edit(remote, path);
+            Self::report_download(app, &name, requested);
+        }
+    }
+
+    /// Starts downloading the selected file and opening it with the
+    /// default application. Directories are not opened.
+    pub(super) fn handle_open_file(app: &mut App) {
+        if let Some((remote, path, name)) = selected_file(app) {
+            let requested = app.request_open(remote, path);
+            Self::report_download(app, &name, requested);
+        }
+    }
+
+    fn report_download(app: &mut App, name: &str, requested: io::Result<()>) {
+        match requested {
+            Ok(()) => app.notifications.info(format!("Downloading {}", name)),
+            Err(e) => {
+                warn!(error = %e, "could not prepare local copy");
+                app.notifications
+                    .error(format!("Could not prepare a local copy: {}", e));
+            }
+        }
+    }
+
+    /// Called by the launcher once the editor exits. Uploads the copy
+    /// if the editor succeeded and the content changed; otherwise the
+    /// copy is thrown away.
+    pub fn finish_edit(app: &mut App, session: EditSession, status: io::Result<ExitStatus>) {
+        let EditSession {
+            remote,
+            path,
+            local,
+            original,
+        } = session;
+        match status {
+            Ok(status) if status.success() => {}
+            Ok(status) => {
+                warn!(%status, "editor failed");
+                app.notifications.warning(format!(
+                    "Editor exited with {}; {} not uploaded",
+                    status, path
+                ));
+                edit::discard_copy(&local);
+                return;
+            }
+            Err(e) => {
+                warn!(error = %e, "could not start editor");
+                app.notifications
+                    .error(format!("Could not start editor: {}", e));
+                edit::discard_copy(&local);
+                return;
+            }
+        }
+        match std::fs::read(&local) {
+            Ok(contents) if contents == original => {
+                debug!(path = %path, "file unchanged");
+                app.notifications.info(format!("{} unchanged", path));
+                edit::discard_copy(&local);
+            }
+            Ok(contents) => {
+                app.notifications.info(format!("Uploading {}", path));
+                app.request_upload(remote, path, local, contents);
+            }
+            Err(e) => {
+                warn!(local = %local.display(), error = %e, "could not read edited copy");
+                app.notifications
+                    .error(format!("Could not read edited copy of {}: {}", path, e));
+            }
+        }
+    }
+
+    /// Uploads the copy at `local` again, as when retrying a failed
+    /// upload.
+    pub(super) fn upload_edit(app: &mut App, remote: String, path: String, local: PathBuf) {
+        match std::fs::read(&local) {
+            Ok(contents) => app.request_upload(remote, path, local, contents),
+            Err(e) => app
+                .notifications
+                .error(format!("Could not read edited copy of {}: {}", path, e)),
+        }
+    }
+
+    /// Applies a finished download: the launcher opens the editor
+    /// before the next frame.
+    pub(super) fn apply_edit_ready(app: &mut App, path: &str, result: Result<EditSession>) {
+        match result {
+            Ok(session) => app.pending_edit = Some(session),
+            Err(e) => {
+                warn!(path = %path, error = %e, "download for edit failed");
+                app.notifications
+                    .error(format!("Could not download {}: {}", path, e));
+            }
+        }
+    }
+
+    /// Reports whether a file reached the default application.
+    pub(super) fn apply_file_opened(app: &mut App, path: &str, result: Result<()>) {
+        match result {
+            Ok(()) => {
+                info!(path = %path, "opened with default application");
+                app.notifications.success(format!("Opened {}", path));
+            }
+            Err(e) => {
+                warn!(path = %path, error = %e, "could not open file");
+                app.notifications
+                    .error(format!("Could not open {}: {}", path, e));
+            }
+        }
+    }
+
+    /// Applies a finished upload. A failure keeps the local copy and
+    /// offers to retry, so the edits are not lost.
+    pub(super) fn apply_uploaded(
+        app: &mut App,
+        remote: String,
+        path: String,
+        local: PathBuf,
+        result: Result<()>,
+    ) {
+        match result {
+            Ok(()) => {
+                info!(remote = %remote, path = %path, "edit uploaded");
+                app.notifications.success(format!("Saved {}", path));
+                edit::discard_copy(&local);
+                if app.current_remote.as_deref() == Some(remote.as_str()) {
+                    app.reload_files();
+                }
+            }
+            Err(err) => {
+                warn!(remote = %remote, path = %path, error = %err, "upload failed");
+                app.notifications.warning(format!(
+                    "Edits kept in {} until lazyfile exits",
+                    local.display()
+                ));
+                if app.modal.is_some() {
+                    app.notifications.error(err.to_string());
+                } else {
+                    app.modal = Some(ActiveModal::Error {
+                        modal: ErrorModal::new(&err),
+                        retry: Box::new(Retry::Upload {
+                            remote,
+                            path,
+                            local,
+                        }),
+                    });
+                }
+            }
+        }
+    }
+}
+
+/// Returns the remote, path and name of the selected entry, unless it
+/// is a directory.
+fn selected_file(app: &App) -> Option<(String, String, String)> {
+    let remote = app.current_remote.clone()?;
+    let item = app.files.get(app.files_selected)?;
+    if item.is_dir() {
+        return None;
+    }
+    Some((remote, app.selected_path()?, item.name().to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::handler::test_support::{files_app, key};
+    use crossterm::event::KeyCode;
+
+    /// Writes `contents` to a scratch copy and returns a session whose
+    /// original content is `original`.
+    fn session(app: &mut App, original: &[u8], contents: &[u8]) -> EditSession {
+        let scratch = app
+            .scratch
+            .insert(edit::Scratch::create().expect("temp dir is writable"));
+        let local = scratch.file_for("docs/notes.md").unwrap();
+        std::fs::write(&local, contents).unwrap();
+        EditSession {
+            remote: "gdrive".to_string(),
+            path: "docs/notes.md".to_string(),
+            local,
+            original: original.to_vec(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_directories_are_not_edited() {
+        let mut app = files_app("docs", &["sub/", "notes.md"]);
+
+        Handler::handle_key(&mut app, key(KeyCode::Char('e')))
+            .await
+            .unwrap();
+
+        assert!(app.notifications.is_empty());
+        assert!(app.scratch.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_failed_download_is_a_toast() {
+        let mut app = files_app("docs", &["sub/", "notes.md"]);
+        app.files_selected = 1;
+
+        Handler::handle_key(&mut app, key(KeyCode::Char('e')))
+            .await
+            .unwrap();
+        let event = app.wait_event().await.unwrap();
+        Handler::handle_app_event(&mut app, event);
+
+        assert!(app.pending_edit.is_none());
+        let last = app.notifications.toasts().last().unwrap();
+        assert!(last.message.starts_with("Could not download docs/notes.md"));
+    }
+
+    #[tokio::test]
+    async fn test_failed_open_is_a_toast() {
+        let mut app = files_app("docs", &["sub/", "notes.md"]);
+        app.files_selected = 1;
+
+        Handler::handle_key(&mut app, key(KeyCode::Char('o')))
+            .await
+            .unwrap();
+        let event = app.wait_event().await.unwrap();
+        Handler::handle_app_event(&mut app, event);
+
+        let last = app.notifications.toasts().last().unwrap();
+        assert!(last.message.starts_with("Could not open docs/notes.md"));
+    }
+
+    #[tokio::test]
+    async fn test_unchanged_file_is_not_uploaded() {
+        let mut app = files_app("docs", &["sub/", "notes.md"]);
+        let session = session(&mut app, b"same", b"same");
+        let local = session.local.clone();
+
+        Handler::finish_edit(&mut app, session, Ok(ExitStatus::default()));
+
+        assert!(app.next_event().is_none());
+        assert!(!local.exists());
+    }
+
+    #[tokio::test]
+    async fn test_failed_upload_offers_retry_and_keeps_copy() {
+        let mut app = files_app("docs", &["sub/", "notes.md"]);
+        let session = session(&mut app, b"old", b"new");
+        let local = session.local.clone();
+
+        Handler::finish_edit(&mut app, session, Ok(ExitStatus::default()));
+        let event = app.wait_event().await.unwrap();
+        Handler::handle_app_event(&mut app, event);
+
+        match app.modal {
+            Some(ActiveModal::Error { ref retry, .. }) => {
+                assert!(
+                    matches!(**retry, Retry::Upload { ref path, .. } if path == "docs/notes.md")
+                );
+            }
+            ref other => panic!("expected error modal, got {:?}", other),
+        }
+        assert!(local.exists());
+    }
+
+    #[tokio::test]
+    async fn test_editor_that_fails_to_start_discards_copy() {
+        let mut app = files_app("docs", &["sub/", "notes.md"]);
+        let session = session(&mut app, b"old", b"new");
+        let local = session.local.clone();
+
+        let err = io::Error::from(io::ErrorKind::NotFound);
+        Handler::finish_edit(&mut app, session, Err(err));
+
+        assert!(app.next_event().is_none());
+        assert!(!local.exists());
+    }
+}
@@ -0,0 +1,212 @@
+//! Recovery from failed rclone requests.
+
+use super::Handler;
+use crate::app::state::{ActiveModal, App, Panel, Retry};
+use crate::error::Result;
+use crate::ui::{ErrorChoice, ErrorModal, ErrorWidget};
+use crossterm::event::{Event, KeyCode, KeyEventKind, MouseButton, MouseEventKind};
+use std::time::Instant;
+use tracing::{debug, warn};
+
+/// State an event may change before its request fails, restored so a
+/// failed operation leaves no half-applied navigation behind.
+struct Snapshot {
+    modal: Option<ActiveModal>,
+    current_remote: Option<String>,
+    current_path: String,
+    focused_panel: Panel,
+    last_click: Option<(Instant, u16, u16)>,
+}
+
+impl Snapshot {
+    fn capture(app: &App) -> Self {
+        Self {
+            modal: app.modal.clone(),
+            current_remote: app.current_remote.clone(),
+            current_path: app.current_path.clone(),
+            focused_panel: app.focused_panel,
+            last_click: app.last_click,
+        }
+    }
+
+    /// Puts the captured state back and returns the captured modal,
+    /// leaving no modal open.
+    fn restore(self, app: &mut App) -> Option<ActiveModal> {
+        app.current_remote = self.current_remote;
+        app.current_path = self.current_path;
+        app.focused_panel = self.focused_panel;
+        app.last_click = self.last_click;
+        app.modal = None;
+        self.modal
+    }
+}
+
+impl Handler {
+    /// Processes a terminal event and updates app state.
+    ///
+    /// A recoverable failure (see
+    /// [`LazyFileError::is_recoverable`](crate::error::LazyFileError::is_recoverable))
+    /// rolls back the navigation the event started and opens an error
+    /// modal instead of ending the session.
+    ///
+    /// # Errors
+    /// Returns only errors the session cannot continue after.
+    pub async fn handle_event(app: &mut App, event: Event) -> Result<()> {
+        let event = if matches!(app.modal, Some(ActiveModal::Error { .. })) {
+            match Self::handle_error_event(app, &event) {
+                Some(retry) => retry,
+                None => return Ok(()),
+            }
+        } else {
+            event
+        };
+
+        let snapshot = Snapshot::capture(app);
+        let result = match event {
+            Event::Key(key) => Self::handle_key(app, key).await,
+            Event::Mouse(mouse) => Self::handle_mouse(app, mouse).await,
+            Event::Paste(ref text) => Self::handle_paste(app, text).await,
+            _ => Ok(()),
+        };
+        match result {
+            Err(err) if err.is_recoverable() => {
+                warn!(error = %err, "operation failed");
+                let modal = snapshot.restore(app);
+                app.modal = Some(ActiveModal::Error {
+                    modal: ErrorModal::new(&err),
+                    retry: Box::new(Retry::Event { event, modal }),
+                });
+                Ok(())
+            }
+            result => result,
+        }
+    }
+
+    /// Handles input while the error modal is open. Returns the event
+    /// to replay when the user picks Retry on a failed event.
+    fn handle_error_event(app: &mut App, event: &Event) -> Option<Event> {
+        let Some(ActiveModal::Error { ref mut modal, .. }) = app.modal else {
+            return None;
+        };
+        let choice = match event {
+            Event::Key(key) if key.kind == KeyEventKind::Press => match key.code {
+                KeyCode::Tab | KeyCode::BackTab | KeyCode::Left | KeyCode::Right => {
+                    modal.toggle();
+                    None
+                }
+                KeyCode::Char('r') => Some(ErrorChoice::Retry),
+                KeyCode::Char('i' | 'q') | KeyCode::Esc => Some(ErrorChoice::Ignore),
+                KeyCode::Enter => Some(modal.selected),
+                _ => None,
+            },
+            Event::Mouse(mouse) if mouse.kind == MouseEventKind::Down(MouseButton::Left) => {
+                ErrorWidget::choice_at(app.screen, mouse.column, mouse.row)
+            }
+            _ => None,
+        }?;
+
+        debug!(choice = ?choice, "error modal answered");
+        let Some(ActiveModal::Error { retry, .. }) = app.modal.take() else {
+            return None;
+        };
+        match (choice, *retry) {
+            (ErrorChoice::Retry, Retry::Event { event, modal }) => {
+                app.modal = modal;
+                Some(event)
+            }
+            (ErrorChoice::Retry, Retry::ListFiles { remote, path }) => {
+                app.request_files(remote, path);
+                None
+            }
+            (
+                ErrorChoice::Retry,
+                Retry::Upload {
+                    remote,
+                    path,
+                    local,
+                },
+            ) => {
+                Self::upload_edit(app, remote, path, local);
+                None
+            }
+            (ErrorChoice::Ignore, _) => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::handler::test_support::{key, remotes_app};
+
+    #[tokio::test]
+    async fn test_failed_open_shows_error_and_keeps_view() {
+        let mut app = remotes_app(&["gdrive"]);
+
+        Handler::handle_event(&mut app, Event::Key(key(KeyCode::Enter)))
+            .await
+            .unwrap();
+        Handler::settle(&mut app).await;
+
+        assert!(matches!(app.modal, Some(ActiveModal::Error { .. })));
+        assert!(app.current_remote.is_none());
+        assert_eq!(app.focused_panel, Panel::Remotes);
+        assert!(app.running);
+    }
+
+    #[tokio::test]
+    async fn test_ignore_closes_error() {
+        let mut app = remotes_app(&["gdrive"]);
+        Handler::handle_event(&mut app, Event::Key(key(KeyCode::Enter)))
+            .await
+            .unwrap();
+        Handler::settle(&mut app).await;
+
+        Handler::handle_event(&mut app, Event::Key(key(KeyCode::Esc)))
+            .await
+            .unwrap();
+
+        assert!(app.modal.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_retry_lists_again() {
+        let mut app = remotes_app(&["gdrive"]);
+        Handler::handle_event(&mut app, Event::Key(key(KeyCode::Enter)))
+            .await
+            .unwrap();
+        Handler::settle(&mut app).await;
+
+        Handler::handle_event(&mut app, Event::Key(key(KeyCode::Char('r'))))
+            .await
+            .unwrap();
+        assert!(app.loading.is_some());
+        Handler::settle(&mut app).await;
+
+        // Still unreachable, so the repeated listing fails again.
+        assert!(matches!(app.modal, Some(ActiveModal::Error { .. })));
+        assert!(app.current_remote.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_retry_reopens_previous_modal() {
+        let mut app = remotes_app(&["gdrive"]);
+        let modal = ErrorModal::new(&crate::error::LazyFileError::InvalidInput {
+            field: "path",
+            reason: "test",
+        });
+        app.modal = Some(ActiveModal::Error {
+            modal,
+            retry: Box::new(Retry::Event {
+                event: Event::Key(key(KeyCode::Char('j'))),
+                modal: Some(ActiveModal::Help { scroll: 0 }),
+            }),
+        });
+
+        Handler::handle_event(&mut app, Event::Key(key(KeyCode::Enter)))
+            .await
+            .unwrap();
+
+        assert!(matches!(app.modal, Some(ActiveModal::Help { .. })));
+    }
+}
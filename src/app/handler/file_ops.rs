@@ -2,10 +2,12 @@
 
 use super::Handler;
 use crate::app::state::{ActiveModal, App};
+use crate::config::ConfirmStyle;
 use crate::error::Result;
 use crate::ui::FileOperationsModal;
 use crossterm::event::{KeyCode, KeyEvent};
-use tracing::{debug, info};
+use std::time::Duration;
+use tracing::{debug, warn};
 
 impl Handler {
     /// Opens the delete file/directory modal.
@@ -18,12 +20,14 @@ impl Handler {
                     "opening delete directory modal"
                 );
                 FileOperationsModal::delete_directory(file_name)
+                    .with_typed_confirm(app.confirm.purge == ConfirmStyle::Type)
             } else {
                 debug!(
                     file = %file_name,
                     "opening delete file modal"
                 );
                 FileOperationsModal::delete_file(file_name)
+                    .with_typed_confirm(app.confirm.delete == ConfirmStyle::Type)
             };
             app.modal = Some(ActiveModal::FileOperation(modal));
         }
@@ -66,7 +70,9 @@ impl Handler {
         }
     }
 
-    /// Handles keyboard input in file operations modal.
+    /// Handles keyboard input in file operations modal. Only `Esc`
+    /// works while the operation is in flight; it closes the modal
+    /// and the outcome arrives as a notification.
     pub(super) async fn handle_file_operations_key(app: &mut App, key: KeyEvent) -> Result<()> {
         if let Some(ActiveModal::FileOperation(ref mut modal)) = app.modal {
             match key.code {
@@ -74,15 +80,14 @@ impl Handler {
                     debug!("closing file operations modal");
                     app.modal = None;
                 }
+                _ if modal.waiting => {}
                 KeyCode::Char(c) if modal.needs_input() => {
                     modal.input_char(c);
                 }
                 KeyCode::Backspace if modal.needs_input() => {
                     modal.backspace();
                 }
-                KeyCode::Enter => {
-                    Self::handle_file_operations_submit(app).await?;
-                }
+                KeyCode::Enter => Self::handle_file_operations_submit(app),
                 _ => {}
             }
         }
@@ -90,83 +95,64 @@ impl Handler {
     }
 
     /// Handles file operations modal submission.
-    async fn handle_file_operations_submit(app: &mut App) -> Result<()> {
-        let Some(ActiveModal::FileOperation(modal)) = app.modal.take() else {
-            return Ok(());
+    fn handle_file_operations_submit(app: &mut App) {
+        let Some(ActiveModal::FileOperation(ref mut modal)) = app.modal else {
+            return;
         };
-
         if !modal.is_valid() {
-            app.modal = Some(ActiveModal::FileOperation(FileOperationsModal {
-                error: Some("Input is required".to_string()),
-                ..modal
-            }));
-            return Ok(());
+            modal.error = Some(modal.invalid_reason());
+            return;
         }
-
-        let Some(ref remote) = app.current_remote else {
-            return Ok(());
+        let Some(remote) = app.current_remote.clone() else {
+            return;
         };
-        let remote = remote.clone();
+        modal.waiting = true;
+        modal.error = None;
+        let modal = modal.clone();
+        app.request_file_operation(remote, &modal, app.conflict_policy);
+    }
 
-        let result = match modal.operation {
-            crate::ui::FileOperationType::DeleteFile => {
-                info!(file = %modal.file_name, "deleting file");
-                app.client.delete_file(&remote, &modal.file_name).await
-            }
-            crate::ui::FileOperationType::DeleteDirectory => {
-                info!(
-                    dir = %modal.file_name,
-                    "purging directory"
-                );
-                app.client.purge(&remote, &modal.file_name).await
+    /// Closes the modal and lists the directory again once the
+    /// operation succeeded; otherwise shows why it failed in the
+    /// modal, or as a toast if the modal was closed meanwhile.
+    pub(super) fn apply_file_operation(
+        app: &mut App,
+        message: String,
+        elapsed: Duration,
+        result: Result<()>,
+    ) {
+        let modal = match app.modal {
+            Some(ActiveModal::FileOperation(ref mut modal)) if modal.waiting => Some(modal),
+            _ => None,
+        };
+        match (result, modal) {
+            (Ok(()), modal) => {
+                if modal.is_some() {
+                    app.modal = None;
+                }
+                app.notify_if_slow(elapsed, "Operation finished", &message);
+                app.notifications.success(message);
+                app.reload_files();
             }
-            crate::ui::FileOperationType::Mkdir => {
-                let new_path = if modal.current_path == "/" {
-                    format!("/{}", modal.input)
-                } else {
-                    format!("{}/{}", modal.current_path, modal.input)
-                };
-                info!(path = %new_path, "creating directory");
-                app.client.mkdir(&remote, &new_path).await
+            (Err(e), Some(modal)) => {
+                warn!(error = %e, "file operation failed");
+                modal.waiting = false;
+                modal.error = Some(format!("Error: {}", e));
+                app.notify_if_slow(elapsed, "Operation failed", &e.to_string());
             }
-            crate::ui::FileOperationType::Copy => {
-                info!(
-                    src = %modal.file_name,
-                    dst = %modal.input,
-                    "copying file"
-                );
-                app.client
-                    .copy_file(&remote, &modal.file_name, &remote, &modal.input)
-                    .await
+            (Err(e), None) => {
+                warn!(error = %e, "file operation failed");
+                app.notify_if_slow(elapsed, "Operation failed", &e.to_string());
+                app.notifications.error(format!("Error: {}", e));
             }
-            crate::ui::FileOperationType::Move => {
-                info!(
-                    src = %modal.file_name,
-                    dst = %modal.input,
-                    "moving file"
-                );
-                app.client
-                    .move_file(&remote, &modal.file_name, &remote, &modal.input)
-                    .await
-            }
-        };
-
-        if let Err(e) = result {
-            app.modal = Some(ActiveModal::FileOperation(FileOperationsModal {
-                error: Some(format!("Error: {}", e)),
-                ..modal
-            }));
-            return Ok(());
         }
-
-        app.load_files().await?;
-        Ok(())
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::app::handler::test_support::{key, unreachable_app};
     use crate::app::state::Panel;
     use crate::rclone::{FileItem, RcloneClient};
     use crossterm::event::{KeyCode, KeyEvent, KeyEventKind, KeyEventState, KeyModifiers};
@@ -190,6 +176,7 @@ mod tests {
             size: if is_dir { 0 } else { 100 },
             mod_time: "2024-01-01T00:00:00Z".to_string(),
             is_dir,
+            ..Default::default()
         }
     }
 
@@ -202,7 +189,7 @@ mod tests {
         app.files_selected = 0;
         assert!(app.file_operations_modal().is_none());
 
-        let key = create_key_event(KeyCode::Char('x'));
+        let key = create_key_event(KeyCode::Char('D'));
         Handler::handle_key(&mut app, key).await.unwrap();
 
         assert!(app.file_operations_modal().is_some());
@@ -219,7 +206,7 @@ mod tests {
         app.files = vec![create_file_item("mydir", true)];
         app.files_selected = 0;
 
-        let key = create_key_event(KeyCode::Char('x'));
+        let key = create_key_event(KeyCode::Char('D'));
         Handler::handle_key(&mut app, key).await.unwrap();
 
         assert!(app.file_operations_modal().is_some());
@@ -231,6 +218,32 @@ mod tests {
         assert_eq!(modal.file_name, "mydir");
     }
 
+    #[tokio::test]
+    async fn test_typed_purge_needs_the_name() {
+        let mut app = unreachable_app();
+        app.focused_panel = Panel::Files;
+        app.current_remote = Some("nas".to_string());
+        app.files = vec![create_file_item("mydir", true)];
+        app.confirm.purge = ConfirmStyle::Type;
+
+        for code in [KeyCode::Char('D'), KeyCode::Char('m'), KeyCode::Enter] {
+            Handler::handle_key(&mut app, key(code)).await.unwrap();
+        }
+        let modal = app.file_operations_modal().unwrap();
+        assert!(!modal.waiting);
+        assert_eq!(modal.error.as_deref(), Some("Type 'mydir' to confirm"));
+
+        for c in "ydir".chars() {
+            Handler::handle_key(&mut app, key(KeyCode::Char(c)))
+                .await
+                .unwrap();
+        }
+        Handler::handle_key(&mut app, key(KeyCode::Enter))
+            .await
+            .unwrap();
+        assert!(app.file_operations_modal().unwrap().waiting);
+    }
+
     #[tokio::test]
     async fn test_delete_file_no_files() {
         let client = create_test_client();
@@ -238,7 +251,7 @@ mod tests {
         app.focused_panel = Panel::Files;
         app.files = vec![];
 
-        let key = create_key_event(KeyCode::Char('x'));
+        let key = create_key_event(KeyCode::Char('D'));
         Handler::handle_key(&mut app, key).await.unwrap();
 
         assert!(app.file_operations_modal().is_none());
@@ -251,7 +264,7 @@ mod tests {
         app.focused_panel = Panel::Remotes;
         app.files = vec![create_file_item("test.txt", false)];
 
-        let key = create_key_event(KeyCode::Char('x'));
+        let key = create_key_event(KeyCode::Char('D'));
         Handler::handle_key(&mut app, key).await.unwrap();
 
         assert!(app.file_operations_modal().is_none());
@@ -460,11 +473,42 @@ mod tests {
 
         let key = create_key_event(KeyCode::Enter);
         Handler::handle_key(&mut app, key).await.unwrap();
+        assert!(app.file_operations_modal().unwrap().waiting);
 
+        let event = app.wait_event().await.unwrap();
+        Handler::handle_app_event(&mut app, event);
         let modal = app.file_operations_modal().unwrap();
+        assert!(!modal.waiting);
         assert!(modal.error.as_deref().unwrap().contains("invalid path"));
     }
 
+    #[tokio::test]
+    async fn test_result_after_closing_modal_is_toast() {
+        let mut app = unreachable_app();
+        app.current_remote = Some("remote1".to_string());
+        let mut modal = FileOperationsModal::mkdir("/".to_string());
+        modal.input = "../evil".to_string();
+        app.modal = Some(ActiveModal::FileOperation(modal));
+
+        Handler::handle_key(&mut app, key(KeyCode::Enter))
+            .await
+            .unwrap();
+        Handler::handle_key(&mut app, key(KeyCode::Esc))
+            .await
+            .unwrap();
+        Handler::settle(&mut app).await;
+
+        assert!(app.modal.is_none());
+        assert!(
+            app.notifications
+                .toasts()
+                .last()
+                .unwrap()
+                .message
+                .contains("invalid path")
+        );
+    }
+
     #[tokio::test]
     async fn test_open_modal_keys_ignored_while_file_operation_open() {
         // 'd' opens the delete-remote confirmation only when no modal is
@@ -489,9 +533,9 @@ mod tests {
     async fn test_single_modal_slot_replaces_previous() {
         let client = create_test_client();
         let mut app = App::new(client);
-        app.modal = Some(ActiveModal::CreateRemote(
+        app.modal = Some(ActiveModal::CreateRemote(Box::new(
             crate::ui::CreateRemoteModal::new(crate::ui::CreateRemoteMode::Create),
-        ));
+        )));
 
         app.modal = Some(ActiveModal::FileOperation(FileOperationsModal::mkdir(
             "/".to_string(),
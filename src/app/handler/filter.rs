@@ -0,0 +1,92 @@
+//! The size and age filter on listings.
+
+use super::Handler;
+use crate::app::state::{ActiveModal, App};
+use crate::ui::FilterForm;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use tracing::{debug, info};
+
+impl Handler {
+    /// Opens the filter form with the current limits filled in.
+    pub(super) fn handle_filter(app: &mut App) {
+        debug!("opening filter form");
+        app.modal = Some(ActiveModal::Filter(FilterForm::new(&app.filter)));
+    }
+
+    /// Handles keyboard input in the filter form. Applying the limits
+    /// lists the current directory again with them.
+    pub(super) fn handle_filter_key(app: &mut App, key: KeyEvent) {
+        let Some(ActiveModal::Filter(ref mut form)) = app.modal else {
+            return;
+        };
+        match key.code {
+            KeyCode::Esc => {
+                debug!("closing filter form");
+                app.modal = None;
+            }
+            KeyCode::Char('x') if key.modifiers.contains(KeyModifiers::CONTROL) => form.clear(),
+            KeyCode::Tab | KeyCode::Down => form.step(1),
+            KeyCode::BackTab | KeyCode::Up => form.step(-1),
+            KeyCode::Char(c) if !key.modifiers.contains(KeyModifiers::CONTROL) => {
+                form.input_char(c);
+            }
+            KeyCode::Backspace => form.backspace(),
+            KeyCode::Enter => match form.filter() {
+                Ok(filter) => {
+                    app.modal = None;
+                    if filter == app.filter {
+                        return;
+                    }
+                    if filter.is_empty() {
+                        info!("filter cleared");
+                        app.notifications.info("Filter cleared");
+                    } else {
+                        info!(filter = %filter.describe(), "filter set");
+                        app.notifications
+                            .info(format!("Filtering: {}", filter.describe()));
+                    }
+                    app.filter = filter;
+                    app.reload_files();
+                }
+                Err(e) => form.error = Some(e.to_string()),
+            },
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::handler::test_support::{key, unreachable_app};
+    use crate::app::state::Panel;
+    use crate::rclone::Filter;
+
+    async fn press(app: &mut App, code: KeyCode) {
+        Handler::handle_key(app, key(code)).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_sets_filter_and_relists() {
+        let mut app = unreachable_app();
+        app.focused_panel = Panel::Files;
+        app.current_remote = Some("gdrive".to_string());
+
+        press(&mut app, KeyCode::Char('f')).await;
+        for c in "1x".chars() {
+            press(&mut app, KeyCode::Char(c)).await;
+        }
+        press(&mut app, KeyCode::Enter).await;
+        let Some(ActiveModal::Filter(ref form)) = app.modal else {
+            panic!("filter form closed");
+        };
+        assert!(form.error.is_some());
+
+        press(&mut app, KeyCode::Backspace).await;
+        press(&mut app, KeyCode::Char('M')).await;
+        press(&mut app, KeyCode::Enter).await;
+        assert!(app.modal.is_none());
+        assert_eq!(app.filter, Filter::new("1M", "", "", "").unwrap());
+        assert!(app.loading.is_some());
+    }
+}
@@ -0,0 +1,183 @@
+//! Jumping straight to a typed or pasted `remote:path`.
+
+use super::Handler;
+use crate::app::command::rclone_path;
+use crate::app::state::{ActiveModal, App};
+use crate::error::Result;
+use crate::rclone::FileItem;
+use crate::ui::GoToPrompt;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use tracing::{debug, info, warn};
+
+impl Handler {
+    /// Opens the go-to prompt, filled in with the location on screen.
+    pub(super) fn handle_go_to(app: &mut App) {
+        let location = app
+            .current_remote
+            .as_deref()
+            .map(|remote| rclone_path(remote, &app.current_path))
+            .unwrap_or_default();
+        debug!("opening go-to prompt");
+        app.modal = Some(ActiveModal::GoTo(GoToPrompt::new(location)));
+    }
+
+    /// Handles keyboard input in the go-to prompt. Only `Esc` works
+    /// while the location is looked up.
+    pub(super) fn handle_go_to_key(app: &mut App, key: KeyEvent) {
+        let Some(ActiveModal::GoTo(ref mut prompt)) = app.modal else {
+            return;
+        };
+        match key.code {
+            KeyCode::Esc => {
+                debug!("closing go-to prompt");
+                app.modal = None;
+            }
+            _ if prompt.waiting => {}
+            KeyCode::Char('u') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                prompt.input.clear();
+                prompt.error = None;
+            }
+            KeyCode::Char(c) if !key.modifiers.contains(KeyModifiers::CONTROL) => {
+                prompt.input_char(c);
+            }
+            KeyCode::Backspace => prompt.backspace(),
+            KeyCode::Enter => match prompt.target(&app.remotes) {
+                Ok((remote, path)) => {
+                    prompt.waiting = true;
+                    prompt.error = None;
+                    app.request_go_to(remote, path);
+                }
+                Err(reason) => prompt.error = Some(reason),
+            },
+            _ => {}
+        }
+    }
+
+    /// Opens a directory that was found, or the directory holding a
+    /// file with the file selected. A missing location or a failed
+    /// lookup leaves the prompt open with the reason.
+    pub(super) fn apply_go_to(
+        app: &mut App,
+        remote: String,
+        path: String,
+        result: Result<Option<FileItem>>,
+    ) {
+        let Some(ActiveModal::GoTo(ref mut prompt)) = app.modal else {
+            debug!("go-to prompt closed before the lookup finished");
+            return;
+        };
+        prompt.waiting = false;
+        let item = match result {
+            Ok(Some(item)) => item,
+            Ok(None) => {
+                prompt.error = Some(format!(
+                    "Nothing at {}; check the path's spelling and case",
+                    rclone_path(&remote, &path)
+                ));
+                return;
+            }
+            Err(e) => {
+                warn!(remote = %remote, path = %path, error = %e, "go-to lookup failed");
+                prompt.error = Some(format!("Could not look it up: {}", e));
+                return;
+            }
+        };
+
+        app.modal = None;
+        if let Some(index) = app.remotes.iter().position(|name| *name == remote) {
+            app.remotes_selected = index;
+        }
+        info!(remote = %remote, path = %path, "going to location");
+        if item.is_dir() {
+            app.request_files(remote, path);
+        } else {
+            let (parent, name) = path.rsplit_once('/').unwrap_or(("", path.as_str()));
+            let name = name.to_string();
+            app.request_files(remote, parent.to_string());
+            app.select_on_load = Some(name);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::event::AppEvent;
+    use crate::app::handler::test_support::{key, remotes_app};
+
+    fn open_prompt(app: &mut App, input: &str) {
+        app.modal = Some(ActiveModal::GoTo(GoToPrompt {
+            input: input.to_string(),
+            waiting: true,
+            error: None,
+        }));
+    }
+
+    #[tokio::test]
+    async fn test_unknown_remote_is_rejected() {
+        let mut app = remotes_app(&["gdrive", "s3"]);
+        for code in [KeyCode::Char('g'), KeyCode::Char('p')] {
+            Handler::handle_key(&mut app, key(code)).await.unwrap();
+        }
+        for c in "b2:photos".chars() {
+            Handler::handle_key(&mut app, key(KeyCode::Char(c)))
+                .await
+                .unwrap();
+        }
+        Handler::handle_key(&mut app, key(KeyCode::Enter))
+            .await
+            .unwrap();
+
+        let Some(ActiveModal::GoTo(ref prompt)) = app.modal else {
+            panic!("go-to prompt closed");
+        };
+        assert!(!prompt.waiting);
+        assert_eq!(prompt.error.as_deref(), Some("No remote named 'b2'"));
+    }
+
+    #[tokio::test]
+    async fn test_file_opens_parent_with_it_selected() {
+        let mut app = remotes_app(&["gdrive", "s3"]);
+        open_prompt(&mut app, "s3:docs/a.txt");
+        let file = FileItem {
+            name: "a.txt".to_string(),
+            ..FileItem::default()
+        };
+        Handler::handle_app_event(
+            &mut app,
+            AppEvent::GoToResolved {
+                remote: "s3".to_string(),
+                path: "docs/a.txt".to_string(),
+                result: Ok(Some(file)),
+            },
+        );
+        assert!(app.modal.is_none());
+        assert_eq!(app.remotes_selected, 1);
+        assert_eq!(app.loading.as_ref().unwrap().path, "docs");
+        assert_eq!(app.select_on_load.as_deref(), Some("a.txt"));
+    }
+
+    #[tokio::test]
+    async fn test_missing_path_keeps_prompt_open() {
+        let mut app = remotes_app(&["gdrive", "s3"]);
+        open_prompt(&mut app, "s3:nope");
+        Handler::handle_app_event(
+            &mut app,
+            AppEvent::GoToResolved {
+                remote: "s3".to_string(),
+                path: "nope".to_string(),
+                result: Ok(None),
+            },
+        );
+        let Some(ActiveModal::GoTo(ref prompt)) = app.modal else {
+            panic!("go-to prompt closed");
+        };
+        assert!(
+            prompt
+                .error
+                .as_deref()
+                .unwrap()
+                .starts_with("Nothing at s3:nope")
+        );
+    }
+}
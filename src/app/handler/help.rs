@@ -0,0 +1,117 @@
+//! Help overlay input handling.
+
+use super::Handler;
+use crate::app::state::{ActiveModal, App};
+use crate::ui::HelpOverlayWidget;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use tracing::debug;
+
+impl Handler {
+    /// Handles keys while the help overlay is open: vim-style
+    /// scrolling, and `Esc`, `q`, `?` or `Enter` to close.
+    pub(super) fn handle_help_key(app: &mut App, key: KeyEvent) {
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+        let page = usize::from(app.screen.height.saturating_sub(2) / 2).max(1);
+        match key.code {
+            KeyCode::Esc | KeyCode::Enter | KeyCode::Char('q' | '?') => {
+                debug!("closing help");
+                app.modal = None;
+            }
+            KeyCode::Char('d') if ctrl => Self::scroll_help(app, |s| s.saturating_add(page)),
+            KeyCode::Char('u') if ctrl => Self::scroll_help(app, |s| s.saturating_sub(page)),
+            KeyCode::Char('j') | KeyCode::Down => Self::scroll_help(app, |s| s.saturating_add(1)),
+            KeyCode::Char('k') | KeyCode::Up => Self::scroll_help(app, |s| s.saturating_sub(1)),
+            KeyCode::PageDown => Self::scroll_help(app, |s| s.saturating_add(page)),
+            KeyCode::PageUp => Self::scroll_help(app, |s| s.saturating_sub(page)),
+            KeyCode::Char('g') | KeyCode::Home => Self::scroll_help(app, |_| 0),
+            KeyCode::Char('G') | KeyCode::End => Self::scroll_help(app, |_| usize::MAX),
+            _ => {}
+        }
+    }
+
+    /// Applies `to` to the help overlay's scroll offset, clamped so
+    /// the last line never scrolls above the bottom of the screen.
+    pub(super) fn scroll_help(app: &mut App, to: impl FnOnce(usize) -> usize) {
+        let max = HelpOverlayWidget::max_scroll(&app.keymap, app.screen);
+        if let Some(ActiveModal::Help { ref mut scroll }) = app.modal {
+            *scroll = to(*scroll).min(max);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::handler::test_support::{key, unreachable_app};
+    use ratatui::layout::Rect;
+
+    fn open_help(height: u16) -> App {
+        let mut app = unreachable_app();
+        app.set_screen(Rect::new(0, 0, 80, height));
+        app.modal = Some(ActiveModal::Help { scroll: 0 });
+        app
+    }
+
+    fn scroll(app: &App) -> usize {
+        match app.modal {
+            Some(ActiveModal::Help { scroll }) => scroll,
+            _ => panic!("help should be open"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_scrolls_down_and_up() {
+        let mut app = open_help(10);
+
+        Handler::handle_key(&mut app, key(KeyCode::Char('j')))
+            .await
+            .unwrap();
+        assert_eq!(scroll(&app), 1);
+
+        Handler::handle_key(&mut app, key(KeyCode::Char('k')))
+            .await
+            .unwrap();
+        assert_eq!(scroll(&app), 0);
+    }
+
+    #[tokio::test]
+    async fn test_scroll_is_clamped_to_content() {
+        let mut app = open_help(10);
+        let max = HelpOverlayWidget::max_scroll(&app.keymap, app.screen);
+
+        Handler::handle_key(&mut app, key(KeyCode::Char('G')))
+            .await
+            .unwrap();
+        assert_eq!(scroll(&app), max);
+
+        Handler::handle_key(&mut app, key(KeyCode::Char('j')))
+            .await
+            .unwrap();
+        assert_eq!(scroll(&app), max);
+
+        Handler::handle_key(&mut app, key(KeyCode::Char('g')))
+            .await
+            .unwrap();
+        assert_eq!(scroll(&app), 0);
+    }
+
+    #[tokio::test]
+    async fn test_no_scroll_when_everything_fits() {
+        let mut app = open_help(500);
+
+        Handler::handle_key(&mut app, key(KeyCode::Char('j')))
+            .await
+            .unwrap();
+        assert_eq!(scroll(&app), 0);
+    }
+
+    #[tokio::test]
+    async fn test_question_mark_closes() {
+        let mut app = open_help(10);
+
+        Handler::handle_key(&mut app, key(KeyCode::Char('?')))
+            .await
+            .unwrap();
+        assert!(app.modal.is_none());
+    }
+}
@@ -0,0 +1,105 @@
+//! Transfer history view handling: reading the history file and
+//! browsing it.
+
+use super::Handler;
+use crate::app::state::{ActiveModal, App};
+use crate::config::TransferRecord;
+use crate::error::Result;
+use crate::ui::HistoryView;
+use crossterm::event::{KeyCode, KeyEvent};
+use tracing::{debug, warn};
+
+impl Handler {
+    /// Opens the transfer history view.
+    pub(super) fn handle_show_history(app: &mut App) {
+        if app.history.is_none() {
+            app.notifications
+                .warning("No transfer history: there is nowhere to keep one on this system");
+            return;
+        }
+        debug!("opening transfer history");
+        let records = Self::load_history(app);
+        app.modal = Some(ActiveModal::History(Box::new(HistoryView::new(records))));
+    }
+
+    /// Handles keys while the transfer history view is open.
+    pub(super) fn handle_history_key(app: &mut App, key: KeyEvent) {
+        let Some(ActiveModal::History(ref mut view)) = app.modal else {
+            return;
+        };
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => {
+                debug!("closing transfer history");
+                app.modal = None;
+            }
+            KeyCode::Char('j') | KeyCode::Down => view.select_next(),
+            KeyCode::Char('k') | KeyCode::Up => view.select_prev(),
+            KeyCode::Char('f') => view.toggle_failed_only(),
+            KeyCode::Char('r') => {
+                let records = Self::load_history(app);
+                if let Some(ActiveModal::History(ref mut view)) = app.modal {
+                    view.set_records(records);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Reads the history file, newest record first.
+    fn load_history(app: &App) -> Result<Vec<TransferRecord>> {
+        let records = app.history.as_ref().map_or(Ok(Vec::new()), |h| h.load());
+        if let Err(ref e) = records {
+            warn!(error = %e, "reading transfer history failed");
+        }
+        records
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::handler::test_support::{key, unreachable_app};
+    use crate::config::{History, TransferKind};
+
+    #[tokio::test]
+    async fn test_gh_shows_recorded_transfers() {
+        let mut app = unreachable_app();
+        for c in ['g', 'h'] {
+            Handler::handle_key(&mut app, key(KeyCode::Char(c)))
+                .await
+                .unwrap();
+        }
+        assert!(app.modal.is_none());
+
+        let dir = std::env::temp_dir().join(format!("lazyfile-gh-{}", std::process::id()));
+        let history = History::new(dir.join("history.jsonl"));
+        history
+            .append(&TransferRecord {
+                finished: 1,
+                kind: TransferKind::Copy,
+                source: "gdrive:a.txt".to_string(),
+                destination: "s3:a.txt".to_string(),
+                bytes: Some(12),
+                seconds: 0.2,
+                error: None,
+            })
+            .unwrap();
+        app.history = Some(history);
+        for c in ['g', 'h'] {
+            Handler::handle_key(&mut app, key(KeyCode::Char(c)))
+                .await
+                .unwrap();
+        }
+        let Some(ActiveModal::History(ref view)) = app.modal else {
+            panic!("history view not open");
+        };
+        assert_eq!(view.records.len(), 1);
+        assert_eq!(view.selected_record().unwrap().source, "gdrive:a.txt");
+
+        Handler::handle_key(&mut app, key(KeyCode::Esc))
+            .await
+            .unwrap();
+        assert!(app.modal.is_none());
+        let _ = std::fs::remove_dir_all(dir);
+    }
+}
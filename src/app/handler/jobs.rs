@@ -0,0 +1,223 @@
+//! Jobs view handling: listing the daemon's jobs and stopping them.
+//! While anything runs the view lists them again every
+//! [`JOBS_REFRESH`], so transfer progress stays live.
+
+use super::Handler;
+use crate::app::state::{ActiveModal, App};
+use crate::error::Result;
+use crate::rclone::{Job, JobState, Transfer};
+use crate::ui::JobsView;
+use crossterm::event::{KeyCode, KeyEvent};
+use std::time::{Duration, Instant};
+use tracing::{debug, info, warn};
+
+/// Time between listings while a job runs or a file is in flight.
+pub const JOBS_REFRESH: Duration = Duration::from_secs(1);
+
+impl Handler {
+    /// Opens the jobs view and starts listing the jobs.
+    pub(super) fn handle_show_jobs(app: &mut App) {
+        debug!("opening jobs");
+        app.modal = Some(ActiveModal::Jobs(Box::new(JobsView::new())));
+        app.request_jobs();
+    }
+
+    /// Handles keys while the jobs view is open. `J` closes it again,
+    /// like `Esc` and `q`.
+    pub(super) fn handle_jobs_key(app: &mut App, key: KeyEvent) {
+        let Some(ActiveModal::Jobs(ref mut view)) = app.modal else {
+            return;
+        };
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q' | 'J') => {
+                debug!("closing jobs");
+                app.modal = None;
+            }
+            KeyCode::Char('j') | KeyCode::Down => view.select_next(),
+            KeyCode::Char('k') | KeyCode::Up => view.select_prev(),
+            KeyCode::Char('e') => view.show_error = !view.show_error,
+            KeyCode::Char('r') => {
+                view.loading = true;
+                app.request_jobs();
+            }
+            KeyCode::Char('s') => match view.selected_job() {
+                Some(job) if job.state() == JobState::Running => {
+                    info!(id = job.id, "stopping job");
+                    let id = job.id;
+                    app.request_stop_job(id);
+                }
+                Some(job) => {
+                    let message = format!("Job {} has already finished", job.id);
+                    app.notifications.info(message);
+                }
+                None => {}
+            },
+            _ => {}
+        }
+    }
+
+    /// Shows a finished listing if the jobs view is still open.
+    pub(super) fn apply_jobs(app: &mut App, result: Result<Vec<Job>>) {
+        if let Err(ref e) = result {
+            warn!(error = %e, "listing jobs failed");
+        }
+        if let Some(ActiveModal::Jobs(ref mut view)) = app.modal {
+            view.set_jobs(result);
+            view.next_refresh = view.is_busy().then(|| Instant::now() + JOBS_REFRESH);
+        }
+    }
+
+    /// Shows the files in flight if the jobs view is still open.
+    pub(super) fn apply_transfers(app: &mut App, result: Result<Vec<Transfer>>) {
+        if let Err(ref e) = result {
+            debug!(error = %e, "fetching transfer stats failed");
+        }
+        if let Some(ActiveModal::Jobs(ref mut view)) = app.modal {
+            view.set_transfers(result);
+        }
+    }
+
+    /// Reports a stopped job and lists the jobs again.
+    pub(super) fn apply_job_stopped(app: &mut App, id: i64, result: Result<()>) {
+        match result {
+            Ok(()) => {
+                app.notifications.success(format!("Stopped job {}", id));
+                if matches!(app.modal, Some(ActiveModal::Jobs(_))) {
+                    app.request_jobs();
+                }
+            }
+            Err(e) => {
+                warn!(id, error = %e, "stopping job failed");
+                app.notifications
+                    .error(format!("Could not stop job {}: {}", id, e));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::event::AppEvent;
+    use crate::app::handler::test_support::{key, unreachable_app};
+
+    fn job(id: i64, finished: bool) -> Job {
+        Job {
+            id,
+            finished,
+            ..Job::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_j_toggles_jobs_view() {
+        let mut app = unreachable_app();
+        Handler::handle_key(&mut app, key(KeyCode::Char('J')))
+            .await
+            .unwrap();
+        assert!(matches!(app.modal, Some(ActiveModal::Jobs(_))));
+
+        for _ in 0..2 {
+            let event = app.wait_event().await.unwrap();
+            assert!(matches!(
+                event,
+                AppEvent::JobsLoaded(Err(_)) | AppEvent::TransfersLoaded(Err(_))
+            ));
+            Handler::handle_app_event(&mut app, event);
+        }
+        let Some(ActiveModal::Jobs(ref view)) = app.modal else {
+            panic!("jobs view closed");
+        };
+        assert!(view.error.is_some());
+        assert!(view.next_refresh.is_none());
+
+        Handler::handle_key(&mut app, key(KeyCode::Char('J')))
+            .await
+            .unwrap();
+        assert!(app.modal.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_stop_runs_only_for_running_jobs() {
+        let mut app = unreachable_app();
+        let mut view = JobsView::new();
+        view.set_jobs(Ok(vec![job(1, true), job(2, false)]));
+        app.modal = Some(ActiveModal::Jobs(Box::new(view)));
+
+        Handler::handle_key(&mut app, key(KeyCode::Char('s')))
+            .await
+            .unwrap();
+        assert!(
+            app.notifications
+                .toasts()
+                .last()
+                .unwrap()
+                .message
+                .contains("already finished")
+        );
+
+        Handler::handle_key(&mut app, key(KeyCode::Down))
+            .await
+            .unwrap();
+        Handler::handle_key(&mut app, key(KeyCode::Char('s')))
+            .await
+            .unwrap();
+        let event = app.wait_event().await.unwrap();
+        assert!(matches!(event, AppEvent::JobStopped { id: 2, .. }));
+        Handler::handle_app_event(&mut app, event);
+        assert!(
+            app.notifications
+                .toasts()
+                .last()
+                .unwrap()
+                .message
+                .contains("Could not stop job 2")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_refreshes_while_a_job_runs() {
+        let mut app = unreachable_app();
+        app.modal = Some(ActiveModal::Jobs(Box::new(JobsView::new())));
+        Handler::handle_app_event(&mut app, AppEvent::JobsLoaded(Ok(vec![job(1, false)])));
+        let Some(ActiveModal::Jobs(ref view)) = app.modal else {
+            panic!("jobs view closed");
+        };
+        let due = view.next_refresh.expect("refresh scheduled");
+
+        app.tick_jobs(due - JOBS_REFRESH);
+        assert_eq!(app.workers.busy(), 0);
+        app.tick_jobs(due);
+        let Some(ActiveModal::Jobs(ref view)) = app.modal else {
+            panic!("jobs view closed");
+        };
+        assert!(view.loading);
+        assert!(view.next_refresh.is_none());
+        let event = app.wait_event().await.unwrap();
+        assert!(matches!(
+            event,
+            AppEvent::JobsLoaded(_) | AppEvent::TransfersLoaded(_)
+        ));
+
+        Handler::handle_app_event(&mut app, AppEvent::JobsLoaded(Ok(vec![job(1, true)])));
+        let Some(ActiveModal::Jobs(ref view)) = app.modal else {
+            panic!("jobs view closed");
+        };
+        assert!(view.next_refresh.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_e_switches_to_error() {
+        let mut app = unreachable_app();
+        app.modal = Some(ActiveModal::Jobs(Box::default()));
+
+        Handler::handle_key(&mut app, key(KeyCode::Char('e')))
+            .await
+            .unwrap();
+
+        let Some(ActiveModal::Jobs(ref view)) = app.modal else {
+            panic!("jobs view closed");
+        };
+        assert!(view.show_error);
+    }
+}
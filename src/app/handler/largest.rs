@@ -0,0 +1,233 @@
+//! Largest files view handling: scanning the current directory for
+//! its biggest files, and deleting, moving or going to one of them.
+
+use super::Handler;
+use crate::app::command::rclone_path;
+use crate::app::state::{ActiveModal, App};
+use crate::error::Result;
+use crate::rclone::FileItem;
+use crate::ui::{FileOperationsModal, LargestPrompt, LargestView};
+use crossterm::event::{KeyCode, KeyEvent};
+use tracing::{debug, info, warn};
+
+impl Handler {
+    /// Opens the largest files view for the current directory and
+    /// starts listing every file below it.
+    pub(super) fn handle_show_largest(app: &mut App) {
+        let Some(remote) = app.current_remote.clone() else {
+            return;
+        };
+        let path = app.current_path.clone();
+        debug!(remote = %remote, path = %path, "opening largest files");
+        let mut view = LargestView::new(remote.clone(), path.clone());
+        view.cancel = Some(app.request_largest(remote, path));
+        app.modal = Some(ActiveModal::Largest(Box::new(view)));
+    }
+
+    /// Handles keys while the largest files view is open: `j`/`k`
+    /// select a file, `Enter` goes to it, `D` deletes it after a `y`,
+    /// `m` moves it to a typed path, `r` scans again, and `Esc`, `q`
+    /// or `W` close. One file is changed at a time.
+    pub(super) fn handle_largest_key(app: &mut App, key: KeyEvent) {
+        let Some(ActiveModal::Largest(ref mut view)) = app.modal else {
+            return;
+        };
+        match view.prompt {
+            Some(LargestPrompt::Delete) => {
+                view.prompt = None;
+                if key.code == KeyCode::Char('y') {
+                    Self::start_largest_delete(app);
+                }
+                return;
+            }
+            Some(LargestPrompt::Move(_)) => {
+                match key.code {
+                    KeyCode::Esc => view.prompt = None,
+                    KeyCode::Enter => Self::start_largest_move(app),
+                    KeyCode::Backspace => view.backspace(),
+                    KeyCode::Char(c) => view.input_char(c),
+                    _ => {}
+                }
+                return;
+            }
+            None => {}
+        }
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q' | 'W') => {
+                debug!("closing largest files");
+                if let Some(cancel) = view.cancel.take() {
+                    cancel.cancel();
+                }
+                app.modal = None;
+            }
+            KeyCode::Char('j') | KeyCode::Down => view.select_next(),
+            KeyCode::Char('k') | KeyCode::Up => view.select_prev(),
+            KeyCode::Enter => Self::go_to_largest(app),
+            KeyCode::Char('r') if view.files.is_some() => {
+                view.files = None;
+                let (remote, path) = (view.remote.clone(), view.root.clone());
+                let cancel = app.request_largest(remote, path);
+                if let Some(ActiveModal::Largest(ref mut view)) = app.modal {
+                    view.cancel = Some(cancel);
+                }
+            }
+            _ if view.busy.is_some() => {}
+            KeyCode::Char('D') if view.selected_file().is_some() => {
+                view.prompt = Some(LargestPrompt::Delete);
+            }
+            KeyCode::Char('m') => {
+                if let Some(item) = view.selected_file() {
+                    view.prompt = Some(LargestPrompt::Move(view.path_of(item)));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Closes the view and lists the directory holding the
+    /// highlighted file, selecting it there.
+    fn go_to_largest(app: &mut App) {
+        let Some(ActiveModal::Largest(ref view)) = app.modal else {
+            return;
+        };
+        let Some(path) = view.selected_file().map(|item| view.path_of(item)) else {
+            return;
+        };
+        let remote = view.remote.clone();
+        if let Some(cancel) = view.cancel.clone() {
+            cancel.cancel();
+        }
+        app.modal = None;
+        info!(remote = %remote, path = %path, "going to large file");
+        let (parent, name) = path.rsplit_once('/').unwrap_or(("", path.as_str()));
+        let name = name.to_string();
+        app.request_files(remote, parent.to_string());
+        app.select_on_load = Some(name);
+    }
+
+    /// Deletes the highlighted file, like the delete modal would.
+    fn start_largest_delete(app: &mut App) {
+        let Some(ActiveModal::Largest(ref mut view)) = app.modal else {
+            return;
+        };
+        let Some(path) = view.selected_file().map(|item| view.path_of(item)) else {
+            return;
+        };
+        view.busy = Some(path.clone());
+        let remote = view.remote.clone();
+        let modal = FileOperationsModal::delete_file(path);
+        app.request_file_operation(remote, &modal, app.conflict_policy);
+    }
+
+    /// Moves the highlighted file to the typed path, like the move
+    /// modal would. An empty path or the file's own is ignored.
+    fn start_largest_move(app: &mut App) {
+        let Some(ActiveModal::Largest(ref mut view)) = app.modal else {
+            return;
+        };
+        let Some(LargestPrompt::Move(dst)) = view.prompt.take() else {
+            return;
+        };
+        let Some(path) = view.selected_file().map(|item| view.path_of(item)) else {
+            return;
+        };
+        let dst = dst.trim().trim_start_matches('/').to_string();
+        if dst.is_empty() || dst == path {
+            return;
+        }
+        view.busy = Some(path.clone());
+        let remote = view.remote.clone();
+        let mut modal = FileOperationsModal::move_file(path, view.root.clone());
+        modal.input = dst;
+        app.request_file_operation(remote, &modal, app.conflict_policy);
+    }
+
+    /// Shows a finished scan if the largest files view is still open
+    /// on the directory that was scanned.
+    pub(super) fn apply_largest(
+        app: &mut App,
+        remote: &str,
+        path: &str,
+        result: Result<Vec<FileItem>>,
+    ) {
+        let result = result.map_err(|e| {
+            warn!(remote, path, error = %e, "scanning for largest files failed");
+            format!("Could not list files: {}", e)
+        });
+        if let Some(ActiveModal::Largest(ref mut view)) = app.modal
+            && view.remote == remote
+            && view.root == path
+        {
+            view.set_files(result);
+        }
+    }
+
+    /// Drops a file deleted or moved from the view from its list once
+    /// that succeeded. `target` is the file, as `remote:path`.
+    pub(super) fn apply_largest_operation(app: &mut App, target: &str, ok: bool) {
+        let Some(ActiveModal::Largest(ref mut view)) = app.modal else {
+            return;
+        };
+        let Some(path) = view.busy.take() else {
+            return;
+        };
+        if rclone_path(&view.remote, &path) != target {
+            view.busy = Some(path);
+            return;
+        }
+        if ok {
+            view.remove(&path);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::event::AppEvent;
+    use crate::app::handler::test_support::{key, unreachable_app};
+    use crate::app::state::Panel;
+
+    fn file(name: &str, size: i64) -> FileItem {
+        FileItem {
+            name: name.to_string(),
+            size,
+            ..FileItem::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_largest_files_are_deleted_from_the_list() {
+        let mut app = unreachable_app();
+        app.focused_panel = Panel::Files;
+        app.current_remote = Some("s3".to_string());
+        app.current_path = "backup".to_string();
+        Handler::handle_key(&mut app, key(KeyCode::Char('W')))
+            .await
+            .unwrap();
+        let event = app.wait_event().await.unwrap();
+        assert!(matches!(
+            event,
+            AppEvent::LargestLoaded { ref path, .. } if path == "backup"
+        ));
+
+        let files = vec![file("a.bin", 10), file("2024/b.iso", 900)];
+        Handler::apply_largest(&mut app, "s3", "backup", Ok(files));
+        for c in ['D', 'y'] {
+            Handler::handle_key(&mut app, key(KeyCode::Char(c)))
+                .await
+                .unwrap();
+        }
+        let Some(ActiveModal::Largest(ref view)) = app.modal else {
+            panic!("largest files closed");
+        };
+        assert_eq!(view.busy.as_deref(), Some("backup/2024/b.iso"));
+
+        Handler::apply_largest_operation(&mut app, "s3:backup/2024/b.iso", true);
+        let Some(ActiveModal::Largest(ref view)) = app.modal else {
+            panic!("largest files closed");
+        };
+        assert!(view.busy.is_none());
+        assert_eq!(view.selected_file().unwrap().name(), "a.bin");
+    }
+}
@@ -0,0 +1,142 @@
+//! Marking the entries whose names match a glob, and narrowing the
+//! recursive listing to the files one matches.
+
+use super::Handler;
+use crate::app::state::{ActiveModal, App};
+use crate::ui::GlobPrompt;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use tracing::debug;
+
+impl Handler {
+    /// Opens the glob prompt for the current directory.
+    pub(super) fn handle_mark_glob(app: &mut App) {
+        if app.current_remote.is_none() || app.files.is_empty() {
+            return;
+        }
+        let names = app
+            .files
+            .iter()
+            .map(|item| item.name().to_string())
+            .collect();
+        debug!("opening glob prompt");
+        app.modal = Some(ActiveModal::MarkGlob(GlobPrompt::new(names)));
+    }
+
+    /// Opens the glob prompt for narrowing the recursive listing.
+    pub(super) fn handle_narrow_listing(app: &mut App) {
+        let Some(ref flat) = app.flat else {
+            app.notifications
+                .info("Only the recursive listing can be narrowed");
+            return;
+        };
+        let paths = flat
+            .all()
+            .iter()
+            .map(|item| item.name().to_string())
+            .collect();
+        debug!("opening narrowing prompt");
+        let prompt = GlobPrompt::narrowing(paths, flat.pattern.clone());
+        app.modal = Some(ActiveModal::MarkGlob(prompt));
+    }
+
+    /// Handles keyboard input in the glob prompt.
+    pub(super) fn handle_mark_glob_key(app: &mut App, key: KeyEvent) {
+        let Some(ActiveModal::MarkGlob(ref mut prompt)) = app.modal else {
+            return;
+        };
+        match key.code {
+            KeyCode::Esc => {
+                debug!("closing glob prompt");
+                app.modal = None;
+            }
+            KeyCode::Char(c) if !key.modifiers.contains(KeyModifiers::CONTROL) => {
+                prompt.input_char(c);
+            }
+            KeyCode::Backspace => prompt.backspace(),
+            KeyCode::Enter if prompt.narrow => {
+                let pattern = prompt.pattern.clone();
+                app.modal = None;
+                app.narrow_flat(&pattern);
+            }
+            KeyCode::Enter if !prompt.pattern.is_empty() => {
+                let pattern = prompt.pattern.clone();
+                app.modal = None;
+                match app.mark_matching(&pattern) {
+                    0 => app
+                        .notifications
+                        .warning(format!("Nothing matches '{}'", pattern)),
+                    count => app
+                        .notifications
+                        .info(format!("Marked {} entries matching '{}'", count, pattern)),
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::handler::test_support::{files_app, key};
+    use crate::rclone::FileItem;
+
+    #[tokio::test]
+    async fn test_marks_matching_entries() {
+        let mut app = files_app("", &["a.jpg", "b.png", "c.jpg"]);
+        for c in "+*.jpg".chars() {
+            Handler::handle_key(&mut app, key(KeyCode::Char(c)))
+                .await
+                .unwrap();
+        }
+        let Some(ActiveModal::MarkGlob(ref prompt)) = app.modal else {
+            panic!("glob prompt not open");
+        };
+        assert_eq!(prompt.matches(), 2);
+
+        Handler::handle_key(&mut app, key(KeyCode::Enter))
+            .await
+            .unwrap();
+        assert!(app.modal.is_none());
+        assert_eq!(app.marked.len(), 2);
+        assert_eq!(
+            app.notifications.toasts().last().unwrap().message,
+            "Marked 2 entries matching '*.jpg'"
+        );
+
+        Handler::handle_key(&mut app, key(KeyCode::Char('*')))
+            .await
+            .unwrap();
+        assert!(app.marked.contains("b.png"));
+        assert_eq!(app.marked.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_narrows_only_the_recursive_listing() {
+        let mut app = files_app("", &["a.jpg", "b.png", "c.jpg"]);
+        Handler::handle_key(&mut app, key(KeyCode::Char('/')))
+            .await
+            .unwrap();
+        assert!(app.modal.is_none());
+
+        app.flat = Some(crate::app::flat::FlatListing::default());
+        let files = std::mem::take(&mut app.files)
+            .into_iter()
+            .map(|item| FileItem {
+                name: format!("photos/{}", item.name),
+                ..item
+            })
+            .collect();
+        app.apply_flat(files);
+        for c in "/*.jpg".chars() {
+            Handler::handle_key(&mut app, key(KeyCode::Char(c)))
+                .await
+                .unwrap();
+        }
+        Handler::handle_key(&mut app, key(KeyCode::Enter))
+            .await
+            .unwrap();
+        let names: Vec<&str> = app.files.iter().map(FileItem::name).collect();
+        assert_eq!(names, ["photos/a.jpg", "photos/c.jpg"]);
+    }
+}
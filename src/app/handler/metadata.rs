@@ -0,0 +1,241 @@
+//! Metadata view handling: the selected file's backend metadata, such
+//! as `content-type` or `cache-control`, and setting keys of it where
+//! the backend writes metadata.
+
+use super::Handler;
+use crate::app::state::{ActiveModal, App};
+use crate::error::Result;
+use crate::rclone::{FsInfo, ObjectInfo};
+use crate::ui::MetadataView;
+use crossterm::event::{KeyCode, KeyEvent};
+use std::collections::BTreeMap;
+use tracing::{debug, info, warn};
+
+impl Handler {
+    /// Opens the metadata view for the selected file and starts
+    /// fetching its metadata. Directories have none to show.
+    pub(super) fn handle_show_metadata(app: &mut App) {
+        let (Some(remote), Some(item), Some(path)) = (
+            app.current_remote.clone(),
+            app.files.get(app.files_selected),
+            app.selected_path(),
+        ) else {
+            return;
+        };
+        if item.is_dir() {
+            return;
+        }
+        debug!(remote = %remote, path = %path, "opening metadata view");
+        app.modal = Some(ActiveModal::Metadata(Box::new(MetadataView::new(
+            remote.clone(),
+            path.clone(),
+        ))));
+        app.request_metadata(remote, path);
+    }
+
+    /// Handles keys while the metadata view is open: `j`/`k` select a
+    /// key, `e` edits its value and `a` adds one where the backend
+    /// writes metadata, `r` reloads, and `Esc`, `q` or `I` close.
+    pub(super) fn handle_metadata_key(app: &mut App, key: KeyEvent) {
+        let Some(ActiveModal::Metadata(ref mut view)) = app.modal else {
+            return;
+        };
+        if view.edit.is_some() {
+            Self::handle_metadata_edit_key(app, key);
+            return;
+        }
+        let writable = view.writable == Some(true) && !view.saving;
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q' | 'I') => app.modal = None,
+            KeyCode::Char('j') | KeyCode::Down => view.select_next(),
+            KeyCode::Char('k') | KeyCode::Up => view.select_prev(),
+            KeyCode::Char('e') | KeyCode::Enter if writable => view.start_edit(false),
+            KeyCode::Char('a') if writable => view.start_edit(true),
+            KeyCode::Char('r') if !view.saving => {
+                let (remote, path) = (view.remote.clone(), view.path.clone());
+                view.info = None;
+                app.request_metadata(remote, path);
+            }
+            _ => {}
+        }
+    }
+
+    /// Handles keys while a key is being set. `Enter` writes it; the
+    /// form stays open until the write lands, so a failure can be
+    /// retried.
+    fn handle_metadata_edit_key(app: &mut App, key: KeyEvent) {
+        let Some(ActiveModal::Metadata(ref mut view)) = app.modal else {
+            return;
+        };
+        let Some(ref mut edit) = view.edit else {
+            return;
+        };
+        if view.saving {
+            if key.code == KeyCode::Esc {
+                view.edit = None;
+            }
+            return;
+        }
+        match key.code {
+            KeyCode::Esc => {
+                view.edit = None;
+                view.error = None;
+            }
+            KeyCode::Tab | KeyCode::BackTab => edit.toggle_field(),
+            KeyCode::Enter => {
+                let key = edit.key.trim().to_lowercase();
+                if key.is_empty() {
+                    view.error = Some("The key cannot be empty".to_string());
+                    return;
+                }
+                let changes = BTreeMap::from([(key, edit.value.clone())]);
+                view.saving = true;
+                view.error = None;
+                let (remote, path) = (view.remote.clone(), view.path.clone());
+                app.request_set_metadata(remote, path, changes);
+            }
+            _ => {
+                let mut input = edit.input;
+                input.handle_key(edit.text_mut(), key);
+                edit.input = input;
+            }
+        }
+    }
+
+    /// Shows the fetched metadata, unless the view was closed or moved
+    /// on to another file meanwhile.
+    pub(super) fn apply_metadata_loaded(
+        app: &mut App,
+        remote: &str,
+        path: &str,
+        info: Result<Option<ObjectInfo>>,
+        features: Result<FsInfo>,
+    ) {
+        let Some(ActiveModal::Metadata(ref mut view)) = app.modal else {
+            debug!(path, "metadata view closed, dropping metadata");
+            return;
+        };
+        if view.remote != remote || view.path != path {
+            return;
+        }
+        let info = match info {
+            Ok(Some(info)) => Ok(info),
+            Ok(None) => Err(format!("{}:{} no longer exists", remote, path)),
+            Err(e) => {
+                warn!(remote, path, error = %e, "could not fetch metadata");
+                Err(format!("Could not fetch metadata: {}", e))
+            }
+        };
+        view.set_info(info);
+        match features {
+            Ok(features) => view.writable = Some(features.supports("WriteMetadata")),
+            Err(e) => {
+                warn!(remote, error = %e, "could not fetch backend features");
+                view.writable = Some(false);
+            }
+        }
+    }
+
+    /// Closes the form and fetches the metadata again once a key is
+    /// set, or shows why it was not.
+    pub(super) fn apply_metadata_saved(
+        app: &mut App,
+        remote: String,
+        path: String,
+        result: Result<()>,
+    ) {
+        let view = match app.modal {
+            Some(ActiveModal::Metadata(ref mut view))
+                if view.remote == remote && view.path == path =>
+            {
+                view.saving = false;
+                Some(view)
+            }
+            _ => None,
+        };
+        match result {
+            Ok(()) => {
+                info!(remote = %remote, path = %path, "metadata set");
+                let open = view.map(|view| view.edit = None).is_some();
+                app.notifications
+                    .success(format!("Updated metadata of {}", path));
+                if open {
+                    app.request_metadata(remote, path);
+                }
+            }
+            Err(e) => {
+                warn!(remote = %remote, path = %path, error = %e, "setting metadata failed");
+                match view {
+                    Some(view) => view.error = Some(e.to_string()),
+                    None => app
+                        .notifications
+                        .error(format!("Could not set metadata of {}: {}", path, e)),
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::handler::test_support::{key, unreachable_app};
+    use crate::app::state::Panel;
+    use crate::rclone::FileItem;
+
+    #[tokio::test]
+    async fn test_edit_opens_only_where_metadata_is_writable() {
+        let mut app = unreachable_app();
+        app.focused_panel = Panel::Files;
+        app.current_remote = Some("s3".to_string());
+        app.files = vec![FileItem {
+            name: "a.jpg".to_string(),
+            ..Default::default()
+        }];
+
+        Handler::handle_key(&mut app, key(KeyCode::Char('I')))
+            .await
+            .unwrap();
+        let metadata = BTreeMap::from([("content-type".to_string(), "image/jpeg".to_string())]);
+        let info = ObjectInfo {
+            metadata: Some(metadata),
+            ..ObjectInfo::default()
+        };
+        Handler::apply_metadata_loaded(
+            &mut app,
+            "s3",
+            "a.jpg",
+            Ok(Some(info)),
+            Ok(FsInfo::default()),
+        );
+        Handler::handle_key(&mut app, key(KeyCode::Char('e')))
+            .await
+            .unwrap();
+        let Some(ActiveModal::Metadata(ref mut view)) = app.modal else {
+            panic!("metadata view not open");
+        };
+        assert_eq!(view.writable, Some(false));
+        assert!(view.edit.is_none());
+
+        view.writable = Some(true);
+        Handler::handle_key(&mut app, key(KeyCode::Char('e')))
+            .await
+            .unwrap();
+        let keys = [KeyCode::Backspace; 4]
+            .into_iter()
+            .chain("png".chars().map(KeyCode::Char));
+        for key in keys {
+            Handler::handle_key(&mut app, KeyEvent::from(key))
+                .await
+                .unwrap();
+        }
+        let Some(ActiveModal::Metadata(ref view)) = app.modal else {
+            panic!("metadata view not open");
+        };
+        let edit = view.edit.as_ref().unwrap();
+        assert_eq!(
+            (edit.key.as_str(), edit.value.as_str()),
+            ("content-type", "image/png")
+        );
+    }
+}
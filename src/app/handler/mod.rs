@@ -1,13 +1,52 @@
 //! Keyboard event handling.
 
+mod audit;
+mod background;
+mod batch_rename;
+mod clipboard;
+mod compare;
+mod config_password;
+mod config_wizard;
+mod conflict;
+mod copy_remote;
+mod copy_url;
+mod daemon_setup;
+mod delete_remote;
+mod edit;
+mod error;
 mod file_ops;
+mod filter;
+mod goto;
+mod help;
+mod history;
+mod jobs;
+mod largest;
+mod mark_glob;
+mod metadata;
+mod mounts;
+mod mouse;
 mod navigation;
+mod palette;
+mod paste;
+mod preview;
+mod quit;
+mod recent;
+mod remote_info;
 mod remote_modal;
+mod script;
+#[cfg(test)]
+pub(crate) mod test_support;
+mod tier;
+mod usage;
+mod versions;
+mod yank;
 
-use super::state::{ActiveModal, App, Panel};
+use super::action::Action;
+use super::keymap::{KeyMatch, KeyPress};
+use super::state::{ActiveModal, App};
 use crate::error::Result;
-use crossterm::event::{KeyCode, KeyEvent, KeyEventKind};
-use tracing::{debug, info};
+use crossterm::event::{KeyEvent, KeyEventKind};
+use tracing::debug;
 
 /// Handles keyboard input events.
 pub struct Handler;
@@ -29,60 +68,236 @@ impl Handler {
             Some(ActiveModal::FileOperation(_)) => {
                 return Self::handle_file_operations_key(app, key).await;
             }
+            Some(ActiveModal::BatchRename(_)) => {
+                Self::handle_batch_rename_key(app, key);
+                return Ok(());
+            }
+            Some(ActiveModal::GoTo(_)) => {
+                Self::handle_go_to_key(app, key);
+                return Ok(());
+            }
+            Some(ActiveModal::Recent(_)) => {
+                Self::handle_recent_key(app, key);
+                return Ok(());
+            }
+            Some(ActiveModal::Filter(_)) => {
+                Self::handle_filter_key(app, key);
+                return Ok(());
+            }
+            Some(ActiveModal::Tier(_)) => {
+                Self::handle_tier_key(app, key);
+                return Ok(());
+            }
+            Some(ActiveModal::CopyUrl(_)) => {
+                Self::handle_copy_url_key(app, key);
+                return Ok(());
+            }
+            Some(ActiveModal::Versions(_)) => {
+                Self::handle_versions_key(app, key);
+                return Ok(());
+            }
+            Some(ActiveModal::Largest(_)) => {
+                Self::handle_largest_key(app, key);
+                return Ok(());
+            }
+            Some(ActiveModal::Metadata(_)) => {
+                Self::handle_metadata_key(app, key);
+                return Ok(());
+            }
+            Some(ActiveModal::MarkGlob(_)) => {
+                Self::handle_mark_glob_key(app, key);
+                return Ok(());
+            }
+            Some(ActiveModal::Conflict(_)) => {
+                Self::handle_conflict_key(app, key);
+                return Ok(());
+            }
             Some(ActiveModal::ConfirmDeleteRemote { .. }) => {
                 return Self::handle_confirm_key(app, key).await;
             }
             Some(ActiveModal::CreateRemote(_)) => {
                 return Self::handle_modal_key(app, key).await;
             }
-            None => {}
-        }
-
-        match key.code {
-            KeyCode::Char('q') => {
-                info!("quit requested");
-                app.running = false;
+            Some(ActiveModal::ConfigWizard(_)) => {
+                return Self::handle_wizard_key(app, key).await;
+            }
+            Some(ActiveModal::ConfigPassword(_)) => {
+                Self::handle_config_password_key(app, key);
+                return Ok(());
+            }
+            Some(ActiveModal::RemoteInfo(_)) => {
+                Self::handle_remote_info_key(app, key);
+                return Ok(());
+            }
+            Some(ActiveModal::CopyRemote(_)) => {
+                Self::handle_copy_remote_key(app, key);
+                return Ok(());
             }
-            KeyCode::Char('a') if matches!(app.focused_panel, Panel::Remotes) => {
-                debug!("opening create remote modal");
-                app.modal = Some(ActiveModal::CreateRemote(
-                    crate::ui::CreateRemoteModal::new(crate::ui::CreateRemoteMode::Create),
-                ));
+            Some(ActiveModal::Help { .. }) => {
+                Self::handle_help_key(app, key);
+                return Ok(());
             }
-            KeyCode::Char('e') if matches!(app.focused_panel, Panel::Remotes) => {
-                Self::handle_edit_remote(app).await?;
+            Some(ActiveModal::CommandPalette(_)) => {
+                return Self::handle_palette_key(app, key).await;
             }
-            KeyCode::Char('d') if matches!(app.focused_panel, Panel::Remotes) => {
-                Self::handle_delete_remote(app);
+            Some(ActiveModal::Preview(_)) => {
+                Self::handle_preview_key(app, key);
+                return Ok(());
             }
-            KeyCode::Char('x') if matches!(app.focused_panel, Panel::Files) => {
-                Self::handle_delete_file(app);
+            Some(ActiveModal::Mounts(_)) => {
+                Self::handle_mounts_key(app, key);
+                return Ok(());
             }
-            KeyCode::Char('n') if matches!(app.focused_panel, Panel::Files) => {
-                Self::handle_mkdir(app);
+            Some(ActiveModal::Jobs(_)) => {
+                Self::handle_jobs_key(app, key);
+                return Ok(());
             }
-            KeyCode::Char('c') if matches!(app.focused_panel, Panel::Files) => {
-                Self::handle_copy_file(app);
+            Some(ActiveModal::History(_)) => {
+                Self::handle_history_key(app, key);
+                return Ok(());
             }
-            KeyCode::Char('m') if matches!(app.focused_panel, Panel::Files) => {
-                Self::handle_move_file(app);
+            Some(ActiveModal::Audit(_)) => {
+                Self::handle_audit_key(app, key);
+                return Ok(());
             }
-            KeyCode::Char('j') | KeyCode::Down => {
-                app.navigate_down();
+            Some(ActiveModal::DaemonSetup(_)) => {
+                Self::handle_daemon_setup_key(app, key);
+                return Ok(());
+            }
+            Some(ActiveModal::Compare(_)) => {
+                Self::handle_compare_key(app, key);
+                return Ok(());
+            }
+            Some(ActiveModal::Usage(_)) => {
+                Self::handle_usage_key(app, key);
+                return Ok(());
+            }
+            Some(ActiveModal::Quit(_)) => {
+                Self::handle_quit_key(app, key);
+                return Ok(());
+            }
+            // Answered in `handle_event`, which can replay the failed
+            // event.
+            Some(ActiveModal::Error { .. }) => return Ok(()),
+            None => {}
+        }
+
+        let press = KeyPress::from(key);
+        app.pending_keys.push(press);
+        let mut found = app.keymap.resolve(&app.pending_keys, app.focused_panel);
+        // A key that breaks a pending sequence (`g` then `j`) is
+        // handled as if it had been pressed on its own.
+        if found == KeyMatch::Unbound && app.pending_keys.len() > 1 {
+            app.pending_keys = vec![press];
+            found = app.keymap.resolve(&app.pending_keys, app.focused_panel);
+        }
+
+        match found {
+            KeyMatch::Action(action) => {
+                app.pending_keys.clear();
+                Self::dispatch(app, action).await?;
+            }
+            KeyMatch::Pending => {}
+            KeyMatch::Unbound => app.pending_keys.clear(),
+        }
+        Ok(())
+    }
+
+    /// Runs an action regardless of which key triggered it.
+    ///
+    /// # Errors
+    /// Returns error if rclone API calls fail.
+    pub async fn dispatch(app: &mut App, action: Action) -> Result<()> {
+        match action {
+            Action::Quit => Self::handle_quit(app),
+            Action::NavigateDown => app.navigate_down(),
+            Action::NavigateUp => app.navigate_up(),
+            Action::JumpTop => app.jump_top(),
+            Action::JumpBottom => app.jump_bottom(),
+            Action::HalfPageDown => app.half_page_down(),
+            Action::HalfPageUp => app.half_page_up(),
+            Action::ScreenTop => app.screen_top(),
+            Action::ScreenMiddle => app.screen_middle(),
+            Action::ScreenBottom => app.screen_bottom(),
+            Action::SwitchPanel => app.switch_panel(),
+            Action::Open => Self::handle_enter(app),
+            Action::Back => Self::handle_backspace(app),
+            Action::Cancel => Self::handle_cancel(app),
+            Action::ShowHelp => {
+                debug!("opening help");
+                app.modal = Some(ActiveModal::Help { scroll: 0 });
             }
-            KeyCode::Char('k') | KeyCode::Up => {
-                app.navigate_up();
+            Action::CommandPalette => {
+                debug!("opening command palette");
+                let profiles = app.profiles.keys().cloned().collect();
+                let mut palette =
+                    crate::ui::CommandPalette::with_profiles(profiles, app.profile.clone());
+                palette.scripts = app.scripts.keys().cloned().collect();
+                palette.commands = app.commands.keys().cloned().collect();
+                app.modal = Some(ActiveModal::CommandPalette(palette));
             }
-            KeyCode::Tab => {
-                app.switch_panel();
+            Action::GoToPath => Self::handle_go_to(app),
+            Action::RecentLocations => Self::handle_recent(app),
+            Action::ShowMounts => Self::handle_show_mounts(app),
+            Action::ShowJobs => Self::handle_show_jobs(app),
+            Action::TransferHistory => Self::handle_show_history(app),
+            Action::AuditLog => Self::handle_show_audit(app),
+            Action::ShowUsage => Self::handle_show_usage(app),
+            Action::Compare => Self::handle_compare(app),
+            Action::ToggleLogs => app.toggle_logs(),
+            Action::ToggleLogFollow => app.toggle_log_follow(),
+            Action::ToggleDebugLogging => app.toggle_debug_logging(),
+            Action::ToggleCalls => app.calls_visible = !app.calls_visible,
+            Action::WidenRemotes => Self::handle_remote_layout(app, |app| app.resize_remotes(true)),
+            Action::NarrowRemotes => {
+                Self::handle_remote_layout(app, |app| app.resize_remotes(false))
             }
-            KeyCode::Enter => {
-                Self::handle_enter(app).await?;
+            Action::ToggleSidebar => app.toggle_sidebar(),
+            Action::ToggleZoom => app.toggle_zoom(),
+            Action::AddRemote => Self::handle_add_remote(app),
+            Action::EditRemote => Self::handle_edit_remote(app),
+            Action::DeleteRemote => Self::handle_delete_remote(app),
+            Action::ShowRemoteInfo => Self::handle_remote_info(app),
+            Action::RenameRemote => {
+                Self::handle_copy_remote(app, crate::ui::CopyRemoteModal::rename)
             }
-            KeyCode::Backspace => {
-                Self::handle_backspace(app).await?;
+            Action::DuplicateRemote => {
+                Self::handle_copy_remote(app, crate::ui::CopyRemoteModal::duplicate)
             }
-            _ => {}
+            Action::ToggleFavorite => Self::handle_remote_layout(app, App::toggle_favorite),
+            Action::MoveRemoteUp => Self::handle_remote_layout(app, |app| app.move_remote(false)),
+            Action::MoveRemoteDown => Self::handle_remote_layout(app, |app| app.move_remote(true)),
+            Action::ToggleMark => app.toggle_mark(),
+            Action::MarkAll => app.mark_all(),
+            Action::InvertMarks => app.invert_marks(),
+            Action::MarkGlob => Self::handle_mark_glob(app),
+            Action::Filter => Self::handle_filter(app),
+            Action::ToggleColumns => app.toggle_compact(),
+            Action::ToggleExact => app.exact = !app.exact,
+            Action::ToggleTree => app.toggle_tree(),
+            Action::ExpandDir => app.expand_selected(),
+            Action::CollapseDir => app.collapse_selected(),
+            Action::ToggleFlat => app.toggle_flat(),
+            Action::SortFlat => app.cycle_flat_sort(),
+            Action::NarrowFlat => Self::handle_narrow_listing(app),
+            Action::LargestFiles => Self::handle_show_largest(app),
+            Action::DeleteFile => Self::handle_delete_file(app),
+            Action::Mkdir => Self::handle_mkdir(app),
+            Action::CopyFile => Self::handle_copy_file(app),
+            Action::MoveFile => Self::handle_move_file(app),
+            Action::BatchRename => Self::handle_batch_rename(app),
+            Action::ChangeTier => Self::handle_change_tier(app),
+            Action::ShowMetadata => Self::handle_show_metadata(app),
+            Action::ShowVersions => Self::handle_show_versions(app),
+            Action::CopyUrl => Self::handle_copy_url(app),
+            Action::Preview => Self::handle_preview(app),
+            Action::EditFile => Self::handle_edit_file(app),
+            Action::OpenFile => Self::handle_open_file(app),
+            Action::Yank => Self::handle_yank(app, false),
+            Action::Cut => Self::handle_yank(app, true),
+            Action::Put => Self::handle_put(app),
+            Action::CopyPath => Self::handle_copy_path(app, false),
+            Action::CopyCommand => Self::handle_copy_path(app, true),
         }
         Ok(())
     }
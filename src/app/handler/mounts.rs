@@ -0,0 +1,266 @@
+//! Mounts view handling: listing, mounting and unmounting remotes.
+
+use super::Handler;
+use crate::app::command::rclone_path;
+use crate::app::state::{ActiveModal, App, Panel};
+use crate::error::Result;
+use crate::rclone::Mount;
+use crate::ui::{MountForm, MountsView};
+use crossterm::event::{KeyCode, KeyEvent};
+use tracing::{debug, info, warn};
+
+impl Handler {
+    /// Opens the mounts view and starts listing the mounts.
+    pub(super) fn handle_show_mounts(app: &mut App) {
+        debug!("opening mounts");
+        app.modal = Some(ActiveModal::Mounts(Box::new(MountsView::new())));
+        app.request_mounts();
+    }
+
+    /// Handles keys while the mounts view is open. The form, when
+    /// open, takes every key but `Esc`, which closes it.
+    pub(super) fn handle_mounts_key(app: &mut App, key: KeyEvent) {
+        let source = Self::mount_source(app);
+        let Some(ActiveModal::Mounts(ref mut view)) = app.modal else {
+            return;
+        };
+        if let Some(ref mut form) = view.form {
+            match key.code {
+                KeyCode::Esc => view.form = None,
+                _ if form.waiting => {}
+                KeyCode::Tab | KeyCode::BackTab => form.toggle_focus(),
+                KeyCode::Char(c) => form.input_char(c),
+                KeyCode::Backspace => form.backspace(),
+                KeyCode::Enter => Self::submit_mount(app),
+                _ => {}
+            }
+            return;
+        }
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => {
+                debug!("closing mounts");
+                app.modal = None;
+            }
+            KeyCode::Char('j') | KeyCode::Down => view.select_next(),
+            KeyCode::Char('k') | KeyCode::Up => view.select_prev(),
+            KeyCode::Char('n') => view.form = Some(MountForm::new(source)),
+            KeyCode::Char('r') => {
+                view.loading = true;
+                app.request_mounts();
+            }
+            KeyCode::Char('u') => {
+                if let Some(mount_point) = view.selected_mount().map(|m| m.mount_point.clone()) {
+                    info!(mount_point = %mount_point, "unmounting");
+                    app.request_unmount(mount_point);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Returns what a new mount starts from: the open directory when
+    /// the files panel is focused, else the selected remote's root.
+    fn mount_source(app: &App) -> String {
+        match (&app.focused_panel, &app.current_remote) {
+            (Panel::Files, Some(remote)) => rclone_path(remote, &app.current_path),
+            _ => app
+                .remotes
+                .get(app.remotes_selected)
+                .map(|remote| rclone_path(remote, ""))
+                .unwrap_or_default(),
+        }
+    }
+
+    /// Sends the mount form, unless it is incomplete.
+    fn submit_mount(app: &mut App) {
+        let Some(ActiveModal::Mounts(ref mut view)) = app.modal else {
+            return;
+        };
+        let Some(ref mut form) = view.form else {
+            return;
+        };
+        match form.target() {
+            Ok((remote, path)) => {
+                form.waiting = true;
+                form.error = None;
+                let mount_point = form.mount_point.clone();
+                app.request_mount(remote, path, mount_point);
+            }
+            Err(reason) => form.error = Some(reason.to_string()),
+        }
+    }
+
+    /// Shows a finished listing if the mounts view is still open.
+    pub(super) fn apply_mounts(app: &mut App, result: Result<Vec<Mount>>) {
+        if let Err(ref e) = result {
+            warn!(error = %e, "listing mounts failed");
+        }
+        if let Some(ActiveModal::Mounts(ref mut view)) = app.modal {
+            view.set_mounts(result);
+        }
+    }
+
+    /// Closes the form and lists the mounts again once mounting
+    /// succeeded; otherwise shows why it failed in the form.
+    pub(super) fn apply_mounted(
+        app: &mut App,
+        source: &str,
+        mount_point: &str,
+        result: Result<()>,
+    ) {
+        let form = match app.modal {
+            Some(ActiveModal::Mounts(ref mut view)) => view.form.as_mut(),
+            _ => None,
+        };
+        match result {
+            Ok(()) => {
+                info!(source, mount_point, "mounted");
+                app.notifications
+                    .success(format!("Mounted {} on {}", source, mount_point));
+                if let Some(ActiveModal::Mounts(ref mut view)) = app.modal {
+                    view.form = None;
+                    app.request_mounts();
+                }
+            }
+            Err(e) => {
+                warn!(source, mount_point, error = %e, "mount failed");
+                match form {
+                    Some(form) => {
+                        form.waiting = false;
+                        form.error = Some(format!("Error: {}", e));
+                    }
+                    None => app
+                        .notifications
+                        .error(format!("Could not mount {}: {}", source, e)),
+                }
+            }
+        }
+    }
+
+    /// Reports an unmount and lists the mounts again.
+    pub(super) fn apply_unmounted(app: &mut App, mount_point: &str, result: Result<()>) {
+        match result {
+            Ok(()) => {
+                info!(mount_point, "unmounted");
+                app.notifications
+                    .success(format!("Unmounted {}", mount_point));
+                if matches!(app.modal, Some(ActiveModal::Mounts(_))) {
+                    app.request_mounts();
+                }
+            }
+            Err(e) => {
+                warn!(mount_point, error = %e, "unmount failed");
+                app.notifications
+                    .error(format!("Could not unmount {}: {}", mount_point, e));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::event::AppEvent;
+    use crate::app::handler::test_support::{key, unreachable_app};
+
+    fn view(app: &App) -> &MountsView {
+        match app.modal {
+            Some(ActiveModal::Mounts(ref view)) => view,
+            ref other => panic!("expected mounts view, got {:?}", other),
+        }
+    }
+
+    async fn type_text(app: &mut App, text: &str) {
+        for c in text.chars() {
+            Handler::handle_key(app, key(KeyCode::Char(c)))
+                .await
+                .unwrap();
+        }
+    }
+
+    #[tokio::test]
+    async fn test_failed_listing_is_shown_in_view() {
+        let mut app = unreachable_app();
+        Handler::handle_show_mounts(&mut app);
+        assert!(view(&app).loading);
+
+        let event = app.wait_event().await.unwrap();
+        assert!(matches!(event, AppEvent::MountsLoaded(Err(_))));
+        Handler::handle_app_event(&mut app, event);
+        assert!(!view(&app).loading);
+        assert!(view(&app).error.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_new_mount_starts_from_selected_remote() {
+        let mut app = unreachable_app();
+        app.remotes = vec!["gdrive".to_string()];
+        app.modal = Some(ActiveModal::Mounts(Box::default()));
+
+        type_text(&mut app, "n/mnt/g").await;
+        let form = view(&app).form.as_ref().unwrap();
+        assert_eq!(form.source, "gdrive:");
+        assert_eq!(form.mount_point, "/mnt/g");
+
+        Handler::handle_key(&mut app, key(KeyCode::Enter))
+            .await
+            .unwrap();
+        assert!(view(&app).form.as_ref().unwrap().waiting);
+
+        let event = app.wait_event().await.unwrap();
+        Handler::handle_app_event(&mut app, event);
+        let form = view(&app).form.as_ref().unwrap();
+        assert!(!form.waiting);
+        assert!(form.error.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_incomplete_form_is_not_sent() {
+        let mut app = unreachable_app();
+        app.modal = Some(ActiveModal::Mounts(Box::default()));
+
+        type_text(&mut app, "n").await;
+        Handler::handle_key(&mut app, key(KeyCode::Enter))
+            .await
+            .unwrap();
+
+        let form = view(&app).form.as_ref().unwrap();
+        assert!(!form.waiting);
+        assert!(form.error.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_unmount_reports_outcome() {
+        let mut app = unreachable_app();
+        app.modal = Some(ActiveModal::Mounts(Box::default()));
+        Handler::apply_unmounted(&mut app, "/mnt/g", Ok(()));
+
+        assert!(
+            app.notifications
+                .toasts()
+                .last()
+                .unwrap()
+                .message
+                .contains("/mnt/g")
+        );
+        let event = app.wait_event().await.unwrap();
+        assert!(matches!(event, AppEvent::MountsLoaded(_)));
+    }
+
+    #[tokio::test]
+    async fn test_escape_closes_form_then_view() {
+        let mut app = unreachable_app();
+        app.modal = Some(ActiveModal::Mounts(Box::default()));
+
+        type_text(&mut app, "n").await;
+        Handler::handle_key(&mut app, key(KeyCode::Esc))
+            .await
+            .unwrap();
+        assert!(view(&app).form.is_none());
+
+        Handler::handle_key(&mut app, key(KeyCode::Esc))
+            .await
+            .unwrap();
+        assert!(app.modal.is_none());
+    }
+}
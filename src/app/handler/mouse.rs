@@ -0,0 +1,389 @@
+//! Mouse event handling.
+
+use super::Handler;
+use crate::app::action::Action;
+use crate::app::state::{ActiveModal, App, Panel};
+use crate::error::Result;
+use crate::ui::{CommandPaletteWidget, ConfirmChoice, ConfirmWidget, CreateRemoteWidget, Layout};
+use crossterm::event::{MouseButton, MouseEvent, MouseEventKind};
+use ratatui::layout::Position;
+use std::time::{Duration, Instant};
+use tracing::debug;
+
+/// Two clicks on the same cell within this window open the row.
+const DOUBLE_CLICK: Duration = Duration::from_millis(400);
+
+/// Rows moved per scroll-wheel notch.
+const SCROLL_ROWS: usize = 3;
+
+impl Handler {
+    /// Processes a mouse event and updates app state.
+    ///
+    /// Positions are mapped through the same layout the last frame
+    /// was drawn with, stored in [`App::screen`].
+    ///
+    /// # Errors
+    /// Returns error if rclone API calls fail.
+    pub async fn handle_mouse(app: &mut App, mouse: MouseEvent) -> Result<()> {
+        // A click abandons any half-typed key sequence.
+        app.pending_keys.clear();
+        if app.modal.is_some() {
+            return Self::handle_modal_mouse(app, mouse).await;
+        }
+
+        let rects = app.layout(app.screen);
+        let position = Position::new(mouse.column, mouse.row);
+        let (panel, area, offset) = if rects.remotes.contains(position) {
+            (Panel::Remotes, rects.remotes, app.remotes_offset)
+        } else if rects.files.contains(position) {
+            (Panel::Files, rects.files, app.files_offset)
+        } else if let Some(logs) = rects.logs.filter(|logs| logs.contains(position)) {
+            (Panel::Logs, logs, app.logs_offset)
+        } else {
+            return Ok(());
+        };
+
+        match mouse.kind {
+            MouseEventKind::Down(MouseButton::Left) => {
+                let double = Self::register_click(app, mouse);
+                app.focused_panel = panel;
+                let Some(index) = Layout::list_row(area, offset, mouse.column, mouse.row) else {
+                    return Ok(());
+                };
+                let len = match panel {
+                    Panel::Remotes => app.remotes.len(),
+                    Panel::Files => app.files.len(),
+                    Panel::Logs => app.logs.len(),
+                };
+                if index >= len {
+                    return Ok(());
+                }
+                debug!(panel = ?panel, index, double, "mouse select");
+                app.select(index);
+                if double {
+                    app.last_click = None;
+                    Self::dispatch(app, Action::Open).await?;
+                }
+            }
+            MouseEventKind::ScrollDown => {
+                app.focused_panel = panel;
+                for _ in 0..SCROLL_ROWS {
+                    app.navigate_down();
+                }
+            }
+            MouseEventKind::ScrollUp => {
+                app.focused_panel = panel;
+                for _ in 0..SCROLL_ROWS {
+                    app.navigate_up();
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Handles mouse input while a modal is open. Clicks outside the
+    /// modal's controls are ignored.
+    async fn handle_modal_mouse(app: &mut App, mouse: MouseEvent) -> Result<()> {
+        let area = app.screen;
+        let (column, row) = (mouse.column, mouse.row);
+        let clicked = mouse.kind == MouseEventKind::Down(MouseButton::Left);
+
+        match app.modal {
+            Some(ActiveModal::ConfirmDeleteRemote { ref mut modal, .. }) if clicked => {
+                if let Some(choice) = ConfirmWidget::choice_at(area, modal, column, row)
+                    && (choice == ConfirmChoice::No || modal.unlocked())
+                {
+                    debug!(choice = ?choice, "confirm button clicked");
+                    modal.selected = choice;
+                    Self::handle_confirm_submit(app).await?;
+                }
+            }
+            Some(ActiveModal::CreateRemote(ref mut modal)) if clicked => {
+                if let Some(field) = CreateRemoteWidget::field_at(area, modal, column, row)
+                    && modal.fields().contains(&field)
+                {
+                    modal.focus_field = field;
+                }
+            }
+            Some(ActiveModal::CommandPalette(ref mut palette)) => match mouse.kind {
+                MouseEventKind::Down(MouseButton::Left) => {
+                    if let Some(index) = CommandPaletteWidget::index_at(area, palette, column, row)
+                    {
+                        palette.selected = index;
+                        Self::handle_palette_submit(app).await?;
+                    }
+                }
+                MouseEventKind::ScrollDown => palette.select_next(),
+                MouseEventKind::ScrollUp => palette.select_prev(),
+                _ => {}
+            },
+            Some(ActiveModal::Preview(_)) => match mouse.kind {
+                MouseEventKind::ScrollDown => {
+                    Self::scroll_preview(app, |s| s.saturating_add(SCROLL_ROWS));
+                }
+                MouseEventKind::ScrollUp => {
+                    Self::scroll_preview(app, |s| s.saturating_sub(SCROLL_ROWS));
+                }
+                _ => {}
+            },
+            Some(ActiveModal::RemoteInfo(ref mut view)) => match mouse.kind {
+                MouseEventKind::ScrollDown => {
+                    view.scroll_to(view.scroll.saturating_add(SCROLL_ROWS))
+                }
+                MouseEventKind::ScrollUp => view.scroll_to(view.scroll.saturating_sub(SCROLL_ROWS)),
+                _ => {}
+            },
+            Some(ActiveModal::Mounts(ref mut view)) if view.form.is_none() => match mouse.kind {
+                MouseEventKind::ScrollDown => view.select_next(),
+                MouseEventKind::ScrollUp => view.select_prev(),
+                _ => {}
+            },
+            Some(ActiveModal::Compare(ref mut view)) if view.form.is_none() => match mouse.kind {
+                MouseEventKind::ScrollDown => view.select_next(),
+                MouseEventKind::ScrollUp => view.select_prev(),
+                _ => {}
+            },
+            Some(ActiveModal::BatchRename(ref mut view)) => match mouse.kind {
+                MouseEventKind::ScrollDown => view.scroll_by(1),
+                MouseEventKind::ScrollUp => view.scroll_by(-1),
+                _ => {}
+            },
+            Some(ActiveModal::Usage(ref mut view)) => match mouse.kind {
+                MouseEventKind::ScrollDown => view.select_next(),
+                MouseEventKind::ScrollUp => view.select_prev(),
+                _ => {}
+            },
+            Some(ActiveModal::Recent(ref mut picker)) => match mouse.kind {
+                MouseEventKind::ScrollDown => picker.select_next(),
+                MouseEventKind::ScrollUp => picker.select_prev(),
+                _ => {}
+            },
+            Some(ActiveModal::Jobs(ref mut view)) => match mouse.kind {
+                MouseEventKind::ScrollDown => view.select_next(),
+                MouseEventKind::ScrollUp => view.select_prev(),
+                _ => {}
+            },
+            Some(ActiveModal::History(ref mut view)) => match mouse.kind {
+                MouseEventKind::ScrollDown => view.select_next(),
+                MouseEventKind::ScrollUp => view.select_prev(),
+                _ => {}
+            },
+            Some(ActiveModal::Help { .. }) => match mouse.kind {
+                MouseEventKind::ScrollDown => {
+                    Self::scroll_help(app, |s| s.saturating_add(SCROLL_ROWS));
+                }
+                MouseEventKind::ScrollUp => {
+                    Self::scroll_help(app, |s| s.saturating_sub(SCROLL_ROWS));
+                }
+                _ => {}
+            },
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Records a left click and returns whether it completes a
+    /// double click on the same cell.
+    fn register_click(app: &mut App, mouse: MouseEvent) -> bool {
+        let now = Instant::now();
+        let double = app.last_click.is_some_and(|(at, column, row)| {
+            column == mouse.column && row == mouse.row && now.duration_since(at) <= DOUBLE_CLICK
+        });
+        app.last_click = Some((now, mouse.column, mouse.row));
+        double
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::handler::test_support::unreachable_app;
+    use crate::ui::{
+        CommandPalette, ConfirmChoice, ConfirmModal, CreateRemoteModal, CreateRemoteMode,
+        RemoteField,
+    };
+    use crossterm::event::KeyModifiers;
+    use ratatui::layout::Rect;
+
+    /// An app on a 100x30 screen listing more remotes than the
+    /// remotes panel shows.
+    fn scrolling_app() -> App {
+        let mut app = unreachable_app();
+        app.set_screen(Rect::new(0, 0, 100, 30));
+        app.remotes = (0..20).map(|i| format!("remote_{}", i)).collect();
+        app
+    }
+
+    fn mouse(kind: MouseEventKind, column: u16, row: u16) -> MouseEvent {
+        MouseEvent {
+            kind,
+            column,
+            row,
+            modifiers: KeyModifiers::NONE,
+        }
+    }
+
+    fn click(column: u16, row: u16) -> MouseEvent {
+        mouse(MouseEventKind::Down(MouseButton::Left), column, row)
+    }
+
+    /// Screen cell of row `index` in the remotes panel.
+    fn remote_row(app: &App, index: u16) -> (u16, u16) {
+        let rects = app.layout(app.screen);
+        (rects.remotes.x + 2, rects.remotes.y + 1 + index)
+    }
+
+    #[tokio::test]
+    async fn test_click_selects_row() {
+        let mut app = scrolling_app();
+        let (column, row) = remote_row(&app, 4);
+
+        Handler::handle_mouse(&mut app, click(column, row))
+            .await
+            .unwrap();
+
+        assert_eq!(app.remotes_selected, 4);
+    }
+
+    #[tokio::test]
+    async fn test_click_focuses_panel() {
+        let mut app = scrolling_app();
+        let rects = app.layout(app.screen);
+
+        Handler::handle_mouse(&mut app, click(rects.files.x + 2, rects.files.y + 1))
+            .await
+            .unwrap();
+
+        assert_eq!(app.focused_panel, Panel::Files);
+    }
+
+    #[tokio::test]
+    async fn test_click_past_end_keeps_selection() {
+        let mut app = scrolling_app();
+        app.remotes.truncate(2);
+        let (column, row) = remote_row(&app, 10);
+
+        Handler::handle_mouse(&mut app, click(column, row))
+            .await
+            .unwrap();
+
+        assert_eq!(app.remotes_selected, 0);
+    }
+
+    #[tokio::test]
+    async fn test_click_accounts_for_scroll_offset() {
+        let mut app = scrolling_app();
+        app.remotes_offset = 5;
+        let (column, row) = remote_row(&app, 1);
+
+        Handler::handle_mouse(&mut app, click(column, row))
+            .await
+            .unwrap();
+
+        assert_eq!(app.remotes_selected, 6);
+    }
+
+    #[tokio::test]
+    async fn test_clicks_on_different_rows_are_not_double() {
+        let mut app = scrolling_app();
+        let (column, row) = remote_row(&app, 1);
+        Handler::handle_mouse(&mut app, click(column, row))
+            .await
+            .unwrap();
+
+        assert!(!Handler::register_click(&mut app, click(column, row + 1)));
+        assert!(Handler::register_click(&mut app, click(column, row + 1)));
+    }
+
+    #[tokio::test]
+    async fn test_scroll_moves_selection() {
+        let mut app = scrolling_app();
+        let (column, row) = remote_row(&app, 0);
+
+        Handler::handle_mouse(&mut app, mouse(MouseEventKind::ScrollDown, column, row))
+            .await
+            .unwrap();
+        assert_eq!(app.remotes_selected, SCROLL_ROWS);
+
+        Handler::handle_mouse(&mut app, mouse(MouseEventKind::ScrollUp, column, row))
+            .await
+            .unwrap();
+        assert_eq!(app.remotes_selected, 0);
+    }
+
+    #[tokio::test]
+    async fn test_click_outside_panels_is_ignored() {
+        let mut app = scrolling_app();
+
+        Handler::handle_mouse(&mut app, click(0, 0)).await.unwrap();
+
+        assert_eq!(app.remotes_selected, 0);
+        assert_eq!(app.focused_panel, Panel::Remotes);
+    }
+
+    #[tokio::test]
+    async fn test_no_button_closes_confirm() {
+        let mut app = scrolling_app();
+        let modal = ConfirmModal::new("Delete", "Delete?");
+        let (column, row) = (0..app.screen.width)
+            .flat_map(|c| (0..app.screen.height).map(move |r| (c, r)))
+            .find(|&(c, r)| {
+                ConfirmWidget::choice_at(app.screen, &modal, c, r) == Some(ConfirmChoice::No)
+            })
+            .unwrap();
+        app.modal = Some(ActiveModal::ConfirmDeleteRemote {
+            remote: "remote_0".to_string(),
+            modal,
+        });
+
+        Handler::handle_mouse(&mut app, click(column, row))
+            .await
+            .unwrap();
+
+        assert!(app.modal.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_click_focuses_remote_field() {
+        let mut app = scrolling_app();
+        let modal = CreateRemoteModal::new(CreateRemoteMode::Create);
+        let (column, row) = (0..app.screen.width)
+            .flat_map(|c| (0..app.screen.height).map(move |r| (c, r)))
+            .find(|&(c, r)| {
+                CreateRemoteWidget::field_at(app.screen, &modal, c, r) == Some(RemoteField::Path)
+            })
+            .unwrap();
+        app.modal = Some(ActiveModal::CreateRemote(Box::new(modal)));
+
+        Handler::handle_mouse(&mut app, click(column, row))
+            .await
+            .unwrap();
+
+        assert_eq!(
+            app.create_remote_modal().map(|m| m.focus_field),
+            Some(RemoteField::Path)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_click_runs_palette_entry() {
+        let mut app = scrolling_app();
+        let palette = CommandPalette {
+            query: "switch panel".to_string(),
+            ..CommandPalette::default()
+        };
+        let (column, row) = (0..app.screen.width)
+            .flat_map(|c| (0..app.screen.height).map(move |r| (c, r)))
+            .find(|&(c, r)| CommandPaletteWidget::index_at(app.screen, &palette, c, r) == Some(0))
+            .unwrap();
+        app.modal = Some(ActiveModal::CommandPalette(palette));
+
+        Handler::handle_mouse(&mut app, click(column, row))
+            .await
+            .unwrap();
+
+        assert!(app.modal.is_none());
+        assert_eq!(app.focused_panel, Panel::Files);
+    }
+}
@@ -2,72 +2,80 @@
 
 use super::Handler;
 use crate::app::state::{App, Panel};
-use crate::error::Result;
 use tracing::{debug, info};
 
 impl Handler {
-    /// Handles Enter key: select remote or open directory.
-    pub(super) async fn handle_enter(app: &mut App) -> Result<()> {
+    /// Handles Enter key: select remote or open directory. The
+    /// listing loads in the background.
+    pub(super) fn handle_enter(app: &mut App) {
         match app.focused_panel {
             Panel::Remotes => {
                 if let Some(remote) = app.remotes.get(app.remotes_selected) {
-                    info!(remote = %remote, "selecting remote");
-                    app.current_remote = Some(remote.clone());
-                    app.current_path = String::new();
-                    app.load_files().await?;
-                    app.focused_panel = Panel::Files;
+                    let path = app.remote_layout.start_path(remote).to_string();
+                    info!(remote = %remote, path = %path, "selecting remote");
+                    app.request_files(remote.clone(), path);
                 }
             }
             Panel::Files => {
-                if let Some(item) = app.files.get(app.files_selected)
-                    && item.is_dir()
+                if let (Some(remote), Some(item), Some(path)) = (
+                    app.current_remote.clone(),
+                    app.files.get(app.files_selected),
+                    app.selected_path(),
+                ) && item.is_dir()
                 {
-                    let name = item.name();
-                    debug!(dir = name, "opening directory");
-                    if app.current_path.is_empty() {
-                        app.current_path = name.to_string();
-                    } else {
-                        app.current_path = format!("{}/{}", app.current_path, name);
-                    }
-                    app.load_files().await?;
+                    debug!(dir = item.name(), "opening directory");
+                    app.request_files(remote, path);
                 }
             }
+            Panel::Logs => {}
         }
-        Ok(())
     }
 
     /// Handles Backspace key: go to parent directory or back to
     /// remotes.
-    pub(super) async fn handle_backspace(app: &mut App) -> Result<()> {
+    pub(super) fn handle_backspace(app: &mut App) {
         match app.focused_panel {
             Panel::Files => {
-                if !app.current_path.is_empty() {
-                    if let Some(last_slash) = app.current_path.rfind('/') {
-                        debug!(
-                            path = %app.current_path,
-                            "going back"
-                        );
-                        app.current_path.truncate(last_slash);
-                    } else {
-                        app.current_path.clear();
-                    }
-                    app.load_files().await?;
+                if let Some(remote) = app.current_remote.clone()
+                    && !app.current_path.is_empty()
+                {
+                    debug!(path = %app.current_path, "going back");
+                    let parent = app
+                        .current_path
+                        .rfind('/')
+                        .map_or_else(String::new, |i| app.current_path[..i].to_string());
+                    app.request_files(remote, parent);
                 } else {
                     info!("going back to remotes");
+                    app.cancel_loading();
                     app.current_remote = None;
-                    app.focused_panel = Panel::Remotes;
+                    app.current_path.clear();
+                    app.focus_remotes();
                     app.files.clear();
                 }
             }
-            Panel::Remotes => {}
+            Panel::Remotes | Panel::Logs => {}
         }
-        Ok(())
+    }
+
+    /// Handles Esc: abandons a slow listing, leaving the panels as
+    /// they were before it started.
+    pub(super) fn handle_cancel(app: &mut App) {
+        let Some(ref loading) = app.loading else {
+            return;
+        };
+        let location = format!("{}:{}", loading.remote, loading.path);
+        info!(location = %location, "listing cancelled");
+        app.cancel_loading();
+        app.notifications
+            .info(format!("Cancelled listing {}", location));
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::app::handler::test_support::{key, unreachable_app};
     use crate::rclone::RcloneClient;
     use crossterm::event::{KeyCode, KeyEvent, KeyEventKind, KeyEventState, KeyModifiers};
 
@@ -211,12 +219,14 @@ mod tests {
                 size: 100,
                 mod_time: "".to_string(),
                 is_dir: false,
+                ..Default::default()
             },
             crate::rclone::FileItem {
                 name: "file2.txt".to_string(),
                 size: 100,
                 mod_time: "".to_string(),
                 is_dir: false,
+                ..Default::default()
             },
         ];
         app.focused_panel = Panel::Files;
@@ -237,12 +247,14 @@ mod tests {
                 size: 100,
                 mod_time: "".to_string(),
                 is_dir: false,
+                ..Default::default()
             },
             crate::rclone::FileItem {
                 name: "file2.txt".to_string(),
                 size: 100,
                 mod_time: "".to_string(),
                 is_dir: false,
+                ..Default::default()
             },
         ];
         app.files_selected = 1;
@@ -386,4 +398,173 @@ mod tests {
 
         assert_eq!(app.remotes_selected, 4);
     }
+
+    #[tokio::test]
+    async fn test_gg_jumps_to_top() {
+        let mut app = unreachable_app();
+        app.remotes = vec!["r1".to_string(), "r2".to_string(), "r3".to_string()];
+        app.remotes_selected = 2;
+
+        let event = key(KeyCode::Char('g'));
+        Handler::handle_key(&mut app, event).await.unwrap();
+        assert_eq!(app.remotes_selected, 2, "single g must wait for the second");
+
+        Handler::handle_key(&mut app, event).await.unwrap();
+        assert_eq!(app.remotes_selected, 0);
+        assert!(app.pending_keys.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_g_followed_by_other_key_runs_that_key() {
+        let mut app = unreachable_app();
+        app.remotes = vec!["r1".to_string(), "r2".to_string(), "r3".to_string()];
+        app.remotes_selected = 1;
+
+        let event = key(KeyCode::Char('g'));
+        Handler::handle_key(&mut app, event).await.unwrap();
+        let event = key(KeyCode::Char('j'));
+        Handler::handle_key(&mut app, event).await.unwrap();
+
+        assert_eq!(app.remotes_selected, 2);
+        assert!(app.pending_keys.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_shift_g_jumps_to_bottom() {
+        let mut app = unreachable_app();
+        app.remotes = vec!["r1".to_string(), "r2".to_string(), "r3".to_string()];
+
+        let event = KeyEvent {
+            code: KeyCode::Char('G'),
+            modifiers: KeyModifiers::SHIFT,
+            kind: KeyEventKind::Press,
+            state: KeyEventState::NONE,
+        };
+        Handler::handle_key(&mut app, event).await.unwrap();
+
+        assert_eq!(app.remotes_selected, 2);
+    }
+
+    #[tokio::test]
+    async fn test_ctrl_d_and_ctrl_u_scroll_half_page() {
+        let mut app = unreachable_app();
+        app.remotes = (0..30).map(|i| format!("r{}", i)).collect();
+        app.set_viewport_height(10);
+
+        let ctrl = |c| KeyEvent {
+            code: KeyCode::Char(c),
+            modifiers: KeyModifiers::CONTROL,
+            kind: KeyEventKind::Press,
+            state: KeyEventState::NONE,
+        };
+        Handler::handle_key(&mut app, ctrl('d')).await.unwrap();
+        assert_eq!(app.remotes_selected, 5);
+        assert!(app.modal.is_none(), "Ctrl-d must not open delete remote");
+
+        Handler::handle_key(&mut app, ctrl('u')).await.unwrap();
+        assert_eq!(app.remotes_selected, 0);
+    }
+
+    #[tokio::test]
+    async fn test_h_m_l_select_screen_rows() {
+        let mut app = unreachable_app();
+        app.remotes = (0..30).map(|i| format!("r{}", i)).collect();
+        app.set_viewport_height(10);
+
+        Handler::handle_key(&mut app, key(KeyCode::Char('L')))
+            .await
+            .unwrap();
+        assert_eq!(app.remotes_selected, 9);
+
+        Handler::handle_key(&mut app, key(KeyCode::Char('M')))
+            .await
+            .unwrap();
+        assert_eq!(app.remotes_selected, 4);
+
+        Handler::handle_key(&mut app, key(KeyCode::Char('H')))
+            .await
+            .unwrap();
+        assert_eq!(app.remotes_selected, 0);
+    }
+
+    #[tokio::test]
+    async fn test_esc_drops_a_listing_in_flight() {
+        // Accepts connections but never answers, like a daemon stuck
+        // on a slow backend.
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let _held = tokio::spawn(async move {
+            let mut sockets = Vec::new();
+            while let Ok((socket, _)) = listener.accept().await {
+                sockets.push(socket);
+            }
+        });
+        let client = RcloneClient::new("127.0.0.1", port).expect("valid host");
+        let mut app = App::new(client);
+        app.request_files("s3".to_string(), "big".to_string());
+
+        Handler::handle_key(&mut app, key(KeyCode::Esc))
+            .await
+            .unwrap();
+        assert!(app.loading.is_none());
+        assert_eq!(
+            app.notifications.toasts().last().unwrap().message,
+            "Cancelled listing s3:big"
+        );
+
+        let event = tokio::time::timeout(std::time::Duration::from_secs(5), app.wait_event())
+            .await
+            .expect("request dropped without waiting for the daemon");
+        let Some(crate::app::event::AppEvent::FilesLoaded(listing)) = event else {
+            panic!("no listing");
+        };
+        assert!(matches!(
+            listing.result,
+            Err(crate::error::LazyFileError::Cancelled { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_question_mark_opens_help() {
+        let mut app = unreachable_app();
+
+        Handler::handle_key(&mut app, key(KeyCode::Char('?')))
+            .await
+            .unwrap();
+        assert!(matches!(
+            app.modal,
+            Some(crate::app::ActiveModal::Help { scroll: 0 })
+        ));
+
+        Handler::handle_key(&mut app, key(KeyCode::Char('j')))
+            .await
+            .unwrap();
+        assert!(app.modal.is_some(), "list keys must not leak to panels");
+
+        Handler::handle_key(&mut app, key(KeyCode::Esc))
+            .await
+            .unwrap();
+        assert!(app.modal.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_remapped_key_dispatches_action() {
+        let mut app = unreachable_app();
+        let mut keys = std::collections::BTreeMap::new();
+        keys.insert(
+            "quit".to_string(),
+            crate::config::KeyList::One("Q".to_string()),
+        );
+        app.keymap = crate::app::Keymap::from_config(&keys).unwrap();
+
+        Handler::handle_key(&mut app, key(KeyCode::Char('q')))
+            .await
+            .unwrap();
+        assert!(app.running, "default key no longer quits");
+
+        Handler::handle_key(&mut app, key(KeyCode::Char('Q')))
+            .await
+            .unwrap();
+        assert!(!app.running);
+    }
 }
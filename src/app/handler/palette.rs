@@ -0,0 +1,189 @@
+//! Command palette input handling.
+
+use super::Handler;
+use crate::app::state::{ActiveModal, App};
+use crate::error::Result;
+use crate::ui::PaletteItem;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use tracing::{debug, info};
+
+impl Handler {
+    /// Handles keys while the command palette is open.
+    ///
+    /// `Enter` closes the palette before running the highlighted
+    /// action, so actions that open their own modal replace it.
+    pub(super) async fn handle_palette_key(app: &mut App, key: KeyEvent) -> Result<()> {
+        let Some(ActiveModal::CommandPalette(ref mut palette)) = app.modal else {
+            return Ok(());
+        };
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+
+        match key.code {
+            KeyCode::Esc => {
+                debug!("closing command palette");
+                app.modal = None;
+            }
+            KeyCode::Enter => Self::handle_palette_submit(app).await?,
+            KeyCode::Down | KeyCode::Tab => palette.select_next(),
+            KeyCode::Up | KeyCode::BackTab => palette.select_prev(),
+            KeyCode::Char('n') if ctrl => palette.select_next(),
+            KeyCode::Char('p') if ctrl => palette.select_prev(),
+            KeyCode::Backspace => palette.pop_char(),
+            KeyCode::Char(c) if !ctrl => palette.push_char(c),
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Closes the palette and runs the highlighted entry, if any.
+    pub(super) async fn handle_palette_submit(app: &mut App) -> Result<()> {
+        let Some(ActiveModal::CommandPalette(palette)) = app.modal.take() else {
+            return Ok(());
+        };
+        match palette.selected_item() {
+            Some(PaletteItem::Action(action)) => {
+                info!(
+                    action = action.name(),
+                    "running action from command palette"
+                );
+                Self::dispatch(app, action).await?;
+            }
+            Some(PaletteItem::Theme(preset)) => {
+                info!(theme = preset.name(), "switching theme");
+                app.theme = preset.theme();
+            }
+            Some(PaletteItem::Profile(name)) => {
+                app.switch_profile(&name)?;
+                app.notifications
+                    .info(format!("Switching to profile {}", name));
+            }
+            Some(PaletteItem::Script(name)) => Self::handle_run_script(app, &name),
+            Some(PaletteItem::Command(name)) => Self::handle_run_command(app, &name),
+            None => {}
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::Panel;
+    use crate::app::handler::test_support::{key, unreachable_app};
+    use crate::ui::ThemePreset;
+
+    async fn type_text(app: &mut App, text: &str) {
+        for c in text.chars() {
+            Handler::handle_key(app, key(KeyCode::Char(c)))
+                .await
+                .unwrap();
+        }
+    }
+
+    #[tokio::test]
+    async fn test_colon_opens_palette() {
+        let mut app = unreachable_app();
+
+        Handler::handle_key(&mut app, key(KeyCode::Char(':')))
+            .await
+            .unwrap();
+
+        assert!(matches!(app.modal, Some(ActiveModal::CommandPalette(_))));
+    }
+
+    #[tokio::test]
+    async fn test_palette_captures_bound_keys() {
+        let mut app = unreachable_app();
+        type_text(&mut app, ":q").await;
+
+        assert!(app.running);
+        let Some(ActiveModal::CommandPalette(ref palette)) = app.modal else {
+            panic!("palette should stay open");
+        };
+        assert_eq!(palette.query, "q");
+    }
+
+    #[tokio::test]
+    async fn test_enter_runs_selected_action() {
+        let mut app = unreachable_app();
+        type_text(&mut app, ":switch").await;
+
+        Handler::handle_key(&mut app, key(KeyCode::Enter))
+            .await
+            .unwrap();
+
+        assert!(app.modal.is_none());
+        assert_eq!(app.focused_panel, Panel::Files);
+    }
+
+    #[tokio::test]
+    async fn test_enter_can_open_another_modal() {
+        let mut app = unreachable_app();
+        type_text(&mut app, ":add remote").await;
+
+        Handler::handle_key(&mut app, key(KeyCode::Enter))
+            .await
+            .unwrap();
+
+        assert!(app.create_remote_modal().is_some());
+    }
+
+    #[tokio::test]
+    async fn test_enter_without_match_closes() {
+        let mut app = unreachable_app();
+        type_text(&mut app, ":zzzz").await;
+
+        Handler::handle_key(&mut app, key(KeyCode::Enter))
+            .await
+            .unwrap();
+
+        assert!(app.modal.is_none());
+        assert!(app.running);
+    }
+
+    #[tokio::test]
+    async fn test_enter_switches_theme() {
+        let mut app = unreachable_app();
+        type_text(&mut app, ":theme high").await;
+
+        Handler::handle_key(&mut app, key(KeyCode::Enter))
+            .await
+            .unwrap();
+
+        assert!(app.modal.is_none());
+        assert_eq!(app.theme, ThemePreset::HighContrast.theme());
+    }
+
+    #[tokio::test]
+    async fn test_palette_lists_profiles() {
+        let mut app = unreachable_app();
+        app.profiles.insert(
+            "nas".to_string(),
+            crate::config::Profile {
+                host: "nas.local".to_string(),
+                ..crate::config::Profile::default()
+            },
+        );
+        type_text(&mut app, ":profile nas").await;
+
+        let Some(ActiveModal::CommandPalette(ref palette)) = app.modal else {
+            panic!("palette should be open");
+        };
+        assert_eq!(
+            palette.selected_item(),
+            Some(PaletteItem::Profile("nas".to_string()))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_esc_closes_palette() {
+        let mut app = unreachable_app();
+        type_text(&mut app, ":").await;
+
+        Handler::handle_key(&mut app, key(KeyCode::Esc))
+            .await
+            .unwrap();
+
+        assert!(app.modal.is_none());
+    }
+}
@@ -0,0 +1,114 @@
+//! Bracketed paste: text pasted into the terminal arrives as one
+//! event, however long, rather than as a burst of keys.
+
+use super::Handler;
+use crate::app::state::{ActiveModal, App};
+use crate::error::Result;
+use crate::ui::LargestPrompt;
+use crossterm::event::{KeyCode, KeyEvent};
+use tracing::debug;
+
+impl Handler {
+    /// Inserts pasted text into the open modal's text field. The
+    /// remote form and the password prompt take it at once; other
+    /// fields get it typed in, a character at a time. Outside a text
+    /// field the paste is dropped, so pasted text never fires
+    /// keybindings.
+    ///
+    /// # Errors
+    /// Returns error if rclone API calls fail.
+    pub(super) async fn handle_paste(app: &mut App, text: &str) -> Result<()> {
+        match app.modal {
+            Some(ActiveModal::CreateRemote(ref mut modal)) => {
+                if !modal.waiting && modal.paste(text) {
+                    modal.error = None;
+                    Self::list_upstream_dirs(app);
+                }
+            }
+            Some(ActiveModal::ConfigPassword(ref mut modal)) => {
+                if !modal.waiting {
+                    modal.paste(text);
+                }
+            }
+            Some(ActiveModal::ConfirmDeleteRemote { ref mut modal, .. }) => {
+                if modal.expected.is_some() {
+                    modal.paste(text);
+                }
+            }
+            _ => {
+                for c in text.chars().filter(|c| !c.is_control()) {
+                    if !Self::takes_text(app) {
+                        debug!(len = text.len(), "paste outside a text field dropped");
+                        break;
+                    }
+                    Self::handle_key(app, KeyEvent::from(KeyCode::Char(c))).await?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns whether the open modal has a text field that typed
+    /// characters go to.
+    fn takes_text(app: &App) -> bool {
+        match app.modal {
+            Some(ActiveModal::FileOperation(ref modal)) => modal.needs_input(),
+            Some(ActiveModal::Mounts(ref view)) => view.form.is_some(),
+            Some(ActiveModal::Compare(ref view)) => view.form.is_some(),
+            Some(ActiveModal::RemoteInfo(ref view)) => view.editing.is_some(),
+            Some(ActiveModal::Metadata(ref view)) => view.edit.is_some() && !view.saving,
+            Some(ActiveModal::Largest(ref view)) => {
+                matches!(view.prompt, Some(LargestPrompt::Move(_)))
+            }
+            Some(
+                ActiveModal::BatchRename(_)
+                | ActiveModal::GoTo(_)
+                | ActiveModal::Recent(_)
+                | ActiveModal::Filter(_)
+                | ActiveModal::MarkGlob(_)
+                | ActiveModal::ConfigWizard(_)
+                | ActiveModal::CopyRemote(_)
+                | ActiveModal::CopyUrl(_)
+                | ActiveModal::CommandPalette(_),
+            ) => true,
+            _ => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::handler::test_support::unreachable_app;
+    use crate::ui::{CreateRemoteModal, CreateRemoteMode, GlobPrompt};
+
+    #[tokio::test]
+    async fn test_paste_fills_the_focused_field() {
+        let mut app = unreachable_app();
+        app.modal = Some(ActiveModal::CreateRemote(Box::new(CreateRemoteModal::new(
+            CreateRemoteMode::Create,
+        ))));
+        let token = "eyJhbGciOi.".repeat(40);
+        Handler::handle_paste(&mut app, &format!("{}\n", token))
+            .await
+            .unwrap();
+        let Some(ActiveModal::CreateRemote(ref modal)) = app.modal else {
+            panic!("remote form closed");
+        };
+        assert_eq!(modal.name, token);
+    }
+
+    #[tokio::test]
+    async fn test_paste_types_into_text_fields_only() {
+        let mut app = unreachable_app();
+        Handler::handle_paste(&mut app, "+*.jpg").await.unwrap();
+        assert!(app.modal.is_none());
+
+        app.modal = Some(ActiveModal::MarkGlob(GlobPrompt::new(Vec::new())));
+        Handler::handle_paste(&mut app, "*.jpg").await.unwrap();
+        let Some(ActiveModal::MarkGlob(ref prompt)) = app.modal else {
+            panic!("glob prompt closed");
+        };
+        assert_eq!(prompt.pattern, "*.jpg");
+    }
+}
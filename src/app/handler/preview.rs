@@ -0,0 +1,123 @@
+//! File preview handling.
+
+use super::Handler;
+use crate::app::state::{ActiveModal, App};
+use crate::ui::{Preview, PreviewWidget};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use tracing::debug;
+
+impl Handler {
+    /// Opens the preview for the selected file and starts fetching
+    /// its content. Directories are not previewed.
+    pub(super) fn handle_preview(app: &mut App) {
+        let (Some(remote), Some(item), Some(path)) = (
+            app.current_remote.clone(),
+            app.files.get(app.files_selected),
+            app.selected_path(),
+        ) else {
+            return;
+        };
+        if item.is_dir() {
+            return;
+        }
+        debug!(remote = %remote, path = %path, "opening preview");
+        let size = u64::try_from(item.size).unwrap_or(0);
+        app.modal = Some(ActiveModal::Preview(Preview::new(&remote, &path, size)));
+        app.request_preview(remote, path);
+    }
+
+    /// Handles keys while the preview is open: vim-style scrolling,
+    /// and `Esc`, `q` or `p` to close.
+    pub(super) fn handle_preview_key(app: &mut App, key: KeyEvent) {
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+        let page = usize::from(app.screen.height.saturating_sub(2) / 2).max(1);
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q' | 'p') => {
+                debug!("closing preview");
+                app.modal = None;
+            }
+            KeyCode::Char('d') if ctrl => Self::scroll_preview(app, |s| s.saturating_add(page)),
+            KeyCode::Char('u') if ctrl => Self::scroll_preview(app, |s| s.saturating_sub(page)),
+            KeyCode::Char('j') | KeyCode::Down => {
+                Self::scroll_preview(app, |s| s.saturating_add(1));
+            }
+            KeyCode::Char('k') | KeyCode::Up => {
+                Self::scroll_preview(app, |s| s.saturating_sub(1));
+            }
+            KeyCode::PageDown => Self::scroll_preview(app, |s| s.saturating_add(page)),
+            KeyCode::PageUp => Self::scroll_preview(app, |s| s.saturating_sub(page)),
+            KeyCode::Char('g') | KeyCode::Home => Self::scroll_preview(app, |_| 0),
+            KeyCode::Char('G') | KeyCode::End => Self::scroll_preview(app, |_| usize::MAX),
+            _ => {}
+        }
+    }
+
+    /// Applies `to` to the preview's scroll offset, clamped to the
+    /// content.
+    pub(super) fn scroll_preview(app: &mut App, to: impl FnOnce(usize) -> usize) {
+        let screen = app.screen;
+        if let Some(ActiveModal::Preview(ref mut preview)) = app.modal {
+            let max = PreviewWidget::max_scroll(preview, screen);
+            preview.scroll = to(preview.scroll).min(max);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::handler::test_support::{files_app, key, unreachable_app};
+    use ratatui::layout::Rect;
+
+    #[tokio::test]
+    async fn test_p_opens_preview_of_selected_file() {
+        let mut app = files_app("docs", &["sub/", "a.bin"]);
+        app.files_selected = 1;
+
+        Handler::handle_key(&mut app, key(KeyCode::Char('i')))
+            .await
+            .unwrap();
+
+        match app.modal {
+            Some(ActiveModal::Preview(ref preview)) => {
+                assert_eq!(preview.remote, "gdrive");
+                assert_eq!(preview.path, "docs/a.bin");
+            }
+            ref other => panic!("expected preview, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_directories_are_not_previewed() {
+        let mut app = files_app("docs", &["sub/", "a.bin"]);
+
+        Handler::handle_key(&mut app, key(KeyCode::Char('i')))
+            .await
+            .unwrap();
+
+        assert!(app.modal.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_scrolls_hex_dump_and_closes() {
+        let mut app = unreachable_app();
+        app.set_screen(Rect::new(0, 0, 80, 10));
+        let mut preview = Preview::new("gdrive", "docs/a.bin", 1024);
+        preview.set_bytes(vec![0; 1024]);
+        app.modal = Some(ActiveModal::Preview(preview));
+
+        Handler::handle_key(&mut app, key(KeyCode::Char('G')))
+            .await
+            .unwrap();
+        match app.modal {
+            // 64 rows of 16 bytes, 8 visible.
+            Some(ActiveModal::Preview(ref preview)) => assert_eq!(preview.scroll, 56),
+            ref other => panic!("expected preview, got {:?}", other),
+        }
+
+        Handler::handle_key(&mut app, key(KeyCode::Esc))
+            .await
+            .unwrap();
+        assert!(app.modal.is_none());
+    }
+}
@@ -0,0 +1,152 @@
+//! Quitting while work is pending: the quit dialog lists the daemon's
+//! running jobs and offers to wait for them, leave them running, or
+//! stop them first.
+
+use super::Handler;
+use super::jobs::JOBS_REFRESH;
+use crate::app::state::{ActiveModal, App};
+use crate::error::Result;
+use crate::rclone::{Job, JobState};
+use crate::ui::{QuitChoice, QuitModal};
+use crossterm::event::{KeyCode, KeyEvent};
+use std::time::Instant;
+use tracing::{debug, info, warn};
+
+impl Handler {
+    /// Quits, unless LazyFile still has changes to files in flight;
+    /// then the daemon's jobs are listed first and the quit dialog
+    /// opens.
+    pub(super) fn handle_quit(app: &mut App) {
+        info!("quit requested");
+        if app.workers.transfers() == 0 {
+            app.running = false;
+            return;
+        }
+        app.request_quit_check(false);
+    }
+
+    /// Opens the quit dialog, or while it waits, quits once the work
+    /// is done.
+    pub(super) fn apply_quit_check(app: &mut App, waiting: bool, result: Result<Vec<Job>>) {
+        let jobs: Vec<Job> = match result {
+            Ok(jobs) => jobs
+                .into_iter()
+                .filter(|job| job.state() == JobState::Running)
+                .collect(),
+            Err(e) => {
+                warn!(error = %e, "listing jobs before quitting failed");
+                Vec::new()
+            }
+        };
+        let transfers = app.workers.transfers();
+
+        if waiting {
+            let Some(ActiveModal::Quit(ref mut modal)) = app.modal else {
+                return;
+            };
+            modal.loading = false;
+            modal.jobs = jobs;
+            modal.transfers = transfers;
+            if modal.jobs.is_empty() && (modal.cancelling || modal.transfers == 0) {
+                info!("pending work done, quitting");
+                app.running = false;
+            } else {
+                modal.next_refresh = Some(Instant::now() + JOBS_REFRESH);
+            }
+            return;
+        }
+
+        if jobs.is_empty() && transfers == 0 {
+            app.running = false;
+        } else if app.modal.is_none() {
+            debug!(jobs = jobs.len(), transfers, "opening quit dialog");
+            app.modal = Some(ActiveModal::Quit(Box::new(QuitModal::new(
+                jobs,
+                transfers,
+                // A kept daemon finishes its jobs after LazyFile exits.
+                app.owns_daemon && !app.keep_daemon,
+            ))));
+        }
+    }
+
+    /// Handles keyboard input in the quit dialog.
+    pub(super) fn handle_quit_key(app: &mut App, key: KeyEvent) {
+        let Some(ActiveModal::Quit(ref mut modal)) = app.modal else {
+            return;
+        };
+        let choice = match key.code {
+            KeyCode::Esc => {
+                debug!("quit dialog closed");
+                app.modal = None;
+                return;
+            }
+            KeyCode::Left | KeyCode::BackTab | KeyCode::Char('h') => {
+                modal.step(-1);
+                return;
+            }
+            KeyCode::Right | KeyCode::Tab | KeyCode::Char('l') => {
+                modal.step(1);
+                return;
+            }
+            KeyCode::Char('w') => QuitChoice::Wait,
+            KeyCode::Char('d') if !modal.owns_daemon => QuitChoice::Detach,
+            KeyCode::Char('c') => QuitChoice::Cancel,
+            KeyCode::Enter => modal.selected,
+            _ => return,
+        };
+        modal.selected = choice;
+        match choice {
+            QuitChoice::Wait => {
+                info!("waiting for pending work before quitting");
+                modal.waiting = true;
+                modal.next_refresh = Some(Instant::now());
+            }
+            QuitChoice::Detach => {
+                info!(jobs = modal.jobs.len(), "quitting, leaving jobs running");
+                app.running = false;
+            }
+            QuitChoice::Cancel => {
+                info!(jobs = modal.jobs.len(), "stopping jobs before quitting");
+                modal.waiting = true;
+                modal.cancelling = true;
+                modal.next_refresh = Some(Instant::now() + JOBS_REFRESH);
+                let ids: Vec<i64> = modal.jobs.iter().map(|job| job.id).collect();
+                for id in ids {
+                    app.request_stop_job(id);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::event::AppEvent;
+    use crate::app::handler::test_support::{key, unreachable_app};
+
+    #[tokio::test]
+    async fn test_asks_before_dropping_changes_in_flight() {
+        let mut app = unreachable_app();
+        app.workers.spawn_transfer(std::future::pending());
+
+        Handler::handle_key(&mut app, key(KeyCode::Char('q')))
+            .await
+            .unwrap();
+        assert!(app.running);
+        let Some(AppEvent::QuitChecked { waiting, result }) = app.wait_event().await else {
+            panic!("no quit check");
+        };
+        Handler::apply_quit_check(&mut app, waiting, result);
+        let Some(ActiveModal::Quit(ref modal)) = app.modal else {
+            panic!("quit dialog not open");
+        };
+        assert_eq!(modal.transfers, 1);
+        assert!(app.running);
+
+        Handler::handle_key(&mut app, key(KeyCode::Char('d')))
+            .await
+            .unwrap();
+        assert!(!app.running);
+    }
+}
@@ -0,0 +1,140 @@
+//! Jumping back to a recently visited location.
+
+use super::Handler;
+use crate::app::command::rclone_path;
+use crate::app::state::{ActiveModal, App};
+use crate::ui::RecentPicker;
+use crate::ui::widgets::compare::split_location;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use tracing::{debug, info, warn};
+
+impl Handler {
+    /// Opens the recent locations picker, leaving out the location on
+    /// screen.
+    pub(super) fn handle_recent(app: &mut App) {
+        let current = app
+            .current_remote
+            .as_deref()
+            .map(|remote| rclone_path(remote, &app.current_path));
+        let locations = app
+            .state
+            .recent
+            .iter()
+            .filter(|location| Some(location.as_str()) != current.as_deref())
+            .cloned()
+            .collect();
+        debug!("opening recent locations");
+        app.modal = Some(ActiveModal::Recent(RecentPicker::new(locations)));
+    }
+
+    /// Handles keyboard input in the recent locations picker.
+    pub(super) fn handle_recent_key(app: &mut App, key: KeyEvent) {
+        let Some(ActiveModal::Recent(ref mut picker)) = app.modal else {
+            return;
+        };
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+        match key.code {
+            KeyCode::Esc => {
+                debug!("closing recent locations");
+                app.modal = None;
+            }
+            KeyCode::Down | KeyCode::Tab => picker.select_next(),
+            KeyCode::Up | KeyCode::BackTab => picker.select_prev(),
+            KeyCode::Char('n') if ctrl => picker.select_next(),
+            KeyCode::Char('p') if ctrl => picker.select_prev(),
+            KeyCode::Char('x') if ctrl => {
+                if let Some(location) = picker.selected_location() {
+                    picker.remove(&location);
+                    app.forget_location(&location);
+                }
+            }
+            KeyCode::Backspace => picker.pop_char(),
+            KeyCode::Char(c) if !ctrl => picker.push_char(c),
+            KeyCode::Enter => {
+                let Some(location) = picker.selected_location() else {
+                    return;
+                };
+                app.modal = None;
+                Self::open_recent(app, &location);
+            }
+            _ => {}
+        }
+    }
+
+    /// Lists a remembered location. One whose remote is gone is
+    /// forgotten instead.
+    fn open_recent(app: &mut App, location: &str) {
+        let target = split_location(location).ok().and_then(|(remote, path)| {
+            let index = app.remotes.iter().position(|name| *name == remote)?;
+            Some((index, remote, path))
+        });
+        let Some((index, remote, path)) = target else {
+            warn!(location, "recent location no longer exists");
+            app.forget_location(location);
+            app.notifications
+                .warning(format!("{} is no longer available", location));
+            return;
+        };
+        info!(location, "going to recent location");
+        app.remotes_selected = index;
+        app.request_files(remote, path);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::handler::test_support::{key, remotes_app};
+
+    #[tokio::test]
+    async fn test_two_keys_return_to_previous_location() {
+        let mut app = remotes_app(&["gdrive", "s3"]);
+        app.current_remote = Some("gdrive".to_string());
+        app.current_path = "docs".to_string();
+        app.state.recent = vec![
+            "gdrive:docs".to_string(),
+            "s3:backup/2024".to_string(),
+            "b2:old".to_string(),
+        ];
+        for event in [
+            KeyEvent::new(KeyCode::Char('r'), KeyModifiers::CONTROL),
+            key(KeyCode::Enter),
+        ] {
+            Handler::handle_key(&mut app, event).await.unwrap();
+        }
+        assert!(app.modal.is_none());
+        assert_eq!(app.remotes_selected, 1);
+        let loading = app.loading.as_ref().unwrap();
+        assert_eq!(
+            (loading.remote.as_str(), loading.path.as_str()),
+            ("s3", "backup/2024")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_missing_remote_is_forgotten() {
+        let mut app = remotes_app(&["gdrive", "s3"]);
+        app.current_remote = Some("gdrive".to_string());
+        app.current_path = "docs".to_string();
+        app.state.recent = vec![
+            "gdrive:docs".to_string(),
+            "s3:backup/2024".to_string(),
+            "b2:old".to_string(),
+        ];
+        Handler::handle_recent(&mut app);
+        for c in "b2".chars() {
+            Handler::handle_key(&mut app, key(KeyCode::Char(c)))
+                .await
+                .unwrap();
+        }
+        Handler::handle_key(&mut app, key(KeyCode::Enter))
+            .await
+            .unwrap();
+        assert!(app.loading.is_none());
+        assert_eq!(app.state.recent, ["gdrive:docs", "s3:backup/2024"]);
+        assert_eq!(
+            app.notifications.toasts().last().unwrap().message,
+            "b2:old is no longer available"
+        );
+    }
+}
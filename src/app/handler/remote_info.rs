@@ -0,0 +1,200 @@
+//! Remote details handling.
+
+use super::Handler;
+use crate::app::state::{ActiveModal, App};
+use crate::error::Result;
+use crate::rclone::FsInfo;
+use crate::ui::{RemoteInfo, text};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use std::collections::HashMap;
+use tracing::{debug, info, warn};
+
+impl Handler {
+    /// Starts fetching the selected remote's details; the view opens
+    /// once they arrive.
+    pub(super) fn handle_remote_info(app: &mut App) {
+        if let Some(remote) = app.remotes.get(app.remotes_selected) {
+            info!(remote = %remote, "showing remote details");
+            app.request_remote_info(remote.clone());
+        }
+    }
+
+    /// Handles keys while the details are open: `j`/`k` scroll, `p`
+    /// edits the start path, and `Esc`, `q` or `i` close.
+    pub(super) fn handle_remote_info_key(app: &mut App, key: KeyEvent) {
+        let Some(ActiveModal::RemoteInfo(ref mut view)) = app.modal else {
+            return;
+        };
+        if view.editing.is_some() {
+            Self::handle_start_path_key(app, key);
+            return;
+        }
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q' | 'i') => app.modal = None,
+            KeyCode::Char('p') => view.editing = Some(view.start_path.clone()),
+            KeyCode::Char('j') | KeyCode::Down => view.scroll_to(view.scroll.saturating_add(1)),
+            KeyCode::Char('k') | KeyCode::Up => view.scroll_to(view.scroll.saturating_sub(1)),
+            KeyCode::Char('g') | KeyCode::Home => view.scroll_to(0),
+            KeyCode::Char('G') | KeyCode::End => view.scroll_to(usize::MAX),
+            _ => {}
+        }
+    }
+
+    /// Handles keys while the start path is typed. `Enter` saves it
+    /// to the config file; an empty path opens the remote at its root.
+    fn handle_start_path_key(app: &mut App, key: KeyEvent) {
+        let Some(ActiveModal::RemoteInfo(ref mut view)) = app.modal else {
+            return;
+        };
+        let Some(ref mut input) = view.editing else {
+            return;
+        };
+        match key.code {
+            KeyCode::Esc => view.editing = None,
+            KeyCode::Char('u') if key.modifiers.contains(KeyModifiers::CONTROL) => input.clear(),
+            KeyCode::Char(c) if !key.modifiers.contains(KeyModifiers::CONTROL) => input.push(c),
+            KeyCode::Backspace => {
+                text::pop_grapheme(input);
+            }
+            KeyCode::Enter => {
+                let input = view.editing.take().unwrap_or_default();
+                let remote = view.name.clone();
+                let result = app.set_start_path(&remote, &input);
+                let path = app.remote_layout.start_path(&remote).to_string();
+                if let Some(ActiveModal::RemoteInfo(ref mut view)) = app.modal {
+                    view.start_path = path.clone();
+                }
+                match result {
+                    Ok(()) if path.is_empty() => app
+                        .notifications
+                        .info(format!("'{}' opens at its root", remote)),
+                    Ok(()) => app
+                        .notifications
+                        .info(format!("'{}' opens at {}:{}", remote, remote, path)),
+                    Err(e) => {
+                        warn!(error = %e, "could not save remote layout");
+                        app.notifications.error(e.to_string());
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Opens the details view, unless another modal opened while they
+    /// were fetched. Features that could not be fetched are shown as
+    /// missing rather than failing the view.
+    pub(super) fn apply_remote_info(
+        app: &mut App,
+        name: String,
+        config: Result<HashMap<String, String>>,
+        info: Result<FsInfo>,
+    ) {
+        match config {
+            Ok(_) if app.modal.is_some() => {
+                debug!(remote = %name, "modal open, not opening remote details");
+            }
+            Ok(config) => {
+                if let Err(ref e) = info {
+                    warn!(remote = %name, error = %e, "could not fetch backend features");
+                }
+                let info = info.map_err(|e| e.to_string());
+                let mut view = RemoteInfo::new(name, config, info, &app.providers);
+                view.start_path = app.remote_layout.start_path(&view.name).to_string();
+                app.modal = Some(ActiveModal::RemoteInfo(view));
+            }
+            Err(e) => {
+                warn!(remote = %name, error = %e, "could not load remote settings");
+                app.notifications
+                    .error(format!("Could not load remote '{}': {}", name, e));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::event::AppEvent;
+    use crate::app::handler::test_support::{key, unreachable_app};
+
+    #[tokio::test]
+    async fn test_details_open_without_features() {
+        let mut app = unreachable_app();
+        let config = HashMap::from([("type".to_string(), "s3".to_string())]);
+        let unreachable = crate::error::LazyFileError::RcloneApi {
+            endpoint: "operations/fsinfo",
+            message: "timeout".to_string(),
+        };
+
+        Handler::handle_app_event(
+            &mut app,
+            AppEvent::RemoteInfoLoaded {
+                name: "s3".to_string(),
+                config: Ok(config),
+                info: Err(unreachable),
+            },
+        );
+
+        let Some(ActiveModal::RemoteInfo(ref view)) = app.modal else {
+            panic!("details not open");
+        };
+        assert_eq!(view.remote_type, "s3");
+        assert!(view.info.is_err());
+
+        Handler::handle_key(&mut app, key(KeyCode::Char('i')))
+            .await
+            .unwrap();
+        assert!(app.modal.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_edits_start_path() {
+        let mut app = unreachable_app();
+        app.remotes = vec!["gdrive".to_string()];
+        let config = HashMap::from([("type".to_string(), "drive".to_string())]);
+        Handler::handle_app_event(
+            &mut app,
+            AppEvent::RemoteInfoLoaded {
+                name: "gdrive".to_string(),
+                config: Ok(config),
+                info: Ok(FsInfo::default()),
+            },
+        );
+        for c in "p/Work".chars() {
+            Handler::handle_key(&mut app, key(KeyCode::Char(c)))
+                .await
+                .unwrap();
+        }
+        Handler::handle_key(&mut app, key(KeyCode::Enter))
+            .await
+            .unwrap();
+
+        let Some(ActiveModal::RemoteInfo(ref view)) = app.modal else {
+            panic!("details closed");
+        };
+        assert_eq!(view.start_path, "Work");
+        assert_eq!(app.remote_layout.start_paths, ["gdrive:Work"]);
+
+        app.modal = None;
+        Handler::handle_key(&mut app, key(KeyCode::Enter))
+            .await
+            .unwrap();
+        assert_eq!(app.loading.as_ref().unwrap().path, "Work");
+    }
+
+    #[tokio::test]
+    async fn test_failed_fetch_shows_toast() {
+        let mut app = unreachable_app();
+        app.remotes = vec!["gdrive".to_string()];
+        Handler::handle_key(&mut app, key(KeyCode::Char('i')))
+            .await
+            .unwrap();
+
+        let event = app.wait_event().await.unwrap();
+        assert!(matches!(event, AppEvent::RemoteInfoLoaded { .. }));
+        Handler::handle_app_event(&mut app, event);
+        assert!(app.modal.is_none());
+        assert_eq!(app.notifications.toasts().count(), 1);
+    }
+}
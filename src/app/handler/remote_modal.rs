@@ -1,15 +1,17 @@
-//! Remote modal handling (create, edit, delete, confirm).
+//! Remote modal handling (create, edit).
 
 use super::Handler;
 use crate::app::state::{ActiveModal, App};
 use crate::error::Result;
-use crate::ui::{ConfirmModal, CreateRemoteModal, CreateRemoteMode};
-use crossterm::event::{KeyCode, KeyEvent};
+use crate::rclone::Provider;
+use crate::ui::{ConfigWizard, CreateRemoteModal, CreateRemoteMode, Edit, RemoteField};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use std::collections::HashMap;
-use tracing::{debug, info};
+use tracing::{debug, info, warn};
 
 impl Handler {
     /// Handles keyboard input while the create/edit modal is open.
+    /// Only `Esc` works while an edit is being saved.
     pub(super) async fn handle_modal_key(app: &mut App, key: KeyEvent) -> Result<()> {
         if let Some(ActiveModal::CreateRemote(ref mut modal)) = app.modal {
             match key.code {
@@ -17,141 +19,249 @@ impl Handler {
                     debug!("closing create remote modal");
                     app.modal = None;
                 }
+                _ if modal.waiting => {}
                 KeyCode::Tab => {
-                    modal.next_field();
+                    // Focus stays on a completed upstream, which may go
+                    // on into its folders.
+                    let completed = modal.picks_upstream() && modal.accept_upstream();
+                    if !completed {
+                        if modal.focus_field == RemoteField::Type {
+                            modal.accept_type();
+                        }
+                        modal.next_field();
+                    }
                 }
                 KeyCode::BackTab => {
                     modal.prev_field();
                 }
-                KeyCode::Char(c) => {
-                    modal.input_char(c);
-                    modal.error = None;
+                KeyCode::Down if modal.focus_field == RemoteField::Type => {
+                    modal.select_next_type();
+                }
+                KeyCode::Up if modal.focus_field == RemoteField::Type => {
+                    modal.select_prev_type();
                 }
-                KeyCode::Backspace => {
-                    modal.backspace();
-                    modal.error = None;
+                KeyCode::Down if modal.picks_upstream() => modal.select_next_upstream(),
+                KeyCode::Up if modal.picks_upstream() => modal.select_prev_upstream(),
+                KeyCode::Down => modal.next_example(),
+                KeyCode::Up => modal.prev_example(),
+                KeyCode::Char('a') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                    modal.toggle_advanced();
                 }
                 KeyCode::Enter => {
-                    Self::handle_modal_submit(app).await?;
+                    // A half-typed type is completed first, so the user
+                    // sees what will be created.
+                    if modal.focus_field == RemoteField::Type && modal.accept_type() {
+                        return Ok(());
+                    }
+                    if !(modal.picks_upstream() && modal.accept_upstream()) {
+                        Self::handle_modal_submit(app).await?;
+                    }
+                }
+                _ => {
+                    if modal.edit(key) == Edit::Changed {
+                        modal.error = None;
+                    }
                 }
-                _ => {}
             }
+            Self::list_upstream_dirs(app);
         }
         Ok(())
     }
 
+    /// Lists the folders the upstream picker needs next, if any.
+    pub(super) fn list_upstream_dirs(app: &mut App) {
+        if let Some(ActiveModal::CreateRemote(ref mut modal)) = app.modal
+            && let Some((remote, path)) = modal.dirs_to_list()
+        {
+            app.request_upstream_dirs(remote, path);
+        }
+    }
+
+    /// Hands listed folders to the upstream picker. Without them the
+    /// path is typed in full, so a failure is only logged.
+    pub(super) fn apply_upstream_dirs(app: &mut App, location: &str, result: Result<Vec<String>>) {
+        match result {
+            Ok(dirs) => {
+                if let Some(ActiveModal::CreateRemote(ref mut modal)) = app.modal {
+                    modal.set_dirs(location, dirs);
+                }
+            }
+            Err(e) => warn!(location, error = %e, "could not list upstream folders"),
+        }
+    }
+
     /// Handles modal submission.
     async fn handle_modal_submit(app: &mut App) -> Result<()> {
-        let Some(ActiveModal::CreateRemote(modal)) = app.modal.take() else {
+        let Some(ActiveModal::CreateRemote(mut modal)) = app.modal.take() else {
             return Ok(());
         };
 
-        if !modal.is_valid() {
-            app.modal = Some(ActiveModal::CreateRemote(CreateRemoteModal {
-                error: Some("Name and Type are required".to_string()),
-                ..modal
-            }));
+        if let Some(error) = Self::modal_error(app, &modal) {
+            modal.error = Some(error);
+            app.modal = Some(ActiveModal::CreateRemote(modal));
             return Ok(());
         }
 
-        let mut params = HashMap::new();
-        if !modal.path.is_empty() {
-            params.insert("path".to_string(), modal.path.clone());
-        }
-
+        let params = modal.parameters();
         let name = modal.name.clone();
         let remote_type = modal.remote_type.clone();
-        let mode = modal.mode;
 
-        match mode {
+        match modal.mode {
             CreateRemoteMode::Create => {
                 info!(remote = %name, "creating remote");
-                if let Err(e) = app.client.create_remote(&name, &remote_type, params).await {
-                    app.modal = Some(ActiveModal::CreateRemote(CreateRemoteModal {
-                        error: Some(format!("Error: {}", e)),
-                        ..modal
-                    }));
-                    return Ok(());
-                }
+                app.modal = Some(ActiveModal::ConfigWizard(Box::new(ConfigWizard::new(
+                    name.clone(),
+                    remote_type.clone(),
+                ))));
+                app.request_remote_config(name, remote_type, params);
+            }
+            CreateRemoteMode::Edit if params.is_empty() => {
+                debug!(remote = %name, "no settings changed");
+                app.notifications
+                    .info(format!("No changes to remote '{}'", name));
             }
             CreateRemoteMode::Edit => {
-                info!(remote = %name, "updating remote");
-                if let Err(e) = app.client.update_remote(&name, params).await {
-                    app.modal = Some(ActiveModal::CreateRemote(CreateRemoteModal {
-                        error: Some(format!("Error: {}", e)),
-                        ..modal
-                    }));
-                    return Ok(());
-                }
+                modal.waiting = true;
+                app.modal = Some(ActiveModal::CreateRemote(modal));
+                app.request_update_remote(name, params);
             }
         }
-
-        app.load_remotes().await?;
         Ok(())
     }
 
-    /// Opens the edit remote modal.
-    pub(super) async fn handle_edit_remote(app: &mut App) -> Result<()> {
+    /// Returns why the form cannot be submitted as it is, if it
+    /// cannot.
+    fn modal_error(app: &App, modal: &CreateRemoteModal) -> Option<String> {
+        if !modal.is_valid() {
+            return Some("Name and Type are required".to_string());
+        }
+        if let Some(reason) = modal.name_error() {
+            return Some(format!("Name: {}", reason));
+        }
+        if modal.mode == CreateRemoteMode::Create
+            && !modal.providers.is_empty()
+            && modal.provider().is_none()
+        {
+            return Some(format!("Unknown type '{}'", modal.remote_type));
+        }
+        if let Some(option) = modal.missing_required() {
+            return Some(format!("'{}' is required", option));
+        }
+        // Cancelling the wizard deletes the remote, which must not
+        // take an existing one with it.
+        if modal.mode == CreateRemoteMode::Create && app.remotes.contains(&modal.name) {
+            return Some(format!("Remote '{}' already exists", modal.name));
+        }
+        None
+    }
+
+    /// Opens the create remote modal, offering the fetched backends
+    /// for its type field and the existing remotes as upstreams.
+    pub(super) fn handle_add_remote(app: &mut App) {
+        debug!("opening create remote modal");
+        let modal = CreateRemoteModal::new(CreateRemoteMode::Create)
+            .with_providers(app.providers.clone())
+            .with_remotes(app.remotes.clone());
+        app.modal = Some(ActiveModal::CreateRemote(Box::new(modal)));
+    }
+
+    /// Fetches the selected remote's settings; the edit modal opens
+    /// once they arrive.
+    pub(super) fn handle_edit_remote(app: &mut App) {
         if let Some(remote) = app.remotes.get(app.remotes_selected) {
             info!(remote = %remote, "editing remote");
-            let modal = CreateRemoteModal::new(CreateRemoteMode::Edit)
-                .with_name(remote.clone())
-                .with_type("local".to_string());
-            app.modal = Some(ActiveModal::CreateRemote(modal));
+            app.request_remote_settings(remote.clone());
         }
-        Ok(())
     }
 
-    /// Opens the delete remote confirmation modal.
-    pub(super) fn handle_delete_remote(app: &mut App) {
-        if let Some(remote) = app.remotes.get(app.remotes_selected) {
-            debug!(remote = %remote, "opening delete confirmation");
-            app.modal = Some(ActiveModal::ConfirmDeleteRemote {
-                remote: remote.clone(),
-                modal: ConfirmModal::new("Delete Remote", format!("Delete '{}'?", remote)),
-            });
+    /// Opens the edit modal filled with a remote's settings, unless
+    /// another modal opened while they were fetched.
+    pub(super) fn apply_remote_settings(
+        app: &mut App,
+        name: String,
+        result: Result<HashMap<String, String>>,
+    ) {
+        match result {
+            Ok(_) if app.modal.is_some() => {
+                debug!(remote = %name, "modal open, not opening edit form");
+            }
+            Ok(config) => {
+                let modal = CreateRemoteModal::new(CreateRemoteMode::Edit)
+                    .with_name(name)
+                    .with_providers(app.providers.clone())
+                    .with_config(config);
+                app.modal = Some(ActiveModal::CreateRemote(Box::new(modal)));
+            }
+            Err(e) => {
+                warn!(remote = %name, error = %e, "could not load remote settings");
+                app.notifications
+                    .error(format!("Could not load remote '{}': {}", name, e));
+            }
         }
     }
 
-    /// Handles confirmation modal input.
-    pub(super) async fn handle_confirm_key(app: &mut App, key: KeyEvent) -> Result<()> {
-        if let Some(ActiveModal::ConfirmDeleteRemote { ref mut modal, .. }) = app.modal {
-            match key.code {
-                KeyCode::Esc => {
-                    debug!("cancelling delete");
-                    app.modal = None;
-                }
-                KeyCode::Tab | KeyCode::Right | KeyCode::Left => {
-                    modal.toggle();
-                }
-                KeyCode::Char(c) if c == 'y' || c == 'n' => {
-                    let confirmed = c == 'y';
-                    if confirmed != modal.is_confirmed() {
-                        modal.toggle();
-                    }
+    /// Keeps the fetched backends, handing them to an open create
+    /// form that has none yet. Without them the type is typed in
+    /// full, so a failure is only logged.
+    pub(super) fn apply_providers(app: &mut App, result: Result<Vec<Provider>>) {
+        match result {
+            Ok(providers) => {
+                if let Some(ActiveModal::CreateRemote(ref mut modal)) = app.modal
+                    && modal.mode == CreateRemoteMode::Create
+                    && modal.providers.is_empty()
+                {
+                    modal.providers = providers.clone();
                 }
-                KeyCode::Enter => {
-                    // Enter always closes the modal; the delete only runs
-                    // when "Yes" is selected.
-                    let confirmed = modal.is_confirmed();
-                    if let Some(ActiveModal::ConfirmDeleteRemote { remote, .. }) = app.modal.take()
-                        && confirmed
-                    {
-                        info!(remote = %remote, "deleting remote");
-                        app.client.delete_remote(&remote).await?;
-                        app.load_remotes().await?;
-                    }
+                app.providers = providers;
+            }
+            Err(e) => warn!(error = %e, "could not load providers"),
+        }
+    }
+
+    /// Stars or moves a remote with `change`, reporting a failure to
+    /// save the new layout.
+    pub(super) fn handle_remote_layout(app: &mut App, change: fn(&mut App) -> Result<()>) {
+        if let Err(e) = change(app) {
+            warn!(error = %e, "could not save remote layout");
+            app.notifications.error(e.to_string());
+        }
+    }
+
+    /// Closes the edit form and reloads the remotes once the changes
+    /// are saved; otherwise shows why saving failed in the form.
+    pub(super) fn apply_remote_updated(app: &mut App, name: &str, result: Result<()>) {
+        let form = match app.modal {
+            Some(ActiveModal::CreateRemote(ref mut modal)) if modal.waiting => Some(modal),
+            _ => None,
+        };
+        match (result, form) {
+            (Ok(()), form) => {
+                info!(remote = name, "remote updated");
+                if form.is_some() {
+                    app.modal = None;
                 }
-                _ => {}
+                app.notifications
+                    .success(format!("Updated remote '{}'", name));
+                app.request_remotes();
+            }
+            (Err(e), Some(form)) => {
+                warn!(remote = name, error = %e, "updating remote failed");
+                form.waiting = false;
+                form.error = Some(format!("Error: {}", e));
+            }
+            (Err(e), None) => {
+                warn!(remote = name, error = %e, "updating remote failed");
+                app.notifications
+                    .error(format!("Could not update remote '{}': {}", name, e));
             }
         }
-        Ok(())
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::app::handler::test_support::{key, unreachable_app};
     use crate::rclone::RcloneClient;
     use crossterm::event::{KeyCode, KeyEvent, KeyEventKind, KeyEventState, KeyModifiers};
 
@@ -195,56 +305,13 @@ mod tests {
         assert!(app.create_remote_modal().is_none());
     }
 
-    #[tokio::test]
-    async fn test_open_delete_remote_modal() {
-        let client = create_test_client();
-        let mut app = App::new(client);
-        app.focused_panel = crate::app::state::Panel::Remotes;
-        app.remotes = vec!["test_remote".to_string()];
-        app.remotes_selected = 0;
-        assert!(app.confirm_modal().is_none());
-
-        let key = create_key_event(KeyCode::Char('d'));
-        Handler::handle_key(&mut app, key).await.unwrap();
-
-        assert!(app.confirm_modal().is_some());
-        assert_eq!(app.pending_delete_remote(), Some("test_remote"));
-    }
-
-    #[tokio::test]
-    async fn test_delete_remote_no_remotes() {
-        let client = create_test_client();
-        let mut app = App::new(client);
-        app.focused_panel = crate::app::state::Panel::Remotes;
-        app.remotes = vec![];
-
-        let key = create_key_event(KeyCode::Char('d'));
-        Handler::handle_key(&mut app, key).await.unwrap();
-
-        assert!(app.confirm_modal().is_none());
-        assert!(app.pending_delete_remote().is_none());
-    }
-
-    #[tokio::test]
-    async fn test_delete_remote_key_ignored_in_files_panel() {
-        let client = create_test_client();
-        let mut app = App::new(client);
-        app.focused_panel = crate::app::state::Panel::Files;
-        app.remotes = vec!["test_remote".to_string()];
-
-        let key = create_key_event(KeyCode::Char('d'));
-        Handler::handle_key(&mut app, key).await.unwrap();
-
-        assert!(app.confirm_modal().is_none());
-    }
-
     #[tokio::test]
     async fn test_modal_input_char() {
         let client = create_test_client();
         let mut app = App::new(client);
-        app.modal = Some(ActiveModal::CreateRemote(CreateRemoteModal::new(
+        app.modal = Some(ActiveModal::CreateRemote(Box::new(CreateRemoteModal::new(
             CreateRemoteMode::Create,
-        )));
+        ))));
 
         let key = create_key_event(KeyCode::Char('a'));
         Handler::handle_key(&mut app, key).await.unwrap();
@@ -259,7 +326,7 @@ mod tests {
         let mut app = App::new(client);
         let mut modal = CreateRemoteModal::new(CreateRemoteMode::Create);
         modal.name = "test".to_string();
-        app.modal = Some(ActiveModal::CreateRemote(modal));
+        app.modal = Some(ActiveModal::CreateRemote(Box::new(modal)));
 
         let key = create_key_event(KeyCode::Backspace);
         Handler::handle_key(&mut app, key).await.unwrap();
@@ -272,9 +339,9 @@ mod tests {
     async fn test_modal_escape_closes() {
         let client = create_test_client();
         let mut app = App::new(client);
-        app.modal = Some(ActiveModal::CreateRemote(CreateRemoteModal::new(
+        app.modal = Some(ActiveModal::CreateRemote(Box::new(CreateRemoteModal::new(
             CreateRemoteMode::Create,
-        )));
+        ))));
 
         let key = create_key_event(KeyCode::Esc);
         Handler::handle_key(&mut app, key).await.unwrap();
@@ -287,7 +354,7 @@ mod tests {
         let client = create_test_client();
         let mut app = App::new(client);
         let modal = CreateRemoteModal::new(CreateRemoteMode::Create);
-        app.modal = Some(ActiveModal::CreateRemote(modal));
+        app.modal = Some(ActiveModal::CreateRemote(Box::new(modal)));
 
         let key = create_key_event(KeyCode::Tab);
         Handler::handle_key(&mut app, key).await.unwrap();
@@ -302,7 +369,7 @@ mod tests {
         let mut app = App::new(client);
         let mut modal = CreateRemoteModal::new(CreateRemoteMode::Create);
         modal.focus_field = crate::ui::RemoteField::Type;
-        app.modal = Some(ActiveModal::CreateRemote(modal));
+        app.modal = Some(ActiveModal::CreateRemote(Box::new(modal)));
 
         let key = create_key_event(KeyCode::BackTab);
         Handler::handle_key(&mut app, key).await.unwrap();
@@ -312,119 +379,213 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn test_confirm_modal_escape_closes() {
+    async fn test_modal_input_clears_error() {
         let client = create_test_client();
         let mut app = App::new(client);
-        app.modal = Some(ActiveModal::ConfirmDeleteRemote {
-            remote: "test".to_string(),
-            modal: ConfirmModal::new("Test", "Test message".to_string()),
-        });
+        let mut modal = CreateRemoteModal::new(CreateRemoteMode::Create);
+        modal.error = Some("Previous error".to_string());
+        app.modal = Some(ActiveModal::CreateRemote(Box::new(modal)));
 
-        let key = create_key_event(KeyCode::Esc);
+        let key = create_key_event(KeyCode::Char('a'));
         Handler::handle_key(&mut app, key).await.unwrap();
 
-        assert!(app.confirm_modal().is_none());
-        assert!(app.pending_delete_remote().is_none());
+        let modal = app.create_remote_modal().unwrap();
+        assert!(modal.error.is_none());
+    }
+
+    fn provider(name: &str, description: &str) -> Provider {
+        Provider {
+            name: name.to_string(),
+            description: description.to_string(),
+            ..Provider::default()
+        }
     }
 
     #[tokio::test]
-    async fn test_confirm_modal_tab_toggles() {
-        let client = create_test_client();
-        let mut app = App::new(client);
-        app.modal = Some(ActiveModal::ConfirmDeleteRemote {
-            remote: "test".to_string(),
-            modal: ConfirmModal::new("Test", "Test message".to_string()),
-        });
+    async fn test_add_remote_offers_providers() {
+        let mut app = unreachable_app();
+        app.providers = vec![provider("drive", "Google Drive")];
 
-        assert!(!app.confirm_modal().unwrap().is_confirmed());
+        Handler::handle_add_remote(&mut app);
 
-        let key = create_key_event(KeyCode::Tab);
-        Handler::handle_key(&mut app, key).await.unwrap();
+        assert_eq!(app.create_remote_modal().unwrap().providers.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_enter_on_type_completes_before_submitting() {
+        let mut app = unreachable_app();
+        let mut modal = CreateRemoteModal::new(CreateRemoteMode::Create)
+            .with_name("gdrive".to_string())
+            .with_type("goog".to_string())
+            .with_providers(vec![provider("drive", "Google Drive")]);
+        modal.focus_field = RemoteField::Type;
+        app.modal = Some(ActiveModal::CreateRemote(Box::new(modal)));
+
+        let event = key(KeyCode::Enter);
+        Handler::handle_key(&mut app, event).await.unwrap();
 
-        assert!(app.confirm_modal().unwrap().is_confirmed());
+        let modal = app.create_remote_modal().unwrap();
+        assert_eq!(modal.remote_type, "drive");
     }
 
     #[tokio::test]
-    async fn test_confirm_modal_left_right_toggle() {
-        let client = create_test_client();
-        let mut app = App::new(client);
-        app.modal = Some(ActiveModal::ConfirmDeleteRemote {
-            remote: "test".to_string(),
-            modal: ConfirmModal::new("Test", "Test message".to_string()),
-        });
+    async fn test_unknown_type_is_rejected() {
+        let mut app = unreachable_app();
+        let modal = CreateRemoteModal::new(CreateRemoteMode::Create)
+            .with_name("gdrive".to_string())
+            .with_type("nope".to_string())
+            .with_providers(vec![provider("drive", "Google Drive")]);
+        app.modal = Some(ActiveModal::CreateRemote(Box::new(modal)));
 
-        let key = create_key_event(KeyCode::Right);
-        Handler::handle_key(&mut app, key).await.unwrap();
-        assert!(app.confirm_modal().unwrap().is_confirmed());
+        let event = key(KeyCode::Enter);
+        Handler::handle_key(&mut app, event).await.unwrap();
 
-        let key = create_key_event(KeyCode::Left);
-        Handler::handle_key(&mut app, key).await.unwrap();
-        assert!(!app.confirm_modal().unwrap().is_confirmed());
+        let modal = app.create_remote_modal().unwrap();
+        assert!(modal.error.as_deref().unwrap().contains("Unknown type"));
     }
 
     #[tokio::test]
-    async fn test_confirm_modal_y_key() {
-        let client = create_test_client();
-        let mut app = App::new(client);
-        app.modal = Some(ActiveModal::ConfirmDeleteRemote {
-            remote: "test".to_string(),
-            modal: ConfirmModal::new("Test", "Test message".to_string()),
-        });
+    async fn test_missing_required_option_is_rejected() {
+        let mut app = unreachable_app();
+        let webdav = Provider {
+            options: Some(vec![crate::rclone::ConfigOption {
+                name: "url".to_string(),
+                required: true,
+                ..Default::default()
+            }]),
+            ..provider("webdav", "WebDAV")
+        };
+        let modal = CreateRemoteModal::new(CreateRemoteMode::Create)
+            .with_name("dav".to_string())
+            .with_type("webdav".to_string())
+            .with_providers(vec![webdav]);
+        app.modal = Some(ActiveModal::CreateRemote(Box::new(modal)));
 
-        let key = create_key_event(KeyCode::Char('y'));
-        Handler::handle_key(&mut app, key).await.unwrap();
+        let event = key(KeyCode::Enter);
+        Handler::handle_key(&mut app, event).await.unwrap();
 
-        assert!(app.confirm_modal().unwrap().is_confirmed());
+        let modal = app.create_remote_modal().unwrap();
+        assert_eq!(modal.error.as_deref(), Some("'url' is required"));
     }
 
     #[tokio::test]
-    async fn test_confirm_modal_n_key() {
-        let client = create_test_client();
-        let mut app = App::new(client);
-        let mut modal = ConfirmModal::new("Test", "Test message".to_string());
-        modal.toggle();
-        app.modal = Some(ActiveModal::ConfirmDeleteRemote {
-            remote: "test".to_string(),
-            modal,
-        });
-
-        let key = create_key_event(KeyCode::Char('n'));
-        Handler::handle_key(&mut app, key).await.unwrap();
+    async fn test_alias_upstream_is_picked_from_remotes() {
+        let mut app = unreachable_app();
+        app.remotes = vec!["gdrive".to_string()];
+        app.providers = vec![Provider {
+            options: Some(vec![crate::rclone::ConfigOption {
+                name: "remote".to_string(),
+                ..Default::default()
+            }]),
+            ..provider("alias", "Alias for an existing remote")
+        }];
+        Handler::handle_add_remote(&mut app);
+        if let Some(ActiveModal::CreateRemote(ref mut modal)) = app.modal {
+            modal.name = "docs".to_string();
+            modal.remote_type = "alias".to_string();
+            modal.focus_field = RemoteField::Setting(0);
+        }
+
+        Handler::handle_key(&mut app, key(KeyCode::Tab))
+            .await
+            .unwrap();
 
-        assert!(!app.confirm_modal().unwrap().is_confirmed());
+        let modal = app.create_remote_modal().unwrap();
+        assert_eq!(modal.value("remote"), "gdrive:");
+        assert_eq!(modal.focus_field, RemoteField::Setting(0));
+        assert_eq!(modal.dirs_location, "gdrive:");
     }
 
     #[tokio::test]
-    async fn test_modal_input_clears_error() {
-        let client = create_test_client();
-        let mut app = App::new(client);
-        let mut modal = CreateRemoteModal::new(CreateRemoteMode::Create);
-        modal.error = Some("Previous error".to_string());
-        app.modal = Some(ActiveModal::CreateRemote(modal));
+    async fn test_upstream_dirs_reach_open_form() {
+        let mut app = unreachable_app();
+        Handler::handle_add_remote(&mut app);
+        if let Some(ActiveModal::CreateRemote(ref mut modal)) = app.modal {
+            modal.dirs_location = "gdrive:".to_string();
+        }
 
-        let key = create_key_event(KeyCode::Char('a'));
-        Handler::handle_key(&mut app, key).await.unwrap();
+        Handler::handle_app_event(
+            &mut app,
+            crate::app::event::AppEvent::UpstreamDirsLoaded {
+                location: "gdrive:".to_string(),
+                result: Ok(vec!["docs".to_string()]),
+            },
+        );
+
+        assert_eq!(app.create_remote_modal().unwrap().dirs, ["docs"]);
+    }
+
+    #[tokio::test]
+    async fn test_edit_form_opens_with_settings() {
+        let mut app = unreachable_app();
+        let config = HashMap::from([
+            ("type".to_string(), "local".to_string()),
+            ("nounc".to_string(), "true".to_string()),
+        ]);
+
+        Handler::handle_app_event(
+            &mut app,
+            crate::app::event::AppEvent::RemoteSettingsLoaded {
+                name: "disk".to_string(),
+                result: Ok(config),
+            },
+        );
 
         let modal = app.create_remote_modal().unwrap();
-        assert!(modal.error.is_none());
+        assert_eq!(modal.mode, CreateRemoteMode::Edit);
+        assert_eq!(modal.remote_type, "local");
+        assert_eq!(modal.value("nounc"), "true");
     }
 
     #[tokio::test]
-    async fn test_open_modal_keys_ignored_while_confirm_open() {
-        // 'a' opens the create-remote modal only when no modal is open;
-        // with the confirmation active the key must not replace it.
-        let client = create_test_client();
-        let mut app = App::new(client);
-        app.focused_panel = crate::app::state::Panel::Remotes;
-        app.modal = Some(ActiveModal::ConfirmDeleteRemote {
-            remote: "test".to_string(),
-            modal: ConfirmModal::new("Test", "Test message".to_string()),
-        });
+    async fn test_unchanged_edit_is_not_sent() {
+        let mut app = unreachable_app();
+        let config = HashMap::from([
+            ("type".to_string(), "local".to_string()),
+            ("nounc".to_string(), "true".to_string()),
+        ]);
+        let modal = CreateRemoteModal::new(CreateRemoteMode::Edit)
+            .with_name("disk".to_string())
+            .with_config(config);
+        app.modal = Some(ActiveModal::CreateRemote(Box::new(modal)));
+
+        let event = key(KeyCode::Enter);
+        Handler::handle_key(&mut app, event).await.unwrap();
+
+        assert!(app.modal.is_none());
+        let toast = app.notifications.toasts().last().unwrap();
+        assert!(toast.message.contains("No changes"));
+    }
 
-        let key = create_key_event(KeyCode::Char('a'));
-        Handler::handle_key(&mut app, key).await.unwrap();
+    #[tokio::test]
+    async fn test_late_providers_reach_open_form() {
+        let mut app = unreachable_app();
+        Handler::handle_add_remote(&mut app);
 
-        assert!(app.confirm_modal().is_some());
-        assert!(app.create_remote_modal().is_none());
+        Handler::handle_app_event(
+            &mut app,
+            crate::app::event::AppEvent::ProvidersLoaded(Ok(vec![provider("s3", "Amazon S3")])),
+        );
+
+        assert_eq!(app.providers.len(), 1);
+        assert_eq!(app.create_remote_modal().unwrap().providers.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_failed_edit_keeps_form_open() {
+        let mut app = unreachable_app();
+        let mut modal = CreateRemoteModal::new(CreateRemoteMode::Edit);
+        modal.waiting = true;
+        app.modal = Some(ActiveModal::CreateRemote(Box::new(modal)));
+
+        let err = crate::error::LazyFileError::RcloneApi {
+            endpoint: "config/update",
+            message: "refused".to_string(),
+        };
+        Handler::apply_remote_updated(&mut app, "gdrive", Err(err));
+
+        let modal = app.create_remote_modal().unwrap();
+        assert!(!modal.waiting);
+        assert!(modal.error.as_deref().unwrap().contains("refused"));
     }
 }
@@ -0,0 +1,140 @@
+//! Running user scripts and commands from the command palette.
+
+use super::Handler;
+use crate::app::state::App;
+use crate::error::Result;
+use std::io;
+use std::process::ExitStatus;
+use tracing::{info, warn};
+
+impl Handler {
+    /// Starts the script `name` with the current selection.
+    pub(super) fn handle_run_script(app: &mut App, name: &str) {
+        if app.request_script(name) {
+            info!(script = name, "running script");
+            app.notifications.info(format!("Running {}", name));
+        } else {
+            app.notifications
+                .error(format!("Script {} no longer exists", name));
+        }
+    }
+
+    /// Leaves the command `name` for the launcher to run with the
+    /// current selection.
+    pub(super) fn handle_run_command(app: &mut App, name: &str) {
+        if !app.request_command(name) {
+            app.notifications
+                .error(format!("Command {} no longer exists", name));
+        }
+    }
+
+    /// Reports a finished script with the last line it printed, and
+    /// reloads the listing since the script may have changed it.
+    pub(super) fn apply_script_finished(app: &mut App, name: &str, result: Result<Vec<String>>) {
+        match result {
+            Ok(lines) => {
+                info!(script = name, lines = lines.len(), "script finished");
+                let message = match lines.last() {
+                    Some(line) => format!("{}: {}", name, line),
+                    None => format!("{} finished", name),
+                };
+                app.notifications.success(message);
+            }
+            Err(e) => {
+                warn!(script = name, error = %e, "script failed");
+                app.notifications.error(e.to_string());
+            }
+        }
+        app.reload_files();
+    }
+
+    /// Called by the launcher once a user command exits. Reports how
+    /// it ended and reloads the listing, which it may have changed.
+    pub fn finish_command(app: &mut App, name: &str, status: io::Result<ExitStatus>) {
+        match status {
+            Ok(status) if status.success() => {
+                info!(command = name, "command finished");
+                app.notifications.success(format!("{} finished", name));
+            }
+            Ok(status) => {
+                warn!(command = name, %status, "command failed");
+                app.notifications
+                    .warning(format!("{} exited with {}", name, status));
+            }
+            Err(e) => {
+                warn!(command = name, error = %e, "could not start command");
+                app.notifications
+                    .error(format!("Could not run {}: {}", name, e));
+            }
+        }
+        app.reload_files();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::handler::test_support::unreachable_app;
+    use crate::error::LazyFileError;
+
+    #[tokio::test]
+    async fn test_unknown_script_is_a_toast() {
+        let mut app = unreachable_app();
+
+        Handler::handle_run_script(&mut app, "archive");
+
+        let last = app.notifications.toasts().last().unwrap();
+        assert!(last.message.contains("no longer exists"));
+    }
+
+    #[tokio::test]
+    async fn test_finished_script_shows_last_line() {
+        let mut app = unreachable_app();
+
+        let lines = vec!["moving".to_string(), "archived 3 files".to_string()];
+        Handler::apply_script_finished(&mut app, "archive", Ok(lines));
+
+        let last = app.notifications.toasts().last().unwrap();
+        assert_eq!(last.message, "archive: archived 3 files");
+    }
+
+    #[tokio::test]
+    async fn test_palette_command_is_left_for_launcher() {
+        let mut app = unreachable_app();
+        app.commands
+            .insert("size".to_string(), "rclone size %dir%".to_string());
+        app.current_remote = Some("gdrive".to_string());
+        app.current_path = "docs".to_string();
+
+        assert!(app.request_command("size"));
+
+        let command = app.pending_command.unwrap();
+        assert_eq!(command.name, "size");
+        assert_eq!(command.line, "rclone size gdrive:docs");
+    }
+
+    #[tokio::test]
+    async fn test_command_that_fails_to_start_is_a_toast() {
+        let mut app = unreachable_app();
+
+        let err = io::Error::from(io::ErrorKind::NotFound);
+        Handler::finish_command(&mut app, "size", Err(err));
+
+        let last = app.notifications.toasts().last().unwrap();
+        assert!(last.message.starts_with("Could not run size"));
+    }
+
+    #[tokio::test]
+    async fn test_failed_script_shows_error() {
+        let mut app = unreachable_app();
+
+        let err = LazyFileError::Script {
+            name: "archive".to_string(),
+            message: "boom".to_string(),
+        };
+        Handler::apply_script_finished(&mut app, "archive", Err(err));
+
+        let last = app.notifications.toasts().last().unwrap();
+        assert_eq!(last.message, "script archive failed: boom");
+    }
+}
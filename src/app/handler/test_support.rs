@@ -0,0 +1,48 @@
+//! Fixtures shared by the tests of the handlers and the app state.
+
+use crate::app::state::{App, Panel};
+use crate::rclone::{FileItem, RcloneClient};
+use crossterm::event::{KeyCode, KeyEvent};
+
+/// A press of `code` without modifiers.
+pub(crate) fn key(code: KeyCode) -> KeyEvent {
+    KeyEvent::from(code)
+}
+
+/// An app whose client points at a port nothing listens on, so every
+/// request fails straight away.
+pub(crate) fn unreachable_app() -> App {
+    App::new(RcloneClient::new("localhost", 1).expect("valid host"))
+}
+
+/// An unreachable app listing `remotes` in the remotes panel.
+pub(crate) fn remotes_app(remotes: &[&str]) -> App {
+    let mut app = unreachable_app();
+    app.remotes = remotes.iter().map(|name| name.to_string()).collect();
+    app
+}
+
+/// An unreachable app with the files panel focused on `path` in
+/// `gdrive`, listing `names`. A name ending in `/` is a directory.
+pub(crate) fn files_app(path: &str, names: &[&str]) -> App {
+    let mut app = unreachable_app();
+    app.focused_panel = Panel::Files;
+    app.current_remote = Some("gdrive".to_string());
+    app.current_path = path.to_string();
+    app.files = names
+        .iter()
+        .map(|name| match name.strip_suffix('/') {
+            Some(dir) => FileItem {
+                name: dir.to_string(),
+                is_dir: true,
+                ..FileItem::default()
+            },
+            None => FileItem {
+                name: name.to_string(),
+                size: 1,
+                ..FileItem::default()
+            },
+        })
+        .collect();
+    app
+}
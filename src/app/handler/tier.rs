@@ -0,0 +1,141 @@
+//! Changing the storage tier of the marked entries, or the selected
+//! one, on object stores.
+
+use super::Handler;
+use crate::app::state::{ActiveModal, App};
+use crate::error::Result;
+use crate::ui::TierPrompt;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use tracing::{debug, info, warn};
+
+impl Handler {
+    /// Opens the tier prompt for the selection.
+    pub(super) fn handle_change_tier(app: &mut App) {
+        let Some(remote) = app.current_remote.clone() else {
+            return;
+        };
+        let entries: Vec<(String, bool)> = app
+            .selection()
+            .into_iter()
+            .map(|item| (app.child_path(item.name()), item.is_dir))
+            .collect();
+        if entries.is_empty() {
+            return;
+        }
+        let remote_type = app.remote_types.get(&remote).map_or("", String::as_str);
+        debug!(count = entries.len(), remote_type, "opening tier prompt");
+        app.modal = Some(ActiveModal::Tier(TierPrompt::new(
+            remote,
+            remote_type,
+            entries,
+        )));
+    }
+
+    /// Handles keyboard input in the tier prompt.
+    pub(super) fn handle_tier_key(app: &mut App, key: KeyEvent) {
+        let Some(ActiveModal::Tier(ref mut prompt)) = app.modal else {
+            return;
+        };
+        match key.code {
+            KeyCode::Esc => {
+                debug!("closing tier prompt");
+                app.modal = None;
+            }
+            KeyCode::Up => prompt.step(-1),
+            KeyCode::Down => prompt.step(1),
+            KeyCode::Char(c) if !key.modifiers.contains(KeyModifiers::CONTROL) => {
+                prompt.input_char(c);
+            }
+            KeyCode::Backspace => prompt.backspace(),
+            KeyCode::Enter if !prompt.tier.is_empty() => {
+                let TierPrompt {
+                    remote,
+                    entries,
+                    tier,
+                    ..
+                } = prompt.clone();
+                app.modal = None;
+                app.request_set_tier(remote, entries, tier);
+            }
+            _ => {}
+        }
+    }
+
+    /// Reports how many entries changed tier, clears the marks and
+    /// lists the directory again.
+    pub(super) fn apply_tier_changed(
+        app: &mut App,
+        changed: usize,
+        total: usize,
+        tier: &str,
+        result: Result<()>,
+    ) {
+        match result {
+            Ok(()) => {
+                info!(changed, tier, "storage tier changed");
+                app.notifications
+                    .success(format!("Moved {} entries to {}", changed, tier));
+            }
+            Err(e) => {
+                warn!(error = %e, changed, total, tier, "changing storage tier failed");
+                app.notifications.error(format!(
+                    "Moved {} of {} to {}, then: {}",
+                    changed, total, tier, e
+                ));
+            }
+        }
+        app.marked.clear();
+        app.reload_files();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::handler::test_support::{key, unreachable_app};
+    use crate::app::state::Panel;
+    use crate::rclone::FileItem;
+
+    #[tokio::test]
+    async fn test_prompt_offers_the_backends_tiers() {
+        let mut app = unreachable_app();
+        app.focused_panel = Panel::Files;
+        app.current_remote = Some("archive".to_string());
+        app.current_path = "logs".to_string();
+        app.remote_types
+            .insert("archive".to_string(), "azureblob".to_string());
+        app.files = ["2023", "2024.tar"]
+            .iter()
+            .map(|name| FileItem {
+                name: name.to_string(),
+                is_dir: !name.contains('.'),
+                ..Default::default()
+            })
+            .collect();
+        app.marked
+            .extend(["2023".to_string(), "2024.tar".to_string()]);
+
+        Handler::handle_key(&mut app, key(KeyCode::Char('T')))
+            .await
+            .unwrap();
+        Handler::handle_key(&mut app, key(KeyCode::Up))
+            .await
+            .unwrap();
+        let Some(ActiveModal::Tier(ref prompt)) = app.modal else {
+            panic!("tier prompt not open");
+        };
+        assert_eq!(prompt.tier, "Archive");
+        assert_eq!(
+            prompt.entries,
+            [
+                ("logs/2023".to_string(), true),
+                ("logs/2024.tar".to_string(), false)
+            ]
+        );
+
+        Handler::handle_key(&mut app, key(KeyCode::Esc))
+            .await
+            .unwrap();
+        assert!(app.modal.is_none());
+    }
+}
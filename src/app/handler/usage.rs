@@ -0,0 +1,139 @@
+//! Usage view handling: sizing the current directory and moving
+//! through the result.
+
+use super::Handler;
+use crate::app::state::{ActiveModal, App};
+use crate::error::Result;
+use crate::rclone::TreeEntry;
+use crate::ui::UsageView;
+use crossterm::event::{KeyCode, KeyEvent};
+use tracing::{debug, warn};
+
+impl Handler {
+    /// Opens the usage view for the current directory and starts
+    /// listing everything below it.
+    pub(super) fn handle_show_usage(app: &mut App) {
+        let Some(remote) = app.current_remote.clone() else {
+            return;
+        };
+        let path = app.current_path.clone();
+        debug!(remote = %remote, path = %path, "opening usage");
+        let mut view = UsageView::new(remote.clone(), path.clone());
+        view.cancel = Some(app.request_usage(remote, path));
+        app.modal = Some(ActiveModal::Usage(Box::new(view)));
+    }
+
+    /// Handles keys while the usage view is open. `u` closes it again,
+    /// like `Esc` and `q`; closing it while the directory is still
+    /// being sized drops the listing.
+    pub(super) fn handle_usage_key(app: &mut App, key: KeyEvent) {
+        let Some(ActiveModal::Usage(ref mut view)) = app.modal else {
+            return;
+        };
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q' | 'u') => {
+                debug!("closing usage");
+                if let Some(cancel) = view.cancel.take() {
+                    cancel.cancel();
+                }
+                app.modal = None;
+            }
+            KeyCode::Char('j') | KeyCode::Down => view.select_next(),
+            KeyCode::Char('k') | KeyCode::Up => view.select_prev(),
+            KeyCode::Enter | KeyCode::Char('l') | KeyCode::Right => view.enter(),
+            KeyCode::Backspace | KeyCode::Char('h') | KeyCode::Left => view.leave(),
+            KeyCode::Char('r') if !view.loading => {
+                view.loading = true;
+                let (remote, path) = (view.remote.clone(), view.root.clone());
+                let cancel = app.request_usage(remote, path);
+                if let Some(ActiveModal::Usage(ref mut view)) = app.modal {
+                    view.cancel = Some(cancel);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Shows a finished listing if the usage view is still open on
+    /// the directory that was listed.
+    pub(super) fn apply_usage(
+        app: &mut App,
+        remote: &str,
+        path: &str,
+        result: Result<Vec<TreeEntry>>,
+    ) {
+        if let Err(ref e) = result {
+            warn!(remote, path, error = %e, "sizing directory failed");
+        }
+        if let Some(ActiveModal::Usage(ref mut view)) = app.modal
+            && view.remote == remote
+            && view.root == path
+        {
+            view.set_entries(result);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::event::AppEvent;
+    use crate::app::handler::test_support::{key, unreachable_app};
+    use crate::app::state::Panel;
+
+    fn entry(path: &str, size: i64, is_dir: bool) -> TreeEntry {
+        TreeEntry {
+            path: path.to_string(),
+            size,
+            is_dir,
+            hashes: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_usage_sizes_current_directory() {
+        let mut app = unreachable_app();
+        app.focused_panel = Panel::Files;
+        app.current_remote = Some("gdrive".to_string());
+        app.current_path = "backup".to_string();
+        Handler::handle_key(&mut app, key(KeyCode::Char('u')))
+            .await
+            .unwrap();
+
+        let event = app.wait_event().await.unwrap();
+        assert!(matches!(
+            event,
+            AppEvent::UsageLoaded { ref path, .. } if path == "backup"
+        ));
+        Handler::handle_app_event(&mut app, event);
+        let Some(ActiveModal::Usage(ref view)) = app.modal else {
+            panic!("usage closed");
+        };
+        assert!(!view.loading);
+        assert!(view.error.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_stale_listing_is_ignored() {
+        let mut app = unreachable_app();
+        let view = UsageView::new("gdrive".to_string(), "backup".to_string());
+        app.modal = Some(ActiveModal::Usage(Box::new(view)));
+
+        let result = Ok(vec![entry("a.txt", 10, false)]);
+        Handler::apply_usage(&mut app, "gdrive", "other", result);
+        let Some(ActiveModal::Usage(ref view)) = app.modal else {
+            panic!("usage closed");
+        };
+        assert!(view.loading);
+
+        let result = Ok(vec![entry("dir", -1, true), entry("dir/a.txt", 10, false)]);
+        Handler::apply_usage(&mut app, "gdrive", "backup", result);
+        Handler::handle_key(&mut app, key(KeyCode::Enter))
+            .await
+            .unwrap();
+        let Some(ActiveModal::Usage(ref view)) = app.modal else {
+            panic!("usage closed");
+        };
+        assert_eq!(view.location(), "gdrive:backup/dir");
+    }
+}
@@ -0,0 +1,206 @@
+//! Versions view handling: prior versions of the selected file on
+//! backends that keep them, downloading one or restoring it over the
+//! file.
+
+use super::Handler;
+use crate::app::state::{ActiveModal, App};
+use crate::error::Result;
+use crate::rclone::Version;
+use crate::ui::VersionsView;
+use crate::ui::widgets::versions::keeps_versions;
+use crossterm::event::{KeyCode, KeyEvent};
+use std::path::PathBuf;
+use tracing::{debug, info, warn};
+
+impl Handler {
+    /// Opens the versions view for the selected file and starts listing
+    /// its versions. Only files on S3 and B2 remotes have them.
+    pub(super) fn handle_show_versions(app: &mut App) {
+        let (Some(remote), Some(item), Some(path)) = (
+            app.current_remote.clone(),
+            app.files.get(app.files_selected),
+            app.selected_path(),
+        ) else {
+            return;
+        };
+        if item.is_dir() {
+            return;
+        }
+        let remote_type = app.remote_types.get(&remote).map_or("", String::as_str);
+        if !keeps_versions(remote_type) {
+            app.notifications
+                .warning(format!("'{}' does not keep file versions", remote));
+            return;
+        }
+        debug!(remote = %remote, path = %path, "opening versions view");
+        app.modal = Some(ActiveModal::Versions(Box::new(VersionsView::new(
+            remote.clone(),
+            path.clone(),
+        ))));
+        app.request_versions(remote, path);
+    }
+
+    /// Handles keys while the versions view is open: `j`/`k` select a
+    /// version, `d` downloads it, `r` restores it, and `Esc`, `q` or
+    /// `V` close. One version is handled at a time.
+    pub(super) fn handle_versions_key(app: &mut App, key: KeyEvent) {
+        let Some(ActiveModal::Versions(ref mut view)) = app.modal else {
+            return;
+        };
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q' | 'V') => app.modal = None,
+            KeyCode::Char('j') | KeyCode::Down => view.select_next(),
+            KeyCode::Char('k') | KeyCode::Up => view.select_prev(),
+            _ if view.busy.is_some() => {}
+            KeyCode::Char('d') => {
+                let Some(Version { name, .. }) = view.selected_version().cloned() else {
+                    return;
+                };
+                view.busy = Some(format!("Downloading {}...", name));
+                let (remote, path) = (view.remote.clone(), view.path.clone());
+                app.request_download_version(remote, path, name);
+            }
+            KeyCode::Char('r') => {
+                let Some(Version { name, time, .. }) = view.selected_version().cloned() else {
+                    return;
+                };
+                view.busy = Some(format!("Restoring the version of {} UTC...", time));
+                let (remote, path) = (view.remote.clone(), view.path.clone());
+                app.request_restore_version(remote, path, name);
+            }
+            _ => {}
+        }
+    }
+
+    /// Shows listed versions, unless the view was closed or moved on to
+    /// another file meanwhile.
+    pub(super) fn apply_versions_loaded(
+        app: &mut App,
+        remote: &str,
+        path: &str,
+        result: Result<Vec<Version>>,
+    ) {
+        let Some(ActiveModal::Versions(ref mut view)) = app.modal else {
+            debug!(path, "versions view closed, dropping versions");
+            return;
+        };
+        if view.remote != remote || view.path != path {
+            return;
+        }
+        let result = result.map_err(|e| {
+            warn!(remote, path, error = %e, "could not list versions");
+            format!("Could not list versions: {}", e)
+        });
+        view.set_versions(result);
+    }
+
+    /// Reports where a version was saved, or why it was not.
+    pub(super) fn apply_version_downloaded(app: &mut App, version: &str, result: Result<PathBuf>) {
+        if let Some(ActiveModal::Versions(ref mut view)) = app.modal {
+            view.busy = None;
+        }
+        match result {
+            Ok(local) => {
+                info!(version, local = %local.display(), "version downloaded");
+                app.notifications
+                    .success(format!("Saved {}", local.display()));
+            }
+            Err(e) => {
+                warn!(version, error = %e, "downloading version failed");
+                app.notifications
+                    .error(format!("Could not download {}: {}", version, e));
+            }
+        }
+    }
+
+    /// Reports a restore and lists the versions and the directory
+    /// again, since the replaced file became a version.
+    pub(super) fn apply_version_restored(
+        app: &mut App,
+        remote: String,
+        path: String,
+        version: &str,
+        result: Result<()>,
+    ) {
+        let open = match app.modal {
+            Some(ActiveModal::Versions(ref mut view))
+                if view.remote == remote && view.path == path =>
+            {
+                view.busy = None;
+                true
+            }
+            _ => false,
+        };
+        match result {
+            Ok(()) => {
+                info!(remote = %remote, path = %path, version, "version restored");
+                app.notifications
+                    .success(format!("Restored {} from {}", path, version));
+                app.reload_files();
+                if open {
+                    app.request_versions(remote, path);
+                }
+            }
+            Err(e) => {
+                warn!(remote = %remote, path = %path, version, error = %e, "restore failed");
+                app.notifications
+                    .error(format!("Could not restore {}: {}", path, e));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::handler::test_support::{key, unreachable_app};
+    use crate::app::state::Panel;
+    use crate::rclone::FileItem;
+
+    #[tokio::test]
+    async fn test_versions_only_open_on_backends_that_keep_them() {
+        let mut app = unreachable_app();
+        app.focused_panel = Panel::Files;
+        app.current_remote = Some("nas".to_string());
+        app.remote_types
+            .insert("nas".to_string(), "sftp".to_string());
+        app.files = vec![FileItem {
+            name: "a.txt".to_string(),
+            ..Default::default()
+        }];
+
+        Handler::handle_key(&mut app, key(KeyCode::Char('V')))
+            .await
+            .unwrap();
+        assert!(app.modal.is_none());
+
+        app.remote_types.insert("nas".to_string(), "b2".to_string());
+        Handler::handle_key(&mut app, key(KeyCode::Char('V')))
+            .await
+            .unwrap();
+        let version = Version {
+            name: "a-v2024-03-01-093012-250.txt".to_string(),
+            ..Version::default()
+        };
+        Handler::apply_versions_loaded(&mut app, "nas", "a.txt", Ok(vec![version]));
+        Handler::handle_key(&mut app, key(KeyCode::Char('r')))
+            .await
+            .unwrap();
+        let Some(ActiveModal::Versions(ref view)) = app.modal else {
+            panic!("versions view not open");
+        };
+        assert!(view.busy.is_some());
+
+        // The listing requested on opening fails too.
+        for _ in 0..2 {
+            let event = app.wait_event().await.unwrap();
+            Handler::handle_app_event(&mut app, event);
+        }
+        let Some(ActiveModal::Versions(ref view)) = app.modal else {
+            panic!("versions view closed");
+        };
+        assert!(view.busy.is_none());
+        let last = app.notifications.toasts().last().unwrap();
+        assert!(last.message.starts_with("Could not restore a.txt"));
+    }
+}
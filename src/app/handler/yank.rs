@@ -0,0 +1,167 @@
+//! Yanking, cutting and putting entries through the internal file
+//! clipboard.
+
+use super::Handler;
+use crate::app::command::rclone_path;
+use crate::app::state::App;
+use crate::error::Result;
+use std::time::Duration;
+use tracing::{info, warn};
+
+impl Handler {
+    /// Fills the clipboard with the marked entries, or the selected
+    /// one, to be copied or, if `cut`, moved by the next put.
+    pub(super) fn handle_yank(app: &mut App, cut: bool) {
+        if !app.yank(cut) {
+            return;
+        }
+        let Some(ref yank) = app.yanked else {
+            return;
+        };
+        let verb = if cut { "Cut" } else { "Yanked" };
+        app.notifications
+            .info(format!("{} {}", verb, yank.describe()));
+    }
+
+    /// Copies or moves the clipboard's entries into the directory on
+    /// screen.
+    pub(super) fn handle_put(app: &mut App) {
+        let Some(ref remote) = app.current_remote else {
+            return;
+        };
+        let Some(ref yank) = app.yanked else {
+            app.notifications.warning("Nothing yanked");
+            return;
+        };
+        let verb = if yank.cut { "Moving" } else { "Copying" };
+        app.notifications.info(format!(
+            "{} {} to {}",
+            verb,
+            yank.describe(),
+            rclone_path(remote, &app.current_path)
+        ));
+        app.request_put(
+            yank.clone(),
+            remote.clone(),
+            app.current_path.clone(),
+            app.conflict_policy,
+        );
+    }
+
+    /// Reports how a put went and lists the directory again. Entries
+    /// that were cut leave the clipboard once every one was moved,
+    /// since their source is gone; copied ones stay for another put.
+    pub(super) fn apply_put(
+        app: &mut App,
+        (done, total): (usize, usize),
+        skipped: &[String],
+        cut: bool,
+        elapsed: Duration,
+        result: Result<()>,
+    ) {
+        let verb = if cut { "Moved" } else { "Copied" };
+        let (summary, message) = match result {
+            Ok(()) => {
+                info!(done, skipped = skipped.len(), cut, "put finished");
+                if cut && skipped.is_empty() {
+                    app.yanked = None;
+                }
+                if skipped.is_empty() {
+                    let message = format!("{} {} of {}", verb, done, total);
+                    app.notifications.success(message.clone());
+                    ("Put finished", message)
+                } else {
+                    let message = format!(
+                        "{} {} of {}; skipped {} (already there)",
+                        verb,
+                        done,
+                        total,
+                        skipped.join(", ")
+                    );
+                    app.notifications.warning(message.clone());
+                    ("Put finished", message)
+                }
+            }
+            Err(e) => {
+                warn!(error = %e, done, total, "put failed");
+                let message = format!("{} {} of {}, then: {}", verb, done, total, e);
+                app.notifications.error(message.clone());
+                ("Put failed", message)
+            }
+        };
+        app.notify_if_slow(elapsed, summary, &message);
+        app.reload_files();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::event::AppEvent;
+    use crate::app::handler::test_support::{files_app, key};
+    use crossterm::event::KeyCode;
+
+    #[tokio::test]
+    async fn test_cut_then_put_empties_clipboard() {
+        let mut app = files_app("", &["a.txt"]);
+        Handler::handle_key(&mut app, key(KeyCode::Char('x')))
+            .await
+            .unwrap();
+        assert_eq!(
+            app.notifications.toasts().last().unwrap().message,
+            "Cut 'a.txt'"
+        );
+
+        app.current_remote = Some("s3".to_string());
+        app.current_path = "backup".to_string();
+        Handler::handle_key(&mut app, key(KeyCode::Char('p')))
+            .await
+            .unwrap();
+        assert_eq!(
+            app.notifications.toasts().last().unwrap().message,
+            "Moving 'a.txt' to s3:backup"
+        );
+
+        Handler::handle_app_event(
+            &mut app,
+            AppEvent::Put {
+                done: 1,
+                total: 1,
+                skipped: Vec::new(),
+                source: "s3:a".to_string(),
+                destination: "s3:b".to_string(),
+                cut: true,
+                elapsed: Duration::ZERO,
+                result: Ok(()),
+            },
+        );
+        assert!(app.yanked.is_none());
+        assert_eq!(app.loading.as_ref().unwrap().path, "backup");
+    }
+
+    #[tokio::test]
+    async fn test_copies_stay_yanked() {
+        let mut app = files_app("", &["a.txt"]);
+        Handler::handle_key(&mut app, key(KeyCode::Char('y')))
+            .await
+            .unwrap();
+        Handler::handle_app_event(
+            &mut app,
+            AppEvent::Put {
+                done: 0,
+                total: 1,
+                skipped: vec!["a.txt".to_string()],
+                source: "s3:a".to_string(),
+                destination: "s3:b".to_string(),
+                cut: false,
+                elapsed: Duration::ZERO,
+                result: Ok(()),
+            },
+        );
+        assert!(app.yanked.is_some());
+        assert_eq!(
+            app.notifications.toasts().last().unwrap().message,
+            "Copied 0 of 1; skipped a.txt (already there)"
+        );
+    }
+}
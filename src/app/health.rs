@@ -0,0 +1,227 @@
+//! Daemon health: a periodic `core/version` ping that notices when
+//! the daemon goes away and keeps retrying, backing off, until it is
+//! back.
+
+use super::event::AppEvent;
+use super::state::App;
+use crate::error::Result;
+use crate::rclone::RequestPolicy;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+use tracing::{debug, info, warn};
+
+/// Time between pings while the daemon answers.
+pub const PING_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Wait before the first retry once a ping failed; doubled after
+/// every further failure.
+const MIN_BACKOFF: Duration = Duration::from_secs(1);
+
+/// Longest wait between retries.
+const MAX_BACKOFF: Duration = Duration::from_secs(30);
+
+/// Ping schedule and the outcome of the last pings.
+#[derive(Debug, Clone)]
+pub struct Health {
+    /// When the next ping is due.
+    pub next_ping: Instant,
+    /// Whether a ping is in flight.
+    pub in_flight: bool,
+    /// Pings that failed in a row.
+    pub failures: u32,
+    /// Version the daemon reported in its last answer.
+    pub version: Option<String>,
+}
+
+impl Health {
+    /// Schedules the first ping one interval from `now`.
+    pub fn new(now: Instant) -> Self {
+        Self {
+            next_ping: now + PING_INTERVAL,
+            in_flight: false,
+            failures: 0,
+            version: None,
+        }
+    }
+
+    /// Returns how long until the next retry while the daemon is
+    /// down, or `None` when it is up or a ping is in flight.
+    pub fn retry_in(&self, now: Instant) -> Option<Duration> {
+        (self.failures > 0 && !self.in_flight)
+            .then(|| self.next_ping.saturating_duration_since(now))
+    }
+}
+
+/// Returns the wait before retrying after `failures` failed pings in
+/// a row: one second, doubled per failure, at most thirty.
+pub fn backoff(failures: u32) -> Duration {
+    let doublings = failures.saturating_sub(1).min(16);
+    (MIN_BACKOFF * 2u32.pow(doublings)).min(MAX_BACKOFF)
+}
+
+impl App {
+    /// Pings the daemon on a background task if a ping is due. The
+    /// answer arrives as [`AppEvent::Pinged`].
+    pub fn tick_health(&mut self, now: Instant) {
+        if self.health.in_flight || now < self.health.next_ping {
+            return;
+        }
+        self.health.in_flight = true;
+        // Pings back off on their own; retrying each one as well
+        // would only delay noticing the daemon is gone.
+        let client = self.client.with_policy(RequestPolicy {
+            retries: 0,
+            ..self.client.policy().clone()
+        });
+        self.workers
+            .spawn(async move { AppEvent::Pinged(client.version().await) });
+    }
+
+    /// Pings again right away, e.g. after switching to another
+    /// daemon.
+    pub fn reset_health(&mut self, now: Instant) {
+        self.health = Health {
+            next_ping: now,
+            ..Health::new(now)
+        };
+    }
+
+    /// Records a ping's answer and schedules the next one.
+    ///
+    /// A failure marks the daemon disconnected and retries after
+    /// [`backoff`]. The first answer after that marks it connected
+    /// again and reloads the remotes if they never arrived.
+    pub fn apply_ping(&mut self, result: Result<String>, now: Instant) {
+        self.health.in_flight = false;
+        match result {
+            Ok(version) => {
+                if self.health.failures > 0 || !self.connected {
+                    info!(version = %version, "daemon reachable again");
+                    self.notifications
+                        .success(format!("Reconnected to rclone {}", version));
+                    if self.remotes.is_empty() {
+                        self.request_remotes();
+                    }
+                    self.health.failures = 0;
+                    self.run_queued();
+                } else {
+                    debug!(version = %version, "daemon answered ping");
+                }
+                self.connected = true;
+                self.health.failures = 0;
+                self.health.version = Some(version);
+                self.health.next_ping = now + PING_INTERVAL;
+            }
+            Err(e) => {
+                if self.health.failures == 0 {
+                    warn!(error = %e, "daemon stopped answering");
+                    self.notifications
+                        .warning("Lost connection to the rclone daemon; retrying");
+                } else {
+                    debug!(error = %e, failures = self.health.failures, "ping failed");
+                }
+                self.connected = false;
+                self.health.failures = self.health.failures.saturating_add(1);
+                self.health.next_ping = now + backoff(self.health.failures);
+                self.restart_exited_daemon();
+            }
+        }
+    }
+
+    /// Starts the daemon LazyFile owns again if it exited, e.g. it
+    /// crashed or was killed. The new one listens on another port, so
+    /// the client is swapped once it is up.
+    fn restart_exited_daemon(&mut self) {
+        if !self.owns_daemon {
+            return;
+        }
+        let Some(ref mut daemon) = self.daemon else {
+            return;
+        };
+        if !daemon.has_exited() {
+            return;
+        }
+        let program = PathBuf::from(daemon.program());
+        warn!("rclone daemon exited, restarting it");
+        self.notifications
+            .warning("rclone rcd exited; restarting it");
+        self.daemon = None;
+        self.request_start_daemon(program);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::handler::test_support::unreachable_app;
+    use crate::error::LazyFileError;
+
+    fn unreachable() -> LazyFileError {
+        LazyFileError::RcloneApi {
+            endpoint: "core/version",
+            message: "connection refused".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_backoff_doubles_up_to_cap() {
+        assert_eq!(backoff(1), Duration::from_secs(1));
+        assert_eq!(backoff(2), Duration::from_secs(2));
+        assert_eq!(backoff(4), Duration::from_secs(8));
+        assert_eq!(backoff(6), MAX_BACKOFF);
+        assert_eq!(backoff(u32::MAX), MAX_BACKOFF);
+    }
+
+    #[tokio::test]
+    async fn test_ping_only_when_due() {
+        let mut app = unreachable_app();
+        let now = Instant::now();
+        app.tick_health(now);
+        assert!(!app.health.in_flight);
+
+        app.tick_health(now + PING_INTERVAL);
+        assert!(app.health.in_flight);
+        let event = app.wait_event().await.unwrap();
+        assert!(matches!(event, AppEvent::Pinged(Err(_))));
+    }
+
+    #[tokio::test]
+    async fn test_failures_back_off_and_recover() {
+        let mut app = unreachable_app();
+        let now = Instant::now();
+
+        app.apply_ping(Err(unreachable()), now);
+        app.apply_ping(Err(unreachable()), now);
+        assert!(!app.connected);
+        assert_eq!(app.health.next_ping, now + backoff(2));
+        assert_eq!(app.health.retry_in(now), Some(backoff(2)));
+        assert_eq!(app.notifications.toasts().count(), 1);
+
+        app.remotes = vec!["gdrive".to_string()];
+        app.apply_ping(Ok("v1.68.2".to_string()), now);
+        assert!(app.connected);
+        assert_eq!(app.health.failures, 0);
+        assert_eq!(app.health.retry_in(now), None);
+        assert!(
+            app.notifications
+                .toasts()
+                .last()
+                .unwrap()
+                .message
+                .contains("Reconnected")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_reconnect_loads_missing_remotes() {
+        let mut app = unreachable_app();
+        app.connected = false;
+
+        app.apply_ping(Ok("v1.68.2".to_string()), Instant::now());
+
+        let event = app
+            .wait_event_matching(|event| matches!(event, AppEvent::RemotesLoaded(_)))
+            .await;
+        assert!(event.is_some());
+    }
+}
@@ -0,0 +1,59 @@
+//! Timing copies and moves and recording them in the transfer
+//! history once they finish.
+
+use crate::config::{History, TransferKind, TransferRecord};
+use crate::error::Result;
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+use tracing::warn;
+
+/// A copy or move in flight, on its way into the history.
+#[derive(Debug)]
+pub(super) struct Timed {
+    kind: TransferKind,
+    source: String,
+    destination: String,
+    bytes: Option<u64>,
+    started: Instant,
+}
+
+impl Timed {
+    /// Starts timing a transfer between two `remote:path` locations.
+    pub(super) fn start(
+        kind: TransferKind,
+        source: String,
+        destination: String,
+        bytes: Option<u64>,
+    ) -> Self {
+        Self {
+            kind,
+            source,
+            destination,
+            bytes,
+            started: Instant::now(),
+        }
+    }
+
+    /// Appends the finished transfer to `history`, if there is one. A
+    /// history that cannot be written is logged; it never fails the
+    /// transfer itself.
+    pub(super) fn finish(self, history: Option<&History>, result: &Result<()>) {
+        let Some(history) = history else {
+            return;
+        };
+        let finished = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |elapsed| elapsed.as_secs() as i64);
+        let record = TransferRecord {
+            finished,
+            kind: self.kind,
+            source: self.source,
+            destination: self.destination,
+            bytes: self.bytes,
+            seconds: self.started.elapsed().as_secs_f64(),
+            error: result.as_ref().err().map(ToString::to_string),
+        };
+        if let Err(e) = history.append(&record) {
+            warn!(error = %e, "could not record transfer");
+        }
+    }
+}
@@ -0,0 +1,216 @@
+//! Background requests that look into files and trees: versions,
+//! metadata, disk usage, the largest files and directory comparison.
+
+use super::event::AppEvent;
+use super::state::App;
+use crate::error::{LazyFileError, Result};
+use crate::rclone::{CancelToken, Filter, RcloneClient};
+use crate::ui::DiffRow;
+use crate::ui::widgets::compare::{diff, split_location};
+use std::collections::BTreeMap;
+use tokio::io::AsyncWriteExt;
+use tracing::{debug, info};
+
+impl App {
+    /// Lists the prior versions of the file at `path` on `remote` on a
+    /// background task. They arrive as [`AppEvent::VersionsLoaded`].
+    pub fn request_versions(&self, remote: String, path: String) {
+        debug!(remote = %remote, path = %path, "loading versions");
+        let client = self.client.clone();
+        self.workers.spawn(async move {
+            let result = client.list_versions(&remote, &path).await;
+            AppEvent::VersionsLoaded {
+                remote,
+                path,
+                result,
+            }
+        });
+    }
+
+    /// Downloads the version `version` of the file at `path` on
+    /// `remote` into the current directory, under the version's name,
+    /// on a background task. A file already there is left alone. The
+    /// outcome arrives as [`AppEvent::VersionDownloaded`].
+    pub fn request_download_version(&self, remote: String, path: String, version: String) {
+        let local = std::env::current_dir().unwrap_or_default().join(&version);
+        debug!(remote = %remote, path = %path, local = %local.display(), "downloading version");
+        let client = self.client.clone();
+        self.workers.spawn_transfer(async move {
+            let result = async {
+                let contents = client.download_version(&remote, &path, &version).await?;
+                let mut file = tokio::fs::OpenOptions::new()
+                    .write(true)
+                    .create_new(true)
+                    .open(&local)
+                    .await?;
+                file.write_all(&contents).await?;
+                Ok::<_, LazyFileError>(local)
+            }
+            .await;
+            AppEvent::VersionDownloaded { version, result }
+        });
+    }
+
+    /// Copies the version `version` of the file at `path` on `remote`
+    /// over the file on a background task. The outcome arrives as
+    /// [`AppEvent::VersionRestored`].
+    pub fn request_restore_version(&mut self, remote: String, path: String, version: String) {
+        if self.offline() {
+            self.queue(format!("restoring {}", path), move |app| {
+                app.request_restore_version(remote, path, version);
+            });
+            return;
+        }
+        info!(remote = %remote, path = %path, version = %version, "restoring version");
+        let client = self.client.clone();
+        self.workers.spawn_transfer(async move {
+            let result = client.restore_version(&remote, &path, &version).await;
+            AppEvent::VersionRestored {
+                remote,
+                path,
+                version,
+                result,
+            }
+        });
+    }
+
+    /// Fetches the file at `path` on `remote` with its metadata, and
+    /// whether its backend writes metadata, on a background task. Both
+    /// arrive as [`AppEvent::MetadataLoaded`].
+    pub fn request_metadata(&self, remote: String, path: String) {
+        debug!(remote = %remote, path = %path, "loading metadata");
+        let client = self.client.clone();
+        self.workers.spawn(async move {
+            let (info, features) = tokio::join!(
+                client.stat_metadata(&remote, &path),
+                client.fs_info(&remote)
+            );
+            AppEvent::MetadataLoaded {
+                remote,
+                path,
+                info,
+                features,
+            }
+        });
+    }
+
+    /// Sets the metadata keys in `changes` on the file at `path` on
+    /// `remote` on a background task. The outcome arrives as
+    /// [`AppEvent::MetadataSaved`].
+    pub fn request_set_metadata(
+        &mut self,
+        remote: String,
+        path: String,
+        changes: BTreeMap<String, String>,
+    ) {
+        if self.offline() {
+            self.queue(format!("setting metadata on {}", path), move |app| {
+                app.request_set_metadata(remote, path, changes);
+            });
+            return;
+        }
+        info!(remote = %remote, path = %path, keys = changes.len(), "setting metadata");
+        let client = self.client.clone();
+        self.workers.spawn_transfer(async move {
+            let result = client.set_metadata(&remote, &path, &changes).await;
+            AppEvent::MetadataSaved {
+                remote,
+                path,
+                keys: changes.into_keys().collect(),
+                result,
+            }
+        });
+    }
+
+    /// Lists everything below `path` on `remote` for the usage view on
+    /// a background task. The result arrives as
+    /// [`AppEvent::UsageLoaded`]; cancelling the returned token drops
+    /// the request.
+    pub fn request_usage(&self, remote: String, path: String) -> CancelToken {
+        debug!(remote = %remote, path = %path, "sizing directory");
+        let cancel = CancelToken::new();
+        let client = self.client.with_cancel(cancel.clone());
+        let filter = self.filter.clone();
+        self.workers.spawn(async move {
+            let result = client.list_tree(&remote, &path, None, &filter).await;
+            AppEvent::UsageLoaded {
+                remote,
+                path,
+                result,
+            }
+        });
+        cancel
+    }
+
+    /// Lists every file below `path` on `remote` for the largest files
+    /// view on a background task. The result arrives as
+    /// [`AppEvent::LargestLoaded`]; cancelling the returned token drops
+    /// the request.
+    pub fn request_largest(&self, remote: String, path: String) -> CancelToken {
+        debug!(remote = %remote, path = %path, "scanning for largest files");
+        let cancel = CancelToken::new();
+        let client = self.client.with_cancel(cancel.clone());
+        let filter = self.filter.clone();
+        self.workers.spawn(async move {
+            let result = client.list_recursive(&remote, &path, None, &filter).await;
+            AppEvent::LargestLoaded {
+                remote,
+                path,
+                result,
+            }
+        });
+        cancel
+    }
+
+    /// Lists the `remote:path` directories `left` and `right` for the
+    /// compare view on a background task. The result arrives as
+    /// [`AppEvent::Compared`].
+    pub fn request_compare(&self, left: String, right: String) {
+        debug!(left = %left, right = %right, "comparing directories");
+        let client = self.client.clone();
+        let filter = self.filter.clone();
+        self.workers.spawn(async move {
+            let (hash, result) = compare_dirs(&client, &left, &right, &filter).await;
+            AppEvent::Compared {
+                left,
+                right,
+                hash,
+                result,
+            }
+        });
+    }
+}
+
+/// Lists both directories, with hashes of the first type both
+/// backends support, and matches their files that `filter` keeps.
+/// Returns the hash type compared along with the result.
+async fn compare_dirs(
+    client: &RcloneClient,
+    left: &str,
+    right: &str,
+    filter: &Filter,
+) -> (Option<String>, Result<Vec<DiffRow>>) {
+    let (Ok((left_remote, left_path)), Ok((right_remote, right_path))) =
+        (split_location(left), split_location(right))
+    else {
+        let error = LazyFileError::InvalidInput {
+            field: "location",
+            reason: "expected remote:path",
+        };
+        return (None, Err(error));
+    };
+    // Without both backends' hash types, sizes alone are compared.
+    let hash = match tokio::join!(client.fs_info(&left_remote), client.fs_info(&right_remote)) {
+        (Ok(l), Ok(r)) => l.hashes().iter().find(|h| r.hashes().contains(h)).cloned(),
+        _ => None,
+    };
+    let (l, r) = tokio::join!(
+        client.list_tree(&left_remote, &left_path, hash.as_deref(), filter),
+        client.list_tree(&right_remote, &right_path, hash.as_deref(), filter),
+    );
+    let result = match (l, r) {
+        (Ok(l), Ok(r)) => Ok(diff(&l, &r, hash.as_deref())),
+        (Err(e), _) | (_, Err(e)) => Err(e),
+    };
+    (hash, result)
+}
@@ -0,0 +1,111 @@
+//! Background requests for the daemon's mounts and jobs, and the
+//! check for unfinished transfers on quit.
+
+use super::command::rclone_path;
+use super::event::AppEvent;
+use super::state::{ActiveModal, App};
+use std::time::Instant;
+use tracing::debug;
+
+impl App {
+    /// Fetches the daemon's active mounts on a background task. The
+    /// result arrives as [`AppEvent::MountsLoaded`].
+    pub fn request_mounts(&self) {
+        debug!("loading mounts");
+        let client = self.client.clone();
+        self.workers
+            .spawn(async move { AppEvent::MountsLoaded(client.list_mounts().await) });
+    }
+
+    /// Mounts `path` on `remote` at `mount_point` on a background
+    /// task. The outcome arrives as [`AppEvent::Mounted`].
+    pub fn request_mount(&self, remote: String, path: String, mount_point: String) {
+        debug!(remote = %remote, path = %path, mount_point = %mount_point, "requesting mount");
+        let client = self.client.clone();
+        self.workers.spawn(async move {
+            let result = client.mount(&remote, &path, &mount_point).await;
+            AppEvent::Mounted {
+                source: rclone_path(&remote, &path),
+                mount_point,
+                result,
+            }
+        });
+    }
+
+    /// Unmounts `mount_point` on a background task. The outcome
+    /// arrives as [`AppEvent::Unmounted`].
+    pub fn request_unmount(&self, mount_point: String) {
+        debug!(mount_point = %mount_point, "requesting unmount");
+        let client = self.client.clone();
+        self.workers.spawn(async move {
+            let result = client.unmount(&mount_point).await;
+            AppEvent::Unmounted {
+                mount_point,
+                result,
+            }
+        });
+    }
+
+    /// Fetches the daemon's jobs and the files in flight on
+    /// background tasks. The results arrive as
+    /// [`AppEvent::JobsLoaded`] and [`AppEvent::TransfersLoaded`].
+    pub fn request_jobs(&self) {
+        debug!("loading jobs");
+        let client = self.client.clone();
+        self.workers
+            .spawn(async move { AppEvent::JobsLoaded(client.list_jobs().await) });
+        let client = self.client.clone();
+        self.workers
+            .spawn(async move { AppEvent::TransfersLoaded(client.transfers().await) });
+    }
+
+    /// Lists the jobs again if the jobs view is open and a refresh is
+    /// due, so progress stays live while anything runs.
+    pub fn tick_jobs(&mut self, now: Instant) {
+        let Some(ActiveModal::Jobs(ref mut view)) = self.modal else {
+            return;
+        };
+        if view.loading || view.next_refresh.is_none_or(|due| now < due) {
+            return;
+        }
+        view.loading = true;
+        view.next_refresh = None;
+        self.request_jobs();
+    }
+
+    /// Lists the daemon's jobs on a background task before quitting.
+    /// The outcome arrives as [`AppEvent::QuitChecked`].
+    pub fn request_quit_check(&self, waiting: bool) {
+        debug!(waiting, "checking for running jobs before quitting");
+        let client = self.client.clone();
+        self.workers.spawn(async move {
+            let result = client.list_jobs().await;
+            AppEvent::QuitChecked { waiting, result }
+        });
+    }
+
+    /// Lists the jobs again if the quit dialog waits for them and a
+    /// refresh is due.
+    pub fn tick_quit(&mut self, now: Instant) {
+        let Some(ActiveModal::Quit(ref mut modal)) = self.modal else {
+            return;
+        };
+        if !modal.waiting || modal.loading || modal.next_refresh.is_none_or(|due| now < due) {
+            return;
+        }
+        modal.loading = true;
+        modal.next_refresh = None;
+        self.request_quit_check(true);
+    }
+
+    /// Stops job `id` on a background task. The outcome arrives as
+    /// [`AppEvent::JobStopped`].
+    pub fn request_stop_job(&self, id: i64) {
+        debug!(id, "requesting job stop");
+        let client = self.client.clone();
+        self.workers.spawn(async move {
+            let result = client.stop_job(id).await;
+            AppEvent::JobStopped { id, result }
+        });
+    }
+}
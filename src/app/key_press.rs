@@ -0,0 +1,219 @@
+//! Key presses and sequences as written in `[keys]`, and matching
+//! them against terminal events.
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use std::fmt;
+
+/// A single key press with the modifiers that matter for binding.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KeyPress {
+    /// The key itself.
+    pub code: KeyCode,
+    /// Ctrl/Alt/Shift modifiers held with the key.
+    pub modifiers: KeyModifiers,
+}
+
+impl KeyPress {
+    /// Creates a key press with the given modifiers.
+    pub const fn new(code: KeyCode, modifiers: KeyModifiers) -> Self {
+        Self { code, modifiers }
+    }
+
+    /// Creates a key press without modifiers.
+    pub const fn plain(code: KeyCode) -> Self {
+        Self::new(code, KeyModifiers::NONE)
+    }
+
+    /// Parses a single key such as `q`, `G`, `ctrl-d` or `enter`.
+    pub(super) fn parse(token: &str) -> Option<Self> {
+        let mut modifiers = KeyModifiers::NONE;
+        let mut rest = token;
+        loop {
+            let lower = rest.to_ascii_lowercase();
+            let (flag, len) = if lower.starts_with("ctrl-") {
+                (KeyModifiers::CONTROL, 5)
+            } else if lower.starts_with("alt-") {
+                (KeyModifiers::ALT, 4)
+            } else if lower.starts_with("shift-") {
+                (KeyModifiers::SHIFT, 6)
+            } else {
+                break;
+            };
+            // A trailing "-" is the minus key, not a separator.
+            if rest.len() == len {
+                break;
+            }
+            modifiers |= flag;
+            rest = &rest[len..];
+        }
+
+        let mut chars = rest.chars();
+        let code = match (chars.next(), chars.next()) {
+            (Some(c), None) => KeyCode::Char(c),
+            _ => match rest.to_ascii_lowercase().as_str() {
+                "enter" => KeyCode::Enter,
+                "esc" => KeyCode::Esc,
+                "tab" if modifiers.contains(KeyModifiers::SHIFT) => KeyCode::BackTab,
+                "tab" => KeyCode::Tab,
+                "backtab" => KeyCode::BackTab,
+                "backspace" => KeyCode::Backspace,
+                "delete" => KeyCode::Delete,
+                "insert" => KeyCode::Insert,
+                "up" => KeyCode::Up,
+                "down" => KeyCode::Down,
+                "left" => KeyCode::Left,
+                "right" => KeyCode::Right,
+                "home" => KeyCode::Home,
+                "end" => KeyCode::End,
+                "pageup" => KeyCode::PageUp,
+                "pagedown" => KeyCode::PageDown,
+                "space" => KeyCode::Char(' '),
+                name => {
+                    let number = name.strip_prefix('f')?.parse().ok()?;
+                    if !(1..=12).contains(&number) {
+                        return None;
+                    }
+                    KeyCode::F(number)
+                }
+            },
+        };
+        Some(Self::normalized(code, modifiers))
+    }
+
+    /// Drops modifiers that are already encoded in the key code, so
+    /// `G` matches whether or not the terminal reports Shift.
+    fn normalized(code: KeyCode, modifiers: KeyModifiers) -> Self {
+        let mut modifiers =
+            modifiers & (KeyModifiers::CONTROL | KeyModifiers::ALT | KeyModifiers::SHIFT);
+        if matches!(code, KeyCode::Char(_) | KeyCode::BackTab) {
+            modifiers.remove(KeyModifiers::SHIFT);
+        }
+        Self { code, modifiers }
+    }
+}
+
+impl From<KeyEvent> for KeyPress {
+    fn from(event: KeyEvent) -> Self {
+        Self::normalized(event.code, event.modifiers)
+    }
+}
+
+impl fmt::Display for KeyPress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.modifiers.contains(KeyModifiers::CONTROL) {
+            f.write_str("ctrl-")?;
+        }
+        if self.modifiers.contains(KeyModifiers::ALT) {
+            f.write_str("alt-")?;
+        }
+        if self.modifiers.contains(KeyModifiers::SHIFT) {
+            f.write_str("shift-")?;
+        }
+        match self.code {
+            KeyCode::Char(' ') => f.write_str("space"),
+            KeyCode::Char(c) => write!(f, "{}", c),
+            KeyCode::F(n) => write!(f, "f{}", n),
+            KeyCode::Enter => f.write_str("enter"),
+            KeyCode::Esc => f.write_str("esc"),
+            KeyCode::Tab => f.write_str("tab"),
+            KeyCode::BackTab => f.write_str("shift-tab"),
+            KeyCode::Backspace => f.write_str("backspace"),
+            KeyCode::Delete => f.write_str("delete"),
+            KeyCode::Insert => f.write_str("insert"),
+            KeyCode::Up => f.write_str("up"),
+            KeyCode::Down => f.write_str("down"),
+            KeyCode::Left => f.write_str("left"),
+            KeyCode::Right => f.write_str("right"),
+            KeyCode::Home => f.write_str("home"),
+            KeyCode::End => f.write_str("end"),
+            KeyCode::PageUp => f.write_str("pageup"),
+            KeyCode::PageDown => f.write_str("pagedown"),
+            other => write!(f, "{:?}", other),
+        }
+    }
+}
+
+/// An ordered sequence of key presses, written `g g` in the config.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeySequence(pub(super) Vec<KeyPress>);
+
+impl KeySequence {
+    /// Returns the key presses in order.
+    pub fn presses(&self) -> &[KeyPress] {
+        &self.0
+    }
+
+    /// Parses a whitespace-separated sequence of keys.
+    pub(super) fn parse(text: &str) -> Option<Self> {
+        let presses = text
+            .split_whitespace()
+            .map(KeyPress::parse)
+            .collect::<Option<Vec<_>>>()?;
+        (!presses.is_empty()).then_some(Self(presses))
+    }
+
+    pub(super) fn single(press: KeyPress) -> Self {
+        Self(vec![press])
+    }
+}
+
+impl fmt::Display for KeySequence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, press) in self.0.iter().enumerate() {
+            if i > 0 {
+                f.write_str(" ")?;
+            }
+            write!(f, "{}", press)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn press(c: char) -> KeyPress {
+        KeyPress::plain(KeyCode::Char(c))
+    }
+
+    #[test]
+    fn test_parses_named_and_modified_keys() {
+        assert_eq!(
+            KeyPress::parse("enter"),
+            Some(KeyPress::plain(KeyCode::Enter))
+        );
+        assert_eq!(
+            KeyPress::parse("ctrl-d"),
+            Some(KeyPress::new(KeyCode::Char('d'), KeyModifiers::CONTROL))
+        );
+        assert_eq!(
+            KeyPress::parse("shift-tab"),
+            Some(KeyPress::plain(KeyCode::BackTab))
+        );
+        assert_eq!(KeyPress::parse("f5"), Some(KeyPress::plain(KeyCode::F(5))));
+        assert_eq!(KeyPress::parse("space"), Some(press(' ')));
+        assert_eq!(KeyPress::parse("-"), Some(press('-')));
+    }
+
+    #[test]
+    fn test_rejects_unknown_key_names() {
+        assert_eq!(KeyPress::parse("hyper-x"), None);
+        assert_eq!(KeyPress::parse("f13"), None);
+        assert_eq!(KeySequence::parse("   "), None);
+    }
+
+    #[test]
+    fn test_display_round_trips_through_parse() {
+        for text in ["q", "G", "ctrl-d", "g g", "shift-tab", "alt-enter", "space"] {
+            let keys = KeySequence::parse(text).unwrap();
+            assert_eq!(keys.to_string(), text);
+        }
+    }
+
+    #[test]
+    fn test_shifted_char_event_matches_uppercase_binding() {
+        let event = KeyEvent::new(KeyCode::Char('G'), KeyModifiers::SHIFT);
+        assert_eq!(KeyPress::from(event), press('G'));
+    }
+}
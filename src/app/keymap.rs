@@ -0,0 +1,398 @@
+//! Key bindings: parsing, conflict detection, and lookup.
+
+use super::action::Action;
+pub use super::key_press::{KeyPress, KeySequence};
+use super::state::Panel;
+use crate::config::KeyList;
+use crate::error::{LazyFileError, Result};
+use crossterm::event::{KeyCode, KeyModifiers};
+use std::collections::BTreeMap;
+
+/// A key sequence bound to an action.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Binding {
+    /// Keys that trigger the action.
+    pub keys: KeySequence,
+    /// The action to run.
+    pub action: Action,
+}
+
+/// Result of looking up the keys pressed so far.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyMatch {
+    /// The keys complete a binding.
+    Action(Action),
+    /// The keys are a prefix of at least one binding.
+    Pending,
+    /// No binding starts with the keys.
+    Unbound,
+}
+
+/// The active set of key bindings.
+#[derive(Debug, Clone)]
+pub struct Keymap {
+    bindings: Vec<Binding>,
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        use KeyCode::{Backspace, Char, Down, Enter, Esc, F, Left, Right, Tab, Up};
+
+        let plain = |code| KeySequence::single(KeyPress::plain(code));
+        let ctrl = |c| KeySequence::single(KeyPress::new(Char(c), KeyModifiers::CONTROL));
+        let defaults = [
+            (Action::Quit, plain(Char('q'))),
+            (Action::NavigateDown, plain(Char('j'))),
+            (Action::NavigateDown, plain(Down)),
+            (Action::NavigateUp, plain(Char('k'))),
+            (Action::NavigateUp, plain(Up)),
+            (
+                Action::JumpTop,
+                KeySequence(vec![KeyPress::plain(Char('g')), KeyPress::plain(Char('g'))]),
+            ),
+            (Action::JumpBottom, plain(Char('G'))),
+            (Action::HalfPageDown, ctrl('d')),
+            (Action::HalfPageUp, ctrl('u')),
+            (Action::ScreenTop, plain(Char('H'))),
+            (Action::ScreenMiddle, plain(Char('M'))),
+            (Action::ScreenBottom, plain(Char('L'))),
+            (Action::SwitchPanel, plain(Tab)),
+            (Action::Open, plain(Enter)),
+            (Action::Back, plain(Backspace)),
+            (Action::Cancel, plain(Esc)),
+            (Action::ShowHelp, plain(Char('?'))),
+            (Action::CommandPalette, plain(Char(':'))),
+            (
+                Action::GoToPath,
+                KeySequence(vec![KeyPress::plain(Char('g')), KeyPress::plain(Char('p'))]),
+            ),
+            (Action::RecentLocations, ctrl('r')),
+            (
+                Action::ShowMounts,
+                KeySequence(vec![KeyPress::plain(Char('g')), KeyPress::plain(Char('m'))]),
+            ),
+            (Action::ShowJobs, plain(Char('J'))),
+            (
+                Action::TransferHistory,
+                KeySequence(vec![KeyPress::plain(Char('g')), KeyPress::plain(Char('h'))]),
+            ),
+            (
+                Action::AuditLog,
+                KeySequence(vec![KeyPress::plain(Char('g')), KeyPress::plain(Char('a'))]),
+            ),
+            (Action::ToggleLogs, plain(Char('~'))),
+            (Action::ToggleLogFollow, plain(Char('f'))),
+            (Action::ToggleDebugLogging, plain(Char('d'))),
+            (Action::ToggleCalls, plain(F(12))),
+            (
+                Action::WidenRemotes,
+                KeySequence::single(KeyPress::new(Right, KeyModifiers::CONTROL)),
+            ),
+            (
+                Action::NarrowRemotes,
+                KeySequence::single(KeyPress::new(Left, KeyModifiers::CONTROL)),
+            ),
+            (Action::ToggleSidebar, ctrl('b')),
+            (Action::ToggleZoom, plain(Char('Z'))),
+            (Action::AddRemote, plain(Char('a'))),
+            (Action::EditRemote, plain(Char('e'))),
+            (Action::DeleteRemote, plain(Char('d'))),
+            (Action::ShowRemoteInfo, plain(Char('i'))),
+            (Action::RenameRemote, plain(Char('R'))),
+            (Action::DuplicateRemote, plain(Char('D'))),
+            (Action::ToggleFavorite, plain(Char('s'))),
+            (Action::MoveRemoteUp, plain(Char('['))),
+            (Action::MoveRemoteDown, plain(Char(']'))),
+            (Action::ToggleMark, plain(Char(' '))),
+            (Action::MarkAll, ctrl('a')),
+            (Action::InvertMarks, plain(Char('*'))),
+            (Action::MarkGlob, plain(Char('+'))),
+            (Action::Filter, plain(Char('f'))),
+            (Action::ToggleColumns, plain(Char('v'))),
+            (Action::ToggleExact, plain(Char('#'))),
+            (Action::DeleteFile, plain(Char('D'))),
+            (Action::Mkdir, plain(Char('n'))),
+            (Action::CopyFile, plain(Char('c'))),
+            (Action::MoveFile, plain(Char('m'))),
+            (Action::BatchRename, plain(Char('R'))),
+            (Action::ChangeTier, plain(Char('T'))),
+            (Action::ShowMetadata, plain(Char('I'))),
+            (Action::ShowVersions, plain(Char('V'))),
+            (Action::CopyUrl, plain(Char('U'))),
+            (Action::Preview, plain(Char('i'))),
+            (Action::EditFile, plain(Char('e'))),
+            (Action::OpenFile, plain(Char('o'))),
+            (Action::Yank, plain(Char('y'))),
+            (Action::Cut, plain(Char('x'))),
+            (Action::Put, plain(Char('p'))),
+            (
+                Action::CopyPath,
+                KeySequence(vec![KeyPress::plain(Char('g')), KeyPress::plain(Char('y'))]),
+            ),
+            (
+                Action::CopyCommand,
+                KeySequence(vec![KeyPress::plain(Char('g')), KeyPress::plain(Char('Y'))]),
+            ),
+            (Action::ShowUsage, plain(Char('u'))),
+            (Action::Compare, plain(Char('C'))),
+            (Action::ToggleTree, plain(Char('t'))),
+            (Action::ExpandDir, plain(Char('l'))),
+            (Action::ExpandDir, plain(Right)),
+            (Action::CollapseDir, plain(Char('h'))),
+            (Action::CollapseDir, plain(Left)),
+            (Action::ToggleFlat, plain(Char('F'))),
+            (Action::SortFlat, plain(Char('S'))),
+            (Action::NarrowFlat, plain(Char('/'))),
+            (Action::LargestFiles, plain(Char('W'))),
+        ];
+        Self {
+            bindings: defaults
+                .into_iter()
+                .map(|(action, keys)| Binding { keys, action })
+                .collect(),
+        }
+    }
+}
+
+impl Keymap {
+    /// Builds the keymap from the defaults plus the config file's
+    /// `[keys]` table. An action listed in the table loses all of
+    /// its default keys; an empty list unbinds it.
+    ///
+    /// # Errors
+    /// Returns `InvalidKeyBinding` for unknown action names or
+    /// unparseable keys, and `KeyConflict` when two actions reachable
+    /// from the same context share a sequence or one is a prefix of
+    /// the other.
+    pub fn from_config(overrides: &BTreeMap<String, KeyList>) -> Result<Self> {
+        let mut keymap = Self::default();
+        for (name, keys) in overrides {
+            let Some(action) = Action::from_name(name) else {
+                return Err(LazyFileError::InvalidKeyBinding {
+                    action: name.clone(),
+                    keys: keys.as_slice().join(", "),
+                    reason: "unknown action",
+                });
+            };
+            keymap.bindings.retain(|binding| binding.action != action);
+            for text in keys.as_slice() {
+                let keys =
+                    KeySequence::parse(text).ok_or_else(|| LazyFileError::InvalidKeyBinding {
+                        action: name.clone(),
+                        keys: text.clone(),
+                        reason: "unrecognized key",
+                    })?;
+                keymap.bindings.push(Binding { keys, action });
+            }
+        }
+        keymap.check_conflicts()?;
+        Ok(keymap)
+    }
+
+    /// Returns all bindings, defaults first.
+    pub fn bindings(&self) -> &[Binding] {
+        &self.bindings
+    }
+
+    /// Returns the key sequences bound to `action`.
+    pub fn keys_for(&self, action: Action) -> impl Iterator<Item = &KeySequence> {
+        self.bindings
+            .iter()
+            .filter(move |binding| binding.action == action)
+            .map(|binding| &binding.keys)
+    }
+
+    /// Looks up the keys pressed so far against the bindings active
+    /// for `panel`.
+    pub fn resolve(&self, pressed: &[KeyPress], panel: Panel) -> KeyMatch {
+        let mut is_prefix = false;
+        for binding in &self.bindings {
+            if !binding.action.context().applies_to(panel) {
+                continue;
+            }
+            let keys = binding.keys.presses();
+            if keys == pressed {
+                return KeyMatch::Action(binding.action);
+            }
+            is_prefix |= keys.starts_with(pressed);
+        }
+        if is_prefix {
+            KeyMatch::Pending
+        } else {
+            KeyMatch::Unbound
+        }
+    }
+
+    fn check_conflicts(&self) -> Result<()> {
+        for (i, first) in self.bindings.iter().enumerate() {
+            for second in &self.bindings[i + 1..] {
+                if first.action == second.action
+                    || !first.action.context().overlaps(second.action.context())
+                {
+                    continue;
+                }
+                let (a, b) = (first.keys.presses(), second.keys.presses());
+                if a.starts_with(b) || b.starts_with(a) {
+                    let shorter = if a.len() <= b.len() {
+                        &first.keys
+                    } else {
+                        &second.keys
+                    };
+                    return Err(LazyFileError::KeyConflict {
+                        keys: shorter.to_string(),
+                        first: first.action.name(),
+                        second: second.action.name(),
+                    });
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn overrides(entries: &[(&str, &[&str])]) -> BTreeMap<String, KeyList> {
+        entries
+            .iter()
+            .map(|(name, keys)| {
+                (
+                    name.to_string(),
+                    KeyList::Many(keys.iter().map(|k| k.to_string()).collect()),
+                )
+            })
+            .collect()
+    }
+
+    fn press(c: char) -> KeyPress {
+        KeyPress::plain(KeyCode::Char(c))
+    }
+
+    #[test]
+    fn test_default_keymap_has_no_conflicts() {
+        assert!(Keymap::default().check_conflicts().is_ok());
+    }
+
+    #[test]
+    fn test_resolves_single_and_multi_key_bindings() {
+        let keymap = Keymap::default();
+        assert_eq!(
+            keymap.resolve(&[press('q')], Panel::Remotes),
+            KeyMatch::Action(Action::Quit)
+        );
+        assert_eq!(
+            keymap.resolve(&[press('g')], Panel::Files),
+            KeyMatch::Pending
+        );
+        assert_eq!(
+            keymap.resolve(&[press('g'), press('g')], Panel::Files),
+            KeyMatch::Action(Action::JumpTop)
+        );
+        assert_eq!(
+            keymap.resolve(&[press('z')], Panel::Files),
+            KeyMatch::Unbound
+        );
+    }
+
+    #[test]
+    fn test_panel_bindings_only_fire_in_their_panel() {
+        let keymap = Keymap::default();
+        assert_eq!(
+            keymap.resolve(&[press('d')], Panel::Remotes),
+            KeyMatch::Action(Action::DeleteRemote)
+        );
+        assert_eq!(
+            keymap.resolve(&[press('d')], Panel::Files),
+            KeyMatch::Unbound
+        );
+    }
+
+    #[test]
+    fn test_override_replaces_default_keys() {
+        let keymap = Keymap::from_config(&overrides(&[("quit", &["Q", "ctrl-q"])])).unwrap();
+        assert_eq!(
+            keymap.resolve(&[press('q')], Panel::Files),
+            KeyMatch::Unbound
+        );
+        assert_eq!(
+            keymap.resolve(&[press('Q')], Panel::Files),
+            KeyMatch::Action(Action::Quit)
+        );
+        assert_eq!(keymap.keys_for(Action::Quit).count(), 2);
+    }
+
+    #[test]
+    fn test_single_string_is_accepted() {
+        let mut map = BTreeMap::new();
+        map.insert("mkdir".to_string(), KeyList::One("N".to_string()));
+        let keymap = Keymap::from_config(&map).unwrap();
+        assert_eq!(
+            keymap.resolve(&[press('N')], Panel::Files),
+            KeyMatch::Action(Action::Mkdir)
+        );
+    }
+
+    #[test]
+    fn test_empty_list_unbinds_action() {
+        let keymap = Keymap::from_config(&overrides(&[("delete_remote", &[])])).unwrap();
+        assert_eq!(keymap.keys_for(Action::DeleteRemote).count(), 0);
+    }
+
+    #[test]
+    fn test_rejects_unknown_action() {
+        let err = Keymap::from_config(&overrides(&[("fly", &["f"])])).unwrap_err();
+        assert!(matches!(
+            err,
+            LazyFileError::InvalidKeyBinding {
+                reason: "unknown action",
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn test_rejects_unparseable_key() {
+        let err = Keymap::from_config(&overrides(&[("quit", &["meta-q"])])).unwrap_err();
+        assert!(matches!(
+            err,
+            LazyFileError::InvalidKeyBinding {
+                reason: "unrecognized key",
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn test_detects_conflict_with_global_binding() {
+        let err = Keymap::from_config(&overrides(&[("mkdir", &["q"])])).unwrap_err();
+        assert!(matches!(
+            err,
+            LazyFileError::KeyConflict {
+                first: "quit",
+                second: "mkdir",
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn test_detects_prefix_conflict() {
+        let err = Keymap::from_config(&overrides(&[("quit", &["g"])])).unwrap_err();
+        assert!(matches!(err, LazyFileError::KeyConflict { .. }));
+    }
+
+    #[test]
+    fn test_same_key_in_separate_panels_is_allowed() {
+        let keymap = Keymap::from_config(&overrides(&[("mkdir", &["d"])])).unwrap();
+        assert_eq!(
+            keymap.resolve(&[press('d')], Panel::Files),
+            KeyMatch::Action(Action::Mkdir)
+        );
+        assert_eq!(
+            keymap.resolve(&[press('d')], Panel::Remotes),
+            KeyMatch::Action(Action::DeleteRemote)
+        );
+    }
+}
@@ -0,0 +1,264 @@
+//! Directory listings kept for a short while, so opening a directory
+//! that was just listed, or prefetched while the cursor rested on it,
+//! needs no round trip to the daemon.
+//!
+//! Anything that may have changed a directory goes through
+//! [`App::reload_files`](super::state::App::reload_files), which
+//! clears the cache, so only changes made outside LazyFile can show
+//! up late, and never later than [`CACHE_TTL`].
+
+use super::event::AppEvent;
+use super::state::{App, Panel};
+use crate::error::Result;
+use crate::rclone::FileItem;
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+use tracing::debug;
+
+/// How long a listing is served from the cache.
+pub const CACHE_TTL: Duration = Duration::from_secs(30);
+
+/// Most directories kept; the oldest listing goes first.
+const MAX_CACHED: usize = 64;
+
+/// How long the cursor must rest on a directory before it is
+/// prefetched.
+pub const PREFETCH_DELAY: Duration = Duration::from_millis(300);
+
+/// A listing and when it was fetched.
+#[derive(Debug, Clone)]
+struct Cached {
+    files: Vec<FileItem>,
+    fetched: Instant,
+}
+
+/// Recent listings, keyed by remote and path.
+#[derive(Debug, Clone, Default)]
+pub struct ListingCache {
+    entries: HashMap<(String, String), Cached>,
+    generation: u64,
+}
+
+impl ListingCache {
+    /// Returns the listing of `path` on `remote` if it was fetched
+    /// less than [`CACHE_TTL`] before `now`.
+    pub fn get(&self, remote: &str, path: &str, now: Instant) -> Option<&[FileItem]> {
+        self.entries
+            .get(&(remote.to_string(), path.to_string()))
+            .filter(|cached| now.saturating_duration_since(cached.fetched) < CACHE_TTL)
+            .map(|cached| cached.files.as_slice())
+    }
+
+    /// Returns the last listing of `path` on `remote`, however old,
+    /// for browsing while the daemon is unreachable.
+    pub fn get_stale(&self, remote: &str, path: &str) -> Option<&[FileItem]> {
+        self.entries
+            .get(&(remote.to_string(), path.to_string()))
+            .map(|cached| cached.files.as_slice())
+    }
+
+    /// Keeps the listing of `path` on `remote`, fetched at `now`,
+    /// dropping the oldest listing past the limit.
+    pub fn insert(&mut self, remote: String, path: String, files: Vec<FileItem>, now: Instant) {
+        self.entries.insert(
+            (remote, path),
+            Cached {
+                files,
+                fetched: now,
+            },
+        );
+        if self.entries.len() > MAX_CACHED
+            && let Some(oldest) = self
+                .entries
+                .iter()
+                .min_by_key(|(_, cached)| cached.fetched)
+                .map(|(key, _)| key.clone())
+        {
+            self.entries.remove(&oldest);
+        }
+    }
+
+    /// Forgets every listing. Fetches started before are recognised by
+    /// their [`Self::generation`] and not kept.
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.generation += 1;
+    }
+
+    /// Returns a number that changes whenever the cache is cleared.
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+}
+
+/// The directory the cursor rests on, and whether it was prefetched.
+#[derive(Debug, Clone)]
+pub struct Prefetch {
+    /// Remote and path of the highlighted directory, if one is.
+    pub target: Option<(String, String)>,
+    /// When the cursor reached it.
+    pub since: Instant,
+    /// Whether its listing was requested.
+    pub requested: bool,
+}
+
+impl Prefetch {
+    /// Creates the state with nothing highlighted yet.
+    pub fn new(now: Instant) -> Self {
+        Self {
+            target: None,
+            since: now,
+            requested: false,
+        }
+    }
+}
+
+impl App {
+    /// Returns the remote and path of the directory under the cursor
+    /// in the files panel, if nothing else is going on and the daemon
+    /// answers.
+    fn prefetch_target(&self) -> Option<(String, String)> {
+        if self.focused_panel != Panel::Files
+            || self.modal.is_some()
+            || self.loading.is_some()
+            || self.offline()
+        {
+            return None;
+        }
+        let remote = self.current_remote.clone()?;
+        let item = self.files.get(self.files_selected)?;
+        item.is_dir()
+            .then(|| (remote, self.child_path(item.name())))
+    }
+
+    /// Prefetches the highlighted directory on a background task once
+    /// the cursor has rested on it for [`PREFETCH_DELAY`]. The
+    /// listing arrives as [`AppEvent::Prefetched`].
+    pub fn tick_prefetch(&mut self, now: Instant) {
+        let target = self.prefetch_target();
+        if target != self.prefetch.target {
+            self.prefetch = Prefetch {
+                target,
+                ..Prefetch::new(now)
+            };
+            return;
+        }
+        let Some((ref remote, ref path)) = self.prefetch.target else {
+            return;
+        };
+        if self.prefetch.requested
+            || now.saturating_duration_since(self.prefetch.since) < PREFETCH_DELAY
+            || self.listing_cache.get(remote, path, now).is_some()
+        {
+            return;
+        }
+        self.prefetch.requested = true;
+        debug!(remote = %remote, path = %path, "prefetching listing");
+        let client = self.client.clone();
+        let filter = self.filter.clone();
+        let hash = self.hash_column().map(str::to_string);
+        let generation = self.listing_cache.generation();
+        let (remote, path) = (remote.clone(), path.clone());
+        self.workers.spawn(async move {
+            let result = client
+                .list_dir(&remote, &path, hash.as_deref(), &filter)
+                .await;
+            AppEvent::Prefetched {
+                remote,
+                path,
+                generation,
+                result,
+            }
+        });
+    }
+
+    /// Keeps a prefetched listing, unless the cache was cleared since
+    /// it was requested. A failed prefetch is left for the real
+    /// listing to report.
+    pub fn apply_prefetch(
+        &mut self,
+        remote: String,
+        path: String,
+        generation: u64,
+        result: Result<Vec<FileItem>>,
+    ) {
+        match result {
+            Ok(files) if generation == self.listing_cache.generation() => {
+                debug!(remote = %remote, path = %path, count = files.len(), "prefetched listing");
+                self.listing_cache
+                    .insert(remote, path, files, Instant::now());
+            }
+            Ok(_) => debug!(remote = %remote, path = %path, "dropping outdated prefetch"),
+            Err(e) => debug!(remote = %remote, path = %path, error = %e, "prefetch failed"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::handler::test_support::unreachable_app;
+
+    fn dir(name: &str) -> FileItem {
+        FileItem {
+            name: name.to_string(),
+            is_dir: true,
+            ..FileItem::default()
+        }
+    }
+
+    #[test]
+    fn test_serves_fresh_listings_and_evicts_oldest() {
+        let now = Instant::now();
+        let mut cache = ListingCache::default();
+        cache.insert("s3".to_string(), "a".to_string(), vec![dir("x")], now);
+        assert_eq!(cache.get("s3", "a", now).unwrap().len(), 1);
+        assert!(cache.get("s3", "a", now + CACHE_TTL).is_none());
+
+        for i in 0..MAX_CACHED {
+            let later = now + Duration::from_millis(1 + i as u64);
+            cache.insert("s3".to_string(), i.to_string(), Vec::new(), later);
+        }
+        assert!(cache.get("s3", "a", now).is_none());
+        assert!(cache.get("s3", "0", now).is_some());
+
+        let generation = cache.generation();
+        cache.clear();
+        assert!(cache.get("s3", "0", now).is_none());
+        assert_ne!(cache.generation(), generation);
+    }
+
+    #[tokio::test]
+    async fn test_prefetches_after_the_cursor_rests() {
+        let mut app = unreachable_app();
+        app.focused_panel = Panel::Files;
+        app.current_remote = Some("s3".to_string());
+        app.current_path = "docs".to_string();
+        app.files = vec![dir("2024")];
+        let now = Instant::now();
+
+        app.tick_prefetch(now);
+        assert!(!app.prefetch.requested);
+        app.tick_prefetch(now + PREFETCH_DELAY);
+        assert!(app.prefetch.requested);
+        let event = app.wait_event().await.unwrap();
+        assert!(matches!(
+            event,
+            AppEvent::Prefetched { ref path, .. } if path == "docs/2024"
+        ));
+
+        app.apply_prefetch(
+            "s3".to_string(),
+            "docs/2024".to_string(),
+            app.listing_cache.generation(),
+            Ok(vec![dir("q1")]),
+        );
+        app.request_files("s3".to_string(), "docs/2024".to_string());
+        let Some(AppEvent::FilesLoaded(listing)) = app.wait_event().await else {
+            panic!("no listing");
+        };
+        assert!(listing.cached);
+        app.apply_listing(listing).unwrap();
+        assert_eq!(app.files[0].name, "q1");
+    }
+}
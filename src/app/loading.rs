@@ -0,0 +1,324 @@
+//! Directory listings and previews, which run in the background.
+//!
+//! Each request runs on the [`Workers`](super::worker::Workers) pool
+//! and reports back with an [`AppEvent`]. File operations, remote
+//! configuration, inspections, mounts and jobs are requested the same
+//! way from their own modules.
+
+use super::event::{AppEvent, Listing};
+use super::state::{App, Panel};
+use crate::error::Result;
+use crate::rclone::{CancelToken, FileItem};
+use crate::ui::PreviewContent;
+use crate::ui::widgets::preview::PREVIEW_LIMIT;
+use std::time::Instant;
+use tracing::{debug, info, warn};
+
+/// A directory listing in flight.
+#[derive(Debug)]
+pub struct Loading {
+    /// Request identifier, matched against [`Listing::id`].
+    pub id: u64,
+    /// Remote being listed.
+    pub remote: String,
+    /// Path being listed.
+    pub path: String,
+    /// When the request started, which drives the spinner.
+    pub started: Instant,
+    cancel: CancelToken,
+}
+
+impl App {
+    /// Starts listing `path` on `remote` on a background task.
+    ///
+    /// Nothing changes until the listing arrives: then the files
+    /// panel shows it and takes focus. A newer request supersedes
+    /// this one.
+    pub fn request_files(&mut self, remote: String, path: String) {
+        self.cancel_loading();
+        self.select_on_load = None;
+        self.focus_on_load = None;
+        self.next_request_id += 1;
+        let id = self.next_request_id;
+        debug!(remote = %remote, path = %path, id, "loading files");
+
+        let cancel = CancelToken::new();
+        let client = self.client.with_cancel(cancel.clone());
+        let filter = self.filter.clone();
+        let hash = self.hash_column().map(str::to_string);
+        let (task_remote, task_path) = (remote.clone(), path.clone());
+        let recursive = self.flat.is_some();
+        let cached = if recursive {
+            None
+        } else if self.offline() {
+            self.listing_cache.get_stale(&remote, &path)
+        } else {
+            self.listing_cache.get(&remote, &path, Instant::now())
+        }
+        .map(<[FileItem]>::to_vec);
+        self.workers.spawn(async move {
+            let (cached, result) = match cached {
+                Some(files) => {
+                    debug!(id, "serving cached listing");
+                    (true, Ok(files))
+                }
+                None if recursive => {
+                    let result = client
+                        .list_recursive(&task_remote, &task_path, hash.as_deref(), &filter)
+                        .await;
+                    (false, result)
+                }
+                None => {
+                    let result = client
+                        .list_dir(&task_remote, &task_path, hash.as_deref(), &filter)
+                        .await;
+                    (false, result)
+                }
+            };
+            AppEvent::FilesLoaded(Listing {
+                id,
+                remote: task_remote,
+                path: task_path,
+                cached,
+                recursive,
+                result,
+            })
+        });
+        self.loading = Some(Loading {
+            id,
+            remote,
+            path,
+            started: Instant::now(),
+            cancel,
+        });
+    }
+
+    /// Starts fetching the first [`PREVIEW_LIMIT`] bytes of a file on
+    /// a background task, highlighted with the current theme's
+    /// scheme. The result arrives as [`AppEvent::PreviewLoaded`].
+    pub fn request_preview(&self, remote: String, path: String) {
+        debug!(remote = %remote, path = %path, "loading preview");
+        let client = self.client.clone();
+        let syntax = self.theme.syntax;
+        self.workers.spawn(async move {
+            let content = match client.read_file(&remote, &path, PREVIEW_LIMIT).await {
+                Ok(bytes) => {
+                    let file = path.clone();
+                    tokio::task::spawn_blocking(move || {
+                        PreviewContent::highlighted(bytes, &file, syntax)
+                    })
+                    .await
+                    .unwrap_or_else(|e| PreviewContent::Failed(e.to_string()))
+                }
+                Err(e) => {
+                    warn!(remote = %remote, path = %path, error = %e, "preview failed");
+                    PreviewContent::Failed(e.to_string())
+                }
+            };
+            AppEvent::PreviewLoaded {
+                remote,
+                path,
+                content,
+            }
+        });
+    }
+
+    /// Looks up `path` on `remote` for the go-to prompt on a
+    /// background task. A remote's root always exists, so it is not
+    /// looked up. The result arrives as [`AppEvent::GoToResolved`].
+    pub fn request_go_to(&self, remote: String, path: String) {
+        debug!(remote = %remote, path = %path, "looking up go-to location");
+        let client = self.client.clone();
+        self.workers.spawn(async move {
+            let result = if path.is_empty() {
+                Ok(Some(FileItem {
+                    is_dir: true,
+                    ..FileItem::default()
+                }))
+            } else {
+                client.stat(&remote, &path).await
+            };
+            AppEvent::GoToResolved {
+                remote,
+                path,
+                result,
+            }
+        });
+    }
+
+    /// Lists the current directory again, if a remote is open. The
+    /// listing cache is cleared first: reloads follow changes, which
+    /// may have touched any cached directory.
+    pub fn reload_files(&mut self) {
+        self.listing_cache.clear();
+        if let Some(remote) = self.current_remote.clone() {
+            self.request_files(remote, self.current_path.clone());
+        }
+    }
+
+    /// Abandons the listing in flight, if any, dropping its request
+    /// to the daemon.
+    pub fn cancel_loading(&mut self) {
+        if let Some(loading) = self.loading.take() {
+            debug!(id = loading.id, "cancelling listing");
+            loading.cancel.cancel();
+        }
+    }
+
+    /// Returns the next message from a background task without
+    /// waiting.
+    pub fn next_event(&mut self) -> Option<AppEvent> {
+        self.events_rx.try_recv().ok()
+    }
+
+    /// Waits for the next message from a background task.
+    pub(crate) async fn wait_event(&mut self) -> Option<AppEvent> {
+        self.events_rx.recv().await
+    }
+
+    /// Waits for the next message from a background task that `wanted`
+    /// accepts, dropping the ones before it. Requests running alongside
+    /// finish in any order, so tests wait for the one they look at.
+    #[cfg(test)]
+    pub(crate) async fn wait_event_matching(
+        &mut self,
+        wanted: impl Fn(&AppEvent) -> bool,
+    ) -> Option<AppEvent> {
+        while let Some(event) = self.wait_event().await {
+            if wanted(&event) {
+                return Some(event);
+            }
+        }
+        None
+    }
+
+    /// Applies a finished listing. Listings that were cancelled or
+    /// superseded are dropped.
+    ///
+    /// # Errors
+    /// Returns the listing's error if the current request failed.
+    pub fn apply_listing(&mut self, listing: Listing) -> Result<()> {
+        if self.loading.as_ref().is_none_or(|l| l.id != listing.id) {
+            debug!(id = listing.id, "dropping stale listing");
+            return Ok(());
+        }
+        self.loading = None;
+        match listing.result {
+            Ok(files) => {
+                info!(count = files.len(), cached = listing.cached, "loaded files");
+                if !listing.cached && !listing.recursive {
+                    self.listing_cache.insert(
+                        listing.remote.clone(),
+                        listing.path.clone(),
+                        files.clone(),
+                        Instant::now(),
+                    );
+                }
+                let same_dir = self.current_remote.as_deref() == Some(listing.remote.as_str())
+                    && self.current_path == listing.path;
+                self.current_remote = Some(listing.remote);
+                self.current_path = listing.path;
+                if listing.recursive {
+                    self.apply_flat(files);
+                } else {
+                    self.files = files;
+                    self.apply_tree_top(same_dir);
+                }
+                self.retain_marks(same_dir);
+                self.files_selected = 0;
+                self.files_offset = 0;
+                if let Some(name) = self.select_on_load.take()
+                    && let Some(index) = self.files.iter().position(|item| item.name() == name)
+                {
+                    self.files_selected = index;
+                    self.keep_selection_visible();
+                }
+                self.focused_panel = self.focus_on_load.take().unwrap_or(Panel::Files);
+                self.connected = true;
+                self.remember_location();
+                Ok(())
+            }
+            Err(e) => {
+                self.connected = false;
+                Err(e)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::handler::test_support::unreachable_app;
+    use crate::error::LazyFileError;
+    use crate::rclone::FileItem;
+
+    fn listing(id: u64, result: Result<Vec<FileItem>>) -> Listing {
+        Listing {
+            id,
+            remote: "gdrive".to_string(),
+            path: "docs".to_string(),
+            cached: false,
+            recursive: false,
+            result,
+        }
+    }
+
+    fn file(name: &str) -> FileItem {
+        FileItem {
+            name: name.to_string(),
+            size: 0,
+            mod_time: String::new(),
+            is_dir: false,
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_listing_commits_navigation() {
+        let mut app = unreachable_app();
+        app.request_files("gdrive".to_string(), "docs".to_string());
+        let id = app.loading.as_ref().unwrap().id;
+
+        app.apply_listing(listing(id, Ok(vec![file("a.txt")])))
+            .unwrap();
+
+        assert!(app.loading.is_none());
+        assert_eq!(app.current_remote.as_deref(), Some("gdrive"));
+        assert_eq!(app.current_path, "docs");
+        assert_eq!(app.files.len(), 1);
+        assert_eq!(app.focused_panel, Panel::Files);
+    }
+
+    #[tokio::test]
+    async fn test_stale_listing_is_dropped() {
+        let mut app = unreachable_app();
+        app.request_files("gdrive".to_string(), "old".to_string());
+        let stale = app.loading.as_ref().unwrap().id;
+        app.request_files("gdrive".to_string(), "docs".to_string());
+
+        app.apply_listing(listing(stale, Ok(vec![file("a.txt")])))
+            .unwrap();
+
+        assert!(app.loading.is_some());
+        assert!(app.files.is_empty());
+        assert!(app.current_remote.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_failed_listing_keeps_view() {
+        let mut app = unreachable_app();
+        app.request_files("gdrive".to_string(), "docs".to_string());
+        let id = app.loading.as_ref().unwrap().id;
+
+        let err = LazyFileError::InvalidInput {
+            field: "path",
+            reason: "test",
+        };
+        assert!(app.apply_listing(listing(id, Err(err))).is_err());
+
+        assert!(app.loading.is_none());
+        assert!(app.current_remote.is_none());
+        assert!(!app.connected);
+    }
+}
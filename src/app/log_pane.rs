@@ -0,0 +1,159 @@
+//! Log pane visibility, follow mode and log level.
+
+use super::state::{App, Panel};
+
+impl App {
+    /// Shows or hides the log pane. Hiding it while focused hands
+    /// focus back to the files panel.
+    pub fn toggle_logs(&mut self) {
+        self.logs_visible = !self.logs_visible;
+        if !self.logs_visible && self.focused_panel == Panel::Logs {
+            self.focused_panel = Panel::Files;
+        }
+        // Re-split the screen so viewport heights match the new layout.
+        self.set_screen(self.screen);
+    }
+
+    /// Pins the log pane to the newest line, or releases it at the
+    /// current selection.
+    pub fn toggle_log_follow(&mut self) {
+        self.logs_follow = !self.logs_follow;
+        self.sync_logs();
+    }
+
+    /// Switches the log pane and log file to debug level, or back to
+    /// the level they started with.
+    pub fn toggle_debug_logging(&mut self) {
+        let Some(ref mut verbosity) = self.verbosity else {
+            self.notifications
+                .warning("Logging was set up outside LazyFile");
+            return;
+        };
+        match verbosity.toggle() {
+            Ok(true) => self.notifications.info("Debug logging on"),
+            Ok(false) => self.notifications.info("Debug logging off"),
+            Err(e) => self
+                .notifications
+                .error(format!("Could not change log level: {}", e)),
+        }
+    }
+
+    /// Keeps the log selection in range as lines arrive and, in
+    /// follow mode, moves it to the newest line. Called once per
+    /// frame; new lines flag a redraw while the pane is shown.
+    pub fn sync_logs(&mut self) {
+        let pushed = self.logs.pushed();
+        if pushed != self.logs_seen {
+            self.logs_seen = pushed;
+            self.dirty |= self.logs_visible;
+        }
+        let len = self.logs.len();
+        self.logs_selected = if self.logs_follow {
+            len.saturating_sub(1)
+        } else {
+            self.logs_selected.min(len.saturating_sub(1))
+        };
+        let rows = self.logs_viewport_height.max(1);
+        if self.logs_selected >= self.logs_offset + rows {
+            self.logs_offset = self.logs_selected + 1 - rows;
+        } else if self.logs_selected < self.logs_offset {
+            self.logs_offset = self.logs_selected;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::handler::test_support::unreachable_app;
+    use crate::logs::LogLine;
+    use ratatui::layout::Rect;
+    use tracing::Level;
+
+    fn create_app(lines: usize) -> App {
+        let mut app = unreachable_app();
+        for i in 0..lines {
+            app.logs.push(LogLine {
+                level: Level::INFO,
+                target: "test".to_string(),
+                message: format!("line {}", i),
+            });
+        }
+        app.toggle_logs();
+        app.set_screen(Rect::new(0, 0, 80, 40));
+        app
+    }
+
+    #[test]
+    fn test_follow_tracks_newest_line() {
+        let mut app = create_app(50);
+        app.sync_logs();
+        assert_eq!(app.logs_selected, 49);
+        assert_eq!(app.logs_offset + app.logs_viewport_height, 50);
+    }
+
+    #[test]
+    fn test_moving_up_pauses_follow() {
+        let mut app = create_app(50);
+        app.sync_logs();
+        app.focused_panel = Panel::Logs;
+
+        app.navigate_up();
+        app.logs.push(LogLine {
+            level: Level::WARN,
+            target: "test".to_string(),
+            message: "late".to_string(),
+        });
+        app.sync_logs();
+
+        assert!(!app.logs_follow);
+        assert_eq!(app.logs_selected, 48);
+    }
+
+    #[test]
+    fn test_jumping_to_bottom_resumes_follow() {
+        let mut app = create_app(50);
+        app.focused_panel = Panel::Logs;
+        app.logs_follow = false;
+
+        app.jump_bottom();
+
+        assert!(app.logs_follow);
+    }
+
+    #[test]
+    fn test_hiding_focused_pane_moves_focus() {
+        let mut app = create_app(0);
+        app.focused_panel = Panel::Logs;
+
+        app.toggle_logs();
+
+        assert!(!app.logs_visible);
+        assert_eq!(app.focused_panel, Panel::Files);
+    }
+
+    #[test]
+    fn test_toggles_debug_logging() {
+        use crate::logs::Verbosity;
+        use std::sync::{Arc, Mutex};
+
+        let mut app = create_app(0);
+        let applied = Arc::new(Mutex::new(Vec::new()));
+        app.verbosity = Some(Verbosity::new({
+            let applied = Arc::clone(&applied);
+            move |debug| {
+                applied.lock().unwrap().push(debug);
+                Ok(())
+            }
+        }));
+
+        app.toggle_debug_logging();
+        app.toggle_debug_logging();
+
+        assert_eq!(*applied.lock().unwrap(), [true, false]);
+        assert_eq!(
+            app.notifications.toasts().last().unwrap().message,
+            "Debug logging off"
+        );
+    }
+}
@@ -0,0 +1,158 @@
+//! Marked entries of the files panel, which bulk operations apply to
+//! instead of the entry under the cursor.
+//!
+//! Marks are kept by name and belong to the directory on screen:
+//! opening another directory clears them, and listing the same one
+//! again drops the marks of entries that are gone.
+
+use super::state::App;
+use crate::rclone::FileItem;
+use crate::ui::widgets::mark_glob::glob_match;
+use tracing::debug;
+
+impl App {
+    /// Marks the selected entry, or unmarks it, and moves the cursor
+    /// to the next entry so a run of entries can be marked by holding
+    /// the key.
+    pub fn toggle_mark(&mut self) {
+        let Some(item) = self.files.get(self.files_selected) else {
+            return;
+        };
+        let name = item.name().to_string();
+        if !self.marked.remove(&name) {
+            self.marked.insert(name);
+        }
+        debug!(marked = self.marked.len(), "toggled mark");
+        self.navigate_down();
+        self.keep_selection_visible();
+    }
+
+    /// Marks every entry, or clears the marks if every entry already
+    /// is marked.
+    pub fn mark_all(&mut self) {
+        if self
+            .files
+            .iter()
+            .all(|item| self.marked.contains(item.name()))
+        {
+            self.marked.clear();
+        } else {
+            self.marked = self
+                .files
+                .iter()
+                .map(|item| item.name().to_string())
+                .collect();
+        }
+        debug!(marked = self.marked.len(), "marked all");
+    }
+
+    /// Marks the unmarked entries and unmarks the marked ones.
+    pub fn invert_marks(&mut self) {
+        self.marked = self
+            .files
+            .iter()
+            .map(|item| item.name().to_string())
+            .filter(|name| !self.marked.contains(name))
+            .collect();
+        debug!(marked = self.marked.len(), "inverted marks");
+    }
+
+    /// Adds the entries whose names match the glob `pattern` to the
+    /// marks, returning how many matched.
+    pub fn mark_matching(&mut self, pattern: &str) -> usize {
+        let matching: Vec<String> = self
+            .files
+            .iter()
+            .map(|item| item.name().to_string())
+            .filter(|name| glob_match(pattern, name))
+            .collect();
+        let count = matching.len();
+        self.marked.extend(matching);
+        debug!(pattern, count, "marked matching");
+        count
+    }
+
+    /// Returns the entries a bulk operation applies to: the marked
+    /// ones in listing order, or else the selected one.
+    pub fn selection(&self) -> Vec<&FileItem> {
+        if self.marked.is_empty() {
+            self.files.get(self.files_selected).into_iter().collect()
+        } else {
+            self.files
+                .iter()
+                .filter(|item| self.marked.contains(item.name()))
+                .collect()
+        }
+    }
+
+    /// Returns the paths within the current remote of
+    /// [`Self::selection`].
+    pub fn selected_paths(&self) -> Vec<String> {
+        self.selection()
+            .into_iter()
+            .map(|item| self.child_path(item.name()))
+            .collect()
+    }
+
+    /// Keeps the marks when a listing of the directory they were made
+    /// in arrives, minus those of entries that are gone, and clears
+    /// them for any other directory.
+    pub(super) fn retain_marks(&mut self, same_dir: bool) {
+        if same_dir {
+            let files = &self.files;
+            self.marked
+                .retain(|name| files.iter().any(|item| item.name() == name));
+        } else {
+            self.marked.clear();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::app::handler::test_support::files_app;
+
+    #[test]
+    fn test_selection_falls_back_to_cursor() {
+        let mut app = files_app("docs", &["a", "b", "c"]);
+        app.files_selected = 1;
+        assert_eq!(app.selected_paths(), ["docs/b"]);
+
+        app.files_selected = 0;
+        app.toggle_mark();
+        app.toggle_mark();
+        app.toggle_mark();
+        assert_eq!(app.files_selected, 2);
+        assert_eq!(app.selected_paths(), ["docs/a", "docs/b", "docs/c"]);
+
+        app.files_selected = 1;
+        app.toggle_mark();
+        assert_eq!(app.selected_paths(), ["docs/a", "docs/c"]);
+    }
+
+    #[test]
+    fn test_mark_all_invert_and_glob() {
+        let mut app = files_app("docs", &["a.jpg", "b.png", "c.jpg"]);
+        app.mark_all();
+        assert_eq!(app.marked.len(), 3);
+        app.mark_all();
+        assert!(app.marked.is_empty());
+
+        assert_eq!(app.mark_matching("*.jpg"), 2);
+        assert_eq!(app.selected_paths(), ["docs/a.jpg", "docs/c.jpg"]);
+        app.invert_marks();
+        assert_eq!(app.selected_paths(), ["docs/b.png"]);
+    }
+
+    #[test]
+    fn test_marks_follow_the_directory() {
+        let mut app = files_app("docs", &["a", "b"]);
+        app.marked.extend(["a".to_string(), "b".to_string()]);
+        app.files.remove(0);
+        app.retain_marks(true);
+        assert_eq!(app.marked.len(), 1);
+
+        app.retain_marks(false);
+        assert!(app.marked.is_empty());
+    }
+}
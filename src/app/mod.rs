@@ -1,7 +1,52 @@
 //! Application state and event handling.
 
+pub mod action;
+pub mod activity;
+mod audit;
+mod columns;
+pub mod command;
+mod desktop;
+pub mod edit;
+pub mod event;
+pub mod flat;
+pub mod fuzzy;
 pub mod handler;
+pub mod health;
+mod history;
+mod inspect;
+mod jobs;
+mod key_press;
+pub mod keymap;
+pub mod listing_cache;
+pub mod loading;
+mod log_pane;
+mod marks;
+mod modal;
+mod motion;
+mod navigation;
+pub mod offline;
+mod operations;
+pub mod profile;
+mod recent;
+mod redraw;
+mod remote_order;
+mod remotes;
+pub mod script;
+mod session;
 pub mod state;
+pub mod tree;
+pub mod worker;
+pub mod yank;
 
+pub use action::{Action, KeyContext};
+pub use edit::EditSession;
+pub use event::{AppEvent, Listing};
 pub use handler::Handler;
-pub use state::{ActiveModal, App, Panel};
+pub use health::Health;
+pub use keymap::{KeyMatch, KeyPress, Keymap};
+pub use listing_cache::{ListingCache, Prefetch};
+pub use loading::Loading;
+pub use offline::Queued;
+pub use state::{ActiveModal, App, Panel, Retry};
+pub use tree::Tree;
+pub use yank::Yank;
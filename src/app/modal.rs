@@ -0,0 +1,160 @@
+//! The modal open over the panels, and how to retry a failed
+//! operation from the error modal.
+
+use super::state::App;
+use crate::ui::{
+    AuditView, BatchRename, CommandPalette, CompareView, ConfigPasswordModal, ConfigWizard,
+    ConfirmModal, ConflictModal, CopyRemoteModal, CopyUrlPrompt, CreateRemoteModal, DaemonSetup,
+    ErrorModal, FileOperationsModal, FilterForm, GlobPrompt, GoToPrompt, HistoryView, JobsView,
+    LargestView, MetadataView, MountsView, Preview, QuitModal, RecentPicker, RemoteInfo,
+    TierPrompt, UsageView, VersionsView,
+};
+use crossterm::event::Event;
+use std::path::PathBuf;
+
+/// The currently open modal, if any.
+///
+/// A single slot makes overlapping modals unrepresentable: opening a
+/// modal replaces whatever was open before.
+#[derive(Debug, Clone)]
+pub enum ActiveModal {
+    /// Create/edit remote form.
+    CreateRemote(Box<CreateRemoteModal>),
+    /// Questions rclone asks while creating a remote.
+    ConfigWizard(Box<ConfigWizard>),
+    /// Password prompt for the daemon's encrypted config.
+    ConfigPassword(ConfigPasswordModal),
+    /// Rename/duplicate remote prompt.
+    CopyRemote(CopyRemoteModal),
+    /// Delete confirmation for the named remote.
+    ConfirmDeleteRemote {
+        /// Remote pending deletion, applied on confirm.
+        remote: String,
+        /// Yes/no confirmation state.
+        modal: ConfirmModal,
+    },
+    /// File operation (delete, mkdir, copy, move) form.
+    FileOperation(FileOperationsModal),
+    /// Find/replace form renaming the selection.
+    BatchRename(Box<BatchRename>),
+    /// Size and age filter form.
+    Filter(FilterForm),
+    /// Prompt for a `remote:path` to jump to.
+    GoTo(GoToPrompt),
+    /// Fuzzy-searchable list of recently visited locations.
+    Recent(RecentPicker),
+    /// Glob prompt marking the entries that match.
+    MarkGlob(GlobPrompt),
+    /// Prompt for the storage tier to move the selection to.
+    Tier(TierPrompt),
+    /// Backend metadata of the selected file, with a form to set keys.
+    Metadata(Box<MetadataView>),
+    /// Prompt for a URL to download into the current directory.
+    CopyUrl(CopyUrlPrompt),
+    /// Prior versions of the selected file.
+    Versions(Box<VersionsView>),
+    /// Skip/overwrite/rename choice for a copy or move whose
+    /// destination exists.
+    Conflict(Box<ConflictModal>),
+    /// Full-screen help listing the active key bindings.
+    Help {
+        /// Index of the first visible line.
+        scroll: usize,
+    },
+    /// Fuzzy-searchable list of every action.
+    CommandPalette(CommandPalette),
+    /// Content of the selected file.
+    Preview(Preview),
+    /// Settings and features of the selected remote.
+    RemoteInfo(RemoteInfo),
+    /// Active mounts and the form for a new one.
+    Mounts(Box<MountsView>),
+    /// The daemon's jobs.
+    Jobs(Box<JobsView>),
+    /// Copies and moves LazyFile ran.
+    History(Box<HistoryView>),
+    /// Changes LazyFile made, and who made them.
+    Audit(Box<AuditView>),
+    /// Sizes below the current directory.
+    Usage(Box<UsageView>),
+    /// Two directories side by side.
+    Compare(Box<CompareView>),
+    /// Largest files below the current directory.
+    Largest(Box<LargestView>),
+    /// No daemon answered at startup; offers to start one, or says
+    /// how to install rclone.
+    DaemonSetup(Box<DaemonSetup>),
+    /// Jobs still running when quitting; offers to wait for them,
+    /// leave them running, or stop them.
+    Quit(Box<QuitModal>),
+    /// A request failed; offers to retry it or carry on.
+    Error {
+        /// Error details and the highlighted button.
+        modal: ErrorModal,
+        /// How to run the failed operation again.
+        retry: Box<Retry>,
+    },
+}
+
+/// How to run a failed operation again.
+#[derive(Debug, Clone)]
+pub enum Retry {
+    /// Replay the input that led to the failed request.
+    Event {
+        /// Key or mouse event to handle again.
+        event: Event,
+        /// Modal that was open when the event first arrived, reopened
+        /// before replaying it.
+        modal: Option<ActiveModal>,
+    },
+    /// Request the directory listing again.
+    ListFiles {
+        /// Remote to list.
+        remote: String,
+        /// Path within the remote.
+        path: String,
+    },
+    /// Upload an edited file again.
+    Upload {
+        /// Remote to write to.
+        remote: String,
+        /// Path within the remote.
+        path: String,
+        /// Local copy holding the edits.
+        local: PathBuf,
+    },
+}
+
+impl App {
+    /// Returns the create/edit remote modal if it is the open modal.
+    pub fn create_remote_modal(&self) -> Option<&CreateRemoteModal> {
+        match self.modal {
+            Some(ActiveModal::CreateRemote(ref modal)) => Some(modal),
+            _ => None,
+        }
+    }
+
+    /// Returns the delete confirmation modal if it is the open modal.
+    pub fn confirm_modal(&self) -> Option<&ConfirmModal> {
+        match self.modal {
+            Some(ActiveModal::ConfirmDeleteRemote { ref modal, .. }) => Some(modal),
+            _ => None,
+        }
+    }
+
+    /// Returns the file operations modal if it is the open modal.
+    pub fn file_operations_modal(&self) -> Option<&FileOperationsModal> {
+        match self.modal {
+            Some(ActiveModal::FileOperation(ref modal)) => Some(modal),
+            _ => None,
+        }
+    }
+
+    /// Returns the remote pending deletion while confirmation is open.
+    pub fn pending_delete_remote(&self) -> Option<&str> {
+        match self.modal {
+            Some(ActiveModal::ConfirmDeleteRemote { ref remote, .. }) => Some(remote),
+            _ => None,
+        }
+    }
+}
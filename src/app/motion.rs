@@ -0,0 +1,286 @@
+//! Vim-style cursor motions over the focused list.
+
+use super::state::{App, Panel};
+use crate::ui::Layout;
+use crate::ui::layout::LayoutRects;
+use ratatui::layout::Rect;
+use tracing::debug;
+
+impl App {
+    /// Records the terminal area and sizes the list viewports to
+    /// match. The launcher calls this before each frame.
+    pub fn set_screen(&mut self, area: Rect) {
+        self.screen = area;
+        let rects = self.layout(area);
+        // Panels have a one-row border above and below.
+        let logs_height = rects.logs.map_or(0, |logs| logs.height.saturating_sub(2));
+        self.logs_viewport_height = logs_height.into();
+        scroll_into_view(
+            self.logs_selected,
+            &mut self.logs_offset,
+            self.logs_viewport_height.max(1),
+        );
+        self.set_viewport_height(rects.files.height.saturating_sub(2).into());
+    }
+
+    /// Divides `area` into the panels as currently arranged; zoomed,
+    /// the focused panel takes the room of all of them.
+    pub fn layout(&self, area: Rect) -> LayoutRects {
+        let mut rects = Layout::split(
+            area,
+            self.logs_visible,
+            self.remote_layout.width(),
+            self.sidebar,
+        );
+        if self.zoomed {
+            rects.zoom(self.focused_panel);
+        }
+        rects
+    }
+
+    /// Updates the number of visible list rows and scrolls both
+    /// lists so their selections stay on screen after a resize.
+    pub fn set_viewport_height(&mut self, rows: usize) {
+        self.viewport_height = rows;
+        let rows = self.visible_rows();
+        scroll_into_view(self.remotes_selected, &mut self.remotes_offset, rows);
+        scroll_into_view(self.files_selected, &mut self.files_offset, rows);
+    }
+
+    /// Jumps to the first item in the focused list (`gg`).
+    pub fn jump_top(&mut self) {
+        self.select(0);
+    }
+
+    /// Jumps to the last item in the focused list (`G`).
+    pub fn jump_bottom(&mut self) {
+        let (_, _, len) = self.focused_cursor();
+        self.select(len.saturating_sub(1));
+    }
+
+    /// Moves the selection down by half a screen (`Ctrl-d`).
+    pub fn half_page_down(&mut self) {
+        let (selected, _, _) = self.focused_cursor();
+        self.select(selected.saturating_add(self.half_page()));
+    }
+
+    /// Moves the selection up by half a screen (`Ctrl-u`).
+    pub fn half_page_up(&mut self) {
+        let (selected, _, _) = self.focused_cursor();
+        self.select(selected.saturating_sub(self.half_page()));
+    }
+
+    /// Selects the first visible row (`H`).
+    pub fn screen_top(&mut self) {
+        let (_, offset, _) = self.focused_cursor();
+        self.select(offset);
+    }
+
+    /// Selects the middle visible row (`M`).
+    pub fn screen_middle(&mut self) {
+        let (_, offset, _) = self.focused_cursor();
+        let shown = self.shown_rows();
+        self.select(offset + shown.saturating_sub(1) / 2);
+    }
+
+    /// Selects the last visible row (`L`).
+    pub fn screen_bottom(&mut self) {
+        let (_, offset, _) = self.focused_cursor();
+        let shown = self.shown_rows();
+        self.select(offset + shown.saturating_sub(1));
+    }
+
+    /// Scrolls the focused list so its selection is on screen.
+    pub(crate) fn keep_selection_visible(&mut self) {
+        let rows = self.visible_rows();
+        match self.focused_panel {
+            Panel::Remotes => {
+                scroll_into_view(self.remotes_selected, &mut self.remotes_offset, rows);
+            }
+            Panel::Files => {
+                scroll_into_view(self.files_selected, &mut self.files_offset, rows);
+            }
+            Panel::Logs => {
+                scroll_into_view(self.logs_selected, &mut self.logs_offset, rows);
+            }
+        }
+    }
+
+    /// Returns `(selected, offset, len)` for the focused list.
+    fn focused_cursor(&self) -> (usize, usize, usize) {
+        match self.focused_panel {
+            Panel::Remotes => (
+                self.remotes_selected,
+                self.remotes_offset,
+                self.remotes.len(),
+            ),
+            Panel::Files => (self.files_selected, self.files_offset, self.files.len()),
+            Panel::Logs => (self.logs_selected, self.logs_offset, self.logs.len()),
+        }
+    }
+
+    /// Selects `index` in the focused list, clamped to its bounds.
+    pub(crate) fn select(&mut self, index: usize) {
+        let (_, _, len) = self.focused_cursor();
+        if len == 0 {
+            return;
+        }
+        let index = index.min(len - 1);
+        match self.focused_panel {
+            Panel::Remotes => self.remotes_selected = index,
+            Panel::Files => self.files_selected = index,
+            Panel::Logs => {
+                // Moving to the newest line resumes following; any
+                // other motion pauses it.
+                self.logs_selected = index;
+                self.logs_follow = index == len - 1;
+            }
+        }
+        // Logging motions inside the log pane would scroll it.
+        if self.focused_panel != Panel::Logs {
+            debug!(panel = ?self.focused_panel, index, "motion");
+        }
+        self.keep_selection_visible();
+    }
+
+    /// Before the first frame the viewport height is unknown; one
+    /// row keeps the motions well-defined.
+    fn visible_rows(&self) -> usize {
+        match self.focused_panel {
+            Panel::Logs => self.logs_viewport_height.max(1),
+            Panel::Remotes | Panel::Files => self.viewport_height.max(1),
+        }
+    }
+
+    fn half_page(&self) -> usize {
+        (self.visible_rows() / 2).max(1)
+    }
+
+    /// Rows of the focused list actually occupied by items, which is
+    /// less than the viewport when the list ends on screen.
+    fn shown_rows(&self) -> usize {
+        let (_, offset, len) = self.focused_cursor();
+        self.visible_rows().min(len.saturating_sub(offset))
+    }
+}
+
+fn scroll_into_view(selected: usize, offset: &mut usize, rows: usize) {
+    if selected < *offset {
+        *offset = selected;
+    } else if selected >= *offset + rows {
+        *offset = selected + 1 - rows;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::app::handler::test_support::unreachable_app;
+    use crate::app::state::{App, Panel};
+
+    fn create_app(remote_count: usize, rows: usize) -> App {
+        let mut app = unreachable_app();
+        app.remotes = (0..remote_count).map(|i| format!("remote_{}", i)).collect();
+        app.focused_panel = Panel::Remotes;
+        app.set_viewport_height(rows);
+        app
+    }
+
+    #[test]
+    fn test_jump_bottom_and_top() {
+        let mut app = create_app(50, 10);
+
+        app.jump_bottom();
+        assert_eq!(app.remotes_selected, 49);
+        assert_eq!(app.remotes_offset, 40, "bottom item must be on screen");
+
+        app.jump_top();
+        assert_eq!(app.remotes_selected, 0);
+        assert_eq!(app.remotes_offset, 0);
+    }
+
+    #[test]
+    fn test_half_page_moves_by_half_viewport() {
+        let mut app = create_app(50, 10);
+
+        app.half_page_down();
+        assert_eq!(app.remotes_selected, 5);
+        app.half_page_down();
+        assert_eq!(app.remotes_selected, 10);
+        assert_eq!(app.remotes_offset, 1);
+
+        app.half_page_up();
+        assert_eq!(app.remotes_selected, 5);
+        assert_eq!(app.remotes_offset, 1, "still visible, no scroll");
+    }
+
+    #[test]
+    fn test_half_page_clamps_to_list_bounds() {
+        let mut app = create_app(3, 10);
+
+        app.half_page_down();
+        assert_eq!(app.remotes_selected, 3 - 1);
+
+        app.half_page_up();
+        app.half_page_up();
+        assert_eq!(app.remotes_selected, 0);
+    }
+
+    #[test]
+    fn test_screen_relative_motions_use_offset() {
+        let mut app = create_app(50, 10);
+        app.remotes_selected = 25;
+        app.remotes_offset = 20;
+
+        app.screen_top();
+        assert_eq!(app.remotes_selected, 20);
+
+        app.screen_middle();
+        assert_eq!(app.remotes_selected, 24);
+
+        app.screen_bottom();
+        assert_eq!(app.remotes_selected, 29);
+        assert_eq!(app.remotes_offset, 20, "screen motions never scroll");
+    }
+
+    #[test]
+    fn test_screen_motions_on_short_list() {
+        let mut app = create_app(4, 10);
+
+        app.screen_bottom();
+        assert_eq!(app.remotes_selected, 3);
+
+        app.screen_middle();
+        assert_eq!(app.remotes_selected, 1);
+    }
+
+    #[test]
+    fn test_motions_on_empty_list_are_noops() {
+        let mut app = create_app(0, 10);
+
+        app.jump_bottom();
+        app.half_page_down();
+        app.screen_bottom();
+        assert_eq!(app.remotes_selected, 0);
+        assert_eq!(app.remotes_offset, 0);
+    }
+
+    #[test]
+    fn test_navigate_down_scrolls_past_viewport() {
+        let mut app = create_app(20, 5);
+
+        for _ in 0..7 {
+            app.navigate_down();
+        }
+        assert_eq!(app.remotes_selected, 7);
+        assert_eq!(app.remotes_offset, 3);
+    }
+
+    #[test]
+    fn test_shrinking_viewport_keeps_selection_visible() {
+        let mut app = create_app(50, 20);
+        app.remotes_selected = 15;
+
+        app.set_viewport_height(5);
+        assert_eq!(app.remotes_offset, 11);
+    }
+}
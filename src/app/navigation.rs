@@ -0,0 +1,363 @@
+//! Moving the selection in the focused list and focus between the
+//! panels.
+
+use super::state::{App, Panel};
+use crate::ui::Sidebar;
+use tracing::{debug, info};
+
+impl App {
+    /// Move selection down in focused panel.
+    pub fn navigate_down(&mut self) {
+        match self.focused_panel {
+            Panel::Remotes => {
+                if self.remotes_selected < self.remotes.len().saturating_sub(1) {
+                    self.remotes_selected += 1;
+                    debug!("Navigate down in remotes: {}", self.remotes_selected);
+                }
+            }
+            Panel::Files => {
+                if self.files_selected < self.files.len().saturating_sub(1) {
+                    self.files_selected += 1;
+                    debug!("Navigate down in files: {}", self.files_selected);
+                }
+            }
+            Panel::Logs => self.select(self.logs_selected.saturating_add(1)),
+        }
+        self.keep_selection_visible();
+    }
+
+    /// Move selection up in focused panel.
+    pub fn navigate_up(&mut self) {
+        match self.focused_panel {
+            Panel::Remotes => {
+                if self.remotes_selected > 0 {
+                    self.remotes_selected -= 1;
+                    debug!("Navigate up in remotes: {}", self.remotes_selected);
+                }
+            }
+            Panel::Files => {
+                if self.files_selected > 0 {
+                    self.files_selected -= 1;
+                    debug!("Navigate up in files: {}", self.files_selected);
+                }
+            }
+            Panel::Logs => self.select(self.logs_selected.saturating_sub(1)),
+        }
+        self.keep_selection_visible();
+    }
+
+    /// Lets the focused panel fill the screen, or restores the layout.
+    pub fn toggle_zoom(&mut self) {
+        self.zoomed = !self.zoomed;
+        info!(zoomed = self.zoomed, panel = ?self.focused_panel, "toggled zoom");
+        self.set_screen(self.screen);
+    }
+
+    /// Cycle focus through the remotes (unless hidden), files and
+    /// (when shown) log panels.
+    pub fn switch_panel(&mut self) {
+        self.focused_panel = match self.focused_panel {
+            Panel::Remotes => {
+                debug!("Switching focus to Files");
+                Panel::Files
+            }
+            Panel::Files if self.logs_visible => {
+                debug!("Switching focus to Logs");
+                Panel::Logs
+            }
+            Panel::Files | Panel::Logs if self.sidebar == Sidebar::Hidden => {
+                debug!("Remotes hidden, switching focus to Files");
+                Panel::Files
+            }
+            Panel::Files | Panel::Logs => {
+                debug!("Switching focus to Remotes");
+                Panel::Remotes
+            }
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rclone::{FileItem, RcloneClient};
+
+    fn create_test_client() -> RcloneClient {
+        RcloneClient::new("localhost", 5572).expect("default reqwest client config is valid")
+    }
+
+    #[test]
+    fn test_navigate_down_remotes() {
+        let client = create_test_client();
+        let mut app = App::new(client);
+        app.remotes = vec!["remote1".to_string(), "remote2".to_string()];
+        app.focused_panel = Panel::Remotes;
+
+        app.navigate_down();
+        assert_eq!(app.remotes_selected, 1);
+
+        app.navigate_down();
+        assert_eq!(app.remotes_selected, 1); // stays at max
+    }
+
+    #[test]
+    fn test_navigate_down_files() {
+        let client = create_test_client();
+        let mut app = App::new(client);
+        app.files = vec![
+            FileItem {
+                name: "file1".to_string(),
+                size: 100,
+                mod_time: "".to_string(),
+                is_dir: false,
+                ..Default::default()
+            },
+            FileItem {
+                name: "file2".to_string(),
+                size: 200,
+                mod_time: "".to_string(),
+                is_dir: false,
+                ..Default::default()
+            },
+        ];
+        app.focused_panel = Panel::Files;
+
+        app.navigate_down();
+        assert_eq!(app.files_selected, 1);
+    }
+
+    #[test]
+    fn test_navigate_up_remotes() {
+        let client = create_test_client();
+        let mut app = App::new(client);
+        app.remotes = vec!["remote1".to_string(), "remote2".to_string()];
+        app.remotes_selected = 1;
+        app.focused_panel = Panel::Remotes;
+
+        app.navigate_up();
+        assert_eq!(app.remotes_selected, 0);
+
+        app.navigate_up();
+        assert_eq!(app.remotes_selected, 0); // stays at min
+    }
+
+    #[test]
+    fn test_navigate_up_files() {
+        let client = create_test_client();
+        let mut app = App::new(client);
+        app.files = vec![FileItem {
+            name: "file1".to_string(),
+            size: 100,
+            mod_time: "".to_string(),
+            is_dir: false,
+            ..Default::default()
+        }];
+        app.files_selected = 1;
+        app.focused_panel = Panel::Files;
+
+        app.navigate_up();
+        assert_eq!(app.files_selected, 0);
+    }
+
+    #[test]
+    fn test_switch_panel_to_files() {
+        let client = create_test_client();
+        let mut app = App::new(client);
+        assert_eq!(app.focused_panel, Panel::Remotes);
+
+        app.switch_panel();
+        assert_eq!(app.focused_panel, Panel::Files);
+    }
+
+    #[test]
+    fn test_switch_panel_to_remotes() {
+        let client = create_test_client();
+        let mut app = App::new(client);
+        app.focused_panel = Panel::Files;
+
+        app.switch_panel();
+        assert_eq!(app.focused_panel, Panel::Remotes);
+    }
+
+    #[test]
+    fn test_switch_panel_multiple_times() {
+        let client = create_test_client();
+        let mut app = App::new(client);
+
+        for _ in 0..4 {
+            assert_eq!(app.focused_panel, Panel::Remotes);
+            app.switch_panel();
+            assert_eq!(app.focused_panel, Panel::Files);
+            app.switch_panel();
+        }
+    }
+
+    #[test]
+    fn test_navigate_down_empty_remotes() {
+        let client = create_test_client();
+        let mut app = App::new(client);
+        app.remotes = vec![];
+        app.focused_panel = Panel::Remotes;
+
+        app.navigate_down();
+        assert_eq!(app.remotes_selected, 0);
+    }
+
+    #[test]
+    fn test_navigate_up_empty_remotes() {
+        let client = create_test_client();
+        let mut app = App::new(client);
+        app.remotes = vec![];
+        app.focused_panel = Panel::Remotes;
+
+        app.navigate_up();
+        assert_eq!(app.remotes_selected, 0);
+    }
+
+    #[test]
+    fn test_navigate_down_empty_files() {
+        let client = create_test_client();
+        let mut app = App::new(client);
+        app.files = vec![];
+        app.focused_panel = Panel::Files;
+
+        app.navigate_down();
+        assert_eq!(app.files_selected, 0);
+    }
+
+    #[test]
+    fn test_navigate_up_empty_files() {
+        let client = create_test_client();
+        let mut app = App::new(client);
+        app.files = vec![];
+        app.focused_panel = Panel::Files;
+
+        app.navigate_up();
+        assert_eq!(app.files_selected, 0);
+    }
+
+    #[test]
+    fn test_navigate_down_single_item() {
+        let client = create_test_client();
+        let mut app = App::new(client);
+        app.remotes = vec!["single".to_string()];
+        app.focused_panel = Panel::Remotes;
+
+        app.navigate_down();
+        assert_eq!(app.remotes_selected, 0);
+    }
+
+    #[test]
+    fn test_navigate_up_single_item() {
+        let client = create_test_client();
+        let mut app = App::new(client);
+        app.remotes = vec!["single".to_string()];
+        app.focused_panel = Panel::Remotes;
+
+        app.navigate_up();
+        assert_eq!(app.remotes_selected, 0);
+    }
+
+    #[test]
+    fn test_navigate_down_boundary() {
+        let client = create_test_client();
+        let mut app = App::new(client);
+        app.remotes = vec!["r1".to_string(), "r2".to_string(), "r3".to_string()];
+        app.remotes_selected = 2;
+        app.focused_panel = Panel::Remotes;
+
+        app.navigate_down();
+        assert_eq!(app.remotes_selected, 2);
+    }
+
+    #[test]
+    fn test_navigate_up_boundary() {
+        let client = create_test_client();
+        let mut app = App::new(client);
+        app.remotes = vec!["r1".to_string(), "r2".to_string(), "r3".to_string()];
+        app.remotes_selected = 0;
+        app.focused_panel = Panel::Remotes;
+
+        app.navigate_up();
+        assert_eq!(app.remotes_selected, 0);
+    }
+
+    #[test]
+    fn test_navigate_many_items() {
+        let client = create_test_client();
+        let mut app = App::new(client);
+        app.remotes = (0..100).map(|i| format!("remote_{}", i)).collect();
+        app.focused_panel = Panel::Remotes;
+
+        for _ in 0..50 {
+            app.navigate_down();
+        }
+        assert_eq!(app.remotes_selected, 50);
+
+        for _ in 0..25 {
+            app.navigate_up();
+        }
+        assert_eq!(app.remotes_selected, 25);
+    }
+
+    #[test]
+    fn test_navigate_files_many_items() {
+        let client = create_test_client();
+        let mut app = App::new(client);
+        app.files = (0..100)
+            .map(|i| FileItem {
+                name: format!("file_{}.txt", i),
+                size: i * 100,
+                mod_time: "".to_string(),
+                is_dir: false,
+                ..Default::default()
+            })
+            .collect();
+        app.focused_panel = Panel::Files;
+
+        for _ in 0..150 {
+            app.navigate_down();
+        }
+        assert_eq!(app.files_selected, 99);
+
+        for _ in 0..150 {
+            app.navigate_up();
+        }
+        assert_eq!(app.files_selected, 0);
+    }
+
+    #[test]
+    fn test_selection_persists_after_switch() {
+        let client = create_test_client();
+        let mut app = App::new(client);
+        app.remotes = vec!["r1".to_string(), "r2".to_string(), "r3".to_string()];
+        app.files = vec![
+            FileItem {
+                name: "f1".to_string(),
+                size: 0,
+                mod_time: "".to_string(),
+                is_dir: false,
+                ..Default::default()
+            },
+            FileItem {
+                name: "f2".to_string(),
+                size: 0,
+                mod_time: "".to_string(),
+                is_dir: false,
+                ..Default::default()
+            },
+        ];
+
+        app.focused_panel = Panel::Remotes;
+        app.navigate_down();
+        app.navigate_down();
+        assert_eq!(app.remotes_selected, 2);
+
+        app.switch_panel();
+        app.navigate_down();
+        assert_eq!(app.files_selected, 1);
+
+        app.switch_panel();
+        assert_eq!(app.remotes_selected, 2);
+    }
+}
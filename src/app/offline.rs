@@ -0,0 +1,133 @@
+//! Browsing while the daemon is unreachable: directories listed
+//! before are shown from the listing cache, however old, and changes
+//! to files wait in a queue until the daemon answers again.
+
+use super::state::App;
+use std::fmt;
+use tracing::info;
+
+/// A change to files held back until the daemon is reachable.
+pub struct Queued {
+    /// What it does, for the user.
+    pub description: String,
+    run: Box<dyn FnOnce(&mut App) + Send>,
+}
+
+impl fmt::Debug for Queued {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Queued")
+            .field("description", &self.description)
+            .finish_non_exhaustive()
+    }
+}
+
+impl App {
+    /// Returns whether the daemon stopped answering pings.
+    pub fn offline(&self) -> bool {
+        self.health.failures > 0
+    }
+
+    /// Holds back `request`, described by `description`, until the
+    /// daemon answers again.
+    pub(super) fn queue(
+        &mut self,
+        description: String,
+        request: impl FnOnce(&mut App) + Send + 'static,
+    ) {
+        info!(request = %description, "daemon unreachable, queueing");
+        self.notifications.warning(format!(
+            "Offline: {} will run once the daemon is back",
+            description
+        ));
+        self.queued.push(Queued {
+            description,
+            run: Box::new(request),
+        });
+    }
+
+    /// Drops every queued change, saying how many, for when they no
+    /// longer go to the daemon they were meant for.
+    pub(super) fn discard_queued(&mut self) {
+        if self.queued.is_empty() {
+            return;
+        }
+        info!(count = self.queued.len(), "discarding queued changes");
+        self.notifications.warning(format!(
+            "Discarded {} queued change(s) meant for the previous daemon",
+            self.queued.len()
+        ));
+        self.queued.clear();
+    }
+
+    /// Runs every queued change, oldest first.
+    pub(super) fn run_queued(&mut self) {
+        if self.queued.is_empty() {
+            return;
+        }
+        info!(count = self.queued.len(), "running queued changes");
+        self.notifications
+            .info(format!("Running {} queued change(s)", self.queued.len()));
+        for queued in std::mem::take(&mut self.queued) {
+            (queued.run)(self);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::app::event::AppEvent;
+    use crate::app::handler::test_support::unreachable_app;
+    use crate::rclone::FileItem;
+    use std::time::Instant;
+
+    #[tokio::test]
+    async fn test_browses_cache_and_queues_changes_while_offline() {
+        let mut app = unreachable_app();
+        let long_ago = Instant::now()
+            .checked_sub(crate::app::listing_cache::CACHE_TTL * 2)
+            .expect("clock started before the cache TTL");
+        let listing = vec![FileItem {
+            name: "a.txt".to_string(),
+            ..FileItem::default()
+        }];
+        app.listing_cache
+            .insert("s3".to_string(), "docs".to_string(), listing, long_ago);
+        app.apply_ping(
+            Err(crate::error::LazyFileError::RcloneApi {
+                endpoint: "core/version",
+                message: "connection refused".to_string(),
+            }),
+            Instant::now(),
+        );
+        assert!(app.offline());
+
+        app.request_files("s3".to_string(), "docs".to_string());
+        let Some(AppEvent::FilesLoaded(listing)) = app.wait_event().await else {
+            panic!("no listing");
+        };
+        assert!(listing.cached);
+        app.apply_listing(listing).unwrap();
+        assert_eq!(app.files[0].name, "a.txt");
+
+        app.request_batch_rename(
+            "s3".to_string(),
+            "docs".to_string(),
+            vec![("a.txt".to_string(), "b.txt".to_string(), false)],
+        );
+        assert_eq!(app.queued.len(), 1);
+        assert_eq!(app.workers.busy(), 0);
+
+        app.apply_ping(Ok("v1.68.0".to_string()), Instant::now());
+        assert!(!app.offline());
+        assert!(app.queued.is_empty());
+        let mut renamed = false;
+        while let Some(event) = app.wait_event().await {
+            if matches!(event, AppEvent::BatchRenamed { .. }) {
+                renamed = true;
+                break;
+            }
+        }
+        assert!(renamed);
+    }
+}
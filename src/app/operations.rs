@@ -0,0 +1,407 @@
+//! Background requests that change files: copies, moves, deletes,
+//! uploads, renames and storage tiers.
+
+use super::command::rclone_path;
+use super::event::AppEvent;
+use super::history::Timed;
+use super::state::App;
+use super::tree::leaf_name;
+use super::yank::{Yank, join_path};
+use crate::config::{ConflictPolicy, TransferKind};
+use crate::error::{LazyFileError, Result};
+use crate::rclone::{FileItem, Filter, RcloneClient};
+use crate::ui::widgets::conflict::numbered;
+use crate::ui::{FileOperationType, FileOperationsModal};
+use std::time::Instant;
+use tracing::{debug, info};
+
+impl App {
+    /// Has the daemon download `url` into the file at `path` on
+    /// `remote` on a background task. The outcome arrives as
+    /// [`AppEvent::UrlCopied`].
+    pub fn request_copy_url(&mut self, remote: String, path: String, url: String) {
+        if self.offline() {
+            self.queue(format!("downloading {}", url), move |app| {
+                app.request_copy_url(remote, path, url);
+            });
+            return;
+        }
+        info!(remote = %remote, path = %path, url = %url, "copying URL");
+        let client = self.client.clone();
+        self.workers.spawn_transfer(async move {
+            let result = client.copy_url(&remote, &path, &url).await;
+            AppEvent::UrlCopied {
+                remote,
+                path,
+                url,
+                result,
+            }
+        });
+    }
+
+    /// Runs the operation `modal` describes on `remote` on a
+    /// background task. The outcome arrives as
+    /// [`AppEvent::FileOperationFinished`].
+    ///
+    /// A copy or move first looks at its destination. If something is
+    /// there, `policy` decides whether to skip, overwrite, or pick a
+    /// free numbered name; with [`ConflictPolicy::Ask`] nothing is
+    /// transferred and [`AppEvent::TransferConflict`] arrives instead.
+    pub fn request_file_operation(
+        &mut self,
+        remote: String,
+        modal: &FileOperationsModal,
+        policy: ConflictPolicy,
+    ) {
+        if self.offline() {
+            self.modal = None;
+            let modal = modal.clone();
+            self.queue(modal.pending_message(), move |app| {
+                app.request_file_operation(remote, &modal, policy);
+            });
+            return;
+        }
+        let client = self.client.clone();
+        let history = self.history.clone();
+        let bytes = self
+            .files
+            .iter()
+            .find(|item| item.name() == modal.file_name && !item.is_dir())
+            .and_then(|item| u64::try_from(item.size).ok());
+        let mut modal = modal.clone();
+        self.workers.spawn_transfer(async move {
+            let started = Instant::now();
+            let file = modal.file_name.clone();
+            let mut target = rclone_path(&remote, &file);
+            let result = match modal.operation {
+                FileOperationType::DeleteFile => {
+                    info!(file = %file, "deleting file");
+                    client.delete_file(&remote, &file).await
+                }
+                FileOperationType::DeleteDirectory => {
+                    info!(dir = %file, "purging directory");
+                    client.purge(&remote, &file).await
+                }
+                FileOperationType::Mkdir => {
+                    let dir = &modal.current_path;
+                    let new_path = if dir == "/" {
+                        format!("/{}", modal.input)
+                    } else {
+                        format!("{}/{}", dir, modal.input)
+                    };
+                    target = rclone_path(&remote, &new_path);
+                    info!(path = %new_path, "creating directory");
+                    client.mkdir(&remote, &new_path).await
+                }
+                FileOperationType::Copy | FileOperationType::Move => {
+                    match resolve_destination(&client, &remote, &modal.input, policy).await {
+                        Ok(Destination::Write(dst)) => {
+                            modal.input = dst;
+                            let dst = &modal.input;
+                            let copy = modal.operation == FileOperationType::Copy;
+                            let timed = Timed::start(
+                                if copy {
+                                    TransferKind::Copy
+                                } else {
+                                    TransferKind::Move
+                                },
+                                rclone_path(&remote, &file),
+                                rclone_path(&remote, dst),
+                                bytes,
+                            );
+                            let result = if copy {
+                                info!(src = %file, dst = %dst, "copying file");
+                                client.copy_file(&remote, &file, &remote, dst).await
+                            } else {
+                                info!(src = %file, dst = %dst, "moving file");
+                                client.move_file(&remote, &file, &remote, dst).await
+                            };
+                            timed.finish(history.as_ref(), &result);
+                            result
+                        }
+                        Ok(Destination::Skip) => {
+                            info!(src = %file, dst = %modal.input, "destination exists, skipping");
+                            return AppEvent::FileOperationFinished {
+                                operation: modal.operation,
+                                target,
+                                message: format!(
+                                    "Skipped '{}': '{}' already exists",
+                                    file, modal.input
+                                ),
+                                elapsed: started.elapsed(),
+                                result: Ok(()),
+                            };
+                        }
+                        Ok(Destination::Ask(existing)) => {
+                            debug!(dst = %modal.input, "destination exists, asking");
+                            return AppEvent::TransferConflict {
+                                remote,
+                                operation: modal,
+                                existing,
+                            };
+                        }
+                        Err(e) => Err(e),
+                    }
+                }
+            };
+            AppEvent::FileOperationFinished {
+                operation: modal.operation,
+                target,
+                message: modal.done_message(),
+                elapsed: started.elapsed(),
+                result,
+            }
+        });
+    }
+
+    /// Renames entries of `dir` on `remote`, old and new names with
+    /// whether each is a directory, one by one on a background task,
+    /// stopping at the first failure. The outcome arrives as
+    /// [`AppEvent::BatchRenamed`].
+    pub fn request_batch_rename(
+        &mut self,
+        remote: String,
+        dir: String,
+        renames: Vec<(String, String, bool)>,
+    ) {
+        if self.offline() {
+            self.modal = None;
+            self.queue(format!("renaming {} entries", renames.len()), move |app| {
+                app.request_batch_rename(remote, dir, renames);
+            });
+            return;
+        }
+        info!(remote = %remote, dir = %dir, count = renames.len(), "batch renaming");
+        let client = self.client.clone();
+        let location = rclone_path(&remote, &dir);
+        let path = move |name: &str| {
+            if dir.is_empty() {
+                name.to_string()
+            } else {
+                format!("{}/{}", dir, name)
+            }
+        };
+        self.workers.spawn_transfer(async move {
+            let total = renames.len();
+            let mut renamed = 0;
+            let mut result = Ok(());
+            for (old, new, is_dir) in &renames {
+                debug!(old = %old, new = %new, is_dir, "renaming");
+                let (old, new) = (path(old), path(new));
+                result = if *is_dir {
+                    // A renamed directory keeps all of its files,
+                    // whatever the filter hides.
+                    let everything = Filter::default();
+                    client
+                        .move_dir(&remote, &old, &remote, &new, &everything)
+                        .await
+                } else {
+                    client.move_file(&remote, &old, &remote, &new).await
+                };
+                if result.is_err() {
+                    break;
+                }
+                renamed += 1;
+            }
+            AppEvent::BatchRenamed {
+                renamed,
+                total,
+                location,
+                result,
+            }
+        });
+    }
+
+    /// Moves `entries` on `remote`, paths with whether each is a
+    /// directory, to storage tier `tier`, one by one on a background
+    /// task and stopping at the first failure. The outcome arrives as
+    /// [`AppEvent::TierChanged`].
+    pub fn request_set_tier(&mut self, remote: String, entries: Vec<(String, bool)>, tier: String) {
+        if self.offline() {
+            self.queue(
+                format!("changing the tier of {} entries", entries.len()),
+                move |app| app.request_set_tier(remote, entries, tier),
+            );
+            return;
+        }
+        info!(remote = %remote, count = entries.len(), tier = %tier, "changing storage tier");
+        let client = self.client.clone();
+        let location = rclone_path(&remote, &self.current_path);
+        self.workers.spawn_transfer(async move {
+            let total = entries.len();
+            let mut changed = 0;
+            let mut result = Ok(());
+            for (path, is_dir) in &entries {
+                result = client.set_tier(&remote, path, *is_dir, &tier).await;
+                if result.is_err() {
+                    break;
+                }
+                changed += 1;
+            }
+            AppEvent::TierChanged {
+                changed,
+                total,
+                location,
+                tier,
+                result,
+            }
+        });
+    }
+
+    /// Copies the clipboard's entries into `dir` on `remote`, or moves
+    /// them if they were cut, one by one on a background task and
+    /// stopping at the first failure. The outcome arrives as
+    /// [`AppEvent::Put`].
+    ///
+    /// A taken destination is handled by `policy`, except that
+    /// [`ConflictPolicy::Ask`] skips it: asking once per entry would
+    /// stall a large put. Copying into the source directory itself
+    /// always picks a free numbered name. Directories are copied or
+    /// moved through the current filter, like their listings.
+    pub fn request_put(&mut self, yank: Yank, remote: String, dir: String, policy: ConflictPolicy) {
+        if self.offline() {
+            self.queue(format!("putting {}", yank.describe()), move |app| {
+                app.request_put(yank, remote, dir, policy);
+            });
+            return;
+        }
+        info!(
+            src = %rclone_path(&yank.remote, &yank.dir),
+            dst = %rclone_path(&remote, &dir),
+            count = yank.entries.len(),
+            cut = yank.cut,
+            "putting entries"
+        );
+        let client = self.client.clone();
+        let history = self.history.clone();
+        let filter = self.filter.clone();
+        self.workers.spawn_transfer(async move {
+            let started = Instant::now();
+            let total = yank.entries.len();
+            let mut done = 0;
+            let mut skipped = Vec::new();
+            let mut result = Ok(());
+            for item in &yank.entries {
+                let src = yank.source(item.name());
+                let dst = join_path(&dir, leaf_name(item.name()));
+                let same_remote = yank.remote == remote;
+                if same_remote && src == dst && yank.cut {
+                    done += 1;
+                    continue;
+                }
+                if same_remote
+                    && item.is_dir()
+                    && (dir == src || dir.starts_with(&format!("{}/", src)))
+                {
+                    result = Err(LazyFileError::InvalidInput {
+                        field: "destination",
+                        reason: "a directory cannot be put inside itself",
+                    });
+                    break;
+                }
+                let policy = match policy {
+                    _ if same_remote && src == dst => ConflictPolicy::Rename,
+                    ConflictPolicy::Ask => ConflictPolicy::Skip,
+                    policy => policy,
+                };
+                let dst = match resolve_destination(&client, &remote, &dst, policy).await {
+                    Ok(Destination::Write(dst)) => dst,
+                    Ok(Destination::Skip | Destination::Ask(_)) => {
+                        debug!(dst = %dst, "destination exists, skipping");
+                        skipped.push(item.name().to_string());
+                        continue;
+                    }
+                    Err(e) => {
+                        result = Err(e);
+                        break;
+                    }
+                };
+                debug!(src = %src, dst = %dst, "putting entry");
+                let timed = Timed::start(
+                    if yank.cut {
+                        TransferKind::Move
+                    } else {
+                        TransferKind::Copy
+                    },
+                    rclone_path(&yank.remote, &src),
+                    rclone_path(&remote, &dst),
+                    (!item.is_dir())
+                        .then(|| u64::try_from(item.size).ok())
+                        .flatten(),
+                );
+                result = match (item.is_dir(), yank.cut) {
+                    (false, false) => client.copy_file(&yank.remote, &src, &remote, &dst).await,
+                    (false, true) => client.move_file(&yank.remote, &src, &remote, &dst).await,
+                    (true, false) => {
+                        client
+                            .copy_dir(&yank.remote, &src, &remote, &dst, &filter)
+                            .await
+                    }
+                    (true, true) => {
+                        client
+                            .move_dir(&yank.remote, &src, &remote, &dst, &filter)
+                            .await
+                    }
+                };
+                timed.finish(history.as_ref(), &result);
+                if result.is_err() {
+                    break;
+                }
+                done += 1;
+            }
+            AppEvent::Put {
+                done,
+                total,
+                skipped,
+                source: rclone_path(&yank.remote, &yank.dir),
+                destination: rclone_path(&remote, &dir),
+                cut: yank.cut,
+                elapsed: started.elapsed(),
+                result,
+            }
+        });
+    }
+}
+
+/// Most numbered names tried for [`ConflictPolicy::Rename`] before
+/// giving up.
+const MAX_RENAME_ATTEMPTS: usize = 100;
+
+/// Where a copy or move goes once its destination was checked.
+enum Destination {
+    /// Write to this path, which is free or may be overwritten.
+    Write(String),
+    /// Leave the existing entry alone.
+    Skip,
+    /// Let the user decide about the existing entry.
+    Ask(FileItem),
+}
+
+/// Looks up `dst` on `remote` and applies `policy` if it is taken.
+async fn resolve_destination(
+    client: &RcloneClient,
+    remote: &str,
+    dst: &str,
+    policy: ConflictPolicy,
+) -> Result<Destination> {
+    let Some(existing) = client.stat(remote, dst).await? else {
+        return Ok(Destination::Write(dst.to_string()));
+    };
+    match policy {
+        ConflictPolicy::Ask => Ok(Destination::Ask(existing)),
+        ConflictPolicy::Skip => Ok(Destination::Skip),
+        ConflictPolicy::Overwrite => Ok(Destination::Write(dst.to_string())),
+        ConflictPolicy::Rename => {
+            for n in 1..=MAX_RENAME_ATTEMPTS {
+                let candidate = numbered(dst, n);
+                if client.stat(remote, &candidate).await?.is_none() {
+                    return Ok(Destination::Write(candidate));
+                }
+            }
+            Err(LazyFileError::InvalidInput {
+                field: "destination",
+                reason: "no free numbered name",
+            })
+        }
+    }
+}
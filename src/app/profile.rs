@@ -0,0 +1,153 @@
+//! Switching between connection profiles at runtime.
+
+use super::state::App;
+use crate::error::{LazyFileError, Result};
+use crate::rclone::RcloneClient;
+use std::time::Instant;
+use tracing::info;
+
+impl App {
+    /// Reconnects to the daemon of the named profile and starts
+    /// loading its remotes and backends. Everything shown from the
+    /// previous daemon is cleared first, so a failed connection does
+    /// not leave stale entries; the remotes arrive as
+    /// [`AppEvent::RemotesLoaded`](super::AppEvent::RemotesLoaded).
+    ///
+    /// # Errors
+    /// Returns `UnknownProfile` for a name without a profile, or the
+    /// error of building the client.
+    pub fn switch_profile(&mut self, name: &str) -> Result<()> {
+        let profile = self
+            .profiles
+            .get(name)
+            .ok_or_else(|| LazyFileError::UnknownProfile {
+                name: name.to_string(),
+            })?;
+        let client = RcloneClient::connect(&profile.connection()?)?
+            .with_policy(self.client.policy().clone());
+        info!(profile = name, "switching profile");
+
+        self.cancel_loading();
+        // Queued changes run against whatever daemon is current once
+        // one answers, which would be the new one.
+        self.discard_queued();
+        self.client = client;
+        // The daemon LazyFile started, if any, is not the one in use
+        // any more.
+        self.owns_daemon = false;
+        self.profile = Some(name.to_string());
+        self.remotes.clear();
+        self.quotas.clear();
+        // Listings are keyed by remote and path only, and the same
+        // names on another daemon are other directories.
+        self.listing_cache.clear();
+        self.remotes_selected = 0;
+        self.remotes_offset = 0;
+        self.current_remote = None;
+        self.current_path.clear();
+        self.files.clear();
+        self.files_selected = 0;
+        self.files_offset = 0;
+        self.focus_remotes();
+        self.providers.clear();
+        self.reset_health(Instant::now());
+        self.request_remotes();
+        self.request_providers();
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::AppEvent;
+    use crate::app::handler::test_support::unreachable_app;
+    use crate::config::Profile;
+    use crate::rclone::FileItem;
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    /// Profile pointed at a port nothing listens on.
+    fn unreachable_profile() -> Profile {
+        Profile {
+            port: 1,
+            ..Profile::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_switch_clears_previous_daemon() {
+        let mut app = unreachable_app();
+        app.profiles
+            .insert("nas".to_string(), unreachable_profile());
+        app.remotes = vec!["gdrive".to_string()];
+        app.current_remote = Some("gdrive".to_string());
+        app.files = vec![FileItem {
+            name: "a.txt".to_string(),
+            size: 0,
+            mod_time: String::new(),
+            is_dir: false,
+            ..Default::default()
+        }];
+        app.listing_cache.insert(
+            "gdrive".to_string(),
+            String::new(),
+            app.files.clone(),
+            Instant::now(),
+        );
+
+        app.switch_profile("nas").unwrap();
+
+        assert_eq!(app.profile.as_deref(), Some("nas"));
+        assert!(app.remotes.is_empty());
+        assert!(app.files.is_empty());
+        assert!(app.current_remote.is_none());
+        assert!(app.listing_cache.get_stale("gdrive", "").is_none());
+
+        while let Some(event) = app.wait_event().await {
+            if let AppEvent::RemotesLoaded(result) = event {
+                assert!(app.apply_remotes(result).is_err());
+                break;
+            }
+        }
+        assert!(!app.connected);
+    }
+
+    #[tokio::test]
+    async fn test_switch_discards_changes_queued_for_previous_daemon() {
+        let mut app = unreachable_app();
+        app.profiles
+            .insert("nas".to_string(), unreachable_profile());
+        let ran = Arc::new(AtomicBool::new(false));
+        let flag = Arc::clone(&ran);
+        app.queue("deleting 'a.txt'".to_string(), move |_| {
+            flag.store(true, Ordering::SeqCst);
+        });
+
+        app.switch_profile("nas").unwrap();
+        app.connected = false;
+        app.apply_ping(Ok("v1.68.2".to_string()), Instant::now());
+
+        assert!(!ran.load(Ordering::SeqCst));
+        assert!(app.queued.is_empty());
+        assert!(
+            app.notifications
+                .toasts()
+                .any(|toast| toast.message.starts_with("Discarded 1 queued change(s)"))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_unknown_profile_keeps_connection() {
+        let mut app = unreachable_app();
+        app.profiles
+            .insert("nas".to_string(), unreachable_profile());
+        app.remotes = vec!["gdrive".to_string()];
+
+        let err = app.switch_profile("vps").unwrap_err();
+
+        assert!(matches!(err, LazyFileError::UnknownProfile { .. }));
+        assert!(app.profile.is_none());
+        assert_eq!(app.remotes.len(), 1);
+    }
+}
@@ -0,0 +1,50 @@
+//! Recently visited locations, remembered across sessions.
+
+use super::command::rclone_path;
+use super::state::App;
+use tracing::debug;
+
+impl App {
+    /// Records the location on screen as the most recent one and
+    /// saves the state file. A failed save is logged, not shown:
+    /// browsing works the same without it.
+    pub fn remember_location(&mut self) {
+        let Some(ref remote) = self.current_remote else {
+            return;
+        };
+        let location = rclone_path(remote, &self.current_path);
+        if self.state.push_recent(&location) {
+            self.save_state();
+        }
+    }
+
+    /// Forgets a recent location and saves the state file.
+    pub fn forget_location(&mut self, location: &str) {
+        if self.state.forget_recent(location) {
+            debug!(location, "forgot recent location");
+            self.save_state();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::app::handler::test_support::unreachable_app;
+
+    #[test]
+    fn test_remembers_locations_most_recent_first() {
+        let mut app = unreachable_app();
+        app.remember_location();
+        assert!(app.state.recent.is_empty());
+
+        app.current_remote = Some("gdrive".to_string());
+        app.remember_location();
+        app.current_path = "docs".to_string();
+        app.remember_location();
+        assert_eq!(app.state.recent, ["gdrive:docs", "gdrive:"]);
+
+        app.forget_location("gdrive:");
+        assert_eq!(app.state.recent, ["gdrive:docs"]);
+    }
+}
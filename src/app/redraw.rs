@@ -0,0 +1,55 @@
+//! Deciding when to draw a frame, so an app idling in a terminal
+//! all day does not repaint an unchanged screen five times a second.
+
+use super::state::App;
+use std::time::{Duration, Instant};
+
+/// Longest an unchanged screen goes without a redraw, so relative
+/// times such as `3m ago` keep up.
+pub const MAX_IDLE_REDRAW: Duration = Duration::from_secs(60);
+
+impl App {
+    /// Returns whether the frame at `now` must be drawn: state
+    /// changed, the loading spinner, the reconnect countdown or a
+    /// request's retries are moving, or the screen has gone
+    /// [`MAX_IDLE_REDRAW`] unchanged.
+    pub fn needs_redraw(&self, now: Instant) -> bool {
+        self.dirty
+            || self.loading.is_some()
+            || self.health.retry_in(now).is_some()
+            || self.client.retrying().is_some()
+            || now.saturating_duration_since(self.drawn_at) >= MAX_IDLE_REDRAW
+    }
+
+    /// Records that a frame was drawn at `now`.
+    pub fn mark_drawn(&mut self, now: Instant) {
+        self.dirty = false;
+        self.drawn_at = now;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::handler::test_support::unreachable_app;
+
+    #[test]
+    fn test_redraws_only_after_changes_or_when_stale() {
+        let mut app = unreachable_app();
+        let now = Instant::now();
+        assert!(app.needs_redraw(now));
+
+        app.mark_drawn(now);
+        assert!(!app.needs_redraw(now + Duration::from_secs(1)));
+        assert!(app.needs_redraw(now + MAX_IDLE_REDRAW));
+
+        app.logs_visible = true;
+        app.logs.push(crate::logs::LogLine {
+            level: tracing::Level::INFO,
+            target: String::new(),
+            message: "listed".to_string(),
+        });
+        app.sync_logs();
+        assert!(app.needs_redraw(now));
+    }
+}
@@ -0,0 +1,276 @@
+//! Favorite remotes, the manual order of the remotes panel, the path
+//! each remote opens at and the panel's width, kept in the config
+//! file's `[remotes]` table, and collapsing the panel.
+
+use super::state::{App, Panel};
+use crate::config::{Config, REMOTES_WIDTH_RANGE};
+use crate::error::Result;
+use crate::ui::Sidebar;
+use tracing::{debug, info};
+
+/// Percent of the screen width one resize moves the split by.
+const WIDTH_STEP: u16 = 5;
+
+impl App {
+    /// Stars the selected remote, pinning it to the top, or unstars
+    /// it, then saves the layout.
+    ///
+    /// # Errors
+    /// Returns `ConfigWrite` if the config file cannot be written;
+    /// the change still applies until LazyFile exits.
+    pub fn toggle_favorite(&mut self) -> Result<()> {
+        let Some(remote) = self.remotes.get(self.remotes_selected).cloned() else {
+            return Ok(());
+        };
+        let layout = &mut self.remote_layout;
+        if layout.is_favorite(&remote) {
+            layout.favorites.retain(|name| *name != remote);
+        } else {
+            layout.favorites.push(remote.clone());
+        }
+        info!(remote = %remote, favorite = layout.is_favorite(&remote), "toggled favorite");
+
+        layout.order = self.remotes.clone();
+        layout.arrange(&mut self.remotes);
+        if let Some(index) = self.remotes.iter().position(|name| *name == remote) {
+            self.remotes_selected = index;
+        }
+        self.keep_selection_visible();
+        self.save_remote_layout()
+    }
+
+    /// Swaps the selected remote with the one below it (`down`) or
+    /// above it, then saves the layout. Favorites and the other
+    /// remotes are ordered separately, so nothing moves across the
+    /// boundary between them.
+    ///
+    /// # Errors
+    /// Returns `ConfigWrite` if the config file cannot be written;
+    /// the change still applies until LazyFile exits.
+    pub fn move_remote(&mut self, down: bool) -> Result<()> {
+        let from = self.remotes_selected;
+        let to = if down {
+            from + 1
+        } else {
+            let Some(to) = from.checked_sub(1) else {
+                return Ok(());
+            };
+            to
+        };
+        let (Some(selected), Some(other)) = (self.remotes.get(from), self.remotes.get(to)) else {
+            return Ok(());
+        };
+        if self.remote_layout.is_favorite(selected) != self.remote_layout.is_favorite(other) {
+            debug!("not moving a remote past the favorites boundary");
+            return Ok(());
+        }
+
+        self.remotes.swap(from, to);
+        self.remotes_selected = to;
+        self.remote_layout.order = self.remotes.clone();
+        self.keep_selection_visible();
+        self.save_remote_layout()
+    }
+
+    /// Moves remote `from`'s favorite star, place in the manual order
+    /// and start path over to `to`, after a rename, then saves the
+    /// layout.
+    ///
+    /// # Errors
+    /// Returns `ConfigWrite` if the config file cannot be written;
+    /// the change still applies until LazyFile exits.
+    pub fn rename_in_layout(&mut self, from: &str, to: &str) -> Result<()> {
+        let layout = &mut self.remote_layout;
+        let mut changed = false;
+        for name in layout.favorites.iter_mut().chain(layout.order.iter_mut()) {
+            if name == from {
+                *name = to.to_string();
+                changed = true;
+            }
+        }
+        let path = layout.start_path(from).to_string();
+        if !path.is_empty() {
+            layout.set_start_path(from, "");
+            layout.set_start_path(to, &path);
+            changed = true;
+        }
+        if !changed {
+            return Ok(());
+        }
+        info!(from, to, "renamed remote in layout");
+        self.save_remote_layout()
+    }
+
+    /// Makes `remote` open at `path`, or at its root if `path` is
+    /// empty, then saves the layout.
+    ///
+    /// # Errors
+    /// Returns `ConfigWrite` if the config file cannot be written;
+    /// the change still applies until LazyFile exits.
+    pub fn set_start_path(&mut self, remote: &str, path: &str) -> Result<()> {
+        if self.remote_layout.start_path(remote) == path.trim().trim_matches('/') {
+            return Ok(());
+        }
+        self.remote_layout.set_start_path(remote, path);
+        info!(
+            remote,
+            path = self.remote_layout.start_path(remote),
+            "set start path"
+        );
+        self.save_remote_layout()
+    }
+
+    /// Moves the split between the remotes and files panels, making
+    /// the remotes panel `wider` or narrower, then saves the layout.
+    ///
+    /// # Errors
+    /// Returns `ConfigWrite` if the config file cannot be written;
+    /// the change still applies until LazyFile exits.
+    pub fn resize_remotes(&mut self, wider: bool) -> Result<()> {
+        let (min, max) = REMOTES_WIDTH_RANGE;
+        let current = self.remote_layout.width();
+        let width = if wider {
+            current + WIDTH_STEP
+        } else {
+            current.saturating_sub(WIDTH_STEP)
+        }
+        .clamp(min, max);
+        if width == current {
+            return Ok(());
+        }
+        self.remote_layout.width = Some(width);
+        info!(width, "resized remotes panel");
+        self.set_screen(self.screen);
+        self.save_remote_layout()
+    }
+
+    /// Collapses the remotes panel to a strip, hides it, or brings it
+    /// back, moving focus to the files once it is hidden.
+    pub fn toggle_sidebar(&mut self) {
+        self.sidebar = self.sidebar.next();
+        info!(sidebar = ?self.sidebar, "toggled remotes panel");
+        if self.sidebar == Sidebar::Hidden && self.focused_panel == Panel::Remotes {
+            self.focused_panel = Panel::Files;
+        }
+        self.set_screen(self.screen);
+    }
+
+    /// Focuses the remotes panel, showing it again if hidden.
+    pub fn focus_remotes(&mut self) {
+        self.focused_panel = Panel::Remotes;
+        if self.sidebar == Sidebar::Hidden {
+            self.sidebar = Sidebar::Full;
+            self.set_screen(self.screen);
+        }
+    }
+
+    /// Writes the layout to the config file, if there is one.
+    fn save_remote_layout(&self) -> Result<()> {
+        let Some(path) = self.config_path.as_deref() else {
+            debug!("no config file location, remote layout not saved");
+            return Ok(());
+        };
+        Config::save_remote_layout(path, &self.remote_layout)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::handler::test_support::remotes_app;
+    use ratatui::layout::Rect;
+    use std::fs;
+
+    #[test]
+    fn test_favorite_moves_to_top_and_keeps_selection() {
+        let mut app = remotes_app(&["a", "b", "c"]);
+        app.remotes_selected = 2;
+
+        app.toggle_favorite().unwrap();
+        assert_eq!(app.remotes, ["c", "a", "b"]);
+        assert_eq!(app.remotes_selected, 0);
+        assert_eq!(app.remote_layout.favorites, ["c"]);
+
+        app.toggle_favorite().unwrap();
+        assert!(app.remote_layout.favorites.is_empty());
+        assert_eq!(app.remotes, ["c", "a", "b"]);
+    }
+
+    #[test]
+    fn test_move_stays_within_group() {
+        let mut app = remotes_app(&["a", "b", "c"]);
+        app.remote_layout.favorites = vec!["a".to_string()];
+        app.remotes_selected = 1;
+
+        app.move_remote(false).unwrap();
+        assert_eq!(app.remotes, ["a", "b", "c"]);
+
+        app.move_remote(true).unwrap();
+        assert_eq!(app.remotes, ["a", "c", "b"]);
+        assert_eq!(app.remotes_selected, 2);
+        assert_eq!(app.remote_layout.order, ["a", "c", "b"]);
+
+        app.move_remote(true).unwrap();
+        assert_eq!(app.remotes_selected, 2);
+    }
+
+    #[test]
+    fn test_hidden_sidebar_gives_up_focus() {
+        let mut app = remotes_app(&["a"]);
+        app.set_screen(Rect::new(0, 0, 100, 40));
+        app.toggle_sidebar();
+        assert_eq!(app.sidebar, Sidebar::Strip);
+        assert_eq!(app.focused_panel, Panel::Remotes);
+
+        app.toggle_sidebar();
+        assert_eq!(app.sidebar, Sidebar::Hidden);
+        assert_eq!(app.focused_panel, Panel::Files);
+        app.switch_panel();
+        assert_eq!(app.focused_panel, Panel::Files);
+
+        app.focus_remotes();
+        assert_eq!(app.sidebar, Sidebar::Full);
+    }
+
+    #[test]
+    fn test_rename_carries_star_and_place() {
+        let mut app = remotes_app(&["a", "b"]);
+        app.remote_layout.favorites = vec!["b".to_string()];
+        app.remote_layout.order = vec!["b".to_string(), "a".to_string()];
+
+        app.set_start_path("b", "/Work").unwrap();
+
+        app.rename_in_layout("b", "c").unwrap();
+        assert_eq!(app.remote_layout.favorites, ["c"]);
+        assert_eq!(app.remote_layout.order, ["c", "a"]);
+        assert_eq!(app.remote_layout.start_paths, ["c:Work"]);
+    }
+
+    #[test]
+    fn test_resize_stays_within_range() {
+        let mut app = remotes_app(&[]);
+
+        app.resize_remotes(true).unwrap();
+        assert_eq!(app.remote_layout.width, Some(35));
+
+        for _ in 0..20 {
+            app.resize_remotes(false).unwrap();
+        }
+        assert_eq!(app.remote_layout.width(), REMOTES_WIDTH_RANGE.0);
+    }
+
+    #[test]
+    fn test_layout_is_saved_to_config_file() {
+        let dir = std::env::temp_dir().join(format!("lazyfile-layout-{}", std::process::id()));
+        let path = dir.join("config.toml");
+        let mut app = remotes_app(&["a", "b"]);
+        app.config_path = Some(path.clone());
+        app.remotes_selected = 1;
+
+        app.toggle_favorite().unwrap();
+        app.resize_remotes(true).unwrap();
+        let config = Config::load(&path).unwrap();
+        let _ = fs::remove_dir_all(&dir);
+        assert_eq!(config.remotes, app.remote_layout);
+    }
+}
@@ -0,0 +1,257 @@
+//! Remotes and the rclone config: listing and configuring remotes in
+//! the background and applying what comes back, unlocking the config
+//! and starting the daemon.
+
+use super::event::AppEvent;
+use super::state::App;
+use crate::daemon::Daemon;
+use crate::error::Result;
+use crate::rclone::{FileItem, Quota};
+use crate::ui::widgets::create_remote_upstream::upstream_location;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use tracing::{debug, info, warn};
+
+impl App {
+    /// Starts creating a remote through the config state machine on a
+    /// background task. The first question, if any, arrives as
+    /// [`AppEvent::RemoteConfigStep`].
+    pub fn request_remote_config(
+        &self,
+        name: String,
+        remote_type: String,
+        parameters: HashMap<String, String>,
+    ) {
+        debug!(remote = %name, r#type = %remote_type, "starting remote config");
+        let client = self.client.clone();
+        self.workers.spawn(async move {
+            let result = client
+                .start_remote_config(&name, &remote_type, parameters)
+                .await;
+            AppEvent::RemoteConfigStep { name, result }
+        });
+    }
+
+    /// Sends `answer` to the question asked in `state` on a background
+    /// task. The next question, if any, arrives as
+    /// [`AppEvent::RemoteConfigStep`].
+    pub fn request_config_answer(&self, name: String, state: String, answer: String) {
+        debug!(remote = %name, state = %state, "answering remote config question");
+        let client = self.client.clone();
+        self.workers.spawn(async move {
+            let result = client.continue_remote_config(&name, &state, &answer).await;
+            AppEvent::RemoteConfigStep { name, result }
+        });
+    }
+
+    /// Fetches the list of remotes and their types on background
+    /// tasks. The results arrive as [`AppEvent::RemotesLoaded`] and
+    /// [`AppEvent::RemoteTypesLoaded`].
+    pub fn request_remotes(&self) {
+        debug!("loading remotes");
+        let client = self.client.clone();
+        self.workers
+            .spawn(async move { AppEvent::RemotesLoaded(client.list_remotes().await) });
+        let client = self.client.clone();
+        self.workers
+            .spawn(async move { AppEvent::RemoteTypesLoaded(client.list_remote_types().await) });
+    }
+
+    /// Fetches the quota of every remote on background tasks, one per
+    /// remote. The results arrive as [`AppEvent::QuotaLoaded`].
+    pub fn request_quotas(&self) {
+        debug!(count = self.remotes.len(), "loading quotas");
+        for remote in &self.remotes {
+            let client = self.client.clone();
+            let remote = remote.clone();
+            self.workers.spawn(async move {
+                let result = client.about(&remote).await;
+                AppEvent::QuotaLoaded { remote, result }
+            });
+        }
+    }
+
+    /// Unlocks the daemon's encrypted config with `password` on a
+    /// background task. The outcome arrives as
+    /// [`AppEvent::ConfigUnlocked`].
+    pub fn request_unlock_config(&self, password: String) {
+        debug!("unlocking config");
+        let client = self.client.clone();
+        self.workers.spawn(async move {
+            let result = client.unlock_config(&password).await;
+            AppEvent::ConfigUnlocked(result)
+        });
+    }
+
+    /// Starts `rclone rcd` from `program` on a background task, kept
+    /// running after exit if `keep_daemon` says so. The daemon, once it
+    /// listens, arrives as [`AppEvent::DaemonStarted`].
+    pub fn request_start_daemon(&self, program: PathBuf) {
+        info!(program = %program.display(), "starting rclone daemon for the session");
+        let keep = self.keep_daemon;
+        self.workers.spawn(async move {
+            let program = program.to_string_lossy();
+            AppEvent::DaemonStarted(Daemon::spawn_program(&program, keep).await)
+        });
+    }
+
+    /// Fetches the catalogue of backends on a background task. The
+    /// result arrives as [`AppEvent::ProvidersLoaded`].
+    pub fn request_providers(&self) {
+        debug!("loading providers");
+        let client = self.client.clone();
+        self.workers
+            .spawn(async move { AppEvent::ProvidersLoaded(client.list_providers().await) });
+    }
+
+    /// Fetches the settings of remote `name` on a background task.
+    /// The result arrives as [`AppEvent::RemoteSettingsLoaded`].
+    pub fn request_remote_settings(&self, name: String) {
+        debug!(remote = %name, "loading remote settings");
+        let client = self.client.clone();
+        self.workers.spawn(async move {
+            let result = client.get_remote(&name).await;
+            AppEvent::RemoteSettingsLoaded { name, result }
+        });
+    }
+
+    /// Fetches remote `name`'s settings and backend features on a
+    /// background task. Both arrive as [`AppEvent::RemoteInfoLoaded`].
+    pub fn request_remote_info(&self, name: String) {
+        debug!(remote = %name, "loading remote details");
+        let client = self.client.clone();
+        self.workers.spawn(async move {
+            let (config, info) = tokio::join!(client.get_remote(&name), client.fs_info(&name));
+            AppEvent::RemoteInfoLoaded { name, config, info }
+        });
+    }
+
+    /// Lists the folders of `path` on `remote` for the upstream picker
+    /// on a background task. The result arrives as
+    /// [`AppEvent::UpstreamDirsLoaded`].
+    pub fn request_upstream_dirs(&self, remote: String, path: String) {
+        debug!(remote = %remote, path = %path, "loading upstream folders");
+        let client = self.client.clone();
+        self.workers.spawn(async move {
+            let result = client.list_files(&remote, &path).await.map(|items| {
+                items
+                    .into_iter()
+                    .filter(FileItem::is_dir)
+                    .map(|item| item.name)
+                    .collect()
+            });
+            let location = upstream_location(&remote, &path);
+            AppEvent::UpstreamDirsLoaded { location, result }
+        });
+    }
+
+    /// Saves changed settings of remote `name` on a background task.
+    /// The outcome arrives as [`AppEvent::RemoteUpdated`].
+    pub fn request_update_remote(&self, name: String, parameters: HashMap<String, String>) {
+        info!(remote = %name, "updating remote");
+        let client = self.client.clone();
+        self.workers.spawn(async move {
+            let result = client.update_remote(&name, parameters).await;
+            AppEvent::RemoteUpdated { name, result }
+        });
+    }
+
+    /// Copies remote `from` as `to` on a background task, then deletes
+    /// `from` if `remove_source` is set and the copy succeeded. The
+    /// outcome arrives as [`AppEvent::RemoteCopied`].
+    pub fn request_copy_remote(&self, from: String, to: String, remove_source: bool) {
+        info!(from = %from, to = %to, remove_source, "copying remote");
+        let client = self.client.clone();
+        self.workers.spawn(async move {
+            let result = client.copy_remote(&from, &to).await;
+            let removed = match result {
+                Ok(()) if remove_source => Some(client.delete_remote(&from).await),
+                _ => None,
+            };
+            AppEvent::RemoteCopied {
+                from,
+                to,
+                result,
+                removed,
+            }
+        });
+    }
+
+    /// Deletes remote `name` on a background task. The outcome
+    /// arrives as [`AppEvent::RemoteDeleted`]; `discard` marks the
+    /// half-configured remote of a cancelled wizard, whose deletion is
+    /// not reported.
+    pub fn request_delete_remote(&self, name: String, discard: bool) {
+        info!(remote = %name, discard, "deleting remote");
+        let client = self.client.clone();
+        self.workers.spawn(async move {
+            let result = client.delete_remote(&name).await;
+            AppEvent::RemoteDeleted {
+                name,
+                discard,
+                result,
+            }
+        });
+    }
+
+    /// Loads remotes from rclone daemon.
+    ///
+    /// # Errors
+    /// Returns error if rclone daemon is unreachable.
+    pub async fn load_remotes(&mut self) -> Result<()> {
+        debug!("loading remotes");
+        let result = self.client.list_remotes().await;
+        self.apply_remotes(result)?;
+        let types = self.client.list_remote_types().await;
+        self.apply_remote_types(types);
+        Ok(())
+    }
+
+    /// Shows a fetched list of remotes, or marks the daemon as
+    /// unreachable.
+    ///
+    /// # Errors
+    /// Returns the fetch's error if it failed.
+    pub fn apply_remotes(&mut self, result: Result<Vec<String>>) -> Result<()> {
+        match result {
+            Ok(mut remotes) => {
+                self.remote_layout.arrange(&mut remotes);
+                self.remotes = remotes;
+                self.remotes_selected = 0;
+                self.remotes_offset = 0;
+                self.connected = true;
+                info!(count = self.remotes.len(), "loaded remotes");
+                self.quotas.retain(|name, _| self.remotes.contains(name));
+                self.request_quotas();
+                Ok(())
+            }
+            Err(e) => {
+                self.connected = false;
+                Err(e)
+            }
+        }
+    }
+
+    /// Keeps fetched remote types. They only decorate the remotes
+    /// panel, so a failure is logged and the old ones stay.
+    pub fn apply_remote_types(&mut self, result: Result<HashMap<String, String>>) {
+        match result {
+            Ok(types) => self.remote_types = types,
+            Err(e) => warn!(error = %e, "could not load remote types"),
+        }
+    }
+
+    /// Keeps a remote's fetched quota. Many backends have none, so a
+    /// failure just leaves the remote without a usage bar.
+    pub fn apply_quota(&mut self, remote: String, result: Result<Quota>) {
+        match result {
+            Ok(quota) => {
+                self.quotas.insert(remote, quota);
+            }
+            Err(e) => {
+                debug!(remote = %remote, error = %e, "no quota for remote");
+                self.quotas.remove(&remote);
+            }
+        }
+    }
+}
@@ -0,0 +1,298 @@
+//! User scripts run from the command palette.
+//!
+//! Every `*.rhai` file in the `scripts` directory next to the config
+//! file becomes a palette entry named after the file. A script sees
+//! where the user is and can call the rclone client, so workflows such
+//! as "archive and move to cold storage" need no new key bindings:
+//!
+//! ```text
+//! for path in selected {
+//!     move_file(remote, path, "glacier", "archive/" + path);
+//! }
+//! print(`archived ${selected.len()} files`);
+//! ```
+//!
+//! Scripts run on a blocking thread and reach the client through the
+//! runtime handle, so the UI keeps drawing while they work.
+
+use super::event::AppEvent;
+use super::state::App;
+use crate::error::{LazyFileError, Result};
+use crate::rclone::RcloneClient;
+use rhai::{Array, Dynamic, Engine, EvalAltResult, Map, Scope};
+use std::collections::BTreeMap;
+use std::fs;
+use std::future::Future;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, PoisonError};
+use tokio::runtime::Handle;
+use tracing::{debug, info, warn};
+
+/// File extension of scripts.
+const EXTENSION: &str = "rhai";
+
+/// Where a script runs: the remote, directory and entries the user
+/// had selected when starting it.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ScriptContext {
+    /// Remote being browsed; empty if none is open.
+    pub remote: String,
+    /// Directory within the remote.
+    pub dir: String,
+    /// Paths of the selected entries within the remote.
+    pub selected: Vec<String>,
+}
+
+/// Returns the scripts in `dir`, keyed by file stem. A missing
+/// directory has none.
+pub fn discover(dir: &Path) -> BTreeMap<String, PathBuf> {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) => {
+            debug!(dir = %dir.display(), error = %e, "no scripts directory");
+            return BTreeMap::new();
+        }
+    };
+    let scripts: BTreeMap<String, PathBuf> = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.is_file() && path.extension().is_some_and(|ext| ext == EXTENSION))
+        .filter_map(|path| {
+            let name = path.file_stem()?.to_str()?.to_string();
+            Some((name, path))
+        })
+        .collect();
+    info!(dir = %dir.display(), count = scripts.len(), "found scripts");
+    scripts
+}
+
+/// Runs the script at `path` to completion, returning what it
+/// printed.
+///
+/// Must be called from a blocking thread of a Tokio runtime, since
+/// client calls wait on the runtime.
+///
+/// # Errors
+/// Returns `Script` if the script cannot be read, does not compile,
+/// or fails while running, including when a client call fails and the
+/// script does not catch it.
+pub fn run(
+    name: &str,
+    path: &Path,
+    client: RcloneClient,
+    context: ScriptContext,
+) -> Result<Vec<String>> {
+    let output = Arc::new(Mutex::new(Vec::new()));
+    let engine = engine(client, Handle::current(), Arc::clone(&output));
+
+    let mut scope = Scope::new();
+    scope.push_constant("remote", context.remote);
+    scope.push_constant("dir", context.dir);
+    let selected: Array = context.selected.into_iter().map(Dynamic::from).collect();
+    scope.push_constant("selected", selected);
+
+    debug!(script = name, path = %path.display(), "running script");
+    engine
+        .run_file_with_scope(&mut scope, path.to_path_buf())
+        .map_err(|e| LazyFileError::Script {
+            name: name.to_string(),
+            message: e.to_string(),
+        })?;
+    let lines = std::mem::take(&mut *output.lock().unwrap_or_else(PoisonError::into_inner));
+    Ok(lines)
+}
+
+/// Builds an engine whose `print` appends to `output` and whose
+/// client functions go through `client`.
+fn engine(client: RcloneClient, runtime: Handle, output: Arc<Mutex<Vec<String>>>) -> Engine {
+    let mut engine = Engine::new();
+    engine.on_print(move |line| {
+        output
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .push(line.to_string());
+    });
+    engine.on_debug(|line, source, pos| {
+        debug!(target: "lazyfile::script", source = source.unwrap_or_default(), %pos, "{}", line);
+    });
+
+    let call = Caller { client, runtime };
+    let c = call.clone();
+    engine.register_fn("list", move |remote: &str, path: &str| {
+        let items = c.block_on(|client| async move { client.list_files(remote, path).await })?;
+        Ok::<_, Box<EvalAltResult>>(
+            items
+                .into_iter()
+                .map(|item| {
+                    let mut entry = Map::new();
+                    entry.insert("name".into(), item.name.into());
+                    entry.insert("size".into(), item.size.into());
+                    entry.insert("mod_time".into(), item.mod_time.into());
+                    entry.insert("is_dir".into(), item.is_dir.into());
+                    Dynamic::from_map(entry)
+                })
+                .collect::<Array>(),
+        )
+    });
+    let c = call.clone();
+    engine.register_fn(
+        "copy_file",
+        move |src_remote: &str, src_path: &str, dst_remote: &str, dst_path: &str| {
+            c.block_on(|client| async move {
+                client
+                    .copy_file(src_remote, src_path, dst_remote, dst_path)
+                    .await
+            })
+        },
+    );
+    let c = call.clone();
+    engine.register_fn(
+        "move_file",
+        move |src_remote: &str, src_path: &str, dst_remote: &str, dst_path: &str| {
+            c.block_on(|client| async move {
+                client
+                    .move_file(src_remote, src_path, dst_remote, dst_path)
+                    .await
+            })
+        },
+    );
+    let c = call.clone();
+    engine.register_fn("delete_file", move |remote: &str, path: &str| {
+        c.block_on(|client| async move { client.delete_file(remote, path).await })
+    });
+    let c = call.clone();
+    engine.register_fn("purge", move |remote: &str, path: &str| {
+        c.block_on(|client| async move { client.purge(remote, path).await })
+    });
+    engine.register_fn("mkdir", move |remote: &str, path: &str| {
+        call.block_on(|client| async move { client.mkdir(remote, path).await })
+    });
+    engine
+}
+
+/// Client and runtime shared by the functions scripts call.
+#[derive(Debug, Clone)]
+struct Caller {
+    client: RcloneClient,
+    runtime: Handle,
+}
+
+impl Caller {
+    /// Waits for a client call, turning its error into a script
+    /// error the script can catch.
+    fn block_on<'a, T, F>(
+        &'a self,
+        call: impl FnOnce(&'a RcloneClient) -> F,
+    ) -> std::result::Result<T, Box<EvalAltResult>>
+    where
+        F: Future<Output = Result<T>>,
+    {
+        self.runtime
+            .block_on(call(&self.client))
+            .map_err(|e| e.to_string().into())
+    }
+}
+
+impl App {
+    /// Starts the script `name` on a blocking thread. The result
+    /// arrives as [`AppEvent::ScriptFinished`].
+    ///
+    /// Returns `false` if no script has that name.
+    pub fn request_script(&self, name: &str) -> bool {
+        let Some(path) = self.scripts.get(name).cloned() else {
+            warn!(script = name, "unknown script");
+            return false;
+        };
+        let context = ScriptContext {
+            remote: self.current_remote.clone().unwrap_or_default(),
+            dir: self.current_path.clone(),
+            selected: self.selected_paths(),
+        };
+        let name = name.to_string();
+        let client = self.client.clone();
+        let events = self.events_tx.clone();
+        tokio::task::spawn_blocking(move || {
+            let result = run(&name, &path, client, context);
+            let _ = events.send(AppEvent::ScriptFinished { name, result });
+        });
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Writes `source` as a script in a fresh directory and returns
+    /// its path.
+    fn script(name: &str, source: &str) -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("lazyfile-scripts-{}-{}", std::process::id(), name));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join(format!("{}.{}", name, EXTENSION));
+        fs::write(&path, source).unwrap();
+        path
+    }
+
+    fn run_blocking(path: PathBuf, context: ScriptContext) -> Result<Vec<String>> {
+        let client = RcloneClient::new("localhost", 1).unwrap();
+        run("test", &path, client, context)
+    }
+
+    #[test]
+    fn test_discovers_only_rhai_files() {
+        let path = script("archive", "");
+        let dir = path.parent().unwrap();
+        fs::write(dir.join("notes.txt"), "").unwrap();
+
+        let scripts = discover(dir);
+        let _ = fs::remove_dir_all(dir);
+        assert_eq!(scripts.keys().collect::<Vec<_>>(), ["archive"]);
+    }
+
+    #[test]
+    fn test_missing_directory_has_no_scripts() {
+        assert!(discover(Path::new("/nonexistent/scripts")).is_empty());
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn script_sees_context_and_prints() {
+        let path = script("context", r#"print(`${remote}:${dir} ${selected.len()}`);"#);
+        let context = ScriptContext {
+            remote: "gdrive".to_string(),
+            dir: "docs".to_string(),
+            selected: vec!["docs/a.txt".to_string()],
+        };
+        let result = tokio::task::spawn_blocking(move || run_blocking(path, context))
+            .await
+            .unwrap();
+        assert_eq!(result.unwrap(), ["gdrive:docs 1"]);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn client_errors_can_be_caught() {
+        let path = script(
+            "catch",
+            r#"
+                try { list("gdrive", ""); } catch (err) { print("caught"); }
+                list("gdrive", "");
+            "#,
+        );
+        let result =
+            tokio::task::spawn_blocking(move || run_blocking(path, ScriptContext::default()))
+                .await
+                .unwrap();
+        let err = result.unwrap_err();
+        assert!(matches!(err, LazyFileError::Script { .. }), "{}", err);
+        assert!(err.to_string().contains("operations/list"), "{}", err);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn syntax_error_is_a_script_error() {
+        let path = script("broken", "let = ;");
+        let result =
+            tokio::task::spawn_blocking(move || run_blocking(path, ScriptContext::default()))
+                .await
+                .unwrap();
+        assert!(matches!(result, Err(LazyFileError::Script { .. })));
+    }
+}
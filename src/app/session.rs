@@ -0,0 +1,86 @@
+//! Picking up where the last session left off.
+
+use super::state::{App, Panel};
+use crate::config::{Focus, Session};
+use tracing::{info, warn};
+
+impl App {
+    /// Records the open remote, path and focused panel, and saves the
+    /// state file. Called once the event loop ends.
+    pub fn save_session(&mut self) {
+        self.state.session = self.current_remote.as_ref().map(|remote| Session {
+            remote: remote.clone(),
+            path: self.current_path.clone(),
+            focus: match self.focused_panel {
+                Panel::Remotes => Focus::Remotes,
+                Panel::Files | Panel::Logs => Focus::Files,
+            },
+        });
+        self.save_state();
+    }
+
+    /// Lists the directory the last session left open, with the focus
+    /// where it was. Does nothing if its remote is gone.
+    pub fn restore_session(&mut self) {
+        let Some(session) = self.state.session.clone() else {
+            return;
+        };
+        let Some(index) = self.remotes.iter().position(|name| *name == session.remote) else {
+            warn!(remote = %session.remote, "last session's remote no longer exists");
+            return;
+        };
+        info!(remote = %session.remote, path = %session.path, "restoring last session");
+        self.remotes_selected = index;
+        self.request_files(session.remote, session.path);
+        self.focus_on_load = Some(match session.focus {
+            Focus::Remotes => Panel::Remotes,
+            Focus::Files => Panel::Files,
+        });
+    }
+
+    /// Writes the state to the state file, if there is one. A failed
+    /// save is logged, not shown: browsing works the same without it.
+    pub(super) fn save_state(&self) {
+        let Some(ref path) = self.state_path else {
+            return;
+        };
+        if let Err(e) = self.state.save(path) {
+            warn!(error = %e, "could not save state");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::Listing;
+    use crate::app::handler::test_support::unreachable_app;
+
+    #[tokio::test]
+    async fn test_restores_location_and_focus() {
+        let mut app = unreachable_app();
+        app.remotes = vec!["gdrive".to_string(), "s3".to_string()];
+        app.current_remote = Some("s3".to_string());
+        app.current_path = "backup".to_string();
+        app.focused_panel = Panel::Remotes;
+        app.save_session();
+
+        app.current_remote = None;
+        app.current_path.clear();
+        app.restore_session();
+        assert_eq!(app.remotes_selected, 1);
+        let loading = app.loading.as_ref().unwrap();
+        assert_eq!(loading.path, "backup");
+        let listing = Listing {
+            id: loading.id,
+            remote: "s3".to_string(),
+            path: "backup".to_string(),
+            cached: false,
+            recursive: false,
+            result: Ok(Vec::new()),
+        };
+        app.apply_listing(listing).unwrap();
+        assert_eq!(app.current_remote.as_deref(), Some("s3"));
+        assert_eq!(app.focused_panel, Panel::Remotes);
+    }
+}
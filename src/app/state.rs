@@ -1,9 +1,34 @@
 //! Application state management.
 
-use crate::error::Result;
-use crate::rclone::{FileItem, RcloneClient};
-use crate::ui::{ConfirmModal, CreateRemoteModal, FileOperationsModal};
-use tracing::{debug, info};
+pub use super::modal::{ActiveModal, Retry};
+
+use super::activity::Activity;
+use super::command::ShellCommand;
+use super::edit::{EditSession, Scratch};
+use super::event::AppEvent;
+use super::flat::FlatListing;
+use super::health::Health;
+use super::keymap::{KeyPress, Keymap};
+use super::listing_cache::{ListingCache, Prefetch};
+use super::loading::Loading;
+use super::offline::Queued;
+use super::tree::Tree;
+use super::worker::Workers;
+use super::yank::Yank;
+use crate::clipboard::Clipboard;
+use crate::config::{
+    AuditLog, ConfirmConfig, ConflictPolicy, FileListConfig, History, IconSet, Profile,
+    QuotaConfig, RemoteLayout, State, StatusBarConfig,
+};
+use crate::daemon::Daemon;
+use crate::logs::{LogBuffer, Verbosity};
+use crate::rclone::{FileItem, Filter, Provider, Quota, RcloneClient};
+use crate::ui::{FileColors, Notifications, Sidebar, Theme};
+use ratatui::layout::Rect;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+use tokio::sync::mpsc::{self, UnboundedReceiver, UnboundedSender};
 
 /// Represents the focused panel in the UI.
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -12,25 +37,8 @@ pub enum Panel {
     Remotes,
     /// Files list on the right.
     Files,
-}
-
-/// The currently open modal, if any.
-///
-/// A single slot makes overlapping modals unrepresentable: opening a
-/// modal replaces whatever was open before.
-#[derive(Debug)]
-pub enum ActiveModal {
-    /// Create/edit remote form.
-    CreateRemote(CreateRemoteModal),
-    /// Delete confirmation for the named remote.
-    ConfirmDeleteRemote {
-        /// Remote pending deletion, applied on confirm.
-        remote: String,
-        /// Yes/no confirmation state.
-        modal: ConfirmModal,
-    },
-    /// File operation (delete, mkdir, copy, move) form.
-    FileOperation(FileOperationsModal),
+    /// Log pane below the lists, focusable only while shown.
+    Logs,
 }
 
 /// Main application state.
@@ -38,18 +46,154 @@ pub enum ActiveModal {
 pub struct App {
     /// RcloneClient for API communication.
     pub client: RcloneClient,
+    /// Connection profiles from the config file, keyed by name.
+    pub profiles: BTreeMap<String, Profile>,
+    /// User scripts offered in the command palette, keyed by name.
+    pub scripts: BTreeMap<String, PathBuf>,
+    /// Shell command templates from `[commands]`, keyed by name.
+    pub commands: BTreeMap<String, String>,
+    /// What copy and move do when the destination exists; starts
+    /// as the config's `on_conflict` and follows an "apply to all"
+    /// choice in the conflict dialog.
+    pub conflict_policy: ConflictPolicy,
+    /// Name of the profile `client` was built from, if any.
+    pub profile: Option<String>,
     /// List of configured remotes.
     pub remotes: Vec<String>,
+    /// Backend type of each remote, keyed by name; empty until
+    /// fetched.
+    pub remote_types: HashMap<String, String>,
+    /// Space each remote has and uses, keyed by name; remotes whose
+    /// backend has no quota are left out.
+    pub quotas: HashMap<String, Quota>,
+    /// When usage bars turn yellow or red, from the config's
+    /// `[quota]`.
+    pub quota: QuotaConfig,
+    /// Favorites and manual order of the remotes panel.
+    pub remote_layout: RemoteLayout,
+    /// How much of the remotes panel is shown.
+    pub sidebar: Sidebar,
+    /// Whether the focused panel fills the screen.
+    pub zoomed: bool,
+    /// Config file the remote layout is saved to, if any.
+    pub config_path: Option<PathBuf>,
+    /// Recent locations and other session state.
+    pub state: State,
+    /// State file `state` is saved to, if any.
+    pub state_path: Option<PathBuf>,
+    /// Where finished copies and moves are recorded, if anywhere.
+    pub history: Option<History>,
+    /// Where changes are recorded, if anywhere.
+    pub audit: Option<AuditLog>,
+    /// How long an operation must run for to raise a desktop
+    /// notification when it finishes; `None` raises none.
+    pub notify_after: Option<Duration>,
+    /// Backends the daemon can create remotes of; empty until
+    /// fetched.
+    pub providers: Vec<Provider>,
     /// Currently selected remote.
     pub current_remote: Option<String>,
     /// Current path within the remote.
     pub current_path: String,
     /// Files and directories in current path.
     pub files: Vec<FileItem>,
+    /// Expanded subdirectories, while the files panel is a tree.
+    pub tree: Tree,
+    /// Every file below the current directory, while the files panel
+    /// lists them recursively.
+    pub flat: Option<FlatListing>,
+    /// Names of the marked entries in the current directory.
+    pub marked: BTreeSet<String>,
+    /// Entry to select once the listing in flight arrives, instead of
+    /// the first.
+    pub select_on_load: Option<String>,
+    /// Panel to focus once the listing in flight arrives, instead of
+    /// the files panel.
+    pub focus_on_load: Option<Panel>,
+    /// Entries yanked or cut, waiting to be put.
+    pub yanked: Option<Yank>,
+    /// Size and age limits on listings, disk usage and compares.
+    pub filter: Filter,
+    /// Columns of the files panel, from the config's `[file_list]`.
+    pub file_list: FileListConfig,
+    /// Segments of the status bar, from the config's `[status_bar]`.
+    pub status_bar: StatusBarConfig,
+    /// How destructive operations are confirmed, from the config's
+    /// `[confirm]`.
+    pub confirm: ConfirmConfig,
+    /// Whether the files panel shows names only.
+    pub compact: bool,
+    /// Whether the files panel shows exact bytes and ISO 8601 times
+    /// instead of `1.5 MiB` and `3h ago`.
+    pub exact: bool,
     /// Selected index in remotes list.
     pub remotes_selected: usize,
     /// Selected index in files list.
     pub files_selected: usize,
+    /// Index of the first visible row in the remotes list.
+    pub remotes_offset: usize,
+    /// Index of the first visible row in the files list.
+    pub files_offset: usize,
+    /// Number of list rows visible in a panel. The launcher updates
+    /// it from the terminal size before each frame.
+    pub viewport_height: usize,
+    /// Terminal area the last frame was laid out in, used to map
+    /// mouse positions back to panels and rows.
+    pub screen: Rect,
+    /// Time and position of the last left click, for detecting
+    /// double clicks.
+    pub last_click: Option<(Instant, u16, u16)>,
+    /// Active key bindings.
+    pub keymap: Keymap,
+    /// Colors used to draw the UI.
+    pub theme: Theme,
+    /// Glyphs drawn before remote and file names.
+    pub icons: IconSet,
+    /// Where the colors of file names come from.
+    pub file_colors: FileColors,
+    /// Clipboard that `y` and `Y` copy paths into.
+    pub clipboard: Clipboard,
+    /// Toasts waiting to time out.
+    pub notifications: Notifications,
+    /// Captured log lines shown in the log pane.
+    pub logs: LogBuffer,
+    /// Switches logging to debug and back; None when the caller set
+    /// up logging itself.
+    pub verbosity: Option<Verbosity>,
+    /// Whether the log pane is shown.
+    pub logs_visible: bool,
+    /// Whether the RC call overlay is shown.
+    pub calls_visible: bool,
+    /// Selected line in the log pane.
+    pub logs_selected: usize,
+    /// Index of the first visible line in the log pane.
+    pub logs_offset: usize,
+    /// Number of lines visible in the log pane.
+    pub logs_viewport_height: usize,
+    /// Whether the log pane sticks to the newest line.
+    pub logs_follow: bool,
+    /// Log lines pushed as of the last frame.
+    pub logs_seen: u64,
+    /// Recent directory listings.
+    pub listing_cache: ListingCache,
+    /// The directory under the cursor, to be prefetched.
+    pub prefetch: Prefetch,
+    /// Changes to files waiting for the daemon to come back.
+    pub queued: Vec<Queued>,
+    /// Whether LazyFile started the daemon, so it stops on quit.
+    pub owns_daemon: bool,
+    /// The daemon started from the setup dialog. It is restarted if
+    /// it dies, and stopped on quit unless `keep_daemon` is set.
+    pub daemon: Option<Daemon>,
+    /// Whether a daemon LazyFile starts keeps running after it exits.
+    pub keep_daemon: bool,
+    /// Whether state changed since the last frame was drawn.
+    pub dirty: bool,
+    /// When the last frame was drawn.
+    pub drawn_at: Instant,
+    /// Keys pressed so far that form a prefix of a multi-key
+    /// binding such as `g g`.
+    pub pending_keys: Vec<KeyPress>,
     /// Currently focused panel.
     pub focused_panel: Panel,
     /// Whether the app should continue running.
@@ -58,154 +202,134 @@ pub struct App {
     pub modal: Option<ActiveModal>,
     /// Whether the rclone daemon is connected.
     pub connected: bool,
+    /// Schedule and outcome of the daemon health pings.
+    pub health: Health,
+    /// Daemon jobs and transfer speed for the status bar.
+    pub activity: Activity,
+    /// Directory listing in flight, if any.
+    pub loading: Option<Loading>,
+    /// Downloaded file the launcher should open in the editor before
+    /// the next frame.
+    pub pending_edit: Option<EditSession>,
+    /// User command the launcher should run with the TUI suspended
+    /// before the next frame.
+    pub pending_command: Option<ShellCommand>,
+    /// Directory holding local copies of edited files, created on
+    /// first use.
+    pub(super) scratch: Option<Scratch>,
+    /// Identifier of the most recent listing request.
+    pub(super) next_request_id: u64,
+    /// Sender cloned into background tasks.
+    pub(super) events_tx: UnboundedSender<AppEvent>,
+    /// Pool the daemon calls run on.
+    pub(super) workers: Workers,
+    /// Results from background tasks, drained by the event loop.
+    pub(super) events_rx: UnboundedReceiver<AppEvent>,
 }
 
 impl App {
     /// Create a new App instance.
     pub fn new(client: RcloneClient) -> Self {
+        let (events_tx, events_rx) = mpsc::unbounded_channel();
         Self {
             client,
+            profiles: BTreeMap::new(),
+            scripts: BTreeMap::new(),
+            commands: BTreeMap::new(),
+            conflict_policy: ConflictPolicy::Ask,
+            profile: None,
             remotes: Vec::new(),
+            remote_types: HashMap::new(),
+            quotas: HashMap::new(),
+            quota: QuotaConfig::default(),
+            remote_layout: RemoteLayout::default(),
+            sidebar: Sidebar::default(),
+            zoomed: false,
+            config_path: None,
+            state: State::default(),
+            state_path: None,
+            history: None,
+            audit: None,
+            notify_after: None,
+            providers: Vec::new(),
             current_remote: None,
             current_path: String::new(),
             files: Vec::new(),
+            tree: Tree::default(),
+            flat: None,
+            marked: BTreeSet::new(),
+            select_on_load: None,
+            focus_on_load: None,
+            yanked: None,
+            filter: Filter::default(),
+            file_list: FileListConfig::default(),
+            status_bar: StatusBarConfig::default(),
+            confirm: ConfirmConfig::default(),
+            compact: false,
+            exact: false,
             remotes_selected: 0,
             files_selected: 0,
+            remotes_offset: 0,
+            files_offset: 0,
+            viewport_height: 1,
+            screen: Rect::default(),
+            last_click: None,
+            keymap: Keymap::default(),
+            theme: Theme::default(),
+            icons: IconSet::default(),
+            file_colors: FileColors::default(),
+            clipboard: Clipboard::new(),
+            notifications: Notifications::new(),
+            logs: LogBuffer::default(),
+            verbosity: None,
+            logs_visible: false,
+            calls_visible: false,
+            logs_selected: 0,
+            logs_offset: 0,
+            logs_viewport_height: 1,
+            logs_follow: true,
+            logs_seen: 0,
+            listing_cache: ListingCache::default(),
+            prefetch: Prefetch::new(Instant::now()),
+            queued: Vec::new(),
+            owns_daemon: false,
+            daemon: None,
+            keep_daemon: false,
+            dirty: true,
+            drawn_at: Instant::now(),
+            pending_keys: Vec::new(),
             focused_panel: Panel::Remotes,
             running: true,
             modal: None,
             connected: true,
+            health: Health::new(Instant::now()),
+            activity: Activity::new(Instant::now()),
+            loading: None,
+            pending_edit: None,
+            pending_command: None,
+            scratch: None,
+            next_request_id: 0,
+            workers: Workers::new(events_tx.clone()),
+            events_tx,
+            events_rx,
         }
     }
 
-    /// Returns the create/edit remote modal if it is the open modal.
-    pub fn create_remote_modal(&self) -> Option<&CreateRemoteModal> {
-        match self.modal {
-            Some(ActiveModal::CreateRemote(ref modal)) => Some(modal),
-            _ => None,
-        }
-    }
-
-    /// Returns the delete confirmation modal if it is the open modal.
-    pub fn confirm_modal(&self) -> Option<&ConfirmModal> {
-        match self.modal {
-            Some(ActiveModal::ConfirmDeleteRemote { ref modal, .. }) => Some(modal),
-            _ => None,
-        }
-    }
-
-    /// Returns the file operations modal if it is the open modal.
-    pub fn file_operations_modal(&self) -> Option<&FileOperationsModal> {
-        match self.modal {
-            Some(ActiveModal::FileOperation(ref modal)) => Some(modal),
-            _ => None,
-        }
-    }
-
-    /// Returns the remote pending deletion while confirmation is open.
-    pub fn pending_delete_remote(&self) -> Option<&str> {
-        match self.modal {
-            Some(ActiveModal::ConfirmDeleteRemote { ref remote, .. }) => Some(remote),
-            _ => None,
-        }
-    }
-
-    /// Loads remotes from rclone daemon.
-    ///
-    /// # Errors
-    /// Returns error if rclone daemon is unreachable.
-    pub async fn load_remotes(&mut self) -> Result<()> {
-        debug!("loading remotes");
-        match self.client.list_remotes().await {
-            Ok(remotes) => {
-                self.remotes = remotes;
-                self.remotes_selected = 0;
-                self.connected = true;
-                info!(count = self.remotes.len(), "loaded remotes");
-                Ok(())
-            }
-            Err(e) => {
-                self.connected = false;
-                Err(e)
-            }
-        }
+    /// Returns the path within the current remote of the selected
+    /// file or directory.
+    pub fn selected_path(&self) -> Option<String> {
+        let item = self.files.get(self.files_selected)?;
+        Some(self.child_path(item.name()))
     }
 
-    /// Loads files from current remote and path.
-    ///
-    /// # Errors
-    /// Returns error if rclone daemon is unreachable.
-    pub async fn load_files(&mut self) -> Result<()> {
-        if let Some(ref remote) = self.current_remote {
-            debug!(
-                remote = %remote,
-                path = %self.current_path,
-                "loading files"
-            );
-            match self.client.list_files(remote, &self.current_path).await {
-                Ok(files) => {
-                    self.files = files;
-                    self.connected = true;
-                    info!(count = self.files.len(), "loaded files");
-                }
-                Err(e) => {
-                    self.connected = false;
-                    return Err(e);
-                }
-            }
+    /// Returns the path within the current remote of the entry
+    /// `name` in the current directory.
+    pub fn child_path(&self, name: &str) -> String {
+        if self.current_path.is_empty() {
+            name.to_string()
+        } else {
+            format!("{}/{}", self.current_path, name)
         }
-        self.files_selected = 0;
-        Ok(())
-    }
-
-    /// Move selection down in focused panel.
-    pub fn navigate_down(&mut self) {
-        match self.focused_panel {
-            Panel::Remotes => {
-                if self.remotes_selected < self.remotes.len().saturating_sub(1) {
-                    self.remotes_selected += 1;
-                    debug!("Navigate down in remotes: {}", self.remotes_selected);
-                }
-            }
-            Panel::Files => {
-                if self.files_selected < self.files.len().saturating_sub(1) {
-                    self.files_selected += 1;
-                    debug!("Navigate down in files: {}", self.files_selected);
-                }
-            }
-        }
-    }
-
-    /// Move selection up in focused panel.
-    pub fn navigate_up(&mut self) {
-        match self.focused_panel {
-            Panel::Remotes => {
-                if self.remotes_selected > 0 {
-                    self.remotes_selected -= 1;
-                    debug!("Navigate up in remotes: {}", self.remotes_selected);
-                }
-            }
-            Panel::Files => {
-                if self.files_selected > 0 {
-                    self.files_selected -= 1;
-                    debug!("Navigate up in files: {}", self.files_selected);
-                }
-            }
-        }
-    }
-
-    /// Switch focus between remotes and files panels.
-    pub fn switch_panel(&mut self) {
-        self.focused_panel = match self.focused_panel {
-            Panel::Remotes => {
-                debug!("Switching focus to Files");
-                Panel::Files
-            }
-            Panel::Files => {
-                debug!("Switching focus to Remotes");
-                Panel::Remotes
-            }
-        };
     }
 }
 
@@ -233,109 +357,6 @@ mod tests {
         assert!(app.modal.is_none());
     }
 
-    #[test]
-    fn test_navigate_down_remotes() {
-        let client = create_test_client();
-        let mut app = App::new(client);
-        app.remotes = vec!["remote1".to_string(), "remote2".to_string()];
-        app.focused_panel = Panel::Remotes;
-
-        app.navigate_down();
-        assert_eq!(app.remotes_selected, 1);
-
-        app.navigate_down();
-        assert_eq!(app.remotes_selected, 1); // stays at max
-    }
-
-    #[test]
-    fn test_navigate_down_files() {
-        let client = create_test_client();
-        let mut app = App::new(client);
-        app.files = vec![
-            FileItem {
-                name: "file1".to_string(),
-                size: 100,
-                mod_time: "".to_string(),
-                is_dir: false,
-            },
-            FileItem {
-                name: "file2".to_string(),
-                size: 200,
-                mod_time: "".to_string(),
-                is_dir: false,
-            },
-        ];
-        app.focused_panel = Panel::Files;
-
-        app.navigate_down();
-        assert_eq!(app.files_selected, 1);
-    }
-
-    #[test]
-    fn test_navigate_up_remotes() {
-        let client = create_test_client();
-        let mut app = App::new(client);
-        app.remotes = vec!["remote1".to_string(), "remote2".to_string()];
-        app.remotes_selected = 1;
-        app.focused_panel = Panel::Remotes;
-
-        app.navigate_up();
-        assert_eq!(app.remotes_selected, 0);
-
-        app.navigate_up();
-        assert_eq!(app.remotes_selected, 0); // stays at min
-    }
-
-    #[test]
-    fn test_navigate_up_files() {
-        let client = create_test_client();
-        let mut app = App::new(client);
-        app.files = vec![FileItem {
-            name: "file1".to_string(),
-            size: 100,
-            mod_time: "".to_string(),
-            is_dir: false,
-        }];
-        app.files_selected = 1;
-        app.focused_panel = Panel::Files;
-
-        app.navigate_up();
-        assert_eq!(app.files_selected, 0);
-    }
-
-    #[test]
-    fn test_switch_panel_to_files() {
-        let client = create_test_client();
-        let mut app = App::new(client);
-        assert_eq!(app.focused_panel, Panel::Remotes);
-
-        app.switch_panel();
-        assert_eq!(app.focused_panel, Panel::Files);
-    }
-
-    #[test]
-    fn test_switch_panel_to_remotes() {
-        let client = create_test_client();
-        let mut app = App::new(client);
-        app.focused_panel = Panel::Files;
-
-        app.switch_panel();
-        assert_eq!(app.focused_panel, Panel::Remotes);
-    }
-
-    #[test]
-    fn test_switch_panel_multiple_times() {
-        let client = create_test_client();
-        let mut app = App::new(client);
-
-        for _ in 0..4 {
-            assert_eq!(app.focused_panel, Panel::Remotes);
-            app.switch_panel();
-            assert_eq!(app.focused_panel, Panel::Files);
-            app.switch_panel();
-        }
-    }
-
     #[test]
     fn test_panel_equality() {
         assert_eq!(Panel::Remotes, Panel::Remotes);
@@ -346,7 +367,7 @@ mod tests {
     #[test]
     fn test_panel_clone() {
         let panel = Panel::Remotes;
-        let cloned = panel.clone();
+        let cloned = Clone::clone(&panel);
         assert_eq!(panel, cloned);
     }
 
@@ -357,139 +378,6 @@ mod tests {
         assert_eq!(panel, copied);
     }
 
-    #[test]
-    fn test_navigate_down_empty_remotes() {
-        let client = create_test_client();
-        let mut app = App::new(client);
-        app.remotes = vec![];
-        app.focused_panel = Panel::Remotes;
-
-        app.navigate_down();
-        assert_eq!(app.remotes_selected, 0);
-    }
-
-    #[test]
-    fn test_navigate_up_empty_remotes() {
-        let client = create_test_client();
-        let mut app = App::new(client);
-        app.remotes = vec![];
-        app.focused_panel = Panel::Remotes;
-
-        app.navigate_up();
-        assert_eq!(app.remotes_selected, 0);
-    }
-
-    #[test]
-    fn test_navigate_down_empty_files() {
-        let client = create_test_client();
-        let mut app = App::new(client);
-        app.files = vec![];
-        app.focused_panel = Panel::Files;
-
-        app.navigate_down();
-        assert_eq!(app.files_selected, 0);
-    }
-
-    #[test]
-    fn test_navigate_up_empty_files() {
-        let client = create_test_client();
-        let mut app = App::new(client);
-        app.files = vec![];
-        app.focused_panel = Panel::Files;
-
-        app.navigate_up();
-        assert_eq!(app.files_selected, 0);
-    }
-
-    #[test]
-    fn test_navigate_down_single_item() {
-        let client = create_test_client();
-        let mut app = App::new(client);
-        app.remotes = vec!["single".to_string()];
-        app.focused_panel = Panel::Remotes;
-
-        app.navigate_down();
-        assert_eq!(app.remotes_selected, 0);
-    }
-
-    #[test]
-    fn test_navigate_up_single_item() {
-        let client = create_test_client();
-        let mut app = App::new(client);
-        app.remotes = vec!["single".to_string()];
-        app.focused_panel = Panel::Remotes;
-
-        app.navigate_up();
-        assert_eq!(app.remotes_selected, 0);
-    }
-
-    #[test]
-    fn test_navigate_down_boundary() {
-        let client = create_test_client();
-        let mut app = App::new(client);
-        app.remotes = vec!["r1".to_string(), "r2".to_string(), "r3".to_string()];
-        app.remotes_selected = 2;
-        app.focused_panel = Panel::Remotes;
-
-        app.navigate_down();
-        assert_eq!(app.remotes_selected, 2);
-    }
-
-    #[test]
-    fn test_navigate_up_boundary() {
-        let client = create_test_client();
-        let mut app = App::new(client);
-        app.remotes = vec!["r1".to_string(), "r2".to_string(), "r3".to_string()];
-        app.remotes_selected = 0;
-        app.focused_panel = Panel::Remotes;
-
-        app.navigate_up();
-        assert_eq!(app.remotes_selected, 0);
-    }
-
-    #[test]
-    fn test_navigate_many_items() {
-        let client = create_test_client();
-        let mut app = App::new(client);
-        app.remotes = (0..100).map(|i| format!("remote_{}", i)).collect();
-        app.focused_panel = Panel::Remotes;
-
-        for _ in 0..50 {
-            app.navigate_down();
-        }
-        assert_eq!(app.remotes_selected, 50);
-
-        for _ in 0..25 {
-            app.navigate_up();
-        }
-        assert_eq!(app.remotes_selected, 25);
-    }
-
-    #[test]
-    fn test_navigate_files_many_items() {
-        let client = create_test_client();
-        let mut app = App::new(client);
-        app.files = (0..100)
-            .map(|i| FileItem {
-                name: format!("file_{}.txt", i),
-                size: i * 100,
-                mod_time: "".to_string(),
-                is_dir: false,
-            })
-            .collect();
-        app.focused_panel = Panel::Files;
-
-        for _ in 0..150 {
-            app.navigate_down();
-        }
-        assert_eq!(app.files_selected, 99);
-
-        for _ in 0..150 {
-            app.navigate_up();
-        }
-        assert_eq!(app.files_selected, 0);
-    }
-
     #[test]
     fn test_app_running_flag() {
         let client = create_test_client();
@@ -572,6 +460,7 @@ mod tests {
             size: 100,
             mod_time: "".to_string(),
             is_dir: false,
+            ..Default::default()
         });
 
         assert_eq!(app.files.len(), 1);
@@ -592,39 +481,6 @@ mod tests {
         assert_eq!(app.files_selected, 0);
     }
 
-    #[test]
-    fn test_selection_persists_after_switch() {
-        let client = create_test_client();
-        let mut app = App::new(client);
-        app.remotes = vec!["r1".to_string(), "r2".to_string(), "r3".to_string()];
-        app.files = vec![
-            FileItem {
-                name: "f1".to_string(),
-                size: 0,
-                mod_time: "".to_string(),
-                is_dir: false,
-            },
-            FileItem {
-                name: "f2".to_string(),
-                size: 0,
-                mod_time: "".to_string(),
-                is_dir: false,
-            },
-        ];
-
-        app.focused_panel = Panel::Remotes;
-        app.navigate_down();
-        app.navigate_down();
-        assert_eq!(app.remotes_selected, 2);
-
-        app.switch_panel();
-        app.navigate_down();
-        assert_eq!(app.files_selected, 1);
-
-        app.switch_panel();
-        assert_eq!(app.remotes_selected, 2);
-    }
-
     #[test]
     fn test_file_item_size() {
         let file = FileItem {
@@ -632,6 +488,7 @@ mod tests {
             size: 1024 * 1024 * 1024,
             mod_time: "2024-01-01T00:00:00Z".to_string(),
             is_dir: false,
+            ..Default::default()
         };
 
         assert_eq!(file.size, 1024 * 1024 * 1024);
@@ -644,6 +501,7 @@ mod tests {
             size: -1,
             mod_time: "".to_string(),
             is_dir: false,
+            ..Default::default()
         };
 
         assert_eq!(file.size, -1);
@@ -656,6 +514,7 @@ mod tests {
             size: 0,
             mod_time: "2024-12-24T15:30:00Z".to_string(),
             is_dir: false,
+            ..Default::default()
         };
 
         assert_eq!(file.mod_time, "2024-12-24T15:30:00Z");
@@ -668,6 +527,7 @@ mod tests {
             size: 0,
             mod_time: "".to_string(),
             is_dir: true,
+            ..Default::default()
         };
 
         assert!(dir.is_dir());
@@ -681,6 +541,7 @@ mod tests {
             size: 1024,
             mod_time: "".to_string(),
             is_dir: false,
+            ..Default::default()
         };
 
         assert!(!file.is_dir());
@@ -694,6 +555,7 @@ mod tests {
             size: 0,
             mod_time: "".to_string(),
             is_dir: false,
+            ..Default::default()
         };
 
         assert_eq!(item.name(), "file with spaces & special chars!.txt");
@@ -706,6 +568,7 @@ mod tests {
             size: 0,
             mod_time: "".to_string(),
             is_dir: false,
+            ..Default::default()
         };
 
         assert_eq!(item.name(), "файл_日本語.txt");
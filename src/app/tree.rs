@@ -0,0 +1,328 @@
+//! Tree view of the files panel: subdirectories expand in place below
+//! their row, so deep hierarchies can be seen without leaving the
+//! directory.
+//!
+//! Rows of an expanded subdirectory are kept in [`App::files`] after
+//! it, named by their path relative to the current directory, e.g.
+//! `photos/2024/a.jpg`. Everything that works on the selected entry
+//! or the marks, such as [`App::selected_path`], thus reaches nested
+//! entries too. Subdirectories are listed when first expanded, from
+//! the listing cache if it has them.
+
+use super::event::AppEvent;
+use super::state::{App, Panel};
+use crate::error::Result;
+use crate::rclone::FileItem;
+use std::collections::BTreeMap;
+use std::time::Instant;
+use tracing::{debug, warn};
+
+/// Which subdirectories are expanded, and what they hold.
+#[derive(Debug, Clone, Default)]
+pub struct Tree {
+    /// Whether the files panel is a tree.
+    pub enabled: bool,
+    /// Entries of the expanded subdirectories, keyed by their path
+    /// relative to the current directory; `None` while listing.
+    expanded: BTreeMap<String, Option<Vec<FileItem>>>,
+}
+
+impl Tree {
+    /// Returns whether the subdirectory at relative path `path` is
+    /// expanded.
+    pub fn is_expanded(&self, path: &str) -> bool {
+        self.expanded.contains_key(path)
+    }
+
+    /// Returns the rows of the tree: `top`, the entries of the current
+    /// directory, each followed by those of its expanded
+    /// subdirectories.
+    fn rows(&self, top: Vec<FileItem>) -> Vec<FileItem> {
+        let mut rows = Vec::with_capacity(top.len());
+        for item in top {
+            self.push_row(item, &mut rows);
+        }
+        rows
+    }
+
+    fn push_row(&self, item: FileItem, rows: &mut Vec<FileItem>) {
+        let children = match self.expanded.get(item.name()) {
+            Some(Some(children)) if item.is_dir() => children.as_slice(),
+            _ => &[],
+        };
+        let prefix = item.name().to_string();
+        rows.push(item);
+        for child in children {
+            let nested = FileItem {
+                name: format!("{}/{}", prefix, child.name()),
+                ..child.clone()
+            };
+            self.push_row(nested, rows);
+        }
+    }
+}
+
+/// Returns the last segment of a row's name: the name of the entry
+/// itself.
+pub fn leaf_name(name: &str) -> &str {
+    name.rsplit('/').next().unwrap_or(name)
+}
+
+/// Returns how many directories deep below the current one a row is.
+pub fn depth(name: &str) -> usize {
+    name.matches('/').count()
+}
+
+impl App {
+    /// Switches the files panel between a flat listing and a tree.
+    /// Leaving the tree collapses it, keeping the selection on the
+    /// top-level entry holding the selected one. The recursive listing
+    /// is left for the directory's own.
+    pub fn toggle_tree(&mut self) {
+        self.tree.enabled = !self.tree.enabled;
+        debug!(tree = self.tree.enabled, "toggled tree view");
+        if self.tree.enabled && self.flat.is_some() {
+            self.toggle_flat();
+        }
+        if !self.tree.enabled {
+            self.tree.expanded.clear();
+            self.rebuild_tree();
+        }
+    }
+
+    /// Expands the selected subdirectory, listing it if needed.
+    pub fn expand_selected(&mut self) {
+        if !self.tree.enabled || self.focused_panel != Panel::Files {
+            return;
+        }
+        let Some(item) = self.files.get(self.files_selected) else {
+            return;
+        };
+        if !item.is_dir() || self.tree.is_expanded(item.name()) {
+            return;
+        }
+        let path = item.name().to_string();
+        self.tree.expanded.insert(path.clone(), None);
+        self.request_tree_dir(path);
+    }
+
+    /// Collapses the selected subdirectory or, if it is not expanded,
+    /// the one holding it, selecting that instead.
+    pub fn collapse_selected(&mut self) {
+        if !self.tree.enabled || self.focused_panel != Panel::Files {
+            return;
+        }
+        let Some(item) = self.files.get(self.files_selected) else {
+            return;
+        };
+        let path = if self.tree.is_expanded(item.name()) {
+            item.name().to_string()
+        } else if let Some((parent, _)) = item.name().rsplit_once('/') {
+            parent.to_string()
+        } else {
+            return;
+        };
+        let below = format!("{}/", path);
+        self.tree
+            .expanded
+            .retain(|dir, _| *dir != path && !dir.starts_with(&below));
+        self.rebuild_tree();
+        if let Some(index) = self.files.iter().position(|item| item.name() == path) {
+            self.files_selected = index;
+            self.keep_selection_visible();
+        }
+    }
+
+    /// Lists the subdirectory at relative path `path` on a background
+    /// task, from the cache if it has it. The listing arrives as
+    /// [`AppEvent::TreeListed`].
+    fn request_tree_dir(&self, path: String) {
+        let Some(ref remote) = self.current_remote else {
+            return;
+        };
+        let full = self.child_path(&path);
+        debug!(remote = %remote, path = %full, "expanding directory");
+        let cached = if self.offline() {
+            self.listing_cache.get_stale(remote, &full)
+        } else {
+            self.listing_cache.get(remote, &full, Instant::now())
+        }
+        .map(<[FileItem]>::to_vec);
+        let client = self.client.clone();
+        let filter = self.filter.clone();
+        let hash = self.hash_column().map(str::to_string);
+        let generation = self.listing_cache.generation();
+        let (remote, dir) = (remote.clone(), self.current_path.clone());
+        self.workers.spawn(async move {
+            let (cached, result) = match cached {
+                Some(files) => (true, Ok(files)),
+                None => {
+                    let result = client
+                        .list_dir(&remote, &full, hash.as_deref(), &filter)
+                        .await;
+                    (false, result)
+                }
+            };
+            AppEvent::TreeListed {
+                remote,
+                dir,
+                path,
+                cached,
+                generation,
+                result,
+            }
+        });
+    }
+
+    /// Shows the entries of an expanded subdirectory below it, unless
+    /// the panel moved to another directory or the subdirectory was
+    /// collapsed meanwhile. A failed listing collapses it again.
+    pub fn apply_tree_listing(
+        &mut self,
+        remote: String,
+        dir: String,
+        path: String,
+        cached: bool,
+        generation: u64,
+        result: Result<Vec<FileItem>>,
+    ) {
+        if self.current_remote.as_deref() != Some(remote.as_str())
+            || self.current_path != dir
+            || !self.tree.is_expanded(&path)
+        {
+            debug!(path = %path, "dropping outdated tree listing");
+            return;
+        }
+        match result {
+            Ok(files) => {
+                if !cached && generation == self.listing_cache.generation() {
+                    let full = self.child_path(&path);
+                    self.listing_cache
+                        .insert(remote, full, files.clone(), Instant::now());
+                }
+                self.tree.expanded.insert(path, Some(files));
+            }
+            Err(e) => {
+                warn!(path = %path, error = %e, "could not expand directory");
+                self.notifications
+                    .error(format!("Could not list {}: {}", path, e));
+                self.tree.expanded.remove(&path);
+            }
+        }
+        self.rebuild_tree();
+    }
+
+    /// Lays out a listing of the current directory that just arrived.
+    /// The same directory listed again keeps its expanded
+    /// subdirectories, which are listed again too; any other one
+    /// starts collapsed.
+    pub(super) fn apply_tree_top(&mut self, same_dir: bool) {
+        if !self.tree.enabled {
+            return;
+        }
+        if !same_dir {
+            self.tree.expanded.clear();
+            return;
+        }
+        let paths: Vec<String> = self.tree.expanded.keys().cloned().collect();
+        for path in paths {
+            self.request_tree_dir(path);
+        }
+        let top = std::mem::take(&mut self.files);
+        self.files = self.tree.rows(top);
+    }
+
+    /// Rebuilds the rows from the top-level entries, keeping the
+    /// selection on the same entry or, if it is hidden now, the
+    /// nearest one holding it.
+    fn rebuild_tree(&mut self) {
+        let selected = self
+            .files
+            .get(self.files_selected)
+            .map(|item| item.name().to_string());
+        let top: Vec<FileItem> = std::mem::take(&mut self.files)
+            .into_iter()
+            .filter(|item| depth(item.name()) == 0)
+            .collect();
+        self.files = self.tree.rows(top);
+        self.retain_marks(true);
+        let mut name = selected.as_deref();
+        while let Some(current) = name {
+            if let Some(index) = self.files.iter().position(|item| item.name() == current) {
+                self.files_selected = index;
+                break;
+            }
+            name = current.rsplit_once('/').map(|(parent, _)| parent);
+        }
+        self.files_selected = self.files_selected.min(self.files.len().saturating_sub(1));
+        self.keep_selection_visible();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::handler::test_support::unreachable_app;
+
+    fn item(name: &str, is_dir: bool) -> FileItem {
+        FileItem {
+            name: name.to_string(),
+            is_dir,
+            ..FileItem::default()
+        }
+    }
+
+    fn names(app: &App) -> Vec<&str> {
+        app.files.iter().map(FileItem::name).collect()
+    }
+
+    #[tokio::test]
+    async fn test_expands_and_collapses_in_place() {
+        let mut app = unreachable_app();
+        app.focused_panel = Panel::Files;
+        app.current_remote = Some("s3".to_string());
+        app.current_path = "docs".to_string();
+        app.files = vec![item("2024", true), item("a.txt", false)];
+        app.toggle_tree();
+
+        app.expand_selected();
+        let Some(AppEvent::TreeListed {
+            remote,
+            dir,
+            path,
+            generation,
+            ..
+        }) = app.wait_event().await
+        else {
+            panic!("no tree listing");
+        };
+        assert_eq!((dir.as_str(), path.as_str()), ("docs", "2024"));
+        let children = vec![item("q1", true), item("b.txt", false)];
+        app.apply_tree_listing(remote, dir, path, false, generation, Ok(children));
+        assert_eq!(names(&app), ["2024", "2024/q1", "2024/b.txt", "a.txt"]);
+        assert!(
+            app.listing_cache
+                .get("s3", "docs/2024", Instant::now())
+                .is_some()
+        );
+
+        app.files_selected = 2;
+        assert_eq!(app.selected_path().as_deref(), Some("docs/2024/b.txt"));
+        app.collapse_selected();
+        assert_eq!(names(&app), ["2024", "a.txt"]);
+        assert_eq!(app.files_selected, 0);
+
+        app.expand_selected();
+        app.toggle_tree();
+        assert_eq!(names(&app), ["2024", "a.txt"]);
+        assert!(!app.tree.is_expanded("2024"));
+    }
+
+    #[test]
+    fn test_names_give_depth_and_leaf() {
+        assert_eq!(depth("a.txt"), 0);
+        assert_eq!(depth("2024/q1/b.txt"), 2);
+        assert_eq!(leaf_name("2024/q1/b.txt"), "b.txt");
+        assert_eq!(leaf_name("a.txt"), "a.txt");
+    }
+}
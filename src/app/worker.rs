@@ -0,0 +1,147 @@
+//! Pool of background tasks that make the daemon calls.
+//!
+//! Every call to the daemon runs here rather than in the event loop,
+//! so a slow or unreachable daemon can delay results but never the
+//! next frame. Each request resolves to one [`AppEvent`], which the
+//! event loop applies once it arrives.
+
+use super::event::AppEvent;
+use std::future::Future;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use tokio::sync::Semaphore;
+use tokio::sync::mpsc::UnboundedSender;
+use tokio::task::JoinHandle;
+
+/// Requests that may talk to the daemon at once; later ones wait for
+/// a free worker.
+pub const WORKERS: usize = 4;
+
+/// Handle to the pool, cloned into everything that starts requests.
+#[derive(Debug, Clone)]
+pub struct Workers {
+    permits: Arc<Semaphore>,
+    busy: Arc<AtomicUsize>,
+    transfers: Arc<AtomicUsize>,
+    events: UnboundedSender<AppEvent>,
+}
+
+/// Counts a request as busy from when it is queued until it finishes
+/// or is aborted.
+struct Busy(Arc<AtomicUsize>);
+
+impl Busy {
+    fn new(busy: &Arc<AtomicUsize>) -> Self {
+        busy.fetch_add(1, Ordering::SeqCst);
+        Self(Arc::clone(busy))
+    }
+}
+
+impl Drop for Busy {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+impl Workers {
+    /// Creates a pool that sends results to `events`.
+    pub fn new(events: UnboundedSender<AppEvent>) -> Self {
+        Self {
+            permits: Arc::new(Semaphore::new(WORKERS)),
+            busy: Arc::new(AtomicUsize::new(0)),
+            transfers: Arc::new(AtomicUsize::new(0)),
+            events,
+        }
+    }
+
+    /// Runs `request` once a worker is free and sends the event it
+    /// resolves to. Aborting the returned handle drops the request.
+    pub fn spawn<F>(&self, request: F) -> JoinHandle<()>
+    where
+        F: Future<Output = AppEvent> + Send + 'static,
+    {
+        self.spawn_counted(request, None)
+    }
+
+    /// Like [`Self::spawn`], for a request that changes files; it
+    /// also counts towards [`Self::transfers`] until it finishes.
+    pub fn spawn_transfer<F>(&self, request: F) -> JoinHandle<()>
+    where
+        F: Future<Output = AppEvent> + Send + 'static,
+    {
+        self.spawn_counted(request, Some(Busy::new(&self.transfers)))
+    }
+
+    fn spawn_counted<F>(&self, request: F, transfer: Option<Busy>) -> JoinHandle<()>
+    where
+        F: Future<Output = AppEvent> + Send + 'static,
+    {
+        let busy = Busy::new(&self.busy);
+        let permits = Arc::clone(&self.permits);
+        let events = self.events.clone();
+        tokio::spawn(async move {
+            let _busy = (busy, transfer);
+            // The semaphore is never closed, so this always succeeds.
+            let Ok(_permit) = permits.acquire_owned().await else {
+                return;
+            };
+            let event = request.await;
+            // The receiver lives as long as the app, so a failed send
+            // only happens during shutdown.
+            let _ = events.send(event);
+        })
+    }
+
+    /// Returns the number of requests queued or running.
+    pub fn busy(&self) -> usize {
+        self.busy.load(Ordering::SeqCst)
+    }
+
+    /// Returns the number of requests changing files that are queued
+    /// or running.
+    pub fn transfers(&self) -> usize {
+        self.transfers.load(Ordering::SeqCst)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::sync::{mpsc, oneshot};
+
+    #[tokio::test]
+    async fn test_requests_beyond_pool_wait() {
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let workers = Workers::new(tx);
+        let mut gates = Vec::new();
+        for _ in 0..WORKERS {
+            let (open, gate) = oneshot::channel::<()>();
+            gates.push(open);
+            workers.spawn(async move {
+                let _ = gate.await;
+                AppEvent::JobsLoaded(Ok(Vec::new()))
+            });
+        }
+        workers.spawn(async { AppEvent::MountsLoaded(Ok(Vec::new())) });
+        assert_eq!(workers.busy(), WORKERS + 1);
+
+        tokio::task::yield_now().await;
+        assert!(rx.try_recv().is_err());
+
+        let _ = gates.pop().unwrap().send(());
+        assert!(matches!(rx.recv().await, Some(AppEvent::JobsLoaded(_))));
+        assert!(matches!(rx.recv().await, Some(AppEvent::MountsLoaded(_))));
+    }
+
+    #[tokio::test]
+    async fn test_aborted_request_is_not_busy() {
+        let (tx, _rx) = mpsc::unbounded_channel();
+        let workers = Workers::new(tx);
+        let task = workers.spawn(std::future::pending());
+        assert_eq!(workers.busy(), 1);
+
+        task.abort();
+        let _ = task.await;
+        assert_eq!(workers.busy(), 0);
+    }
+}
@@ -0,0 +1,101 @@
+//! The internal file clipboard: entries yanked or cut in one
+//! directory and put into another, on the same remote or a different
+//! one.
+//!
+//! Unlike marks, the clipboard outlives the directory it was filled
+//! in, which is what makes it useful for moving files around.
+
+use super::state::App;
+use crate::rclone::FileItem;
+use tracing::info;
+
+/// Entries waiting to be put.
+#[derive(Debug, Clone)]
+pub struct Yank {
+    /// Remote the entries are on.
+    pub remote: String,
+    /// Directory holding them, within the remote.
+    pub dir: String,
+    /// The entries, in listing order.
+    pub entries: Vec<FileItem>,
+    /// Whether putting moves them rather than copying.
+    pub cut: bool,
+}
+
+impl Yank {
+    /// Returns the path within [`Self::remote`] of the entry `name`.
+    pub fn source(&self, name: &str) -> String {
+        join_path(&self.dir, name)
+    }
+
+    /// Returns `'name'` for a single entry, else `N entries`.
+    pub fn describe(&self) -> String {
+        match self.entries.as_slice() {
+            [item] => format!("'{}'", item.name()),
+            entries => format!("{} entries", entries.len()),
+        }
+    }
+}
+
+impl App {
+    /// Fills the clipboard with the marked entries, or the selected
+    /// one, to be copied (or moved, if `cut`) by the next put. The
+    /// marks are cleared. Returns whether anything was yanked.
+    pub fn yank(&mut self, cut: bool) -> bool {
+        let Some(ref remote) = self.current_remote else {
+            return false;
+        };
+        let entries: Vec<FileItem> = self.selection().into_iter().cloned().collect();
+        if entries.is_empty() {
+            return false;
+        }
+        info!(count = entries.len(), cut, "yanked entries");
+        self.yanked = Some(Yank {
+            remote: remote.clone(),
+            dir: self.current_path.clone(),
+            entries,
+            cut,
+        });
+        self.marked.clear();
+        true
+    }
+}
+
+/// Returns the path of the entry `name` in the directory `dir`.
+pub(crate) fn join_path(dir: &str, name: &str) -> String {
+    if dir.is_empty() {
+        name.to_string()
+    } else {
+        format!("{}/{}", dir, name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::handler::test_support::unreachable_app;
+
+    #[test]
+    fn test_yanks_marked_entries_and_clears_marks() {
+        let mut app = unreachable_app();
+        app.files = ["a.txt", "b.txt"]
+            .iter()
+            .map(|name| FileItem {
+                name: name.to_string(),
+                ..Default::default()
+            })
+            .collect();
+        assert!(!app.yank(false));
+
+        app.current_remote = Some("gdrive".to_string());
+        app.current_path = "docs".to_string();
+        app.marked.insert("b.txt".to_string());
+        assert!(app.yank(true));
+
+        let yank = app.yanked.as_ref().unwrap();
+        assert!(yank.cut);
+        assert_eq!(yank.describe(), "'b.txt'");
+        assert_eq!(yank.source("b.txt"), "docs/b.txt");
+        assert!(app.marked.is_empty());
+    }
+}
@@ -1,16 +1,391 @@
 //! Clap config
-use crate::config::{RCLONE_HOST, RCLONE_PORT};
-use clap::Parser;
+use crate::config::Config;
+use crate::error::{LazyFileError, Result};
+use crate::logs;
+use crate::rclone::ByteRange;
+use crate::ui::ThemePreset;
+use clap::builder::PossibleValuesParser;
+use clap::{Parser, Subcommand};
+use clap_complete::engine::{ArgValueCandidates, CompletionCandidate};
+use clap_complete::env::Shells;
+use std::fmt;
+use std::io::Write;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+/// Environment variable the completion scripts set when asking the
+/// binary for candidates.
+pub const COMPLETE_VAR: &str = "COMPLETE";
+
+/// Name the completion scripts invoke and register under.
+const BIN_NAME: &str = "lazyfile";
 
 /// LazyFile - TUI file manager for cloud storage via rclone.
 #[derive(Parser, Debug)]
 #[command(version, about, long_about = None)]
 pub struct Args {
-    /// rclone daemon host address (default: "localhost")
-    #[arg(long, default_value = RCLONE_HOST)]
-    pub host: String,
+    /// rclone daemon host address, overriding the profile's
+    /// (default: "localhost")
+    #[arg(long)]
+    pub host: Option<String>,
+
+    /// rclone daemon port, overriding the profile's (default: 5572)
+    #[arg(long)]
+    pub port: Option<u16>,
+
+    /// Unix socket of a daemon started with
+    /// `--rc-addr unix:///path.sock`, used instead of host and port
+    #[arg(long, value_name = "PATH", conflicts_with_all = ["host", "port"])]
+    pub socket: Option<String>,
+
+    /// Connect over HTTPS, for daemons started with `--rc-cert`
+    #[arg(long)]
+    pub tls: bool,
+
+    /// PEM file of the CA that signed the daemon's certificate, for
+    /// self-signed setups; implies `--tls`
+    #[arg(long, value_name = "PATH")]
+    pub ca_cert: Option<PathBuf>,
+
+    /// Skip verification of the daemon's certificate; implies `--tls`
+    #[arg(long)]
+    pub insecure: bool,
+
+    /// PEM client certificate for daemons that require mutual TLS,
+    /// optionally followed by its key; implies `--tls`
+    #[arg(long, value_name = "PATH")]
+    pub client_cert: Option<PathBuf>,
+
+    /// PEM private key of `--client-cert`, if kept in its own file
+    #[arg(long, value_name = "PATH", requires = "client_cert")]
+    pub client_key: Option<PathBuf>,
+
+    /// `--rc-user` of the daemon, overriding the profile's login
+    #[arg(long, env = "LAZYFILE_USER", conflicts_with = "bearer")]
+    pub user: Option<String>,
+
+    /// `--rc-pass` of the daemon. Prefer the environment variable:
+    /// the command line is visible to other users
+    #[arg(
+        long,
+        env = "LAZYFILE_PASS",
+        hide_env_values = true,
+        conflicts_with = "bearer"
+    )]
+    pub pass: Option<Secret>,
+
+    /// Token sent as `Authorization: Bearer`, for daemons behind an
+    /// authenticating proxy
+    #[arg(
+        long,
+        value_name = "TOKEN",
+        env = "LAZYFILE_BEARER",
+        hide_env_values = true
+    )]
+    pub bearer: Option<Secret>,
+
+    /// Connection profile from the config file, overriding its
+    /// `profile` entry
+    #[arg(long, add = ArgValueCandidates::new(profile_candidates))]
+    pub profile: Option<String>,
+
+    /// Config file to read instead of the default location
+    #[arg(long, value_name = "PATH")]
+    pub config: Option<PathBuf>,
+
+    /// Color theme, overriding the config file's `[theme] preset`
+    #[arg(long, value_parser = PossibleValuesParser::new(ThemePreset::ALL.map(ThemePreset::name)))]
+    pub theme: Option<String>,
+
+    /// Also write logs to this file, rotated at 10 MiB with three old
+    /// files kept
+    #[arg(long, value_name = "PATH")]
+    pub log_file: Option<PathBuf>,
+
+    /// Level of LazyFile's own logs in the log pane and `--log-file`,
+    /// overriding `RUST_LOG` (default: info)
+    #[arg(long, value_name = "LEVEL", value_parser = PossibleValuesParser::new(logs::LEVELS))]
+    pub log_level: Option<String>,
+
+    /// Start at the remotes list instead of where the last session
+    /// left off
+    #[arg(long)]
+    pub fresh: bool,
+
+    /// Leave a daemon LazyFile starts running when it exits, instead of
+    /// stopping it
+    #[arg(long)]
+    pub keep_daemon: bool,
+
+    /// Print subcommand results as JSON on stdout
+    #[arg(long, global = true)]
+    pub json: bool,
+
+    /// Runs one operation and exits instead of starting the TUI
+    #[command(subcommand)]
+    pub command: Option<Task>,
+}
+
+/// Everything that runs instead of the TUI.
+#[derive(Subcommand, Debug, Clone, PartialEq, Eq)]
+pub enum Task {
+    #[command(flatten)]
+    Remote(Command),
+    /// Print the shell completion script, e.g.
+    /// `source <(lazyfile completions bash)`
+    Completions {
+        /// Shell to complete for
+        #[arg(value_parser = PossibleValuesParser::new(Shells::builtins().names()))]
+        shell: String,
+    },
+}
+
+/// Operations run without the TUI, for scripts.
+#[derive(Subcommand, Debug, Clone, PartialEq, Eq)]
+pub enum Command {
+    /// List a directory, one entry per line; directories end in `/`
+    Ls {
+        /// Directory to list, as `remote:path`
+        path: RemotePath,
+    },
+    /// Copy a file. A destination ending in `/` keeps the file name
+    Cp {
+        /// File to copy, as `remote:path`
+        src: RemotePath,
+        /// Where to copy it, as `remote:path`
+        dst: RemotePath,
+    },
+    /// Move a file. A destination ending in `/` keeps the file name
+    Mv {
+        /// File to move, as `remote:path`
+        src: RemotePath,
+        /// Where to move it, as `remote:path`
+        dst: RemotePath,
+    },
+    /// Delete a file, or a directory with `-r`
+    Rm {
+        /// What to delete, as `remote:path`
+        path: RemotePath,
+        /// Delete a directory and everything in it
+        #[arg(short, long)]
+        recursive: bool,
+    },
+    /// Stream stdin into a file, e.g. `pg_dump db | lazyfile rcat
+    /// s3:backups/db.sql`
+    Rcat {
+        /// File to write, as `remote:path`
+        dst: RemotePath,
+    },
+    /// Stream a file to stdout, e.g. `lazyfile cat s3:logs.tar.gz | tar
+    /// tz`
+    Cat {
+        /// File to read, as `remote:path`
+        src: RemotePath,
+        /// Only bytes START to END, both included; to the end of the
+        /// file without END
+        #[arg(long, value_name = "START-END")]
+        range: Option<ByteRange>,
+    },
+    /// Save every remote's settings to a file: JSON for `.json`,
+    /// otherwise the layout of `rclone.conf`
+    Export {
+        /// File to write
+        file: PathBuf,
+    },
+    /// Create the remotes saved by `export`, or in an `rclone.conf`,
+    /// skipping names the daemon already has
+    Import {
+        /// File to read
+        file: PathBuf,
+    },
+}
+
+impl Command {
+    /// Returns the subcommand's name as typed.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Ls { .. } => "ls",
+            Self::Cp { .. } => "cp",
+            Self::Mv { .. } => "mv",
+            Self::Rm { .. } => "rm",
+            Self::Rcat { .. } => "rcat",
+            Self::Cat { .. } => "cat",
+            Self::Export { .. } => "export",
+            Self::Import { .. } => "import",
+        }
+    }
+}
+
+/// A `remote:path` argument, written the way the rclone CLI takes it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RemotePath {
+    /// Remote name, without the colon.
+    pub remote: String,
+    /// Path inside the remote; empty for its root.
+    pub path: String,
+}
+
+impl FromStr for RemotePath {
+    type Err = String;
+
+    fn from_str(value: &str) -> std::result::Result<Self, Self::Err> {
+        match value.split_once(':') {
+            Some((remote, path)) if !remote.is_empty() => Ok(Self {
+                remote: remote.to_string(),
+                path: path.trim_start_matches('/').to_string(),
+            }),
+            _ => Err(format!("expected remote:path, got '{}'", value)),
+        }
+    }
+}
+
+impl fmt::Display for RemotePath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.remote, self.path)
+    }
+}
+
+/// Writes the script that registers completions for `shell`. The
+/// script calls back into the binary with [`COMPLETE_VAR`] set, so
+/// candidates such as profile names are looked up as the user types.
+///
+/// # Errors
+/// Returns `InvalidInput` for a shell clap_complete does not know, or
+/// `Io` if `out` cannot be written.
+pub fn write_completions(shell: &str, out: &mut impl Write) -> Result<()> {
+    let shells = Shells::builtins();
+    let completer = shells.completer(shell).ok_or(LazyFileError::InvalidInput {
+        field: "shell",
+        reason: "not a supported shell",
+    })?;
+    completer.write_registration(COMPLETE_VAR, BIN_NAME, BIN_NAME, BIN_NAME, out)?;
+    Ok(())
+}
+
+/// Offers the profiles of the default config file. A broken or
+/// missing file offers nothing rather than failing the completion.
+fn profile_candidates() -> Vec<CompletionCandidate> {
+    Config::load_default()
+        .map(|config| {
+            config
+                .profiles
+                .into_keys()
+                .map(CompletionCandidate::new)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Flag value that is never printed, for passwords and tokens.
+#[derive(Clone, PartialEq, Eq)]
+pub struct Secret(pub String);
+
+impl From<String> for Secret {
+    fn from(value: String) -> Self {
+        Self(value)
+    }
+}
+
+impl fmt::Debug for Secret {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("<redacted>")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parses_remote_path() {
+        let parsed: RemotePath = "gdrive:/docs/a.txt".parse().unwrap();
+        assert_eq!(parsed.remote, "gdrive");
+        assert_eq!(parsed.path, "docs/a.txt");
+        assert_eq!(parsed.to_string(), "gdrive:docs/a.txt");
+
+        let root: RemotePath = "gdrive:".parse().unwrap();
+        assert_eq!(root.path, "");
+    }
+
+    #[test]
+    fn test_rejects_path_without_remote() {
+        assert!("docs/a.txt".parse::<RemotePath>().is_err());
+        assert!(":docs".parse::<RemotePath>().is_err());
+    }
+
+    #[test]
+    fn test_parses_subcommand_after_connection_flags() {
+        let args =
+            Args::try_parse_from(["lazyfile", "--profile", "nas", "rm", "-r", "s3:old"]).unwrap();
+        assert_eq!(args.profile.as_deref(), Some("nas"));
+        assert!(matches!(
+            args.command,
+            Some(Task::Remote(Command::Rm { ref path, recursive: true })) if path.remote == "s3"
+        ));
+    }
+
+    #[test]
+    fn test_parses_export_file() {
+        let args = Args::try_parse_from(["lazyfile", "export", "remotes.json"]).unwrap();
+        assert_eq!(
+            args.command,
+            Some(Task::Remote(Command::Export {
+                file: PathBuf::from("remotes.json")
+            }))
+        );
+    }
+
+    #[test]
+    fn test_parses_cat_range() {
+        let args = Args::try_parse_from(["lazyfile", "cat", "s3:a.log", "--range", "10-"]).unwrap();
+        let Some(Task::Remote(Command::Cat { src, range })) = args.command else {
+            panic!("not a cat command");
+        };
+        assert_eq!(src.to_string(), "s3:a.log");
+        assert_eq!(
+            range,
+            Some(ByteRange {
+                start: 10,
+                end: None
+            })
+        );
+        assert!(Args::try_parse_from(["lazyfile", "cat", "s3:a", "--range", "5-1"]).is_err());
+    }
+
+    #[test]
+    fn test_parses_completions_shell() {
+        let args = Args::try_parse_from(["lazyfile", "completions", "zsh"]).unwrap();
+        assert_eq!(
+            args.command,
+            Some(Task::Completions {
+                shell: "zsh".to_string()
+            })
+        );
+        assert!(Args::try_parse_from(["lazyfile", "completions", "cmd"]).is_err());
+    }
+
+    #[test]
+    fn test_completion_script_calls_back_into_binary() {
+        let mut out = Vec::new();
+        write_completions("bash", &mut out).unwrap();
+        let script = String::from_utf8(out).unwrap();
+        assert!(script.contains("COMPLETE=\"bash\""), "{}", script);
+        assert!(script.contains(BIN_NAME));
+    }
+
+    #[test]
+    fn test_json_flag_goes_after_subcommand() {
+        let args = Args::try_parse_from(["lazyfile", "ls", "gdrive:", "--json"]).unwrap();
+        assert!(args.json);
+    }
 
-    /// rclone daemon port (default: 5572)
-    #[arg(long, default_value_t = RCLONE_PORT)]
-    pub port: u16,
+    #[test]
+    fn test_no_subcommand_starts_tui() {
+        assert!(
+            Args::try_parse_from(["lazyfile"])
+                .unwrap()
+                .command
+                .is_none()
+        );
+    }
 }
@@ -0,0 +1,91 @@
+//! Copying text to the clipboard.
+//!
+//! The system clipboard is used when one is reachable. Over SSH or on
+//! a console there is none, so the text is sent to the terminal as an
+//! OSC 52 escape sequence instead, which most modern terminals turn
+//! into a clipboard write on the local machine.
+
+use crate::error::Result;
+use base64::Engine as _;
+use base64::engine::general_purpose::STANDARD;
+use std::fmt;
+use std::io::{self, Write};
+use tracing::debug;
+
+/// Where copied text ended up.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CopiedVia {
+    /// The operating system's clipboard.
+    System,
+    /// An OSC 52 sequence written to the terminal, which may ignore it.
+    Terminal,
+}
+
+/// Clipboard handle, connected on first use.
+///
+/// On X11 the copied text is served by the process that owns the
+/// clipboard, so the connection is kept for the app's lifetime rather
+/// than opened per copy.
+#[derive(Default)]
+pub struct Clipboard {
+    system: Option<arboard::Clipboard>,
+}
+
+impl fmt::Debug for Clipboard {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Clipboard")
+            .field("connected", &self.system.is_some())
+            .finish()
+    }
+}
+
+impl Clipboard {
+    /// Create a handle without connecting yet.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Copies `text`, falling back to OSC 52 when the system
+    /// clipboard is unavailable.
+    ///
+    /// # Errors
+    /// Returns error if the fallback cannot be written to the
+    /// terminal.
+    pub fn copy(&mut self, text: &str) -> Result<CopiedVia> {
+        match self.copy_system(text) {
+            Ok(()) => Ok(CopiedVia::System),
+            Err(e) => {
+                debug!(error = %e, "system clipboard unavailable, using OSC 52");
+                let mut out = io::stdout();
+                write_osc52(&mut out, text)?;
+                out.flush()?;
+                Ok(CopiedVia::Terminal)
+            }
+        }
+    }
+
+    fn copy_system(&mut self, text: &str) -> std::result::Result<(), arboard::Error> {
+        let clipboard = match self.system {
+            Some(ref mut clipboard) => clipboard,
+            None => self.system.insert(arboard::Clipboard::new()?),
+        };
+        clipboard.set_text(text)
+    }
+}
+
+/// Writes the OSC 52 sequence that sets the clipboard to `text`.
+pub fn write_osc52(out: &mut impl Write, text: &str) -> io::Result<()> {
+    write!(out, "\x1b]52;c;{}\x07", STANDARD.encode(text))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_osc52_encodes_text_as_base64() {
+        let mut out = Vec::new();
+        write_osc52(&mut out, "gdrive:a b.txt").unwrap();
+        assert_eq!(out, b"\x1b]52;c;Z2RyaXZlOmEgYi50eHQ=\x07");
+    }
+}
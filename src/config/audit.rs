@@ -0,0 +1,160 @@
+//! Audit log of the changes LazyFile made: remotes created, edited or
+//! deleted, files uploaded, deleted or moved, scripts run. One JSON
+//! object per line, only ever appended to, so a team sharing storage
+//! can see who changed what and when.
+
+use super::State;
+use crate::error::{LazyFileError, Result};
+use serde::{Deserialize, Serialize};
+use std::fs::OpenOptions;
+use std::io::{ErrorKind, Write};
+use std::path::{Path, PathBuf};
+use tracing::{debug, warn};
+
+/// Most recent records loaded for the audit view.
+pub const AUDIT_LIMIT: usize = 1000;
+
+/// One change.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct AuditRecord {
+    /// When it finished, in Unix seconds.
+    pub at: i64,
+    /// Login name of who made it.
+    pub user: String,
+    /// What was done, e.g. `delete` or `create remote`.
+    pub action: String,
+    /// What it was done to: `remote:path`, or a remote's name.
+    pub target: String,
+    /// More about it, such as where a file was moved to.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+    /// Why it failed; None if it succeeded.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl AuditRecord {
+    /// Returns the login name records are made under: `USER`, or
+    /// `USERNAME` on Windows, else `unknown`.
+    pub fn current_user() -> String {
+        ["USER", "USERNAME"]
+            .into_iter()
+            .find_map(|var| std::env::var(var).ok().filter(|name| !name.is_empty()))
+            .unwrap_or_else(|| "unknown".to_string())
+    }
+}
+
+/// The audit file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AuditLog {
+    path: PathBuf,
+}
+
+impl AuditLog {
+    /// Creates an audit log kept at `path`.
+    pub fn new(path: PathBuf) -> Self {
+        Self { path }
+    }
+
+    /// Returns the default audit file location, next to the state
+    /// file.
+    pub fn default_path() -> Option<PathBuf> {
+        State::default_path().map(|path| path.with_file_name("audit.jsonl"))
+    }
+
+    /// Returns where the audit log is kept.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Appends `record`, creating the file and its directory if
+    /// needed.
+    ///
+    /// # Errors
+    /// Returns `Audit` if the file cannot be written.
+    pub fn append(&self, record: &AuditRecord) -> Result<()> {
+        let audit_error = |e: &dyn std::fmt::Display| LazyFileError::Audit {
+            path: self.path.clone(),
+            message: e.to_string(),
+        };
+        let mut line = serde_json::to_string(record).map_err(|e| audit_error(&e))?;
+        line.push('\n');
+        if let Some(dir) = self.path.parent() {
+            std::fs::create_dir_all(dir).map_err(|e| audit_error(&e))?;
+        }
+        OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .and_then(|mut file| file.write_all(line.as_bytes()))
+            .map_err(|e| audit_error(&e))?;
+        debug!(path = %self.path.display(), action = %record.action, "recorded change");
+        Ok(())
+    }
+
+    /// Loads the last [`AUDIT_LIMIT`] records, newest first. A missing
+    /// file yields none; lines that do not parse are skipped.
+    ///
+    /// # Errors
+    /// Returns `Audit` if the file exists but cannot be read.
+    pub fn load(&self) -> Result<Vec<AuditRecord>> {
+        let text = match std::fs::read_to_string(&self.path) {
+            Ok(text) => text,
+            Err(e) if e.kind() == ErrorKind::NotFound => {
+                debug!(path = %self.path.display(), "no audit file");
+                return Ok(Vec::new());
+            }
+            Err(e) => {
+                return Err(LazyFileError::Audit {
+                    path: self.path.clone(),
+                    message: e.to_string(),
+                });
+            }
+        };
+        Ok(text
+            .lines()
+            .rev()
+            .filter(|line| !line.trim().is_empty())
+            .filter_map(|line| {
+                serde_json::from_str(line)
+                    .inspect_err(|e| warn!(error = %e, "skipping malformed audit line"))
+                    .ok()
+            })
+            .take(AUDIT_LIMIT)
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(at: i64, action: &str) -> AuditRecord {
+        AuditRecord {
+            at,
+            user: "ana".to_string(),
+            action: action.to_string(),
+            target: "s3:team/report.pdf".to_string(),
+            detail: None,
+            error: None,
+        }
+    }
+
+    #[test]
+    fn test_appends_and_loads_newest_first() {
+        let dir = std::env::temp_dir().join(format!("lazyfile-audit-{}", std::process::id()));
+        let audit = AuditLog::new(dir.join("audit.jsonl"));
+        assert!(audit.load().unwrap().is_empty());
+
+        audit.append(&record(1, "upload")).unwrap();
+        audit.append(&record(2, "delete")).unwrap();
+
+        let text = std::fs::read_to_string(audit.path()).unwrap();
+        assert!(!text.contains("detail"));
+        assert_eq!(
+            audit.load().unwrap(),
+            [record(2, "delete"), record(1, "upload")]
+        );
+        let _ = std::fs::remove_dir_all(dir);
+    }
+}
@@ -0,0 +1,212 @@
+//! What the panels show: icons, file list columns, status bar
+//! segments and how confirmations are asked.
+
+use serde::Deserialize;
+use std::collections::BTreeMap;
+
+/// Glyphs drawn before remote and file names, from the `icons` key.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum IconSet {
+    /// Geometric shapes most fonts have, for remotes only.
+    #[default]
+    Unicode,
+    /// Nerd Font glyphs for remotes and files; needs a patched font.
+    Nerd,
+    /// Plain letters, for terminals with neither.
+    Ascii,
+}
+
+/// A column of the files panel, after the always-shown name.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Column {
+    /// File size.
+    Size,
+    /// Last modification time.
+    Modtime,
+    /// MIME type.
+    Mime,
+    /// Hash of the type set by `hash`.
+    Hash,
+}
+
+impl Column {
+    /// Returns the width used when `[file_list] widths` does not set
+    /// one.
+    pub fn default_width(self) -> u16 {
+        match self {
+            Self::Size => 12,
+            Self::Modtime => 25,
+            Self::Mime => 24,
+            Self::Hash => 12,
+        }
+    }
+}
+
+/// Columns of the files panel, from the `[file_list]` table.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields, default)]
+pub struct FileListConfig {
+    /// Columns after the name, left to right.
+    pub columns: Vec<Column>,
+    /// Width overrides, in cells.
+    pub widths: BTreeMap<Column, u16>,
+    /// Hash type of the `hash` column, e.g. `md5` or `sha1`.
+    pub hash: String,
+}
+
+impl Default for FileListConfig {
+    fn default() -> Self {
+        Self {
+            columns: vec![Column::Size, Column::Modtime],
+            widths: BTreeMap::new(),
+            hash: "md5".to_string(),
+        }
+    }
+}
+
+impl FileListConfig {
+    /// Returns the width of `column`.
+    pub fn width(&self, column: Column) -> u16 {
+        self.widths
+            .get(&column)
+            .copied()
+            .unwrap_or_else(|| column.default_width())
+    }
+
+    /// Returns the hash type listings must ask for, if the hash
+    /// column is shown.
+    pub fn hash_type(&self) -> Option<&str> {
+        self.columns
+            .contains(&Column::Hash)
+            .then_some(self.hash.as_str())
+    }
+}
+
+/// A piece of the status bar.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Segment {
+    /// Remote and path, as `remote:path`.
+    Location,
+    /// Open remote.
+    Remote,
+    /// Path within the open remote.
+    Path,
+    /// Whether the daemon answers.
+    Connection,
+    /// Number of marked entries.
+    Selection,
+    /// Combined speed of the daemon's transfers.
+    Speed,
+    /// Number of jobs running on the daemon.
+    Jobs,
+}
+
+/// Segments of the status bar, from the `[status_bar]` table.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields, default)]
+pub struct StatusBarConfig {
+    /// Segments left to right.
+    pub segments: Vec<Segment>,
+}
+
+impl Default for StatusBarConfig {
+    fn default() -> Self {
+        Self {
+            segments: vec![Segment::Location, Segment::Connection],
+        }
+    }
+}
+
+impl StatusBarConfig {
+    /// Returns whether a segment needs the daemon's jobs and
+    /// transfers polled.
+    pub fn polls(&self) -> bool {
+        self.segments
+            .iter()
+            .any(|segment| matches!(segment, Segment::Speed | Segment::Jobs))
+    }
+}
+
+/// How a destructive operation is confirmed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ConfirmStyle {
+    /// Choose Yes.
+    #[default]
+    Button,
+    /// Type the name of what goes first, then choose Yes.
+    Type,
+}
+
+/// How each class of destructive operation is confirmed, from the
+/// `[confirm]` table.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields, default)]
+pub struct ConfirmConfig {
+    /// Deleting a file.
+    pub delete: ConfirmStyle,
+    /// Deleting a directory and everything in it.
+    pub purge: ConfirmStyle,
+    /// Deleting a remote from the rclone config.
+    pub delete_remote: ConfirmStyle,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+
+    #[test]
+    fn test_parses_icon_set() {
+        assert_eq!(Config::parse("").unwrap().icons, IconSet::Unicode);
+        assert_eq!(
+            Config::parse("icons = \"nerd\"").unwrap().icons,
+            IconSet::Nerd
+        );
+        assert!(Config::parse("icons = \"emoji\"").is_err());
+    }
+
+    #[test]
+    fn test_parses_confirm_styles() {
+        let config = Config::parse("[confirm]\npurge = \"type\"").unwrap();
+        assert_eq!(config.confirm.purge, ConfirmStyle::Type);
+        assert_eq!(config.confirm.delete_remote, ConfirmStyle::Button);
+        assert!(Config::parse("[confirm]\nrmdir = \"type\"").is_err());
+        assert!(Config::parse("[confirm]\npurge = \"twice\"").is_err());
+    }
+
+    #[test]
+    fn test_parses_file_list_columns() {
+        assert_eq!(
+            Config::parse("").unwrap().file_list.columns,
+            [Column::Size, Column::Modtime]
+        );
+        let config = Config::parse(
+            "[file_list]\ncolumns = [\"hash\", \"size\"]\nwidths = { hash = 32 }\nhash = \"sha1\"",
+        )
+        .unwrap();
+        assert_eq!(config.file_list.columns, [Column::Hash, Column::Size]);
+        assert_eq!(config.file_list.width(Column::Hash), 32);
+        assert_eq!(config.file_list.width(Column::Size), 12);
+        assert_eq!(config.file_list.hash_type(), Some("sha1"));
+        assert!(Config::parse("[file_list]\ncolumns = [\"owner\"]").is_err());
+    }
+
+    #[test]
+    fn test_parses_status_bar_segments() {
+        let config = Config::parse("").unwrap();
+        assert_eq!(
+            config.status_bar.segments,
+            [Segment::Location, Segment::Connection]
+        );
+        assert!(!config.status_bar.polls());
+
+        let config = Config::parse("[status_bar]\nsegments = [\"remote\", \"jobs\"]").unwrap();
+        assert_eq!(config.status_bar.segments, [Segment::Remote, Segment::Jobs]);
+        assert!(config.status_bar.polls());
+        assert!(Config::parse("[status_bar]\nsegments = [\"clock\"]").is_err());
+    }
+}
@@ -0,0 +1,436 @@
+//! User config file (`$XDG_CONFIG_HOME/lazyfile/config.toml`).
+
+use super::display::{ConfirmConfig, FileListConfig, IconSet, StatusBarConfig};
+use super::layout::RemoteLayout;
+use super::profile::Profile;
+use crate::error::{LazyFileError, Result};
+use crate::rclone::RequestPolicy;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tracing::{debug, info};
+
+/// One key sequence or a list of them, as written in `[keys]`.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(untagged)]
+pub enum KeyList {
+    /// `quit = "q"`
+    One(String),
+    /// `quit = ["q", "ctrl-c"]`
+    Many(Vec<String>),
+}
+
+impl KeyList {
+    /// Returns the key sequences as a slice.
+    pub fn as_slice(&self) -> &[String] {
+        match self {
+            Self::One(keys) => std::slice::from_ref(keys),
+            Self::Many(keys) => keys,
+        }
+    }
+}
+
+/// What to do when a copy or move would replace an existing file,
+/// from the `on_conflict` key.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ConflictPolicy {
+    /// Ask each time.
+    #[default]
+    Ask,
+    /// Leave the existing file and do nothing.
+    Skip,
+    /// Replace the existing file.
+    Overwrite,
+    /// Copy or move under a free name such as `a (1).txt`.
+    Rename,
+}
+
+impl ConflictPolicy {
+    /// Returns the label shown in the conflict dialog.
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Ask => "Ask",
+            Self::Skip => "Skip",
+            Self::Overwrite => "Overwrite",
+            Self::Rename => "Rename",
+        }
+    }
+}
+
+/// When finished operations raise a desktop notification, from the
+/// `[notifications]` table.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields, default)]
+pub struct NotificationConfig {
+    /// Whether desktop notifications are raised at all.
+    pub desktop: bool,
+    /// Seconds an operation must run for before its end is worth a
+    /// notification.
+    pub after_seconds: u64,
+}
+
+impl Default for NotificationConfig {
+    fn default() -> Self {
+        Self {
+            desktop: true,
+            after_seconds: 30,
+        }
+    }
+}
+
+impl NotificationConfig {
+    /// Returns how long an operation must run for to raise a desktop
+    /// notification, or `None` if they are off.
+    pub fn threshold(&self) -> Option<Duration> {
+        self.desktop
+            .then(|| Duration::from_secs(self.after_seconds))
+    }
+}
+
+/// `[requests]` table: how long calls to the daemon may take and how
+/// often one that never reached it is retried.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields, default)]
+pub struct RequestConfig {
+    /// Seconds an ordinary call may take.
+    pub timeout_seconds: u64,
+    /// Seconds a recursive listing, or one with hashes, may take.
+    pub listing_timeout_seconds: u64,
+    /// Seconds a step of remote setup, such as an OAuth sign-in, may
+    /// take.
+    pub config_timeout_seconds: u64,
+    /// Retries of a call that could not reach the daemon.
+    pub retries: u32,
+    /// Milliseconds before the first retry, doubled for each further
+    /// one.
+    pub backoff_ms: u64,
+}
+
+impl Default for RequestConfig {
+    fn default() -> Self {
+        let policy = RequestPolicy::default();
+        Self {
+            timeout_seconds: policy.timeout.as_secs(),
+            listing_timeout_seconds: policy.tree_timeout.as_secs(),
+            config_timeout_seconds: policy.config_timeout.as_secs(),
+            retries: 3,
+            backoff_ms: policy.backoff.as_millis() as u64,
+        }
+    }
+}
+
+impl RequestConfig {
+    /// Returns the client policy these settings describe.
+    pub fn policy(&self) -> RequestPolicy {
+        RequestPolicy {
+            timeout: Duration::from_secs(self.timeout_seconds),
+            tree_timeout: Duration::from_secs(self.listing_timeout_seconds),
+            config_timeout: Duration::from_secs(self.config_timeout_seconds),
+            retries: self.retries,
+            backoff: Duration::from_millis(self.backoff_ms),
+        }
+    }
+}
+
+/// How full a remote is, against its quota thresholds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum QuotaLevel {
+    /// Below the warning threshold.
+    Normal,
+    /// At or above the warning threshold.
+    Warning,
+    /// At or above the critical threshold.
+    Critical,
+}
+
+/// Thresholds of one remote in `[quota.remotes]`. Those left out fall
+/// back to the `[quota]` table's.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct QuotaThresholds {
+    /// Percent used at which the usage bar turns yellow.
+    pub warning: Option<u8>,
+    /// Percent used at which the usage bar turns red.
+    pub critical: Option<u8>,
+}
+
+/// When the usage bars of the remotes panel change color, from the
+/// `[quota]` table.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields, default)]
+pub struct QuotaConfig {
+    /// Percent used at which a bar turns yellow.
+    pub warning: u8,
+    /// Percent used at which a bar turns red.
+    pub critical: u8,
+    /// Overrides, keyed by remote name.
+    pub remotes: BTreeMap<String, QuotaThresholds>,
+}
+
+impl Default for QuotaConfig {
+    fn default() -> Self {
+        Self {
+            warning: 80,
+            critical: 95,
+            remotes: BTreeMap::new(),
+        }
+    }
+}
+
+impl QuotaConfig {
+    /// Returns how full `remote` is with `percent` of its space used.
+    pub fn level(&self, remote: &str, percent: u8) -> QuotaLevel {
+        let overrides = self.remotes.get(remote).copied().unwrap_or_default();
+        if percent >= overrides.critical.unwrap_or(self.critical) {
+            QuotaLevel::Critical
+        } else if percent >= overrides.warning.unwrap_or(self.warning) {
+            QuotaLevel::Warning
+        } else {
+            QuotaLevel::Normal
+        }
+    }
+}
+
+/// Settings read from the config file. Every section is optional.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Config {
+    /// Profile used when `--profile` is not given.
+    pub profile: Option<String>,
+    /// What copy and move do when the destination exists.
+    #[serde(default)]
+    pub on_conflict: ConflictPolicy,
+    /// Glyphs drawn before remote and file names.
+    #[serde(default)]
+    pub icons: IconSet,
+    /// Daemons to choose from, keyed by profile name.
+    #[serde(default)]
+    pub profiles: BTreeMap<String, Profile>,
+    /// Key binding overrides, keyed by action name.
+    #[serde(default)]
+    pub keys: BTreeMap<String, KeyList>,
+    /// Shell command templates offered in the command palette, keyed
+    /// by name.
+    #[serde(default)]
+    pub commands: BTreeMap<String, String>,
+    /// Color overrides, keyed by theme slot.
+    #[serde(default)]
+    pub theme: BTreeMap<String, String>,
+    /// Favorites and manual order of the remotes panel.
+    #[serde(default)]
+    pub remotes: RemoteLayout,
+    /// Columns of the files panel.
+    #[serde(default)]
+    pub file_list: FileListConfig,
+    /// Segments of the status bar.
+    #[serde(default)]
+    pub status_bar: StatusBarConfig,
+    /// How destructive operations are confirmed.
+    #[serde(default)]
+    pub confirm: ConfirmConfig,
+    /// Desktop notifications for long operations.
+    #[serde(default)]
+    pub notifications: NotificationConfig,
+    /// Request timeouts and retries.
+    #[serde(default)]
+    pub requests: RequestConfig,
+    /// Usage thresholds of the remotes panel.
+    #[serde(default)]
+    pub quota: QuotaConfig,
+}
+
+impl Config {
+    /// Returns the default config file location, if the platform
+    /// has a config directory.
+    pub fn default_path() -> Option<PathBuf> {
+        dirs::config_dir().map(|dir| dir.join("lazyfile").join("config.toml"))
+    }
+
+    /// Loads the config from the default location. A missing file
+    /// yields the defaults.
+    ///
+    /// # Errors
+    /// Returns `Config` if the file exists but cannot be read or
+    /// parsed.
+    pub fn load_default() -> Result<Self> {
+        match Self::default_path() {
+            Some(path) => Self::load_or_default(&path),
+            None => Ok(Self::default()),
+        }
+    }
+
+    /// Loads the config from a path the user gave explicitly. Unlike
+    /// the default location, the file must exist.
+    ///
+    /// # Errors
+    /// Returns `Config` if the file cannot be read or parsed.
+    pub fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Err(LazyFileError::Config {
+                path: path.to_path_buf(),
+                message: "file not found".to_string(),
+            });
+        }
+        Self::load_or_default(path)
+    }
+
+    /// Loads the config from `path`, falling back to the defaults
+    /// when the file does not exist.
+    ///
+    /// # Errors
+    /// Returns `Config` if the file exists but cannot be read or
+    /// parsed.
+    pub fn load_or_default(path: &Path) -> Result<Self> {
+        let text = match std::fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(e) if e.kind() == ErrorKind::NotFound => {
+                debug!(path = %path.display(), "no config file, using defaults");
+                return Ok(Self::default());
+            }
+            Err(e) => {
+                return Err(LazyFileError::Config {
+                    path: path.to_path_buf(),
+                    message: e.to_string(),
+                });
+            }
+        };
+        let config = Self::parse(&text).map_err(|message| LazyFileError::Config {
+            path: path.to_path_buf(),
+            message,
+        })?;
+        info!(path = %path.display(), "loaded config");
+        Ok(config)
+    }
+
+    pub(super) fn parse(text: &str) -> std::result::Result<Self, String> {
+        toml::from_str(text).map_err(|e| e.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_empty_file_is_default() {
+        let config = Config::parse("").unwrap();
+        assert!(config.keys.is_empty());
+    }
+
+    #[test]
+    fn test_parses_keys_as_string_or_list() {
+        let config = Config::parse(
+            r#"
+            [keys]
+            quit = "Q"
+            top = ["g g", "home"]
+            "#,
+        )
+        .unwrap();
+        assert_eq!(config.keys["quit"], KeyList::One("Q".to_string()));
+        assert_eq!(config.keys["top"].as_slice(), ["g g", "home"]);
+    }
+
+    #[test]
+    fn test_parses_theme_table() {
+        let config = Config::parse("[theme]\nborder = \"magenta\"").unwrap();
+        assert_eq!(config.theme["border"], "magenta");
+    }
+
+    #[test]
+    fn test_parses_conflict_policy() {
+        assert_eq!(Config::parse("").unwrap().on_conflict, ConflictPolicy::Ask);
+        let config = Config::parse("on_conflict = \"rename\"").unwrap();
+        assert_eq!(config.on_conflict, ConflictPolicy::Rename);
+        assert!(Config::parse("on_conflict = \"merge\"").is_err());
+    }
+
+    #[test]
+    fn test_parses_notification_threshold() {
+        let config = Config::parse("").unwrap();
+        assert_eq!(
+            config.notifications.threshold(),
+            Some(Duration::from_secs(30))
+        );
+        let config = Config::parse("[notifications]\nafter_seconds = 120").unwrap();
+        assert_eq!(
+            config.notifications.threshold(),
+            Some(Duration::from_secs(120))
+        );
+        let config = Config::parse("[notifications]\ndesktop = false").unwrap();
+        assert_eq!(config.notifications.threshold(), None);
+    }
+
+    #[test]
+    fn test_parses_request_policy() {
+        let config = Config::parse("").unwrap();
+        assert_eq!(config.requests.policy().retries, 3);
+        let config = Config::parse(
+            "[requests]
+timeout_seconds = 5
+retries = 0",
+        )
+        .unwrap();
+        let policy = config.requests.policy();
+        assert_eq!(policy.timeout, Duration::from_secs(5));
+        assert_eq!(policy.retries, 0);
+        assert!(
+            Config::parse(
+                "[requests]
+retry = 1"
+            )
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn test_rejects_unknown_section() {
+        assert!(Config::parse("[colours]\nfg = \"red\"").is_err());
+    }
+
+    #[test]
+    fn test_rejects_wrong_value_type() {
+        assert!(Config::parse("[keys]\nquit = 5").is_err());
+    }
+
+    #[test]
+    fn test_parses_commands_table() {
+        let config = Config::parse("[commands]\nsize = \"rclone size %paths%\"").unwrap();
+        assert_eq!(config.commands["size"], "rclone size %paths%");
+    }
+
+    #[test]
+    fn test_explicit_path_must_exist() {
+        let err = Config::load(Path::new("/nonexistent/lazyfile.toml")).unwrap_err();
+        assert!(matches!(err, LazyFileError::Config { .. }));
+    }
+
+    #[test]
+    fn test_missing_file_yields_defaults() {
+        let config = Config::load_or_default(Path::new("/nonexistent/lazyfile.toml")).unwrap();
+        assert!(config.keys.is_empty());
+    }
+
+    #[test]
+    fn test_quota_thresholds_fall_back_per_field() {
+        let config = Config::parse(
+            "[quota]
+warning = 70
+
+[quota.remotes.gdrive]
+critical = 85
+",
+        )
+        .unwrap();
+        let quota = &config.quota;
+        assert_eq!(quota.level("s3", 69), QuotaLevel::Normal);
+        assert_eq!(quota.level("s3", 70), QuotaLevel::Warning);
+        assert_eq!(quota.level("s3", 90), QuotaLevel::Warning);
+        assert_eq!(quota.level("gdrive", 72), QuotaLevel::Warning);
+        assert_eq!(quota.level("gdrive", 85), QuotaLevel::Critical);
+        assert!(Config::parse("[quota.remotes.s3]\nwarn = 1\n").is_err());
+    }
+}
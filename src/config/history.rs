@@ -0,0 +1,169 @@
+//! Log of the copies and moves LazyFile ran, one JSON object per
+//! line, so it is cheap to append to and easy to read with other
+//! tools.
+
+use super::State;
+use crate::error::{LazyFileError, Result};
+use serde::{Deserialize, Serialize};
+use std::fs::OpenOptions;
+use std::io::{ErrorKind, Write};
+use std::path::{Path, PathBuf};
+use tracing::{debug, warn};
+
+/// Most recent records loaded for the history view.
+pub const HISTORY_LIMIT: usize = 1000;
+
+/// What a transfer did with its source.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TransferKind {
+    /// The source was left in place.
+    Copy,
+    /// The source was removed.
+    Move,
+}
+
+/// One finished transfer.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct TransferRecord {
+    /// When it finished, in Unix seconds.
+    pub finished: i64,
+    /// Whether it copied or moved.
+    pub kind: TransferKind,
+    /// Source as `remote:path`.
+    pub source: String,
+    /// Destination as `remote:path`.
+    pub destination: String,
+    /// Size of the file; None for directories, whose size is not
+    /// known up front.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bytes: Option<u64>,
+    /// Seconds it took.
+    pub seconds: f64,
+    /// Why it failed; None if it succeeded.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// The history file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct History {
+    path: PathBuf,
+}
+
+impl History {
+    /// Creates a history kept at `path`.
+    pub fn new(path: PathBuf) -> Self {
+        Self { path }
+    }
+
+    /// Returns the default history file location, next to the state
+    /// file.
+    pub fn default_path() -> Option<PathBuf> {
+        State::default_path().map(|path| path.with_file_name("history.jsonl"))
+    }
+
+    /// Returns where the history is kept.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Appends `record`, creating the file and its directory if
+    /// needed.
+    ///
+    /// # Errors
+    /// Returns `History` if the file cannot be written.
+    pub fn append(&self, record: &TransferRecord) -> Result<()> {
+        let history_error = |e: &dyn std::fmt::Display| LazyFileError::History {
+            path: self.path.clone(),
+            message: e.to_string(),
+        };
+        let mut line = serde_json::to_string(record).map_err(|e| history_error(&e))?;
+        line.push('\n');
+        if let Some(dir) = self.path.parent() {
+            std::fs::create_dir_all(dir).map_err(|e| history_error(&e))?;
+        }
+        OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .and_then(|mut file| file.write_all(line.as_bytes()))
+            .map_err(|e| history_error(&e))?;
+        debug!(path = %self.path.display(), "recorded transfer");
+        Ok(())
+    }
+
+    /// Loads the last [`HISTORY_LIMIT`] records, newest first. A
+    /// missing file yields none; lines that do not parse, e.g. one cut
+    /// short by a crash, are skipped.
+    ///
+    /// # Errors
+    /// Returns `History` if the file exists but cannot be read.
+    pub fn load(&self) -> Result<Vec<TransferRecord>> {
+        let text = match std::fs::read_to_string(&self.path) {
+            Ok(text) => text,
+            Err(e) if e.kind() == ErrorKind::NotFound => {
+                debug!(path = %self.path.display(), "no history file");
+                return Ok(Vec::new());
+            }
+            Err(e) => {
+                return Err(LazyFileError::History {
+                    path: self.path.clone(),
+                    message: e.to_string(),
+                });
+            }
+        };
+        Ok(text
+            .lines()
+            .rev()
+            .filter(|line| !line.trim().is_empty())
+            .filter_map(|line| {
+                serde_json::from_str(line)
+                    .inspect_err(|e| warn!(error = %e, "skipping malformed history line"))
+                    .ok()
+            })
+            .take(HISTORY_LIMIT)
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(finished: i64, error: Option<&str>) -> TransferRecord {
+        TransferRecord {
+            finished,
+            kind: TransferKind::Copy,
+            source: "gdrive:a.txt".to_string(),
+            destination: "s3:backup/a.txt".to_string(),
+            bytes: Some(12),
+            seconds: 0.5,
+            error: error.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn test_appends_and_loads_newest_first() {
+        let dir = std::env::temp_dir().join(format!("lazyfile-history-{}", std::process::id()));
+        let history = History::new(dir.join("history.jsonl"));
+        assert!(history.load().unwrap().is_empty());
+
+        history.append(&record(1, None)).unwrap();
+        let mut file = OpenOptions::new()
+            .append(true)
+            .open(history.path())
+            .unwrap();
+        file.write_all(b"{\"finished\": 2, \"kin\n").unwrap();
+        history
+            .append(&record(3, Some("permission denied")))
+            .unwrap();
+
+        let records = history.load().unwrap();
+        assert_eq!(
+            records,
+            [record(3, Some("permission denied")), record(1, None)]
+        );
+        let _ = std::fs::remove_dir_all(dir);
+    }
+}
@@ -0,0 +1,218 @@
+//! How the remotes panel is arranged, from the `[remotes]` table, and
+//! saving it back to the config file.
+
+use super::file::Config;
+use crate::error::{LazyFileError, Result};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::io::ErrorKind;
+use std::path::Path;
+use toml_edit::{DocumentMut, Item, Table};
+use tracing::debug;
+
+/// Percent of the screen width the remotes panel takes by default.
+pub const DEFAULT_REMOTES_WIDTH: u16 = 30;
+
+/// Narrowest and widest the remotes panel can be made, in percent.
+pub const REMOTES_WIDTH_RANGE: (u16, u16) = (10, 70);
+
+/// How the remotes panel orders remotes, from the `[remotes]` table.
+/// LazyFile rewrites that table when remotes are starred, moved or
+/// the panel is resized.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct RemoteLayout {
+    /// Starred remotes, pinned to the top.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub favorites: Vec<String>,
+    /// Manual order. Remotes not listed follow in the daemon's
+    /// order.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub order: Vec<String>,
+    /// Directory each remote opens at, as `remote:path`. Remotes not
+    /// listed open at their root.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub start_paths: Vec<String>,
+    /// Percent of the screen width the panel takes, next to the file
+    /// list; [`DEFAULT_REMOTES_WIDTH`] when unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub width: Option<u16>,
+}
+
+impl RemoteLayout {
+    /// Returns whether `remote` is starred.
+    pub fn is_favorite(&self, remote: &str) -> bool {
+        self.favorites.iter().any(|name| name == remote)
+    }
+
+    /// Sorts `remotes`: favorites first, each group in the manual
+    /// order, and unlisted remotes last in the order they came.
+    pub fn arrange(&self, remotes: &mut [String]) {
+        remotes.sort_by_key(|remote| {
+            let position = self.order.iter().position(|name| name == remote);
+            (!self.is_favorite(remote), position.unwrap_or(usize::MAX))
+        });
+    }
+
+    /// Returns the panel's width in percent, within
+    /// [`REMOTES_WIDTH_RANGE`].
+    pub fn width(&self) -> u16 {
+        let (min, max) = REMOTES_WIDTH_RANGE;
+        self.width.unwrap_or(DEFAULT_REMOTES_WIDTH).clamp(min, max)
+    }
+
+    /// Returns the path `remote` opens at; empty for its root.
+    pub fn start_path(&self, remote: &str) -> &str {
+        self.start_paths
+            .iter()
+            .find_map(|location| match location.split_once(':') {
+                Some((name, path)) if name == remote => Some(path.trim_matches('/')),
+                _ => None,
+            })
+            .unwrap_or("")
+    }
+
+    /// Makes `remote` open at `path`, or at its root if `path` is
+    /// empty.
+    pub fn set_start_path(&mut self, remote: &str, path: &str) {
+        let prefix = format!("{}:", remote);
+        self.start_paths
+            .retain(|location| !location.starts_with(&prefix));
+        let path = path.trim().trim_matches('/');
+        if !path.is_empty() {
+            self.start_paths.push(format!("{}{}", prefix, path));
+        }
+    }
+}
+
+impl Config {
+    /// Writes `layout` as the `[remotes]` table of the file at `path`,
+    /// creating the file if needed. The rest of the file, comments
+    /// included, is left as it is.
+    ///
+    /// # Errors
+    /// Returns `ConfigWrite` if the file cannot be read or written.
+    pub fn save_remote_layout(path: &Path, layout: &RemoteLayout) -> Result<()> {
+        let write_error = |e: &dyn fmt::Display| LazyFileError::ConfigWrite {
+            path: path.to_path_buf(),
+            message: e.to_string(),
+        };
+        let text = match std::fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(e) if e.kind() == ErrorKind::NotFound => String::new(),
+            Err(e) => return Err(write_error(&e)),
+        };
+        let values = toml::to_string(layout).map_err(|e| write_error(&e))?;
+        // A file the table cannot be found in for sure is left alone:
+        // appending a second one would stop the next start.
+        let text = replace_table(&text, REMOTES_TABLE, &values).map_err(|e| write_error(&e))?;
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir).map_err(|e| write_error(&e))?;
+        }
+        std::fs::write(path, text).map_err(|e| write_error(&e))?;
+        debug!(path = %path.display(), "saved remote layout");
+        Ok(())
+    }
+}
+
+/// Name of the table [`RemoteLayout`] is read from.
+const REMOTES_TABLE: &str = "remotes";
+
+/// Replaces the values of table `name` in the TOML document `text`
+/// with `values`, a document of keys and values, or appends the table
+/// if there is none. However the table is written, as a `[name]`
+/// header, dotted keys or an inline table, it is edited where it is;
+/// everything else, comments included, stays as it was.
+///
+/// # Errors
+/// Returns why if `text` or `values` is not TOML, or `name` holds
+/// something other than a table.
+fn replace_table(text: &str, name: &str, values: &str) -> std::result::Result<String, String> {
+    let mut document: DocumentMut = text.parse().map_err(|e| format!("{}", e))?;
+    let values: DocumentMut = values.parse().map_err(|e| format!("{}", e))?;
+    match document.get_mut(name) {
+        Some(item) => {
+            let table = item
+                .as_table_like_mut()
+                .ok_or_else(|| format!("`{}` is not a table", name))?;
+            table.clear();
+            for (key, value) in values.iter() {
+                table.insert(key, value.clone());
+            }
+        }
+        None => {
+            let mut table = Table::new();
+            for (key, value) in values.iter() {
+                table.insert(key, value.clone());
+            }
+            document.insert(name, Item::Table(table));
+        }
+    }
+    Ok(document.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_arranges_favorites_then_manual_order() {
+        let config = Config::parse(
+            "[remotes]\nfavorites = [\"s3\"]\norder = [\"b2\", \"gdrive\", \"s3\"]\n",
+        )
+        .unwrap();
+        let mut remotes: Vec<String> = ["gdrive", "local", "s3", "b2"].map(String::from).to_vec();
+        config.remotes.arrange(&mut remotes);
+        assert_eq!(remotes, ["s3", "b2", "gdrive", "local"]);
+    }
+
+    #[test]
+    fn test_looks_up_start_paths() {
+        let mut config = Config::parse(
+            "[remotes]
+start_paths = [\"gdrive:/Work/\"]
+",
+        )
+        .unwrap();
+        let layout = &mut config.remotes;
+        assert_eq!(layout.start_path("gdrive"), "Work");
+        assert_eq!(layout.start_path("s3"), "");
+
+        layout.set_start_path("s3", "backup/2024");
+        layout.set_start_path("gdrive", "");
+        assert_eq!(layout.start_paths, ["s3:backup/2024"]);
+    }
+
+    #[test]
+    fn test_replaces_table_keeping_the_rest() {
+        let text = "profile = \"home\"\n\n[remotes]\norder = [\"a\"]\n\n# Colors\n[theme]\n";
+        assert_eq!(
+            replace_table(text, "remotes", "order = [\"b\"]\n").unwrap(),
+            "profile = \"home\"\n\n[remotes]\norder = [\"b\"]\n\n# Colors\n[theme]\n"
+        );
+        assert_eq!(
+            replace_table("[theme]\n", "remotes", "order = [\"b\"]\n").unwrap(),
+            "[theme]\n\n[remotes]\norder = [\"b\"]\n"
+        );
+    }
+
+    #[test]
+    fn test_replaces_table_however_it_is_written() {
+        let starred = "favorites = [\"s3\"]\n";
+        for text in [
+            "[remotes]  # order\norder = [\"a\"]\n",
+            "[ remotes ]\norder = [\"a\"]\n",
+            "remotes.order = [\"a\"]\n",
+            "remotes = { order = [\"a\"] }\n",
+        ] {
+            let replaced = replace_table(text, "remotes", starred).unwrap();
+            let config = Config::parse(&replaced).unwrap();
+            assert_eq!(config.remotes.favorites, ["s3"], "{}", text);
+            assert!(config.remotes.order.is_empty(), "{}", text);
+        }
+        let replaced = replace_table("[remotes]  # order\n", "remotes", starred).unwrap();
+        assert_eq!(replaced, "[remotes]  # order\nfavorites = [\"s3\"]\n");
+        assert!(replace_table("remotes = 1\n", "remotes", starred).is_err());
+        assert!(replace_table("[remotes\n", "remotes", starred).is_err());
+    }
+}
@@ -1,4 +1,27 @@
-//! Configuration constants.
+//! Configuration constants and the user config file.
+
+mod audit;
+mod display;
+mod file;
+mod history;
+mod layout;
+mod private;
+mod profile;
+mod state;
+
+pub use audit::{AUDIT_LIMIT, AuditLog, AuditRecord};
+pub use display::{
+    Column, ConfirmConfig, ConfirmStyle, FileListConfig, IconSet, Segment, StatusBarConfig,
+};
+pub use file::{
+    Config, ConflictPolicy, KeyList, NotificationConfig, QuotaConfig, QuotaLevel, QuotaThresholds,
+    RequestConfig,
+};
+pub use history::{HISTORY_LIMIT, History, TransferKind, TransferRecord};
+pub use layout::{DEFAULT_REMOTES_WIDTH, REMOTES_WIDTH_RANGE, RemoteLayout};
+pub use private::write_private;
+pub use profile::Profile;
+pub use state::{Focus, MAX_RECENT, Session, State};
 
 /// Default rclone daemon host.
 pub const RCLONE_HOST: &str = "localhost";
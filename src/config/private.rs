@@ -0,0 +1,52 @@
+//! Files only their owner may read, for anything holding credentials.
+
+use crate::error::Result;
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::Path;
+
+/// Writes `text` to `path`, readable only by the owner on Unix,
+/// replacing what the file held.
+///
+/// # Errors
+/// Returns `Io` if the file cannot be opened, narrowed or written.
+pub fn write_private(path: &Path, text: &str) -> Result<()> {
+    let mut options = OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    let mut file = options.open(path)?;
+    // The mode only applies to a file being created; one that already
+    // exists keeps its own unless it is narrowed before writing.
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        file.set_permissions(std::fs::Permissions::from_mode(0o600))?;
+    }
+    file.write_all(text.as_bytes())?;
+    Ok(())
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use std::os::unix::fs::PermissionsExt;
+
+    #[test]
+    fn test_readable_file_is_made_private() {
+        let file =
+            std::env::temp_dir().join(format!("lazyfile-private-{}.conf", std::process::id()));
+        std::fs::write(&file, "old").unwrap();
+        std::fs::set_permissions(&file, std::fs::Permissions::from_mode(0o644)).unwrap();
+
+        write_private(&file, "[s3]\ntype = s3\n").unwrap();
+        let mode = std::fs::metadata(&file).unwrap().permissions().mode();
+        let text = std::fs::read_to_string(&file).unwrap();
+        std::fs::remove_file(&file).unwrap();
+        assert_eq!(mode & 0o777, 0o600);
+        assert_eq!(text, "[s3]\ntype = s3\n");
+    }
+}
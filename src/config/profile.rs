@@ -0,0 +1,231 @@
+//! Connection profiles from the `[profiles]` table.
+
+use super::file::Config;
+use super::{RCLONE_HOST, RCLONE_PORT};
+use crate::error::{LazyFileError, Result};
+use crate::rclone::{Connection, Credentials, socket_path};
+use serde::Deserialize;
+use std::fmt;
+use std::path::PathBuf;
+
+/// A named rclone daemon, from a `[profiles.NAME]` table.
+#[derive(Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Profile {
+    /// Host name or IPv4 address.
+    #[serde(default = "default_host")]
+    pub host: String,
+    /// RC port.
+    #[serde(default = "default_port")]
+    pub port: u16,
+    /// Whether the daemon serves HTTPS.
+    #[serde(default)]
+    pub tls: bool,
+    /// PEM file of the CA that signed the daemon's certificate.
+    /// Implies `tls`.
+    pub ca_cert: Option<PathBuf>,
+    /// Whether to skip certificate verification. Implies `tls`.
+    #[serde(default)]
+    pub insecure: bool,
+    /// PEM client certificate for mutual TLS, possibly with its
+    /// private key. Implies `tls`.
+    pub client_cert: Option<PathBuf>,
+    /// PEM private key of `client_cert`, if kept separately.
+    pub client_key: Option<PathBuf>,
+    /// `--rc-user` of the daemon.
+    pub user: Option<String>,
+    /// `--rc-pass` of the daemon.
+    pub password: Option<String>,
+    /// Unix socket the daemon listens on, used instead of `host` and
+    /// `port`.
+    pub socket: Option<String>,
+}
+
+fn default_host() -> String {
+    RCLONE_HOST.to_string()
+}
+
+fn default_port() -> u16 {
+    RCLONE_PORT
+}
+
+impl Default for Profile {
+    fn default() -> Self {
+        Self {
+            host: default_host(),
+            port: default_port(),
+            tls: false,
+            ca_cert: None,
+            insecure: false,
+            client_cert: None,
+            client_key: None,
+            user: None,
+            password: None,
+            socket: None,
+        }
+    }
+}
+
+impl fmt::Debug for Profile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Profile")
+            .field("host", &self.host)
+            .field("port", &self.port)
+            .field("tls", &self.tls)
+            .field("ca_cert", &self.ca_cert)
+            .field("insecure", &self.insecure)
+            .field("client_cert", &self.client_cert)
+            .field("client_key", &self.client_key)
+            .field("user", &self.user)
+            .field("password", &self.password.as_ref().map(|_| "<redacted>"))
+            .field("socket", &self.socket)
+            .finish()
+    }
+}
+
+impl Profile {
+    /// Returns how to reach the profile's daemon.
+    ///
+    /// # Errors
+    /// Returns `InvalidInput` if only one of `user` and `password`
+    /// is set, or `client_key` is set without `client_cert`.
+    pub fn connection(&self) -> Result<Connection> {
+        if self.client_key.is_some() && self.client_cert.is_none() {
+            return Err(LazyFileError::InvalidInput {
+                field: "profile",
+                reason: "client_key needs client_cert",
+            });
+        }
+        let credentials = match (&self.user, &self.password) {
+            (Some(user), Some(password)) => Some(Credentials::Basic {
+                user: user.clone(),
+                password: password.clone(),
+            }),
+            (None, None) => None,
+            _ => {
+                return Err(LazyFileError::InvalidInput {
+                    field: "profile",
+                    reason: "user and password must be set together",
+                });
+            }
+        };
+        Ok(Connection {
+            host: self.host.clone(),
+            port: self.port,
+            tls: self.tls || self.ca_cert.is_some() || self.insecure || self.client_cert.is_some(),
+            ca_cert: self.ca_cert.clone(),
+            insecure: self.insecure,
+            client_cert: self.client_cert.clone(),
+            client_key: self.client_key.clone(),
+            credentials,
+            socket: self.socket.as_deref().map(socket_path),
+        })
+    }
+}
+
+impl Config {
+    /// Picks the profile to start with: `requested` (the `--profile`
+    /// flag), else the file's `profile` entry. Returns its name and
+    /// connection, or `None` to fall back to `--host`/`--port`.
+    ///
+    /// # Errors
+    /// Returns `UnknownProfile` if the chosen name has no table, or
+    /// `InvalidInput` if the profile's login is incomplete.
+    pub fn startup_profile(&self, requested: Option<&str>) -> Result<Option<(String, Connection)>> {
+        let Some(name) = requested.or(self.profile.as_deref()) else {
+            return Ok(None);
+        };
+        let profile = self
+            .profiles
+            .get(name)
+            .ok_or_else(|| LazyFileError::UnknownProfile {
+                name: name.to_string(),
+            })?;
+        Ok(Some((name.to_string(), profile.connection()?)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    const PROFILES: &str = r#"
+        profile = "nas"
+
+        [profiles.nas]
+        host = "nas.local"
+        user = "admin"
+        password = "secret"
+
+        [profiles.vps]
+        host = "vps.example.com"
+        port = 443
+        tls = true
+    "#;
+
+    #[test]
+    fn test_parses_profiles_with_defaults() {
+        let config = Config::parse(PROFILES).unwrap();
+        let nas = &config.profiles["nas"];
+        assert_eq!(nas.port, RCLONE_PORT);
+        assert!(!nas.tls);
+        assert!(config.profiles["vps"].tls);
+    }
+
+    #[test]
+    fn test_flag_wins_over_default_profile() {
+        let config = Config::parse(PROFILES).unwrap();
+        let (name, connection) = config.startup_profile(Some("vps")).unwrap().unwrap();
+        assert_eq!(name, "vps");
+        assert_eq!(connection.base_url(), "https://vps.example.com:443");
+
+        let (name, connection) = config.startup_profile(None).unwrap().unwrap();
+        assert_eq!(name, "nas");
+        assert!(matches!(
+            connection.credentials,
+            Some(Credentials::Basic { ref user, .. }) if user == "admin"
+        ));
+    }
+
+    #[test]
+    fn test_no_profile_falls_back_to_flags() {
+        assert!(Config::default().startup_profile(None).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_ca_cert_implies_tls() {
+        let config = Config::parse("[profiles.home]\nca_cert = \"/etc/rclone/ca.pem\"").unwrap();
+        let connection = config.profiles["home"].connection().unwrap();
+        assert!(connection.tls);
+        assert_eq!(connection.ca_cert.unwrap(), Path::new("/etc/rclone/ca.pem"));
+    }
+
+    #[test]
+    fn test_rejects_client_key_without_cert() {
+        let config =
+            Config::parse("[profiles.home]\nclient_key = \"/etc/rclone/key.pem\"").unwrap();
+        assert!(config.profiles["home"].connection().is_err());
+    }
+
+    #[test]
+    fn test_rejects_unknown_profile() {
+        let err = Config::parse(PROFILES)
+            .unwrap()
+            .startup_profile(Some("laptop"))
+            .unwrap_err();
+        assert!(matches!(err, LazyFileError::UnknownProfile { ref name } if name == "laptop"));
+    }
+
+    #[test]
+    fn test_rejects_user_without_password() {
+        let config = Config::parse("[profiles.nas]\nuser = \"admin\"").unwrap();
+        assert!(config.profiles["nas"].connection().is_err());
+    }
+
+    #[test]
+    fn test_profile_debug_hides_password() {
+        let config = Config::parse(PROFILES).unwrap();
+        assert!(!format!("{:?}", config).contains("secret"));
+    }
+}
@@ -0,0 +1,156 @@
+//! What LazyFile remembers between sessions, kept apart from the
+//! config file so the user's settings are never rewritten by use.
+
+use crate::error::{LazyFileError, Result};
+use serde::{Deserialize, Serialize};
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use tracing::debug;
+
+/// Most recent locations remembered.
+pub const MAX_RECENT: usize = 50;
+
+/// Session state saved to the state file.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct State {
+    /// Visited locations as `remote:path`, most recent first.
+    pub recent: Vec<String>,
+    /// Where the last session left off, if it had a remote open.
+    pub session: Option<Session>,
+}
+
+/// Where a session left off, restored on the next launch.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct Session {
+    /// Remote that was open.
+    pub remote: String,
+    /// Path within the remote.
+    pub path: String,
+    /// Panel that had the focus.
+    #[serde(default)]
+    pub focus: Focus,
+}
+
+/// Panel a session left the focus on.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Focus {
+    /// The remotes panel.
+    Remotes,
+    /// The files panel.
+    #[default]
+    Files,
+}
+
+impl State {
+    /// Returns the default state file location, if the platform has a
+    /// state or local data directory.
+    pub fn default_path() -> Option<PathBuf> {
+        dirs::state_dir()
+            .or_else(dirs::data_local_dir)
+            .map(|dir| dir.join("lazyfile").join("state.toml"))
+    }
+
+    /// Loads the state from `path`. A missing file yields an empty
+    /// state.
+    ///
+    /// # Errors
+    /// Returns `State` if the file exists but cannot be read or
+    /// parsed.
+    pub fn load(path: &Path) -> Result<Self> {
+        let state_error = |message: String| LazyFileError::State {
+            path: path.to_path_buf(),
+            message,
+        };
+        let text = match std::fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(e) if e.kind() == ErrorKind::NotFound => {
+                debug!(path = %path.display(), "no state file");
+                return Ok(Self::default());
+            }
+            Err(e) => return Err(state_error(e.to_string())),
+        };
+        toml::from_str(&text).map_err(|e| state_error(e.to_string()))
+    }
+
+    /// Writes the state to `path`, creating its directory if needed.
+    ///
+    /// # Errors
+    /// Returns `State` if the file cannot be written.
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let state_error = |e: &dyn std::fmt::Display| LazyFileError::State {
+            path: path.to_path_buf(),
+            message: e.to_string(),
+        };
+        let text = toml::to_string(self).map_err(|e| state_error(&e))?;
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir).map_err(|e| state_error(&e))?;
+        }
+        std::fs::write(path, text).map_err(|e| state_error(&e))?;
+        debug!(path = %path.display(), "saved state");
+        Ok(())
+    }
+
+    /// Moves `location` to the front of the recent locations,
+    /// dropping the oldest past [`MAX_RECENT`]. Returns whether the
+    /// list changed.
+    pub fn push_recent(&mut self, location: &str) -> bool {
+        if self.recent.first().is_some_and(|first| first == location) {
+            return false;
+        }
+        self.recent.retain(|recent| recent != location);
+        self.recent.insert(0, location.to_string());
+        self.recent.truncate(MAX_RECENT);
+        true
+    }
+
+    /// Forgets `location`. Returns whether it was remembered.
+    pub fn forget_recent(&mut self, location: &str) -> bool {
+        let before = self.recent.len();
+        self.recent.retain(|recent| recent != location);
+        self.recent.len() != before
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_keeps_recent_unique_and_capped() {
+        let mut state = State::default();
+        assert!(state.push_recent("gdrive:docs"));
+        assert!(state.push_recent("s3:backup"));
+        assert!(!state.push_recent("s3:backup"));
+        assert!(state.push_recent("gdrive:docs"));
+        assert_eq!(state.recent, ["gdrive:docs", "s3:backup"]);
+
+        for i in 0..MAX_RECENT {
+            state.push_recent(&format!("s3:{}", i));
+        }
+        assert_eq!(state.recent.len(), MAX_RECENT);
+        assert!(!state.recent.contains(&"s3:backup".to_string()));
+        assert!(state.forget_recent("s3:0"));
+        assert!(!state.forget_recent("s3:0"));
+    }
+
+    #[test]
+    fn test_round_trips_through_file() {
+        let path = std::env::temp_dir()
+            .join(format!("lazyfile-state-{}", std::process::id()))
+            .join("state.toml");
+        assert_eq!(State::load(&path).unwrap(), State::default());
+
+        let mut state = State::default();
+        state.push_recent("gdrive:docs/2024");
+        state.session = Some(Session {
+            remote: "gdrive".to_string(),
+            path: "docs/2024".to_string(),
+            focus: Focus::Remotes,
+        });
+        state.save(&path).unwrap();
+        assert_eq!(State::load(&path).unwrap(), state);
+        let _ = std::fs::remove_dir_all(path.parent().unwrap());
+    }
+}
@@ -0,0 +1,354 @@
+//! `rclone rcd` child processes started by LazyFile.
+//!
+//! When no daemon is reachable, LazyFile can start one for the
+//! session. It listens on a free loopback port behind a random
+//! login, so other local users cannot drive it, and is shut down when
+//! LazyFile exits unless it is kept. When rclone is not installed,
+//! [`install_commands`] tells the user how to get it.
+
+use crate::config::write_private;
+use crate::error::{LazyFileError, Result};
+use crate::rclone::{Connection, Credentials, RcloneClient};
+use base64::Engine as _;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use std::ffi::OsStr;
+use std::net::{Ipv4Addr, TcpListener};
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::time::Duration;
+use tokio::io::{AsyncBufReadExt, BufReader};
+use tokio::net::TcpStream;
+use tokio::process::{Child, Command};
+use tracing::{debug, info, warn};
+
+/// Program started when none is given.
+pub const RCLONE_BINARY: &str = "rclone";
+
+/// User name of spawned daemons.
+const DAEMON_USER: &str = "lazyfile";
+
+/// How long a spawned daemon gets to start listening.
+const STARTUP_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// How long a daemon gets to exit after `core/quit` before it is
+/// killed.
+const SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Name of the file, next to the state file, holding the login of a
+/// daemon kept running after LazyFile exits.
+pub const LOGIN_FILE: &str = "daemon.env";
+
+/// Interval between checks for a listening daemon.
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// A running `rclone rcd` owned by this process. Killed if dropped
+/// without [`Daemon::shutdown`], unless it was started to be kept.
+#[derive(Debug)]
+pub struct Daemon {
+    child: Child,
+    connection: Connection,
+    program: String,
+}
+
+impl Daemon {
+    /// Starts `rclone rcd` and waits until it accepts connections.
+    ///
+    /// # Errors
+    /// Returns `Daemon` if rclone cannot be run, exits early, or does
+    /// not start listening in time.
+    pub async fn spawn() -> Result<Self> {
+        Self::spawn_program(RCLONE_BINARY, false).await
+    }
+
+    /// Like [`Self::spawn`], with the path of the rclone binary. A
+    /// `keep` daemon outlives LazyFile: it is not killed when dropped,
+    /// and its log is discarded since nothing reads it once LazyFile
+    /// exits.
+    ///
+    /// # Errors
+    /// Returns `Daemon` if the program cannot be run, exits early, or
+    /// does not start listening in time.
+    pub async fn spawn_program(program: &str, keep: bool) -> Result<Self> {
+        let failed = |message: String| LazyFileError::Daemon { message };
+        let port = free_port().map_err(|e| failed(format!("no free port: {}", e)))?;
+        let host = Ipv4Addr::LOCALHOST.to_string();
+        let password = random_password()?;
+
+        info!(port, keep, "starting rclone daemon");
+        // The login goes through the environment, which unlike the
+        // command line is not visible to other users.
+        let mut child = Command::new(program)
+            .args(["rcd", "--rc-serve", "--rc-addr"])
+            .arg(format!("{}:{}", host, port))
+            .env("RCLONE_RC_USER", DAEMON_USER)
+            .env("RCLONE_RC_PASS", &password)
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(if keep { Stdio::null() } else { Stdio::piped() })
+            .kill_on_drop(!keep)
+            .spawn()
+            .map_err(|e| failed(format!("{}: {}", program, e)))?;
+        if let Some(stderr) = child.stderr.take() {
+            tokio::spawn(forward_logs(BufReader::new(stderr)));
+        }
+
+        let connection = Connection {
+            host,
+            port,
+            credentials: Some(Credentials::Basic {
+                user: DAEMON_USER.to_string(),
+                password,
+            }),
+            ..Connection::default()
+        };
+        let mut daemon = Self {
+            child,
+            connection,
+            program: program.to_string(),
+        };
+        daemon.wait_until_listening().await?;
+        info!(port, "rclone daemon ready");
+        Ok(daemon)
+    }
+
+    /// Returns how to reach the daemon, login included.
+    pub fn connection(&self) -> &Connection {
+        &self.connection
+    }
+
+    /// Returns the rclone binary the daemon was started from.
+    pub fn program(&self) -> &str {
+        &self.program
+    }
+
+    /// Returns whether the daemon has exited, e.g. crashed or was
+    /// killed.
+    pub fn has_exited(&mut self) -> bool {
+        match self.child.try_wait() {
+            Ok(status) => status.is_some(),
+            Err(e) => {
+                warn!(error = %e, "could not check on rclone daemon");
+                false
+            }
+        }
+    }
+
+    /// Asks the daemon to exit through a client of its own, for when
+    /// the one it was started with is gone.
+    pub async fn stop(self) {
+        match RcloneClient::connect(&self.connection) {
+            Ok(client) => self.shutdown(&client).await,
+            // Dropping the child kills it.
+            Err(e) => warn!(error = %e, "cannot reach rclone daemon to stop it, killing it"),
+        }
+    }
+
+    /// Asks the daemon to exit through `client`, killing it if it
+    /// does not within a few seconds.
+    pub async fn shutdown(mut self, client: &RcloneClient) {
+        if let Err(e) = client.quit().await {
+            debug!(error = %e, "core/quit failed");
+        }
+        match tokio::time::timeout(SHUTDOWN_TIMEOUT, self.child.wait()).await {
+            Ok(Ok(status)) => info!(%status, "rclone daemon exited"),
+            Ok(Err(e)) => warn!(error = %e, "could not wait for rclone daemon"),
+            Err(_) => {
+                warn!("rclone daemon did not exit, killing it");
+                if let Err(e) = self.child.kill().await {
+                    warn!(error = %e, "could not kill rclone daemon");
+                }
+            }
+        }
+    }
+
+    async fn wait_until_listening(&mut self) -> Result<()> {
+        let failed = |message: String| LazyFileError::Daemon { message };
+        let address = (self.connection.host.as_str(), self.connection.port);
+        let deadline = tokio::time::Instant::now() + STARTUP_TIMEOUT;
+        loop {
+            if let Some(status) = self.child.try_wait()? {
+                return Err(failed(format!("rclone exited with {}", status)));
+            }
+            if TcpStream::connect(address).await.is_ok() {
+                return Ok(());
+            }
+            if tokio::time::Instant::now() >= deadline {
+                return Err(failed("rclone did not start listening in time".to_string()));
+            }
+            tokio::time::sleep(POLL_INTERVAL).await;
+        }
+    }
+}
+
+/// Returns whether anything accepts connections where `connection`
+/// points.
+pub async fn is_listening(connection: &Connection) -> bool {
+    #[cfg(unix)]
+    if let Some(ref socket) = connection.socket {
+        return tokio::net::UnixStream::connect(socket).await.is_ok();
+    }
+    TcpStream::connect((connection.host.as_str(), connection.port))
+        .await
+        .is_ok()
+}
+
+/// Saves the login of `connection` to `path` as `LAZYFILE_USER` and
+/// `LAZYFILE_PASS` lines, readable only by the owner on Unix, so a kept
+/// daemon can be reached again without its password being shown.
+///
+/// # Errors
+/// Returns `Io` if the file cannot be written.
+pub fn save_login(connection: &Connection, path: &Path) -> Result<()> {
+    let text = match connection.credentials {
+        Some(Credentials::Basic {
+            ref user,
+            ref password,
+        }) => format!("LAZYFILE_USER={}\nLAZYFILE_PASS={}\n", user, password),
+        _ => String::new(),
+    };
+    write_private(path, &text)
+}
+
+/// Returns where the rclone binary is on `PATH`, if anywhere.
+pub fn find_rclone() -> Option<PathBuf> {
+    find_program(RCLONE_BINARY, &std::env::var_os("PATH")?)
+}
+
+/// Returns the first file named `name`, plus the platform's executable
+/// suffix, in the directories of `path`.
+fn find_program(name: &str, path: &OsStr) -> Option<PathBuf> {
+    let file = format!("{}{}", name, std::env::consts::EXE_SUFFIX);
+    std::env::split_paths(path)
+        .map(|dir| dir.join(&file))
+        .find(|candidate| candidate.is_file())
+}
+
+/// Returns commands that install rclone on this platform, each with
+/// where it applies, ending with the page covering the rest.
+pub fn install_commands() -> &'static [(&'static str, &'static str)] {
+    if cfg!(target_os = "macos") {
+        &[
+            ("brew install rclone", "Homebrew"),
+            ("sudo port install rclone", "MacPorts"),
+            ("https://rclone.org/install/", "other ways"),
+        ]
+    } else if cfg!(target_os = "windows") {
+        &[
+            ("winget install Rclone.Rclone", "winget"),
+            ("scoop install rclone", "Scoop"),
+            ("choco install rclone", "Chocolatey"),
+            ("https://rclone.org/install/", "other ways"),
+        ]
+    } else if cfg!(target_os = "linux") {
+        &[
+            ("sudo apt install rclone", "Debian, Ubuntu"),
+            ("sudo dnf install rclone", "Fedora"),
+            ("sudo pacman -S rclone", "Arch"),
+            (
+                "curl https://rclone.org/install.sh | sudo bash",
+                "latest release",
+            ),
+            ("https://rclone.org/install/", "other ways"),
+        ]
+    } else {
+        &[("https://rclone.org/downloads/", "prebuilt binaries")]
+    }
+}
+
+/// Picks a loopback port nothing listens on right now.
+fn free_port() -> std::io::Result<u16> {
+    let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0))?;
+    Ok(listener.local_addr()?.port())
+}
+
+/// Returns 192 random bits as URL-safe text.
+fn random_password() -> Result<String> {
+    let mut bytes = [0u8; 24];
+    getrandom::fill(&mut bytes).map_err(|e| LazyFileError::Daemon {
+        message: format!("no randomness for the password: {}", e),
+    })?;
+    Ok(URL_SAFE_NO_PAD.encode(bytes))
+}
+
+/// Copies the daemon's log lines into tracing, so they show up in the
+/// log pane instead of over the TUI.
+async fn forward_logs(stderr: BufReader<tokio::process::ChildStderr>) {
+    let mut lines = stderr.lines();
+    while let Ok(Some(line)) = lines.next_line().await {
+        info!(target: "lazyfile::daemon::rclone", "{}", line);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_passwords_are_random() {
+        let first = random_password().unwrap();
+        assert_eq!(first.len(), 32);
+        assert_ne!(first, random_password().unwrap());
+    }
+
+    #[test]
+    fn test_free_port_is_bindable() {
+        let port = free_port().unwrap();
+        assert_ne!(port, 0);
+        assert!(TcpListener::bind((Ipv4Addr::LOCALHOST, port)).is_ok());
+    }
+
+    #[test]
+    fn test_finds_programs_on_path() {
+        let dir = std::env::temp_dir().join(format!("lazyfile-path-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let program = dir.join(format!("rclone{}", std::env::consts::EXE_SUFFIX));
+        std::fs::write(&program, "").unwrap();
+        let path = std::env::join_paths([PathBuf::from("/nonexistent"), dir.clone()]).unwrap();
+
+        assert_eq!(find_program("rclone", &path), Some(program));
+        assert_eq!(find_program("rsync-not-here", &path), None);
+        assert!(!install_commands().is_empty());
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn test_login_is_saved_for_reconnecting() {
+        let path = std::env::temp_dir().join(format!("lazyfile-login-{}", std::process::id()));
+        let connection = Connection {
+            credentials: Some(Credentials::Basic {
+                user: DAEMON_USER.to_string(),
+                password: "s3cret".to_string(),
+            }),
+            ..Connection::default()
+        };
+        save_login(&connection, &path).unwrap();
+        let text = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(text, "LAZYFILE_USER=lazyfile\nLAZYFILE_PASS=s3cret\n");
+    }
+
+    #[tokio::test]
+    async fn test_missing_binary_is_a_daemon_error() {
+        let err = Daemon::spawn_program("/nonexistent/rclone", false)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, LazyFileError::Daemon { .. }));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_early_exit_is_reported() {
+        let err = Daemon::spawn_program("false", false).await.unwrap_err();
+        assert!(err.to_string().contains("exited"), "{}", err);
+    }
+
+    #[tokio::test]
+    async fn test_nothing_listens_on_port_one() {
+        let connection = Connection {
+            host: "127.0.0.1".to_string(),
+            port: 1,
+            ..Connection::default()
+        };
+        assert!(!is_listening(&connection).await);
+    }
+}
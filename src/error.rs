@@ -1,5 +1,6 @@
 //! Error types for LazyFile.
 
+use std::path::PathBuf;
 use thiserror::Error;
 
 /// LazyFile error type.
@@ -16,16 +17,136 @@ pub enum LazyFileError {
         message: String,
     },
 
+    /// The rclone daemon answered with a non-success HTTP status.
+    #[error("rclone API error on {endpoint}: {status}: {body}")]
+    RcloneStatus {
+        endpoint: &'static str,
+        status: reqwest::StatusCode,
+        body: String,
+    },
+
+    /// A request was cancelled before the daemon answered.
+    #[error("request to {endpoint} was cancelled")]
+    Cancelled { endpoint: &'static str },
+
     /// HTTP request error.
     #[error("HTTP error: {0}")]
     Http(#[from] reqwest::Error),
 
+    /// The daemon's HTTPS certificate failed verification.
+    #[error(
+        "the rclone daemon's certificate is not trusted ({reason}); pass --ca-cert with the CA \
+         that signed it, or --insecure to skip verification"
+    )]
+    UntrustedCertificate { reason: String },
+
+    /// A `--ca-cert`, `--client-cert` or `--client-key` file could
+    /// not be read or does not hold what it should.
+    #[error("invalid certificate file {}: {message}", path.display())]
+    Certificate { path: PathBuf, message: String },
+
     /// User input rejected before reaching the rclone API.
     #[error("invalid {field}: {reason}")]
     InvalidInput {
         field: &'static str,
         reason: &'static str,
     },
+
+    /// Config file could not be read or parsed.
+    #[error("invalid config file {}: {message}", path.display())]
+    Config { path: PathBuf, message: String },
+
+    /// Config file could not be written back.
+    #[error("could not write config file {}: {message}", path.display())]
+    ConfigWrite { path: PathBuf, message: String },
+
+    /// State file could not be read, parsed or written.
+    #[error("invalid state file {}: {message}", path.display())]
+    State { path: PathBuf, message: String },
+
+    /// Transfer history could not be read or appended to.
+    #[error("could not use transfer history {}: {message}", path.display())]
+    History { path: PathBuf, message: String },
+
+    /// Audit log could not be read or appended to.
+    #[error("could not use audit log {}: {message}", path.display())]
+    Audit { path: PathBuf, message: String },
+
+    /// The file given to `--log-file` could not be opened.
+    #[error("could not open log file {}: {message}", path.display())]
+    LogFile { path: PathBuf, message: String },
+
+    /// A file given to `lazyfile import` is not a backup of remotes.
+    #[error("invalid backup file {}: {message}", path.display())]
+    Backup { path: PathBuf, message: String },
+
+    /// A key binding in the config file could not be parsed.
+    #[error("invalid key binding '{keys}' for {action}: {reason}")]
+    InvalidKeyBinding {
+        action: String,
+        keys: String,
+        reason: &'static str,
+    },
+
+    /// Two actions reachable from the same context share a key
+    /// sequence, or one sequence is a prefix of the other.
+    #[error("key '{keys}' is bound to both {first} and {second}")]
+    KeyConflict {
+        keys: String,
+        first: &'static str,
+        second: &'static str,
+    },
+
+    /// An `rclone rcd` child process could not be started.
+    #[error("could not start rclone daemon: {message}")]
+    Daemon { message: String },
+
+    /// A user script failed to compile or run.
+    #[error("script {name} failed: {message}")]
+    Script { name: String, message: String },
+
+    /// A profile was requested that the config file does not define.
+    #[error("unknown profile '{name}': add a [profiles.{name}] table to the config file")]
+    UnknownProfile { name: String },
+
+    /// A `[theme]` entry in the config file names an unknown slot or
+    /// preset, or is not a color.
+    #[error("invalid theme entry {key} = '{value}': {reason}")]
+    InvalidTheme {
+        key: String,
+        value: String,
+        reason: &'static str,
+    },
+}
+
+/// What rclone's errors say when the config file is encrypted and no
+/// password was given, or the password was wrong.
+const CONFIG_LOCKED: [&str; 2] = ["decrypt configuration", "RCLONE_CONFIG_PASS"];
+
+impl LazyFileError {
+    /// Returns whether the daemon refused the call because its config
+    /// file is encrypted and still locked.
+    pub fn is_config_locked(&self) -> bool {
+        match self {
+            Self::RcloneStatus { body, .. } => CONFIG_LOCKED.iter().any(|text| body.contains(text)),
+            _ => false,
+        }
+    }
+
+    /// Returns whether the error comes from a single failed request
+    /// that the session can survive, as opposed to a broken terminal
+    /// or config.
+    pub fn is_recoverable(&self) -> bool {
+        matches!(
+            self,
+            Self::RcloneApi { .. }
+                | Self::RcloneStatus { .. }
+                | Self::Cancelled { .. }
+                | Self::UntrustedCertificate { .. }
+                | Self::Http(_)
+                | Self::InvalidInput { .. }
+        )
+    }
 }
 
 pub type Result<T> = std::result::Result<T, LazyFileError>;
@@ -0,0 +1,351 @@
+//! Single operations run without the TUI, for scripts.
+//!
+//! `lazyfile ls gdrive:docs` and friends go through the same
+//! [`RcloneClient`] calls as the TUI, against whichever daemon the
+//! connection flags or profile point at. With `--json`, results and
+//! failures are printed as JSON for tools like jq.
+//!
+//! `rcat` streams stdin into a remote file, so backups can be piped
+//! through the daemon with LazyFile's saved login. `cat` streams a
+//! file the other way, to stdout, for a quick look through `less` or
+//! `tar tz`.
+//!
+//! `export` and `import` back up a daemon's remotes to a file and
+//! restore them, e.g. onto another daemon.
+
+use crate::cli::{Command, RemotePath};
+use crate::config::write_private;
+use crate::error::{LazyFileError, Result};
+use crate::rclone::backup::{self, Format};
+use crate::rclone::{FileItem, RcloneClient};
+use serde_json::{Value, json};
+use std::io::{IsTerminal, Write};
+use std::path::Path;
+use tracing::{debug, info};
+
+/// What a command produced.
+#[derive(Debug)]
+enum Outcome {
+    /// Entries of a listed directory.
+    Listing(Vec<FileItem>),
+    /// Remotes created from a backup, and those skipped because the
+    /// daemon already had a remote of that name.
+    Imported {
+        created: Vec<String>,
+        skipped: Vec<String>,
+    },
+    /// Bytes streamed into a file.
+    Streamed(u64),
+    /// A file's contents, already streamed to stdout, so nothing else
+    /// is written.
+    Contents,
+    /// An operation that produces no output succeeded.
+    Done,
+}
+
+/// Runs `command` against the daemon, writing its result to `out`,
+/// as JSON when `json` is set.
+///
+/// In JSON mode a listing is an array in the shape of `rclone
+/// lsjson`; anything else, including a failure, is an object with an
+/// `ok` field. `cat` writes the file itself, even in JSON mode,
+/// unless it fails.
+///
+/// # Errors
+/// Returns error if the daemon rejects or cannot be sent the request,
+/// or `out` cannot be written.
+pub async fn run(
+    client: &RcloneClient,
+    command: Command,
+    json: bool,
+    out: &mut impl Write,
+) -> Result<()> {
+    debug!(command = ?command, json, "running headless command");
+    let result = execute(client, &command).await;
+    match (result, json) {
+        (Ok(Outcome::Listing(items)), false) => {
+            for item in items {
+                let suffix = if item.is_dir() { "/" } else { "" };
+                writeln!(out, "{}{}", item.name(), suffix)?;
+            }
+        }
+        (Ok(Outcome::Imported { created, skipped }), false) => {
+            for name in created {
+                writeln!(out, "created {}", name)?;
+            }
+            for name in skipped {
+                writeln!(out, "skipped {}: already exists", name)?;
+            }
+        }
+        (Ok(Outcome::Streamed(_) | Outcome::Done), false) | (Ok(Outcome::Contents), _) => {}
+        (Ok(Outcome::Listing(items)), true) => write_json(out, &json!(items))?,
+        (Ok(Outcome::Imported { created, skipped }), true) => {
+            let mut report = report(&command, None);
+            report["created"] = json!(created);
+            report["skipped"] = json!(skipped);
+            write_json(out, &report)?;
+        }
+        (Ok(Outcome::Streamed(bytes)), true) => {
+            let mut report = report(&command, None);
+            report["bytes"] = json!(bytes);
+            write_json(out, &report)?;
+        }
+        (Ok(Outcome::Done), true) => write_json(out, &report(&command, None))?,
+        (Err(err), false) => return Err(err),
+        (Err(err), true) => {
+            write_json(out, &report(&command, Some(&err.to_string())))?;
+            out.flush()?;
+            return Err(err);
+        }
+    }
+    out.flush()?;
+    Ok(())
+}
+
+async fn execute(client: &RcloneClient, command: &Command) -> Result<Outcome> {
+    match command {
+        Command::Ls { path } => Ok(Outcome::Listing(
+            client.list_files(&path.remote, &path.path).await?,
+        )),
+        Command::Cp { src, dst } => {
+            let dst_path = destination(src, dst);
+            client
+                .copy_file(&src.remote, &src.path, &dst.remote, &dst_path)
+                .await?;
+            Ok(Outcome::Done)
+        }
+        Command::Mv { src, dst } => {
+            let dst_path = destination(src, dst);
+            client
+                .move_file(&src.remote, &src.path, &dst.remote, &dst_path)
+                .await?;
+            Ok(Outcome::Done)
+        }
+        Command::Rm {
+            path,
+            recursive: false,
+        } => {
+            client.delete_file(&path.remote, &path.path).await?;
+            Ok(Outcome::Done)
+        }
+        Command::Rm {
+            path,
+            recursive: true,
+        } => {
+            client.purge(&path.remote, &path.path).await?;
+            Ok(Outcome::Done)
+        }
+        Command::Rcat { dst } => {
+            if dst.path.is_empty() || dst.path.ends_with('/') {
+                return Err(LazyFileError::InvalidInput {
+                    field: "rcat destination",
+                    reason: "must name a file, not a directory",
+                });
+            }
+            if std::io::stdin().is_terminal() {
+                return Err(LazyFileError::InvalidInput {
+                    field: "stdin",
+                    reason: "pipe the data to upload into rcat",
+                });
+            }
+            let bytes = client
+                .upload_stream(&dst.remote, &dst.path, tokio::io::stdin())
+                .await?;
+            info!(bytes, dst = %dst, "streamed stdin");
+            Ok(Outcome::Streamed(bytes))
+        }
+        Command::Cat { src, range } => {
+            let bytes = client
+                .cat_file(&src.remote, &src.path, *range, &mut tokio::io::stdout())
+                .await?;
+            info!(bytes, src = %src, "streamed file");
+            Ok(Outcome::Contents)
+        }
+        Command::Export { file } => {
+            let configs = client.dump_config().await?;
+            let text = backup::render(&configs, Format::from_path(file));
+            // A backup holds the remotes' credentials, and rclone's
+            // obscuring is not encryption.
+            write_private(file, &text)?;
+            info!(count = configs.len(), file = %file.display(), "exported remotes");
+            Ok(Outcome::Done)
+        }
+        Command::Import { file } => import(client, file).await,
+    }
+}
+
+/// Creates the remotes saved in backup `file` that the daemon does
+/// not have yet. A failure stops the import; remotes created before
+/// it are kept.
+async fn import(client: &RcloneClient, file: &Path) -> Result<Outcome> {
+    let invalid = |message: String| LazyFileError::Backup {
+        path: file.to_path_buf(),
+        message,
+    };
+    let text = std::fs::read_to_string(file).map_err(|e| invalid(e.to_string()))?;
+    let configs = backup::parse(&text).map_err(invalid)?;
+    let existing = client.list_remotes().await?;
+
+    let mut created = Vec::new();
+    let mut skipped = Vec::new();
+    for (name, mut config) in configs {
+        if existing.contains(&name) {
+            skipped.push(name);
+            continue;
+        }
+        // `backup::parse` only accepts remotes that have a type.
+        let remote_type = config.remove("type").unwrap_or_default();
+        client
+            .restore_remote(&name, &remote_type, config.into_iter().collect())
+            .await?;
+        created.push(name);
+    }
+    info!(
+        created = created.len(),
+        skipped = skipped.len(),
+        "imported remotes"
+    );
+    Ok(Outcome::Imported { created, skipped })
+}
+
+/// Builds the JSON object describing an operation and, if it failed,
+/// why.
+fn report(command: &Command, error: Option<&str>) -> Value {
+    let mut report = json!({
+        "ok": error.is_none(),
+        "command": command.name(),
+    });
+    match command {
+        Command::Ls { path }
+        | Command::Rm { path, .. }
+        | Command::Rcat { dst: path }
+        | Command::Cat { src: path, .. } => {
+            report["path"] = json!(path.to_string());
+        }
+        Command::Cp { src, dst } | Command::Mv { src, dst } => {
+            report["src"] = json!(src.to_string());
+            report["dst"] = json!(format!("{}:{}", dst.remote, destination(src, dst)));
+        }
+        Command::Export { file } | Command::Import { file } => {
+            report["file"] = json!(file.display().to_string());
+        }
+    }
+    if let Some(error) = error {
+        report["error"] = json!(error);
+    }
+    report
+}
+
+fn write_json(out: &mut impl Write, value: &Value) -> Result<()> {
+    serde_json::to_writer(&mut *out, value).map_err(std::io::Error::from)?;
+    writeln!(out)?;
+    Ok(())
+}
+
+/// Returns the path a transfer writes to: `dst` itself, or the source
+/// file's name inside `dst` when that names a directory (a remote's
+/// root or a path ending in `/`).
+fn destination(src: &RemotePath, dst: &RemotePath) -> String {
+    if !dst.path.is_empty() && !dst.path.ends_with('/') {
+        return dst.path.clone();
+    }
+    let name = src.path.rsplit('/').next().unwrap_or_default();
+    format!("{}{}", dst.path, name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::LazyFileError;
+
+    fn remote_path(value: &str) -> RemotePath {
+        value.parse().unwrap()
+    }
+
+    #[test]
+    fn test_destination_keeps_explicit_name() {
+        let src = remote_path("gdrive:docs/a.txt");
+        assert_eq!(destination(&src, &remote_path("s3:b.txt")), "b.txt");
+    }
+
+    #[test]
+    fn test_destination_directory_keeps_source_name() {
+        let src = remote_path("gdrive:docs/a.txt");
+        assert_eq!(
+            destination(&src, &remote_path("s3:backup/")),
+            "backup/a.txt"
+        );
+        assert_eq!(destination(&src, &remote_path("s3:")), "a.txt");
+    }
+
+    #[test]
+    fn test_report_names_resolved_destination() {
+        let command = Command::Cp {
+            src: remote_path("gdrive:docs/a.txt"),
+            dst: remote_path("s3:backup/"),
+        };
+        assert_eq!(
+            report(&command, None),
+            json!({
+                "ok": true,
+                "command": "cp",
+                "src": "gdrive:docs/a.txt",
+                "dst": "s3:backup/a.txt",
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_unreachable_daemon_is_an_error() {
+        let client = RcloneClient::new("localhost", 1).unwrap();
+        let mut out = Vec::new();
+        let command = Command::Ls {
+            path: remote_path("gdrive:"),
+        };
+        let err = run(&client, command, false, &mut out).await.unwrap_err();
+        assert!(matches!(err, LazyFileError::RcloneApi { .. }), "{}", err);
+        assert!(out.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_import_rejects_malformed_backup_before_request() {
+        let client = RcloneClient::new("localhost", 1).unwrap();
+        let file =
+            std::env::temp_dir().join(format!("lazyfile-backup-{}.conf", std::process::id()));
+        std::fs::write(&file, "[s3]\nprovider = AWS\n").unwrap();
+        let mut out = Vec::new();
+        let command = Command::Import { file: file.clone() };
+
+        let err = run(&client, command, false, &mut out).await.unwrap_err();
+        std::fs::remove_file(&file).unwrap();
+        assert!(matches!(err, LazyFileError::Backup { .. }), "{}", err);
+    }
+
+    #[tokio::test]
+    async fn test_rcat_needs_a_file_name() {
+        let client = RcloneClient::new("localhost", 1).unwrap();
+        let mut out = Vec::new();
+        let command = Command::Rcat {
+            dst: remote_path("s3:backups/"),
+        };
+        let err = run(&client, command, false, &mut out).await.unwrap_err();
+        assert!(matches!(err, LazyFileError::InvalidInput { .. }), "{}", err);
+    }
+
+    #[tokio::test]
+    async fn test_json_mode_reports_failure_on_stdout() {
+        let client = RcloneClient::new("localhost", 1).unwrap();
+        let mut out = Vec::new();
+        let command = Command::Rm {
+            path: remote_path("gdrive:old"),
+            recursive: false,
+        };
+        assert!(run(&client, command, true, &mut out).await.is_err());
+
+        let printed: Value = serde_json::from_slice(&out).unwrap();
+        assert_eq!(printed["ok"], false);
+        assert_eq!(printed["command"], "rm");
+        assert_eq!(printed["path"], "gdrive:old");
+        assert!(printed["error"].is_string());
+    }
+}
@@ -1,81 +1,539 @@
 //! Application init
 
-use crate::app::{App, Handler};
+use crate::app::command::ShellCommand;
+use crate::app::{App, EditSession, Handler};
 use crate::error::Result;
-use crate::ui::Layout;
-use crossterm::event::{self, Event};
+use crossterm::event::{
+    self, DisableBracketedPaste, DisableMouseCapture, EnableBracketedPaste, EnableMouseCapture,
+    Event,
+};
+use crossterm::execute;
+use crossterm::terminal::{
+    EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode,
+};
+use ratatui::layout::Rect;
 use ratatui::{DefaultTerminal, Frame};
+use std::future::Future;
+use std::io::{self, Write as _};
+use std::process::ExitStatus;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::sync::mpsc::{self, UnboundedReceiver};
+use tokio::task::JoinHandle;
+
+/// How long to wait before checking timers (toasts, pings, job
+/// refreshes) when nothing happens and nothing is loading.
+const IDLE_TICK: Duration = Duration::from_millis(200);
+
+/// How often the input reader checks whether it should stop.
+const INPUT_POLL: Duration = Duration::from_millis(50);
+
+/// Terminal input, read on a blocking thread and forwarded over a
+/// channel so the event loop never waits on the terminal.
+struct Input {
+    events: UnboundedReceiver<io::Result<Event>>,
+    stop: Arc<AtomicBool>,
+    reader: Option<JoinHandle<()>>,
+}
+
+impl Input {
+    /// Starts reading input.
+    fn start() -> Self {
+        let (tx, events) = mpsc::unbounded_channel();
+        let stop = Arc::new(AtomicBool::new(false));
+        let flag = Arc::clone(&stop);
+        let reader = tokio::task::spawn_blocking(move || {
+            while !flag.load(Ordering::Relaxed) {
+                match event::poll(INPUT_POLL) {
+                    Ok(false) => {}
+                    Ok(true) => {
+                        if tx.send(event::read()).is_err() {
+                            break;
+                        }
+                    }
+                    Err(e) => {
+                        let _ = tx.send(Err(e));
+                        break;
+                    }
+                }
+            }
+        });
+        Self {
+            events,
+            stop,
+            reader: Some(reader),
+        }
+    }
+
+    /// Stops reading and waits for the reader to finish, so a program
+    /// given the terminal gets every keystroke.
+    async fn stop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+        if let Some(reader) = self.reader.take() {
+            let _ = reader.await;
+        }
+    }
+}
 
 /// Main
 async fn run_app(terminal: &mut DefaultTerminal, app: &mut App) -> Result<()> {
+    let mut input = Input::start();
+    let result = event_loop(terminal, app, &mut input).await;
+    input.stop().await;
+    result
+}
+
+/// Draws and handles events until the app quits.
+///
+/// Input, results from the worker pool and the render tick each wake
+/// the loop; none of them waits on the daemon, so a slow call never
+/// holds up a frame. A frame is only drawn when
+/// [`App::needs_redraw`] says the screen changed.
+async fn event_loop(
+    terminal: &mut DefaultTerminal,
+    app: &mut App,
+    input: &mut Input,
+) -> Result<()> {
     while app.running {
-        terminal.draw(|f| ui_render(f, app))?;
+        while let Some(event) = app.next_event() {
+            Handler::handle_app_event(app, event);
+            app.dirty = true;
+        }
+        if let Some(session) = app.pending_edit.take() {
+            let status = run_editor(terminal, input, &session).await?;
+            Handler::finish_edit(app, session, status);
+            app.dirty = true;
+        }
+        if let Some(command) = app.pending_command.take() {
+            let status = run_command(terminal, input, &command).await?;
+            Handler::finish_command(app, &command.name, status);
+            app.dirty = true;
+        }
+        let now = Instant::now();
+        app.dirty |= app.notifications.prune(now);
+        app.tick_health(now);
+        app.tick_activity(now);
+        app.tick_jobs(now);
+        app.tick_quit(now);
+        app.tick_prefetch(now);
+        let size = terminal.size()?;
+        app.set_screen(Rect::new(0, 0, size.width, size.height));
+        app.sync_logs();
+        if app.needs_redraw(now) {
+            terminal.draw(|f| ui_render(f, app))?;
+            app.mark_drawn(now);
+        }
 
-        if crossterm::event::poll(std::time::Duration::from_millis(200))?
-            && let Event::Key(key) = event::read()?
-        {
-            Handler::handle_key(app, key).await?;
+        // Tick faster while a listing is in flight so the spinner
+        // animates.
+        let tick = if app.loading.is_some() {
+            crate::ui::widgets::spinner::FRAME_TIME
+        } else {
+            IDLE_TICK
+        };
+        tokio::select! {
+            Some(event) = input.events.recv() => {
+                Handler::handle_event(app, event?).await?;
+                app.dirty = true;
+            }
+            Some(event) = app.wait_event() => {
+                Handler::handle_app_event(app, event);
+                app.dirty = true;
+            }
+            () = tokio::time::sleep(tick) => {}
         }
     }
 
     tracing::debug!("Application exiting");
+    app.save_session();
     Ok(())
 }
 
+/// Hands the terminal to the editor for the session's local copy and
+/// takes it back once the editor exits.
+///
+/// The outer error means the terminal could not be suspended or
+/// restored; the inner one that the editor could not be started.
+async fn run_editor(
+    terminal: &mut DefaultTerminal,
+    input: &mut Input,
+    session: &EditSession,
+) -> Result<io::Result<ExitStatus>> {
+    let (program, args) = crate::app::edit::editor_command();
+    tracing::info!(editor = %program, path = %session.path, "opening editor");
+
+    let editor = tokio::process::Command::new(&program)
+        .args(&args)
+        .arg(&session.local)
+        .status();
+    suspended(terminal, input, editor).await
+}
+
+/// Runs a user command in the shell with the terminal handed over,
+/// and waits for Enter afterwards so its output can be read.
+///
+/// Errors are split as in [`run_editor`].
+async fn run_command(
+    terminal: &mut DefaultTerminal,
+    input: &mut Input,
+    command: &ShellCommand,
+) -> Result<io::Result<ExitStatus>> {
+    let (shell, flag) = crate::app::command::shell();
+    tracing::info!(command = %command.name, line = %command.line, "running user command");
+
+    suspended(terminal, input, async {
+        let mut stdout = tokio::io::stdout();
+        let _ = stdout
+            .write_all(format!("$ {}\n", command.line).as_bytes())
+            .await;
+        let _ = stdout.flush().await;
+        let status = tokio::process::Command::new(shell)
+            .arg(flag)
+            .arg(&command.line)
+            .status()
+            .await;
+        let outcome = match status {
+            Ok(ref status) => status.to_string(),
+            Err(ref e) => e.to_string(),
+        };
+        let _ = stdout
+            .write_all(format!("\n[{}] Press Enter to return to lazyfile", outcome).as_bytes())
+            .await;
+        let _ = stdout.flush().await;
+        let _ = BufReader::new(tokio::io::stdin())
+            .read_line(&mut String::new())
+            .await;
+        status
+    })
+    .await
+}
+
+/// Leaves the TUI for the duration of `run`, then takes the terminal
+/// back. Input is not read meanwhile.
+async fn suspended<T>(
+    terminal: &mut DefaultTerminal,
+    input: &mut Input,
+    run: impl Future<Output = T>,
+) -> Result<T> {
+    input.stop().await;
+    execute!(
+        io::stdout(),
+        DisableBracketedPaste,
+        DisableMouseCapture,
+        LeaveAlternateScreen
+    )?;
+    disable_raw_mode()?;
+    let output = run.await;
+    enable_raw_mode()?;
+    execute!(
+        io::stdout(),
+        EnterAlternateScreen,
+        EnableMouseCapture,
+        EnableBracketedPaste
+    )?;
+    *input = Input::start();
+    // Whatever ran drew over the screen ratatui thinks it still shows.
+    terminal.clear()?;
+    Ok(output)
+}
+
+/// Returns what the help line describes: the open modal, by its
+/// heading in the help overlay, or else the focused panel.
+fn help_context(app: &App) -> crate::ui::HelpContext {
+    use crate::app::ActiveModal;
+    let Some(ref modal) = app.modal else {
+        return crate::ui::HelpContext::Panel(app.focused_panel);
+    };
+    crate::ui::HelpContext::Modal(match modal {
+        ActiveModal::CreateRemote(_)
+        | ActiveModal::ConfigWizard(_)
+        | ActiveModal::ConfigPassword(_)
+        | ActiveModal::ConfirmDeleteRemote { .. }
+        | ActiveModal::FileOperation(_)
+        | ActiveModal::Quit(_) => "Forms and confirmations",
+        ActiveModal::CopyRemote(_) => "Rename / duplicate remote",
+        ActiveModal::BatchRename(_) => "Batch rename",
+        ActiveModal::Filter(_) => "Filter",
+        ActiveModal::GoTo(_) => "Go to",
+        ActiveModal::Recent(_) => "Recent locations",
+        ActiveModal::MarkGlob(_) => "Mark matching",
+        ActiveModal::Tier(_) => "Change storage tier",
+        ActiveModal::Metadata(_) => "Object metadata",
+        ActiveModal::Versions(_) => "File versions",
+        ActiveModal::CopyUrl(_) => "Copy URL",
+        ActiveModal::Conflict(_) => "File exists",
+        ActiveModal::Help { .. } | ActiveModal::Preview(_) => "Help and preview",
+        ActiveModal::CommandPalette(_) => "Command palette",
+        ActiveModal::RemoteInfo(_) => "Remote details",
+        ActiveModal::Mounts(_) => "Mounts",
+        ActiveModal::Jobs(_) => "Jobs",
+        ActiveModal::History(_) => "Transfer history",
+        ActiveModal::Audit(_) => "Audit log",
+        ActiveModal::DaemonSetup(_) => "rclone daemon",
+        ActiveModal::Usage(_) => "Disk usage",
+        ActiveModal::Largest(_) => "Largest files",
+        ActiveModal::Compare(_) => "Compare",
+        ActiveModal::Error { .. } => "Errors",
+    })
+}
+
 /// Render the UI frame.
 fn ui_render(f: &mut Frame, app: &App) {
-    let rects = Layout::split(f.area());
+    let rects = app.layout(f.area());
 
-    crate::ui::HelpWidget::render(f, rects.help);
+    if let Some(area) = rects.help {
+        crate::ui::HelpWidget::render(f, area, &app.keymap, help_context(app), &app.theme);
+    }
 
     crate::ui::RemoteListWidget::render(
         f,
         rects.remotes,
         &app.remotes,
+        crate::ui::RemoteDetails {
+            types: &app.remote_types,
+            favorites: &app.remote_layout.favorites,
+            icons: app.icons,
+            quotas: &app.quotas,
+            quota: &app.quota,
+        },
+        app.remotes_offset,
         app.remotes_selected,
         matches!(app.focused_panel, crate::app::state::Panel::Remotes),
+        &app.theme,
     );
 
     crate::ui::FileListWidget::render(
         f,
         rects.files,
         &app.files,
+        app.files_offset,
         app.files_selected,
+        &app.marked,
+        &app.filter,
+        app.flat.as_ref(),
+        (!app.compact).then_some(crate::ui::ColumnView {
+            config: &app.file_list,
+            exact: app.exact,
+        }),
+        crate::ui::NameView {
+            icons: app.icons,
+            colors: &app.file_colors,
+            tree: app.tree.enabled.then_some(&app.tree),
+        },
+        app.offline().then_some(app.queued.len()),
         matches!(app.focused_panel, crate::app::state::Panel::Files),
+        &app.theme,
     );
 
+    if let Some(area) = rects.logs {
+        let view = crate::ui::LogView {
+            offset: app.logs_offset,
+            selected: app.logs_selected,
+            focused: matches!(app.focused_panel, crate::app::state::Panel::Logs),
+            follow: app.logs_follow,
+        };
+        crate::ui::LogsWidget::render(f, area, &app.logs, view, &app.theme);
+    }
+
+    if let Some(ref loading) = app.loading {
+        crate::ui::SpinnerWidget::render(
+            f,
+            rects.files,
+            loading.started.elapsed(),
+            "Loading",
+            &app.theme,
+        );
+    }
+
     crate::ui::StatusBarWidget::render(
         f,
         rects.status,
-        app.current_remote.as_deref(),
-        &app.current_path,
-        app.connected,
+        &app.status_bar.segments,
+        &crate::ui::StatusView {
+            remote: app.current_remote.as_deref(),
+            path: &app.current_path,
+            connected: app.connected,
+            retry_in: app.health.retry_in(Instant::now()),
+            retrying: app.client.retrying(),
+            marked: app.marked.len(),
+            speed: app.activity.speed,
+            jobs: app.activity.jobs,
+        },
+        &app.theme,
     );
 
+    if app.calls_visible {
+        let area = Rect {
+            height: rects.status.y.saturating_sub(rects.remotes.y),
+            y: rects.remotes.y,
+            ..f.area()
+        };
+        crate::ui::CallsWidget::render(f, area, &app.client.calls(), &app.theme);
+    }
+
     if let Some(ref modal) = app.modal {
         match modal {
             crate::app::ActiveModal::FileOperation(m) => {
-                crate::ui::FileOperationsWidget::render(f, f.area(), m);
+                crate::ui::FileOperationsWidget::render(f, f.area(), m, &app.theme);
+            }
+            crate::app::ActiveModal::BatchRename(v) => {
+                crate::ui::BatchRenameWidget::render(f, f.area(), v, &app.theme);
+            }
+            crate::app::ActiveModal::GoTo(p) => {
+                crate::ui::GoToWidget::render(f, f.area(), p, &app.theme);
+            }
+            crate::app::ActiveModal::Recent(p) => {
+                crate::ui::RecentWidget::render(f, f.area(), p, &app.theme);
+            }
+            crate::app::ActiveModal::Filter(form) => {
+                crate::ui::FilterWidget::render(f, f.area(), form, &app.theme);
+            }
+            crate::app::ActiveModal::MarkGlob(p) => {
+                crate::ui::GlobPromptWidget::render(f, f.area(), p, &app.theme);
+            }
+            crate::app::ActiveModal::Tier(p) => {
+                crate::ui::TierWidget::render(f, f.area(), p, &app.theme);
+            }
+            crate::app::ActiveModal::Metadata(v) => {
+                crate::ui::MetadataWidget::render(f, f.area(), v, &app.theme);
+            }
+            crate::app::ActiveModal::Versions(v) => {
+                crate::ui::VersionsWidget::render(f, f.area(), v, &app.theme);
+            }
+            crate::app::ActiveModal::CopyUrl(p) => {
+                crate::ui::CopyUrlWidget::render(f, f.area(), p, &app.theme);
+            }
+            crate::app::ActiveModal::Conflict(m) => {
+                crate::ui::ConflictWidget::render(f, f.area(), m, &app.theme);
             }
             crate::app::ActiveModal::ConfirmDeleteRemote { modal: m, .. } => {
-                crate::ui::ConfirmWidget::render(f, f.area(), m);
+                crate::ui::ConfirmWidget::render(f, f.area(), m, &app.theme);
             }
             crate::app::ActiveModal::CreateRemote(m) => {
-                crate::ui::CreateRemoteWidget::render(f, f.area(), m);
+                crate::ui::CreateRemoteWidget::render(f, f.area(), m, &app.theme);
+            }
+            crate::app::ActiveModal::ConfigWizard(w) => {
+                crate::ui::ConfigWizardWidget::render(f, f.area(), w, &app.theme);
+            }
+            crate::app::ActiveModal::Help { scroll } => {
+                crate::ui::HelpOverlayWidget::render(f, f.area(), &app.keymap, *scroll, &app.theme);
+            }
+            crate::app::ActiveModal::CommandPalette(p) => {
+                crate::ui::CommandPaletteWidget::render(f, f.area(), p, &app.keymap, &app.theme);
+            }
+            crate::app::ActiveModal::ConfigPassword(m) => {
+                crate::ui::ConfigPasswordWidget::render(f, f.area(), m, &app.theme);
+            }
+            crate::app::ActiveModal::RemoteInfo(view) => {
+                crate::ui::RemoteInfoWidget::render(f, f.area(), view, &app.theme);
+            }
+            crate::app::ActiveModal::CopyRemote(m) => {
+                crate::ui::CopyRemoteWidget::render(f, f.area(), m, &app.theme);
+            }
+            crate::app::ActiveModal::Mounts(v) => {
+                crate::ui::MountsWidget::render(f, f.area(), v, &app.theme);
+            }
+            crate::app::ActiveModal::Jobs(v) => {
+                crate::ui::JobsWidget::render(f, f.area(), v, &app.theme);
+            }
+            crate::app::ActiveModal::History(v) => {
+                crate::ui::HistoryWidget::render(f, f.area(), v, &app.theme);
+            }
+            crate::app::ActiveModal::Audit(v) => {
+                crate::ui::AuditWidget::render(f, f.area(), v, &app.theme);
+            }
+            crate::app::ActiveModal::DaemonSetup(v) => {
+                crate::ui::DaemonSetupWidget::render(f, f.area(), v, &app.theme);
+            }
+            crate::app::ActiveModal::Compare(v) => {
+                crate::ui::CompareWidget::render(f, f.area(), v, &app.theme);
+            }
+            crate::app::ActiveModal::Usage(v) => {
+                crate::ui::UsageWidget::render(f, f.area(), v, &app.theme);
+            }
+            crate::app::ActiveModal::Largest(v) => {
+                crate::ui::LargestWidget::render(f, f.area(), v, &app.theme);
+            }
+            crate::app::ActiveModal::Quit(m) => {
+                crate::ui::QuitWidget::render(f, f.area(), m, &app.theme);
+            }
+            crate::app::ActiveModal::Preview(p) => {
+                crate::ui::PreviewWidget::render(f, f.area(), p, &app.theme);
+            }
+            crate::app::ActiveModal::Error { modal: m, .. } => {
+                crate::ui::ErrorWidget::render(f, f.area(), m, &app.theme);
             }
         }
     }
+
+    // Toasts sit above everything, including modals, but leave the
+    // status bar visible.
+    let toast_area = Rect {
+        height: rects.status.y.saturating_sub(rects.remotes.y),
+        y: rects.remotes.y,
+        ..f.area()
+    };
+    crate::ui::NotificationsWidget::render(f, toast_area, &app.notifications, &app.theme);
+}
+
+/// Leaves a daemon LazyFile started running once the terminal is
+/// restored, and says how to reconnect. Its login goes to a private
+/// file next to the state file rather than to the terminal, whose
+/// scrollback and logs would keep the password. A daemon whose login
+/// cannot be saved could not be reached again, so it is stopped.
+async fn keep_daemon(daemon: crate::daemon::Daemon, state_path: Option<&std::path::Path>) {
+    let Some(dir) = state_path.and_then(std::path::Path::parent) else {
+        tracing::warn!("no state directory for the rclone daemon login, stopping it");
+        daemon.stop().await;
+        return;
+    };
+    let login = dir.join(crate::daemon::LOGIN_FILE);
+    let connection = daemon.connection();
+    let saved = std::fs::create_dir_all(dir)
+        .map_err(Into::into)
+        .and_then(|()| crate::daemon::save_login(connection, &login));
+    if let Err(e) = saved {
+        tracing::warn!(error = %e, "could not save the rclone daemon login, stopping it");
+        daemon.stop().await;
+        return;
+    }
+    tracing::info!(address = %connection.address(), "leaving rclone daemon running");
+    let login = crate::app::command::shell_quote(&login.display().to_string());
+    // The TUI is gone by now, so this no longer draws over it.
+    let _ = writeln!(
+        io::stderr(),
+        "rclone rcd is still running on {}; its login is in {}. Reconnect with:\n  \
+         env $(cat {}) lazyfile --host {} --port {}",
+        connection.address(),
+        login,
+        login,
+        connection.host,
+        connection.port
+    );
 }
 
 /// Start app.
 pub async fn start(mut app: App) -> Result<()> {
     // try_init/try_restore keep setup errors in the Result chain
-    // instead of panicking. Mouse capture is intentionally not
-    // enabled so the terminal keeps native text selection.
+    // instead of panicking. With mouse capture on, most terminals
+    // still offer native text selection with Shift held. Bracketed
+    // paste delivers a paste as one event rather than as keys.
     let mut terminal = ratatui::try_init()?;
-    let res = run_app(&mut terminal, &mut app).await;
+    let res = match execute!(io::stdout(), EnableMouseCapture, EnableBracketedPaste) {
+        Ok(()) => run_app(&mut terminal, &mut app).await,
+        Err(e) => Err(e.into()),
+    };
+    let released = execute!(io::stdout(), DisableBracketedPaste, DisableMouseCapture);
     let restored = ratatui::try_restore();
+    match app.daemon.take() {
+        Some(daemon) if app.keep_daemon => keep_daemon(daemon, app.state_path.as_deref()).await,
+        Some(daemon) => daemon.stop().await,
+        None => {}
+    }
 
     // An app error takes precedence over a restore error.
-    res.and(restored.map_err(Into::into))
+    res.and(released.map_err(Into::into))
+        .and(restored.map_err(Into::into))
 }
@@ -1,11 +1,28 @@
 //! LazyFile - TUI file manager for cloud storage using rclone.
 //!
 //! This library provides the core functionality for the LazyFile application.
+//!
+//! The rclone client ([`rclone`]), config file ([`config`]), daemon
+//! handling ([`daemon`]) and errors are always built. Everything else
+//! is the terminal UI and needs the default `tui` feature; depend on
+//! the crate with `default-features = false` to reuse the typed
+//! [`rclone::RcloneClient`] without ratatui or crossterm.
 
+#[cfg(feature = "tui")]
 pub mod app;
+#[cfg(feature = "tui")]
 pub mod cli;
+#[cfg(feature = "tui")]
+pub mod clipboard;
 pub mod config;
+pub mod daemon;
 pub mod error;
+#[cfg(feature = "tui")]
+pub mod headless;
+#[cfg(feature = "tui")]
 pub mod launcher;
+#[cfg(feature = "tui")]
+pub mod logs;
 pub mod rclone;
+#[cfg(feature = "tui")]
 pub mod ui;
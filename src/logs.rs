@@ -0,0 +1,396 @@
+//! In-memory capture of `tracing` events for the in-app log pane,
+//! the rotating file behind `--log-file`, and switching both to debug
+//! while the TUI runs.
+
+use std::collections::VecDeque;
+use std::fmt::{self, Write as _};
+use std::fs::{self, File, OpenOptions};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use tracing::field::{Field, Visit};
+use tracing::{Event, Level, Subscriber};
+use tracing_subscriber::layer::{Context, Layer};
+
+/// Lines kept when no capacity is given.
+pub const DEFAULT_CAPACITY: usize = 1000;
+
+/// Filter for the log pane when `RUST_LOG` is unset. Debug events
+/// fire on every key press, which would drown out RC traffic.
+pub const DEFAULT_FILTER: &str = "lazyfile=info,warn";
+
+/// Filter while debug logging is switched on at runtime.
+pub const DEBUG_FILTER: &str = "lazyfile=debug,warn";
+
+/// Levels `--log-level` accepts, quietest first.
+pub const LEVELS: [&str; 5] = ["error", "warn", "info", "debug", "trace"];
+
+/// Size at which the log file is rotated.
+pub const MAX_LOG_BYTES: u64 = 10 * 1024 * 1024;
+
+/// Rotated log files kept, as `<file>.1` (newest) to `<file>.3`.
+pub const LOG_FILES_KEPT: u32 = 3;
+
+/// Returns the filter logging `level` from LazyFile and only warnings
+/// from its dependencies.
+pub fn level_filter(level: &str) -> String {
+    format!("lazyfile={},warn", level)
+}
+
+/// One captured event.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LogLine {
+    /// Event level.
+    pub level: Level,
+    /// Module path that emitted the event.
+    pub target: String,
+    /// Message followed by `key=value` fields.
+    pub message: String,
+}
+
+/// Bounded ring of captured log lines, shared between the tracing
+/// layer and the UI. Cloning shares the same buffer.
+#[derive(Debug, Clone)]
+pub struct LogBuffer {
+    inner: Arc<Mutex<Ring>>,
+}
+
+#[derive(Debug)]
+struct Ring {
+    lines: VecDeque<LogLine>,
+    capacity: usize,
+    pushed: u64,
+}
+
+impl Default for LogBuffer {
+    fn default() -> Self {
+        Self::new(DEFAULT_CAPACITY)
+    }
+}
+
+impl LogBuffer {
+    /// Create a buffer holding at most `capacity` lines.
+    pub fn new(capacity: usize) -> Self {
+        let capacity = capacity.max(1);
+        Self {
+            inner: Arc::new(Mutex::new(Ring {
+                lines: VecDeque::with_capacity(capacity),
+                capacity,
+                pushed: 0,
+            })),
+        }
+    }
+
+    /// Appends a line, dropping the oldest when full.
+    pub fn push(&self, line: LogLine) {
+        let mut ring = self.lock();
+        if ring.lines.len() == ring.capacity {
+            ring.lines.pop_front();
+        }
+        ring.lines.push_back(line);
+        ring.pushed += 1;
+    }
+
+    /// Returns how many lines were ever pushed. Unlike [`Self::len`]
+    /// it keeps changing once the ring is full.
+    pub fn pushed(&self) -> u64 {
+        self.lock().pushed
+    }
+
+    /// Returns the number of buffered lines.
+    pub fn len(&self) -> usize {
+        self.lock().lines.len()
+    }
+
+    /// Returns whether nothing has been captured.
+    pub fn is_empty(&self) -> bool {
+        self.lock().lines.is_empty()
+    }
+
+    /// Returns up to `count` lines starting at `start`, oldest first.
+    pub fn lines(&self, start: usize, count: usize) -> Vec<LogLine> {
+        self.lock()
+            .lines
+            .iter()
+            .skip(start)
+            .take(count)
+            .cloned()
+            .collect()
+    }
+
+    /// A panic while holding the lock cannot leave the ring
+    /// half-updated, so a poisoned lock is still safe to use.
+    fn lock(&self) -> MutexGuard<'_, Ring> {
+        self.inner.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+/// `tracing` layer that copies every event it sees into a
+/// [`LogBuffer`].
+pub struct LogLayer {
+    buffer: LogBuffer,
+}
+
+impl LogLayer {
+    /// Create a layer writing into `buffer`.
+    pub fn new(buffer: LogBuffer) -> Self {
+        Self { buffer }
+    }
+}
+
+impl<S: Subscriber> Layer<S> for LogLayer {
+    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+        let mut visitor = MessageVisitor::default();
+        event.record(&mut visitor);
+        let metadata = event.metadata();
+        self.buffer.push(LogLine {
+            level: *metadata.level(),
+            target: metadata.target().to_string(),
+            message: visitor.finish(),
+        });
+    }
+}
+
+/// Log file that moves aside once it grows past a size limit, keeping
+/// the last few. Cloning shares the same file.
+#[derive(Debug, Clone)]
+pub struct RotatingFile {
+    inner: Arc<Mutex<Output>>,
+}
+
+#[derive(Debug)]
+struct Output {
+    path: PathBuf,
+    /// None after reopening failed; retried on the next write.
+    file: Option<File>,
+    written: u64,
+    max_bytes: u64,
+}
+
+impl RotatingFile {
+    /// Opens `path` for appending, creating it and its directory,
+    /// rotating whenever it would grow past `max_bytes`.
+    pub fn open(path: &Path, max_bytes: u64) -> io::Result<Self> {
+        if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+            fs::create_dir_all(dir)?;
+        }
+        let file = append(path)?;
+        let written = file.metadata()?.len();
+        Ok(Self {
+            inner: Arc::new(Mutex::new(Output {
+                path: path.to_path_buf(),
+                file: Some(file),
+                written,
+                max_bytes,
+            })),
+        })
+    }
+}
+
+fn append(path: &Path) -> io::Result<File> {
+    OpenOptions::new().create(true).append(true).open(path)
+}
+
+/// Returns the name of rotated file number `n`.
+fn rotated(path: &Path, n: u32) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(format!(".{}", n));
+    PathBuf::from(name)
+}
+
+impl Output {
+    /// Shifts `<file>.1` to `<file>.2` and so on, drops the oldest,
+    /// and starts a new file. Failures are not reported: logging them
+    /// would come straight back here.
+    fn rotate(&mut self) {
+        self.file = None;
+        for n in (1..LOG_FILES_KEPT).rev() {
+            let _ = fs::rename(rotated(&self.path, n), rotated(&self.path, n + 1));
+        }
+        let _ = fs::rename(&self.path, rotated(&self.path, 1));
+        self.file = append(&self.path).ok();
+        self.written = 0;
+    }
+}
+
+impl io::Write for RotatingFile {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let mut output = self.inner.lock().unwrap_or_else(PoisonError::into_inner);
+        let len = buf.len() as u64;
+        if output.written > 0 && output.written + len > output.max_bytes {
+            output.rotate();
+        }
+        if output.file.is_none() {
+            output.file = append(&output.path).ok();
+        }
+        // A line that cannot be written is dropped rather than
+        // reported on stderr, which the TUI draws over.
+        let Some(file) = output.file.as_mut() else {
+            return Ok(buf.len());
+        };
+        let written = file.write(buf)?;
+        output.written += written as u64;
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        let mut output = self.inner.lock().unwrap_or_else(PoisonError::into_inner);
+        match output.file.as_mut() {
+            Some(file) => file.flush(),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Switches logging between the filters chosen at startup and
+/// [`DEBUG_FILTER`] without restarting.
+pub struct Verbosity {
+    debug: bool,
+    apply: Box<dyn Fn(bool) -> Result<(), String> + Send + Sync>,
+}
+
+impl fmt::Debug for Verbosity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Verbosity")
+            .field("debug", &self.debug)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Verbosity {
+    /// Wraps `apply`, which installs the debug filters when passed
+    /// true and the startup ones when passed false.
+    pub fn new(apply: impl Fn(bool) -> Result<(), String> + Send + Sync + 'static) -> Self {
+        Self {
+            debug: false,
+            apply: Box::new(apply),
+        }
+    }
+
+    /// Returns whether debug logging is on.
+    pub fn debug(&self) -> bool {
+        self.debug
+    }
+
+    /// Switches debug logging on or off, returning the new state.
+    pub fn toggle(&mut self) -> Result<bool, String> {
+        (self.apply)(!self.debug)?;
+        self.debug = !self.debug;
+        Ok(self.debug)
+    }
+}
+
+/// Collects the `message` field and the remaining fields as
+/// `key=value` pairs.
+#[derive(Default)]
+struct MessageVisitor {
+    message: String,
+    fields: String,
+}
+
+impl MessageVisitor {
+    fn finish(self) -> String {
+        match (self.message.is_empty(), self.fields.is_empty()) {
+            (_, true) => self.message,
+            (true, false) => self.fields,
+            (false, false) => format!("{} {}", self.message, self.fields),
+        }
+    }
+}
+
+impl Visit for MessageVisitor {
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        if field.name() == "message" {
+            let _ = write!(self.message, "{:?}", value);
+        } else {
+            if !self.fields.is_empty() {
+                self.fields.push(' ');
+            }
+            let _ = write!(self.fields, "{}={:?}", field.name(), value);
+        }
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == "message" {
+            self.message.push_str(value);
+        } else {
+            self.record_debug(field, &format_args!("{}", value));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tracing_subscriber::layer::SubscriberExt;
+
+    fn line(message: &str) -> LogLine {
+        LogLine {
+            level: Level::INFO,
+            target: "test".to_string(),
+            message: message.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_drops_oldest_when_full() {
+        let buffer = LogBuffer::new(2);
+        buffer.push(line("a"));
+        buffer.push(line("b"));
+        buffer.push(line("c"));
+
+        let messages: Vec<_> = buffer.lines(0, 10).into_iter().map(|l| l.message).collect();
+        assert_eq!(messages, ["b", "c"]);
+    }
+
+    #[test]
+    fn test_lines_returns_a_window() {
+        let buffer = LogBuffer::new(10);
+        for message in ["a", "b", "c", "d"] {
+            buffer.push(line(message));
+        }
+
+        let messages: Vec<_> = buffer.lines(1, 2).into_iter().map(|l| l.message).collect();
+        assert_eq!(messages, ["b", "c"]);
+    }
+
+    #[test]
+    fn test_clones_share_the_buffer() {
+        let buffer = LogBuffer::new(10);
+        buffer.clone().push(line("shared"));
+        assert_eq!(buffer.len(), 1);
+    }
+
+    #[test]
+    fn test_layer_captures_message_and_fields() {
+        let buffer = LogBuffer::new(10);
+        let subscriber = tracing_subscriber::registry().with(LogLayer::new(buffer.clone()));
+
+        tracing::subscriber::with_default(subscriber, || {
+            tracing::warn!(remote = "gdrive", count = 3, "listing failed");
+        });
+
+        let captured = buffer.lines(0, 1);
+        assert_eq!(captured[0].level, Level::WARN);
+        assert_eq!(captured[0].message, "listing failed remote=gdrive count=3");
+    }
+
+    #[test]
+    fn test_rotates_the_log_file() {
+        use std::io::Write as _;
+
+        let dir = std::env::temp_dir().join(format!("lazyfile-logs-{}", std::process::id()));
+        let path = dir.join("lazyfile.log");
+        let mut file = RotatingFile::open(&path, 8).unwrap();
+        for line in ["first\n", "second\n", "third\n", "fourth\n", "fifth\n"] {
+            file.write_all(line.as_bytes()).unwrap();
+        }
+
+        let read = |path: PathBuf| fs::read_to_string(path).unwrap();
+        assert_eq!(read(path.clone()), "fifth\n");
+        assert_eq!(read(rotated(&path, 1)), "fourth\n");
+        assert_eq!(read(rotated(&path, LOG_FILES_KEPT)), "second\n");
+        assert!(!rotated(&path, LOG_FILES_KEPT + 1).exists());
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
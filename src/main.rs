@@ -1,26 +1,234 @@
 //! LazyFile - TUI file manager for cloud storage using rclone.
 
-use clap::Parser;
-use lazyfile::app::App;
-use lazyfile::cli::Args;
-use lazyfile::error;
+use clap::{CommandFactory, Parser};
+use clap_complete::CompleteEnv;
+use lazyfile::app::{ActiveModal, App, Keymap, command, script};
+use lazyfile::cli::{self, Args, Secret, Task};
+use lazyfile::config::{AuditLog, Config, History, State};
+use lazyfile::daemon;
+use lazyfile::error::{self, LazyFileError};
+use lazyfile::headless;
 use lazyfile::launcher;
-use lazyfile::rclone::RcloneClient;
+use lazyfile::logs::{self, LogBuffer, LogLayer, RotatingFile, Verbosity};
+use lazyfile::rclone::{Connection, Credentials, RcloneClient, socket_path};
+use lazyfile::ui::{ConfigPasswordModal, DaemonSetup, FileColors, Theme, ThemePreset};
+use std::io::IsTerminal;
+use std::path::Path;
+use tracing_subscriber::Layer;
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::{EnvFilter, reload};
 
 #[tokio::main]
 async fn main() -> error::Result<()> {
+    // Completion scripts run the binary with `COMPLETE` set; answer
+    // them before anything else touches stdout.
+    CompleteEnv::with_factory(Args::command)
+        .var(cli::COMPLETE_VAR)
+        .complete();
     let args = Args::parse();
+    if let Some(Task::Completions { ref shell }) = args.command {
+        return cli::write_completions(shell, &mut std::io::stdout().lock());
+    }
 
-    tracing_subscriber::fmt()
-        .with_env_filter(tracing_subscriber::EnvFilter::from_default_env())
-        .with_writer(std::io::stderr)
-        .init();
+    let (logs, verbosity) = init_logging(args.log_file.as_deref(), args.log_level.as_deref())?;
 
     tracing::debug!("Starting LazyFile");
 
-    let client = RcloneClient::new(&args.host, args.port)?;
+    let config = match args.config {
+        Some(ref path) => Config::load(path)?,
+        None => Config::load_default()?,
+    };
+    let (profile, mut connection) = match config.startup_profile(args.profile.as_deref())? {
+        Some((name, connection)) => (Some(name), connection),
+        None => (None, Connection::default()),
+    };
+    if let Some(host) = args.host {
+        connection.host = host;
+    }
+    if let Some(port) = args.port {
+        connection.port = port;
+    }
+    if let Some(ref socket) = args.socket {
+        connection.socket = Some(socket_path(socket));
+    }
+    if args.ca_cert.is_some() {
+        connection.ca_cert = args.ca_cert;
+    }
+    if args.client_cert.is_some() {
+        connection.client_cert = args.client_cert;
+        connection.client_key = args.client_key;
+    }
+    if let Some(credentials) = cli_credentials(args.user, args.pass, args.bearer)? {
+        connection.credentials = Some(credentials);
+    }
+    connection.insecure |= args.insecure;
+    connection.tls |= args.tls
+        || connection.ca_cert.is_some()
+        || connection.insecure
+        || connection.client_cert.is_some();
+
+    let policy = config.requests.policy();
+    let client = RcloneClient::connect(&connection)?.with_policy(policy);
+    if let Some(Task::Remote(command)) = args.command {
+        return headless::run(&client, command, args.json, &mut std::io::stdout()).await;
+    }
+
+    let keymap = Keymap::from_config(&config.keys)?;
+    let preset = args.theme.as_deref().and_then(ThemePreset::from_name);
+    let theme = Theme::from_config(preset, &config.theme)?;
+    let file_colors = FileColors::from_config(&config.theme)?;
     let mut app = App::new(client);
-    app.load_remotes().await?;
+    app.profiles = config.profiles;
+    if command::SUPPORTED {
+        app.commands = config.commands;
+    } else if !config.commands.is_empty() {
+        tracing::warn!("ignoring [commands]: not supported on this platform");
+        app.notifications
+            .warning("Custom commands are not supported on Windows; [commands] is ignored");
+    }
+    app.conflict_policy = config.on_conflict;
+    app.icons = config.icons;
+    app.file_colors = file_colors;
+    app.remote_layout = config.remotes;
+    app.file_list = config.file_list;
+    app.status_bar = config.status_bar;
+    app.confirm = config.confirm;
+    app.quota = config.quota;
+    app.notify_after = config.notifications.threshold();
+    let config_path = args.config.clone().or_else(Config::default_path);
+    if let Some(dir) = config_path.as_deref().and_then(Path::parent) {
+        app.scripts = script::discover(&dir.join("scripts"));
+    }
+    app.config_path = config_path;
+    app.state_path = State::default_path();
+    app.history = History::default_path().map(History::new);
+    app.audit = AuditLog::default_path().map(AuditLog::new);
+    if let Some(ref path) = app.state_path {
+        // A broken state file costs the history, not the session.
+        app.state = State::load(path).unwrap_or_else(|e| {
+            tracing::warn!(error = %e, "ignoring state file");
+            State::default()
+        });
+    }
+    app.profile = profile;
+    app.keymap = keymap;
+    app.theme = theme;
+    app.logs = logs;
+    app.verbosity = Some(verbosity);
+    app.keep_daemon = args.keep_daemon;
+
+    match app.load_remotes().await {
+        // The daemon is up but cannot read its config until the TUI
+        // asks for the password.
+        Err(err) if err.is_config_locked() => {
+            app.modal = Some(ActiveModal::ConfigPassword(ConfigPasswordModal::default()));
+        }
+        // Something answers but is not a usable daemon, or there is no
+        // one to ask: keep failing as before.
+        Err(err) if daemon::is_listening(&connection).await || !std::io::stdin().is_terminal() => {
+            return Err(err);
+        }
+        Err(err) => {
+            tracing::warn!(error = %err, "no rclone daemon answers");
+            app.modal = Some(ActiveModal::DaemonSetup(Box::new(DaemonSetup::new(
+                connection.address(),
+                daemon::find_rclone(),
+            ))));
+        }
+        Ok(()) => {}
+    }
+
+    if !args.fresh {
+        app.restore_session();
+    }
+    app.request_providers();
 
     launcher::start(app).await
 }
+
+/// Sets up logging. stderr keeps honoring `RUST_LOG` as before; the
+/// log pane and `--log-file` log at `--log-level`, or as `RUST_LOG`
+/// says, or fall back to a quieter default so they are useful without
+/// any setup. Returns the pane's lines and the switch to debug level.
+fn init_logging(
+    log_file: Option<&Path>,
+    log_level: Option<&str>,
+) -> error::Result<(LogBuffer, Verbosity)> {
+    let spec = match log_level {
+        Some(level) => logs::level_filter(level),
+        None => std::env::var(EnvFilter::DEFAULT_ENV)
+            .ok()
+            .filter(|spec| EnvFilter::try_new(spec).is_ok())
+            .unwrap_or_else(|| logs::DEFAULT_FILTER.to_string()),
+    };
+    let file = log_file
+        .map(|path| {
+            RotatingFile::open(path, logs::MAX_LOG_BYTES).map_err(|e| LazyFileError::LogFile {
+                path: path.to_path_buf(),
+                message: e.to_string(),
+            })
+        })
+        .transpose()?;
+
+    let logs = LogBuffer::default();
+    let (pane_filter, pane_handle) = reload::Layer::new(EnvFilter::new(&spec));
+    let (file_layer, file_handle) = match file {
+        Some(file) => {
+            let (filter, handle) = reload::Layer::new(EnvFilter::new(&spec));
+            let layer = tracing_subscriber::fmt::layer()
+                .with_ansi(false)
+                .with_writer(move || file.clone())
+                .with_filter(filter);
+            (Some(layer), Some(handle))
+        }
+        None => (None, None),
+    };
+    tracing_subscriber::registry()
+        .with(
+            tracing_subscriber::fmt::layer()
+                .with_writer(std::io::stderr)
+                .with_filter(EnvFilter::from_default_env()),
+        )
+        .with(LogLayer::new(logs.clone()).with_filter(pane_filter))
+        .with(file_layer)
+        .init();
+
+    let verbosity = Verbosity::new(move |debug| {
+        let spec = if debug { logs::DEBUG_FILTER } else { &spec };
+        pane_handle
+            .reload(EnvFilter::new(spec))
+            .map_err(|e| e.to_string())?;
+        match file_handle {
+            Some(ref handle) => handle
+                .reload(EnvFilter::new(spec))
+                .map_err(|e| e.to_string()),
+            None => Ok(()),
+        }
+    });
+    Ok((logs, verbosity))
+}
+
+/// Returns the login given by `--user`/`--pass` or `--bearer` (or
+/// their environment variables), which replaces the profile's.
+fn cli_credentials(
+    user: Option<String>,
+    pass: Option<Secret>,
+    bearer: Option<Secret>,
+) -> error::Result<Option<Credentials>> {
+    match (user, pass, bearer) {
+        (None, None, Some(Secret(token))) => Ok(Some(Credentials::Bearer { token })),
+        (Some(user), Some(Secret(password)), None) => {
+            Ok(Some(Credentials::Basic { user, password }))
+        }
+        (None, None, None) => Ok(None),
+        (_, _, Some(_)) => Err(LazyFileError::InvalidInput {
+            field: "credentials",
+            reason: "--bearer cannot be combined with --user or --pass",
+        }),
+        _ => Err(LazyFileError::InvalidInput {
+            field: "credentials",
+            reason: "--user and --pass must be given together",
+        }),
+    }
+}
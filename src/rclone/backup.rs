@@ -0,0 +1,180 @@
+//! Backup files of a daemon's remotes, for `lazyfile export` and
+//! `lazyfile import`.
+//!
+//! A backup is either the JSON `config/dump` returns or an INI file in
+//! the layout of `rclone.conf`, so a backup can also be handed to the
+//! rclone CLI and an existing `rclone.conf` imported. Passwords stay in
+//! the obscured form the daemon reports them in.
+
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// Settings of every remote, keyed by remote name, each including its
+/// `type`.
+pub type RemoteConfigs = BTreeMap<String, BTreeMap<String, String>>;
+
+/// How a backup file is written.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    /// The object `config/dump` returns.
+    Json,
+    /// Sections in the layout of `rclone.conf`.
+    Conf,
+}
+
+impl Format {
+    /// Picks JSON for a `.json` file and the `rclone.conf` layout for
+    /// anything else.
+    pub fn from_path(path: &Path) -> Self {
+        match path.extension() {
+            Some(ext) if ext.eq_ignore_ascii_case("json") => Self::Json,
+            _ => Self::Conf,
+        }
+    }
+}
+
+/// Renders `configs` as a backup file in `format`.
+pub fn render(configs: &RemoteConfigs, format: Format) -> String {
+    match format {
+        Format::Json => {
+            let mut text = serde_json::to_string_pretty(configs).unwrap_or_default();
+            text.push('\n');
+            text
+        }
+        Format::Conf => {
+            let mut text = String::new();
+            for (name, config) in configs {
+                if !text.is_empty() {
+                    text.push('\n');
+                }
+                text.push_str(&format!("[{}]\n", name));
+                // rclone writes `type` first; keep that for readers.
+                if let Some(remote_type) = config.get("type") {
+                    text.push_str(&format!("type = {}\n", remote_type));
+                }
+                for (key, value) in config.iter().filter(|(key, _)| *key != "type") {
+                    text.push_str(&format!("{} = {}\n", key, value));
+                }
+            }
+            text
+        }
+    }
+}
+
+/// Parses a backup file in either format, telling them apart by
+/// whether the text is a JSON object.
+///
+/// # Errors
+/// Returns why the text is not a backup, e.g. a remote without a
+/// `type`.
+pub fn parse(text: &str) -> Result<RemoteConfigs, String> {
+    let configs = if text.trim_start().starts_with('{') {
+        parse_json(text)?
+    } else {
+        parse_conf(text)?
+    };
+    match configs
+        .iter()
+        .find(|(_, config)| !config.contains_key("type"))
+    {
+        Some((name, _)) => Err(format!("remote '{}' has no type", name)),
+        None => Ok(configs),
+    }
+}
+
+/// Parses the JSON `config/dump` returns, turning values that are not
+/// strings into their JSON text.
+pub(crate) fn parse_json(text: &str) -> Result<RemoteConfigs, String> {
+    let dump: BTreeMap<String, BTreeMap<String, serde_json::Value>> =
+        serde_json::from_str(text).map_err(|e| e.to_string())?;
+    Ok(dump
+        .into_iter()
+        .map(|(name, config)| {
+            let config = config
+                .into_iter()
+                .map(|(key, value)| match value {
+                    serde_json::Value::String(text) => (key, text),
+                    other => (key, other.to_string()),
+                })
+                .collect();
+            (name, config)
+        })
+        .collect())
+}
+
+fn parse_conf(text: &str) -> Result<RemoteConfigs, String> {
+    let mut configs = RemoteConfigs::new();
+    let mut current = None;
+    for (number, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') || line.starts_with(';') {
+            continue;
+        }
+        if let Some(name) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+            configs.entry(name.to_string()).or_default();
+            current = Some(name.to_string());
+        } else if let Some((key, value)) = line.split_once('=')
+            && let Some(ref name) = current
+        {
+            configs
+                .entry(name.clone())
+                .or_default()
+                .insert(key.trim().to_string(), value.trim().to_string());
+        } else {
+            return Err(format!(
+                "line {}: expected [remote] or key = value",
+                number + 1
+            ));
+        }
+    }
+    Ok(configs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn configs() -> RemoteConfigs {
+        let mut configs = RemoteConfigs::new();
+        configs.insert(
+            "s3".to_string(),
+            BTreeMap::from([
+                ("type".to_string(), "s3".to_string()),
+                ("provider".to_string(), "AWS".to_string()),
+                ("secret_access_key".to_string(), "a=b".to_string()),
+            ]),
+        );
+        configs.insert(
+            "local".to_string(),
+            BTreeMap::from([("type".to_string(), "local".to_string())]),
+        );
+        configs
+    }
+
+    #[test]
+    fn test_round_trips_both_formats() {
+        for format in [Format::Json, Format::Conf] {
+            let text = render(&configs(), format);
+            assert_eq!(parse(&text), Ok(configs()), "{}", text);
+        }
+    }
+
+    #[test]
+    fn test_conf_matches_rclone_layout() {
+        let text = render(&configs(), Format::Conf);
+        assert!(text.starts_with("[local]\ntype = local\n\n[s3]\ntype = s3\n"));
+    }
+
+    #[test]
+    fn test_rejects_remote_without_type() {
+        assert!(parse("[s3]\nprovider = AWS\n").is_err());
+        assert!(parse("provider = AWS\n").is_err());
+        assert!(parse(r#"{"s3": {"provider": "AWS"}}"#).is_err());
+    }
+
+    #[test]
+    fn test_format_follows_extension() {
+        assert_eq!(Format::from_path(Path::new("remotes.JSON")), Format::Json);
+        assert_eq!(Format::from_path(Path::new("rclone.conf")), Format::Conf);
+    }
+}
@@ -0,0 +1,66 @@
+//! The last requests a client sent, with how long each took, to tell
+//! a slow backend from a slow daemon.
+
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::Duration;
+
+/// Calls kept; older ones are dropped.
+pub const CALL_LOG_LIMIT: usize = 50;
+
+/// One finished request.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Call {
+    /// RC endpoint, or the `--rc-serve` route for file contents.
+    pub endpoint: &'static str,
+    /// Time from sending the request to the end of its response
+    /// headers, or to the failure.
+    pub duration: Duration,
+    /// HTTP status of the answer; None if there was none.
+    pub status: Option<u16>,
+}
+
+/// Calls of a client and every clone of it, oldest first.
+#[derive(Debug, Clone, Default)]
+pub(super) struct CallLog(Arc<Mutex<VecDeque<Call>>>);
+
+impl CallLog {
+    /// Records a finished call, dropping the oldest past the limit.
+    pub(super) fn record(&self, call: Call) {
+        let mut calls = self.0.lock().unwrap_or_else(PoisonError::into_inner);
+        if calls.len() == CALL_LOG_LIMIT {
+            calls.pop_front();
+        }
+        calls.push_back(call);
+    }
+
+    /// Returns the recorded calls, newest first.
+    pub(super) fn newest_first(&self) -> Vec<Call> {
+        let calls = self.0.lock().unwrap_or_else(PoisonError::into_inner);
+        calls.iter().rev().cloned().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_keeps_the_newest_calls() {
+        let log = CallLog::default();
+        for millis in 0..=CALL_LOG_LIMIT as u64 {
+            log.record(Call {
+                endpoint: "operations/list",
+                duration: Duration::from_millis(millis),
+                status: Some(200),
+            });
+        }
+        let calls = log.newest_first();
+        assert_eq!(calls.len(), CALL_LOG_LIMIT);
+        assert_eq!(
+            calls[0].duration,
+            Duration::from_millis(CALL_LOG_LIMIT as u64)
+        );
+        assert_eq!(calls[CALL_LOG_LIMIT - 1].duration, Duration::from_millis(1));
+    }
+}
@@ -0,0 +1,595 @@
+//! Remotes in the rclone config: listing, creating, updating and
+//! deleting them, and unlocking an encrypted config.
+
+use super::RcloneClient;
+use crate::error::{LazyFileError, Result};
+use crate::rclone::backup::{self, RemoteConfigs};
+use crate::rclone::commands;
+use crate::rclone::types::{
+    ConfigCreateRequest, ConfigDeleteRequest, ConfigOpt, ConfigOut, ConfigQuestion,
+    ConfigUpdateRequest, ListProvidersResponse, ListRemotesResponse, Provider,
+};
+use crate::rclone::validate::{validate_new_remote_name, validate_remote_name};
+use std::collections::HashMap;
+use tracing::{debug, info, trace, warn};
+
+impl RcloneClient {
+    /// Lists all configured remotes.
+    ///
+    /// # Errors
+    /// Returns error if rclone daemon is unreachable or responds
+    /// with an error.
+    pub async fn list_remotes(&self) -> Result<Vec<String>> {
+        debug!("listing remotes");
+        let body = self
+            .post_json(commands::LIST_REMOTES, &serde_json::json!({}))
+            .await?;
+        trace!(body = %body, "list_remotes response");
+
+        let remotes = parse_list_remotes(&body)
+            .inspect_err(|e| warn!(error = %e, "malformed list_remotes response"))?;
+        info!(count = remotes.len(), "loaded remotes");
+        Ok(remotes)
+    }
+
+    /// Fetches the backend type of every remote, keyed by remote
+    /// name. The rest of each configuration, secrets included, is
+    /// dropped.
+    ///
+    /// # Errors
+    /// Returns error if rclone daemon is unreachable or responds
+    /// with an error.
+    pub async fn list_remote_types(&self) -> Result<HashMap<String, String>> {
+        debug!("fetching remote types");
+        let body = self
+            .post_json(commands::CONFIG_DUMP, &serde_json::json!({}))
+            .await?;
+        parse_remote_types(&body)
+            .inspect_err(|e| warn!(error = %e, "malformed config/dump response"))
+    }
+
+    /// Fetches the settings of every remote, as a backup. Passwords
+    /// come back obscured.
+    ///
+    /// # Errors
+    /// Returns error if rclone daemon is unreachable or responds
+    /// with an error.
+    pub async fn dump_config(&self) -> Result<RemoteConfigs> {
+        debug!("dumping config");
+        let body = self
+            .post_json(commands::CONFIG_DUMP, &serde_json::json!({}))
+            .await?;
+        backup::parse_json(&body).map_err(|message| {
+            warn!(error = %message, "malformed config/dump response");
+            LazyFileError::RcloneApi {
+                endpoint: commands::CONFIG_DUMP,
+                message: format!("unexpected response format: {}", message),
+            }
+        })
+    }
+
+    /// Lists the backends remotes can be created for, leaving out
+    /// those rclone hides from its own listings.
+    ///
+    /// # Errors
+    /// Returns error if rclone daemon is unreachable or responds
+    /// with an error.
+    pub async fn list_providers(&self) -> Result<Vec<Provider>> {
+        debug!("listing providers");
+        let body = self
+            .post_json(commands::LIST_PROVIDERS, &serde_json::json!({}))
+            .await?;
+        let providers = parse_list_providers(&body)
+            .inspect_err(|e| warn!(error = %e, "malformed config/providers response"))?;
+        info!(count = providers.len(), "loaded providers");
+        Ok(providers)
+    }
+
+    /// Fetches the configuration of remote `name`, including its
+    /// `type`. Passwords come back obscured.
+    ///
+    /// # Errors
+    /// Returns error if rclone daemon is unreachable or responds
+    /// with an error, e.g. because no remote has that name.
+    pub async fn get_remote(&self, name: &str) -> Result<HashMap<String, String>> {
+        validate_remote_name(name)?;
+        debug!(remote = name, "fetching remote config");
+        let body = self
+            .post_json(commands::CONFIG_GET, &serde_json::json!({ "name": name }))
+            .await?;
+        parse_remote_config(&body)
+            .inspect_err(|e| warn!(error = %e, "malformed config/get response"))
+    }
+
+    /// Creates a new remote configuration.
+    ///
+    /// # Errors
+    /// Returns `InvalidInput` if rclone would refuse `name`, or error
+    /// if rclone daemon is unreachable or responds with an error.
+    pub async fn create_remote(
+        &self,
+        name: &str,
+        remote_type: &str,
+        parameters: HashMap<String, String>,
+    ) -> Result<()> {
+        self.create_remote_with(name, remote_type, parameters, None)
+            .await
+    }
+
+    /// Creates a remote from settings whose passwords are already
+    /// obscured, as `config/dump` and `config/get` return them. They
+    /// are stored as given rather than left to rclone to tell apart
+    /// from plain text, so no secret is obscured twice.
+    ///
+    /// # Errors
+    /// Returns `InvalidInput` if rclone would refuse `name`, or error
+    /// if rclone daemon is unreachable or responds with an error.
+    pub async fn restore_remote(
+        &self,
+        name: &str,
+        remote_type: &str,
+        parameters: HashMap<String, String>,
+    ) -> Result<()> {
+        let opt = ConfigOpt {
+            no_obscure: true,
+            ..ConfigOpt::default()
+        };
+        self.create_remote_with(name, remote_type, parameters, Some(opt))
+            .await
+    }
+
+    async fn create_remote_with(
+        &self,
+        name: &str,
+        remote_type: &str,
+        parameters: HashMap<String, String>,
+        opt: Option<ConfigOpt>,
+    ) -> Result<()> {
+        validate_new_remote_name(name)?;
+        debug!(remote = name, r#type = remote_type, "creating remote");
+        let request = ConfigCreateRequest {
+            name: name.to_string(),
+            remote_type: remote_type.to_string(),
+            parameters,
+            opt,
+        };
+        self.post_command(commands::CONFIG_CREATE, &request).await?;
+        info!(remote = name, "remote created");
+        Ok(())
+    }
+
+    /// Creates a remote through the backend's config state machine,
+    /// as `rclone config create --non-interactive` does.
+    ///
+    /// Backends that need more than `parameters`, such as OAuth
+    /// providers, return their first question; answer it with
+    /// [`Self::continue_remote_config`]. `None` means the remote is
+    /// fully configured. The remote exists from the first call on,
+    /// so abandoning the questions leaves it half configured.
+    ///
+    /// # Errors
+    /// Returns `InvalidInput` if rclone would refuse `name`, or error
+    /// if rclone daemon is unreachable or responds with an error.
+    pub async fn start_remote_config(
+        &self,
+        name: &str,
+        remote_type: &str,
+        parameters: HashMap<String, String>,
+    ) -> Result<Option<ConfigQuestion>> {
+        validate_new_remote_name(name)?;
+        debug!(
+            remote = name,
+            r#type = remote_type,
+            "starting remote config"
+        );
+        let request = ConfigCreateRequest {
+            name: name.to_string(),
+            remote_type: remote_type.to_string(),
+            parameters,
+            opt: Some(ConfigOpt {
+                non_interactive: true,
+                obscure: true,
+                ..ConfigOpt::default()
+            }),
+        };
+        let body = self
+            .post_json_timeout(
+                commands::CONFIG_CREATE,
+                &request,
+                self.policy.config_timeout,
+            )
+            .await?;
+        parse_config_out(commands::CONFIG_CREATE, &body)
+    }
+
+    /// Answers a question from [`Self::start_remote_config`] or an
+    /// earlier call, returning the next one or `None` once the remote
+    /// is fully configured.
+    ///
+    /// Answering an OAuth question may not return until the user has
+    /// signed in through the browser.
+    ///
+    /// # Errors
+    /// Returns error if rclone daemon is unreachable or responds
+    /// with an error.
+    pub async fn continue_remote_config(
+        &self,
+        name: &str,
+        state: &str,
+        answer: &str,
+    ) -> Result<Option<ConfigQuestion>> {
+        validate_remote_name(name)?;
+        debug!(remote = name, state, "answering remote config question");
+        let request = ConfigUpdateRequest {
+            name: name.to_string(),
+            parameters: HashMap::new(),
+            opt: Some(ConfigOpt {
+                non_interactive: true,
+                obscure: true,
+                resume: true,
+                state: state.to_string(),
+                result: answer.to_string(),
+                ..ConfigOpt::default()
+            }),
+        };
+        let body = self
+            .post_json_timeout(
+                commands::CONFIG_UPDATE,
+                &request,
+                self.policy.config_timeout,
+            )
+            .await?;
+        parse_config_out(commands::CONFIG_UPDATE, &body)
+    }
+
+    /// Updates an existing remote configuration.
+    ///
+    /// # Errors
+    /// Returns error if rclone daemon is unreachable or responds
+    /// with an error.
+    pub async fn update_remote(
+        &self,
+        name: &str,
+        parameters: HashMap<String, String>,
+    ) -> Result<()> {
+        validate_remote_name(name)?;
+        debug!(remote = name, "updating remote");
+        let request = ConfigUpdateRequest {
+            name: name.to_string(),
+            parameters,
+            opt: None,
+        };
+        self.post_command(commands::CONFIG_UPDATE, &request).await?;
+        info!(remote = name, "remote updated");
+        Ok(())
+    }
+
+    /// Deletes a remote configuration.
+    ///
+    /// # Errors
+    /// Returns error if rclone daemon is unreachable or responds
+    /// with an error.
+    pub async fn delete_remote(&self, name: &str) -> Result<()> {
+        validate_remote_name(name)?;
+        debug!(remote = name, "deleting remote");
+        let request = ConfigDeleteRequest {
+            name: name.to_string(),
+        };
+        self.post_command(commands::CONFIG_DELETE, &request).await?;
+        info!(remote = name, "remote deleted");
+        Ok(())
+    }
+
+    /// Creates remote `to` with the type and settings of remote
+    /// `from`. rclone has no rename, so renaming is this followed by
+    /// [`Self::delete_remote`]. Passwords are copied in their
+    /// obscured form, through [`Self::restore_remote`].
+    ///
+    /// # Errors
+    /// Returns `InvalidInput` if rclone would refuse the name `to` or
+    /// a remote named `to` already exists, since creating it would
+    /// overwrite that remote, or error if
+    /// rclone daemon is unreachable or responds with an error.
+    pub async fn copy_remote(&self, from: &str, to: &str) -> Result<()> {
+        validate_remote_name(from)?;
+        validate_new_remote_name(to)?;
+        if self.list_remotes().await?.iter().any(|name| name == to) {
+            return Err(LazyFileError::InvalidInput {
+                field: "remote name",
+                reason: "a remote with that name already exists",
+            });
+        }
+        let mut config = self.get_remote(from).await?;
+        let remote_type = config
+            .remove("type")
+            .ok_or_else(|| LazyFileError::RcloneApi {
+                endpoint: commands::CONFIG_GET,
+                message: format!("remote '{}' has no type", from),
+            })?;
+        debug!(from, to, r#type = %remote_type, "copying remote");
+        self.restore_remote(to, &remote_type, config).await
+    }
+
+    /// Unlocks the daemon's encrypted config file with `password`, so
+    /// calls that read it stop failing.
+    ///
+    /// # Errors
+    /// Returns error if rclone daemon is unreachable or responds
+    /// with an error, e.g. because the password is wrong.
+    pub async fn unlock_config(&self, password: &str) -> Result<()> {
+        debug!("unlocking config");
+        // Built inline rather than as a request type so the password
+        // never ends up in a Debug impl.
+        self.post_command(
+            commands::CONFIG_UNLOCK,
+            &serde_json::json!({ "configPassword": password }),
+        )
+        .await?;
+        info!("config unlocked");
+        Ok(())
+    }
+}
+
+/// Parses a `config/listremotes` response body into remote names.
+///
+/// A missing or `null` `remotes` field means no remotes are
+/// configured; anything else that does not match the response shape
+/// is an error.
+fn parse_list_remotes(body: &str) -> Result<Vec<String>> {
+    let resp: ListRemotesResponse =
+        serde_json::from_str(body).map_err(|e| LazyFileError::RcloneApi {
+            endpoint: commands::LIST_REMOTES,
+            message: format!("unexpected response format: {}", e),
+        })?;
+    Ok(resp.remotes.unwrap_or_default())
+}
+
+/// Parses a `config/providers` response body into the backends that
+/// are not hidden.
+fn parse_list_providers(body: &str) -> Result<Vec<Provider>> {
+    let resp: ListProvidersResponse =
+        serde_json::from_str(body).map_err(|e| LazyFileError::RcloneApi {
+            endpoint: commands::LIST_PROVIDERS,
+            message: format!("unexpected response format: {}", e),
+        })?;
+    Ok(resp
+        .providers
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|provider| !provider.hide)
+        .collect())
+}
+
+/// Parses a `config/get` response body. Values are text in rclone's
+/// config file; anything else is kept as its JSON form.
+fn parse_remote_config(body: &str) -> Result<HashMap<String, String>> {
+    let config: HashMap<String, serde_json::Value> =
+        serde_json::from_str(body).map_err(|e| LazyFileError::RcloneApi {
+            endpoint: commands::CONFIG_GET,
+            message: format!("unexpected response format: {}", e),
+        })?;
+    Ok(config
+        .into_iter()
+        .map(|(key, value)| match value {
+            serde_json::Value::String(text) => (key, text),
+            other => (key, other.to_string()),
+        })
+        .collect())
+}
+
+/// Parses a `config/dump` response body into each remote's type.
+fn parse_remote_types(body: &str) -> Result<HashMap<String, String>> {
+    let dump: HashMap<String, HashMap<String, serde_json::Value>> = serde_json::from_str(body)
+        .map_err(|e| LazyFileError::RcloneApi {
+            endpoint: commands::CONFIG_DUMP,
+            message: format!("unexpected response format: {}", e),
+        })?;
+    Ok(dump
+        .into_iter()
+        .filter_map(|(name, config)| match config.get("type") {
+            Some(serde_json::Value::String(remote_type)) => Some((name, remote_type.clone())),
+            _ => None,
+        })
+        .collect())
+}
+
+/// Parses a non-interactive `config/create` or `config/update`
+/// response into the next question, or `None` once configuration is
+/// complete.
+fn parse_config_out(endpoint: &'static str, body: &str) -> Result<Option<ConfigQuestion>> {
+    let out: ConfigOut = serde_json::from_str(body).map_err(|e| LazyFileError::RcloneApi {
+        endpoint,
+        message: format!("unexpected response format: {}", e),
+    })?;
+    let error = (!out.error.is_empty()).then_some(out.error);
+    match out.option {
+        Some(option) => Ok(Some(ConfigQuestion {
+            state: out.state,
+            option,
+            error,
+        })),
+        None => match error {
+            Some(message) => Err(LazyFileError::RcloneApi { endpoint, message }),
+            None => Ok(None),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rclone::client::test_server::capture_request;
+
+    #[test]
+    fn parses_remote_names() {
+        let remotes = parse_list_remotes(r#"{"remotes":["gdrive","s3"]}"#).unwrap();
+        assert_eq!(remotes, vec!["gdrive".to_string(), "s3".to_string()]);
+    }
+
+    #[test]
+    fn treats_null_remotes_as_empty() {
+        let remotes = parse_list_remotes(r#"{"remotes":null}"#).unwrap();
+        assert!(remotes.is_empty());
+    }
+
+    #[test]
+    fn treats_missing_remotes_as_empty() {
+        let remotes = parse_list_remotes("{}").unwrap();
+        assert!(remotes.is_empty());
+    }
+
+    #[test]
+    fn rejects_malformed_remotes_body() {
+        let err = parse_list_remotes(r#"{"remotes":"oops"}"#).unwrap_err();
+        assert!(matches!(
+            err,
+            LazyFileError::RcloneApi {
+                endpoint: commands::LIST_REMOTES,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn test_parses_providers_without_hidden_ones() {
+        let body = r#"{"providers": [
+            {"Name": "drive", "Description": "Google Drive", "Prefix": "drive", "Options": []},
+            {"Name": "tardigrade", "Description": "Storj", "Hide": true}
+        ]}"#;
+        let providers = parse_list_providers(body).unwrap();
+        assert_eq!(providers.len(), 1);
+        assert_eq!(providers[0].name, "drive");
+        assert_eq!(providers[0].description, "Google Drive");
+    }
+
+    #[test]
+    fn test_parses_remote_config() {
+        let body = r#"{"type": "s3", "provider": "AWS", "chunk_size": 5}"#;
+        let config = parse_remote_config(body).unwrap();
+        assert_eq!(config["type"], "s3");
+        assert_eq!(config["provider"], "AWS");
+        assert_eq!(config["chunk_size"], "5");
+        assert!(parse_remote_config("[]").is_err());
+    }
+
+    #[test]
+    fn test_parses_remote_types_only() {
+        let body = r#"{
+            "gdrive": {"type": "drive", "token": "{\"access_token\":\"x\"}"},
+            "store": {"type": "s3", "secret_access_key": "hunter2"},
+            "broken": {}
+        }"#;
+        let types = parse_remote_types(body).unwrap();
+        assert_eq!(types.len(), 2);
+        assert_eq!(types["gdrive"], "drive");
+        assert_eq!(types["store"], "s3");
+        assert!(parse_remote_types("[]").is_err());
+    }
+
+    #[test]
+    fn test_parses_config_question() {
+        let body = r#"{
+            "State": "*oauth-islocal,teamdrive,,",
+            "Option": {
+                "Name": "config_is_local",
+                "Help": "Use web browser to automatically authenticate rclone with remote?",
+                "DefaultStr": "true",
+                "Examples": [
+                    {"Value": "true", "Help": "Yes"},
+                    {"Value": "false", "Help": "No"}
+                ],
+                "Exclusive": true
+            },
+            "Error": ""
+        }"#;
+        let question = parse_config_out(commands::CONFIG_CREATE, body)
+            .unwrap()
+            .unwrap();
+        assert_eq!(question.state, "*oauth-islocal,teamdrive,,");
+        assert_eq!(question.option.name, "config_is_local");
+        assert_eq!(question.option.default, "true");
+        assert_eq!(question.option.examples().len(), 2);
+        assert!(question.error.is_none());
+    }
+
+    #[test]
+    fn test_empty_config_state_means_done() {
+        let body = r#"{"State": "", "Option": null, "Error": "", "Result": ""}"#;
+        assert!(
+            parse_config_out(commands::CONFIG_UPDATE, body)
+                .unwrap()
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_config_error_without_question_fails() {
+        let body = r#"{"State": "", "Option": null, "Error": "token expired"}"#;
+        let err = parse_config_out(commands::CONFIG_UPDATE, body).unwrap_err();
+        assert!(err.to_string().contains("token expired"), "{}", err);
+    }
+
+    #[test]
+    fn test_config_opt_uses_rclone_names() {
+        let opt = ConfigOpt {
+            non_interactive: true,
+            resume: true,
+            ..ConfigOpt::default()
+        };
+        let json = serde_json::to_value(opt).unwrap();
+        assert_eq!(json["nonInteractive"], true);
+        assert_eq!(json["continue"], true);
+    }
+
+    #[tokio::test]
+    async fn test_rejects_copy_to_invalid_name_before_request() {
+        let client = RcloneClient::new("localhost", 1).expect("valid host");
+        let err = client.copy_remote("gdrive", "a:b").await.unwrap_err();
+        assert!(matches!(
+            err,
+            LazyFileError::InvalidInput {
+                field: "remote name",
+                ..
+            }
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_interactive_create_rejects_invalid_new_name_before_request() {
+        let client = RcloneClient::new("localhost", 1).expect("valid host");
+        for name in ["-gdrive", " gdrive", "gdrive "] {
+            let err = client
+                .start_remote_config(name, "drive", HashMap::new())
+                .await
+                .unwrap_err();
+            assert!(
+                matches!(
+                    err,
+                    LazyFileError::InvalidInput {
+                        field: "remote name",
+                        ..
+                    }
+                ),
+                "{:?} was not rejected: {:?}",
+                name,
+                err
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_restored_remote_keeps_obscured_passwords() {
+        let (port, server) = capture_request().await;
+        let client = RcloneClient::new("127.0.0.1", port).unwrap();
+        let parameters = HashMap::from([("pass".to_string(), "obscured".to_string())]);
+        client
+            .restore_remote("nas", "sftp", parameters)
+            .await
+            .unwrap();
+
+        let body = server.await.unwrap();
+        assert_eq!(body["parameters"]["pass"], "obscured");
+        assert_eq!(body["opt"]["noObscure"], true);
+        assert_eq!(body["opt"]["obscure"], false);
+    }
+}
@@ -0,0 +1,256 @@
+//! The daemon itself: its version, mounts, background jobs and
+//! transfers, and shutting it down.
+
+use super::{RcloneClient, fs_path};
+use crate::error::{LazyFileError, Result};
+use crate::rclone::commands;
+use crate::rclone::types::{
+    Job, JobRequest, ListJobsResponse, ListMountsResponse, Mount, MountRequest, StatsResponse,
+    Transfer, UnmountRequest, VersionResponse,
+};
+use crate::rclone::validate::{validate_mount_point, validate_path, validate_remote_name};
+use tracing::{debug, info, trace, warn};
+
+impl RcloneClient {
+    /// Asks the daemon to shut down.
+    ///
+    /// # Errors
+    /// Returns error if rclone daemon is unreachable or responds
+    /// with an error.
+    pub async fn quit(&self) -> Result<()> {
+        debug!("asking daemon to quit");
+        self.post_command(commands::QUIT, &serde_json::json!({}))
+            .await
+    }
+
+    /// Fetches the daemon's version. The call is cheap and needs no
+    /// config, so it doubles as a health check.
+    ///
+    /// # Errors
+    /// Returns error if rclone daemon is unreachable or responds
+    /// with an error.
+    pub async fn version(&self) -> Result<String> {
+        let body = self
+            .post_json(commands::CORE_VERSION, &serde_json::json!({}))
+            .await?;
+        trace!(body = %body, "version response");
+        serde_json::from_str::<VersionResponse>(&body)
+            .map(|resp| resp.version)
+            .map_err(|e| LazyFileError::RcloneApi {
+                endpoint: commands::CORE_VERSION,
+                message: format!("unexpected response format: {}", e),
+            })
+    }
+
+    /// Lists the remotes the daemon has mounted.
+    ///
+    /// # Errors
+    /// Returns error if rclone daemon is unreachable or responds
+    /// with an error.
+    pub async fn list_mounts(&self) -> Result<Vec<Mount>> {
+        debug!("listing mounts");
+        let body = self
+            .post_json(commands::LIST_MOUNTS, &serde_json::json!({}))
+            .await?;
+        trace!(body = %body, "list_mounts response");
+        parse_list_mounts(&body).inspect_err(|e| warn!(error = %e, "malformed listmounts response"))
+    }
+
+    /// Mounts `path` on `remote` at the local directory `mount_point`
+    /// on the daemon's machine.
+    ///
+    /// # Errors
+    /// Returns error if rclone daemon is unreachable or responds
+    /// with an error, e.g. because FUSE is not available.
+    pub async fn mount(&self, remote: &str, path: &str, mount_point: &str) -> Result<()> {
+        validate_remote_name(remote)?;
+        validate_path(path)?;
+        validate_mount_point(mount_point)?;
+        let (fs, remote_path) = fs_path(remote, path);
+        let request = MountRequest {
+            fs: format!("{}{}", fs, remote_path),
+            mount_point: mount_point.to_string(),
+        };
+        debug!(fs = %request.fs, mount_point, "mounting");
+        self.post_command(commands::MOUNT, &request).await?;
+        info!(fs = %request.fs, mount_point, "mounted");
+        Ok(())
+    }
+
+    /// Unmounts whatever the daemon mounted at `mount_point`.
+    ///
+    /// # Errors
+    /// Returns error if rclone daemon is unreachable or responds
+    /// with an error, e.g. because nothing is mounted there.
+    pub async fn unmount(&self, mount_point: &str) -> Result<()> {
+        validate_mount_point(mount_point)?;
+        debug!(mount_point, "unmounting");
+        let request = UnmountRequest {
+            mount_point: mount_point.to_string(),
+        };
+        self.post_command(commands::UNMOUNT, &request).await?;
+        info!(mount_point, "unmounted");
+        Ok(())
+    }
+
+    /// Lists the daemon's running and recently finished jobs with
+    /// their status, oldest first. Jobs that expire between the
+    /// listing and their status request are left out.
+    ///
+    /// # Errors
+    /// Returns error if rclone daemon is unreachable or responds
+    /// with an error.
+    pub async fn list_jobs(&self) -> Result<Vec<Job>> {
+        debug!("listing jobs");
+        let body = self
+            .post_json(commands::JOB_LIST, &serde_json::json!({}))
+            .await?;
+        let mut ids = parse_list_jobs(&body)
+            .inspect_err(|e| warn!(error = %e, "malformed job/list response"))?;
+        ids.sort_unstable();
+
+        let mut jobs = Vec::with_capacity(ids.len());
+        for id in ids {
+            match self.job_status(id).await {
+                Ok(job) => jobs.push(job),
+                Err(LazyFileError::RcloneStatus { .. }) => {
+                    debug!(id, "job expired before its status was fetched");
+                }
+                Err(e) => return Err(e),
+            }
+        }
+        info!(count = jobs.len(), "loaded jobs");
+        Ok(jobs)
+    }
+
+    /// Fetches the status of job `id`.
+    ///
+    /// # Errors
+    /// Returns error if rclone daemon is unreachable or responds
+    /// with an error, e.g. because the job has expired.
+    pub async fn job_status(&self, id: i64) -> Result<Job> {
+        let body = self
+            .post_json(commands::JOB_STATUS, &JobRequest { job_id: id })
+            .await?;
+        trace!(body = %body, "job_status response");
+        serde_json::from_str(&body).map_err(|e| LazyFileError::RcloneApi {
+            endpoint: commands::JOB_STATUS,
+            message: format!("unexpected response format: {}", e),
+        })
+    }
+
+    /// Stops job `id`.
+    ///
+    /// # Errors
+    /// Returns error if rclone daemon is unreachable or responds
+    /// with an error, e.g. because the job has finished.
+    pub async fn stop_job(&self, id: i64) -> Result<()> {
+        debug!(id, "stopping job");
+        self.post_command(commands::JOB_STOP, &JobRequest { job_id: id })
+            .await?;
+        info!(id, "job stopped");
+        Ok(())
+    }
+
+    /// Lists the files the daemon is transferring right now, across
+    /// all jobs.
+    ///
+    /// # Errors
+    /// Returns error if rclone daemon is unreachable or responds
+    /// with an error.
+    pub async fn transfers(&self) -> Result<Vec<Transfer>> {
+        let body = self
+            .post_json(commands::CORE_STATS, &serde_json::json!({}))
+            .await?;
+        trace!(body = %body, "core/stats response");
+        parse_stats(&body).inspect_err(|e| warn!(error = %e, "malformed core/stats response"))
+    }
+}
+
+/// Parses a `mount/listmounts` response body. A missing or `null`
+/// `mountPoints` field means nothing is mounted.
+fn parse_list_mounts(body: &str) -> Result<Vec<Mount>> {
+    let resp: ListMountsResponse =
+        serde_json::from_str(body).map_err(|e| LazyFileError::RcloneApi {
+            endpoint: commands::LIST_MOUNTS,
+            message: format!("unexpected response format: {}", e),
+        })?;
+    Ok(resp.mount_points.unwrap_or_default())
+}
+
+/// Parses a `job/list` response body into job ids. A missing or
+/// `null` `jobids` field means there are no jobs.
+fn parse_list_jobs(body: &str) -> Result<Vec<i64>> {
+    let resp: ListJobsResponse =
+        serde_json::from_str(body).map_err(|e| LazyFileError::RcloneApi {
+            endpoint: commands::JOB_LIST,
+            message: format!("unexpected response format: {}", e),
+        })?;
+    Ok(resp.job_ids.unwrap_or_default())
+}
+
+/// Parses a `core/stats` response body into the files in flight. A
+/// missing or `null` `transferring` field means there are none.
+fn parse_stats(body: &str) -> Result<Vec<Transfer>> {
+    let resp: StatsResponse = serde_json::from_str(body).map_err(|e| LazyFileError::RcloneApi {
+        endpoint: commands::CORE_STATS,
+        message: format!("unexpected response format: {}", e),
+    })?;
+    Ok(resp.transferring.unwrap_or_default())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parses_mounts() {
+        let body = r#"{"mountPoints": [{
+            "Fs": "gdrive:docs",
+            "MountPoint": "/mnt/docs",
+            "MountedOn": "2024-01-01T00:00:00Z"
+        }]}"#;
+        let mounts = parse_list_mounts(body).unwrap();
+        assert_eq!(mounts.len(), 1);
+        assert_eq!(mounts[0].fs, "gdrive:docs");
+        assert_eq!(mounts[0].mount_point, "/mnt/docs");
+        assert!(
+            parse_list_mounts(r#"{"mountPoints": null}"#)
+                .unwrap()
+                .is_empty()
+        );
+        assert!(parse_list_mounts("[]").is_err());
+    }
+
+    #[test]
+    fn test_parses_transfers() {
+        let body = r#"{"bytes": 1048576, "speed": 524288, "transferring": [
+            {"name": "docs/a.iso", "size": 4194304, "bytes": 1048576, "percentage": 25,
+             "speed": 524288.5, "speedAvg": 500000, "eta": 6, "group": "job/3",
+             "srcFs": "gdrive:", "dstFs": "s3:"},
+            {"name": "b.txt", "size": -1, "bytes": 0, "percentage": 0, "eta": null}]}"#;
+        let transfers = parse_stats(body).unwrap();
+        assert_eq!(transfers.len(), 2);
+        assert_eq!(transfers[0].percentage, 25);
+        assert_eq!(transfers[0].eta, Some(6.0));
+        assert_eq!(transfers[0].group, "job/3");
+        assert_eq!(transfers[1].eta, None);
+        assert!(parse_stats(r#"{"bytes": 0}"#).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_parses_job_ids_and_status() {
+        let ids = parse_list_jobs(r#"{"jobids": [3, 1], "executeId": "x"}"#).unwrap();
+        assert_eq!(ids, [3, 1]);
+        assert!(parse_list_jobs(r#"{"jobids": null}"#).unwrap().is_empty());
+
+        let job: Job = serde_json::from_str(
+            r#"{"id": 3, "group": "job/3", "finished": true, "success": false,
+                "error": "directory not found", "duration": 1.5, "output": null,
+                "startTime": "2024-01-01T00:00:00Z", "endTime": "2024-01-01T00:00:01Z"}"#,
+        )
+        .unwrap();
+        assert_eq!(job.state(), crate::rclone::JobState::Failed);
+        assert_eq!(job.error, "directory not found");
+    }
+}
@@ -0,0 +1,476 @@
+//! Listing directories and reading what a remote and its entries are.
+
+use super::{RcloneClient, fs_path, with_filter};
+use crate::error::{LazyFileError, Result};
+use crate::rclone::commands;
+use crate::rclone::filter::Filter;
+use crate::rclone::types::{
+    FileItem, FsInfo, ListFilesResponse, ListNestedResponse, ListTreeResponse, ObjectInfo, Quota,
+    StatMetadataResponse, StatResponse, TreeEntry,
+};
+use crate::rclone::validate::{validate_path, validate_remote_name};
+use std::collections::BTreeMap;
+use tracing::{debug, info, trace, warn};
+
+impl RcloneClient {
+    /// Fetches what the backend of remote `name` supports, such as
+    /// its hash types and server-side copy.
+    ///
+    /// # Errors
+    /// Returns error if rclone daemon is unreachable or responds
+    /// with an error, e.g. because the backend cannot be reached.
+    pub async fn fs_info(&self, name: &str) -> Result<FsInfo> {
+        validate_remote_name(name)?;
+        debug!(remote = name, "fetching backend features");
+        let (fs, _) = fs_path(name, "");
+        let body = self
+            .post_json(commands::FSINFO, &serde_json::json!({ "fs": fs }))
+            .await?;
+        trace!(body = %body, "fsinfo response");
+        parse_fs_info(&body).inspect_err(|e| warn!(error = %e, "malformed fsinfo response"))
+    }
+
+    /// Fetches how much space remote `name` has and uses.
+    ///
+    /// # Errors
+    /// Returns error if rclone daemon is unreachable or responds
+    /// with an error, e.g. because the backend has no quota.
+    pub async fn about(&self, name: &str) -> Result<Quota> {
+        validate_remote_name(name)?;
+        debug!(remote = name, "fetching quota");
+        let (fs, _) = fs_path(name, "");
+        let body = self
+            .post_json(commands::ABOUT, &serde_json::json!({ "fs": fs }))
+            .await?;
+        trace!(body = %body, "about response");
+        parse_about(&body).inspect_err(|e| warn!(error = %e, "malformed about response"))
+    }
+
+    /// Lists files in a remote path.
+    ///
+    /// # Errors
+    /// Returns error if rclone daemon is unreachable or responds
+    /// with an error.
+    pub async fn list_files(&self, remote: &str, path: &str) -> Result<Vec<FileItem>> {
+        self.list_dir(remote, path, None, &Filter::default()).await
+    }
+
+    /// Lists files in a remote path, leaving out those `filter`
+    /// excludes. With `hash`, each file also carries that hash, which
+    /// backends that do not store it compute by reading the file, so
+    /// the request gets as long as a recursive listing.
+    ///
+    /// # Errors
+    /// Returns error if rclone daemon is unreachable or responds
+    /// with an error.
+    pub async fn list_dir(
+        &self,
+        remote: &str,
+        path: &str,
+        hash: Option<&str>,
+        filter: &Filter,
+    ) -> Result<Vec<FileItem>> {
+        validate_remote_name(remote)?;
+        validate_path(path)?;
+        let (fs, remote_path) = fs_path(remote, path);
+        debug!(remote, path = %remote_path, hash, filter = %filter.describe(), "listing files");
+
+        let mut request = serde_json::json!({ "fs": fs, "remote": remote_path });
+        let mut timeout = self.policy.timeout;
+        if let Some(hash) = hash {
+            request["opt"] = serde_json::json!({ "showHash": true, "hashTypes": [hash] });
+            timeout = self.policy.tree_timeout;
+        }
+        with_filter(&mut request, filter);
+        let body = self
+            .post_json_timeout(commands::LIST_FILES, &request, timeout)
+            .await?;
+        trace!(body = %body, "list_files response");
+
+        let items = parse_list_files(&body)
+            .inspect_err(|e| warn!(error = %e, "malformed list_files response"))?;
+        info!(count = items.len(), "loaded files");
+        Ok(items)
+    }
+
+    /// Looks up the file or directory at `path`, returning `None` if
+    /// there is nothing.
+    ///
+    /// # Errors
+    /// Returns error if rclone daemon is unreachable or responds
+    /// with an error.
+    pub async fn stat(&self, remote: &str, path: &str) -> Result<Option<FileItem>> {
+        validate_remote_name(remote)?;
+        validate_path(path)?;
+        let (fs, remote_path) = fs_path(remote, path);
+        debug!(remote, path = %remote_path, "looking up path");
+
+        let body = self
+            .post_json(
+                commands::STAT,
+                &serde_json::json!({ "fs": fs, "remote": remote_path }),
+            )
+            .await?;
+        trace!(body = %body, "stat response");
+        parse_stat(&body).inspect_err(|e| warn!(error = %e, "malformed stat response"))
+    }
+
+    /// Looks up the file at `path` with the metadata its backend keeps
+    /// for it, returning `None` if there is nothing.
+    ///
+    /// # Errors
+    /// Returns error if rclone daemon is unreachable or responds
+    /// with an error.
+    pub async fn stat_metadata(&self, remote: &str, path: &str) -> Result<Option<ObjectInfo>> {
+        validate_remote_name(remote)?;
+        validate_path(path)?;
+        let (fs, remote_path) = fs_path(remote, path);
+        debug!(remote, path = %remote_path, "fetching metadata");
+        let request = serde_json::json!({
+            "fs": fs,
+            "remote": remote_path,
+            "opt": { "metadata": true },
+        });
+        let body = self.post_json(commands::STAT, &request).await?;
+        trace!(body = %body, "stat response");
+        parse_stat_metadata(&body).inspect_err(|e| warn!(error = %e, "malformed stat response"))
+    }
+
+    /// Sets the metadata keys in `changes` on the file at `path`,
+    /// keeping its other keys. rclone writes metadata only while
+    /// copying, so the file is copied to a temporary name next to it
+    /// with the changes applied, and moved back over itself.
+    ///
+    /// # Errors
+    /// Returns error if rclone daemon is unreachable or responds
+    /// with an error, e.g. because the backend cannot write metadata.
+    /// If moving the copy back fails, it is left next to the file.
+    pub async fn set_metadata(
+        &self,
+        remote: &str,
+        path: &str,
+        changes: &BTreeMap<String, String>,
+    ) -> Result<()> {
+        validate_remote_name(remote)?;
+        validate_path(path)?;
+        let (fs, remote_path) = fs_path(remote, path);
+        let temporary = match remote_path.rsplit_once('/') {
+            Some((dir, name)) => format!("{}/.{}.lazyfile-metadata", dir, name),
+            None => format!(".{}.lazyfile-metadata", remote_path),
+        };
+        debug!(remote, path = %remote_path, keys = changes.len(), "setting metadata");
+        let transfer = |endpoint, from: &str, to: &str, config| {
+            let request = serde_json::json!({
+                "srcFs": fs,
+                "srcRemote": from,
+                "dstFs": fs,
+                "dstRemote": to,
+                "_config": config,
+            });
+            async move { self.post_command(endpoint, &request).await }
+        };
+        transfer(
+            commands::COPY_FILE,
+            &remote_path,
+            &temporary,
+            serde_json::json!({ "Metadata": true, "MetadataSet": changes }),
+        )
+        .await?;
+        transfer(
+            commands::MOVE_FILE,
+            &temporary,
+            &remote_path,
+            serde_json::json!({ "Metadata": true }),
+        )
+        .await?;
+        info!(remote, path = %remote_path, "metadata set");
+        Ok(())
+    }
+
+    /// Lists everything below `path` on `remote`, directories
+    /// included, in one recursive call. With `hash`, e.g. `md5`, each
+    /// file also carries that hash; backends that do not store it,
+    /// such as local disks, read every file to compute it. Files
+    /// `filter` excludes are left out.
+    ///
+    /// # Errors
+    /// Returns error if rclone daemon is unreachable or responds
+    /// with an error.
+    pub async fn list_tree(
+        &self,
+        remote: &str,
+        path: &str,
+        hash: Option<&str>,
+        filter: &Filter,
+    ) -> Result<Vec<TreeEntry>> {
+        validate_remote_name(remote)?;
+        validate_path(path)?;
+        let (fs, remote_path) = fs_path(remote, path);
+        debug!(remote, path = %remote_path, hash, "listing tree");
+
+        let mut opt = serde_json::json!({ "recurse": true, "noModTime": true, "noMimeType": true });
+        if let Some(hash) = hash {
+            opt["showHash"] = serde_json::json!(true);
+            opt["hashTypes"] = serde_json::json!([hash]);
+        }
+        let mut request = serde_json::json!({ "fs": fs, "remote": remote_path, "opt": opt });
+        with_filter(&mut request, filter);
+        let body = self
+            .post_json_timeout(commands::LIST_FILES, &request, self.policy.tree_timeout)
+            .await?;
+        let entries = parse_list_tree(&body)
+            .inspect_err(|e| warn!(error = %e, "malformed recursive list response"))?;
+        info!(count = entries.len(), "loaded tree");
+        Ok(entries)
+    }
+
+    /// Lists every file below `path` on `remote`, as `rclone ls`
+    /// does, in one recursive call. Each file is named by its path
+    /// relative to `path`, e.g. `photos/a.jpg`; directories are left
+    /// out. `hash` and `filter` work as for [`Self::list_dir`].
+    ///
+    /// # Errors
+    /// Returns error if rclone daemon is unreachable or responds
+    /// with an error.
+    pub async fn list_recursive(
+        &self,
+        remote: &str,
+        path: &str,
+        hash: Option<&str>,
+        filter: &Filter,
+    ) -> Result<Vec<FileItem>> {
+        validate_remote_name(remote)?;
+        validate_path(path)?;
+        let (fs, remote_path) = fs_path(remote, path);
+        debug!(remote, path = %remote_path, hash, "listing files recursively");
+
+        let mut opt = serde_json::json!({ "recurse": true, "filesOnly": true });
+        if let Some(hash) = hash {
+            opt["showHash"] = serde_json::json!(true);
+            opt["hashTypes"] = serde_json::json!([hash]);
+        }
+        let mut request = serde_json::json!({ "fs": fs, "remote": remote_path, "opt": opt });
+        with_filter(&mut request, filter);
+        let body = self
+            .post_json_timeout(commands::LIST_FILES, &request, self.policy.tree_timeout)
+            .await?;
+        let items = parse_list_nested(&body)
+            .inspect_err(|e| warn!(error = %e, "malformed recursive list response"))?;
+        info!(count = items.len(), "loaded files recursively");
+        Ok(items)
+    }
+}
+
+/// Parses an `operations/list` response body into file items.
+///
+/// A missing or `null` `list` field is a valid empty directory;
+/// anything that does not match the response shape is an error.
+pub(super) fn parse_list_files(body: &str) -> Result<Vec<FileItem>> {
+    let resp: ListFilesResponse =
+        serde_json::from_str(body).map_err(|e| LazyFileError::RcloneApi {
+            endpoint: commands::LIST_FILES,
+            message: format!("unexpected response format: {}", e),
+        })?;
+    Ok(resp.list.unwrap_or_default())
+}
+
+/// Parses an `operations/stat` response body. A `null` `item` means
+/// nothing exists at the path.
+fn parse_stat(body: &str) -> Result<Option<FileItem>> {
+    let resp: StatResponse = serde_json::from_str(body).map_err(|e| LazyFileError::RcloneApi {
+        endpoint: commands::STAT,
+        message: format!("unexpected response format: {}", e),
+    })?;
+    Ok(resp.item)
+}
+
+/// Parses an `operations/stat` response body with metadata.
+fn parse_stat_metadata(body: &str) -> Result<Option<ObjectInfo>> {
+    let resp: StatMetadataResponse =
+        serde_json::from_str(body).map_err(|e| LazyFileError::RcloneApi {
+            endpoint: commands::STAT,
+            message: format!("unexpected response format: {}", e),
+        })?;
+    Ok(resp.item)
+}
+
+/// Parses a recursive `operations/list` response body. A missing or
+/// `null` `list` field is a valid empty directory.
+fn parse_list_tree(body: &str) -> Result<Vec<TreeEntry>> {
+    let resp: ListTreeResponse =
+        serde_json::from_str(body).map_err(|e| LazyFileError::RcloneApi {
+            endpoint: commands::LIST_FILES,
+            message: format!("unexpected response format: {}", e),
+        })?;
+    Ok(resp.list.unwrap_or_default())
+}
+
+/// Parses a recursive `operations/list` response body with the
+/// files' details, naming each by its path. A missing or `null`
+/// `list` field means no files.
+fn parse_list_nested(body: &str) -> Result<Vec<FileItem>> {
+    let resp: ListNestedResponse =
+        serde_json::from_str(body).map_err(|e| LazyFileError::RcloneApi {
+            endpoint: commands::LIST_FILES,
+            message: format!("unexpected response format: {}", e),
+        })?;
+    Ok(resp
+        .list
+        .unwrap_or_default()
+        .into_iter()
+        .map(|nested| FileItem {
+            name: nested.path,
+            ..nested.item
+        })
+        .collect())
+}
+
+/// Parses an `operations/fsinfo` response body.
+fn parse_fs_info(body: &str) -> Result<FsInfo> {
+    serde_json::from_str(body).map_err(|e| LazyFileError::RcloneApi {
+        endpoint: commands::FSINFO,
+        message: format!("unexpected response format: {}", e),
+    })
+}
+
+/// Parses an `operations/about` response body.
+fn parse_about(body: &str) -> Result<Quota> {
+    serde_json::from_str(body).map_err(|e| LazyFileError::RcloneApi {
+        endpoint: commands::ABOUT,
+        message: format!("unexpected response format: {}", e),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_valid_file_list() {
+        let body = r#"{"list":[{"Name":"a.txt","Size":10,"ModTime":"2024-01-01T00:00:00Z","IsDir":false}]}"#;
+        let items = parse_list_files(body).unwrap();
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].name(), "a.txt");
+    }
+
+    #[test]
+    fn treats_null_list_as_empty() {
+        let items = parse_list_files(r#"{"list":null}"#).unwrap();
+        assert!(items.is_empty());
+    }
+
+    #[test]
+    fn treats_missing_list_as_empty() {
+        let items = parse_list_files("{}").unwrap();
+        assert!(items.is_empty());
+    }
+
+    #[test]
+    fn rejects_malformed_body() {
+        let err = parse_list_files("not json").unwrap_err();
+        assert!(matches!(
+            err,
+            LazyFileError::RcloneApi {
+                endpoint: commands::LIST_FILES,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn rejects_wrong_item_shape() {
+        let err = parse_list_files(r#"{"list":[{"unexpected":true}]}"#).unwrap_err();
+        assert!(matches!(err, LazyFileError::RcloneApi { .. }));
+    }
+
+    #[test]
+    fn test_parses_tree_paths() {
+        let body = r#"{"list": [
+            {"Path": "photos", "Name": "photos", "Size": -1, "IsDir": true},
+            {"Path": "photos/a.jpg", "Name": "a.jpg", "Size": 2048, "IsDir": false,
+             "Hashes": {"md5": "0cc175b9c0f1b6a831c399e269772661"}}
+        ]}"#;
+        let entries = parse_list_tree(body).unwrap();
+        assert_eq!(entries[1].path, "photos/a.jpg");
+        assert_eq!(entries[1].size, 2048);
+        assert_eq!(entries[0].hash("md5"), None);
+        assert_eq!(
+            entries[1].hash("md5"),
+            Some("0cc175b9c0f1b6a831c399e269772661")
+        );
+        assert!(parse_list_tree(r#"{"list": null}"#).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_parses_nested_files_by_path() {
+        let body = r#"{"list": [
+            {"Path": "photos/2024/a.jpg", "Name": "a.jpg", "Size": 2048,
+             "MimeType": "image/jpeg", "ModTime": "2024-01-01T00:00:00Z", "IsDir": false}
+        ]}"#;
+        let items = parse_list_nested(body).unwrap();
+        assert_eq!(items[0].name(), "photos/2024/a.jpg");
+        assert_eq!(items[0].mime_type, "image/jpeg");
+        assert!(parse_list_nested(r#"{"list": null}"#).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_parses_stat_item() {
+        let body = r#"{"item": {"Path": "docs/a.txt", "Name": "a.txt", "Size": 12,
+            "MimeType": "text/plain", "ModTime": "2024-01-01T00:00:00Z", "IsDir": false}}"#;
+        let item = parse_stat(body).unwrap().unwrap();
+        assert_eq!(item.name, "a.txt");
+        assert_eq!(item.size, 12);
+        assert!(parse_stat(r#"{"item": null}"#).unwrap().is_none());
+        assert!(parse_stat("[]").is_err());
+    }
+
+    #[test]
+    fn test_parses_stat_with_metadata() {
+        let body = r#"{"item": {
+            "Name": "a.jpg", "Size": 3, "ModTime": "2024-01-01T00:00:00Z", "IsDir": false,
+            "Metadata": {"content-type": "image/jpeg", "cache-control": "no-cache"}
+        }}"#;
+        let info = parse_stat_metadata(body).unwrap().unwrap();
+        assert_eq!(info.item.name, "a.jpg");
+        assert_eq!(
+            info.metadata
+                .unwrap()
+                .get("content-type")
+                .map(String::as_str),
+            Some("image/jpeg")
+        );
+        assert!(parse_stat_metadata(r#"{"item": null}"#).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_parses_about() {
+        let quota =
+            parse_about(r#"{"total": 1000, "used": 250, "free": 750, "trashed": 5}"#).unwrap();
+        assert_eq!(quota.percent_used(), Some(25));
+        let quota = parse_about(r#"{"used": 90, "free": 10}"#).unwrap();
+        assert_eq!(quota.percent_used(), Some(90));
+        assert_eq!(parse_about(r#"{"used": 90}"#).unwrap().percent_used(), None);
+        assert!(parse_about(r#"{"used": "lots"}"#).is_err());
+    }
+
+    #[test]
+    fn test_parses_fs_info() {
+        let body = r#"{
+            "Name": "gdrive",
+            "Hashes": ["md5", "sha1"],
+            "Features": {"Copy": true, "Move": false},
+            "Precision": 1000000
+        }"#;
+        let info = parse_fs_info(body).unwrap();
+        assert_eq!(info.hashes(), ["md5", "sha1"]);
+        assert!(info.supports("Copy"));
+        assert!(!info.supports("Move"));
+        assert!(!info.supports("PublicLink"));
+        assert!(
+            parse_fs_info(r#"{"Hashes": null}"#)
+                .unwrap()
+                .hashes()
+                .is_empty()
+        );
+    }
+}
@@ -0,0 +1,386 @@
+//! Rclone JSON-RPC client implementation.
+
+mod config;
+mod jobs;
+mod listing;
+mod objects;
+mod operations;
+#[cfg(test)]
+mod test_server;
+mod transport;
+mod versions;
+
+use crate::error::{LazyFileError, Result};
+use crate::rclone::calls::{Call, CallLog};
+use crate::rclone::cancel::CancelToken;
+use crate::rclone::connection::{Connection, Credentials};
+use crate::rclone::filter::Filter;
+use crate::rclone::retry::{RequestPolicy, Retrying};
+use crate::rclone::validate::validate_host;
+use base64::Engine as _;
+use base64::engine::general_purpose::STANDARD;
+use reqwest::header::{AUTHORIZATION, HeaderMap, HeaderValue};
+use reqwest::{Certificate, Client, ClientBuilder, Identity};
+use std::path::Path;
+use tracing::{debug, trace, warn};
+
+/// HTTP client for communicating with rclone rc daemon.
+///
+/// Clones share the underlying connection pool, so background tasks
+/// get their own handle cheaply.
+#[derive(Debug, Clone)]
+pub struct RcloneClient {
+    base_url: String,
+    client: Client,
+    cancel: Option<CancelToken>,
+    policy: RequestPolicy,
+    retrying: Retrying,
+    calls: CallLog,
+}
+
+impl RcloneClient {
+    /// Creates a new RcloneClient.
+    ///
+    /// # Arguments
+    /// * `host` - Host address of rclone daemon (e.g., "localhost")
+    /// * `port` - Port number of rclone daemon (e.g., 5572)
+    ///
+    /// # Errors
+    /// Returns error if the host is not a valid hostname or IPv4
+    /// address, or if the HTTP client cannot be constructed, e.g.
+    /// when the system TLS or DNS resolver configuration fails to
+    /// load.
+    pub fn new(host: &str, port: u16) -> Result<Self> {
+        Self::connect(&Connection {
+            host: host.to_string(),
+            port,
+            ..Connection::default()
+        })
+    }
+
+    /// Creates a client for a daemon that may use HTTPS or require a
+    /// login.
+    ///
+    /// # Errors
+    /// Returns error if the host is invalid, the user name contains
+    /// `:`, the `ca_cert` or client certificate files cannot be
+    /// loaded, or the HTTP client cannot be constructed.
+    pub fn connect(connection: &Connection) -> Result<Self> {
+        validate_host(&connection.host)?;
+        let base_url = connection.base_url();
+        trace!(base_url = %base_url, "creating RcloneClient");
+
+        let mut headers = HeaderMap::new();
+        if let Some(ref credentials) = connection.credentials {
+            headers.insert(AUTHORIZATION, authorization(credentials)?);
+        }
+        let mut builder = Client::builder().default_headers(headers);
+        if connection.tls {
+            if let Some(ref path) = connection.ca_cert {
+                builder = builder.tls_certs_merge(load_ca_cert(path)?);
+            }
+            if let Some(ref cert) = connection.client_cert {
+                let identity = load_identity(cert, connection.client_key.as_deref())?;
+                builder = builder.identity(identity);
+            }
+            if connection.insecure {
+                warn!(base_url = %base_url, "TLS certificate verification disabled");
+                builder = builder.tls_danger_accept_invalid_certs(true);
+            }
+        } else {
+            // Loading the platform's trust store is slow and useless
+            // for a plain HTTP daemon.
+            builder = builder.tls_certs_only([]);
+        }
+        if let Some(ref socket) = connection.socket {
+            builder = with_socket(builder, socket)?;
+        }
+        let client = builder.build()?;
+        Ok(Self {
+            base_url,
+            client,
+            cancel: None,
+            policy: RequestPolicy::default(),
+            retrying: Retrying::default(),
+            calls: CallLog::default(),
+        })
+    }
+
+    /// Returns a handle that times out and retries requests as
+    /// `policy` says.
+    pub fn with_policy(&self, policy: RequestPolicy) -> Self {
+        Self {
+            policy,
+            ..self.clone()
+        }
+    }
+
+    /// Returns how requests time out and are retried.
+    pub fn policy(&self) -> &RequestPolicy {
+        &self.policy
+    }
+
+    /// Returns the retry a request of this client, or of a clone, is
+    /// on, and how many it gets, while one is being retried.
+    pub fn retrying(&self) -> Option<(u32, u32)> {
+        self.retrying
+            .current()
+            .map(|retry| (retry, self.policy.retries))
+    }
+
+    /// Returns a handle whose requests give up as soon as `token` is
+    /// cancelled, dropping the HTTP request instead of waiting for
+    /// the daemon to answer.
+    pub fn with_cancel(&self, token: CancelToken) -> Self {
+        Self {
+            cancel: Some(token),
+            ..self.clone()
+        }
+    }
+
+    /// Returns the last calls this client and its clones sent, newest
+    /// first.
+    pub fn calls(&self) -> Vec<Call> {
+        self.calls.newest_first()
+    }
+}
+
+/// Routes every connection through a Unix socket.
+#[cfg(unix)]
+fn with_socket(builder: ClientBuilder, socket: &Path) -> Result<ClientBuilder> {
+    debug!(socket = %socket.display(), "connecting through Unix socket");
+    Ok(builder.unix_socket(socket.to_path_buf()))
+}
+
+#[cfg(not(unix))]
+fn with_socket(_builder: ClientBuilder, _socket: &Path) -> Result<ClientBuilder> {
+    Err(LazyFileError::InvalidInput {
+        field: "socket",
+        reason: "Unix sockets are not supported on this platform",
+    })
+}
+
+/// Reads the PEM bundle of CAs to trust in addition to the system's.
+fn load_ca_cert(path: &Path) -> Result<Vec<Certificate>> {
+    let invalid = |message: String| LazyFileError::Certificate {
+        path: path.to_path_buf(),
+        message,
+    };
+    let pem = std::fs::read(path).map_err(|e| invalid(e.to_string()))?;
+    let certs = Certificate::from_pem_bundle(&pem).map_err(|e| invalid(e.to_string()))?;
+    if certs.is_empty() {
+        return Err(invalid("no PEM certificate found".to_string()));
+    }
+    debug!(path = %path.display(), count = certs.len(), "loaded CA certificates");
+    Ok(certs)
+}
+
+/// Reads a client certificate and its private key, which may live in
+/// the certificate file or in `key`.
+fn load_identity(cert: &Path, key: Option<&Path>) -> Result<Identity> {
+    let read = |path: &Path| {
+        std::fs::read(path).map_err(|e| LazyFileError::Certificate {
+            path: path.to_path_buf(),
+            message: e.to_string(),
+        })
+    };
+    let mut pem = read(cert)?;
+    if let Some(key) = key {
+        pem.push(b'\n');
+        pem.extend(read(key)?);
+    }
+    let identity = Identity::from_pem(&pem).map_err(|e| LazyFileError::Certificate {
+        path: cert.to_path_buf(),
+        message: format!("not a PEM certificate and private key: {}", e),
+    })?;
+    debug!(path = %cert.display(), "loaded client certificate");
+    Ok(identity)
+}
+
+/// Builds the `Authorization` header for a login. The value is
+/// marked sensitive so it is never printed.
+fn authorization(credentials: &Credentials) -> Result<HeaderValue> {
+    let (field, value) = match credentials {
+        Credentials::Basic { user, password } => {
+            if user.contains(':') {
+                return Err(LazyFileError::InvalidInput {
+                    field: "user",
+                    reason: "must not contain ':'",
+                });
+            }
+            let token = STANDARD.encode(format!("{}:{}", user, password));
+            ("user", format!("Basic {}", token))
+        }
+        Credentials::Bearer { token } => ("bearer token", format!("Bearer {}", token)),
+    };
+    let mut value = HeaderValue::from_str(&value).map_err(|_| LazyFileError::InvalidInput {
+        field,
+        reason: "contains characters not allowed in a header",
+    })?;
+    value.set_sensitive(true);
+    Ok(value)
+}
+
+/// Normalizes a remote name and path into the fs string and remote
+/// path fields the rclone API expects.
+fn fs_path(remote: &str, path: &str) -> (String, String) {
+    let fs = format!("{}:", remote);
+    let remote_path = path.trim_start_matches('/').to_string();
+    (fs, remote_path)
+}
+
+/// Splits a path within a remote into its directory and name.
+fn split_parent(path: &str) -> (&str, &str) {
+    path.rsplit_once('/').unwrap_or(("", path))
+}
+
+/// Joins a directory within a remote and a name below it.
+fn join_path(dir: &str, name: &str) -> String {
+    if dir.is_empty() {
+        name.to_string()
+    } else {
+        format!("{}/{}", dir, name)
+    }
+}
+
+/// Adds `filter` to an rc request as its `_filter` parameter, unless
+/// the filter is empty.
+fn with_filter(request: &mut serde_json::Value, filter: &Filter) {
+    if !filter.is_empty() {
+        request["_filter"] = filter.to_rc();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fs_path_strips_leading_slashes() {
+        assert_eq!(
+            fs_path("gdrive", "/docs/file.txt"),
+            ("gdrive:".to_string(), "docs/file.txt".to_string())
+        );
+        assert_eq!(
+            fs_path("gdrive", ""),
+            ("gdrive:".to_string(), String::new())
+        );
+    }
+
+    #[test]
+    fn test_basic_auth_is_sensitive() {
+        let value = authorization(&Credentials::Basic {
+            user: "admin".to_string(),
+            password: "secret".to_string(),
+        })
+        .unwrap();
+        assert_eq!(value.to_str().unwrap(), "Basic YWRtaW46c2VjcmV0");
+        assert!(value.is_sensitive());
+    }
+
+    #[test]
+    fn test_bearer_token_is_sensitive() {
+        let value = authorization(&Credentials::Bearer {
+            token: "abc123".to_string(),
+        })
+        .unwrap();
+        assert_eq!(value.to_str().unwrap(), "Bearer abc123");
+        assert!(value.is_sensitive());
+    }
+
+    #[test]
+    fn test_rejects_bearer_token_with_newline() {
+        let err = authorization(&Credentials::Bearer {
+            token: "abc\r\nX-Evil: 1".to_string(),
+        })
+        .unwrap_err();
+        assert!(matches!(
+            err,
+            LazyFileError::InvalidInput {
+                field: "bearer token",
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn test_rejects_colon_in_user() {
+        let connection = Connection {
+            credentials: Some(Credentials::Basic {
+                user: "a:b".to_string(),
+                password: String::new(),
+            }),
+            ..Connection::default()
+        };
+        assert!(matches!(
+            RcloneClient::connect(&connection).unwrap_err(),
+            LazyFileError::InvalidInput { field: "user", .. }
+        ));
+    }
+
+    #[test]
+    fn test_ca_cert_must_hold_a_certificate() {
+        let path = std::env::temp_dir().join(format!("lazyfile-ca-{}.pem", std::process::id()));
+        std::fs::write(&path, "not a certificate").unwrap();
+        let connection = Connection {
+            tls: true,
+            ca_cert: Some(path.clone()),
+            ..Connection::default()
+        };
+        let err = RcloneClient::connect(&connection).unwrap_err();
+        let _ = std::fs::remove_file(&path);
+        assert!(matches!(err, LazyFileError::Certificate { .. }), "{}", err);
+    }
+
+    #[test]
+    fn test_client_cert_needs_a_private_key() {
+        let path = std::env::temp_dir().join(format!("lazyfile-id-{}.pem", std::process::id()));
+        std::fs::write(
+            &path,
+            "-----BEGIN CERTIFICATE-----\n-----END CERTIFICATE-----\n",
+        )
+        .unwrap();
+        let connection = Connection {
+            tls: true,
+            client_cert: Some(path.clone()),
+            ..Connection::default()
+        };
+        let err = RcloneClient::connect(&connection).unwrap_err();
+        let _ = std::fs::remove_file(&path);
+        assert!(matches!(err, LazyFileError::Certificate { .. }), "{}", err);
+    }
+
+    #[test]
+    fn test_missing_client_key_is_reported() {
+        let err = load_identity(
+            Path::new("/nonexistent/client.pem"),
+            Some(Path::new("/nonexistent/client.key")),
+        )
+        .unwrap_err();
+        assert!(
+            err.to_string().contains("/nonexistent/client.pem"),
+            "{}",
+            err
+        );
+    }
+
+    #[test]
+    fn test_missing_ca_cert_is_reported() {
+        let connection = Connection {
+            tls: true,
+            ca_cert: Some("/nonexistent/ca.pem".into()),
+            ..Connection::default()
+        };
+        let err = RcloneClient::connect(&connection).unwrap_err();
+        assert!(err.to_string().contains("/nonexistent/ca.pem"), "{}", err);
+    }
+
+    #[test]
+    fn rejects_invalid_host() {
+        let err = RcloneClient::new("http://evil", 5572).unwrap_err();
+        assert!(matches!(
+            err,
+            LazyFileError::InvalidInput { field: "host", .. }
+        ));
+    }
+}
@@ -0,0 +1,321 @@
+//! File contents: reading, downloading and uploading them over the
+//! daemon's object server.
+
+use super::transport::{check_status, request_failed};
+use super::{RcloneClient, fs_path, split_parent};
+use crate::error::{LazyFileError, Result};
+use crate::rclone::commands;
+use crate::rclone::types::ByteRange;
+use crate::rclone::validate::{validate_path, validate_remote_name};
+use bytes::Bytes;
+use http_body_util::channel::Channel;
+use reqwest::StatusCode;
+use reqwest::header::RANGE;
+use reqwest::multipart::{Form, Part};
+use reqwest::{Body, Url};
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+use tracing::{debug, info, trace};
+
+/// Bytes read at a time for a streaming upload.
+const STREAM_CHUNK_SIZE: usize = 256 * 1024;
+
+/// Chunks of a streaming upload read ahead of the request.
+const STREAM_CHUNKS: usize = 4;
+
+impl RcloneClient {
+    /// Reads up to `limit` bytes from the start of a file.
+    ///
+    /// Objects are fetched over plain HTTP from the daemon's
+    /// `[remote:]/path` route, which rclone only serves when started
+    /// with `--rc-serve`.
+    ///
+    /// # Errors
+    /// Returns error if rclone daemon is unreachable, does not serve
+    /// objects, or the file does not exist.
+    pub async fn read_file(&self, remote: &str, path: &str, limit: u64) -> Result<Vec<u8>> {
+        self.get_object(remote, path, Some(limit)).await
+    }
+
+    /// Downloads a whole file. Needs `--rc-serve`, like
+    /// [`Self::read_file`].
+    ///
+    /// # Errors
+    /// Returns error if rclone daemon is unreachable, does not serve
+    /// objects, or the file does not exist.
+    pub async fn download_file(&self, remote: &str, path: &str) -> Result<Vec<u8>> {
+        self.get_object(remote, path, None).await
+    }
+
+    /// Streams the file at `path`, or the bytes of it in `range`, into
+    /// `out` as they arrive. Needs `--rc-serve`, like
+    /// [`Self::read_file`]. The request has no timeout, so a large file
+    /// takes as long as it needs. Returns the number of bytes written.
+    ///
+    /// # Errors
+    /// Returns error if rclone daemon is unreachable, does not serve
+    /// objects, or the file does not exist, or `out` cannot be
+    /// written.
+    pub async fn cat_file(
+        &self,
+        remote: &str,
+        path: &str,
+        range: Option<ByteRange>,
+        out: &mut (impl AsyncWrite + Unpin),
+    ) -> Result<u64> {
+        validate_remote_name(remote)?;
+        validate_path(path)?;
+        let url = self.object_url(&format!("{}:", remote), path)?;
+        debug!(remote, path, range = ?range, "streaming file");
+
+        let endpoint = commands::SERVE_OBJECT;
+        let build = || {
+            let request = self.client.get(url.clone());
+            match range {
+                Some(range) => request.header(RANGE, range.header()),
+                None => request,
+            }
+        };
+        let mut response = self
+            .cancellable(endpoint, self.send(endpoint, build))
+            .await?;
+        // A server that ignores the range sends the whole object.
+        let (mut skip, mut left) = match range {
+            Some(range) if response.status() != StatusCode::PARTIAL_CONTENT => {
+                (range.start, range.count())
+            }
+            _ => (0, None),
+        };
+        let mut written = 0u64;
+        while left != Some(0) {
+            let next = async {
+                response
+                    .chunk()
+                    .await
+                    .map_err(|e| request_failed(endpoint, e))
+            };
+            let Some(chunk) = self.cancellable(endpoint, next).await? else {
+                break;
+            };
+            let skipped = usize::try_from(skip).unwrap_or(usize::MAX).min(chunk.len());
+            skip -= skipped as u64;
+            let mut chunk = &chunk[skipped..];
+            if let Some(ref mut left) = left {
+                let take = usize::try_from(*left)
+                    .unwrap_or(usize::MAX)
+                    .min(chunk.len());
+                *left -= take as u64;
+                chunk = &chunk[..take];
+            }
+            out.write_all(chunk).await?;
+            written += chunk.len() as u64;
+        }
+        out.flush().await?;
+        trace!(bytes = written, "streamed file");
+        Ok(written)
+    }
+
+    /// Uploads `contents` to `path`, replacing any existing file.
+    ///
+    /// # Errors
+    /// Returns error if rclone daemon is unreachable or responds
+    /// with an error.
+    pub async fn upload_file(&self, remote: &str, path: &str, contents: Vec<u8>) -> Result<()> {
+        validate_remote_name(remote)?;
+        validate_path(path)?;
+        let (fs, remote_path) = fs_path(remote, path);
+        let (dir, name) = match remote_path.rsplit_once('/') {
+            Some((dir, name)) => (dir.to_string(), name.to_string()),
+            None => (String::new(), remote_path.clone()),
+        };
+        debug!(remote, path = %remote_path, len = contents.len(), "uploading file");
+
+        let endpoint = commands::UPLOAD_FILE;
+        let url = self.upload_url(&fs, &dir)?;
+        let form = Form::new().part("file0", Part::bytes(contents).file_name(name));
+        self.cancellable(endpoint, async {
+            // The form is consumed by sending it, so an upload is
+            // never retried.
+            let request = self
+                .client
+                .post(url)
+                .timeout(self.policy.timeout)
+                .multipart(form);
+            let response = self
+                .send_timed(endpoint, request)
+                .await
+                .map_err(|e| request_failed(endpoint, e))?;
+            check_status(endpoint, response).await
+        })
+        .await?;
+        info!(remote, path = %remote_path, "file uploaded");
+        Ok(())
+    }
+
+    /// Streams everything `reader` yields into the file at `path`,
+    /// replacing any existing file, without holding it in memory. The
+    /// request has no timeout: it lasts until `reader` ends. Returns
+    /// the number of bytes sent.
+    ///
+    /// # Errors
+    /// Returns error if `reader` fails, or the rclone daemon is
+    /// unreachable or responds with an error.
+    pub async fn upload_stream<R>(&self, remote: &str, path: &str, mut reader: R) -> Result<u64>
+    where
+        R: AsyncRead + Unpin + Send + 'static,
+    {
+        validate_remote_name(remote)?;
+        validate_path(path)?;
+        let (fs, remote_path) = fs_path(remote, path);
+        let (dir, name) = split_parent(&remote_path);
+        debug!(remote, path = %remote_path, "streaming upload");
+
+        let (mut sender, body) = Channel::<Bytes, std::io::Error>::new(STREAM_CHUNKS);
+        let feeder = tokio::spawn(async move {
+            let mut sent = 0u64;
+            loop {
+                let mut chunk = vec![0; STREAM_CHUNK_SIZE];
+                let read = match reader.read(&mut chunk).await {
+                    Ok(0) => return Ok(sent),
+                    Ok(read) => read,
+                    Err(e) => {
+                        let message = e.to_string();
+                        sender.abort(e);
+                        return Err(std::io::Error::other(message));
+                    }
+                };
+                chunk.truncate(read);
+                sent += read as u64;
+                // A closed channel means the request already failed,
+                // which is the error worth reporting.
+                if sender.send_data(Bytes::from(chunk)).await.is_err() {
+                    return Ok(sent);
+                }
+            }
+        });
+
+        let endpoint = commands::UPLOAD_FILE;
+        let url = self.upload_url(&fs, dir)?;
+        let part = Part::stream(Body::wrap(body)).file_name(name.to_string());
+        let form = Form::new().part("file0", part);
+        let uploaded = self
+            .cancellable(endpoint, async {
+                let request = self.client.post(url).multipart(form);
+                let response = self
+                    .send_timed(endpoint, request)
+                    .await
+                    .map_err(|e| request_failed(endpoint, e))?;
+                check_status(endpoint, response).await
+            })
+            .await;
+        match uploaded {
+            Ok(_) => {}
+            // A failed read is what failed the upload, and says why.
+            Err(e) if feeder.is_finished() => {
+                return match feeder.await {
+                    Ok(Err(read)) => Err(read.into()),
+                    _ => Err(e),
+                };
+            }
+            Err(e) => {
+                feeder.abort();
+                return Err(e);
+            }
+        }
+        let sent = feeder
+            .await
+            .map_err(|e| std::io::Error::other(e.to_string()))??;
+        info!(remote, path = %remote_path, bytes = sent, "stream uploaded");
+        Ok(sent)
+    }
+
+    /// Fetches an object from the `--rc-serve` route, optionally only
+    /// its first `limit` bytes.
+    async fn get_object(&self, remote: &str, path: &str, limit: Option<u64>) -> Result<Vec<u8>> {
+        validate_remote_name(remote)?;
+        validate_path(path)?;
+        let url = self.object_url(&format!("{}:", remote), path)?;
+        debug!(remote, path, limit, "reading file");
+        self.fetch_object(url, limit).await
+    }
+
+    /// Fetches the object at `url`, optionally only its first `limit`
+    /// bytes.
+    pub(super) async fn fetch_object(&self, url: Url, limit: Option<u64>) -> Result<Vec<u8>> {
+        let unreachable = |e| request_failed(commands::SERVE_OBJECT, e);
+        let build = || {
+            let request = self.client.get(url.clone()).timeout(self.policy.timeout);
+            match limit {
+                Some(limit) => {
+                    request.header(RANGE, format!("bytes=0-{}", limit.saturating_sub(1)))
+                }
+                None => request,
+            }
+        };
+        let mut bytes = self
+            .cancellable(commands::SERVE_OBJECT, async {
+                let response = self.send(commands::SERVE_OBJECT, build).await?;
+                response.bytes().await.map_err(unreachable)
+            })
+            .await?
+            .to_vec();
+        if let Some(limit) = limit {
+            // A server that ignores the range sends the whole object.
+            bytes.truncate(usize::try_from(limit).unwrap_or(usize::MAX));
+        }
+        trace!(len = bytes.len(), "read file");
+        Ok(bytes)
+    }
+
+    /// Builds the `--rc-serve` URL of an object on `fs`, e.g.
+    /// `gdrive:`, percent-encoding each path segment.
+    pub(super) fn object_url(&self, fs: &str, path: &str) -> Result<Url> {
+        let invalid = |message: String| LazyFileError::RcloneApi {
+            endpoint: commands::SERVE_OBJECT,
+            message,
+        };
+        let mut url = Url::parse(&self.base_url).map_err(|e| invalid(e.to_string()))?;
+        url.path_segments_mut()
+            .map_err(|()| invalid("base URL cannot have a path".to_string()))?
+            .push(&format!("[{}]", fs))
+            .extend(path.split('/').filter(|segment| !segment.is_empty()));
+        Ok(url)
+    }
+
+    /// Builds the `operations/uploadfile` URL. Multipart requests
+    /// take their parameters from the query string.
+    fn upload_url(&self, fs: &str, dir: &str) -> Result<Url> {
+        Url::parse_with_params(
+            &format!("{}/{}", self.base_url, commands::UPLOAD_FILE),
+            [("fs", fs), ("remote", dir)],
+        )
+        .map_err(|e| LazyFileError::RcloneApi {
+            endpoint: commands::UPLOAD_FILE,
+            message: e.to_string(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_object_url_encodes_segments() {
+        let client = RcloneClient::new("localhost", 5572).unwrap();
+        let url = client.object_url("gdrive:", "/my docs/a#b.txt").unwrap();
+        assert_eq!(
+            url.as_str(),
+            "http://localhost:5572/[gdrive:]/my%20docs/a%23b.txt"
+        );
+    }
+
+    #[test]
+    fn test_upload_url_puts_parameters_in_query() {
+        let client = RcloneClient::new("localhost", 5572).unwrap();
+        let url = client.upload_url("gdrive:", "my docs").unwrap();
+        assert_eq!(
+            url.as_str(),
+            "http://localhost:5572/operations/uploadfile?fs=gdrive%3A&remote=my+docs"
+        );
+    }
+}
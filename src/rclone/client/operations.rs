@@ -0,0 +1,383 @@
+//! Changing files and directories: creating, deleting, copying and
+//! moving them.
+
+use super::{RcloneClient, fs_path};
+use crate::error::{LazyFileError, Result};
+use crate::rclone::commands;
+use crate::rclone::filter::Filter;
+use crate::rclone::types::{
+    DeleteFileRequest, DirTransferRequest, FileTransferRequest, JobStarted, MkdirRequest,
+    PurgeRequest,
+};
+use crate::rclone::validate::{validate_path, validate_remote_name};
+use std::time::Duration;
+use tracing::{debug, info};
+
+/// How often a directory transfer's job is checked on.
+const JOB_POLL: Duration = Duration::from_millis(500);
+
+impl RcloneClient {
+    /// Creates a new directory in a remote.
+    ///
+    /// # Errors
+    /// Returns error if rclone daemon is unreachable or responds
+    /// with an error.
+    pub async fn mkdir(&self, remote: &str, path: &str) -> Result<()> {
+        validate_remote_name(remote)?;
+        validate_path(path)?;
+        let (fs, remote_path) = fs_path(remote, path);
+        debug!(remote, path = %remote_path, "creating directory");
+        let request = MkdirRequest {
+            fs,
+            remote: remote_path,
+        };
+        self.post_command(commands::MKDIR, &request).await?;
+        info!(remote, path = %request.remote, "directory created");
+        Ok(())
+    }
+
+    /// Deletes a file from a remote.
+    ///
+    /// # Errors
+    /// Returns error if rclone daemon is unreachable or responds
+    /// with an error.
+    pub async fn delete_file(&self, remote: &str, path: &str) -> Result<()> {
+        validate_remote_name(remote)?;
+        validate_path(path)?;
+        let (fs, remote_path) = fs_path(remote, path);
+        debug!(remote, path = %remote_path, "deleting file");
+        let request = DeleteFileRequest {
+            fs,
+            remote: remote_path,
+        };
+        self.post_command(commands::DELETE_FILE, &request).await?;
+        info!(remote, path = %request.remote, "file deleted");
+        Ok(())
+    }
+
+    /// Deletes a directory and its contents from a remote.
+    ///
+    /// # Errors
+    /// Returns error if rclone daemon is unreachable or responds
+    /// with an error.
+    pub async fn purge(&self, remote: &str, path: &str) -> Result<()> {
+        validate_remote_name(remote)?;
+        validate_path(path)?;
+        let (fs, remote_path) = fs_path(remote, path);
+        debug!(remote, path = %remote_path, "purging directory");
+        let request = PurgeRequest {
+            fs,
+            remote: remote_path,
+        };
+        self.post_command(commands::PURGE, &request).await?;
+        info!(remote, path = %request.remote, "directory purged");
+        Ok(())
+    }
+
+    /// Has the daemon download `url` into the file at `path`, waiting
+    /// until the job finishes. The data never passes through this
+    /// machine.
+    ///
+    /// # Errors
+    /// Returns error if rclone daemon is unreachable, responds with
+    /// an error, or the download fails.
+    pub async fn copy_url(&self, remote: &str, path: &str, url: &str) -> Result<()> {
+        validate_remote_name(remote)?;
+        validate_path(path)?;
+        let (fs, remote_path) = fs_path(remote, path);
+        debug!(remote, path = %remote_path, url, "copying URL");
+        let request = serde_json::json!({
+            "fs": fs,
+            "remote": remote_path,
+            "url": url,
+            "autoFilename": false,
+            "_async": true,
+        });
+        let body = self.post_json(commands::COPY_URL, &request).await?;
+        let started: JobStarted =
+            serde_json::from_str(&body).map_err(|e| LazyFileError::RcloneApi {
+                endpoint: commands::COPY_URL,
+                message: format!("unexpected response format: {}", e),
+            })?;
+        self.wait_job(commands::COPY_URL, started.job_id).await?;
+        info!(remote, path = %remote_path, "URL copied");
+        Ok(())
+    }
+
+    /// Moves a file, or every file below a directory, to storage tier
+    /// or class `tier`, such as `Archive` or `GLACIER`.
+    ///
+    /// # Errors
+    /// Returns error if rclone daemon is unreachable or responds
+    /// with an error, e.g. because the backend has no such tier.
+    pub async fn set_tier(&self, remote: &str, path: &str, is_dir: bool, tier: &str) -> Result<()> {
+        validate_remote_name(remote)?;
+        validate_path(path)?;
+        let (fs, remote_path) = fs_path(remote, path);
+        debug!(remote, path = %remote_path, tier, is_dir, "changing storage tier");
+        if is_dir {
+            let fs = format!("{}{}", fs, remote_path);
+            self.post_command(
+                commands::SET_TIER,
+                &serde_json::json!({ "fs": fs, "tier": tier }),
+            )
+            .await?;
+        } else {
+            self.post_command(
+                commands::SET_TIER_FILE,
+                &serde_json::json!({ "fs": fs, "remote": remote_path, "tier": tier }),
+            )
+            .await?;
+        }
+        info!(remote, path = %path, tier, "storage tier changed");
+        Ok(())
+    }
+
+    /// Copies a file between remotes or within the same remote.
+    ///
+    /// # Errors
+    /// Returns error if rclone daemon is unreachable or responds
+    /// with an error.
+    pub async fn copy_file(
+        &self,
+        src_remote: &str,
+        src_path: &str,
+        dst_remote: &str,
+        dst_path: &str,
+    ) -> Result<()> {
+        self.transfer_file(
+            commands::COPY_FILE,
+            src_remote,
+            src_path,
+            dst_remote,
+            dst_path,
+        )
+        .await?;
+        info!("file copied");
+        Ok(())
+    }
+
+    /// Moves a file between remotes or within the same remote.
+    ///
+    /// # Errors
+    /// Returns error if rclone daemon is unreachable or responds
+    /// with an error.
+    pub async fn move_file(
+        &self,
+        src_remote: &str,
+        src_path: &str,
+        dst_remote: &str,
+        dst_path: &str,
+    ) -> Result<()> {
+        self.transfer_file(
+            commands::MOVE_FILE,
+            src_remote,
+            src_path,
+            dst_remote,
+            dst_path,
+        )
+        .await?;
+        info!("file moved");
+        Ok(())
+    }
+
+    /// Shared implementation for `copyfile` and `movefile`, which
+    /// take identical parameters.
+    async fn transfer_file(
+        &self,
+        endpoint: &'static str,
+        src_remote: &str,
+        src_path: &str,
+        dst_remote: &str,
+        dst_path: &str,
+    ) -> Result<()> {
+        validate_remote_name(src_remote)?;
+        validate_remote_name(dst_remote)?;
+        validate_path(src_path)?;
+        validate_path(dst_path)?;
+        let (src_fs, src) = fs_path(src_remote, src_path);
+        let (dst_fs, dst) = fs_path(dst_remote, dst_path);
+        debug!(
+            endpoint,
+            src_remote,
+            src_path = %src,
+            dst_remote,
+            dst_path = %dst,
+            "transferring file"
+        );
+        let request = FileTransferRequest {
+            src_fs,
+            src_remote: src,
+            dst_fs,
+            dst_remote: dst,
+        };
+        self.post_command(endpoint, &request).await
+    }
+
+    /// Copies a directory and everything in it that `filter` lets
+    /// through, between remotes or within one, waiting until the
+    /// daemon's job finishes.
+    ///
+    /// # Errors
+    /// Returns error if rclone daemon is unreachable, responds with
+    /// an error, or the job fails.
+    pub async fn copy_dir(
+        &self,
+        src_remote: &str,
+        src_path: &str,
+        dst_remote: &str,
+        dst_path: &str,
+        filter: &Filter,
+    ) -> Result<()> {
+        self.transfer_dir(
+            commands::COPY_DIR,
+            src_remote,
+            src_path,
+            dst_remote,
+            dst_path,
+            filter,
+        )
+        .await?;
+        info!("directory copied");
+        Ok(())
+    }
+
+    /// Moves a directory and everything in it that `filter` lets
+    /// through, between remotes or within one, waiting until the
+    /// daemon's job finishes. Emptied source directories are removed.
+    ///
+    /// # Errors
+    /// Returns error if rclone daemon is unreachable, responds with
+    /// an error, or the job fails.
+    pub async fn move_dir(
+        &self,
+        src_remote: &str,
+        src_path: &str,
+        dst_remote: &str,
+        dst_path: &str,
+        filter: &Filter,
+    ) -> Result<()> {
+        self.transfer_dir(
+            commands::MOVE_DIR,
+            src_remote,
+            src_path,
+            dst_remote,
+            dst_path,
+            filter,
+        )
+        .await?;
+        info!("directory moved");
+        Ok(())
+    }
+
+    /// Shared implementation for `sync/copy` and `sync/move`. They run
+    /// as a job, so a long transfer neither hits the request timeout
+    /// nor hides from the jobs view.
+    async fn transfer_dir(
+        &self,
+        endpoint: &'static str,
+        src_remote: &str,
+        src_path: &str,
+        dst_remote: &str,
+        dst_path: &str,
+        filter: &Filter,
+    ) -> Result<()> {
+        validate_remote_name(src_remote)?;
+        validate_remote_name(dst_remote)?;
+        validate_path(src_path)?;
+        validate_path(dst_path)?;
+        let (src_fs, src) = fs_path(src_remote, src_path);
+        let (dst_fs, dst) = fs_path(dst_remote, dst_path);
+        debug!(
+            endpoint,
+            src_remote,
+            src_path = %src,
+            dst_remote,
+            dst_path = %dst,
+            filter = %filter.describe(),
+            "transferring directory"
+        );
+        let request = DirTransferRequest {
+            src_fs: format!("{}{}", src_fs, src),
+            dst_fs: format!("{}{}", dst_fs, dst),
+            create_empty_src_dirs: true,
+            delete_empty_src_dirs: (endpoint == commands::MOVE_DIR).then_some(true),
+            is_async: true,
+            filter: (!filter.is_empty()).then(|| filter.to_rc()),
+        };
+        let body = self.post_json(endpoint, &request).await?;
+        let started: JobStarted =
+            serde_json::from_str(&body).map_err(|e| LazyFileError::RcloneApi {
+                endpoint,
+                message: format!("unexpected response format: {}", e),
+            })?;
+        self.wait_job(endpoint, started.job_id).await
+    }
+
+    /// Checks on job `id` until it finishes, and returns its error if
+    /// it failed.
+    async fn wait_job(&self, endpoint: &'static str, id: i64) -> Result<()> {
+        loop {
+            let job = self.job_status(id).await?;
+            if job.finished {
+                return if job.success {
+                    Ok(())
+                } else {
+                    Err(LazyFileError::RcloneApi {
+                        endpoint,
+                        message: job.error,
+                    })
+                };
+            }
+            tokio::time::sleep(JOB_POLL).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rclone::client::test_server::capture_request;
+
+    #[tokio::test]
+    async fn rejects_path_traversal_before_request() {
+        let client = RcloneClient::new("localhost", 5572).expect("valid host");
+        let err = client.mkdir("remote", "../evil").await.unwrap_err();
+        assert!(matches!(
+            err,
+            LazyFileError::InvalidInput { field: "path", .. }
+        ));
+    }
+
+    #[tokio::test]
+    async fn rejects_remote_name_with_colon_before_request() {
+        let client = RcloneClient::new("localhost", 5572).expect("valid host");
+        let err = client.delete_file("a:b", "file.txt").await.unwrap_err();
+        assert!(matches!(
+            err,
+            LazyFileError::InvalidInput {
+                field: "remote name",
+                ..
+            }
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_directory_copy_sends_the_filter() {
+        let (port, server) = capture_request().await;
+        let client = RcloneClient::new("127.0.0.1", port).unwrap();
+        let filter = Filter::new("10M", "", "7d", "").unwrap();
+        let result = client
+            .copy_dir("s3", "photos", "b2", "backup", &filter)
+            .await;
+        // The stand-in answers without a job id.
+        assert!(result.is_err());
+
+        let body = server.await.unwrap();
+        assert_eq!(body["srcFs"], "s3:photos");
+        assert_eq!(
+            body["_filter"],
+            serde_json::json!({ "MinSize": "10M", "MaxAge": "7d" })
+        );
+    }
+}
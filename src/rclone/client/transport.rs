@@ -0,0 +1,347 @@
+//! Sending requests: timing, cancelling and retrying them, and turning
+//! failures into errors.
+
+use super::RcloneClient;
+use crate::error::{LazyFileError, Result};
+use crate::rclone::calls::Call;
+use crate::rclone::retry;
+use reqwest::{RequestBuilder, Response};
+use serde::Serialize;
+use std::future::Future;
+use std::time::{Duration, Instant};
+use tracing::{debug, error, trace, warn};
+
+impl RcloneClient {
+    /// Sends `request`, recording how long it took and how it was
+    /// answered.
+    pub(super) async fn send_timed(
+        &self,
+        endpoint: &'static str,
+        request: RequestBuilder,
+    ) -> reqwest::Result<Response> {
+        let started = Instant::now();
+        let outcome = request.send().await;
+        self.calls.record(Call {
+            endpoint,
+            duration: started.elapsed(),
+            status: outcome
+                .as_ref()
+                .ok()
+                .map(|response| response.status().as_u16()),
+        });
+        outcome
+    }
+
+    /// Runs `request`, unless the client's cancel token fires first.
+    ///
+    /// # Errors
+    /// Returns `Cancelled` if it does, and whatever `request` returns
+    /// otherwise.
+    pub(super) async fn cancellable<T>(
+        &self,
+        endpoint: &'static str,
+        request: impl Future<Output = Result<T>>,
+    ) -> Result<T> {
+        let Some(ref token) = self.cancel else {
+            return request.await;
+        };
+        tokio::select! {
+            biased;
+            () = token.cancelled() => {
+                debug!(endpoint, "request cancelled");
+                Err(LazyFileError::Cancelled { endpoint })
+            }
+            result = request => result,
+        }
+    }
+
+    /// Sends a POST request with a JSON body, returning the
+    /// response text on success.
+    pub(super) async fn post_json<B: Serialize>(
+        &self,
+        endpoint: &'static str,
+        body: &B,
+    ) -> Result<String> {
+        self.post_json_timeout(endpoint, body, self.policy.timeout)
+            .await
+    }
+
+    /// Like [`Self::post_json`], giving the request `timeout` instead
+    /// of the default.
+    pub(super) async fn post_json_timeout<B: Serialize>(
+        &self,
+        endpoint: &'static str,
+        body: &B,
+        timeout: Duration,
+    ) -> Result<String> {
+        let url = format!("{}/{}", self.base_url, endpoint);
+        trace!(endpoint, "POST request");
+
+        self.cancellable(endpoint, async {
+            let response = self
+                .send(endpoint, || {
+                    self.client.post(&url).timeout(timeout).json(body)
+                })
+                .await?;
+            response
+                .text()
+                .await
+                .map_err(|e| request_failed(endpoint, e))
+        })
+        .await
+    }
+
+    /// Sends the request `build` makes, building it again for each
+    /// retry of a failure that never reached the daemon.
+    ///
+    /// # Errors
+    /// Returns the last failure once the policy's retries are used
+    /// up, or the first one that is not worth retrying.
+    pub(super) async fn send(
+        &self,
+        endpoint: &'static str,
+        build: impl Fn() -> RequestBuilder,
+    ) -> Result<Response> {
+        let mut guard = None;
+        let mut retry = 0;
+        loop {
+            let outcome = self.send_timed(endpoint, build()).await;
+            let transient = match outcome {
+                Ok(ref response) => retry::is_transient_status(response.status()),
+                Err(ref e) => retry::is_transient(e),
+            };
+            if !transient || retry >= self.policy.retries {
+                drop(guard);
+                let response = outcome.map_err(|e| request_failed(endpoint, e))?;
+                return check_status(endpoint, response).await;
+            }
+            retry += 1;
+            let delay = self.policy.delay(retry);
+            warn!(
+                endpoint,
+                retry,
+                delay_ms = delay.as_millis() as u64,
+                "request failed, retrying"
+            );
+            guard = Some(self.retrying.start(retry));
+            tokio::time::sleep(delay).await;
+        }
+    }
+
+    /// Sends a POST command and discards the response body.
+    pub(super) async fn post_command<B: Serialize>(
+        &self,
+        endpoint: &'static str,
+        body: &B,
+    ) -> Result<()> {
+        self.post_json(endpoint, body).await?;
+        Ok(())
+    }
+}
+
+/// Turns a failed request into an error, singling out certificate
+/// verification failures so they do not read as an unreachable
+/// daemon.
+pub(super) fn request_failed(endpoint: &'static str, e: reqwest::Error) -> LazyFileError {
+    if let Some(reason) = certificate_problem(&e) {
+        error!(endpoint, reason = %reason, "certificate not trusted");
+        return LazyFileError::UntrustedCertificate { reason };
+    }
+    LazyFileError::RcloneApi {
+        endpoint,
+        message: e.to_string(),
+    }
+}
+
+/// Returns the innermost cause in `err`'s source chain that mentions
+/// a certificate. reqwest does not expose the TLS error type, so its
+/// message is all there is to go by.
+pub(super) fn certificate_problem(err: &(dyn std::error::Error + 'static)) -> Option<String> {
+    let mut found = None;
+    let mut cause = Some(err);
+    while let Some(e) = cause {
+        let message = e.to_string();
+        if message.contains("certificate") {
+            found = Some(message);
+        }
+        cause = e.source();
+    }
+    found
+}
+
+/// Turns a non-success response into `RcloneStatus`, keeping the
+/// body rclone uses to explain the failure.
+pub(super) async fn check_status(endpoint: &'static str, response: Response) -> Result<Response> {
+    let status = response.status();
+    if status.is_success() {
+        return Ok(response);
+    }
+    let body = response.text().await.unwrap_or_default();
+    error!(endpoint, %status, "request failed");
+    Err(LazyFileError::RcloneStatus {
+        endpoint,
+        status,
+        body,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rclone::connection::Connection;
+    use crate::rclone::retry::RequestPolicy;
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_talks_http_over_unix_socket() {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let socket =
+            std::env::temp_dir().join(format!("lazyfile-test-{}.sock", std::process::id()));
+        let _ = std::fs::remove_file(&socket);
+        let listener = tokio::net::UnixListener::bind(&socket).unwrap();
+        tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let mut request = [0u8; 1024];
+            let _ = stream.read(&mut request).await.unwrap();
+            let body = r#"{"remotes":["gdrive"]}"#;
+            let response = format!(
+                "HTTP/1.1 200 OK\r\ncontent-length: {}\r\n\r\n{}",
+                body.len(),
+                body
+            );
+            stream.write_all(response.as_bytes()).await.unwrap();
+        });
+
+        let client = RcloneClient::connect(&Connection {
+            socket: Some(socket.clone()),
+            ..Connection::default()
+        })
+        .unwrap();
+        let remotes = client.list_remotes().await.unwrap();
+        let _ = std::fs::remove_file(&socket);
+        assert_eq!(remotes, ["gdrive"]);
+    }
+
+    #[tokio::test]
+    async fn test_retries_when_the_daemon_is_unavailable() {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        tokio::spawn(async move {
+            let body = r#"{"remotes":["gdrive"]}"#;
+            let unavailable = "HTTP/1.1 503 Service Unavailable\r\nconnection: close\r\n\
+                               content-length: 0\r\n\r\n";
+            let answers = [
+                unavailable.to_string(),
+                unavailable.to_string(),
+                format!(
+                    "HTTP/1.1 200 OK\r\nconnection: close\r\ncontent-length: {}\r\n\r\n{}",
+                    body.len(),
+                    body
+                ),
+            ];
+            for answer in answers {
+                let (mut stream, _) = listener.accept().await.unwrap();
+                let mut request = [0u8; 1024];
+                let _ = stream.read(&mut request).await.unwrap();
+                stream.write_all(answer.as_bytes()).await.unwrap();
+            }
+        });
+
+        let policy = RequestPolicy {
+            retries: 0,
+            backoff: Duration::from_millis(1),
+            ..RequestPolicy::default()
+        };
+        let client = RcloneClient::new("127.0.0.1", port).unwrap();
+        let err = client
+            .with_policy(policy.clone())
+            .list_remotes()
+            .await
+            .unwrap_err();
+        assert!(matches!(err, LazyFileError::RcloneStatus { .. }));
+
+        let client = client.with_policy(RequestPolicy {
+            retries: 1,
+            ..policy
+        });
+        assert_eq!(client.list_remotes().await.unwrap(), ["gdrive"]);
+        assert_eq!(client.retrying(), None);
+        let statuses: Vec<_> = client.calls().iter().map(|call| call.status).collect();
+        assert_eq!(statuses, [Some(200), Some(503), Some(503)]);
+    }
+
+    #[tokio::test]
+    async fn test_gateway_timeout_is_not_retried() {
+        use std::sync::Arc;
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let requests = Arc::new(AtomicUsize::new(0));
+        let seen = Arc::clone(&requests);
+        tokio::spawn(async move {
+            let timeout = "HTTP/1.1 504 Gateway Timeout\r\nconnection: close\r\n\
+                           content-length: 0\r\n\r\n";
+            loop {
+                let (mut stream, _) = listener.accept().await.unwrap();
+                let mut request = [0u8; 1024];
+                let _ = stream.read(&mut request).await.unwrap();
+                seen.fetch_add(1, Ordering::SeqCst);
+                stream.write_all(timeout.as_bytes()).await.unwrap();
+            }
+        });
+
+        let client = RcloneClient::new("127.0.0.1", port)
+            .unwrap()
+            .with_policy(RequestPolicy {
+                retries: 3,
+                backoff: Duration::from_millis(1),
+                ..RequestPolicy::default()
+            });
+        let err = client.move_file("s3", "a.txt", "s3", "b.txt").await;
+        assert!(matches!(err, Err(LazyFileError::RcloneStatus { .. })));
+        assert_eq!(requests.load(Ordering::SeqCst), 1);
+        let statuses: Vec<_> = client.calls().iter().map(|call| call.status).collect();
+        assert_eq!(statuses, [Some(504)]);
+    }
+
+    /// Error with a message and an optional cause, for building
+    /// source chains.
+    #[derive(Debug)]
+    struct Layer(&'static str, Option<Box<Layer>>);
+
+    impl std::fmt::Display for Layer {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            f.write_str(self.0)
+        }
+    }
+
+    impl std::error::Error for Layer {
+        fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+            self.1.as_deref().map(|e| e as _)
+        }
+    }
+
+    #[test]
+    fn test_finds_certificate_problem_deep_in_chain() {
+        let err = Layer(
+            "error sending request",
+            Some(Box::new(Layer(
+                "client error (Connect)",
+                Some(Box::new(Layer(
+                    "invalid peer certificate: UnknownIssuer",
+                    None,
+                ))),
+            ))),
+        );
+        assert_eq!(
+            certificate_problem(&err).as_deref(),
+            Some("invalid peer certificate: UnknownIssuer")
+        );
+        assert!(certificate_problem(&Layer("connection refused", None)).is_none());
+    }
+}
@@ -0,0 +1,97 @@
+//! Prior versions of files, on backends that keep them.
+
+use super::listing::parse_list_files;
+use super::{RcloneClient, fs_path, join_path, split_parent};
+use crate::error::Result;
+use crate::rclone::commands;
+use crate::rclone::types::{FileTransferRequest, Version};
+use crate::rclone::validate::{validate_path, validate_remote_name};
+use tracing::{debug, info, trace, warn};
+
+impl RcloneClient {
+    /// Lists the prior versions of the file at `path`, newest first,
+    /// on backends that keep versions, such as S3 and B2. rclone lists
+    /// them next to the file when the remote is opened with its
+    /// `versions` option.
+    ///
+    /// # Errors
+    /// Returns error if rclone daemon is unreachable or responds
+    /// with an error, e.g. because the backend has no `versions`
+    /// option.
+    pub async fn list_versions(&self, remote: &str, path: &str) -> Result<Vec<Version>> {
+        validate_remote_name(remote)?;
+        validate_path(path)?;
+        let (_, remote_path) = fs_path(remote, path);
+        let (dir, name) = split_parent(&remote_path);
+        debug!(remote, path = %remote_path, "listing versions");
+
+        let body = self
+            .post_json(
+                commands::LIST_FILES,
+                &serde_json::json!({ "fs": versions_fs(remote), "remote": dir }),
+            )
+            .await?;
+        trace!(body = %body, "list versions response");
+        let mut versions: Vec<Version> = parse_list_files(&body)
+            .inspect_err(|e| warn!(error = %e, "malformed list versions response"))?
+            .iter()
+            .filter_map(|item| Version::of(name, item))
+            .collect();
+        versions.sort_by(|a, b| b.name.cmp(&a.name));
+        info!(count = versions.len(), "loaded versions");
+        Ok(versions)
+    }
+
+    /// Downloads the version `version` of the file at `path`. Needs
+    /// `--rc-serve`, like [`Self::read_file`].
+    ///
+    /// # Errors
+    /// Returns error if rclone daemon is unreachable, does not serve
+    /// objects, or the version does not exist.
+    pub async fn download_version(
+        &self,
+        remote: &str,
+        path: &str,
+        version: &str,
+    ) -> Result<Vec<u8>> {
+        validate_remote_name(remote)?;
+        validate_path(path)?;
+        validate_path(version)?;
+        let (_, remote_path) = fs_path(remote, path);
+        let (dir, _) = split_parent(&remote_path);
+        let url = self.object_url(&versions_fs(remote), &join_path(dir, version))?;
+        debug!(remote, path = %remote_path, version, "downloading version");
+        self.fetch_object(url, None).await
+    }
+
+    /// Copies the version `version` of the file at `path` over the
+    /// file, server-side. The backend keeps what it replaces as another
+    /// version.
+    ///
+    /// # Errors
+    /// Returns error if rclone daemon is unreachable or responds
+    /// with an error.
+    pub async fn restore_version(&self, remote: &str, path: &str, version: &str) -> Result<()> {
+        validate_remote_name(remote)?;
+        validate_path(path)?;
+        validate_path(version)?;
+        let (fs, remote_path) = fs_path(remote, path);
+        let (dir, _) = split_parent(&remote_path);
+        debug!(remote, path = %remote_path, version, "restoring version");
+        let request = FileTransferRequest {
+            src_fs: versions_fs(remote),
+            src_remote: join_path(dir, version),
+            dst_fs: fs,
+            dst_remote: remote_path.clone(),
+        };
+        self.post_command(commands::COPY_FILE, &request).await?;
+        info!(remote, path = %remote_path, version, "version restored");
+        Ok(())
+    }
+}
+
+/// Returns the fs string opening `remote` with its backend's `versions`
+/// option, which lists prior versions of files next to them.
+fn versions_fs(remote: &str) -> String {
+    format!("{},versions:", remote)
+}
@@ -4,6 +4,8 @@
 pub const LIST_REMOTES: &str = "config/listremotes";
 /// List files in a path.
 pub const LIST_FILES: &str = "operations/list";
+/// Look up a single file or directory.
+pub const STAT: &str = "operations/stat";
 /// Create a directory.
 pub const MKDIR: &str = "operations/mkdir";
 /// Delete a file.
@@ -12,11 +14,55 @@ pub const DELETE_FILE: &str = "operations/deletefile";
 pub const PURGE: &str = "operations/purge";
 /// Copy a file.
 pub const COPY_FILE: &str = "operations/copyfile";
+/// Download a URL into a file on a remote.
+pub const COPY_URL: &str = "operations/copyurl";
 /// Move a file.
 pub const MOVE_FILE: &str = "operations/movefile";
+/// Copy a directory and its contents.
+pub const COPY_DIR: &str = "sync/copy";
+/// Move a directory and its contents.
+pub const MOVE_DIR: &str = "sync/move";
 /// Create a remote configuration.
 pub const CONFIG_CREATE: &str = "config/create";
 /// Update a remote configuration.
 pub const CONFIG_UPDATE: &str = "config/update";
+/// Fetch a remote's configuration.
+pub const CONFIG_GET: &str = "config/get";
+/// Supply the password of an encrypted config file.
+pub const CONFIG_UNLOCK: &str = "config/unlock";
+/// Fetch the configuration of every remote.
+pub const CONFIG_DUMP: &str = "config/dump";
+/// List the backends remotes can be created for.
+pub const LIST_PROVIDERS: &str = "config/providers";
 /// Delete a remote configuration.
 pub const CONFIG_DELETE: &str = "config/delete";
+/// Ask the daemon to exit.
+pub const QUIT: &str = "core/quit";
+/// Fetch the daemon's version.
+pub const CORE_VERSION: &str = "core/version";
+/// Fetch how much space a remote has and uses.
+pub const ABOUT: &str = "operations/about";
+/// Change the storage tier of every file in a directory.
+pub const SET_TIER: &str = "operations/settier";
+/// Change the storage tier of one file.
+pub const SET_TIER_FILE: &str = "operations/settierfile";
+/// Fetch what a remote's backend supports.
+pub const FSINFO: &str = "operations/fsinfo";
+/// Mount a remote on a local directory.
+pub const MOUNT: &str = "mount/mount";
+/// List active mounts.
+pub const LIST_MOUNTS: &str = "mount/listmounts";
+/// Unmount a mount point.
+pub const UNMOUNT: &str = "mount/unmount";
+/// List the ids of running and recently finished jobs.
+pub const JOB_LIST: &str = "job/list";
+/// Fetch the status of a job.
+pub const JOB_STATUS: &str = "job/status";
+/// Stop a running job.
+pub const JOB_STOP: &str = "job/stop";
+/// Fetch transfer stats, including the files being transferred.
+pub const CORE_STATS: &str = "core/stats";
+/// Upload files with a multipart form.
+pub const UPLOAD_FILE: &str = "operations/uploadfile";
+/// Object download route, only served with `rclone rcd --rc-serve`.
+pub const SERVE_OBJECT: &str = "[remote:]/path";
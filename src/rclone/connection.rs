@@ -0,0 +1,137 @@
+//! Where and how to reach an rclone daemon.
+
+use crate::config::{RCLONE_HOST, RCLONE_PORT};
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+/// Prefix of the socket form of `rclone rcd --rc-addr`.
+const UNIX_SCHEME: &str = "unix://";
+
+/// How to log in to a daemon. Secrets are never printed.
+#[derive(Clone, PartialEq, Eq)]
+pub enum Credentials {
+    /// Basic auth, for a daemon started with `--rc-user` and
+    /// `--rc-pass`.
+    Basic { user: String, password: String },
+    /// Bearer token, for a daemon behind an authenticating proxy.
+    Bearer { token: String },
+}
+
+impl fmt::Debug for Credentials {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Basic { user, .. } => f
+                .debug_struct("Basic")
+                .field("user", user)
+                .field("password", &"<redacted>")
+                .finish(),
+            Self::Bearer { .. } => f
+                .debug_struct("Bearer")
+                .field("token", &"<redacted>")
+                .finish(),
+        }
+    }
+}
+
+/// Address and login of an rclone daemon.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Connection {
+    /// Host name or IPv4 address.
+    pub host: String,
+    /// RC port.
+    pub port: u16,
+    /// Whether to use HTTPS, for daemons started with `--rc-cert`.
+    pub tls: bool,
+    /// PEM file of extra CAs to trust, for self-signed daemon
+    /// certificates. The system trust store is still used.
+    pub ca_cert: Option<PathBuf>,
+    /// Whether to skip certificate verification entirely.
+    pub insecure: bool,
+    /// PEM client certificate, for daemons that require mutual TLS.
+    /// May hold the private key too.
+    pub client_cert: Option<PathBuf>,
+    /// PEM private key of `client_cert`, when kept in its own file.
+    pub client_key: Option<PathBuf>,
+    /// Login, if the daemon requires one.
+    pub credentials: Option<Credentials>,
+    /// Unix socket to connect through instead of `host:port`, which
+    /// then only appear in request URLs.
+    pub socket: Option<PathBuf>,
+}
+
+impl Default for Connection {
+    fn default() -> Self {
+        Self {
+            host: RCLONE_HOST.to_string(),
+            port: RCLONE_PORT,
+            tls: false,
+            ca_cert: None,
+            insecure: false,
+            client_cert: None,
+            client_key: None,
+            credentials: None,
+            socket: None,
+        }
+    }
+}
+
+impl Connection {
+    /// Returns the URL every RC endpoint is relative to.
+    pub fn base_url(&self) -> String {
+        let scheme = if self.tls { "https" } else { "http" };
+        format!("{}://{}:{}", scheme, self.host, self.port)
+    }
+
+    /// Returns where the daemon is reached, for messages.
+    pub fn address(&self) -> String {
+        match self.socket {
+            Some(ref socket) => socket.display().to_string(),
+            None => format!("{}:{}", self.host, self.port),
+        }
+    }
+}
+
+/// Returns the socket path of a `--socket` value, which may be given
+/// the way rclone takes it (`unix:///run/rclone.sock`) or as a plain
+/// path.
+pub fn socket_path(value: &str) -> PathBuf {
+    Path::new(value.strip_prefix(UNIX_SCHEME).unwrap_or(value)).to_path_buf()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_base_url_follows_tls() {
+        let mut connection = Connection::default();
+        assert_eq!(connection.base_url(), "http://localhost:5572");
+        connection.tls = true;
+        assert_eq!(connection.base_url(), "https://localhost:5572");
+    }
+
+    #[test]
+    fn test_socket_path_accepts_rclone_form() {
+        assert_eq!(
+            socket_path("unix:///run/rclone.sock"),
+            Path::new("/run/rclone.sock")
+        );
+        assert_eq!(socket_path("rclone.sock"), Path::new("rclone.sock"));
+    }
+
+    #[test]
+    fn test_debug_hides_password() {
+        let credentials = Credentials::Basic {
+            user: "admin".to_string(),
+            password: "hunter2".to_string(),
+        };
+        let printed = format!("{:?}", credentials);
+        assert!(printed.contains("admin"));
+        assert!(!printed.contains("hunter2"));
+
+        let token = Credentials::Bearer {
+            token: "hunter2".to_string(),
+        };
+        assert!(!format!("{:?}", token).contains("hunter2"));
+    }
+}
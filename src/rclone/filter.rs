@@ -0,0 +1,219 @@
+//! Size and age filters, applied by the daemon through the `_filter`
+//! parameter every rc call accepts, and by the rclone command line
+//! through the matching flags.
+//!
+//! Size and age limits only apply to files: directories are listed
+//! whatever their content.
+
+use crate::error::{LazyFileError, Result};
+use serde_json::{Map, Value};
+
+/// Limits on the size and modification time of listed files. Values
+/// use rclone's own syntax, so they pass through unchanged.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Filter {
+    /// Smallest size shown, e.g. `10M`.
+    pub min_size: Option<String>,
+    /// Largest size shown, e.g. `1G`.
+    pub max_size: Option<String>,
+    /// Only files modified after this, as an age (`7d`) or a date
+    /// (`2024-01-31`). rclone calls it `--max-age`.
+    pub modified_after: Option<String>,
+    /// Only files modified before this, as an age or a date. rclone
+    /// calls it `--min-age`.
+    pub modified_before: Option<String>,
+}
+
+impl Filter {
+    /// Builds a filter from the four limits, treating blank ones as
+    /// unset.
+    ///
+    /// # Errors
+    /// Returns `InvalidInput` if a size is not a number with an
+    /// optional `B`, `K`, `M`, `G`, `T` or `P` suffix, or an age is
+    /// neither a duration such as `1h30m` nor a `YYYY-MM-DD` date.
+    pub fn new(
+        min_size: &str,
+        max_size: &str,
+        modified_after: &str,
+        modified_before: &str,
+    ) -> Result<Self> {
+        Ok(Self {
+            min_size: checked(min_size, "minimum size", is_size)?,
+            max_size: checked(max_size, "maximum size", is_size)?,
+            modified_after: checked(modified_after, "modified after", is_age)?,
+            modified_before: checked(modified_before, "modified before", is_age)?,
+        })
+    }
+
+    /// Returns whether no limit is set, so every file passes.
+    pub fn is_empty(&self) -> bool {
+        self.limits().next().is_none()
+    }
+
+    /// Returns the `_filter` object for an rc call.
+    pub fn to_rc(&self) -> Value {
+        Value::Object(
+            self.limits()
+                .map(|(limit, value)| (limit.rc_key.to_string(), Value::from(value)))
+                .collect::<Map<_, _>>(),
+        )
+    }
+
+    /// Returns the command line flags, e.g. `--min-size 10M`.
+    pub fn flags(&self) -> Vec<String> {
+        self.limits()
+            .flat_map(|(limit, value)| [limit.flag.to_string(), value.to_string()])
+            .collect()
+    }
+
+    /// Returns a short description such as `>= 10M, after 7d`.
+    pub fn describe(&self) -> String {
+        self.limits()
+            .map(|(limit, value)| format!("{} {}", limit.label, value))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    fn limits(&self) -> impl Iterator<Item = (&'static Limit, &str)> {
+        [
+            &self.min_size,
+            &self.max_size,
+            &self.modified_after,
+            &self.modified_before,
+        ]
+        .into_iter()
+        .zip(LIMITS.iter())
+        .filter_map(|(value, limit)| value.as_deref().map(|value| (limit, value)))
+    }
+}
+
+/// How one limit is spelled in an rc call, on the command line and in
+/// [`Filter::describe`].
+struct Limit {
+    rc_key: &'static str,
+    flag: &'static str,
+    label: &'static str,
+}
+
+/// The limits in the order of [`Filter`]'s fields.
+const LIMITS: [Limit; 4] = [
+    Limit {
+        rc_key: "MinSize",
+        flag: "--min-size",
+        label: ">=",
+    },
+    Limit {
+        rc_key: "MaxSize",
+        flag: "--max-size",
+        label: "<=",
+    },
+    Limit {
+        rc_key: "MaxAge",
+        flag: "--max-age",
+        label: "after",
+    },
+    Limit {
+        rc_key: "MinAge",
+        flag: "--min-age",
+        label: "before",
+    },
+];
+
+fn checked(value: &str, field: &'static str, valid: fn(&str) -> bool) -> Result<Option<String>> {
+    let value = value.trim();
+    if value.is_empty() {
+        Ok(None)
+    } else if valid(value) {
+        Ok(Some(value.to_string()))
+    } else {
+        let reason = if field.ends_with("size") {
+            "expected a size such as 500K or 1.5G"
+        } else {
+            "expected an age such as 7d or a date such as 2024-01-31"
+        };
+        Err(LazyFileError::InvalidInput { field, reason })
+    }
+}
+
+/// Returns whether `s` is a number with an optional size suffix.
+/// Without one, rclone reads the number as KiB.
+fn is_size(s: &str) -> bool {
+    let number = s
+        .strip_suffix(|c: char| "bkmgtpBKMGTP".contains(c))
+        .unwrap_or(s);
+    is_number(number)
+}
+
+/// Returns whether `s` is a duration such as `1h30m` or a date such
+/// as `2024-01-31`, optionally followed by a time.
+fn is_age(s: &str) -> bool {
+    is_date(s) || is_duration(s)
+}
+
+fn is_number(s: &str) -> bool {
+    let mut parts = s.splitn(2, '.');
+    let whole = parts.next().unwrap_or("");
+    let digits = |part: &str| !part.is_empty() && part.chars().all(|c| c.is_ascii_digit());
+    digits(whole) && parts.next().is_none_or(digits)
+}
+
+fn is_duration(s: &str) -> bool {
+    let mut rest = s;
+    while !rest.is_empty() {
+        let unit_start = rest
+            .find(|c: char| !c.is_ascii_digit() && c != '.')
+            .unwrap_or(rest.len());
+        if !is_number(&rest[..unit_start]) {
+            return false;
+        }
+        rest = &rest[unit_start..];
+        let Some(unit) = ["ms", "s", "m", "h", "d", "w", "M", "y"]
+            .into_iter()
+            .find(|unit| rest.starts_with(unit))
+        else {
+            return false;
+        };
+        rest = &rest[unit.len()..];
+    }
+    !s.is_empty()
+}
+
+fn is_date(s: &str) -> bool {
+    let (date, time) = s.split_once([' ', 'T']).unwrap_or((s, ""));
+    let shape = |part: &str, sep: char, lens: &[usize]| {
+        let fields: Vec<&str> = part.split(sep).collect();
+        fields.len() == lens.len()
+            && fields
+                .iter()
+                .zip(lens)
+                .all(|(f, &len)| f.len() == len && f.chars().all(|c| c.is_ascii_digit()))
+    };
+    shape(date, '-', &[4, 2, 2]) && (time.is_empty() || shape(time, ':', &[2, 2, 2]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validates_limits() {
+        assert!(Filter::new("10M", "1.5g", "7d", "2024-01-31").is_ok());
+        assert!(Filter::new("", "", "1h30m", "2024-01-31 12:00:00").is_ok());
+        assert!(Filter::new("ten", "", "", "").is_err());
+        assert!(Filter::new("", "", "7 days", "").is_err());
+        assert!(Filter::new("", "", "", "2024-1-31").is_err());
+        assert!(Filter::new(" ", "", "", "").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_maps_onto_rclone() {
+        let filter = Filter::new("10M", "", "7d", "").unwrap();
+        assert_eq!(
+            filter.to_rc(),
+            serde_json::json!({ "MinSize": "10M", "MaxAge": "7d" })
+        );
+        assert_eq!(filter.flags(), ["--min-size", "10M", "--max-age", "7d"]);
+        assert_eq!(filter.describe(), ">= 10M, after 7d");
+    }
+}
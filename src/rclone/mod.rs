@@ -1,9 +1,23 @@
 //! Rclone JSON-RPC API client and types.
 
+pub mod backup;
+pub mod calls;
+pub mod cancel;
 pub mod client;
 pub mod commands;
+pub mod connection;
+pub mod filter;
+pub mod retry;
 pub mod types;
 pub(crate) mod validate;
 
+pub use calls::{CALL_LOG_LIMIT, Call};
+pub use cancel::CancelToken;
 pub use client::RcloneClient;
-pub use types::FileItem;
+pub use connection::{Connection, Credentials, socket_path};
+pub use filter::Filter;
+pub use retry::RequestPolicy;
+pub use types::{
+    ByteRange, ConfigExample, ConfigOption, ConfigQuestion, FileItem, FsInfo, HIDE_CONFIGURATOR,
+    Job, JobState, Mount, ObjectInfo, Provider, Quota, Transfer, TreeEntry, Version,
+};
@@ -0,0 +1,132 @@
+//! How long requests to the daemon may take, and retrying the ones
+//! that failed before reaching it.
+
+use reqwest::StatusCode;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::time::Duration;
+
+/// Longest wait between two attempts, however many retries are
+/// configured.
+const MAX_DELAY: Duration = Duration::from_secs(10);
+
+/// Statuses a proxy in front of the daemon answers with when the
+/// daemon never saw the request, or asks to come back later. A `502`
+/// or `504` is not among them: the proxy may have passed the request
+/// on before the daemon failed to answer in time.
+const TRANSIENT_STATUS: [StatusCode; 2] = [
+    StatusCode::TOO_MANY_REQUESTS,
+    StatusCode::SERVICE_UNAVAILABLE,
+];
+
+/// Timeouts and retries of a client's requests. By default nothing is
+/// retried; the TUI retries as its config file says.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RequestPolicy {
+    /// How long an ordinary call may take.
+    pub timeout: Duration,
+    /// How long a recursive listing, or one with hashes, may take. It
+    /// walks every directory below the listed one, or reads every
+    /// file, which on a large remote takes minutes.
+    pub tree_timeout: Duration,
+    /// How long a config step may take. Answering an OAuth question
+    /// makes the daemon wait until the user signs in through the
+    /// browser.
+    pub config_timeout: Duration,
+    /// How often a request that failed before reaching the daemon is
+    /// tried again.
+    pub retries: u32,
+    /// Wait before the first retry; doubled for every further one.
+    pub backoff: Duration,
+}
+
+impl Default for RequestPolicy {
+    fn default() -> Self {
+        Self {
+            timeout: Duration::from_secs(30),
+            tree_timeout: Duration::from_secs(600),
+            config_timeout: Duration::from_secs(600),
+            retries: 0,
+            backoff: Duration::from_millis(500),
+        }
+    }
+}
+
+impl RequestPolicy {
+    /// Returns the wait before retry number `retry`, counting from 1.
+    pub fn delay(&self, retry: u32) -> Duration {
+        let doublings = retry.saturating_sub(1).min(16);
+        (self.backoff * 2u32.pow(doublings)).min(MAX_DELAY)
+    }
+}
+
+/// Returns whether a failed send is worth retrying. Only failures to
+/// connect are: a request that timed out may still have run, and
+/// running a move or delete twice is worse than reporting it.
+pub(super) fn is_transient(error: &reqwest::Error) -> bool {
+    error.is_connect()
+}
+
+/// Returns whether a response says the daemon never handled the
+/// request.
+pub(super) fn is_transient_status(status: StatusCode) -> bool {
+    TRANSIENT_STATUS.contains(&status)
+}
+
+/// The attempt a client's latest retried request is on, shared by
+/// every clone of the client; 0 while none is being retried.
+#[derive(Debug, Clone, Default)]
+pub(super) struct Retrying(Arc<AtomicU32>);
+
+impl Retrying {
+    /// Returns the retry in progress, if any.
+    pub(super) fn current(&self) -> Option<u32> {
+        Some(self.0.load(Ordering::SeqCst)).filter(|&retry| retry > 0)
+    }
+
+    /// Records that retry number `retry` started. The returned guard
+    /// clears it again once the request is done, however it ends.
+    pub(super) fn start(&self, retry: u32) -> RetryGuard {
+        self.0.store(retry, Ordering::SeqCst);
+        RetryGuard {
+            retrying: Arc::clone(&self.0),
+            retry,
+        }
+    }
+}
+
+/// Clears a retry from [`Retrying`] when dropped, unless a later one
+/// took its place.
+pub(super) struct RetryGuard {
+    retrying: Arc<AtomicU32>,
+    retry: u32,
+}
+
+impl Drop for RetryGuard {
+    fn drop(&mut self) {
+        let _ = self
+            .retrying
+            .compare_exchange(self.retry, 0, Ordering::SeqCst, Ordering::SeqCst);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_doubles_delay_up_to_the_cap() {
+        let policy = RequestPolicy::default();
+        assert_eq!(policy.delay(1), Duration::from_millis(500));
+        assert_eq!(policy.delay(3), Duration::from_secs(2));
+        assert_eq!(policy.delay(40), MAX_DELAY);
+
+        let retrying = Retrying::default();
+        let first = retrying.start(1);
+        let second = retrying.start(2);
+        drop(first);
+        assert_eq!(retrying.current(), Some(2));
+        drop(second);
+        assert_eq!(retrying.current(), None);
+    }
+}
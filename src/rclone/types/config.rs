@@ -0,0 +1,269 @@
+//! Remotes, backends and the questions rclone asks while configuring
+//! a remote.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Response from rclone `config/listremotes` call
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ListRemotesResponse {
+    /// Configured remote names. None when no remotes exist
+    /// (rclone marshals an empty Go slice as null).
+    pub remotes: Option<Vec<String>>,
+}
+
+/// Response from rclone `config/providers` call.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ListProvidersResponse {
+    /// Registered backends.
+    pub providers: Option<Vec<Provider>>,
+}
+
+/// A backend rclone can create remotes of, such as `drive` or `s3`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Provider {
+    /// Type name passed to `config/create`.
+    #[serde(rename = "Name")]
+    pub name: String,
+    /// One-line description, e.g. "Google Drive".
+    #[serde(rename = "Description", default)]
+    pub description: String,
+    /// Whether rclone keeps the backend out of its own listings.
+    #[serde(rename = "Hide", default)]
+    pub hide: bool,
+    /// Settings a remote of this backend takes.
+    #[serde(rename = "Options", default)]
+    pub options: Option<Vec<ConfigOption>>,
+}
+
+impl Provider {
+    /// Returns the backend's settings, which rclone may send as
+    /// `null`.
+    pub fn options(&self) -> &[ConfigOption] {
+        self.options.as_deref().unwrap_or_default()
+    }
+}
+
+/// Parameters for rclone `config/create` call.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConfigCreateRequest {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub remote_type: String,
+    #[serde(default)]
+    pub parameters: HashMap<String, String>,
+    /// Controls the config state machine; omitted for a plain create.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub opt: Option<ConfigOpt>,
+}
+
+/// Parameters for rclone `config/update` call.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConfigUpdateRequest {
+    pub name: String,
+    #[serde(default)]
+    pub parameters: HashMap<String, String>,
+    /// Controls the config state machine; omitted for a plain update.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub opt: Option<ConfigOpt>,
+}
+
+/// The `opt` field of `config/create` and `config/update`, which
+/// turns the backend's interactive setup into a question/answer loop.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConfigOpt {
+    /// Return the next question instead of prompting on the daemon's
+    /// terminal.
+    pub non_interactive: bool,
+    /// Answers to password questions are plain text to obscure.
+    pub obscure: bool,
+    /// Passwords are already obscured and are stored as given.
+    pub no_obscure: bool,
+    /// Resume the state machine at `state` with `result`.
+    #[serde(rename = "continue")]
+    pub resume: bool,
+    /// State returned with the question being answered.
+    pub state: String,
+    /// Answer to that question.
+    pub result: String,
+}
+
+/// Response from `config/create` and `config/update` in
+/// non-interactive mode.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConfigOut {
+    /// State to send back with the answer; empty once configuration
+    /// is complete.
+    #[serde(rename = "State", default)]
+    pub state: String,
+    /// Question to answer next, if any.
+    #[serde(rename = "Option")]
+    pub option: Option<ConfigOption>,
+    /// Why the previous answer was rejected, if it was.
+    #[serde(rename = "Error", default)]
+    pub error: String,
+}
+
+/// A backend config option, as rclone describes it to prompt for it.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ConfigOption {
+    /// Parameter name, e.g. `config_is_local`.
+    #[serde(rename = "Name")]
+    pub name: String,
+    /// Explanation shown above the prompt; may span several lines.
+    #[serde(rename = "Help", default)]
+    pub help: String,
+    /// Default value as text.
+    #[serde(rename = "DefaultStr", default)]
+    pub default: String,
+    /// Suggested values.
+    #[serde(rename = "Examples", default)]
+    pub examples: Option<Vec<ConfigExample>>,
+    /// Whether an empty answer is rejected.
+    #[serde(rename = "Required", default)]
+    pub required: bool,
+    /// Whether the answer is a secret not to echo.
+    #[serde(rename = "IsPassword", default)]
+    pub is_password: bool,
+    /// Whether the value is a credential, such as a token or client
+    /// secret, that is stored as typed.
+    #[serde(rename = "Sensitive", default)]
+    pub sensitive: bool,
+    /// Whether only one of `examples` is accepted.
+    #[serde(rename = "Exclusive", default)]
+    pub exclusive: bool,
+    /// Whether the option is only shown on request.
+    #[serde(rename = "Advanced", default)]
+    pub advanced: bool,
+    /// Where rclone hides the option, as [`HIDE_CONFIGURATOR`] and
+    /// other bits.
+    #[serde(rename = "Hide", default)]
+    pub hide: u8,
+    /// Sub-providers the option applies to, in rclone's
+    /// `AWS,Minio` or `!AWS` form; empty for all.
+    #[serde(rename = "Provider", default)]
+    pub provider: String,
+}
+
+/// [`ConfigOption::hide`] bit for options the configurator skips.
+pub const HIDE_CONFIGURATOR: u8 = 4;
+
+impl ConfigOption {
+    /// Returns the suggested values, which rclone may send as `null`.
+    pub fn examples(&self) -> &[ConfigExample] {
+        self.examples.as_deref().unwrap_or_default()
+    }
+
+    /// Returns whether the option applies to `sub_provider`, the value
+    /// of a backend's own `provider` option such as S3's `AWS`. Like
+    /// rclone, everything applies while it is unset.
+    pub fn applies_to(&self, sub_provider: &str) -> bool {
+        matches_provider(&self.provider, sub_provider)
+    }
+
+    /// Returns whether the value must not be shown.
+    pub fn is_secret(&self) -> bool {
+        self.is_password || self.sensitive
+    }
+}
+
+/// Matches an option's or example's `Provider` field against the
+/// chosen sub-provider, the way rclone's configurator does.
+pub fn matches_provider(list: &str, sub_provider: &str) -> bool {
+    if list.is_empty() || sub_provider.is_empty() {
+        return true;
+    }
+    let (list, negate) = match list.strip_prefix('!') {
+        Some(rest) => (rest, true),
+        None => (list, false),
+    };
+    list.split(',').any(|p| p == sub_provider) != negate
+}
+
+/// A suggested value for a [`ConfigOption`].
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ConfigExample {
+    /// Value to answer with.
+    #[serde(rename = "Value")]
+    pub value: String,
+    /// What the value means.
+    #[serde(rename = "Help", default)]
+    pub help: String,
+    /// Sub-providers the value applies to; empty for all.
+    #[serde(rename = "Provider", default)]
+    pub provider: String,
+}
+
+/// A question the daemon asks while configuring a remote.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConfigQuestion {
+    /// State to send back with the answer.
+    pub state: String,
+    /// What is being asked.
+    pub option: ConfigOption,
+    /// Why the previous answer was rejected, if it was.
+    pub error: Option<String>,
+}
+
+/// Parameters for rclone `config/delete` call.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConfigDeleteRequest {
+    pub name: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_config_create_request() {
+        let mut params = HashMap::new();
+        params.insert("path".to_string(), "/bucket".to_string());
+
+        let req = ConfigCreateRequest {
+            name: "myremote".to_string(),
+            remote_type: "s3".to_string(),
+            parameters: params,
+            opt: None,
+        };
+
+        assert_eq!(req.name, "myremote");
+        assert_eq!(req.remote_type, "s3");
+        assert_eq!(req.parameters.get("path").unwrap(), "/bucket");
+    }
+
+    #[test]
+    fn test_provider_matching_follows_rclone() {
+        assert!(matches_provider("", "AWS"));
+        assert!(matches_provider("AWS,Minio", ""));
+        assert!(matches_provider("AWS,Minio", "Minio"));
+        assert!(!matches_provider("AWS,Minio", "Ceph"));
+        assert!(!matches_provider("!AWS,Minio", "AWS"));
+        assert!(matches_provider("!AWS,Minio", "Ceph"));
+    }
+
+    #[test]
+    fn test_config_delete_request() {
+        let req = ConfigDeleteRequest {
+            name: "myremote".to_string(),
+        };
+
+        assert_eq!(req.name, "myremote");
+    }
+
+    #[test]
+    fn test_config_update_request() {
+        let mut params = HashMap::new();
+        params.insert("path".to_string(), "/newpath".to_string());
+
+        let req = ConfigUpdateRequest {
+            name: "myremote".to_string(),
+            parameters: params,
+            opt: None,
+        };
+
+        assert_eq!(req.name, "myremote");
+        assert_eq!(req.parameters.get("path").unwrap(), "/newpath");
+    }
+}
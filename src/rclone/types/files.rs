@@ -0,0 +1,480 @@
+//! Files and directories: listings, metadata, versions, quotas and the
+//! requests that change them.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Parameters for rclone `operations/mkdir` call.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MkdirRequest {
+    pub fs: String,
+    pub remote: String,
+}
+
+/// Parameters for rclone `operations/deletefile` call.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeleteFileRequest {
+    pub fs: String,
+    pub remote: String,
+}
+
+/// Parameters for rclone `operations/purge` call.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PurgeRequest {
+    pub fs: String,
+    pub remote: String,
+}
+
+/// Parameters for rclone `operations/copyfile` and
+/// `operations/movefile` calls, which share the same shape.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FileTransferRequest {
+    #[serde(rename = "srcFs")]
+    pub src_fs: String,
+    #[serde(rename = "srcRemote")]
+    pub src_remote: String,
+    #[serde(rename = "dstFs")]
+    pub dst_fs: String,
+    #[serde(rename = "dstRemote")]
+    pub dst_remote: String,
+}
+
+/// Parameters for rclone `sync/copy` and `sync/move` calls, always
+/// run as an async job.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DirTransferRequest {
+    #[serde(rename = "srcFs")]
+    pub src_fs: String,
+    #[serde(rename = "dstFs")]
+    pub dst_fs: String,
+    #[serde(rename = "createEmptySrcDirs")]
+    pub create_empty_src_dirs: bool,
+    /// Only `sync/move` takes it.
+    #[serde(rename = "deleteEmptySrcDirs", skip_serializing_if = "Option::is_none")]
+    pub delete_empty_src_dirs: Option<bool>,
+    #[serde(rename = "_async")]
+    pub is_async: bool,
+    /// Size and age limits on the files transferred.
+    #[serde(rename = "_filter", skip_serializing_if = "Option::is_none")]
+    pub filter: Option<serde_json::Value>,
+}
+
+/// Space on a remote, from rclone `operations/about`, in bytes. A
+/// backend reports only the figures it knows.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Quota {
+    /// Size of the quota.
+    #[serde(default)]
+    pub total: Option<u64>,
+    /// Space in use.
+    #[serde(default)]
+    pub used: Option<u64>,
+    /// Space left.
+    #[serde(default)]
+    pub free: Option<u64>,
+}
+
+impl Quota {
+    /// Returns the percentage of the quota in use, or `None` if the
+    /// backend does not say enough to tell.
+    pub fn percent_used(&self) -> Option<u8> {
+        let used = self.used?;
+        let total = self
+            .total
+            .or_else(|| self.free.map(|free| free.saturating_add(used)))
+            .filter(|&total| total > 0)?;
+        let percent = (u128::from(used) * 100 / u128::from(total)).min(100);
+        u8::try_from(percent).ok()
+    }
+}
+
+/// Bytes `start` to `end` of a file, both included, as in an HTTP
+/// `Range` header; up to the end of the file without `end`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ByteRange {
+    /// Offset of the first byte.
+    pub start: u64,
+    /// Offset of the last byte, if not the file's last.
+    pub end: Option<u64>,
+}
+
+impl ByteRange {
+    /// Returns the value of the `Range` header asking for the bytes.
+    pub fn header(&self) -> String {
+        match self.end {
+            Some(end) => format!("bytes={}-{}", self.start, end),
+            None => format!("bytes={}-", self.start),
+        }
+    }
+
+    /// Returns how many bytes the range holds, if it ends and the
+    /// count fits in a `u64`.
+    pub fn count(&self) -> Option<u64> {
+        self.end
+            .and_then(|end| end.checked_sub(self.start)?.checked_add(1))
+    }
+}
+
+impl std::str::FromStr for ByteRange {
+    type Err = String;
+
+    /// Parses `start-end` or `start-`, e.g. `0-1023`.
+    fn from_str(value: &str) -> std::result::Result<Self, Self::Err> {
+        let invalid = || format!("expected START-END or START-, got '{}'", value);
+        let (start, end) = value.split_once('-').ok_or_else(invalid)?;
+        let start = start.parse().map_err(|_| invalid())?;
+        let end = match end {
+            "" => None,
+            end => Some(end.parse().map_err(|_| invalid())?),
+        };
+        let range = Self { start, end };
+        match end {
+            Some(end) if end < start => Err(format!("range '{}' ends before it starts", value)),
+            Some(_) if range.count().is_none() => Err(format!("range '{}' is too large", value)),
+            _ => Ok(range),
+        }
+    }
+}
+
+/// A prior version of a file, as rclone lists it on backends that keep
+/// versions: under the file's name with the version's upload time
+/// before the extension, e.g. `report-v2024-03-01-093012-250.pdf`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Version {
+    /// Name the version is listed under.
+    pub name: String,
+    /// Upload time of the version, UTC, e.g. `2024-03-01 09:30:12`.
+    pub time: String,
+    /// Size in bytes.
+    pub size: i64,
+}
+
+impl Version {
+    /// Returns `item` as a version of the file `file_name`, or `None`
+    /// if it is the file itself or another file.
+    pub fn of(file_name: &str, item: &FileItem) -> Option<Self> {
+        let (base, ext) = match file_name.rfind('.') {
+            Some(dot) if dot > 0 => file_name.split_at(dot),
+            _ => (file_name, ""),
+        };
+        let stamp = item
+            .name
+            .strip_prefix(base)?
+            .strip_suffix(ext)?
+            .strip_prefix("-v")?;
+        let digits = |range: std::ops::Range<usize>| {
+            stamp
+                .get(range)
+                .filter(|part| part.bytes().all(|b| b.is_ascii_digit()))
+        };
+        let layout_ok =
+            stamp.len() == 21 && [4, 7, 10, 17].iter().all(|&i| stamp.as_bytes()[i] == b'-');
+        if item.is_dir || !layout_ok {
+            return None;
+        }
+        let time = format!(
+            "{}-{}-{} {}:{}:{}",
+            digits(0..4)?,
+            digits(5..7)?,
+            digits(8..10)?,
+            digits(11..13)?,
+            digits(13..15)?,
+            digits(15..17)?,
+        );
+        digits(18..21)?;
+        Some(Self {
+            name: item.name.clone(),
+            time,
+            size: item.size,
+        })
+    }
+}
+
+/// What a remote's backend supports, from rclone `operations/fsinfo`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FsInfo {
+    /// Hash types the backend computes, e.g. `md5`; None when it
+    /// computes none.
+    #[serde(rename = "Hashes", default)]
+    pub hashes: Option<Vec<String>>,
+    /// Optional features keyed by rclone's name for them, e.g. `Copy`
+    /// for server-side copy.
+    #[serde(rename = "Features", default)]
+    pub features: BTreeMap<String, bool>,
+}
+
+impl FsInfo {
+    /// Returns the backend's hash types.
+    pub fn hashes(&self) -> &[String] {
+        self.hashes.as_deref().unwrap_or_default()
+    }
+
+    /// Returns whether the backend has feature `name`.
+    pub fn supports(&self, name: &str) -> bool {
+        self.features.get(name).copied().unwrap_or(false)
+    }
+}
+
+/// Represents a file or directory from rclone.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct FileItem {
+    /// File or directory name.
+    #[serde(rename = "Name")]
+    pub name: String,
+    /// File size in bytes (0 for directories).
+    #[serde(rename = "Size")]
+    pub size: i64,
+    /// Last modification time.
+    #[serde(rename = "ModTime")]
+    pub mod_time: String,
+    /// True if this is a directory.
+    #[serde(rename = "IsDir")]
+    pub is_dir: bool,
+    /// MIME type, `inode/directory` for directories; empty when the
+    /// daemon did not send one.
+    #[serde(rename = "MimeType", default)]
+    pub mime_type: String,
+    /// Hashes by type, when they were asked for; None otherwise.
+    #[serde(rename = "Hashes", default)]
+    pub hashes: Option<BTreeMap<String, String>>,
+}
+
+/// Response from rclone `operations/list` call.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ListFilesResponse {
+    /// List of files and directories, None if empty.
+    pub list: Option<Vec<FileItem>>,
+}
+
+/// An entry of a recursive listing, placed by its path below the
+/// listed directory.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TreeEntry {
+    /// Path relative to the listed directory, e.g. `photos/a.jpg`.
+    #[serde(rename = "Path")]
+    pub path: String,
+    /// Size in bytes; -1 when the backend cannot tell, e.g. for
+    /// Google Docs.
+    #[serde(rename = "Size")]
+    pub size: i64,
+    /// True if this is a directory.
+    #[serde(rename = "IsDir")]
+    pub is_dir: bool,
+    /// Hashes by type, when they were asked for; None otherwise.
+    #[serde(rename = "Hashes", default)]
+    pub hashes: Option<BTreeMap<String, String>>,
+}
+
+impl TreeEntry {
+    /// Returns the hash of type `kind`, if the backend reported one.
+    /// Backends report an empty hash for files they cannot hash.
+    pub fn hash(&self, kind: &str) -> Option<&str> {
+        self.hashes
+            .as_ref()?
+            .get(kind)
+            .map(String::as_str)
+            .filter(|hash| !hash.is_empty())
+    }
+}
+
+/// Response from rclone `operations/stat` call.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StatResponse {
+    /// The file or directory, None if nothing exists at the path.
+    pub item: Option<FileItem>,
+}
+
+/// A file with the metadata its backend keeps for it, such as
+/// `content-type` or custom headers, from rclone `operations/stat`
+/// with metadata asked for.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ObjectInfo {
+    /// The file itself.
+    #[serde(flatten)]
+    pub item: FileItem,
+    /// Metadata by key; None when the backend keeps none.
+    #[serde(rename = "Metadata", default)]
+    pub metadata: Option<BTreeMap<String, String>>,
+}
+
+/// Response from rclone `operations/stat` with metadata.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StatMetadataResponse {
+    /// The file or directory, None if nothing exists at the path.
+    pub item: Option<ObjectInfo>,
+}
+
+/// Response from a recursive rclone `operations/list` call.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ListTreeResponse {
+    /// Everything below the listed directory, None if it is empty.
+    pub list: Option<Vec<TreeEntry>>,
+}
+
+/// A file of a recursive listing with its details, placed by its path
+/// below the listed directory.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NestedItem {
+    /// Path relative to the listed directory, e.g. `photos/a.jpg`.
+    #[serde(rename = "Path")]
+    pub path: String,
+    /// The file itself.
+    #[serde(flatten)]
+    pub item: FileItem,
+}
+
+/// Response from a recursive rclone `operations/list` call asking
+/// for the files' details.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ListNestedResponse {
+    /// Every file below the listed directory, None if there are none.
+    pub list: Option<Vec<NestedItem>>,
+}
+
+impl FileItem {
+    /// Returns the display name of the item.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns whether this item is a directory.
+    pub fn is_dir(&self) -> bool {
+        self.is_dir
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_file_item_properties() {
+        let item = FileItem {
+            name: "test.txt".to_string(),
+            size: 1024,
+            mod_time: "2024-01-01T00:00:00Z".to_string(),
+            is_dir: false,
+            ..Default::default()
+        };
+
+        assert_eq!(item.name, "test.txt");
+        assert_eq!(item.size, 1024);
+        assert!(!item.is_dir);
+    }
+
+    #[test]
+    fn test_byte_range_parses_open_and_closed_ranges() {
+        let range: ByteRange = "100-199".parse().unwrap();
+        assert_eq!(
+            (range.header().as_str(), range.count()),
+            ("bytes=100-199", Some(100))
+        );
+        let range: ByteRange = "512-".parse().unwrap();
+        assert_eq!(
+            (range.header().as_str(), range.count()),
+            ("bytes=512-", None)
+        );
+        assert!("-512".parse::<ByteRange>().is_err());
+        assert!("9-1".parse::<ByteRange>().is_err());
+        assert!(format!("0-{}", u64::MAX).parse::<ByteRange>().is_err());
+        let range = ByteRange {
+            start: 0,
+            end: Some(u64::MAX),
+        };
+        assert_eq!(range.count(), None);
+        let range: ByteRange = format!("1-{}", u64::MAX).parse().unwrap();
+        assert_eq!(range.count(), Some(u64::MAX));
+    }
+
+    #[test]
+    fn test_version_of_parses_rclone_version_names() {
+        let item = |name: &str| FileItem {
+            name: name.to_string(),
+            size: 7,
+            ..Default::default()
+        };
+        assert_eq!(
+            Version::of("a.tar.gz", &item("a.tar-v2024-03-01-093012-250.gz")),
+            Some(Version {
+                name: "a.tar-v2024-03-01-093012-250.gz".to_string(),
+                time: "2024-03-01 09:30:12".to_string(),
+                size: 7,
+            })
+        );
+        assert!(Version::of("README", &item("README-v2024-03-01-093012-250")).is_some());
+        assert!(Version::of("a.txt", &item("a.txt")).is_none());
+        assert!(Version::of("a.txt", &item("a-v2024-03-01.txt")).is_none());
+        assert!(Version::of("a.txt", &item("ab-v2024-03-01-093012-250.txt")).is_none());
+    }
+
+    #[test]
+    fn test_file_item_directory() {
+        let item = FileItem {
+            name: "folder".to_string(),
+            size: 0,
+            mod_time: "2024-01-01T00:00:00Z".to_string(),
+            is_dir: true,
+            ..Default::default()
+        };
+
+        assert!(item.is_dir);
+        assert_eq!(item.size, 0);
+    }
+
+    #[test]
+    fn test_file_item_name_method() {
+        let item = FileItem {
+            name: "myfile.txt".to_string(),
+            size: 100,
+            mod_time: "2024-01-01T00:00:00Z".to_string(),
+            is_dir: false,
+            ..Default::default()
+        };
+
+        assert_eq!(item.name(), "myfile.txt");
+    }
+
+    #[test]
+    fn test_file_item_is_dir_method() {
+        let dir_item = FileItem {
+            name: "folder".to_string(),
+            size: 0,
+            mod_time: "2024-01-01T00:00:00Z".to_string(),
+            is_dir: true,
+            ..Default::default()
+        };
+
+        let file_item = FileItem {
+            name: "file.txt".to_string(),
+            size: 100,
+            mod_time: "2024-01-01T00:00:00Z".to_string(),
+            is_dir: false,
+            ..Default::default()
+        };
+
+        assert!(dir_item.is_dir());
+        assert!(!file_item.is_dir());
+    }
+
+    #[test]
+    fn test_list_files_response_empty() {
+        let response = ListFilesResponse { list: None };
+        assert!(response.list.is_none());
+    }
+
+    #[test]
+    fn test_list_files_response_with_files() {
+        let items = vec![FileItem {
+            name: "file1.txt".to_string(),
+            size: 100,
+            mod_time: "2024-01-01T00:00:00Z".to_string(),
+            is_dir: false,
+            ..Default::default()
+        }];
+
+        let response = ListFilesResponse { list: Some(items) };
+
+        assert!(response.list.is_some());
+        assert_eq!(response.list.as_ref().unwrap().len(), 1);
+    }
+}
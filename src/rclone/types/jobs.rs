@@ -0,0 +1,154 @@
+//! Background jobs, transfers, mounts and the daemon's version.
+
+use serde::{Deserialize, Serialize};
+
+/// Response from a call started with `_async=true`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JobStarted {
+    #[serde(rename = "jobid")]
+    pub job_id: i64,
+}
+
+/// Parameters for rclone `mount/mount` call.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MountRequest {
+    pub fs: String,
+    #[serde(rename = "mountPoint")]
+    pub mount_point: String,
+}
+
+/// Parameters for rclone `mount/unmount` call.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UnmountRequest {
+    #[serde(rename = "mountPoint")]
+    pub mount_point: String,
+}
+
+/// Response from rclone `core/version` call.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VersionResponse {
+    /// Version string, e.g. `v1.68.2`.
+    pub version: String,
+}
+
+/// Response from rclone `mount/listmounts` call.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ListMountsResponse {
+    /// Active mounts, None when there are none.
+    #[serde(rename = "mountPoints")]
+    pub mount_points: Option<Vec<Mount>>,
+}
+
+/// A remote mounted on a local directory by the daemon.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Mount {
+    /// What is mounted, as `remote:path`.
+    #[serde(rename = "Fs")]
+    pub fs: String,
+    /// Local directory it is mounted on.
+    #[serde(rename = "MountPoint")]
+    pub mount_point: String,
+    /// When it was mounted.
+    #[serde(rename = "MountedOn", default)]
+    pub mounted_on: String,
+}
+
+/// Response from rclone `job/list` call.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ListJobsResponse {
+    /// Ids of running and recently finished jobs, None when there
+    /// are none.
+    #[serde(rename = "jobids")]
+    pub job_ids: Option<Vec<i64>>,
+}
+
+/// Response from rclone `core/stats` call, cut down to the files
+/// being transferred.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StatsResponse {
+    /// Files in flight, None when there are none.
+    pub transferring: Option<Vec<Transfer>>,
+}
+
+/// A file the daemon is transferring, from rclone `core/stats`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Transfer {
+    /// Path of the file being transferred.
+    pub name: String,
+    /// Size of the file in bytes; negative when unknown.
+    #[serde(default)]
+    pub size: i64,
+    /// Bytes transferred so far.
+    #[serde(default)]
+    pub bytes: i64,
+    /// Share transferred, from 0 to 100.
+    #[serde(default)]
+    pub percentage: u8,
+    /// Current speed in bytes per second.
+    #[serde(default)]
+    pub speed: f64,
+    /// Seconds left, None when rclone cannot tell yet.
+    #[serde(default)]
+    pub eta: Option<f64>,
+    /// Stats group, e.g. `job/3`, shared with the job moving it.
+    #[serde(default)]
+    pub group: String,
+}
+
+/// Parameters for rclone `job/status` and `job/stop` calls.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JobRequest {
+    #[serde(rename = "jobid")]
+    pub job_id: i64,
+}
+
+/// A background operation the daemon runs, started by any client
+/// with `_async=true`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Job {
+    /// Job id.
+    pub id: i64,
+    /// Stats group, e.g. `job/3`.
+    #[serde(default)]
+    pub group: String,
+    /// When the job started.
+    #[serde(rename = "startTime", default)]
+    pub start_time: String,
+    /// Whether the job has finished.
+    #[serde(default)]
+    pub finished: bool,
+    /// Whether the job finished without error.
+    #[serde(default)]
+    pub success: bool,
+    /// Why the job failed; empty if it did not.
+    #[serde(default)]
+    pub error: String,
+    /// Seconds the job has run for.
+    #[serde(default)]
+    pub duration: f64,
+    /// What the job returned once finished.
+    #[serde(default)]
+    pub output: Option<serde_json::Value>,
+}
+
+/// Where a [`Job`] stands.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JobState {
+    /// Still running.
+    Running,
+    /// Finished without error.
+    Succeeded,
+    /// Finished with an error.
+    Failed,
+}
+
+impl Job {
+    /// Returns where the job stands.
+    pub fn state(&self) -> JobState {
+        match (self.finished, self.success) {
+            (false, _) => JobState::Running,
+            (true, true) => JobState::Succeeded,
+            (true, false) => JobState::Failed,
+        }
+    }
+}
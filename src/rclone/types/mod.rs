@@ -0,0 +1,20 @@
+//! Data types for rclone API responses.
+
+mod config;
+mod files;
+mod jobs;
+
+pub use config::{
+    ConfigCreateRequest, ConfigDeleteRequest, ConfigExample, ConfigOpt, ConfigOption, ConfigOut,
+    ConfigQuestion, ConfigUpdateRequest, HIDE_CONFIGURATOR, ListProvidersResponse,
+    ListRemotesResponse, Provider, matches_provider,
+};
+pub use files::{
+    ByteRange, DeleteFileRequest, DirTransferRequest, FileItem, FileTransferRequest, FsInfo,
+    ListFilesResponse, ListNestedResponse, ListTreeResponse, MkdirRequest, NestedItem, ObjectInfo,
+    PurgeRequest, Quota, StatMetadataResponse, StatResponse, TreeEntry, Version,
+};
+pub use jobs::{
+    Job, JobRequest, JobStarted, JobState, ListJobsResponse, ListMountsResponse, Mount,
+    MountRequest, StatsResponse, Transfer, UnmountRequest, VersionResponse,
+};
@@ -37,8 +37,9 @@ pub(crate) fn validate_host(host: &str) -> Result<()> {
 
 /// Validates a remote name before it is used in an fs string.
 ///
-/// Accepts alphanumerics, `-`, `_`, `.` and spaces. Rejects `:` and
-/// `/` (fs-string and path separators) and control characters.
+/// Accepts alphanumerics, `-`, `_`, `.`, `+`, `@` and spaces, as
+/// rclone does. Rejects `:` and `/` (fs-string and path separators)
+/// and control characters.
 ///
 /// # Errors
 /// Returns `InvalidInput` if the name is empty or contains rejected
@@ -49,16 +50,33 @@ pub(crate) fn validate_remote_name(name: &str) -> Result<()> {
     }
     if !name
         .chars()
-        .all(|c| c.is_alphanumeric() || matches!(c, '-' | '_' | '.' | ' '))
+        .all(|c| c.is_alphanumeric() || matches!(c, '-' | '_' | '.' | '+' | '@' | ' '))
     {
         return Err(invalid(
             "remote name",
-            "only letters, digits, '-', '_', '.' and spaces are allowed",
+            "only letters, digits, spaces and '-', '_', '.', '+', '@' are allowed",
         ));
     }
     Ok(())
 }
 
+/// Validates the name of a remote about to be created. On top of
+/// [`validate_remote_name`], rclone refuses names starting with `-`
+/// or a space, or ending in a space.
+///
+/// # Errors
+/// Returns `InvalidInput` if rclone would refuse the name.
+pub(crate) fn validate_new_remote_name(name: &str) -> Result<()> {
+    validate_remote_name(name)?;
+    if name.starts_with(['-', ' ']) {
+        return Err(invalid("remote name", "must not start with '-' or a space"));
+    }
+    if name.ends_with(' ') {
+        return Err(invalid("remote name", "must not end with a space"));
+    }
+    Ok(())
+}
+
 /// Validates a path before it is sent to the rclone API.
 ///
 /// An empty path is the remote root and is valid. Rejects control
@@ -76,6 +94,21 @@ pub(crate) fn validate_path(path: &str) -> Result<()> {
     Ok(())
 }
 
+/// Validates a local mount point before it is sent to the daemon.
+///
+/// # Errors
+/// Returns `InvalidInput` if the mount point is empty or contains
+/// control characters.
+pub(crate) fn validate_mount_point(mount_point: &str) -> Result<()> {
+    if mount_point.is_empty() {
+        return Err(invalid("mount point", "must not be empty"));
+    }
+    if mount_point.chars().any(char::is_control) {
+        return Err(invalid("mount point", "control characters are not allowed"));
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -104,6 +137,16 @@ mod tests {
         assert!(validate_remote_name("gdrive").is_ok());
         assert!(validate_remote_name("my remote.backup").is_ok());
         assert!(validate_remote_name("s3_bucket-2").is_ok());
+        assert!(validate_remote_name("me@b2+crypt").is_ok());
+    }
+
+    #[test]
+    fn test_new_remote_name_follows_rclone_rules() {
+        assert!(validate_new_remote_name("my remote").is_ok());
+        assert!(validate_new_remote_name("-remote").is_err());
+        assert!(validate_new_remote_name(" remote").is_err());
+        assert!(validate_new_remote_name("remote ").is_err());
+        assert!(validate_new_remote_name("s3:").is_err());
     }
 
     #[test]
@@ -111,6 +154,14 @@ mod tests {
         assert!(validate_remote_name("").is_err());
     }
 
+    #[test]
+    fn test_mount_point_must_be_nonempty_text() {
+        assert!(validate_mount_point("/mnt/gdrive").is_ok());
+        assert!(validate_mount_point("X:").is_ok());
+        assert!(validate_mount_point("").is_err());
+        assert!(validate_mount_point("/mnt/a\nb").is_err());
+    }
+
     #[test]
     fn rejects_remote_name_with_separator() {
         assert!(validate_remote_name("a:b").is_err());
@@ -0,0 +1,234 @@
+//! Colors of file names by kind of file: the theme's `directory`,
+//! `archive`, `media` and `code` colors, or the `LS_COLORS` variable
+//! `ls` reads, picked with the `[theme]` table's `file_colors` entry.
+
+use super::Theme;
+use super::widgets::FileKind;
+use crate::error::{LazyFileError, Result};
+use crate::rclone::FileItem;
+use ratatui::style::{Color, Modifier, Style};
+use std::collections::{BTreeMap, HashMap};
+
+/// `[theme]` entry picking where file name colors come from.
+pub const FILE_COLORS_KEY: &str = "file_colors";
+
+/// The eight ANSI colors, by SGR offset.
+const ANSI: [Color; 8] = [
+    Color::Black,
+    Color::Red,
+    Color::Green,
+    Color::Yellow,
+    Color::Blue,
+    Color::Magenta,
+    Color::Cyan,
+    Color::Gray,
+];
+
+/// Their bright variants.
+const BRIGHT: [Color; 8] = [
+    Color::DarkGray,
+    Color::LightRed,
+    Color::LightGreen,
+    Color::LightYellow,
+    Color::LightBlue,
+    Color::LightMagenta,
+    Color::LightCyan,
+    Color::White,
+];
+
+/// Where file name colors come from.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub enum FileColors {
+    /// The theme's colors for each kind of file.
+    #[default]
+    Theme,
+    /// The user's `LS_COLORS`.
+    LsColors(LsColors),
+    /// No colors.
+    Off,
+}
+
+impl FileColors {
+    /// Reads the `[theme]` table's `file_colors` entry: `theme` (the
+    /// default), `ls_colors` or `off`. With `ls_colors` and the
+    /// variable unset, the theme's colors are used.
+    ///
+    /// # Errors
+    /// Returns `InvalidTheme` for any other value.
+    pub fn from_config(colors: &BTreeMap<String, String>) -> Result<Self> {
+        match colors.get(FILE_COLORS_KEY).map(String::as_str) {
+            None | Some("theme") => Ok(Self::Theme),
+            Some("off") => Ok(Self::Off),
+            Some("ls_colors") => Ok(std::env::var("LS_COLORS")
+                .ok()
+                .filter(|spec| !spec.is_empty())
+                .map_or(Self::Theme, |spec| Self::LsColors(LsColors::parse(&spec)))),
+            Some(value) => Err(LazyFileError::InvalidTheme {
+                key: FILE_COLORS_KEY.to_string(),
+                value: value.to_string(),
+                reason: "expected theme, ls_colors or off",
+            }),
+        }
+    }
+
+    /// Returns the style of `item`'s name, of kind `kind`.
+    pub fn style(&self, item: &FileItem, kind: FileKind, theme: &Theme) -> Style {
+        match self {
+            Self::Theme => {
+                let color = match kind {
+                    FileKind::Dir => theme.directory,
+                    FileKind::Archive => theme.archive,
+                    FileKind::Image | FileKind::Video | FileKind::Audio => theme.media,
+                    FileKind::Code => theme.code,
+                    FileKind::Document | FileKind::Pdf | FileKind::Other => return Style::new(),
+                };
+                Style::new().fg(color)
+            }
+            Self::LsColors(colors) => colors.style(item),
+            Self::Off => Style::new(),
+        }
+    }
+}
+
+/// Styles read from an `LS_COLORS` value.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct LsColors {
+    /// `di`: directories.
+    directory: Option<Style>,
+    /// `fi`: files no extension entry matches.
+    file: Option<Style>,
+    /// `*.ext` entries, keyed by lowercase extension.
+    extensions: HashMap<String, Style>,
+}
+
+impl LsColors {
+    /// Parses `spec`, e.g. `di=01;34:*.tar=01;31`. Entries for other
+    /// file types, such as links or sockets, are skipped.
+    pub fn parse(spec: &str) -> Self {
+        let mut colors = Self::default();
+        for entry in spec.split(':') {
+            let Some((key, codes)) = entry.split_once('=') else {
+                continue;
+            };
+            let Some(style) = sgr_style(codes) else {
+                continue;
+            };
+            match key {
+                "di" => colors.directory = Some(style),
+                "fi" => colors.file = Some(style),
+                _ => {
+                    if let Some(extension) = key.strip_prefix("*.") {
+                        colors
+                            .extensions
+                            .insert(extension.to_ascii_lowercase(), style);
+                    }
+                }
+            }
+        }
+        colors
+    }
+
+    /// Returns the style of `item`'s name.
+    fn style(&self, item: &FileItem) -> Style {
+        let style = if item.is_dir() {
+            self.directory
+        } else {
+            item.name()
+                .rsplit_once('.')
+                .and_then(|(_, extension)| {
+                    self.extensions
+                        .get(&extension.to_ascii_lowercase())
+                        .copied()
+                })
+                .or(self.file)
+        };
+        style.unwrap_or_default()
+    }
+}
+
+/// Turns SGR codes such as `01;38;5;208` into a style, or None if
+/// they are malformed.
+fn sgr_style(codes: &str) -> Option<Style> {
+    let codes = codes
+        .split(';')
+        .map(|code| code.parse::<u8>().ok())
+        .collect::<Option<Vec<u8>>>()?;
+    let mut style = Style::new();
+    let mut codes = codes.into_iter();
+    while let Some(code) = codes.next() {
+        style = match code {
+            1 => style.add_modifier(Modifier::BOLD),
+            2 => style.add_modifier(Modifier::DIM),
+            3 => style.add_modifier(Modifier::ITALIC),
+            4 => style.add_modifier(Modifier::UNDERLINED),
+            30..=37 => style.fg(ANSI[usize::from(code - 30)]),
+            40..=47 => style.bg(ANSI[usize::from(code - 40)]),
+            90..=97 => style.fg(BRIGHT[usize::from(code - 90)]),
+            100..=107 => style.bg(BRIGHT[usize::from(code - 100)]),
+            38 | 48 => {
+                let color = match codes.next()? {
+                    5 => Color::Indexed(codes.next()?),
+                    2 => Color::Rgb(codes.next()?, codes.next()?, codes.next()?),
+                    _ => return None,
+                };
+                if code == 38 {
+                    style.fg(color)
+                } else {
+                    style.bg(color)
+                }
+            }
+            _ => style,
+        };
+    }
+    Some(style)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(name: &str, is_dir: bool) -> FileItem {
+        FileItem {
+            name: name.to_string(),
+            is_dir,
+            ..FileItem::default()
+        }
+    }
+
+    #[test]
+    fn test_parses_ls_colors() {
+        let colors = LsColors::parse("rs=0:di=01;34:ln=01;36:*.tar=01;31:*.jpg=38;5;208:fi=0");
+        assert_eq!(
+            colors.style(&item("docs", true)),
+            Style::new().fg(Color::Blue).add_modifier(Modifier::BOLD)
+        );
+        assert_eq!(
+            colors.style(&item("a.TAR", false)),
+            Style::new().fg(Color::Red).add_modifier(Modifier::BOLD)
+        );
+        assert_eq!(
+            colors.style(&item("b.jpg", false)),
+            Style::new().fg(Color::Indexed(208))
+        );
+        assert_eq!(colors.style(&item("notes", false)), Style::new());
+        assert_eq!(sgr_style("38;2;1;2"), None);
+    }
+
+    #[test]
+    fn test_theme_colors_by_kind() {
+        let theme = Theme::default();
+        let colors = FileColors::default();
+        let file = item("main.rs", false);
+        assert_eq!(
+            colors.style(&file, FileKind::of(&file), &theme),
+            Style::new().fg(theme.code)
+        );
+        let file = item("notes.txt", false);
+        assert_eq!(
+            colors.style(&file, FileKind::of(&file), &theme),
+            Style::new()
+        );
+        let config = BTreeMap::from([(FILE_COLORS_KEY.to_string(), "rainbow".to_string())]);
+        assert!(FileColors::from_config(&config).is_err());
+    }
+}
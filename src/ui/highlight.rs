@@ -0,0 +1,120 @@
+//! Syntax highlighting for the file preview.
+
+use ratatui::style::{Color, Modifier, Style};
+use std::path::Path;
+use std::sync::LazyLock;
+use syntect::easy::HighlightLines;
+use syntect::highlighting::{FontStyle, ThemeSet};
+use syntect::parsing::SyntaxSet;
+use syntect::util::LinesWithEndings;
+
+/// Grammars bundled with syntect. Loading them takes a noticeable
+/// moment, so it happens on first use.
+static SYNTAXES: LazyLock<SyntaxSet> = LazyLock::new(SyntaxSet::load_defaults_newlines);
+
+/// Color schemes bundled with syntect.
+static THEMES: LazyLock<ThemeSet> = LazyLock::new(ThemeSet::load_defaults);
+
+/// One highlighted line: text runs with their style.
+pub type StyledLine = Vec<(Style, String)>;
+
+/// Highlights `text` with the grammar picked from `path`'s extension
+/// or file name, falling back to the first line (shebangs, XML
+/// declarations).
+///
+/// Returns `None` when no grammar matches or `theme` is not a bundled
+/// scheme; the preview then shows plain text. This walks the whole
+/// text, so call it off the UI thread.
+pub fn highlight(text: &str, path: &str, theme: &str) -> Option<Vec<StyledLine>> {
+    let theme = THEMES.themes.get(theme)?;
+    let file = Path::new(path);
+    let syntax = file
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .and_then(|ext| SYNTAXES.find_syntax_by_extension(ext))
+        .or_else(|| {
+            file.file_name()
+                .and_then(|name| name.to_str())
+                .and_then(|name| SYNTAXES.find_syntax_by_extension(name))
+        })
+        .or_else(|| {
+            text.lines()
+                .next()
+                .and_then(|line| SYNTAXES.find_syntax_by_first_line(line))
+        })?;
+    if syntax.name == SYNTAXES.find_syntax_plain_text().name {
+        return None;
+    }
+
+    let mut highlighter = HighlightLines::new(syntax, theme);
+    let mut lines = Vec::new();
+    for line in LinesWithEndings::from(text) {
+        let ranges = highlighter.highlight_line(line, &SYNTAXES).ok()?;
+        lines.push(
+            ranges
+                .into_iter()
+                .map(|(style, piece)| {
+                    let piece = piece.trim_end_matches(['\n', '\r']).replace('\t', "    ");
+                    (convert(style), piece)
+                })
+                .collect(),
+        );
+    }
+    Some(lines)
+}
+
+/// Maps a syntect style to a ratatui one. Backgrounds are dropped so
+/// the terminal's own background shows through.
+fn convert(style: syntect::highlighting::Style) -> Style {
+    let fg = style.foreground;
+    let mut converted = Style::new().fg(Color::Rgb(fg.r, fg.g, fg.b));
+    if style.font_style.contains(FontStyle::BOLD) {
+        converted = converted.add_modifier(Modifier::BOLD);
+    }
+    if style.font_style.contains(FontStyle::ITALIC) {
+        converted = converted.add_modifier(Modifier::ITALIC);
+    }
+    if style.font_style.contains(FontStyle::UNDERLINE) {
+        converted = converted.add_modifier(Modifier::UNDERLINED);
+    }
+    converted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const THEME: &str = "base16-ocean.dark";
+
+    #[test]
+    fn test_highlights_by_extension() {
+        let lines = highlight("fn main() {}\nlet x = 1;\n", "src/main.rs", THEME).unwrap();
+        assert_eq!(lines.len(), 2);
+        let text: String = lines[0].iter().map(|(_, piece)| piece.as_str()).collect();
+        assert_eq!(text, "fn main() {}");
+        // Keywords and punctuation get different colors.
+        assert!(lines[0].len() > 1);
+    }
+
+    #[test]
+    fn test_detects_shebang() {
+        assert!(highlight("#!/bin/sh\necho hi\n", "run", THEME).is_some());
+    }
+
+    #[test]
+    fn test_unknown_files_stay_plain() {
+        assert!(highlight("just some notes", "notes.unknownext", THEME).is_none());
+        assert!(highlight("fn main() {}", "main.rs", "no-such-theme").is_none());
+    }
+
+    #[test]
+    fn test_every_preset_names_a_bundled_scheme() {
+        for preset in crate::ui::ThemePreset::ALL {
+            assert!(
+                THEMES.themes.contains_key(preset.theme().syntax),
+                "{:?}",
+                preset
+            );
+        }
+    }
+}
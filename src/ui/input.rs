@@ -0,0 +1,288 @@
+//! Single-line text editing shared by the modal forms: a cursor that
+//! `Left`/`Right`/`Home`/`End` move, typing and `Backspace`/`Delete`
+//! at the cursor, and `ctrl-w` deleting the word before it.
+//!
+//! The text stays a plain `String` in the form that owns it; [`Input`]
+//! only keeps where the cursor is. The cursor moves by grapheme
+//! cluster, so it never lands inside an emoji or an accented letter,
+//! and it counts them from the end,
+//! so a form that rewrites the text, say by completing it, leaves the
+//! cursor at the end where it usually is.
+
+use super::text;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{
+    style::{Modifier, Style},
+    text::Span,
+};
+use unicode_segmentation::UnicodeSegmentation;
+
+/// What a key did to a text field.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Edit {
+    /// The text changed.
+    Changed,
+    /// Only the cursor moved.
+    Moved,
+    /// The key does not edit text.
+    Ignored,
+}
+
+/// Cursor of a text field.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Input {
+    /// Grapheme clusters after the cursor.
+    back: usize,
+}
+
+impl Input {
+    /// Returns the cursor's position in `text`, in grapheme clusters.
+    pub fn position(&self, text: &str) -> usize {
+        text::graphemes(text).saturating_sub(self.back)
+    }
+
+    /// Returns the byte offset of the cursor in `text`.
+    fn offset(&self, text: &str) -> usize {
+        let position = self.position(text);
+        text.grapheme_indices(true)
+            .nth(position)
+            .map_or(text.len(), |(i, _)| i)
+    }
+
+    /// Moves the cursor to the end of the text.
+    pub fn end(&mut self) {
+        self.back = 0;
+    }
+
+    /// Inserts `c` at the cursor.
+    pub fn insert(&mut self, text: &mut String, c: char) {
+        let offset = self.offset(text);
+        text.insert(offset, c);
+    }
+
+    /// Inserts `pasted` at the cursor, leaving out control characters
+    /// such as the newline a copied line ends in. Returns whether
+    /// anything was inserted.
+    pub fn paste(&mut self, text: &mut String, pasted: &str) -> bool {
+        let pasted: String = pasted.chars().filter(|c| !c.is_control()).collect();
+        self.back = self.back.min(text::graphemes(text));
+        let offset = self.offset(text);
+        text.insert_str(offset, &pasted);
+        !pasted.is_empty()
+    }
+
+    /// Deletes the grapheme cluster before the cursor.
+    pub fn backspace(&mut self, text: &mut String) -> bool {
+        let offset = self.offset(text);
+        let Some((start, _)) = text[..offset].grapheme_indices(true).next_back() else {
+            return false;
+        };
+        text.replace_range(start..offset, "");
+        true
+    }
+
+    /// Deletes the grapheme cluster under the cursor.
+    pub fn delete(&mut self, text: &mut String) -> bool {
+        let offset = self.offset(text);
+        let Some(grapheme) = text[offset..].graphemes(true).next() else {
+            return false;
+        };
+        text.replace_range(offset..offset + grapheme.len(), "");
+        self.back = self.back.saturating_sub(1);
+        true
+    }
+
+    /// Deletes the word before the cursor, and the spaces after it.
+    pub fn delete_word(&mut self, text: &mut String) -> bool {
+        let offset = self.offset(text);
+        let before = text[..offset].trim_end();
+        let start = before
+            .char_indices()
+            .rev()
+            .find(|(_, c)| c.is_whitespace())
+            .map_or(0, |(i, c)| i + c.len_utf8());
+        if start == offset {
+            return false;
+        }
+        text.replace_range(start..offset, "");
+        true
+    }
+
+    /// Applies `key` to `text`. Control characters, as bracketed
+    /// paste can deliver, are never inserted.
+    pub fn handle_key(&mut self, text: &mut String, key: KeyEvent) -> Edit {
+        let changed = |done: bool| if done { Edit::Changed } else { Edit::Ignored };
+        let len = text::graphemes(text);
+        self.back = self.back.min(len);
+        match key.code {
+            KeyCode::Char('w') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                changed(self.delete_word(text))
+            }
+            KeyCode::Char(_)
+                if key
+                    .modifiers
+                    .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT) =>
+            {
+                Edit::Ignored
+            }
+            KeyCode::Char(c) if c.is_control() => Edit::Ignored,
+            KeyCode::Char(c) => {
+                self.insert(text, c);
+                Edit::Changed
+            }
+            KeyCode::Backspace => changed(self.backspace(text)),
+            KeyCode::Delete => changed(self.delete(text)),
+            KeyCode::Left => {
+                self.back = (self.back + 1).min(len);
+                Edit::Moved
+            }
+            KeyCode::Right => {
+                self.back = self.back.saturating_sub(1);
+                Edit::Moved
+            }
+            KeyCode::Home => {
+                self.back = len;
+                Edit::Moved
+            }
+            KeyCode::End => {
+                self.end();
+                Edit::Moved
+            }
+            _ => Edit::Ignored,
+        }
+    }
+
+    /// Returns `text` as spans at most `width` cells wide, scrolled so
+    /// the cursor shows, with the cursor drawn reversed.
+    pub fn spans(&self, text: &str, width: usize) -> Vec<Span<'static>> {
+        let graphemes: Vec<&str> = text.graphemes(true).collect();
+        let cursor = self.position(text);
+        let under = graphemes.get(cursor).copied().unwrap_or(" ");
+        let mut used = text::width(under);
+        let mut start = cursor;
+        while start > 0 && used + text::width(graphemes[start - 1]) <= width {
+            start -= 1;
+            used += text::width(graphemes[start]);
+        }
+        let mut end = (cursor + 1).min(graphemes.len());
+        while end < graphemes.len() && used + text::width(graphemes[end]) <= width {
+            used += text::width(graphemes[end]);
+            end += 1;
+        }
+        vec![
+            Span::raw(graphemes[start..cursor].concat()),
+            Span::styled(
+                under.to_string(),
+                Style::new().add_modifier(Modifier::REVERSED),
+            ),
+            Span::raw(graphemes.get(cursor + 1..end).unwrap_or_default().concat()),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::{KeyEventKind, KeyEventState};
+
+    fn key(code: KeyCode, modifiers: KeyModifiers) -> KeyEvent {
+        KeyEvent {
+            code,
+            modifiers,
+            kind: KeyEventKind::Press,
+            state: KeyEventState::NONE,
+        }
+    }
+
+    fn press(input: &mut Input, text: &mut String, code: KeyCode) -> Edit {
+        input.handle_key(text, key(code, KeyModifiers::NONE))
+    }
+
+    #[test]
+    fn test_edits_at_the_cursor() {
+        let mut input = Input::default();
+        let mut text = "héllo".to_string();
+
+        press(&mut input, &mut text, KeyCode::Left);
+        press(&mut input, &mut text, KeyCode::Left);
+        press(&mut input, &mut text, KeyCode::Char('X'));
+        assert_eq!(text, "hélXlo");
+        press(&mut input, &mut text, KeyCode::Home);
+        press(&mut input, &mut text, KeyCode::Delete);
+        assert_eq!(text, "élXlo");
+        assert_eq!(
+            press(&mut input, &mut text, KeyCode::Backspace),
+            Edit::Ignored
+        );
+        press(&mut input, &mut text, KeyCode::Right);
+        press(&mut input, &mut text, KeyCode::Backspace);
+        assert_eq!(text, "lXlo");
+        press(&mut input, &mut text, KeyCode::End);
+        press(&mut input, &mut text, KeyCode::Char('!'));
+        assert_eq!(text, "lXlo!");
+        assert_eq!(
+            press(&mut input, &mut text, KeyCode::Char('\n')),
+            Edit::Ignored
+        );
+    }
+
+    #[test]
+    fn test_ctrl_w_deletes_the_word_before_the_cursor() {
+        let mut input = Input::default();
+        let mut text = "one two  three".to_string();
+        let ctrl_w = key(KeyCode::Char('w'), KeyModifiers::CONTROL);
+
+        for _ in 0..5 {
+            press(&mut input, &mut text, KeyCode::Left);
+        }
+        assert_eq!(input.handle_key(&mut text, ctrl_w), Edit::Changed);
+        assert_eq!(text, "one three");
+        input.handle_key(&mut text, ctrl_w);
+        assert_eq!(text, "three");
+        assert_eq!(input.handle_key(&mut text, ctrl_w), Edit::Ignored);
+    }
+
+    #[test]
+    fn test_paste_inserts_at_the_cursor() {
+        let mut input = Input::default();
+        let mut text = "key=".to_string();
+        assert!(input.paste(&mut text, "secret\r\n"));
+        assert_eq!(text, "key=secret");
+
+        press(&mut input, &mut text, KeyCode::Home);
+        input.paste(&mut text, "my_");
+        assert_eq!(text, "my_key=secret");
+        assert_eq!(input.position(&text), 3);
+        assert!(!input.paste(&mut text, "\n"));
+    }
+
+    #[test]
+    fn test_spans_scroll_to_the_cursor() {
+        let mut input = Input::default();
+        let spans = input.spans("abcdef", 4);
+        assert_eq!(spans[0].content, "def");
+        assert_eq!(spans[1].content, " ");
+
+        input.back = 6;
+        let spans = input.spans("abcdef", 4);
+        assert_eq!(spans[0].content, "");
+        assert_eq!(spans[1].content, "a");
+        assert_eq!(spans[2].content, "bcd");
+
+        input.end();
+        let spans = input.spans("写真フォルダ", 7);
+        assert_eq!(spans[0].content, "ォルダ");
+    }
+
+    #[test]
+    fn test_edits_whole_graphemes() {
+        let mut input = Input::default();
+        let mut text = "cafe\u{301}👍🏽".to_string();
+        press(&mut input, &mut text, KeyCode::Left);
+        assert_eq!(input.position(&text), 4);
+        press(&mut input, &mut text, KeyCode::Backspace);
+        assert_eq!(text, "caf👍🏽");
+        press(&mut input, &mut text, KeyCode::Delete);
+        assert_eq!(text, "caf");
+    }
+}
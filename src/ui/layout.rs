@@ -1,50 +1,260 @@
 //! Terminal layout and area management.
 
-use ratatui::layout::{Constraint, Direction, Rect};
+use crate::app::Panel;
+use ratatui::layout::{Constraint, Direction, Position, Rect};
+
+/// Terminals narrower than this stack the remotes above the files,
+/// and modals use the full width.
+pub const NARROW_WIDTH: u16 = 80;
+
+/// Terminals shorter than this hide the help strip, and modals use
+/// the full height.
+pub const SHORT_HEIGHT: u16 = 20;
+
+/// Width of the collapsed remotes strip, borders included.
+pub const STRIP_WIDTH: u16 = 5;
+
+/// How much of the remotes panel is shown.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Sidebar {
+    /// The full list, with names and types.
+    #[default]
+    Full,
+    /// A thin strip with an icon per remote.
+    Strip,
+    /// Not shown; the file list takes the full width.
+    Hidden,
+}
+
+impl Sidebar {
+    /// Returns the state the collapse toggle moves to.
+    pub fn next(self) -> Self {
+        match self {
+            Self::Full => Self::Strip,
+            Self::Strip => Self::Hidden,
+            Self::Hidden => Self::Full,
+        }
+    }
+}
 
 /// Divides terminal into distinct regions.
 pub struct Layout;
 
 impl Layout {
     /// Split terminal area into help, content, and status regions.
+    /// With `show_logs`, the bottom part of the content region goes
+    /// to the log pane. The remotes list takes `remotes_width` percent
+    /// of the width, or less as `sidebar` says. Narrow terminals stack
+    /// the lists vertically; short ones give the help strip's row to
+    /// the lists.
     ///
     /// Returns `LayoutRects` containing areas for each panel.
-    pub fn split(area: Rect) -> LayoutRects {
+    pub fn split(area: Rect, show_logs: bool, remotes_width: u16, sidebar: Sidebar) -> LayoutRects {
+        let short = area.height < SHORT_HEIGHT;
         let chunks = ratatui::layout::Layout::default()
             .direction(Direction::Vertical)
             .constraints([
-                Constraint::Length(1),
+                Constraint::Length(if short { 0 } else { 1 }),
                 Constraint::Min(4),
                 Constraint::Length(1),
             ])
             .split(area);
 
-        let help_area = chunks[0];
-        let content_area = chunks[1];
+        let help_area = (!short).then_some(chunks[0]);
         let status_area = chunks[2];
 
+        let (content_area, logs_area) = if show_logs {
+            let split = ratatui::layout::Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([Constraint::Percentage(65), Constraint::Percentage(35)])
+                .split(chunks[1]);
+            (split[0], Some(split[1]))
+        } else {
+            (chunks[1], None)
+        };
+
+        let narrow = area.width < NARROW_WIDTH;
+        let (direction, remotes) = match (narrow, sidebar) {
+            (_, Sidebar::Hidden) => (Direction::Horizontal, Constraint::Length(0)),
+            // One row between the borders.
+            (true, Sidebar::Strip) => (Direction::Vertical, Constraint::Length(3)),
+            (true, Sidebar::Full) => (Direction::Vertical, Constraint::Percentage(35)),
+            (false, Sidebar::Strip) => (Direction::Horizontal, Constraint::Length(STRIP_WIDTH)),
+            (false, Sidebar::Full) => {
+                (Direction::Horizontal, Constraint::Percentage(remotes_width))
+            }
+        };
         let content_chunks = ratatui::layout::Layout::default()
-            .direction(Direction::Horizontal)
-            .constraints([Constraint::Percentage(30), Constraint::Percentage(70)])
+            .direction(direction)
+            .constraints([remotes, Constraint::Min(0)])
             .split(content_area);
 
         LayoutRects {
             help: help_area,
             remotes: content_chunks[0],
             files: content_chunks[1],
+            logs: logs_area,
             status: status_area,
         }
     }
+
+    /// Returns the list index under a screen position inside a
+    /// bordered list panel, or `None` on the border or outside.
+    ///
+    /// The index may be past the end of the list; callers clamp it.
+    pub fn list_row(area: Rect, offset: usize, column: u16, row: u16) -> Option<usize> {
+        let inner = Rect {
+            x: area.x + 1,
+            y: area.y + 1,
+            width: area.width.saturating_sub(2),
+            height: area.height.saturating_sub(2),
+        };
+        inner
+            .contains(Position::new(column, row))
+            .then(|| offset + usize::from(row - inner.y))
+    }
+}
+
+/// Returns a modal of up to `width` by `height` centered in `area`.
+/// It keeps a margin around it where there is room, and shrinks to
+/// fit where there is not, so it never reaches outside `area`.
+pub fn modal_area(area: Rect, width: u16, height: u16) -> Rect {
+    let margin_x = if area.width < NARROW_WIDTH { 0 } else { 4 };
+    let margin_y = if area.height < SHORT_HEIGHT { 0 } else { 2 };
+    let width = width.min(area.width.saturating_sub(margin_x));
+    let height = height.min(area.height.saturating_sub(margin_y));
+    Rect {
+        x: area.x + (area.width - width) / 2,
+        y: area.y + (area.height - height) / 2,
+        width,
+        height,
+    }
 }
 
 /// Layout regions for different UI components.
 pub struct LayoutRects {
-    /// Help text area at top.
-    pub help: Rect,
-    /// Remotes list area (left).
+    /// Help text area at top; None on short terminals.
+    pub help: Option<Rect>,
+    /// Remotes list area (left, or top on narrow terminals).
     pub remotes: Rect,
-    /// Files list area (right).
+    /// Files list area (right, or below the remotes).
     pub files: Rect,
+    /// Log pane below the lists, when shown.
+    pub logs: Option<Rect>,
     /// Status bar area at bottom.
     pub status: Rect,
 }
+
+impl LayoutRects {
+    /// Gives `panel` the room of every panel, leaving the others
+    /// empty, to zoom in on it.
+    pub fn zoom(&mut self, panel: Panel) {
+        let content = [self.remotes, self.files]
+            .into_iter()
+            .chain(self.logs)
+            .reduce(Rect::union)
+            .unwrap_or(self.files);
+        let empty = Rect {
+            width: 0,
+            height: 0,
+            ..content
+        };
+        self.remotes = if panel == Panel::Remotes {
+            content
+        } else {
+            empty
+        };
+        self.files = if panel == Panel::Files {
+            content
+        } else {
+            empty
+        };
+        self.logs = (panel == Panel::Logs).then_some(content);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_log_pane_takes_space_from_lists() {
+        let area = Rect::new(0, 0, 100, 40);
+        let without = Layout::split(area, false, 30, Sidebar::Full);
+        let with = Layout::split(area, true, 30, Sidebar::Full);
+
+        assert_eq!(without.logs, None);
+        let logs = with.logs.unwrap();
+        assert!(with.files.height < without.files.height);
+        assert_eq!(logs.y, with.files.bottom());
+        assert_eq!(logs.bottom(), with.status.y);
+    }
+
+    #[test]
+    fn test_small_terminals_stack_lists_and_drop_help() {
+        let rects = Layout::split(Rect::new(0, 0, 60, 16), false, 30, Sidebar::Full);
+
+        assert_eq!(rects.help, None);
+        assert_eq!(rects.remotes.y, 0);
+        assert_eq!(rects.remotes.width, 60);
+        assert_eq!(rects.files.y, rects.remotes.bottom());
+        assert_eq!(rects.files.bottom(), rects.status.y);
+    }
+
+    #[test]
+    fn test_remotes_take_the_given_width() {
+        let rects = Layout::split(Rect::new(0, 0, 100, 40), false, 45, Sidebar::Full);
+
+        assert_eq!(rects.remotes.width, 45);
+        assert_eq!(rects.files.x, 45);
+        assert_eq!(rects.files.width, 55);
+    }
+
+    #[test]
+    fn test_collapsed_sidebar_gives_files_the_width() {
+        let area = Rect::new(0, 0, 100, 40);
+        let strip = Layout::split(area, false, 30, Sidebar::Strip);
+        let hidden = Layout::split(area, false, 30, Sidebar::Hidden);
+
+        assert_eq!(strip.remotes.width, STRIP_WIDTH);
+        assert_eq!(strip.files.width, 100 - STRIP_WIDTH);
+        assert_eq!(hidden.remotes.width, 0);
+        assert_eq!(hidden.files, Rect::new(0, 1, 100, 38));
+    }
+
+    #[test]
+    fn test_zoom_gives_the_panel_every_row() {
+        let area = Rect::new(0, 0, 100, 40);
+        let mut rects = Layout::split(area, true, 30, Sidebar::Full);
+        rects.zoom(Panel::Logs);
+
+        assert_eq!(rects.logs, Some(Rect::new(0, 1, 100, 38)));
+        assert_eq!(rects.remotes.width, 0);
+        assert_eq!(rects.files.height, 0);
+
+        let mut rects = Layout::split(area, true, 30, Sidebar::Full);
+        rects.zoom(Panel::Files);
+        assert_eq!(rects.files, Rect::new(0, 1, 100, 38));
+        assert_eq!(rects.logs, None);
+    }
+
+    #[test]
+    fn test_modal_area_fits_small_terminals() {
+        let roomy = modal_area(Rect::new(0, 0, 120, 40), 60, 12);
+        assert_eq!(roomy, Rect::new(30, 14, 60, 12));
+
+        let small = modal_area(Rect::new(0, 0, 50, 8), 60, 12);
+        assert_eq!(small, Rect::new(0, 0, 50, 8));
+    }
+
+    #[test]
+    fn test_list_row_skips_border_and_adds_offset() {
+        let area = Rect::new(10, 5, 20, 10);
+
+        assert_eq!(Layout::list_row(area, 0, 11, 6), Some(0));
+        assert_eq!(Layout::list_row(area, 7, 11, 8), Some(9));
+        assert_eq!(Layout::list_row(area, 0, 11, 5), None);
+        assert_eq!(Layout::list_row(area, 0, 10, 6), None);
+        assert_eq!(Layout::list_row(area, 0, 11, 14), None);
+    }
+}
@@ -1,12 +1,33 @@
 //! User interface components and rendering.
 
+pub mod file_colors;
+pub mod highlight;
+pub mod input;
 pub mod layout;
-pub mod styles;
+pub mod notifications;
+pub mod text;
+pub mod theme;
 pub mod widgets;
 
-pub use layout::Layout;
+pub use file_colors::FileColors;
+pub use input::{Edit, Input};
+pub use layout::{Layout, Sidebar};
+pub use notifications::{Notifications, NotificationsWidget, Severity, Toast};
+pub use theme::{Theme, ThemePreset};
 pub use widgets::{
-    ConfirmModal, ConfirmWidget, CreateRemoteModal, CreateRemoteMode, CreateRemoteWidget,
-    FileListWidget, FileOperationType, FileOperationsModal, FileOperationsWidget, HelpWidget,
-    RemoteField, RemoteListWidget, StatusBarWidget,
+    AuditView, AuditWidget, BatchRename, BatchRenameWidget, CallsWidget, ColumnView,
+    CommandPalette, CommandPaletteWidget, CompareForm, CompareView, CompareWidget,
+    ConfigPasswordModal, ConfigPasswordWidget, ConfigWizard, ConfigWizardWidget, ConfirmChoice,
+    ConfirmModal, ConfirmWidget, ConflictModal, ConflictWidget, CopyRemoteModal, CopyRemoteWidget,
+    CopyUrlField, CopyUrlPrompt, CopyUrlWidget, CreateRemoteModal, CreateRemoteMode,
+    CreateRemoteWidget, DaemonSetup, DaemonSetupWidget, DiffRow, ErrorChoice, ErrorModal,
+    ErrorWidget, FileKind, FileListWidget, FileOperationType, FileOperationsModal,
+    FileOperationsWidget, FilterForm, FilterWidget, GlobPrompt, GlobPromptWidget, GoToPrompt,
+    GoToWidget, HelpContext, HelpOverlayWidget, HelpWidget, HistoryView, HistoryWidget, JobsView,
+    JobsWidget, LargestPrompt, LargestView, LargestWidget, LogView, LogsWidget, MetadataEdit,
+    MetadataField, MetadataView, MetadataWidget, MountField, MountForm, MountsView, MountsWidget,
+    NameView, PaletteItem, Preview, PreviewContent, PreviewWidget, QuitChoice, QuitModal,
+    QuitWidget, RecentPicker, RecentWidget, RemoteDetails, RemoteField, RemoteInfo,
+    RemoteInfoWidget, RemoteListWidget, SpinnerWidget, StatusBarWidget, StatusView, TierPrompt,
+    TierWidget, UsageView, UsageWidget, VersionsView, VersionsWidget,
 };
@@ -0,0 +1,254 @@
+//! Ephemeral toast notifications shown in the bottom-right corner.
+
+use crate::ui::{Theme, text};
+use ratatui::{
+    Frame,
+    layout::Rect,
+    style::{Color, Style},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+};
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+/// Most toasts kept at once; pushing more drops the oldest.
+const MAX_TOASTS: usize = 5;
+
+/// Width of a toast, borders included.
+const TOAST_WIDTH: u16 = 40;
+
+/// How serious a notification is. Decides its color and how long it
+/// stays on screen.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    /// Neutral progress information.
+    Info,
+    /// An operation finished as requested.
+    Success,
+    /// Something worked but deserves attention.
+    Warning,
+    /// An operation failed.
+    Error,
+}
+
+impl Severity {
+    /// Returns how long a toast of this severity stays visible.
+    pub fn default_timeout(self) -> Duration {
+        match self {
+            Self::Info | Self::Success => Duration::from_secs(3),
+            Self::Warning => Duration::from_secs(5),
+            Self::Error => Duration::from_secs(8),
+        }
+    }
+
+    /// Returns the name a toast's title shows when the theme spells
+    /// out what colors convey.
+    fn label(self) -> &'static str {
+        match self {
+            Self::Info => " Info ",
+            Self::Success => " Done ",
+            Self::Warning => " Warning ",
+            Self::Error => " Error ",
+        }
+    }
+
+    fn color(self, theme: &Theme) -> Color {
+        match self {
+            Self::Info => theme.accent,
+            Self::Success => theme.success,
+            Self::Warning => theme.warning,
+            Self::Error => theme.error,
+        }
+    }
+}
+
+/// A single notification.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Toast {
+    /// Text shown to the user.
+    pub message: String,
+    /// Severity, which picks the border color.
+    pub severity: Severity,
+    /// When the toast was pushed.
+    pub created: Instant,
+    /// How long the toast stays visible.
+    pub timeout: Duration,
+}
+
+impl Toast {
+    /// Returns whether the toast should be gone at `now`.
+    pub fn is_expired(&self, now: Instant) -> bool {
+        now.duration_since(self.created) >= self.timeout
+    }
+}
+
+/// Queue of live toasts, newest last.
+#[derive(Debug, Clone, Default)]
+pub struct Notifications {
+    toasts: VecDeque<Toast>,
+}
+
+impl Notifications {
+    /// Create an empty notification queue.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Shows `message` with the severity's default timeout.
+    pub fn push(&mut self, severity: Severity, message: impl Into<String>) {
+        self.push_with_timeout(severity, message, severity.default_timeout());
+    }
+
+    /// Shows `message` for `timeout`.
+    pub fn push_with_timeout(
+        &mut self,
+        severity: Severity,
+        message: impl Into<String>,
+        timeout: Duration,
+    ) {
+        if self.toasts.len() == MAX_TOASTS {
+            self.toasts.pop_front();
+        }
+        self.toasts.push_back(Toast {
+            message: message.into(),
+            severity,
+            created: Instant::now(),
+            timeout,
+        });
+    }
+
+    /// Shows an [`Severity::Info`] toast.
+    pub fn info(&mut self, message: impl Into<String>) {
+        self.push(Severity::Info, message);
+    }
+
+    /// Shows a [`Severity::Success`] toast.
+    pub fn success(&mut self, message: impl Into<String>) {
+        self.push(Severity::Success, message);
+    }
+
+    /// Shows a [`Severity::Warning`] toast.
+    pub fn warning(&mut self, message: impl Into<String>) {
+        self.push(Severity::Warning, message);
+    }
+
+    /// Shows a [`Severity::Error`] toast.
+    pub fn error(&mut self, message: impl Into<String>) {
+        self.push(Severity::Error, message);
+    }
+
+    /// Drops toasts that have timed out by `now`. Returns whether any
+    /// were dropped.
+    pub fn prune(&mut self, now: Instant) -> bool {
+        let before = self.toasts.len();
+        self.toasts.retain(|toast| !toast.is_expired(now));
+        self.toasts.len() != before
+    }
+
+    /// Returns the live toasts, oldest first.
+    pub fn toasts(&self) -> impl DoubleEndedIterator<Item = &Toast> {
+        self.toasts.iter()
+    }
+
+    /// Returns whether there is nothing to show.
+    pub fn is_empty(&self) -> bool {
+        self.toasts.is_empty()
+    }
+}
+
+/// Widget stacking toasts upward from the bottom-right corner.
+pub struct NotificationsWidget;
+
+impl NotificationsWidget {
+    /// Render the toasts inside `area`, newest at the bottom.
+    ///
+    /// # Arguments
+    /// * `f` - Frame for rendering
+    /// * `area` - Area to stack the toasts in
+    /// * `notifications` - Toasts to show
+    /// * `theme` - Colors to draw with
+    pub fn render(f: &mut Frame, area: Rect, notifications: &Notifications, theme: &Theme) {
+        let width = TOAST_WIDTH.min(area.width);
+        let inner_width = usize::from(width.saturating_sub(2)).max(1);
+        let mut bottom = area.bottom();
+
+        for toast in notifications.toasts().rev() {
+            let lines = text::width(&toast.message).div_ceil(inner_width).max(1);
+            let height = u16::try_from(lines + 2).unwrap_or(u16::MAX);
+            if bottom < area.y + height {
+                break;
+            }
+            bottom -= height;
+            let toast_area = Rect {
+                x: area.right().saturating_sub(width),
+                y: bottom,
+                width,
+                height,
+            };
+            let color = toast.severity.color(theme);
+            f.render_widget(Clear, toast_area);
+            let mut block = Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(color));
+            if theme.symbols {
+                block = block.title(toast.severity.label());
+            }
+            let paragraph = Paragraph::new(toast.message.as_str())
+                .wrap(Wrap { trim: true })
+                .block(block);
+            f.render_widget(paragraph, toast_area);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_push_keeps_order() {
+        let mut notifications = Notifications::new();
+        notifications.info("first");
+        notifications.error("second");
+
+        let messages: Vec<_> = notifications.toasts().map(|t| t.message.as_str()).collect();
+        assert_eq!(messages, ["first", "second"]);
+    }
+
+    #[test]
+    fn test_severity_sets_timeout() {
+        let mut notifications = Notifications::new();
+        notifications.error("boom");
+        let toast = notifications.toasts().next().unwrap();
+        assert_eq!(toast.severity, Severity::Error);
+        assert_eq!(toast.timeout, Severity::Error.default_timeout());
+    }
+
+    #[test]
+    fn test_errors_outlive_successes() {
+        assert!(Severity::Error.default_timeout() > Severity::Success.default_timeout());
+    }
+
+    #[test]
+    fn test_prune_drops_expired() {
+        let mut notifications = Notifications::new();
+        notifications.push_with_timeout(Severity::Info, "short", Duration::from_millis(10));
+        notifications.push_with_timeout(Severity::Info, "long", Duration::from_secs(60));
+
+        assert!(notifications.prune(Instant::now() + Duration::from_secs(1)));
+        assert!(!notifications.prune(Instant::now() + Duration::from_secs(1)));
+
+        let messages: Vec<_> = notifications.toasts().map(|t| t.message.as_str()).collect();
+        assert_eq!(messages, ["long"]);
+    }
+
+    #[test]
+    fn test_oldest_is_dropped_when_full() {
+        let mut notifications = Notifications::new();
+        for i in 0..=MAX_TOASTS {
+            notifications.info(format!("toast {}", i));
+        }
+
+        assert_eq!(notifications.toasts().count(), MAX_TOASTS);
+        assert_eq!(notifications.toasts().next().unwrap().message, "toast 1");
+    }
+}
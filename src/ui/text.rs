@@ -0,0 +1,94 @@
+//! Measuring and cutting text by what the terminal draws: grapheme
+//! clusters, so an emoji or an accented letter is never split, and
+//! their width in cells, so CJK characters take the two cells they
+//! fill.
+
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
+
+/// Returns the number of cells `text` takes.
+pub fn width(text: &str) -> usize {
+    UnicodeWidthStr::width(text)
+}
+
+/// Returns the number of grapheme clusters in `text`.
+pub fn graphemes(text: &str) -> usize {
+    text.graphemes(true).count()
+}
+
+/// Removes the last grapheme cluster of `text`, as `Backspace` does.
+pub fn pop_grapheme(text: &mut String) {
+    if let Some((start, _)) = text.grapheme_indices(true).next_back() {
+        text.truncate(start);
+    }
+}
+
+/// Returns the longest start of `text` at most `width` cells wide.
+pub fn take_width(text: &str, width: usize) -> &str {
+    let mut used = 0;
+    for (start, grapheme) in text.grapheme_indices(true) {
+        used += UnicodeWidthStr::width(grapheme);
+        if used > width {
+            return &text[..start];
+        }
+    }
+    text
+}
+
+/// Cuts `text` to at most `width` cells, ending in `…` if anything
+/// was cut.
+pub fn truncate(text: &str, width: usize) -> String {
+    if self::width(text) <= width {
+        return text.to_string();
+    }
+    match width {
+        0 => String::new(),
+        _ => format!("{}…", take_width(text, width - 1)),
+    }
+}
+
+/// Cuts `text` to at most `width` cells from the front, starting in
+/// `…` if anything was cut, so the end of a path stays visible.
+pub fn truncate_start(text: &str, width: usize) -> String {
+    if self::width(text) <= width {
+        return text.to_string();
+    }
+    let mut used = 1;
+    let mut start = text.len();
+    for (i, grapheme) in text.grapheme_indices(true).rev() {
+        used += UnicodeWidthStr::width(grapheme);
+        if used > width {
+            break;
+        }
+        start = i;
+    }
+    format!("…{}", &text[start..])
+}
+
+/// Cuts or pads `text` with spaces to exactly `width` cells. A wide
+/// character that would straddle the edge is left out.
+pub fn pad(text: &str, width: usize) -> String {
+    let cut = truncate(text, width);
+    let fill = width.saturating_sub(self::width(cut.as_str()));
+    format!("{}{}", cut, " ".repeat(fill))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cuts_by_cells_between_graphemes() {
+        assert_eq!(width("日本語"), 6);
+        assert_eq!(truncate("日本語.txt", 6), "日本…");
+        assert_eq!(pad("日本語.txt", 6), "日本… ");
+        assert_eq!(pad("ab", 4), "ab  ");
+        assert_eq!(truncate_start("a/b/写真.jpg", 9), "…写真.jpg");
+        assert_eq!(take_width("e\u{301}x", 1), "e\u{301}");
+
+        let mut family = "hi👨‍👩‍👧".to_string();
+        assert_eq!(graphemes(&family), 3);
+        pop_grapheme(&mut family);
+        assert_eq!(family, "hi");
+    }
+}
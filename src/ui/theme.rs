@@ -0,0 +1,505 @@
+//! Color theme shared by every widget.
+
+use super::file_colors::FILE_COLORS_KEY;
+use crate::error::{LazyFileError, Result};
+use ratatui::style::{Color, Modifier, Style};
+use std::collections::BTreeMap;
+use std::str::FromStr;
+
+/// `[theme]` entry naming the preset the colors are applied on top of.
+const PRESET_KEY: &str = "preset";
+
+/// `[theme]` entry turning symbols on or off.
+const SYMBOLS_KEY: &str = "symbols";
+
+/// Colors used across the UI.
+///
+/// Widgets never hard-code colors; they read them from the theme the
+/// launcher passes down, so a config file can restyle everything.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Theme {
+    /// Borders of the focused panel and of modals.
+    pub border: Color,
+    /// Key hints, input text and the top help line.
+    pub accent: Color,
+    /// Section headings, prompts and the focused form field.
+    pub heading: Color,
+    /// Text of the selected row.
+    pub selection_fg: Color,
+    /// Background of the selected row.
+    pub selection_bg: Color,
+    /// Errors and destructive actions.
+    pub error: Color,
+    /// Completed operations.
+    pub success: Color,
+    /// Things that need attention but did not fail.
+    pub warning: Color,
+    /// Secondary text such as usage hints.
+    pub dim: Color,
+    /// Background painted behind modals.
+    pub backdrop: Color,
+    /// Status bar text.
+    pub status_fg: Color,
+    /// Status bar background.
+    pub status_bg: Color,
+    /// Directory names in the files panel.
+    pub directory: Color,
+    /// Archive names in the files panel.
+    pub archive: Color,
+    /// Image, video and audio file names in the files panel.
+    pub media: Color,
+    /// Source code file names in the files panel.
+    pub code: Color,
+    /// Bundled syntect color scheme used to highlight previews.
+    pub syntax: &'static str,
+    /// Whether what colors convey is also spelled out with symbols:
+    /// `>` before the selected row and around the chosen button, and
+    /// a title naming each toast's severity.
+    pub symbols: bool,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        ThemePreset::Dark.theme()
+    }
+}
+
+/// A built-in theme, selectable with `--theme`, the config file or
+/// the command palette.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ThemePreset {
+    /// ANSI colors tuned for dark terminals. The default.
+    Dark,
+    /// ANSI colors tuned for light terminals.
+    Light,
+    /// Solarized dark palette.
+    Solarized,
+    /// Gruvbox dark palette.
+    Gruvbox,
+    /// Bright colors on black for maximum legibility.
+    HighContrast,
+    /// Black on white for maximum legibility on light terminals.
+    HighContrastLight,
+    /// The Okabe-Ito palette, whose colors stay apart with any kind
+    /// of color blindness: errors are vermillion and successes blue,
+    /// never red against green.
+    Colorblind,
+}
+
+impl ThemePreset {
+    /// Every preset, in the order they are listed.
+    pub const ALL: [Self; 7] = [
+        Self::Dark,
+        Self::Light,
+        Self::Solarized,
+        Self::Gruvbox,
+        Self::HighContrast,
+        Self::HighContrastLight,
+        Self::Colorblind,
+    ];
+
+    /// Returns the identifier used by `--theme` and the config file.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Dark => "dark",
+            Self::Light => "light",
+            Self::Solarized => "solarized",
+            Self::Gruvbox => "gruvbox",
+            Self::HighContrast => "high-contrast",
+            Self::HighContrastLight => "high-contrast-light",
+            Self::Colorblind => "colorblind",
+        }
+    }
+
+    /// Returns the preset with the given identifier.
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|preset| preset.name() == name)
+    }
+
+    /// Returns a short human-readable name.
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Dark => "Dark",
+            Self::Light => "Light",
+            Self::Solarized => "Solarized",
+            Self::Gruvbox => "Gruvbox",
+            Self::HighContrast => "High contrast",
+            Self::HighContrastLight => "High contrast light",
+            Self::Colorblind => "Colorblind safe",
+        }
+    }
+
+    /// Returns the preset's colors.
+    pub fn theme(self) -> Theme {
+        match self {
+            Self::Dark => Theme {
+                border: Color::Cyan,
+                accent: Color::Cyan,
+                heading: Color::Yellow,
+                selection_fg: Color::Black,
+                selection_bg: Color::Cyan,
+                error: Color::Red,
+                success: Color::Green,
+                warning: Color::Yellow,
+                dim: Color::Gray,
+                backdrop: Color::DarkGray,
+                status_fg: Color::Black,
+                status_bg: Color::Gray,
+                directory: Color::Blue,
+                archive: Color::Red,
+                media: Color::Magenta,
+                code: Color::Green,
+                syntax: "base16-ocean.dark",
+                symbols: false,
+            },
+            Self::Light => Theme {
+                border: Color::Blue,
+                accent: Color::Blue,
+                heading: Color::Magenta,
+                selection_fg: Color::White,
+                selection_bg: Color::Blue,
+                error: Color::Red,
+                success: Color::Green,
+                warning: Color::Rgb(0xaf, 0x5f, 0x00),
+                dim: Color::DarkGray,
+                backdrop: Color::Gray,
+                status_fg: Color::White,
+                status_bg: Color::DarkGray,
+                directory: Color::Blue,
+                archive: Color::Red,
+                media: Color::Magenta,
+                code: Color::Green,
+                syntax: "InspiredGitHub",
+                symbols: false,
+            },
+            Self::Solarized => Theme {
+                border: Color::Rgb(0x26, 0x8b, 0xd2),
+                accent: Color::Rgb(0x2a, 0xa1, 0x98),
+                heading: Color::Rgb(0xb5, 0x89, 0x00),
+                selection_fg: Color::Rgb(0x00, 0x2b, 0x36),
+                selection_bg: Color::Rgb(0x26, 0x8b, 0xd2),
+                error: Color::Rgb(0xdc, 0x32, 0x2f),
+                success: Color::Rgb(0x85, 0x99, 0x00),
+                warning: Color::Rgb(0xcb, 0x4b, 0x16),
+                dim: Color::Rgb(0x58, 0x6e, 0x75),
+                backdrop: Color::Rgb(0x07, 0x36, 0x42),
+                status_fg: Color::Rgb(0x00, 0x2b, 0x36),
+                status_bg: Color::Rgb(0x93, 0xa1, 0xa1),
+                directory: Color::Rgb(0x26, 0x8b, 0xd2),
+                archive: Color::Rgb(0xdc, 0x32, 0x2f),
+                media: Color::Rgb(0xd3, 0x36, 0x82),
+                code: Color::Rgb(0x85, 0x99, 0x00),
+                syntax: "Solarized (dark)",
+                symbols: false,
+            },
+            Self::Gruvbox => Theme {
+                border: Color::Rgb(0x8e, 0xc0, 0x7c),
+                accent: Color::Rgb(0x83, 0xa5, 0x98),
+                heading: Color::Rgb(0xfa, 0xbd, 0x2f),
+                selection_fg: Color::Rgb(0x28, 0x28, 0x28),
+                selection_bg: Color::Rgb(0x83, 0xa5, 0x98),
+                error: Color::Rgb(0xfb, 0x49, 0x34),
+                success: Color::Rgb(0xb8, 0xbb, 0x26),
+                warning: Color::Rgb(0xfe, 0x80, 0x19),
+                dim: Color::Rgb(0x92, 0x83, 0x74),
+                backdrop: Color::Rgb(0x3c, 0x38, 0x36),
+                status_fg: Color::Rgb(0x28, 0x28, 0x28),
+                status_bg: Color::Rgb(0xa8, 0x99, 0x84),
+                directory: Color::Rgb(0x83, 0xa5, 0x98),
+                archive: Color::Rgb(0xfb, 0x49, 0x34),
+                media: Color::Rgb(0xd3, 0x86, 0x9b),
+                code: Color::Rgb(0xb8, 0xbb, 0x26),
+                syntax: "base16-eighties.dark",
+                symbols: false,
+            },
+            Self::HighContrast => Theme {
+                border: Color::White,
+                accent: Color::LightCyan,
+                heading: Color::LightYellow,
+                selection_fg: Color::Black,
+                selection_bg: Color::White,
+                error: Color::LightRed,
+                success: Color::LightGreen,
+                warning: Color::LightYellow,
+                dim: Color::White,
+                backdrop: Color::Black,
+                status_fg: Color::Black,
+                status_bg: Color::White,
+                directory: Color::LightBlue,
+                archive: Color::LightRed,
+                media: Color::LightMagenta,
+                code: Color::LightGreen,
+                syntax: "base16-mocha.dark",
+                symbols: true,
+            },
+            Self::HighContrastLight => Theme {
+                border: Color::Black,
+                accent: Color::Blue,
+                heading: Color::Black,
+                selection_fg: Color::White,
+                selection_bg: Color::Black,
+                error: Color::Rgb(0xa0, 0x00, 0x00),
+                success: Color::Rgb(0x00, 0x50, 0x00),
+                warning: Color::Rgb(0x80, 0x40, 0x00),
+                dim: Color::Black,
+                backdrop: Color::White,
+                status_fg: Color::White,
+                status_bg: Color::Black,
+                directory: Color::Blue,
+                archive: Color::Rgb(0xa0, 0x00, 0x00),
+                media: Color::Magenta,
+                code: Color::Rgb(0x00, 0x50, 0x00),
+                syntax: "InspiredGitHub",
+                symbols: true,
+            },
+            Self::Colorblind => Theme {
+                border: Color::Rgb(0x56, 0xb4, 0xe9),
+                accent: Color::Rgb(0x56, 0xb4, 0xe9),
+                heading: Color::Rgb(0xf0, 0xe4, 0x42),
+                selection_fg: Color::Black,
+                selection_bg: Color::Rgb(0x56, 0xb4, 0xe9),
+                error: Color::Rgb(0xd5, 0x5e, 0x00),
+                success: Color::Rgb(0x00, 0x72, 0xb2),
+                warning: Color::Rgb(0xe6, 0x9f, 0x00),
+                dim: Color::Gray,
+                backdrop: Color::DarkGray,
+                status_fg: Color::Black,
+                status_bg: Color::Gray,
+                directory: Color::Rgb(0x56, 0xb4, 0xe9),
+                archive: Color::Rgb(0xd5, 0x5e, 0x00),
+                media: Color::Rgb(0xcc, 0x79, 0xa7),
+                code: Color::Rgb(0x00, 0x9e, 0x73),
+                syntax: "base16-ocean.dark",
+                symbols: true,
+            },
+        }
+    }
+}
+
+impl Theme {
+    /// Builds a theme from a preset plus the config file's `[theme]`
+    /// table, keyed by field name.
+    ///
+    /// The preset is `preset` if given (the `--theme` flag), else the
+    /// table's `preset` entry, else [`ThemePreset::Dark`]. Colors are
+    /// written as names (`red`, `light-blue`), ANSI indexes (`208`)
+    /// or hex (`#ff8800`). A `symbols` entry of `on` or `off`
+    /// overrides whether the preset spells things out with symbols.
+    ///
+    /// # Errors
+    /// Returns `InvalidTheme` for an unknown key, an unknown
+    /// preset, a `symbols` entry other than `on` or `off`, or a value
+    /// that is not a color.
+    pub fn from_config(
+        preset: Option<ThemePreset>,
+        colors: &BTreeMap<String, String>,
+    ) -> Result<Self> {
+        let configured =
+            match colors.get(PRESET_KEY) {
+                Some(name) => Some(ThemePreset::from_name(name).ok_or_else(|| {
+                    LazyFileError::InvalidTheme {
+                        key: PRESET_KEY.to_string(),
+                        value: name.clone(),
+                        reason: "unknown theme preset",
+                    }
+                })?),
+                None => None,
+            };
+        let mut theme = preset.or(configured).unwrap_or(ThemePreset::Dark).theme();
+        for (key, value) in colors {
+            if key == PRESET_KEY || key == FILE_COLORS_KEY {
+                continue;
+            }
+            let invalid = |reason| LazyFileError::InvalidTheme {
+                key: key.clone(),
+                value: value.clone(),
+                reason,
+            };
+            if key == SYMBOLS_KEY {
+                theme.symbols = match value.as_str() {
+                    "on" => true,
+                    "off" => false,
+                    _ => return Err(invalid("expected on or off")),
+                };
+                continue;
+            }
+            let slot = theme
+                .slot_mut(key)
+                .ok_or_else(|| invalid("unknown theme key"))?;
+            *slot = Color::from_str(value)
+                .map_err(|_| invalid("expected a color name, index, or #rrggbb"))?;
+        }
+        Ok(theme)
+    }
+
+    /// Style for the selected row of a list.
+    pub fn selected(&self) -> Style {
+        Style::new()
+            .fg(self.selection_fg)
+            .bg(self.selection_bg)
+            .add_modifier(Modifier::BOLD)
+    }
+
+    /// Returns what goes before the selected row of a list: `> ` with
+    /// symbols on, so the selection shows without its colors.
+    pub fn highlight_symbol(&self) -> &'static str {
+        if self.symbols { "> " } else { "" }
+    }
+
+    /// Returns the text of a button, marked with `>` and `<` when it
+    /// is the chosen one and symbols are on.
+    pub fn button(&self, label: &str, selected: bool) -> String {
+        if self.symbols && selected {
+            format!("> {} <", label)
+        } else {
+            format!(" {} ", label)
+        }
+    }
+
+    /// Style for the border of the focused panel.
+    pub fn focused(&self) -> Style {
+        Style::new().fg(self.border).add_modifier(Modifier::BOLD)
+    }
+
+    /// Style for the status bar.
+    pub fn status_bar(&self) -> Style {
+        Style::new().fg(self.status_fg).bg(self.status_bg)
+    }
+
+    /// Style for the top help line.
+    pub fn header(&self) -> Style {
+        Style::new().fg(self.accent).add_modifier(Modifier::BOLD)
+    }
+
+    fn slot_mut(&mut self, key: &str) -> Option<&mut Color> {
+        let slot = match key {
+            "border" => &mut self.border,
+            "accent" => &mut self.accent,
+            "heading" => &mut self.heading,
+            "selection_fg" => &mut self.selection_fg,
+            "selection_bg" => &mut self.selection_bg,
+            "error" => &mut self.error,
+            "success" => &mut self.success,
+            "warning" => &mut self.warning,
+            "dim" => &mut self.dim,
+            "backdrop" => &mut self.backdrop,
+            "status_fg" => &mut self.status_fg,
+            "status_bg" => &mut self.status_bg,
+            "directory" => &mut self.directory,
+            "archive" => &mut self.archive,
+            "media" => &mut self.media,
+            "code" => &mut self.code,
+            _ => return None,
+        };
+        Some(slot)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn colors(pairs: &[(&str, &str)]) -> BTreeMap<String, String> {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_empty_table_is_default() {
+        assert_eq!(
+            Theme::from_config(None, &BTreeMap::new()).unwrap(),
+            Theme::default()
+        );
+    }
+
+    #[test]
+    fn test_overrides_named_index_and_hex_colors() {
+        let theme = Theme::from_config(
+            None,
+            &colors(&[("border", "magenta"), ("dim", "245"), ("error", "#ff8800")]),
+        )
+        .unwrap();
+        assert_eq!(theme.border, Color::Magenta);
+        assert_eq!(theme.dim, Color::Indexed(245));
+        assert_eq!(theme.error, Color::Rgb(0xff, 0x88, 0x00));
+        assert_eq!(theme.accent, Theme::default().accent);
+    }
+
+    #[test]
+    fn test_rejects_unknown_key() {
+        let err = Theme::from_config(None, &colors(&[("borders", "red")])).unwrap_err();
+        assert!(err.to_string().contains("borders"));
+    }
+
+    #[test]
+    fn test_rejects_invalid_color() {
+        assert!(Theme::from_config(None, &colors(&[("border", "reddish")])).is_err());
+    }
+
+    #[test]
+    fn test_preset_names_round_trip() {
+        for preset in ThemePreset::ALL {
+            assert_eq!(ThemePreset::from_name(preset.name()), Some(preset));
+        }
+    }
+
+    #[test]
+    fn test_presets_are_distinct() {
+        for (i, a) in ThemePreset::ALL.iter().enumerate() {
+            for b in &ThemePreset::ALL[i + 1..] {
+                assert_ne!(a.theme(), b.theme(), "{:?} and {:?}", a, b);
+            }
+        }
+    }
+
+    #[test]
+    fn test_config_preset_is_base_for_overrides() {
+        let theme =
+            Theme::from_config(None, &colors(&[("preset", "gruvbox"), ("border", "red")])).unwrap();
+        assert_eq!(theme.border, Color::Red);
+        assert_eq!(theme.heading, ThemePreset::Gruvbox.theme().heading);
+    }
+
+    #[test]
+    fn test_flag_preset_wins_over_config() {
+        let theme = Theme::from_config(Some(ThemePreset::Light), &colors(&[("preset", "gruvbox")]))
+            .unwrap();
+        assert_eq!(theme, ThemePreset::Light.theme());
+    }
+
+    #[test]
+    fn test_rejects_unknown_preset() {
+        let err = Theme::from_config(None, &colors(&[("preset", "neon")])).unwrap_err();
+        assert!(err.to_string().contains("neon"));
+    }
+
+    #[test]
+    fn test_symbols_follow_the_preset_unless_set() {
+        let theme = Theme::from_config(Some(ThemePreset::Colorblind), &BTreeMap::new()).unwrap();
+        assert!(theme.symbols);
+        assert_eq!(theme.highlight_symbol(), "> ");
+        assert_eq!(theme.button("Yes", true), "> Yes <");
+        assert_eq!(theme.button("No", false), " No ");
+
+        let theme = Theme::from_config(None, &colors(&[("symbols", "on")])).unwrap();
+        assert!(theme.symbols);
+        let theme = Theme::from_config(
+            None,
+            &colors(&[("preset", "high-contrast"), ("symbols", "off")]),
+        )
+        .unwrap();
+        assert_eq!(theme.button("Yes", true), " Yes ");
+        assert!(Theme::from_config(None, &colors(&[("symbols", "yes")])).is_err());
+    }
+
+    #[test]
+    fn test_styles_use_theme_colors() {
+        let theme = Theme {
+            selection_bg: Color::Green,
+            ..Theme::default()
+        };
+        assert_eq!(theme.selected().bg, Some(Color::Green));
+    }
+}
@@ -0,0 +1,502 @@
+//! Batch rename of the selection: find and replace over the names,
+//! with regex capture groups and counters, previewed before anything
+//! is moved.
+
+use crate::ui::Theme;
+use crate::ui::layout::modal_area;
+use crate::ui::text;
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::Style,
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+};
+use regex::Regex;
+use std::collections::{HashMap, HashSet};
+
+/// Input fields of the batch rename form.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RenameField {
+    /// Text or pattern to look for.
+    Find,
+    /// What it is replaced with.
+    Replace,
+}
+
+/// One entry's current and new name.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RenamePair {
+    /// Current name.
+    pub old: String,
+    /// Name after the rename.
+    pub new: String,
+    /// Why the new name cannot be used, if it cannot.
+    pub problem: Option<&'static str>,
+}
+
+impl RenamePair {
+    /// Returns whether the rule changes this entry's name.
+    pub fn changed(&self) -> bool {
+        self.old != self.new
+    }
+}
+
+/// Batch rename form state.
+#[derive(Debug, Clone)]
+pub struct BatchRename {
+    /// Remote the entries are on.
+    pub remote: String,
+    /// Directory holding the entries.
+    pub dir: String,
+    /// Names being renamed, in listing order, which is the order
+    /// counters follow.
+    pub names: Vec<String>,
+    /// Names of the other entries in the directory, which new names
+    /// must not take.
+    pub others: Vec<String>,
+    /// Names among `names` that are directories, which are renamed
+    /// with a directory move.
+    pub dirs: HashSet<String>,
+    /// Text or pattern to look for.
+    pub find: String,
+    /// Replacement, with `{n}` counters and, for a regex, `$1`
+    /// capture groups.
+    pub replace: String,
+    /// Whether `find` is a regular expression.
+    pub regex: bool,
+    /// Field being typed into.
+    pub focus: RenameField,
+    /// Old and new names, or why the pattern is invalid.
+    pub preview: Result<Vec<RenamePair>, String>,
+    /// Index of the first visible preview row.
+    pub scroll: usize,
+    /// Whether the renames are in flight.
+    pub waiting: bool,
+    /// Why the renames cannot start.
+    pub error: Option<String>,
+}
+
+impl BatchRename {
+    /// Creates the form for renaming `names` in `dir`, next to the
+    /// entries named `others`.
+    pub fn new(remote: String, dir: String, names: Vec<String>, others: Vec<String>) -> Self {
+        let mut view = Self {
+            remote,
+            dir,
+            names,
+            others,
+            dirs: HashSet::new(),
+            find: String::new(),
+            replace: String::new(),
+            regex: false,
+            focus: RenameField::Find,
+            preview: Ok(Vec::new()),
+            scroll: 0,
+            waiting: false,
+            error: None,
+        };
+        view.refresh();
+        view
+    }
+
+    /// Marks the entries named `dirs` as directories.
+    pub fn with_dirs(mut self, dirs: impl IntoIterator<Item = String>) -> Self {
+        self.dirs = dirs.into_iter().collect();
+        self
+    }
+
+    pub fn input_char(&mut self, c: char) {
+        if c.is_control() {
+            return;
+        }
+        self.field_mut().push(c);
+        self.refresh();
+    }
+
+    pub fn backspace(&mut self) {
+        text::pop_grapheme(self.field_mut());
+        self.refresh();
+    }
+
+    pub fn toggle_focus(&mut self) {
+        self.focus = match self.focus {
+            RenameField::Find => RenameField::Replace,
+            RenameField::Replace => RenameField::Find,
+        };
+    }
+
+    /// Switches between plain text and regular expressions.
+    pub fn toggle_regex(&mut self) {
+        self.regex = !self.regex;
+        self.refresh();
+    }
+
+    /// Scrolls the preview by `delta` rows.
+    pub fn scroll_by(&mut self, delta: isize) {
+        let last = self.names.len().saturating_sub(1);
+        self.scroll = self.scroll.saturating_add_signed(delta).min(last);
+    }
+
+    /// Returns the `(old, new)` names to rename with whether each is a
+    /// directory, skipping unchanged ones, or why the rule cannot be
+    /// applied.
+    pub fn renames(&self) -> Result<Vec<(String, String, bool)>, String> {
+        let pairs = self.preview.as_ref().map_err(Clone::clone)?;
+        if let Some(pair) = pairs.iter().find(|pair| pair.problem.is_some()) {
+            return Err(format!(
+                "'{}': {}",
+                pair.new,
+                pair.problem.unwrap_or_default()
+            ));
+        }
+        let renames: Vec<_> = pairs
+            .iter()
+            .filter(|pair| pair.changed())
+            .map(|pair| {
+                let is_dir = self.dirs.contains(&pair.old);
+                (pair.old.clone(), pair.new.clone(), is_dir)
+            })
+            .collect();
+        if renames.is_empty() {
+            return Err("The rule changes no names".to_string());
+        }
+        Ok(renames)
+    }
+
+    fn field_mut(&mut self) -> &mut String {
+        self.error = None;
+        match self.focus {
+            RenameField::Find => &mut self.find,
+            RenameField::Replace => &mut self.replace,
+        }
+    }
+
+    fn refresh(&mut self) {
+        self.preview = preview(
+            &self.names,
+            &self.others,
+            &self.find,
+            &self.replace,
+            self.regex,
+        );
+    }
+}
+
+/// Applies the rule to every name in `names`: each match of `find`
+/// becomes `replace`, after the `{n}` counters in it are expanded.
+/// New names that are empty, contain `/`, repeat, or take the name
+/// of another entry are flagged.
+///
+/// # Errors
+/// Returns why `find` is not a valid regular expression.
+pub fn preview(
+    names: &[String],
+    others: &[String],
+    find: &str,
+    replace: &str,
+    regex: bool,
+) -> Result<Vec<RenamePair>, String> {
+    let pattern = if regex && !find.is_empty() {
+        Some(Regex::new(find).map_err(|e| {
+            // The message spans lines, pointing at the bad part.
+            e.to_string()
+                .lines()
+                .rev()
+                .find(|line| !line.trim().is_empty())
+                .unwrap_or("invalid pattern")
+                .trim()
+                .to_string()
+        })?)
+    } else {
+        None
+    };
+    let new_names: Vec<String> = names
+        .iter()
+        .enumerate()
+        .map(|(i, name)| {
+            let replacement = expand_counter(replace, i + 1);
+            match (&pattern, find.is_empty()) {
+                (_, true) => name.clone(),
+                (Some(pattern), _) => pattern.replace_all(name, replacement.as_str()).into_owned(),
+                (None, _) => name.replace(find, &replacement),
+            }
+        })
+        .collect();
+
+    let mut counts: HashMap<&str, usize> = HashMap::new();
+    for name in &new_names {
+        *counts.entry(name).or_default() += 1;
+    }
+    Ok(names
+        .iter()
+        .zip(&new_names)
+        .map(|(old, new)| {
+            let taken = || others.iter().chain(names).any(|name| name == new);
+            let problem = if new.is_empty() {
+                Some("empty name")
+            } else if new.contains('/') {
+                Some("names cannot contain '/'")
+            } else if counts.get(new.as_str()).copied().unwrap_or(0) > 1 {
+                Some("duplicate name")
+            } else if old != new && taken() {
+                Some("already exists")
+            } else {
+                None
+            };
+            RenamePair {
+                old: old.clone(),
+                new: new.clone(),
+                problem,
+            }
+        })
+        .collect())
+}
+
+/// Replaces `{n}` in `template` with `n`, and `{n:W}` with `n`
+/// zero-padded to `W` digits.
+pub fn expand_counter(template: &str, n: usize) -> String {
+    let mut out = String::new();
+    let mut rest = template;
+    while let Some(start) = rest.find("{n") {
+        out.push_str(&rest[..start]);
+        let tail = &rest[start + 2..];
+        let counter = if let Some(after) = tail.strip_prefix('}') {
+            Some((0, after))
+        } else if let Some(spec) = tail.strip_prefix(':')
+            && let Some(end) = spec.find('}')
+            && let Ok(width) = spec[..end].parse::<usize>()
+        {
+            Some((width, &spec[end + 1..]))
+        } else {
+            None
+        };
+        match counter {
+            Some((width, after)) => {
+                out.push_str(&format!("{:0width$}", n, width = width));
+                rest = after;
+            }
+            None => {
+                out.push_str("{n");
+                rest = tail;
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Widget for the batch rename form.
+pub struct BatchRenameWidget;
+
+impl BatchRenameWidget {
+    pub fn render(f: &mut Frame, area: Rect, view: &BatchRename, theme: &Theme) {
+        f.render_widget(Clear, area);
+        f.render_widget(
+            Block::default().style(Style::default().bg(theme.backdrop)),
+            area,
+        );
+
+        let modal_area = modal_area(area, 76, 24);
+        f.render_widget(Clear, modal_area);
+        f.render_widget(
+            Block::default()
+                .title(format!(" Batch Rename ({} entries) ", view.names.len()))
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(theme.border)),
+            modal_area,
+        );
+
+        let inner = Rect {
+            x: modal_area.x + 1,
+            y: modal_area.y + 1,
+            width: modal_area.width.saturating_sub(2),
+            height: modal_area.height.saturating_sub(2),
+        };
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(3),
+                Constraint::Length(3),
+                Constraint::Length(1),
+                Constraint::Min(1),
+                Constraint::Length(1),
+                Constraint::Length(1),
+            ])
+            .split(inner);
+
+        let field = |title: &'static str, text: &str, focused: bool| {
+            let border = if focused { theme.accent } else { theme.border };
+            Paragraph::new(format!(" {} ", text))
+                .style(Style::default().fg(theme.accent))
+                .block(
+                    Block::default()
+                        .title(title)
+                        .borders(Borders::ALL)
+                        .border_style(Style::default().fg(border)),
+                )
+        };
+        f.render_widget(
+            field(" Find ", &view.find, view.focus == RenameField::Find),
+            chunks[0],
+        );
+        f.render_widget(
+            field(
+                " Replace with ",
+                &view.replace,
+                view.focus == RenameField::Replace,
+            ),
+            chunks[1],
+        );
+        let check = if view.regex { "x" } else { " " };
+        f.render_widget(
+            Paragraph::new(format!(
+                "[{}] Regex ($1 = capture group)   {{n}} / {{n:3}} = counter",
+                check
+            ))
+            .style(Style::default().fg(theme.dim)),
+            chunks[2],
+        );
+
+        let rows = usize::from(chunks[3].height);
+        let lines: Vec<Line> = match view.preview {
+            Err(ref message) => vec![Line::styled(
+                format!("Invalid pattern: {}", message),
+                Style::default().fg(theme.error),
+            )],
+            Ok(ref pairs) => pairs
+                .iter()
+                .skip(view.scroll)
+                .take(rows)
+                .map(|pair| {
+                    let new_style = match (pair.problem, pair.changed()) {
+                        (Some(_), _) => Style::default().fg(theme.error),
+                        (None, true) => Style::default().fg(theme.accent),
+                        (None, false) => Style::default().fg(theme.dim),
+                    };
+                    let mut spans = vec![
+                        Span::raw(pair.old.clone()),
+                        Span::styled(" → ", Style::default().fg(theme.dim)),
+                        Span::styled(pair.new.clone(), new_style),
+                    ];
+                    if let Some(problem) = pair.problem {
+                        spans.push(Span::styled(
+                            format!("  ({})", problem),
+                            Style::default().fg(theme.error),
+                        ));
+                    }
+                    Line::from(spans)
+                })
+                .collect(),
+        };
+        f.render_widget(Paragraph::new(lines), chunks[3]);
+
+        if let Some(ref error) = view.error {
+            f.render_widget(
+                Paragraph::new(error.as_str()).style(Style::default().fg(theme.error)),
+                chunks[4],
+            );
+        }
+        let (help, style) = if view.waiting {
+            (
+                "Renaming... | Esc: close",
+                Style::default().fg(theme.warning),
+            )
+        } else {
+            (
+                "Tab: Field | Ctrl-r: Regex | Up/Down: Scroll | Enter: Rename | Esc: Cancel",
+                Style::default().fg(theme.dim),
+            )
+        };
+        f.render_widget(Paragraph::new(help).style(style), chunks[5]);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names(names: &[&str]) -> Vec<String> {
+        names.iter().map(ToString::to_string).collect()
+    }
+
+    fn new_names(pairs: &[RenamePair]) -> Vec<&str> {
+        pairs.iter().map(|pair| pair.new.as_str()).collect()
+    }
+
+    #[test]
+    fn test_plain_text_replaces_every_match() {
+        let pairs = preview(&names(&["a-a.txt", "b.txt"]), &[], "-a", "_b", false).unwrap();
+        assert_eq!(new_names(&pairs), ["a_b.txt", "b.txt"]);
+        assert!(!pairs[1].changed());
+    }
+
+    #[test]
+    fn test_regex_with_groups_and_counter() {
+        let pairs = preview(
+            &names(&["IMG_1.jpg", "IMG_2.jpg"]),
+            &[],
+            r"^IMG_\d+\.(\w+)$",
+            "holiday-{n:03}.$1",
+            true,
+        )
+        .unwrap();
+        assert_eq!(new_names(&pairs), ["holiday-001.jpg", "holiday-002.jpg"]);
+        assert!(preview(&names(&["a"]), &[], "(", "", true).is_err());
+    }
+
+    #[test]
+    fn test_flags_unusable_names() {
+        let pairs = preview(
+            &names(&["a.txt", "b.txt", "c.txt"]),
+            &names(&["x.txt"]),
+            ".txt",
+            "",
+            false,
+        )
+        .unwrap();
+        assert!(pairs.iter().all(|pair| pair.problem.is_none()));
+
+        let pairs = preview(&names(&["a", "b"]), &[], "a", "b", false).unwrap();
+        assert_eq!(pairs[0].problem, Some("duplicate name"));
+
+        let pairs = preview(&names(&["a"]), &names(&["b"]), "a", "b", false).unwrap();
+        assert_eq!(pairs[0].problem, Some("already exists"));
+
+        let pairs = preview(&names(&["a"]), &[], "a", "x/y", false).unwrap();
+        assert!(pairs[0].problem.is_some());
+    }
+
+    #[test]
+    fn test_counter_expansion() {
+        assert_eq!(expand_counter("f{n}", 7), "f7");
+        assert_eq!(expand_counter("{n:4}-{n}", 12), "0012-12");
+        assert_eq!(expand_counter("{name}{n:x}", 1), "{name}{n:x}");
+    }
+
+    #[test]
+    fn test_renames_skip_unchanged() {
+        let mut view = BatchRename::new(
+            "gdrive".to_string(),
+            "docs".to_string(),
+            names(&["a.txt", "b.md", "c.txt"]),
+            Vec::new(),
+        )
+        .with_dirs(["c.txt".to_string()]);
+        assert!(view.renames().is_err());
+        for c in ".txt".chars() {
+            view.input_char(c);
+        }
+        view.toggle_focus();
+        for c in ".md".chars() {
+            view.input_char(c);
+        }
+        assert_eq!(
+            view.renames(),
+            Ok(vec![
+                ("a.txt".to_string(), "a.md".to_string(), false),
+                ("c.txt".to_string(), "c.md".to_string(), true),
+            ])
+        );
+    }
+}
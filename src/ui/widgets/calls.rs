@@ -0,0 +1,107 @@
+//! RC call overlay: the last requests sent to the daemon and how long
+//! each took. A slow `core/version` points at the daemon itself; a
+//! fast one next to slow listings points at the backend.
+
+use crate::rclone::Call;
+use crate::rclone::commands::CORE_VERSION;
+use crate::ui::Theme;
+use ratatui::{
+    Frame,
+    layout::Rect,
+    style::Style,
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+};
+use std::time::Duration;
+
+/// Width of the overlay, borders included.
+const WIDTH: u16 = 48;
+
+/// Calls slower than this are highlighted.
+const SLOW: Duration = Duration::from_secs(1);
+
+/// Returns a call's duration as `842ms` or `3.1s`.
+fn format_latency(duration: Duration) -> String {
+    if duration < Duration::from_secs(1) {
+        format!("{}ms", duration.as_millis())
+    } else {
+        format!("{:.1}s", duration.as_secs_f64())
+    }
+}
+
+/// Widget for the RC call overlay.
+pub struct CallsWidget;
+
+impl CallsWidget {
+    /// Render the overlay in the bottom-right corner of `area`.
+    ///
+    /// # Arguments
+    /// * `f` - Frame for rendering
+    /// * `area` - Area the overlay sits in
+    /// * `calls` - Recorded calls, newest first
+    /// * `theme` - Colors to draw with
+    pub fn render(f: &mut Frame, area: Rect, calls: &[Call], theme: &Theme) {
+        let width = WIDTH.min(area.width);
+        let height = u16::try_from(calls.len() + 3)
+            .unwrap_or(u16::MAX)
+            .min(area.height);
+        let spot = Rect {
+            x: area.right().saturating_sub(width),
+            y: area.bottom().saturating_sub(height),
+            width,
+            height,
+        };
+
+        let ping = calls
+            .iter()
+            .find(|call| call.endpoint == CORE_VERSION)
+            .map_or_else(|| "-".to_string(), |call| format_latency(call.duration));
+        let mut lines = vec![Line::styled(
+            format!("daemon ping {}", ping),
+            Style::default().fg(theme.dim),
+        )];
+        let endpoint_width = usize::from(width.saturating_sub(2)).saturating_sub(14);
+        lines.extend(calls.iter().map(|call| {
+            let (status, color) = match call.status {
+                Some(code) if code < 400 => (code.to_string(), theme.success),
+                Some(code) => (code.to_string(), theme.error),
+                None => ("---".to_string(), theme.error),
+            };
+            let latency = if call.duration >= SLOW {
+                Style::default().fg(theme.warning)
+            } else {
+                Style::default()
+            };
+            Line::from(vec![
+                Span::raw(format!(
+                    "{:<endpoint_width$.endpoint_width$} ",
+                    call.endpoint
+                )),
+                Span::styled(format!("{:>8} ", format_latency(call.duration)), latency),
+                Span::styled(status, Style::default().fg(color)),
+            ])
+        }));
+
+        f.render_widget(Clear, spot);
+        f.render_widget(
+            Paragraph::new(lines).block(
+                Block::default()
+                    .title(" RC calls (F12) ")
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(theme.border)),
+            ),
+            spot,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_formats_latency() {
+        assert_eq!(format_latency(Duration::from_millis(842)), "842ms");
+        assert_eq!(format_latency(Duration::from_millis(3140)), "3.1s");
+    }
+}
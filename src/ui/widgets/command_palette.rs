@@ -0,0 +1,398 @@
+//! Command palette widget.
+
+use crate::app::{Action, Keymap, fuzzy};
+use crate::ui::layout::modal_area;
+use crate::ui::text;
+use crate::ui::{Theme, ThemePreset};
+use ratatui::{
+    Frame,
+    layout::{Position, Rect},
+    style::Style,
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph},
+};
+
+/// Maximum number of matches shown at once.
+const MAX_VISIBLE: usize = 10;
+
+/// An entry the command palette can run.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PaletteItem {
+    /// Run an action, as if its key had been pressed.
+    Action(Action),
+    /// Switch to a built-in theme.
+    Theme(ThemePreset),
+    /// Connect to the daemon of a configured profile.
+    Profile(String),
+    /// Run a user script.
+    Script(String),
+    /// Run a shell command from the config file.
+    Command(String),
+}
+
+impl PaletteItem {
+    /// Every entry: actions, then themes, then the palette's
+    /// profiles, scripts and commands. The palette itself is omitted.
+    fn all(palette: &CommandPalette) -> impl Iterator<Item = Self> + '_ {
+        Action::ALL
+            .into_iter()
+            .filter(|action| *action != Action::CommandPalette)
+            .map(Self::Action)
+            .chain(ThemePreset::ALL.into_iter().map(Self::Theme))
+            .chain(palette.profiles.iter().cloned().map(Self::Profile))
+            .chain(palette.scripts.iter().cloned().map(Self::Script))
+            .chain(palette.commands.iter().cloned().map(Self::Command))
+    }
+
+    /// Returns the text shown in the list.
+    pub fn label(&self) -> String {
+        match self {
+            Self::Action(action) => action.description().to_string(),
+            Self::Theme(preset) => format!("Theme: {}", preset.label()),
+            Self::Profile(name) => format!("Profile: {}", name),
+            Self::Script(name) => format!("Script: {}", name),
+            Self::Command(name) => format!("Run: {}", name),
+        }
+    }
+
+    /// Best fuzzy score of `query` against the label and identifier.
+    fn score(&self, query: &str) -> Option<i64> {
+        let name = match self {
+            Self::Action(action) => action.name(),
+            Self::Theme(preset) => preset.name(),
+            Self::Profile(name) | Self::Script(name) | Self::Command(name) => name,
+        };
+        fuzzy::score(query, &self.label()).max(fuzzy::score(query, name))
+    }
+}
+
+/// State for the command palette.
+#[derive(Debug, Clone, Default)]
+pub struct CommandPalette {
+    /// Text typed so far.
+    pub query: String,
+    /// Index into the current matches.
+    pub selected: usize,
+    /// Connection profiles offered for switching.
+    pub profiles: Vec<String>,
+    /// Profile in use, marked as current.
+    pub current_profile: Option<String>,
+    /// User scripts offered for running.
+    pub scripts: Vec<String>,
+    /// Shell commands from the config file offered for running.
+    pub commands: Vec<String>,
+}
+
+impl CommandPalette {
+    /// Create an empty command palette.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create an empty command palette that also offers switching to
+    /// `profiles`.
+    pub fn with_profiles(profiles: Vec<String>, current_profile: Option<String>) -> Self {
+        Self {
+            profiles,
+            current_profile,
+            ..Self::default()
+        }
+    }
+
+    /// Appends a character to the query and selects the best match.
+    pub fn push_char(&mut self, c: char) {
+        self.query.push(c);
+        self.selected = 0;
+    }
+
+    /// Removes the last query character and selects the best match.
+    pub fn pop_char(&mut self) {
+        text::pop_grapheme(&mut self.query);
+        self.selected = 0;
+    }
+
+    /// Selects the next match, wrapping to the first.
+    pub fn select_next(&mut self) {
+        let len = self.matches().len();
+        if len > 0 {
+            self.selected = (self.selected + 1) % len;
+        }
+    }
+
+    /// Selects the previous match, wrapping to the last.
+    pub fn select_prev(&mut self) {
+        let len = self.matches().len();
+        if len > 0 {
+            self.selected = (self.selected + len - 1) % len;
+        }
+    }
+
+    /// Returns the entries matching the query, best first.
+    ///
+    /// Ties keep actions in [`Action::ALL`] order, then themes, then
+    /// profiles, scripts and commands.
+    pub fn matches(&self) -> Vec<PaletteItem> {
+        let mut scored: Vec<(i64, PaletteItem)> = PaletteItem::all(self)
+            .filter_map(|item| item.score(&self.query).map(|score| (score, item)))
+            .collect();
+        scored.sort_by_key(|(score, _)| std::cmp::Reverse(*score));
+        scored.into_iter().map(|(_, item)| item).collect()
+    }
+
+    /// Returns the highlighted entry, if anything matches.
+    pub fn selected_item(&self) -> Option<PaletteItem> {
+        self.matches().into_iter().nth(self.selected)
+    }
+}
+
+/// Widget for rendering the command palette.
+pub struct CommandPaletteWidget;
+
+impl CommandPaletteWidget {
+    /// Render the command palette near the top of `area`.
+    ///
+    /// # Arguments
+    /// * `f` - Frame for rendering
+    /// * `area` - Area to place the palette in
+    /// * `palette` - Palette state
+    /// * `keymap` - Bindings shown next to each action
+    /// * `theme` - Colors to draw with; the matching preset is marked
+    ///   as current
+    pub fn render(
+        f: &mut Frame,
+        area: Rect,
+        palette: &CommandPalette,
+        keymap: &Keymap,
+        theme: &Theme,
+    ) {
+        let matches = palette.matches();
+        let (modal_area, list_area) = Self::layout(area, matches.len());
+
+        f.render_widget(Clear, modal_area);
+        let block = Block::default()
+            .title(" Command Palette ")
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme.border));
+        let inner = block.inner(modal_area);
+        f.render_widget(block, modal_area);
+        if inner.height == 0 {
+            return;
+        }
+
+        let prompt = Line::from(vec![
+            Span::styled(": ", Style::default().fg(theme.heading)),
+            Span::raw(palette.query.as_str()),
+            Span::styled("_", Style::default().fg(theme.heading)),
+        ]);
+        f.render_widget(Paragraph::new(prompt), Rect { height: 1, ..inner });
+
+        if matches.is_empty() {
+            let empty = Paragraph::new(Span::styled(
+                "No matching commands",
+                Style::default().fg(theme.dim),
+            ));
+            f.render_widget(empty, list_area);
+            return;
+        }
+
+        let key_style = Style::default().fg(theme.dim);
+        let items: Vec<ListItem> = matches
+            .iter()
+            .map(|item| {
+                let hint = match item {
+                    PaletteItem::Action(action) => keymap
+                        .keys_for(*action)
+                        .map(ToString::to_string)
+                        .collect::<Vec<_>>()
+                        .join(" / "),
+                    PaletteItem::Theme(preset) if preset.theme() == *theme => "current".to_string(),
+                    PaletteItem::Theme(_) => String::new(),
+                    PaletteItem::Profile(name)
+                        if palette.current_profile.as_deref() == Some(name.as_str()) =>
+                    {
+                        "current".to_string()
+                    }
+                    PaletteItem::Profile(_) | PaletteItem::Script(_) | PaletteItem::Command(_) => {
+                        String::new()
+                    }
+                };
+                ListItem::new(Line::from(vec![
+                    Span::raw(item.label()),
+                    Span::styled(format!("  {}", hint), key_style),
+                ]))
+            })
+            .collect();
+        let list = List::new(items)
+            .highlight_style(theme.selected())
+            .highlight_symbol(theme.highlight_symbol());
+        let offset = Self::offset(palette.selected, list_area.height);
+        let mut state = ListState::default()
+            .with_offset(offset)
+            .with_selected(Some(palette.selected));
+        f.render_stateful_widget(list, list_area, &mut state);
+    }
+
+    /// Returns the index of the match at a screen position, if any.
+    ///
+    /// `area` must be the same area passed to [`Self::render`].
+    pub fn index_at(area: Rect, palette: &CommandPalette, column: u16, row: u16) -> Option<usize> {
+        let len = palette.matches().len();
+        let (_, list_area) = Self::layout(area, len);
+        if !list_area.contains(Position::new(column, row)) {
+            return None;
+        }
+        let offset = Self::offset(palette.selected, list_area.height);
+        let index = offset + usize::from(row - list_area.y);
+        (index < len).then_some(index)
+    }
+
+    /// Returns the modal area and the area of its match list.
+    fn layout(area: Rect, match_count: usize) -> (Rect, Rect) {
+        let list_rows = match_count.clamp(1, MAX_VISIBLE);
+        let wanted_height = u16::try_from(list_rows + 3).unwrap_or(u16::MAX);
+
+        // Near the top, so the list grows downwards as matches appear.
+        let mut modal_area = modal_area(area, 60, wanted_height);
+        modal_area.y = (area.y + area.height / 6).min(area.bottom() - modal_area.height);
+        // Inside the border, below the one-row prompt.
+        let list_area = Rect {
+            x: modal_area.x + 1,
+            y: modal_area.y + 2,
+            width: modal_area.width.saturating_sub(2),
+            height: modal_area.height.saturating_sub(3),
+        };
+        (modal_area, list_area)
+    }
+
+    /// First visible match, scrolled just far enough to show the
+    /// selection.
+    fn offset(selected: usize, rows: u16) -> usize {
+        selected.saturating_sub(usize::from(rows.max(1)) - 1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn palette(query: &str) -> CommandPalette {
+        CommandPalette {
+            query: query.to_string(),
+            ..CommandPalette::default()
+        }
+    }
+
+    #[test]
+    fn test_empty_query_lists_every_other_action_and_theme() {
+        let matches = palette("").matches();
+        assert_eq!(
+            matches.len(),
+            Action::ALL.len() - 1 + ThemePreset::ALL.len()
+        );
+        assert!(!matches.contains(&PaletteItem::Action(Action::CommandPalette)));
+    }
+
+    #[test]
+    fn test_query_filters_and_ranks() {
+        let matches = palette("add rem").matches();
+        assert_eq!(
+            matches.first(),
+            Some(&PaletteItem::Action(Action::AddRemote))
+        );
+        assert!(!matches.contains(&PaletteItem::Action(Action::Quit)));
+    }
+
+    #[test]
+    fn test_matches_config_names() {
+        assert_eq!(
+            palette("half_page_up").selected_item(),
+            Some(PaletteItem::Action(Action::HalfPageUp))
+        );
+    }
+
+    #[test]
+    fn test_matches_themes() {
+        assert_eq!(
+            palette("theme gruv").selected_item(),
+            Some(PaletteItem::Theme(ThemePreset::Gruvbox))
+        );
+    }
+
+    #[test]
+    fn test_matches_profiles() {
+        let mut p = CommandPalette::with_profiles(
+            vec!["nas".to_string(), "vps".to_string()],
+            Some("nas".to_string()),
+        );
+        p.query = "profile vps".to_string();
+        assert_eq!(
+            p.selected_item(),
+            Some(PaletteItem::Profile("vps".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_matches_scripts() {
+        let mut p = CommandPalette {
+            scripts: vec!["archive".to_string()],
+            ..CommandPalette::default()
+        };
+        p.query = "script arch".to_string();
+        assert_eq!(
+            p.selected_item(),
+            Some(PaletteItem::Script("archive".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_matches_commands() {
+        let mut p = CommandPalette {
+            commands: vec!["size".to_string()],
+            ..CommandPalette::default()
+        };
+        p.query = "run size".to_string();
+        assert_eq!(
+            p.selected_item(),
+            Some(PaletteItem::Command("size".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_no_match_selects_nothing() {
+        assert_eq!(palette("zzzz").selected_item(), None);
+    }
+
+    #[test]
+    fn test_selection_wraps() {
+        let mut p = palette("remote");
+        let len = p.matches().len();
+        p.select_prev();
+        assert_eq!(p.selected, len - 1);
+        p.select_next();
+        assert_eq!(p.selected, 0);
+    }
+
+    #[test]
+    fn test_index_at_maps_rows_to_matches() {
+        let area = Rect::new(0, 0, 80, 24);
+        let p = palette("");
+        let (_, list_area) = CommandPaletteWidget::layout(area, p.matches().len());
+
+        assert_eq!(
+            CommandPaletteWidget::index_at(area, &p, list_area.x, list_area.y + 2),
+            Some(2)
+        );
+        assert_eq!(CommandPaletteWidget::index_at(area, &p, 0, 0), None);
+    }
+
+    #[test]
+    fn test_typing_resets_selection() {
+        let mut p = palette("");
+        p.select_next();
+        p.push_char('q');
+        assert_eq!(p.selected, 0);
+        p.select_next();
+        p.pop_char();
+        assert_eq!(p.selected, 0);
+    }
+}
@@ -0,0 +1,523 @@
+//! Compare view: two directories side by side, marking files that
+//! exist on one side only or differ in size or hash.
+//!
+//! Both sides are listed recursively and matched by path, so the view
+//! shows what a sync between them would have to transfer.
+
+use super::usage::format_size;
+use crate::error::Result;
+use crate::rclone::TreeEntry;
+use crate::ui::Theme;
+use crate::ui::layout::modal_area;
+use crate::ui::text;
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph},
+};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// How a file on one side relates to the other side.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiffKind {
+    /// Only the left directory has it.
+    LeftOnly,
+    /// Only the right directory has it.
+    RightOnly,
+    /// Both have it, with different sizes.
+    SizeDiffers,
+    /// Both have it with the same size, but different hashes.
+    HashDiffers,
+    /// Both have it, and nothing compared tells them apart.
+    Same,
+}
+
+/// A file of either directory and how it compares.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiffRow {
+    /// Path below both directories.
+    pub path: String,
+    pub kind: DiffKind,
+    /// Size on the left, if the left has it.
+    pub left_size: Option<i64>,
+    /// Size on the right, if the right has it.
+    pub right_size: Option<i64>,
+}
+
+/// Matches the files of two recursive listings by path, sorted by
+/// path. Hashes of type `hash` are compared when both sides have one;
+/// sizes a backend cannot tell never count as different.
+pub fn diff(left: &[TreeEntry], right: &[TreeEntry], hash: Option<&str>) -> Vec<DiffRow> {
+    let files = |entries: &[TreeEntry]| -> BTreeMap<String, TreeEntry> {
+        entries
+            .iter()
+            .filter(|entry| !entry.is_dir)
+            .map(|entry| (entry.path.clone(), entry.clone()))
+            .collect()
+    };
+    let (left, right) = (files(left), files(right));
+    let paths: BTreeSet<&String> = left.keys().chain(right.keys()).collect();
+    paths
+        .into_iter()
+        .map(|path| {
+            let (l, r) = (left.get(path), right.get(path));
+            let kind = match (l, r) {
+                (Some(l), Some(r)) => compare(l, r, hash),
+                (Some(_), None) => DiffKind::LeftOnly,
+                _ => DiffKind::RightOnly,
+            };
+            DiffRow {
+                path: path.clone(),
+                kind,
+                left_size: l.map(|entry| entry.size),
+                right_size: r.map(|entry| entry.size),
+            }
+        })
+        .collect()
+}
+
+fn compare(left: &TreeEntry, right: &TreeEntry, hash: Option<&str>) -> DiffKind {
+    if left.size >= 0 && right.size >= 0 && left.size != right.size {
+        return DiffKind::SizeDiffers;
+    }
+    let hashes = hash.and_then(|kind| Some((left.hash(kind)?, right.hash(kind)?)));
+    match hashes {
+        Some((l, r)) if !l.eq_ignore_ascii_case(r) => DiffKind::HashDiffers,
+        _ => DiffKind::Same,
+    }
+}
+
+/// Splits a `remote:path` location into the remote and the path.
+///
+/// # Errors
+/// Returns why `location` is not one.
+pub fn split_location(location: &str) -> std::result::Result<(String, String), &'static str> {
+    match location.trim().split_once(':') {
+        Some((remote, path)) if !remote.is_empty() => {
+            Ok((remote.to_string(), path.trim_matches('/').to_string()))
+        }
+        _ => Err("Locations must be remote:path"),
+    }
+}
+
+/// Which location of the form is being typed into.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompareSide {
+    Left,
+    Right,
+}
+
+/// Form choosing the two directories to compare.
+#[derive(Debug, Clone)]
+pub struct CompareForm {
+    /// Left directory, as `remote:path`.
+    pub left: String,
+    /// Right directory, as `remote:path`.
+    pub right: String,
+    /// Field being typed into.
+    pub focus: CompareSide,
+    /// Why the form cannot be sent.
+    pub error: Option<String>,
+}
+
+impl CompareForm {
+    /// Creates the form with the left directory filled in and the
+    /// right one focused.
+    pub fn new(left: String, right: String) -> Self {
+        Self {
+            left,
+            right,
+            focus: CompareSide::Right,
+            error: None,
+        }
+    }
+
+    /// Moves the focus to the other field.
+    pub fn toggle_focus(&mut self) {
+        self.focus = match self.focus {
+            CompareSide::Left => CompareSide::Right,
+            CompareSide::Right => CompareSide::Left,
+        };
+    }
+
+    pub fn input_char(&mut self, c: char) {
+        // Bracketed paste can deliver control characters as Char
+        // events; they are never part of a path.
+        if c.is_control() {
+            return;
+        }
+        self.field_mut().push(c);
+        self.error = None;
+    }
+
+    pub fn backspace(&mut self) {
+        text::pop_grapheme(self.field_mut());
+        self.error = None;
+    }
+
+    fn field_mut(&mut self) -> &mut String {
+        match self.focus {
+            CompareSide::Left => &mut self.left,
+            CompareSide::Right => &mut self.right,
+        }
+    }
+}
+
+/// Compare view state.
+#[derive(Debug, Clone, Default)]
+pub struct CompareView {
+    /// Left directory, as `remote:path`.
+    pub left: String,
+    /// Right directory, as `remote:path`.
+    pub right: String,
+    /// Hash type compared, if both backends share one.
+    pub hash: Option<String>,
+    /// Files of both directories, once both listings arrived.
+    pub rows: Vec<DiffRow>,
+    /// Whether the listings are in flight.
+    pub loading: bool,
+    /// Why listing failed, if it did.
+    pub error: Option<String>,
+    /// Index of the highlighted row among those shown.
+    pub selected: usize,
+    /// Whether files that match are listed too.
+    pub show_same: bool,
+    /// Form choosing the directories, while it is open.
+    pub form: Option<CompareForm>,
+}
+
+impl CompareView {
+    /// Creates the view with its form open, the left directory filled
+    /// in with `left`.
+    pub fn new(left: String) -> Self {
+        Self {
+            form: Some(CompareForm::new(left, String::new())),
+            ..Self::default()
+        }
+    }
+
+    /// Starts comparing `left` with `right`, dropping the last result.
+    pub fn start(&mut self, left: String, right: String) {
+        self.left = left;
+        self.right = right;
+        self.form = None;
+        self.loading = true;
+        self.error = None;
+        self.rows.clear();
+        self.hash = None;
+        self.selected = 0;
+    }
+
+    /// Shows a finished comparison.
+    pub fn set_rows(&mut self, hash: Option<String>, result: Result<Vec<DiffRow>>) {
+        self.loading = false;
+        self.hash = hash;
+        match result {
+            Ok(rows) => self.rows = rows,
+            Err(e) => self.error = Some(e.to_string()),
+        }
+        self.selected = 0;
+    }
+
+    /// Returns the rows shown: every file, or only those that differ.
+    pub fn visible_rows(&self) -> Vec<&DiffRow> {
+        self.rows
+            .iter()
+            .filter(|row| self.show_same || row.kind != DiffKind::Same)
+            .collect()
+    }
+
+    /// Lists or hides the files that match.
+    pub fn toggle_same(&mut self) {
+        self.show_same = !self.show_same;
+        self.selected = 0;
+    }
+
+    pub fn select_next(&mut self) {
+        if self.selected + 1 < self.visible_rows().len() {
+            self.selected += 1;
+        }
+    }
+
+    pub fn select_prev(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+    }
+
+    /// Counts the rows of each kind that differs, then the matches:
+    /// left only, right only, different, same.
+    pub fn counts(&self) -> [usize; 4] {
+        let mut counts = [0; 4];
+        for row in &self.rows {
+            let slot = match row.kind {
+                DiffKind::LeftOnly => 0,
+                DiffKind::RightOnly => 1,
+                DiffKind::SizeDiffers | DiffKind::HashDiffers => 2,
+                DiffKind::Same => 3,
+            };
+            counts[slot] += 1;
+        }
+        counts
+    }
+}
+
+/// Widget for the compare view.
+pub struct CompareWidget;
+
+impl CompareWidget {
+    pub fn render(f: &mut Frame, area: Rect, view: &CompareView, theme: &Theme) {
+        f.render_widget(Clear, area);
+        f.render_widget(
+            Block::default().style(Style::default().bg(theme.backdrop)),
+            area,
+        );
+
+        let modal_area = modal_area(area, 100, 28);
+        f.render_widget(Clear, modal_area);
+        f.render_widget(
+            Block::default()
+                .title(" Compare ")
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(theme.border)),
+            modal_area,
+        );
+
+        let inner = Rect {
+            x: modal_area.x + 1,
+            y: modal_area.y + 1,
+            width: modal_area.width.saturating_sub(2),
+            height: modal_area.height.saturating_sub(2),
+        };
+        match view.form {
+            Some(ref form) => Self::render_form(f, inner, form, theme),
+            None => Self::render_result(f, inner, view, theme),
+        }
+    }
+
+    fn render_form(f: &mut Frame, area: Rect, form: &CompareForm, theme: &Theme) {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(3),
+                Constraint::Length(3),
+                Constraint::Min(1),
+                Constraint::Length(1),
+            ])
+            .split(area);
+        for (chunk, side, title, value) in [
+            (chunks[0], CompareSide::Left, " Left ", &form.left),
+            (chunks[1], CompareSide::Right, " Right ", &form.right),
+        ] {
+            let border = if form.focus == side {
+                theme.accent
+            } else {
+                theme.border
+            };
+            f.render_widget(
+                Paragraph::new(format!(" {} ", value)).block(
+                    Block::default()
+                        .title(title)
+                        .borders(Borders::ALL)
+                        .border_style(Style::default().fg(border)),
+                ),
+                chunk,
+            );
+        }
+        if let Some(ref error) = form.error {
+            f.render_widget(
+                Paragraph::new(error.as_str()).style(Style::default().fg(theme.error)),
+                chunks[2],
+            );
+        }
+        f.render_widget(
+            Paragraph::new("Tab: Next field | Enter: Compare | Esc: Close")
+                .style(Style::default().fg(theme.dim)),
+            chunks[3],
+        );
+    }
+
+    fn render_result(f: &mut Frame, area: Rect, view: &CompareView, theme: &Theme) {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(1),
+                Constraint::Length(1),
+                Constraint::Min(1),
+                Constraint::Length(1),
+            ])
+            .split(area);
+        let half = usize::from(area.width.saturating_sub(3) / 2);
+
+        f.render_widget(
+            Paragraph::new(Line::from(vec![
+                Span::styled(
+                    format!("{:<half$}", view.left, half = half),
+                    Style::default().fg(theme.success),
+                ),
+                Span::raw("   "),
+                Span::styled(view.right.as_str(), Style::default().fg(theme.accent)),
+            ])),
+            chunks[0],
+        );
+
+        let [left_only, right_only, differ, same] = view.counts();
+        let compared = match view.hash {
+            Some(ref hash) => format!("size and {}", hash),
+            None => "size".to_string(),
+        };
+        let summary = format!(
+            "{} only left, {} only right, {} differ, {} same (by {})",
+            left_only, right_only, differ, same, compared
+        );
+        let status = match (&view.error, view.loading) {
+            (Some(error), _) => Some((error.clone(), theme.error)),
+            (None, true) => Some(("Listing both sides...".to_string(), theme.dim)),
+            (None, false) if view.visible_rows().is_empty() => {
+                Some(("No differences".to_string(), theme.success))
+            }
+            _ => None,
+        };
+        if !view.loading && view.error.is_none() {
+            f.render_widget(
+                Paragraph::new(summary).style(Style::default().fg(theme.dim)),
+                chunks[1],
+            );
+        }
+        match status {
+            Some((text, color)) => f.render_widget(
+                Paragraph::new(text).style(Style::default().fg(color)),
+                chunks[2],
+            ),
+            None => Self::render_rows(f, chunks[2], view, half, theme),
+        }
+
+        let help = format!(
+            "a: {} same | n: Other paths | r: Refresh | Esc: Close",
+            if view.show_same { "Hide" } else { "Show" }
+        );
+        f.render_widget(
+            Paragraph::new(help).style(Style::default().fg(theme.dim)),
+            chunks[3],
+        );
+    }
+
+    /// Draws each file in the column of the side that has it, with a
+    /// marker between the columns saying how they compare.
+    fn render_rows(f: &mut Frame, area: Rect, view: &CompareView, half: usize, theme: &Theme) {
+        let cell = |size: Option<i64>, path: &str, color: Color| match size {
+            Some(size) => {
+                let size = format_size(u64::try_from(size).unwrap_or(0));
+                let name_width = half.saturating_sub(text::width(&size) + 1);
+                Span::styled(
+                    format!("{} {}", text::pad(path, name_width), size),
+                    Style::default().fg(color),
+                )
+            }
+            None => Span::raw(" ".repeat(half)),
+        };
+        let items: Vec<ListItem> = view
+            .visible_rows()
+            .into_iter()
+            .map(|row| {
+                let (marker, color) = match row.kind {
+                    DiffKind::LeftOnly => (" < ", theme.success),
+                    DiffKind::RightOnly => (" > ", theme.accent),
+                    DiffKind::SizeDiffers | DiffKind::HashDiffers => (" ≠ ", theme.warning),
+                    DiffKind::Same => (" = ", theme.dim),
+                };
+                ListItem::new(Line::from(vec![
+                    cell(row.left_size, &row.path, color),
+                    Span::styled(marker, Style::default().fg(color)),
+                    cell(row.right_size, &row.path, color),
+                ]))
+            })
+            .collect();
+        let mut state = ListState::default().with_selected(Some(view.selected));
+        f.render_stateful_widget(
+            List::new(items)
+                .highlight_style(theme.selected())
+                .highlight_symbol(theme.highlight_symbol()),
+            area,
+            &mut state,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn file(path: &str, size: i64, md5: &str) -> TreeEntry {
+        TreeEntry {
+            path: path.to_string(),
+            size,
+            is_dir: false,
+            hashes: Some(BTreeMap::from([("md5".to_string(), md5.to_string())])),
+        }
+    }
+
+    fn kinds(rows: &[DiffRow]) -> Vec<(&str, DiffKind)> {
+        rows.iter()
+            .map(|row| (row.path.as_str(), row.kind))
+            .collect()
+    }
+
+    #[test]
+    fn test_diff_marks_each_kind() {
+        let left = [
+            file("a.txt", 1, "aa"),
+            file("b.txt", 2, "bb"),
+            file("c.txt", 3, "cc"),
+            file("d.txt", 4, "dd"),
+        ];
+        let right = [
+            file("b.txt", 5, "bb"),
+            file("c.txt", 3, "CC"),
+            file("d.txt", 4, "ee"),
+            file("e.txt", 6, "ee"),
+        ];
+        assert_eq!(
+            kinds(&diff(&left, &right, Some("md5"))),
+            [
+                ("a.txt", DiffKind::LeftOnly),
+                ("b.txt", DiffKind::SizeDiffers),
+                ("c.txt", DiffKind::Same),
+                ("d.txt", DiffKind::HashDiffers),
+                ("e.txt", DiffKind::RightOnly),
+            ]
+        );
+        assert_eq!(diff(&left, &right, None)[3].kind, DiffKind::Same);
+    }
+
+    #[test]
+    fn test_unknown_sizes_and_missing_hashes_match() {
+        let left = [file("doc.gdoc", -1, "")];
+        let right = [file("doc.gdoc", 10, "aa")];
+        assert_eq!(diff(&left, &right, Some("md5"))[0].kind, DiffKind::Same);
+    }
+
+    #[test]
+    fn test_hides_matches_until_asked() {
+        let mut view = CompareView::new("gdrive:".to_string());
+        view.start("gdrive:".to_string(), "s3:".to_string());
+        let left = [file("a.txt", 1, "aa"), file("b.txt", 2, "bb")];
+        let right = [file("a.txt", 1, "aa")];
+        view.set_rows(None, Ok(diff(&left, &right, None)));
+
+        assert_eq!(view.visible_rows().len(), 1);
+        assert_eq!(view.counts(), [1, 0, 0, 1]);
+        view.toggle_same();
+        assert_eq!(view.visible_rows().len(), 2);
+    }
+
+    #[test]
+    fn test_splits_locations() {
+        assert_eq!(
+            split_location(" s3:/backup/ "),
+            Ok(("s3".to_string(), "backup".to_string()))
+        );
+        assert!(split_location("backup").is_err());
+        assert!(split_location(":backup").is_err());
+    }
+}
@@ -0,0 +1,143 @@
+//! Prompt for the password of the daemon's encrypted config file.
+//!
+//! A daemon whose rclone config is encrypted fails every call that
+//! reads it until the password is supplied, so remotes cannot load
+//! before this prompt is answered.
+
+use crate::ui::layout::modal_area;
+use crate::ui::{Edit, Input, Theme, text};
+use crossterm::event::KeyEvent;
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::Style,
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+};
+use std::fmt;
+
+/// Config password prompt state.
+#[derive(Clone, Default)]
+pub struct ConfigPasswordModal {
+    /// Password typed so far.
+    pub input: String,
+    /// Whether the unlock request is in flight.
+    pub waiting: bool,
+    /// Why the last attempt failed, if it did.
+    pub error: Option<String>,
+    /// Cursor in the password.
+    pub cursor: Input,
+}
+
+impl fmt::Debug for ConfigPasswordModal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ConfigPasswordModal")
+            .field("input", &"<redacted>")
+            .field("waiting", &self.waiting)
+            .field("error", &self.error)
+            .field("cursor", &self.cursor)
+            .finish()
+    }
+}
+
+impl ConfigPasswordModal {
+    /// Applies an editing key to the password. Bracketed paste can
+    /// end in a newline; it is never part of the password.
+    pub fn edit(&mut self, key: KeyEvent) {
+        if self.cursor.handle_key(&mut self.input, key) == Edit::Changed {
+            self.error = None;
+        }
+    }
+
+    /// Inserts a pasted password at the cursor.
+    pub fn paste(&mut self, pasted: &str) {
+        if self.cursor.paste(&mut self.input, pasted) {
+            self.error = None;
+        }
+    }
+}
+
+/// Widget for the config password prompt.
+pub struct ConfigPasswordWidget;
+
+impl ConfigPasswordWidget {
+    pub fn render(f: &mut Frame, area: Rect, modal: &ConfigPasswordModal, theme: &Theme) {
+        f.render_widget(Clear, area);
+        f.render_widget(
+            Block::default().style(Style::default().bg(theme.backdrop)),
+            area,
+        );
+
+        let modal_area = modal_area(area, 56, 10);
+        f.render_widget(Clear, modal_area);
+        f.render_widget(
+            Block::default()
+                .title(" Config Password ")
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(theme.border)),
+            modal_area,
+        );
+
+        let inner = Rect {
+            x: modal_area.x + 1,
+            y: modal_area.y + 1,
+            width: modal_area.width.saturating_sub(2),
+            height: modal_area.height.saturating_sub(2),
+        };
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(2),
+                Constraint::Length(3),
+                Constraint::Min(1),
+                Constraint::Length(1),
+            ])
+            .split(inner);
+
+        f.render_widget(
+            Paragraph::new("The daemon's rclone config is encrypted. Enter its password:")
+                .wrap(Wrap { trim: false }),
+            chunks[0],
+        );
+        let masked = "*".repeat(text::graphemes(&modal.input));
+        let width = usize::from(chunks[1].width).saturating_sub(4);
+        let mut spans = vec![Span::raw(" ")];
+        spans.extend(modal.cursor.spans(&masked, width));
+        f.render_widget(
+            Paragraph::new(Line::from(spans))
+                .style(Style::default().fg(theme.accent))
+                .block(Block::default().borders(Borders::ALL)),
+            chunks[1],
+        );
+        if let Some(ref error) = modal.error {
+            f.render_widget(
+                Paragraph::new(error.as_str())
+                    .style(Style::default().fg(theme.error))
+                    .wrap(Wrap { trim: false }),
+                chunks[2],
+            );
+        }
+
+        let (help, style) = if modal.waiting {
+            ("Unlocking...", Style::default().fg(theme.warning))
+        } else {
+            ("Enter: Unlock | Esc: Skip", Style::default().fg(theme.dim))
+        };
+        f.render_widget(Paragraph::new(help).style(style), chunks[3]);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_debug_hides_password() {
+        let mut modal = ConfigPasswordModal::default();
+        for c in "hunter2\n".chars() {
+            modal.edit(KeyEvent::from(crossterm::event::KeyCode::Char(c)));
+        }
+        assert_eq!(modal.input, "hunter2");
+        assert!(!format!("{:?}", modal).contains("hunter2"));
+    }
+}
@@ -0,0 +1,364 @@
+//! Wizard for the questions rclone asks while creating a remote.
+//!
+//! Backends such as Drive, Dropbox and OneDrive cannot be set up from
+//! a name and a type alone: rclone walks through questions, OAuth
+//! sign-in among them. The wizard shows one question at a time and
+//! sends each answer back until the remote is complete.
+
+use crate::rclone::ConfigQuestion;
+use crate::ui::Theme;
+use crate::ui::layout::modal_area;
+use crate::ui::text;
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::Style,
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+};
+
+/// Config wizard state.
+#[derive(Debug, Clone)]
+pub struct ConfigWizard {
+    /// Remote being configured.
+    pub name: String,
+    /// Backend type of the remote.
+    pub remote_type: String,
+    /// Question on screen; `None` until the first one arrives.
+    pub question: Option<ConfigQuestion>,
+    /// Number of questions asked so far.
+    pub step: usize,
+    /// Answer typed or picked so far.
+    pub answer: String,
+    /// Index of the example matching the answer, if any.
+    pub selected: Option<usize>,
+    /// Whether a step is in flight.
+    pub waiting: bool,
+    /// Why the last step failed, if it did.
+    pub error: Option<String>,
+}
+
+impl ConfigWizard {
+    /// Creates the wizard for a remote whose creation was just sent.
+    pub fn new(name: impl Into<String>, remote_type: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            remote_type: remote_type.into(),
+            question: None,
+            step: 0,
+            answer: String::new(),
+            selected: None,
+            waiting: true,
+            error: None,
+        }
+    }
+
+    /// Shows the next question, with its default as the answer.
+    pub fn ask(&mut self, question: ConfigQuestion) {
+        self.step += 1;
+        self.answer = question.option.default.clone();
+        self.error = question.error.clone();
+        self.question = Some(question);
+        self.waiting = false;
+        self.sync_selected();
+    }
+
+    /// Picks the next example as the answer.
+    pub fn select_next(&mut self) {
+        let count = self.examples_len();
+        if count > 0 {
+            self.pick(self.selected.map_or(0, |i| (i + 1) % count));
+        }
+    }
+
+    /// Picks the previous example as the answer.
+    pub fn select_prev(&mut self) {
+        let count = self.examples_len();
+        if count > 0 {
+            self.pick(self.selected.map_or(count - 1, |i| (i + count - 1) % count));
+        }
+    }
+
+    pub fn input_char(&mut self, c: char) {
+        // Bracketed paste can deliver control characters as Char
+        // events; they are never part of an answer.
+        if c.is_control() {
+            return;
+        }
+        self.answer.push(c);
+        self.error = None;
+        self.sync_selected();
+    }
+
+    pub fn backspace(&mut self) {
+        text::pop_grapheme(&mut self.answer);
+        self.error = None;
+        self.sync_selected();
+    }
+
+    /// Returns what the user needs outside the TUI to answer the
+    /// question: an `rclone authorize` command line, or else the
+    /// first URL in its help.
+    pub fn link(&self) -> Option<&str> {
+        let help = &self.question.as_ref()?.option.help;
+        if let Some(line) = help
+            .lines()
+            .find(|l| l.trim().starts_with("rclone authorize"))
+        {
+            return Some(line.trim());
+        }
+        help.split_whitespace()
+            .find(|word| word.starts_with("http://") || word.starts_with("https://"))
+            .map(|url| url.trim_end_matches(['.', ',', ')', '"', '\'']))
+    }
+
+    fn examples_len(&self) -> usize {
+        self.question
+            .as_ref()
+            .map_or(0, |q| q.option.examples().len())
+    }
+
+    fn pick(&mut self, index: usize) {
+        if let Some(example) = self
+            .question
+            .as_ref()
+            .and_then(|q| q.option.examples().get(index))
+        {
+            self.answer = example.value.clone();
+            self.selected = Some(index);
+            self.error = None;
+        }
+    }
+
+    fn sync_selected(&mut self) {
+        self.selected = self.question.as_ref().and_then(|q| {
+            q.option
+                .examples()
+                .iter()
+                .position(|example| example.value == self.answer)
+        });
+    }
+}
+
+/// Widget for the config wizard.
+pub struct ConfigWizardWidget;
+
+impl ConfigWizardWidget {
+    pub fn render(f: &mut Frame, area: Rect, wizard: &ConfigWizard, theme: &Theme) {
+        f.render_widget(Clear, area);
+        f.render_widget(
+            Block::default().style(Style::default().bg(theme.backdrop)),
+            area,
+        );
+
+        let modal_area = modal_area(area, 72, 22);
+        f.render_widget(Clear, modal_area);
+        f.render_widget(
+            Block::default()
+                .title(format!(
+                    " Configure {} ({}) ",
+                    wizard.name, wizard.remote_type
+                ))
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(theme.border)),
+            modal_area,
+        );
+
+        let inner = Rect {
+            x: modal_area.x + 1,
+            y: modal_area.y + 1,
+            width: modal_area.width.saturating_sub(2),
+            height: modal_area.height.saturating_sub(2),
+        };
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Min(1),
+                Constraint::Length(2),
+                Constraint::Length(1),
+            ])
+            .split(inner);
+
+        f.render_widget(
+            Paragraph::new(Self::body(wizard, theme)).wrap(Wrap { trim: false }),
+            chunks[0],
+        );
+
+        let answer = match wizard.question {
+            Some(ref q) if q.option.is_password => "*".repeat(text::graphemes(&wizard.answer)),
+            _ => wizard.answer.clone(),
+        };
+        f.render_widget(
+            Paragraph::new(format!("Answer: {}", answer)).style(Style::default().bold()),
+            chunks[1],
+        );
+
+        let (help, style) = match wizard.error {
+            Some(ref error) => (error.clone(), Style::default().fg(theme.error)),
+            None => (
+                "Up/Down: Choose | Enter: Answer | Ctrl+Y: Copy link | Esc: Cancel".to_string(),
+                Style::default().fg(theme.dim),
+            ),
+        };
+        f.render_widget(Paragraph::new(help).style(style), chunks[2]);
+    }
+
+    /// Lines describing the current question, its examples and what
+    /// the wizard is waiting for.
+    fn body<'a>(wizard: &'a ConfigWizard, theme: &Theme) -> Vec<Line<'a>> {
+        let mut lines = Vec::new();
+        let Some(ref question) = wizard.question else {
+            lines.push(Line::styled(
+                "Contacting rclone...",
+                Style::default().fg(theme.dim),
+            ));
+            return lines;
+        };
+        let option = &question.option;
+
+        lines.push(Line::styled(
+            format!("Step {}: {}", wizard.step, option.name),
+            Style::default().fg(theme.heading).bold(),
+        ));
+        lines.push(Line::default());
+        lines.extend(option.help.lines().map(Line::raw));
+
+        if !option.examples().is_empty() {
+            lines.push(Line::default());
+        }
+        for (i, example) in option.examples().iter().enumerate() {
+            let style = if wizard.selected == Some(i) {
+                theme.selected()
+            } else {
+                Style::default()
+            };
+            lines.push(Line::from(vec![
+                Span::styled(
+                    theme.button(&example.value, wizard.selected == Some(i)),
+                    style,
+                ),
+                Span::styled(
+                    format!(" {}", example.help.lines().next().unwrap_or_default()),
+                    Style::default().fg(theme.dim),
+                ),
+            ]));
+        }
+
+        if let Some(link) = wizard.link() {
+            lines.push(Line::default());
+            lines.push(Line::from(vec![
+                Span::styled("Link: ", Style::default().fg(theme.dim)),
+                Span::styled(link, Style::default().fg(theme.accent)),
+            ]));
+        }
+        if wizard.waiting {
+            lines.push(Line::default());
+            lines.push(Line::styled(
+                "Waiting for rclone... If it opened a browser, finish signing in there.",
+                Style::default().fg(theme.warning),
+            ));
+        }
+        lines
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rclone::{ConfigExample, ConfigOption};
+
+    fn question(help: &str, default: &str) -> ConfigQuestion {
+        ConfigQuestion {
+            state: "*oauth-islocal".to_string(),
+            option: ConfigOption {
+                name: "config_is_local".to_string(),
+                help: help.to_string(),
+                default: default.to_string(),
+                examples: Some(vec![
+                    ConfigExample {
+                        value: "true".to_string(),
+                        help: "Yes".to_string(),
+                        ..ConfigExample::default()
+                    },
+                    ConfigExample {
+                        value: "false".to_string(),
+                        help: "No".to_string(),
+                        ..ConfigExample::default()
+                    },
+                ]),
+                ..ConfigOption::default()
+            },
+            error: None,
+        }
+    }
+
+    #[test]
+    fn test_ask_starts_from_default() {
+        let mut wizard = ConfigWizard::new("gdrive", "drive");
+        assert!(wizard.waiting);
+
+        wizard.ask(question("Use a browser?", "true"));
+        assert!(!wizard.waiting);
+        assert_eq!(wizard.step, 1);
+        assert_eq!(wizard.answer, "true");
+        assert_eq!(wizard.selected, Some(0));
+    }
+
+    #[test]
+    fn test_examples_cycle_into_answer() {
+        let mut wizard = ConfigWizard::new("gdrive", "drive");
+        wizard.ask(question("Use a browser?", "true"));
+
+        wizard.select_next();
+        assert_eq!(wizard.answer, "false");
+        wizard.select_next();
+        assert_eq!(wizard.answer, "true");
+        wizard.select_prev();
+        assert_eq!(wizard.answer, "false");
+    }
+
+    #[test]
+    fn test_typing_tracks_matching_example() {
+        let mut wizard = ConfigWizard::new("gdrive", "drive");
+        wizard.ask(question("Use a browser?", ""));
+        assert_eq!(wizard.selected, None);
+
+        for c in "false".chars() {
+            wizard.input_char(c);
+        }
+        assert_eq!(wizard.selected, Some(1));
+        wizard.backspace();
+        assert_eq!(wizard.selected, None);
+    }
+
+    #[test]
+    fn test_link_prefers_authorize_command() {
+        let mut wizard = ConfigWizard::new("gdrive", "drive");
+        wizard.ask(question(
+            "Execute the following on the machine with the web browser\n\
+             \trclone authorize \"drive\" \"eyJzY29wZSI6ImRyaXZlIn0\"\n\
+             Then paste the result.",
+            "",
+        ));
+        assert_eq!(
+            wizard.link(),
+            Some("rclone authorize \"drive\" \"eyJzY29wZSI6ImRyaXZlIn0\"")
+        );
+    }
+
+    #[test]
+    fn test_link_finds_url_in_help() {
+        let mut wizard = ConfigWizard::new("gdrive", "drive");
+        wizard.ask(question(
+            "Get one at https://rclone.org/drive/#making-your-own-client-id.",
+            "",
+        ));
+        assert_eq!(
+            wizard.link(),
+            Some("https://rclone.org/drive/#making-your-own-client-id")
+        );
+
+        wizard.ask(question("Use a browser?", "true"));
+        assert_eq!(wizard.link(), None);
+    }
+}
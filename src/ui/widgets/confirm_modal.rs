@@ -1,9 +1,17 @@
 //! Confirmation modal widget for delete operations.
-
+//!
+//! A modal can ask for a name to be typed first, as GitHub does
+//! before deleting a repository: Yes stays unavailable until the
+//! typed text matches.
+
+use crate::ui::layout::modal_area;
+use crate::ui::{Edit, Input, Theme};
+use crossterm::event::KeyEvent;
 use ratatui::{
     Frame,
-    layout::{Alignment, Constraint, Direction, Layout, Rect},
-    style::{Color, Style},
+    layout::{Alignment, Constraint, Direction, Layout, Position, Rect},
+    style::Style,
+    text::{Line, Span},
     widgets::{Block, Borders, Clear, Paragraph},
 };
 
@@ -13,6 +21,12 @@ pub struct ConfirmModal {
     pub title: String,
     pub message: String,
     pub selected: ConfirmChoice,
+    /// Text to type before Yes can be chosen, if any.
+    pub expected: Option<String>,
+    /// Text typed so far.
+    pub typed: String,
+    /// Cursor in the typed text.
+    pub cursor: Input,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -27,52 +41,190 @@ impl ConfirmModal {
             title: title.into(),
             message: message.into(),
             selected: ConfirmChoice::No, // Default to No for safety
+            expected: None,
+            typed: String::new(),
+            cursor: Input::default(),
         }
     }
 
+    /// Requires `expected` to be typed before Yes can be chosen.
+    pub fn with_expected(mut self, expected: impl Into<String>) -> Self {
+        self.expected = Some(expected.into());
+        self
+    }
+
+    /// Returns whether Yes can be chosen: nothing needs typing, or
+    /// what was typed matches.
+    pub fn unlocked(&self) -> bool {
+        self.expected
+            .as_ref()
+            .is_none_or(|expected| *expected == self.typed)
+    }
+
     pub fn toggle(&mut self) {
         self.selected = match self.selected {
             ConfirmChoice::Yes => ConfirmChoice::No,
-            ConfirmChoice::No => ConfirmChoice::Yes,
+            ConfirmChoice::No if self.unlocked() => ConfirmChoice::Yes,
+            ConfirmChoice::No => ConfirmChoice::No,
+        };
+    }
+
+    /// Applies an editing key to the typed text. Yes is chosen as soon
+    /// as it matches, and No again when it stops matching.
+    pub fn edit(&mut self, key: KeyEvent) -> Edit {
+        let edit = self.cursor.handle_key(&mut self.typed, key);
+        if edit == Edit::Changed {
+            self.sync_choice();
+        }
+        edit
+    }
+
+    /// Inserts pasted text at the cursor.
+    pub fn paste(&mut self, pasted: &str) {
+        if self.cursor.paste(&mut self.typed, pasted) {
+            self.sync_choice();
+        }
+    }
+
+    fn sync_choice(&mut self) {
+        self.selected = if self.unlocked() {
+            ConfirmChoice::Yes
+        } else {
+            ConfirmChoice::No
         };
     }
 
     pub fn is_confirmed(&self) -> bool {
-        self.selected == ConfirmChoice::Yes
+        self.selected == ConfirmChoice::Yes && self.unlocked()
     }
 }
 
 pub struct ConfirmWidget;
 
+/// Screen areas of the confirm modal's parts.
+struct ConfirmLayout {
+    modal: Rect,
+    message: Rect,
+    /// The typed-name field, when the modal asks for one.
+    input: Option<Rect>,
+    yes: Rect,
+    no: Rect,
+    help: Rect,
+}
+
 impl ConfirmWidget {
-    pub fn render(f: &mut Frame, area: Rect, modal: &ConfirmModal) {
+    pub fn render(f: &mut Frame, area: Rect, modal: &ConfirmModal, theme: &Theme) {
         // Render backdrop
         f.render_widget(Clear, area);
         f.render_widget(
-            Block::default().style(Style::default().bg(Color::DarkGray)),
+            Block::default().style(Style::default().bg(theme.backdrop)),
             area,
         );
 
-        // Calculate compact modal size
-        let modal_width = 45.min(area.width.saturating_sub(4));
-        let modal_height = 9;
-        let x = (area.width.saturating_sub(modal_width)) / 2 + area.x;
-        let y = (area.height.saturating_sub(modal_height)) / 2 + area.y;
-
-        let modal_area = Rect {
-            x,
-            y,
-            width: modal_width,
-            height: modal_height,
-        };
+        let layout = Self::layout(area, modal);
 
         // Clear and draw modal border
-        f.render_widget(Clear, modal_area);
+        f.render_widget(Clear, layout.modal);
         let block = Block::default()
             .title(format!(" {} ", modal.title))
             .borders(Borders::ALL)
-            .border_style(Style::default().fg(Color::Red));
-        f.render_widget(block, modal_area);
+            .border_style(Style::default().fg(theme.error));
+        f.render_widget(block, layout.modal);
+
+        // Message
+        let mut message = vec![Line::raw(modal.message.as_str())];
+        if let Some(ref expected) = modal.expected {
+            message.push(Line::from(vec![
+                Span::raw("Type "),
+                Span::styled(expected.as_str(), Style::default().fg(theme.heading).bold()),
+                Span::raw(" to confirm:"),
+            ]));
+        }
+        f.render_widget(Paragraph::new(message), layout.message);
+
+        if let Some(input) = layout.input {
+            let width = usize::from(input.width).saturating_sub(4);
+            let mut spans = vec![Span::raw(" ")];
+            spans.extend(modal.cursor.spans(&modal.typed, width));
+            f.render_widget(
+                Paragraph::new(Line::from(spans))
+                    .style(Style::default().fg(theme.accent))
+                    .block(Block::default().borders(Borders::ALL)),
+                input,
+            );
+        }
+
+        let yes_style = if !modal.unlocked() {
+            Style::default().fg(theme.dim)
+        } else if modal.selected == ConfirmChoice::Yes {
+            Style::default()
+                .fg(theme.selection_fg)
+                .bg(theme.error)
+                .bold()
+        } else {
+            Style::default().fg(theme.error)
+        };
+
+        let no_style = if modal.selected == ConfirmChoice::No {
+            Style::default()
+                .fg(theme.selection_fg)
+                .bg(theme.heading)
+                .bold()
+        } else {
+            Style::default().fg(theme.heading)
+        };
+
+        let yes_btn = Paragraph::new(theme.button(
+            "Yes",
+            modal.unlocked() && modal.selected == ConfirmChoice::Yes,
+        ))
+        .style(yes_style)
+        .alignment(Alignment::Center);
+        let no_btn = Paragraph::new(theme.button("No", modal.selected == ConfirmChoice::No))
+            .style(no_style)
+            .alignment(Alignment::Center);
+
+        f.render_widget(yes_btn, layout.yes);
+        f.render_widget(no_btn, layout.no);
+
+        // Help text
+        let help = if modal.expected.is_some() {
+            "Type the name | Tab: Switch | Enter: Confirm | Esc: Cancel"
+        } else {
+            "Tab: Switch | Enter: Confirm | Esc: Cancel"
+        };
+        let help = Paragraph::new(help).style(Style::default().fg(theme.dim));
+        f.render_widget(help, layout.help);
+    }
+
+    /// Returns the button at a screen position, if any.
+    ///
+    /// `area` must be the same area passed to [`Self::render`].
+    pub fn choice_at(
+        area: Rect,
+        modal: &ConfirmModal,
+        column: u16,
+        row: u16,
+    ) -> Option<ConfirmChoice> {
+        let layout = Self::layout(area, modal);
+        let position = Position::new(column, row);
+        if layout.yes.contains(position) {
+            Some(ConfirmChoice::Yes)
+        } else if layout.no.contains(position) {
+            Some(ConfirmChoice::No)
+        } else {
+            None
+        }
+    }
+
+    fn layout(area: Rect, modal: &ConfirmModal) -> ConfirmLayout {
+        // Calculate compact modal size
+        let typed = modal.expected.is_some();
+        let modal_area = modal_area(
+            area,
+            if typed { 55 } else { 45 },
+            if typed { 13 } else { 9 },
+        );
 
         let inner = Rect {
             x: modal_area.x + 1,
@@ -85,54 +237,35 @@ impl ConfirmWidget {
             .direction(Direction::Vertical)
             .constraints([
                 Constraint::Min(2),
+                Constraint::Length(if typed { 4 } else { 0 }),
                 Constraint::Length(2),
                 Constraint::Length(1),
             ])
             .split(inner);
 
-        // Message
-        let message = Paragraph::new(modal.message.as_str());
-        f.render_widget(message, chunks[0]);
-
-        // Buttons
-        let button_area = chunks[1];
         let button_chunks = Layout::default()
             .direction(Direction::Horizontal)
             .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
-            .split(button_area);
-
-        let yes_style = if modal.selected == ConfirmChoice::Yes {
-            Style::default().fg(Color::Black).bg(Color::Red).bold()
-        } else {
-            Style::default().fg(Color::Red)
-        };
-
-        let no_style = if modal.selected == ConfirmChoice::No {
-            Style::default().fg(Color::Black).bg(Color::Yellow).bold()
-        } else {
-            Style::default().fg(Color::Yellow)
-        };
-
-        let yes_btn = Paragraph::new(" Yes ")
-            .style(yes_style)
-            .alignment(Alignment::Center);
-        let no_btn = Paragraph::new(" No ")
-            .style(no_style)
-            .alignment(Alignment::Center);
-
-        f.render_widget(yes_btn, button_chunks[0]);
-        f.render_widget(no_btn, button_chunks[1]);
-
-        // Help text
-        let help = Paragraph::new("Tab: Switch | Enter: Confirm | Esc: Cancel")
-            .style(Style::default().fg(Color::Gray));
-        f.render_widget(help, chunks[2]);
+            .split(chunks[2]);
+
+        ConfirmLayout {
+            modal: modal_area,
+            message: chunks[0],
+            input: typed.then(|| Rect {
+                height: 3,
+                ..chunks[1]
+            }),
+            yes: button_chunks[0],
+            no: button_chunks[1],
+            help: chunks[3],
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crossterm::event::KeyCode;
 
     #[test]
     fn test_confirm_modal_new() {
@@ -204,6 +337,41 @@ mod tests {
         assert_eq!(modal.message, "Delete 'myremote'?");
     }
 
+    #[test]
+    fn test_choice_at_buttons() {
+        let area = Rect::new(0, 0, 80, 24);
+        let modal = ConfirmModal::new("Test", "Test?");
+        let layout = ConfirmWidget::layout(area, &modal);
+
+        assert_eq!(
+            ConfirmWidget::choice_at(area, &modal, layout.yes.x, layout.yes.y),
+            Some(ConfirmChoice::Yes)
+        );
+        assert_eq!(
+            ConfirmWidget::choice_at(area, &modal, layout.no.x, layout.no.y),
+            Some(ConfirmChoice::No)
+        );
+        assert_eq!(ConfirmWidget::choice_at(area, &modal, 0, 0), None);
+    }
+
+    #[test]
+    fn test_typed_name_unlocks_yes() {
+        let mut modal = ConfirmModal::new("Delete", "Delete 's3'?").with_expected("s3");
+        modal.toggle();
+        assert_eq!(modal.selected, ConfirmChoice::No);
+
+        modal.edit(KeyEvent::from(KeyCode::Char('s')));
+        assert!(!modal.unlocked());
+        modal.edit(KeyEvent::from(KeyCode::Char('3')));
+        assert!(modal.is_confirmed());
+
+        modal.edit(KeyEvent::from(KeyCode::Backspace));
+        assert_eq!(modal.selected, ConfirmChoice::No);
+        assert!(!modal.is_confirmed());
+        modal.selected = ConfirmChoice::Yes;
+        assert!(!modal.is_confirmed());
+    }
+
     #[test]
     fn test_str_conversion() {
         let modal = ConfirmModal::new("Test", "Test message");
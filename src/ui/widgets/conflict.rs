@@ -0,0 +1,202 @@
+//! Dialog shown when a copy or move would replace an existing file.
+
+use super::usage::format_size;
+use crate::config::ConflictPolicy;
+use crate::rclone::FileItem;
+use crate::ui::layout::modal_area;
+use crate::ui::{FileOperationsModal, Theme};
+use ratatui::{
+    Frame,
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::Style,
+    text::Line,
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+};
+
+/// Buttons of the dialog, left to right.
+pub const CHOICES: [ConflictPolicy; 3] = [
+    ConflictPolicy::Skip,
+    ConflictPolicy::Overwrite,
+    ConflictPolicy::Rename,
+];
+
+/// Conflict dialog state.
+#[derive(Debug, Clone)]
+pub struct ConflictModal {
+    /// Remote the copy or move runs on.
+    pub remote: String,
+    /// The copy or move waiting for a decision.
+    pub operation: FileOperationsModal,
+    /// What already exists at the destination.
+    pub existing: FileItem,
+    /// Highlighted button.
+    pub selected: ConflictPolicy,
+    /// Whether the choice also answers every later conflict this
+    /// session.
+    pub apply_to_all: bool,
+}
+
+impl ConflictModal {
+    /// Creates the dialog for `operation`, whose destination is
+    /// taken by `existing`.
+    pub fn new(remote: String, operation: FileOperationsModal, existing: FileItem) -> Self {
+        Self {
+            remote,
+            operation,
+            existing,
+            selected: ConflictPolicy::Skip,
+            apply_to_all: false,
+        }
+    }
+
+    /// Moves the highlight `delta` buttons along, wrapping around.
+    pub fn step(&mut self, delta: isize) {
+        let current = CHOICES
+            .iter()
+            .position(|choice| *choice == self.selected)
+            .unwrap_or(0);
+        let next = (current as isize + delta).rem_euclid(CHOICES.len() as isize);
+        self.selected = CHOICES[next as usize];
+    }
+
+    pub fn toggle_apply_to_all(&mut self) {
+        self.apply_to_all = !self.apply_to_all;
+    }
+}
+
+/// Returns `path` with ` (n)` added to the file name, before its
+/// extension: `docs/a.txt` becomes `docs/a (1).txt`.
+pub fn numbered(path: &str, n: usize) -> String {
+    let name_start = path.rfind('/').map_or(0, |i| i + 1);
+    match path[name_start..].rfind('.') {
+        Some(dot) if dot > 0 => {
+            let dot = name_start + dot;
+            format!("{} ({}){}", &path[..dot], n, &path[dot..])
+        }
+        _ => format!("{} ({})", path, n),
+    }
+}
+
+/// Widget for the conflict dialog.
+pub struct ConflictWidget;
+
+impl ConflictWidget {
+    pub fn render(f: &mut Frame, area: Rect, modal: &ConflictModal, theme: &Theme) {
+        f.render_widget(Clear, area);
+        f.render_widget(
+            Block::default().style(Style::default().bg(theme.backdrop)),
+            area,
+        );
+
+        let modal_area = modal_area(area, 60, 12);
+        f.render_widget(Clear, modal_area);
+        f.render_widget(
+            Block::default()
+                .title(" File Exists ")
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(theme.warning)),
+            modal_area,
+        );
+
+        let inner = Rect {
+            x: modal_area.x + 1,
+            y: modal_area.y + 1,
+            width: modal_area.width.saturating_sub(2),
+            height: modal_area.height.saturating_sub(2),
+        };
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Min(1),
+                Constraint::Length(2),
+                Constraint::Length(1),
+                Constraint::Length(1),
+            ])
+            .split(inner);
+
+        let existing = if modal.existing.is_dir() {
+            "Existing: directory".to_string()
+        } else {
+            format!(
+                "Existing: {}, modified {}",
+                format_size(u64::try_from(modal.existing.size).unwrap_or(0)),
+                modal.existing.mod_time
+            )
+        };
+        let verb = match modal.operation.operation {
+            crate::ui::FileOperationType::Move => "Moving",
+            _ => "Copying",
+        };
+        let lines = vec![
+            Line::raw(format!(
+                "{} '{}' would replace '{}'.",
+                verb, modal.operation.file_name, modal.operation.input
+            )),
+            Line::default(),
+            Line::styled(existing, Style::default().fg(theme.dim)),
+        ];
+        f.render_widget(Paragraph::new(lines).wrap(Wrap { trim: false }), chunks[0]);
+
+        let buttons = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Ratio(1, 3); 3])
+            .split(chunks[1]);
+        for (choice, area) in CHOICES.iter().zip(buttons.iter()) {
+            let style = if modal.selected == *choice {
+                Style::default()
+                    .fg(theme.selection_fg)
+                    .bg(theme.heading)
+                    .bold()
+            } else {
+                Style::default().fg(theme.heading)
+            };
+            f.render_widget(
+                Paragraph::new(theme.button(choice.label(), modal.selected == *choice))
+                    .style(style)
+                    .alignment(Alignment::Center),
+                *area,
+            );
+        }
+
+        let check = if modal.apply_to_all { "x" } else { " " };
+        f.render_widget(
+            Paragraph::new(format!("[{}] Apply to all conflicts this session", check)),
+            chunks[2],
+        );
+        f.render_widget(
+            Paragraph::new("s/o/r: Choose | a: Apply to all | Enter: Confirm | Esc: Cancel")
+                .style(Style::default().fg(theme.dim)),
+            chunks[3],
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_numbered_keeps_extension() {
+        assert_eq!(numbered("docs/a.txt", 1), "docs/a (1).txt");
+        assert_eq!(numbered("a.tar.gz", 2), "a.tar (2).gz");
+        assert_eq!(numbered("docs/.bashrc", 1), "docs/.bashrc (1)");
+        assert_eq!(numbered("v1.0/notes", 3), "v1.0/notes (3)");
+    }
+
+    #[test]
+    fn test_step_wraps_around() {
+        let operation = FileOperationsModal::copy("a.txt".to_string(), "/".to_string());
+        let existing = FileItem {
+            name: "b.txt".to_string(),
+            size: 1,
+            mod_time: String::new(),
+            is_dir: false,
+            ..Default::default()
+        };
+        let mut modal = ConflictModal::new("gdrive".to_string(), operation, existing);
+        modal.step(-1);
+        assert_eq!(modal.selected, ConflictPolicy::Rename);
+        modal.step(1);
+        assert_eq!(modal.selected, ConflictPolicy::Skip);
+    }
+}
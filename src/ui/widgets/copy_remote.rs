@@ -0,0 +1,188 @@
+//! Prompt for the new name of a remote being renamed or duplicated.
+//!
+//! rclone cannot rename a remote, so both create a copy under the new
+//! name; a rename then deletes the original.
+
+use crate::ui::Theme;
+use crate::ui::layout::modal_area;
+use crate::ui::text;
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::Style,
+    widgets::{Block, Borders, Clear, Paragraph},
+};
+
+/// Rename/duplicate prompt state.
+#[derive(Debug, Clone)]
+pub struct CopyRemoteModal {
+    /// Remote being copied.
+    pub source: String,
+    /// New name typed so far.
+    pub name: String,
+    /// Whether the original is deleted once the copy exists, making
+    /// this a rename.
+    pub remove_source: bool,
+    /// Whether the copy is in flight.
+    pub waiting: bool,
+    /// Why the name is rejected, or why copying failed.
+    pub error: Option<String>,
+}
+
+impl CopyRemoteModal {
+    /// Creates the prompt for renaming `source`, starting from its
+    /// current name.
+    pub fn rename(source: String) -> Self {
+        Self {
+            name: source.clone(),
+            source,
+            remove_source: true,
+            waiting: false,
+            error: None,
+        }
+    }
+
+    /// Creates the prompt for duplicating `source`.
+    pub fn duplicate(source: String) -> Self {
+        Self {
+            name: format!("{}-copy", source),
+            remove_source: false,
+            ..Self::rename(source)
+        }
+    }
+
+    pub fn input_char(&mut self, c: char) {
+        if c.is_control() {
+            return;
+        }
+        self.name.push(c);
+        self.error = None;
+    }
+
+    pub fn backspace(&mut self) {
+        text::pop_grapheme(&mut self.name);
+        self.error = None;
+    }
+
+    /// Switches between renaming and duplicating.
+    pub fn toggle_remove(&mut self) {
+        self.remove_source = !self.remove_source;
+    }
+
+    /// Returns the new name, or why it cannot be used given the
+    /// existing `remotes`.
+    pub fn target(&self, remotes: &[String]) -> Result<&str, &'static str> {
+        let name = self.name.trim();
+        if name.is_empty() {
+            Err("Enter a name")
+        } else if name == self.source {
+            Err("The new name must differ from the current one")
+        } else if remotes.iter().any(|remote| remote == name) {
+            Err("A remote with that name already exists")
+        } else {
+            Ok(name)
+        }
+    }
+}
+
+/// Widget for the rename/duplicate prompt.
+pub struct CopyRemoteWidget;
+
+impl CopyRemoteWidget {
+    pub fn render(f: &mut Frame, area: Rect, modal: &CopyRemoteModal, theme: &Theme) {
+        f.render_widget(Clear, area);
+        f.render_widget(
+            Block::default().style(Style::default().bg(theme.backdrop)),
+            area,
+        );
+
+        let modal_area = modal_area(area, 56, 10);
+        let title = if modal.remove_source {
+            " Rename Remote "
+        } else {
+            " Duplicate Remote "
+        };
+        f.render_widget(Clear, modal_area);
+        f.render_widget(
+            Block::default()
+                .title(title)
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(theme.border)),
+            modal_area,
+        );
+
+        let inner = Rect {
+            x: modal_area.x + 1,
+            y: modal_area.y + 1,
+            width: modal_area.width.saturating_sub(2),
+            height: modal_area.height.saturating_sub(2),
+        };
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(1),
+                Constraint::Length(3),
+                Constraint::Length(1),
+                Constraint::Min(1),
+                Constraint::Length(1),
+            ])
+            .split(inner);
+
+        f.render_widget(
+            Paragraph::new(format!("New name for '{}':", modal.source)),
+            chunks[0],
+        );
+        f.render_widget(
+            Paragraph::new(format!(" {} ", modal.name))
+                .style(Style::default().fg(theme.accent))
+                .block(Block::default().borders(Borders::ALL)),
+            chunks[1],
+        );
+        let check = if modal.remove_source { "x" } else { " " };
+        f.render_widget(
+            Paragraph::new(format!("[{}] Delete '{}' afterwards", check, modal.source)),
+            chunks[2],
+        );
+        if let Some(ref error) = modal.error {
+            f.render_widget(
+                Paragraph::new(error.as_str()).style(Style::default().fg(theme.error)),
+                chunks[3],
+            );
+        }
+
+        let (help, style) = if modal.waiting {
+            ("Copying...", Style::default().fg(theme.warning))
+        } else {
+            (
+                "Tab: Toggle delete | Enter: Save | Esc: Cancel",
+                Style::default().fg(theme.dim),
+            )
+        };
+        f.render_widget(Paragraph::new(help).style(style), chunks[4]);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_target_rejects_taken_names() {
+        let remotes = vec!["gdrive".to_string(), "s3".to_string()];
+        let mut modal = CopyRemoteModal::rename("gdrive".to_string());
+        assert!(modal.target(&remotes).is_err());
+
+        modal.name = "s3".to_string();
+        assert!(modal.target(&remotes).is_err());
+
+        modal.name = " work ".to_string();
+        assert_eq!(modal.target(&remotes), Ok("work"));
+    }
+
+    #[test]
+    fn test_duplicate_keeps_original() {
+        let modal = CopyRemoteModal::duplicate("gdrive".to_string());
+        assert!(!modal.remove_source);
+        assert_eq!(modal.name, "gdrive-copy");
+    }
+}
@@ -0,0 +1,225 @@
+//! Prompt for a URL the daemon downloads straight into the current
+//! directory, and the name of the file it lands in.
+
+use crate::ui::Theme;
+use crate::ui::layout::modal_area;
+use crate::ui::text;
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::Style,
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+};
+
+/// Field of the copy URL prompt being typed in.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum CopyUrlField {
+    /// The URL to download.
+    #[default]
+    Url,
+    /// The name of the file to create.
+    Name,
+}
+
+/// Copy URL prompt state.
+#[derive(Debug, Clone, Default)]
+pub struct CopyUrlPrompt {
+    /// Remote to download into.
+    pub remote: String,
+    /// Directory within the remote to download into.
+    pub dir: String,
+    /// URL typed so far.
+    pub url: String,
+    /// File name typed so far, or taken from the URL until one is.
+    pub name: String,
+    /// Field typed in.
+    pub field: CopyUrlField,
+    /// Whether the name was typed, so the URL no longer fills it in.
+    pub name_typed: bool,
+    /// Why the URL or name is rejected.
+    pub error: Option<String>,
+}
+
+impl CopyUrlPrompt {
+    /// Creates the prompt for downloading into `dir` on `remote`.
+    pub fn new(remote: String, dir: String) -> Self {
+        Self {
+            remote,
+            dir,
+            ..Self::default()
+        }
+    }
+
+    pub fn input_char(&mut self, c: char) {
+        if c.is_control() {
+            return;
+        }
+        match self.field {
+            CopyUrlField::Url => {
+                self.url.push(c);
+                self.fill_name();
+            }
+            CopyUrlField::Name => {
+                self.name.push(c);
+                self.name_typed = true;
+            }
+        }
+        self.error = None;
+    }
+
+    pub fn backspace(&mut self) {
+        match self.field {
+            CopyUrlField::Url => {
+                text::pop_grapheme(&mut self.url);
+                self.fill_name();
+            }
+            CopyUrlField::Name => {
+                text::pop_grapheme(&mut self.name);
+                self.name_typed = !self.name.is_empty();
+            }
+        }
+        self.error = None;
+    }
+
+    /// Moves to the other field.
+    pub fn toggle_field(&mut self) {
+        self.field = match self.field {
+            CopyUrlField::Url => CopyUrlField::Name,
+            CopyUrlField::Name => CopyUrlField::Url,
+        };
+    }
+
+    /// Names the file after the last segment of the URL's path, unless
+    /// a name was typed.
+    fn fill_name(&mut self) {
+        if self.name_typed {
+            return;
+        }
+        let url = self.url.split(['?', '#']).next().unwrap_or_default();
+        let path = url.split_once("://").map_or("", |(_, rest)| rest);
+        self.name = path
+            .split_once('/')
+            .and_then(|(_, path)| path.rsplit('/').next())
+            .unwrap_or_default()
+            .to_string();
+    }
+
+    /// Returns the URL and file name typed, or why they cannot be
+    /// used.
+    pub fn target(&self) -> Result<(String, String), String> {
+        let url = self.url.trim();
+        let host = url
+            .strip_prefix("https://")
+            .or_else(|| url.strip_prefix("http://"))
+            .ok_or("The URL must start with http:// or https://")?;
+        if host.split('/').next().unwrap_or_default().is_empty() {
+            return Err("The URL has no host".to_string());
+        }
+        let name = self.name.trim();
+        if name.is_empty() {
+            return Err("Name the file to create".to_string());
+        }
+        if name.contains('/') || matches!(name, "." | "..") {
+            return Err("The file name cannot be a path".to_string());
+        }
+        Ok((url.to_string(), name.to_string()))
+    }
+}
+
+/// Widget for the copy URL prompt.
+pub struct CopyUrlWidget;
+
+impl CopyUrlWidget {
+    pub fn render(f: &mut Frame, area: Rect, prompt: &CopyUrlPrompt, theme: &Theme) {
+        f.render_widget(Clear, area);
+        f.render_widget(
+            Block::default().style(Style::default().bg(theme.backdrop)),
+            area,
+        );
+
+        let modal_area = modal_area(area, 72, 12);
+        f.render_widget(Clear, modal_area);
+        f.render_widget(
+            Block::default()
+                .title(format!(" Copy URL into {}:{} ", prompt.remote, prompt.dir))
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(theme.border)),
+            modal_area,
+        );
+
+        let inner = Rect {
+            x: modal_area.x + 1,
+            y: modal_area.y + 1,
+            width: modal_area.width.saturating_sub(2),
+            height: modal_area.height.saturating_sub(2),
+        };
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(3),
+                Constraint::Length(3),
+                Constraint::Min(1),
+                Constraint::Length(1),
+            ])
+            .split(inner);
+
+        let field = |title: &'static str, value: &str, which: CopyUrlField| {
+            let color = if prompt.field == which {
+                theme.accent
+            } else {
+                theme.dim
+            };
+            Paragraph::new(format!(" {} ", value))
+                .style(Style::default().fg(color))
+                .block(Block::default().title(title).borders(Borders::ALL))
+        };
+        f.render_widget(field(" URL ", &prompt.url, CopyUrlField::Url), chunks[0]);
+        f.render_widget(
+            field(" File name ", &prompt.name, CopyUrlField::Name),
+            chunks[1],
+        );
+        if let Some(ref error) = prompt.error {
+            f.render_widget(
+                Paragraph::new(error.as_str())
+                    .style(Style::default().fg(theme.error))
+                    .wrap(Wrap { trim: true }),
+                chunks[2],
+            );
+        }
+        f.render_widget(
+            Paragraph::new("Tab: URL / name | Enter: Download | Esc: Cancel")
+                .style(Style::default().fg(theme.dim)),
+            chunks[3],
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_name_follows_the_url_until_typed() {
+        let mut prompt = CopyUrlPrompt::new("s3".to_string(), "releases".to_string());
+        for c in "https://example.com/releases/v1/app.tar.gz?sig=abc".chars() {
+            prompt.input_char(c);
+        }
+        assert_eq!(prompt.name, "app.tar.gz");
+        assert_eq!(
+            prompt.target(),
+            Ok((
+                "https://example.com/releases/v1/app.tar.gz?sig=abc".to_string(),
+                "app.tar.gz".to_string()
+            ))
+        );
+
+        prompt.toggle_field();
+        prompt.input_char('1');
+        prompt.toggle_field();
+        prompt.backspace();
+        assert_eq!(prompt.name, "app.tar.gz1");
+
+        prompt.url = "ftp://example.com/a".to_string();
+        assert!(prompt.target().is_err());
+    }
+}
@@ -1,11 +1,27 @@
 //! Create/Edit remote modal widget.
-
-use ratatui::{
-    Frame,
-    layout::{Constraint, Direction, Layout, Rect},
-    style::{Color, Style},
-    widgets::{Block, Borders, Clear, Paragraph},
-};
+//!
+//! Once the type names a backend from the daemon's catalogue, the
+//! form lists that backend's options (S3's keys and region, WebDAV's
+//! URL and vendor, ...) instead of a bare path.
+//!
+//! For `alias`, `union` and `combine` remotes, the option naming the
+//! upstreams offers existing remotes and their folders to pick from,
+//! completing one `remote:path` at a time.
+//!
+//! When editing, the form starts from the remote's current settings
+//! and only sends the ones that changed.
+
+use crate::app::fuzzy;
+use crate::error::LazyFileError;
+use crate::rclone::validate::validate_new_remote_name;
+use crate::rclone::{ConfigOption, Provider};
+use crate::ui::{Edit, Input};
+use crossterm::event::{KeyCode, KeyEvent};
+use std::collections::HashMap;
+
+/// Words marking a setting as secret when the backend's schema does
+/// not say.
+pub(super) const SECRET_WORDS: &[&str] = &["pass", "secret", "token"];
 
 /// Modal state for creating/editing remotes.
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -25,13 +41,44 @@ pub struct CreateRemoteModal {
     pub path: String,
     pub focus_field: RemoteField,
     pub error: Option<String>,
+    /// Backends offered for the type field; empty when the catalogue
+    /// could not be fetched, leaving the type to be typed in full.
+    pub providers: Vec<Provider>,
+    /// Index into [`Self::type_matches`] of the highlighted backend.
+    pub type_selected: usize,
+    /// Values typed for the backend's options, keyed by option name.
+    pub values: HashMap<String, String>,
+    /// Whether advanced options are listed too.
+    pub show_advanced: bool,
+    /// Existing remotes offered as upstreams.
+    pub remotes: Vec<String>,
+    /// Index into [`Self::upstream_matches`] of the highlighted
+    /// upstream.
+    pub upstream_selected: usize,
+    /// Directory listed for the upstream picker, as `remote:path/`.
+    pub dirs_location: String,
+    /// Names of the folders in [`Self::dirs_location`].
+    pub dirs: Vec<String>,
+    /// Settings the remote had when the form opened; empty when
+    /// creating.
+    pub original: HashMap<String, String>,
+    /// Options for the settings in [`Self::original`], so each is
+    /// listed even when the schema hides it or is unknown.
+    pub existing: Vec<ConfigOption>,
+    /// Whether saving an edit is in flight.
+    pub waiting: bool,
+    /// Cursor in the focused field.
+    pub input: Input,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum RemoteField {
     Name,
     Type,
+    /// Free-form path, shown while the type names no known backend.
     Path,
+    /// The option at this index of [`CreateRemoteModal::options`].
+    Setting(usize),
 }
 
 impl CreateRemoteModal {
@@ -43,191 +90,207 @@ impl CreateRemoteModal {
             path: String::new(),
             focus_field: RemoteField::Name,
             error: None,
+            providers: Vec::new(),
+            type_selected: 0,
+            values: HashMap::new(),
+            show_advanced: false,
+            remotes: Vec::new(),
+            upstream_selected: 0,
+            dirs_location: String::new(),
+            dirs: Vec::new(),
+            original: HashMap::new(),
+            existing: Vec::new(),
+            waiting: false,
+            input: Input::default(),
         }
     }
 
-    pub fn with_name(mut self, name: String) -> Self {
-        self.name = name;
+    pub fn with_remotes(mut self, remotes: Vec<String>) -> Self {
+        self.remotes = remotes;
         self
     }
 
-    pub fn with_type(mut self, remote_type: String) -> Self {
-        self.remote_type = remote_type;
+    pub fn with_providers(mut self, providers: Vec<Provider>) -> Self {
+        self.providers = providers;
         self
     }
 
-    pub fn next_field(&mut self) {
-        self.focus_field = match self.focus_field {
-            RemoteField::Name => RemoteField::Type,
-            RemoteField::Type => RemoteField::Path,
-            RemoteField::Path => RemoteField::Name,
-        };
+    /// Returns the backends matching the typed type, best first.
+    pub fn type_matches(&self) -> Vec<&Provider> {
+        let mut scored: Vec<(i64, &Provider)> = self
+            .providers
+            .iter()
+            .filter_map(|provider| {
+                let score = fuzzy::score(&self.remote_type, &provider.name)
+                    .max(fuzzy::score(&self.remote_type, &provider.description))?;
+                Some((score, provider))
+            })
+            .collect();
+        scored.sort_by_key(|(score, _)| std::cmp::Reverse(*score));
+        scored.into_iter().map(|(_, provider)| provider).collect()
     }
 
-    pub fn prev_field(&mut self) {
-        self.focus_field = match self.focus_field {
-            RemoteField::Name => RemoteField::Path,
-            RemoteField::Type => RemoteField::Name,
-            RemoteField::Path => RemoteField::Type,
-        };
+    /// Returns the backend named by the type field, if it is one.
+    pub fn provider(&self) -> Option<&Provider> {
+        self.providers
+            .iter()
+            .find(|provider| provider.name == self.remote_type)
     }
 
-    pub fn input_char(&mut self, c: char) {
-        // Bracketed paste can deliver control characters as Char
-        // events; they are never valid in any field.
-        if c.is_control() {
-            return;
+    /// Highlights the next matching backend, wrapping to the first.
+    pub fn select_next_type(&mut self) {
+        let len = self.type_matches().len();
+        if len > 0 {
+            self.type_selected = (self.type_selected + 1) % len;
         }
-        match self.focus_field {
-            RemoteField::Name => self.name.push(c),
-            RemoteField::Type => self.remote_type.push(c),
-            RemoteField::Path => self.path.push(c),
+    }
+
+    /// Highlights the previous matching backend, wrapping to the last.
+    pub fn select_prev_type(&mut self) {
+        let len = self.type_matches().len();
+        if len > 0 {
+            self.type_selected = (self.type_selected + len - 1) % len;
         }
     }
 
-    pub fn backspace(&mut self) {
-        match self.focus_field {
-            RemoteField::Name => {
-                self.name.pop();
-            }
-            RemoteField::Type => {
-                self.remote_type.pop();
-            }
-            RemoteField::Path => {
-                self.path.pop();
-            }
+    /// Completes the type field with the highlighted backend. Returns
+    /// whether the field changed.
+    pub fn accept_type(&mut self) -> bool {
+        let Some(name) = self
+            .type_matches()
+            .get(self.type_selected)
+            .map(|provider| provider.name.clone())
+        else {
+            return false;
+        };
+        if name == self.remote_type {
+            return false;
         }
+        self.remote_type = name;
+        self.type_selected = 0;
+        true
     }
 
-    pub fn is_valid(&self) -> bool {
-        !self.name.is_empty() && !self.remote_type.is_empty()
+    pub fn with_name(mut self, name: String) -> Self {
+        self.name = name;
+        self
     }
-}
 
-pub struct CreateRemoteWidget;
-
-impl CreateRemoteWidget {
-    pub fn render(f: &mut Frame, area: Rect, modal: &CreateRemoteModal) {
-        // Render backdrop to darken background
-        let backdrop = Block::default().style(Style::default().bg(Color::Black));
-        f.render_widget(Clear, area);
-        f.render_widget(backdrop.style(Style::default().bg(Color::DarkGray)), area);
-
-        // Calculate compact modal size (much smaller)
-        let modal_width = 50.min(area.width.saturating_sub(4));
-        let modal_height = 13; // Compact: title + 3 fields + help
-        let x = (area.width.saturating_sub(modal_width)) / 2 + area.x;
-        let y = (area.height.saturating_sub(modal_height)) / 2 + area.y;
-
-        let modal_area = Rect {
-            x,
-            y,
-            width: modal_width,
-            height: modal_height,
-        };
+    pub fn with_type(mut self, remote_type: String) -> Self {
+        self.remote_type = remote_type;
+        self
+    }
 
-        // Clear the modal area
-        f.render_widget(Clear, modal_area);
+    /// Returns the fields in focus order. A remote's name and type
+    /// cannot change once it exists.
+    pub fn fields(&self) -> Vec<RemoteField> {
+        let mut fields = match self.mode {
+            CreateRemoteMode::Create => vec![RemoteField::Name, RemoteField::Type],
+            CreateRemoteMode::Edit => Vec::new(),
+        };
+        if self.has_options() {
+            fields.extend((0..self.options().len()).map(RemoteField::Setting));
+        } else {
+            fields.push(RemoteField::Path);
+        }
+        fields
+    }
 
-        let block = Block::default()
-            .title(match modal.mode {
-                CreateRemoteMode::Create => " Create Remote ",
-                CreateRemoteMode::Edit => " Edit Remote ",
-            })
-            .borders(Borders::ALL)
-            .border_style(Style::default().fg(Color::Cyan));
+    pub fn next_field(&mut self) {
+        let fields = self.fields();
+        if fields.is_empty() {
+            return;
+        }
+        let next = fields
+            .iter()
+            .position(|field| *field == self.focus_field)
+            .map_or(0, |i| (i + 1) % fields.len());
+        self.focus_field = fields[next];
+        self.input.end();
+    }
 
-        f.render_widget(block, modal_area);
+    pub fn prev_field(&mut self) {
+        let fields = self.fields();
+        let len = fields.len();
+        if len == 0 {
+            return;
+        }
+        let prev = fields
+            .iter()
+            .position(|field| *field == self.focus_field)
+            .map_or(0, |i| (i + len - 1) % len);
+        self.focus_field = fields[prev];
+        self.input.end();
+    }
 
-        let inner = Rect {
-            x: modal_area.x + 1,
-            y: modal_area.y + 1,
-            width: modal_area.width.saturating_sub(2),
-            height: modal_area.height.saturating_sub(2),
-        };
+    /// Returns the text of the focused field.
+    fn field_mut(&mut self) -> Option<&mut String> {
+        match self.focus_field {
+            RemoteField::Name => Some(&mut self.name),
+            RemoteField::Type => Some(&mut self.remote_type),
+            RemoteField::Path => Some(&mut self.path),
+            RemoteField::Setting(_) => {
+                let name = self.focused_option()?.name.clone();
+                Some(self.values.entry(name).or_default())
+            }
+        }
+    }
 
-        let chunks = Layout::default()
-            .direction(Direction::Vertical)
-            .constraints([
-                Constraint::Length(2),
-                Constraint::Length(2),
-                Constraint::Length(2),
-                Constraint::Min(1),
-            ])
-            .split(inner);
-
-        // Name field
-        Self::render_field(
-            f,
-            chunks[0],
-            "Name",
-            &modal.name,
-            modal.focus_field == RemoteField::Name,
-        );
-
-        // Type field
-        Self::render_field(
-            f,
-            chunks[1],
-            "Type",
-            &modal.remote_type,
-            modal.focus_field == RemoteField::Type,
-        );
-
-        // Path field
-        Self::render_field(
-            f,
-            chunks[2],
-            "Path",
-            &modal.path,
-            modal.focus_field == RemoteField::Path,
-        );
-
-        // Error or help text (single line, smaller font)
-        let help_text = if let Some(ref error) = modal.error {
-            error.clone()
-        } else {
-            "Tab: Next | Enter: Save | Esc: Cancel".to_string()
+    /// Applies an editing key to the focused field. Typing resets the
+    /// highlighted backend or upstream.
+    pub fn edit(&mut self, key: KeyEvent) -> Edit {
+        let mut input = self.input;
+        let Some(text) = self.field_mut() else {
+            return Edit::Ignored;
         };
+        let edit = input.handle_key(text, key);
+        self.input = input;
+        if edit == Edit::Changed {
+            self.type_selected = 0;
+            self.upstream_selected = 0;
+        }
+        edit
+    }
 
-        let style = if modal.error.is_some() {
-            Style::default().fg(Color::Red)
-        } else {
-            Style::default().fg(Color::Gray)
+    /// Inserts pasted text into the focused field at once. Returns
+    /// whether the field changed.
+    pub fn paste(&mut self, pasted: &str) -> bool {
+        let mut input = self.input;
+        let Some(text) = self.field_mut() else {
+            return false;
         };
+        let changed = input.paste(text, pasted);
+        self.input = input;
+        if changed {
+            self.type_selected = 0;
+            self.upstream_selected = 0;
+        }
+        changed
+    }
 
-        let help = Paragraph::new(help_text).style(style);
-        f.render_widget(help, chunks[3]);
+    pub fn input_char(&mut self, c: char) {
+        self.edit(KeyEvent::from(KeyCode::Char(c)));
     }
 
-    fn render_field(f: &mut Frame, area: Rect, label: &str, value: &str, focused: bool) {
-        let value_display = if value.len() > 30 {
-            format!("{}...", &value[..27])
-        } else {
-            value.to_string()
-        };
+    pub fn backspace(&mut self) {
+        self.edit(KeyEvent::from(KeyCode::Backspace));
+    }
 
-        let text = format!("{}: {}", label, value_display);
-        let paragraph = Paragraph::new(text).style(if focused {
-            Style::default().fg(Color::Yellow).bold()
-        } else {
-            Style::default()
-        });
-
-        f.render_widget(paragraph, area);
-
-        // Minimal bottom border for focused field
-        if focused && area.height > 1 {
-            let bottom_line = "─".repeat(area.width as usize);
-            let bottom = Paragraph::new(bottom_line).style(Style::default().fg(Color::Cyan));
-            let bottom_area = Rect {
-                y: area.y + 1,
-                height: 1,
-                ..area
-            };
-            f.render_widget(bottom, bottom_area);
+    /// Returns why rclone would refuse the name typed for a new
+    /// remote, or None while it is empty or acceptable.
+    pub fn name_error(&self) -> Option<&'static str> {
+        if self.mode != CreateRemoteMode::Create || self.name.is_empty() {
+            return None;
+        }
+        match validate_new_remote_name(&self.name) {
+            Err(LazyFileError::InvalidInput { reason, .. }) => Some(reason),
+            _ => None,
         }
     }
+
+    pub fn is_valid(&self) -> bool {
+        !self.name.is_empty() && !self.remote_type.is_empty()
+    }
 }
 
 #[cfg(test)]
@@ -261,6 +324,22 @@ mod tests {
         assert_eq!(modal.remote_type, "s3");
     }
 
+    #[test]
+    fn test_name_error_while_typing() {
+        let mut modal = CreateRemoteModal::new(CreateRemoteMode::Create);
+        assert_eq!(modal.name_error(), None);
+        "my drive".chars().for_each(|c| modal.input_char(c));
+        assert_eq!(modal.name_error(), None);
+        modal.input_char(':');
+        assert!(modal.name_error().is_some());
+        modal.backspace();
+        modal.input_char(' ');
+        assert_eq!(modal.name_error(), Some("must not end with a space"));
+
+        let modal = CreateRemoteModal::new(CreateRemoteMode::Edit).with_name("-old".to_string());
+        assert_eq!(modal.name_error(), None);
+    }
+
     #[test]
     fn test_field_navigation() {
         let mut modal = CreateRemoteModal::new(CreateRemoteMode::Create);
@@ -367,4 +446,70 @@ mod tests {
         modal.input_char('a');
         assert_eq!(modal.name, "a");
     }
+
+    fn providers() -> Vec<Provider> {
+        [
+            ("drive", "Google Drive"),
+            ("dropbox", "Dropbox"),
+            ("s3", "Amazon S3 Compliant Storage Providers"),
+        ]
+        .into_iter()
+        .map(|(name, description)| Provider {
+            name: name.to_string(),
+            description: description.to_string(),
+            ..Provider::default()
+        })
+        .collect()
+    }
+
+    fn picker(query: &str) -> CreateRemoteModal {
+        let mut modal = CreateRemoteModal::new(CreateRemoteMode::Create)
+            .with_type(String::new())
+            .with_providers(providers());
+        modal.focus_field = RemoteField::Type;
+        query.chars().for_each(|c| modal.input_char(c));
+        modal
+    }
+
+    #[test]
+    fn test_type_matches_name_and_description() {
+        let names = |modal: &CreateRemoteModal| {
+            modal
+                .type_matches()
+                .iter()
+                .map(|p| p.name.clone())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(names(&picker("dr"))[..2], ["drive", "dropbox"]);
+        assert_eq!(names(&picker("amazon")), ["s3"]);
+        assert_eq!(names(&picker("")).len(), 3);
+    }
+
+    #[test]
+    fn test_accept_type_completes_highlight() {
+        let mut modal = picker("dr");
+        modal.select_next_type();
+        assert!(modal.accept_type());
+        assert_eq!(modal.remote_type, "dropbox");
+        assert_eq!(modal.provider().unwrap().description, "Dropbox");
+
+        // Already complete: nothing changes.
+        assert!(!modal.accept_type());
+    }
+
+    #[test]
+    fn test_type_selection_wraps_and_resets_on_typing() {
+        let mut modal = picker("dr");
+        modal.select_prev_type();
+        assert_eq!(modal.type_selected, modal.type_matches().len() - 1);
+        modal.input_char('o');
+        assert_eq!(modal.type_selected, 0);
+    }
+
+    #[test]
+    fn test_accept_type_without_providers_keeps_text() {
+        let mut modal = CreateRemoteModal::new(CreateRemoteMode::Create).with_type("s3".into());
+        assert!(!modal.accept_type());
+        assert_eq!(modal.remote_type, "s3");
+    }
 }
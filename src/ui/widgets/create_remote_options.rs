@@ -0,0 +1,397 @@
+//! The backend options listed by the create/edit remote form, and
+//! the settings it sends.
+
+use super::create_remote::{CreateRemoteModal, CreateRemoteMode, RemoteField, SECRET_WORDS};
+use crate::rclone::types::matches_provider;
+use crate::rclone::{ConfigOption, HIDE_CONFIGURATOR, Provider};
+use std::collections::{HashMap, HashSet};
+
+/// Option that picks a sub-provider, such as S3's `AWS` or `Minio`,
+/// which decides the other options and examples that apply.
+const SUB_PROVIDER: &str = "provider";
+
+impl CreateRemoteModal {
+    /// Fills the form with a remote's current settings, as fetched
+    /// from the daemon. Secrets start empty, which keeps them as
+    /// they are. Call after [`Self::with_providers`], so the backend's
+    /// schema decides which settings are secret.
+    pub fn with_config(mut self, mut config: HashMap<String, String>) -> Self {
+        if let Some(remote_type) = config.remove("type") {
+            self.remote_type = remote_type;
+        }
+        let sub_provider = config.get(SUB_PROVIDER).map_or("", String::as_str);
+        let schema = self.provider().map_or(&[][..], Provider::options);
+        let mut keys: Vec<&String> = config.keys().collect();
+        keys.sort();
+        let existing: Vec<ConfigOption> = keys
+            .into_iter()
+            .map(|key| {
+                match schema
+                    .iter()
+                    .find(|option| option.name == *key && option.applies_to(sub_provider))
+                {
+                    Some(option) => ConfigOption {
+                        advanced: false,
+                        hide: 0,
+                        provider: String::new(),
+                        ..option.clone()
+                    },
+                    None => ConfigOption {
+                        name: key.clone(),
+                        is_password: SECRET_WORDS.iter().any(|word| key.contains(word)),
+                        ..ConfigOption::default()
+                    },
+                }
+            })
+            .collect();
+        self.values = existing
+            .iter()
+            .filter(|option| !option.is_secret())
+            .filter_map(|option| Some((option.name.clone(), config.get(&option.name)?.clone())))
+            .collect();
+        self.existing = existing;
+        self.original = config;
+        if let Some(&first) = self.fields().first() {
+            self.focus_field = first;
+        }
+        self
+    }
+
+    /// Returns the options of the chosen backend shown as fields:
+    /// those rclone's configurator asks about, advanced ones only on
+    /// request, and only those applying to the chosen sub-provider.
+    /// When editing, the remote's other settings follow.
+    pub fn options(&self) -> Vec<&ConfigOption> {
+        let schema = self.provider().map_or(&[][..], Provider::options);
+        let sub_provider = self.value(SUB_PROVIDER);
+        // Backends like S3 repeat an option per sub-provider; the
+        // first one that applies wins.
+        let mut seen = HashSet::new();
+        schema
+            .iter()
+            .filter(|option| option.hide & HIDE_CONFIGURATOR == 0)
+            .filter(|option| self.show_advanced || !option.advanced)
+            .filter(|option| option.applies_to(sub_provider))
+            .chain(&self.existing)
+            .filter(|option| seen.insert(option.name.as_str()))
+            .collect()
+    }
+
+    /// Whether the form lists options rather than a bare path.
+    pub fn has_options(&self) -> bool {
+        self.provider().is_some() || !self.existing.is_empty()
+    }
+
+    /// Returns the option with focus, if an option field has it.
+    pub fn focused_option(&self) -> Option<&ConfigOption> {
+        match self.focus_field {
+            RemoteField::Setting(i) => self.options().get(i).copied(),
+            _ => None,
+        }
+    }
+
+    /// Returns the value typed for the option `name`.
+    pub fn value(&self, name: &str) -> &str {
+        self.values.get(name).map_or("", String::as_str)
+    }
+
+    /// Shows or hides the advanced options. Focus on an option that
+    /// disappears moves to the type field, or the first option when
+    /// editing.
+    pub fn toggle_advanced(&mut self) {
+        self.show_advanced = !self.show_advanced;
+        if let RemoteField::Setting(i) = self.focus_field
+            && i >= self.options().len()
+        {
+            self.focus_field = match self.mode {
+                CreateRemoteMode::Create => RemoteField::Type,
+                CreateRemoteMode::Edit => RemoteField::Setting(0),
+            };
+        }
+    }
+
+    /// Fills the focused option with its next example, wrapping.
+    pub fn next_example(&mut self) {
+        self.cycle_example(true);
+    }
+
+    /// Fills the focused option with its previous example, wrapping.
+    pub fn prev_example(&mut self) {
+        self.cycle_example(false);
+    }
+
+    fn cycle_example(&mut self, forward: bool) {
+        let Some(option) = self.focused_option() else {
+            return;
+        };
+        let sub_provider = self.value(SUB_PROVIDER);
+        let examples: Vec<&str> = option
+            .examples()
+            .iter()
+            .filter(|example| matches_provider(&example.provider, sub_provider))
+            .map(|example| example.value.as_str())
+            .collect();
+        let len = examples.len();
+        if len == 0 {
+            return;
+        }
+        let current = examples
+            .iter()
+            .position(|value| *value == self.value(&option.name));
+        let next = match (current, forward) {
+            (Some(i), true) => (i + 1) % len,
+            (Some(i), false) => (i + len - 1) % len,
+            (None, true) => 0,
+            (None, false) => len - 1,
+        };
+        let (name, value) = (option.name.clone(), examples[next].to_string());
+        self.values.insert(name, value);
+    }
+
+    /// Returns the parameters to send: the shown options given a
+    /// value, or the path while there are no options. When editing,
+    /// only the settings that changed.
+    pub fn parameters(&self) -> HashMap<String, String> {
+        if !self.has_options() {
+            return if self.path.is_empty() {
+                HashMap::new()
+            } else {
+                HashMap::from([("path".to_string(), self.path.clone())])
+            };
+        }
+        self.options()
+            .into_iter()
+            .filter_map(|option| {
+                let value = self.value(&option.name);
+                let changed = match self.original.get(&option.name) {
+                    Some(_) if option.is_secret() && value.is_empty() => false,
+                    Some(original) => value != original,
+                    None => !value.is_empty(),
+                };
+                changed.then(|| (option.name.clone(), value.to_string()))
+            })
+            .collect()
+    }
+
+    /// Returns the first shown required option that is empty and has
+    /// no default or kept secret to fall back on.
+    pub fn missing_required(&self) -> Option<&str> {
+        self.options()
+            .into_iter()
+            .find(|option| {
+                option.required
+                    && option.default.is_empty()
+                    && self.value(&option.name).is_empty()
+                    && !(option.is_secret() && self.original.contains_key(&option.name))
+            })
+            .map(|option| option.name.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rclone::ConfigExample;
+
+    fn option(name: &str, provider: &str) -> ConfigOption {
+        ConfigOption {
+            name: name.to_string(),
+            provider: provider.to_string(),
+            ..ConfigOption::default()
+        }
+    }
+
+    /// An S3-like backend: a sub-provider choice, an endpoint given
+    /// once per sub-provider, and an advanced option.
+    fn s3_form() -> CreateRemoteModal {
+        let s3 = Provider {
+            name: "s3".to_string(),
+            options: Some(vec![
+                ConfigOption {
+                    examples: Some(vec![
+                        ConfigExample {
+                            value: "AWS".to_string(),
+                            ..ConfigExample::default()
+                        },
+                        ConfigExample {
+                            value: "Minio".to_string(),
+                            ..ConfigExample::default()
+                        },
+                    ]),
+                    ..option("provider", "")
+                },
+                ConfigOption {
+                    required: true,
+                    ..option("endpoint", "Minio")
+                },
+                option("endpoint", "!Minio"),
+                ConfigOption {
+                    is_password: true,
+                    ..option("secret_access_key", "")
+                },
+                ConfigOption {
+                    advanced: true,
+                    ..option("chunk_size", "")
+                },
+                ConfigOption {
+                    hide: HIDE_CONFIGURATOR,
+                    ..option("description", "")
+                },
+            ]),
+            ..Provider::default()
+        };
+        CreateRemoteModal::new(CreateRemoteMode::Create)
+            .with_name("store".to_string())
+            .with_type("s3".to_string())
+            .with_providers(vec![s3])
+    }
+
+    fn option_names(modal: &CreateRemoteModal) -> Vec<&str> {
+        modal
+            .options()
+            .iter()
+            .map(|option| option.name.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn test_options_follow_provider_schema() {
+        let mut modal = s3_form();
+        assert_eq!(
+            option_names(&modal),
+            ["provider", "endpoint", "secret_access_key"]
+        );
+        assert_eq!(
+            modal.fields(),
+            [
+                RemoteField::Name,
+                RemoteField::Type,
+                RemoteField::Setting(0),
+                RemoteField::Setting(1),
+                RemoteField::Setting(2),
+            ]
+        );
+
+        modal.toggle_advanced();
+        assert_eq!(
+            option_names(&modal),
+            ["provider", "endpoint", "secret_access_key", "chunk_size"]
+        );
+    }
+
+    #[test]
+    fn test_sub_provider_picks_applicable_options() {
+        let mut modal = s3_form();
+        modal.focus_field = RemoteField::Setting(0);
+        modal.next_example();
+        assert_eq!(modal.value("provider"), "AWS");
+        assert!(!modal.options()[1].required);
+
+        modal.next_example();
+        assert_eq!(modal.value("provider"), "Minio");
+        assert!(modal.options()[1].required);
+        assert_eq!(modal.missing_required(), Some("endpoint"));
+
+        modal.next_field();
+        "http://minio:9000"
+            .chars()
+            .for_each(|c| modal.input_char(c));
+        assert_eq!(modal.missing_required(), None);
+        assert_eq!(
+            modal.parameters(),
+            HashMap::from([
+                ("provider".to_string(), "Minio".to_string()),
+                ("endpoint".to_string(), "http://minio:9000".to_string()),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_hiding_advanced_moves_focus_off_option() {
+        let mut modal = s3_form();
+        modal.toggle_advanced();
+        modal.focus_field = RemoteField::Setting(3);
+        modal.toggle_advanced();
+        assert_eq!(modal.focus_field, RemoteField::Type);
+    }
+
+    #[test]
+    fn test_unknown_type_keeps_path_field() {
+        let mut modal = CreateRemoteModal::new(CreateRemoteMode::Create);
+        assert!(modal.options().is_empty());
+        modal.focus_field = RemoteField::Path;
+        modal.input_char('/');
+        assert_eq!(
+            modal.parameters(),
+            HashMap::from([("path".to_string(), "/".to_string())])
+        );
+        modal.next_field();
+        assert_eq!(modal.focus_field, RemoteField::Name);
+    }
+
+    /// An edit form for an S3 remote whose settings include a secret
+    /// and an advanced option.
+    fn s3_edit_form() -> CreateRemoteModal {
+        let config = HashMap::from([
+            ("type".to_string(), "s3".to_string()),
+            ("provider".to_string(), "AWS".to_string()),
+            ("secret_access_key".to_string(), "hunter2".to_string()),
+            ("chunk_size".to_string(), "64M".to_string()),
+        ]);
+        CreateRemoteModal::new(CreateRemoteMode::Edit)
+            .with_name("store".to_string())
+            .with_providers(s3_form().providers)
+            .with_config(config)
+    }
+
+    #[test]
+    fn test_edit_form_starts_from_settings() {
+        let modal = s3_edit_form();
+        assert_eq!(modal.remote_type, "s3");
+        assert_eq!(modal.value("provider"), "AWS");
+        assert_eq!(modal.value("secret_access_key"), "");
+        assert_eq!(
+            option_names(&modal),
+            ["provider", "endpoint", "secret_access_key", "chunk_size"]
+        );
+        // Name and type are fixed once the remote exists.
+        assert_eq!(modal.focus_field, RemoteField::Setting(0));
+        assert!(!modal.fields().contains(&RemoteField::Name));
+    }
+
+    #[test]
+    fn test_edit_form_sends_only_changes() {
+        let mut modal = s3_edit_form();
+        assert!(modal.parameters().is_empty());
+        assert_eq!(modal.missing_required(), None);
+
+        modal.focus_field = RemoteField::Setting(3);
+        modal.backspace();
+        modal.input_char('G');
+        modal.focus_field = RemoteField::Setting(2);
+        "s3cret".chars().for_each(|c| modal.input_char(c));
+        assert_eq!(
+            modal.parameters(),
+            HashMap::from([
+                ("chunk_size".to_string(), "64G".to_string()),
+                ("secret_access_key".to_string(), "s3cret".to_string()),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_edit_form_without_schema_guesses_secrets() {
+        let config = HashMap::from([
+            ("type".to_string(), "webdav".to_string()),
+            ("url".to_string(), "https://dav.example.com".to_string()),
+            ("pass".to_string(), "obscured".to_string()),
+        ]);
+        let modal = CreateRemoteModal::new(CreateRemoteMode::Edit)
+            .with_name("dav".to_string())
+            .with_config(config);
+        assert_eq!(option_names(&modal), ["pass", "url"]);
+        assert!(modal.options()[0].is_secret());
+        assert_eq!(modal.value("pass"), "");
+        assert_eq!(modal.value("url"), "https://dav.example.com");
+    }
+}
@@ -0,0 +1,236 @@
+//! Picking upstream remotes and their folders for `alias`, `union`
+//! and `combine` remotes in the create/edit remote form.
+
+use super::create_remote::CreateRemoteModal;
+use crate::app::fuzzy;
+
+/// Options whose value names other remotes, by backend. `union` takes
+/// space-separated upstreams, `combine` space-separated `dir=upstream`
+/// pairs.
+const UPSTREAM_OPTIONS: &[(&str, &str)] = &[
+    ("alias", "remote"),
+    ("union", "upstreams"),
+    ("combine", "upstreams"),
+];
+
+impl CreateRemoteModal {
+    /// Whether the focused option names upstream remotes.
+    pub fn picks_upstream(&self) -> bool {
+        self.focused_option().is_some_and(|option| {
+            UPSTREAM_OPTIONS.contains(&(self.remote_type.as_str(), option.name.as_str()))
+        })
+    }
+
+    /// Returns the upstream being typed: the last word of the focused
+    /// value, without a `combine` directory prefix.
+    fn upstream_token(&self) -> &str {
+        let value = self
+            .focused_option()
+            .map_or("", |option| self.value(&option.name));
+        let word = value.rsplit(' ').next().unwrap_or_default();
+        word.split_once('=').map_or(word, |(_, upstream)| upstream)
+    }
+
+    /// Returns the completions for the upstream being typed, best
+    /// first: other remotes until one is named, then the folders of
+    /// the directory typed so far, once they are listed.
+    pub fn upstream_matches(&self) -> Vec<String> {
+        if !self.picks_upstream() {
+            return Vec::new();
+        }
+        let token = self.upstream_token();
+        let (candidates, query, prefix): (Vec<&str>, &str, String) = match token.split_once(':') {
+            None => (
+                self.remotes
+                    .iter()
+                    .map(String::as_str)
+                    .filter(|remote| *remote != self.name)
+                    .collect(),
+                token,
+                String::new(),
+            ),
+            Some((remote, path)) => {
+                let (dir, leaf) = path.rsplit_once('/').unwrap_or(("", path));
+                let location = upstream_location(remote, dir);
+                if location != self.dirs_location {
+                    return Vec::new();
+                }
+                (
+                    self.dirs.iter().map(String::as_str).collect(),
+                    leaf,
+                    location,
+                )
+            }
+        };
+        let suffix = if prefix.is_empty() { ":" } else { "/" };
+        let mut scored: Vec<(i64, &str)> = candidates
+            .into_iter()
+            .filter_map(|name| Some((fuzzy::score(query, name)?, name)))
+            .collect();
+        scored.sort_by_key(|(score, _)| std::cmp::Reverse(*score));
+        scored
+            .into_iter()
+            .map(|(_, name)| format!("{}{}{}", prefix, name, suffix))
+            .collect()
+    }
+
+    /// Highlights the next upstream, wrapping to the first.
+    pub fn select_next_upstream(&mut self) {
+        let len = self.upstream_matches().len();
+        if len > 0 {
+            self.upstream_selected = (self.upstream_selected + 1) % len;
+        }
+    }
+
+    /// Highlights the previous upstream, wrapping to the last.
+    pub fn select_prev_upstream(&mut self) {
+        let len = self.upstream_matches().len();
+        if len > 0 {
+            self.upstream_selected = (self.upstream_selected + len - 1) % len;
+        }
+    }
+
+    /// Completes the upstream being typed with the highlighted one.
+    /// Returns whether the value changed.
+    pub fn accept_upstream(&mut self) -> bool {
+        let Some(completion) = self.upstream_matches().get(self.upstream_selected).cloned() else {
+            return false;
+        };
+        let Some(name) = self.focused_option().map(|option| option.name.clone()) else {
+            return false;
+        };
+        if self.upstream_token() == completion {
+            return false;
+        }
+        let token_len = self.upstream_token().len();
+        let value = self.values.entry(name).or_default();
+        value.truncate(value.len() - token_len);
+        value.push_str(&completion);
+        self.upstream_selected = 0;
+        true
+    }
+
+    /// Returns the remote and path whose folders the upstream picker
+    /// needs, if they are not listed or being listed already, and
+    /// marks them as being listed.
+    pub fn dirs_to_list(&mut self) -> Option<(String, String)> {
+        if !self.picks_upstream() {
+            return None;
+        }
+        let (remote, path) = self.upstream_token().split_once(':')?;
+        let dir = path.rsplit_once('/').map_or("", |(dir, _)| dir);
+        let location = upstream_location(remote, dir);
+        if location == self.dirs_location {
+            return None;
+        }
+        let request = (remote.to_string(), dir.to_string());
+        self.dirs_location = location;
+        self.dirs.clear();
+        Some(request)
+    }
+
+    /// Fills the upstream picker with the folders of `location`,
+    /// unless the user has moved on to another directory.
+    pub fn set_dirs(&mut self, location: &str, dirs: Vec<String>) {
+        if location == self.dirs_location {
+            self.dirs = dirs;
+            self.upstream_selected = 0;
+        }
+    }
+}
+
+/// Formats a directory of `remote` the way upstreams name it:
+/// `remote:` for its root, `remote:path/` otherwise.
+pub fn upstream_location(remote: &str, dir: &str) -> String {
+    if dir.is_empty() {
+        format!("{}:", remote)
+    } else {
+        format!("{}:{}/", remote, dir)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rclone::{ConfigOption, Provider};
+    use crate::ui::widgets::create_remote::{CreateRemoteMode, RemoteField};
+
+    /// A form for a `type_name` remote named `name` whose upstream
+    /// option has focus and holds `value`.
+    fn upstream_form(type_name: &str, value: &str) -> CreateRemoteModal {
+        let option_name = if type_name == "alias" {
+            "remote"
+        } else {
+            "upstreams"
+        };
+        let backend = Provider {
+            name: type_name.to_string(),
+            options: Some(vec![ConfigOption {
+                name: option_name.to_string(),
+                ..ConfigOption::default()
+            }]),
+            ..Provider::default()
+        };
+        let mut modal = CreateRemoteModal::new(CreateRemoteMode::Create)
+            .with_name("mine".to_string())
+            .with_type(type_name.to_string())
+            .with_providers(vec![backend])
+            .with_remotes(vec!["gdrive".into(), "s3".into(), "mine".into()]);
+        modal.focus_field = RemoteField::Setting(0);
+        value.chars().for_each(|c| modal.input_char(c));
+        modal
+    }
+
+    #[test]
+    fn test_upstream_picker_offers_other_remotes() {
+        let modal = upstream_form("alias", "");
+        assert!(modal.picks_upstream());
+        assert_eq!(modal.upstream_matches(), ["gdrive:", "s3:"]);
+
+        let mut modal = upstream_form("alias", "gd");
+        assert!(modal.accept_upstream());
+        assert_eq!(modal.value("remote"), "gdrive:");
+        assert!(!modal.accept_upstream());
+    }
+
+    #[test]
+    fn test_upstream_picker_descends_into_folders() {
+        let mut modal = upstream_form("alias", "gdrive:");
+        assert_eq!(modal.dirs_to_list(), Some(("gdrive".into(), String::new())));
+        assert_eq!(modal.dirs_to_list(), None);
+        assert!(modal.upstream_matches().is_empty());
+
+        modal.set_dirs("gdrive:", vec!["docs".into(), "photos".into()]);
+        modal.input_char('p');
+        assert_eq!(modal.upstream_matches(), ["gdrive:photos/"]);
+        assert!(modal.accept_upstream());
+        assert_eq!(modal.value("remote"), "gdrive:photos/");
+        assert_eq!(
+            modal.dirs_to_list(),
+            Some(("gdrive".into(), "photos".into()))
+        );
+
+        // A listing for a directory the user has left is dropped.
+        modal.set_dirs("gdrive:", vec!["docs".into()]);
+        assert!(modal.dirs.is_empty());
+    }
+
+    #[test]
+    fn test_upstream_picker_completes_last_word() {
+        let mut modal = upstream_form("union", "gdrive:docs s");
+        assert!(modal.accept_upstream());
+        assert_eq!(modal.value("upstreams"), "gdrive:docs s3:");
+
+        let mut modal = upstream_form("combine", "docs=gdrive:docs pics=gd");
+        assert!(modal.accept_upstream());
+        assert_eq!(modal.value("upstreams"), "docs=gdrive:docs pics=gdrive:");
+    }
+
+    #[test]
+    fn test_other_options_have_no_upstream_picker() {
+        let mut modal = upstream_form("alias", "");
+        modal.remote_type = "s3".to_string();
+        assert!(!modal.picks_upstream());
+        assert!(modal.upstream_matches().is_empty());
+    }
+}
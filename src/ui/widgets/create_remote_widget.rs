@@ -0,0 +1,397 @@
+//! Rendering of the create/edit remote form.
+
+use super::create_remote::{CreateRemoteModal, CreateRemoteMode, RemoteField};
+use crate::ui::layout::modal_area;
+use crate::ui::{Input, Theme, text};
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Position, Rect},
+    style::Style,
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+};
+use std::rc::Rc;
+
+/// Width of the name column in the provider picker.
+const PROVIDER_NAME_WIDTH: usize = 14;
+
+/// Width of the label column in the option list.
+const OPTION_LABEL_WIDTH: usize = 26;
+
+pub struct CreateRemoteWidget;
+
+impl CreateRemoteWidget {
+    pub fn render(f: &mut Frame, area: Rect, modal: &CreateRemoteModal, theme: &Theme) {
+        // Render backdrop to darken background
+        let backdrop = Block::default().style(Style::default().bg(theme.backdrop));
+        f.render_widget(Clear, area);
+        f.render_widget(backdrop, area);
+
+        let (modal_area, chunks) = Self::layout(area);
+
+        // Clear the modal area
+        f.render_widget(Clear, modal_area);
+
+        let block = Block::default()
+            .title(match modal.mode {
+                CreateRemoteMode::Create => " Create Remote ",
+                CreateRemoteMode::Edit => " Edit Remote ",
+            })
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme.border));
+
+        f.render_widget(block, modal_area);
+
+        // Name field
+        Self::render_field(
+            f,
+            chunks[0],
+            "Name",
+            &modal.name,
+            (modal.focus_field == RemoteField::Name).then_some(modal.input),
+            theme,
+        );
+        // Shown while typing, under the name it is about.
+        if let Some(reason) = modal.name_error() {
+            let row = Rect {
+                y: chunks[0].y + 1,
+                height: 1,
+                ..chunks[0]
+            };
+            f.render_widget(
+                Paragraph::new(format!("  Name: {}", reason))
+                    .style(Style::default().fg(theme.error)),
+                row,
+            );
+        }
+
+        // Type field
+        Self::render_field(
+            f,
+            chunks[1],
+            "Type",
+            &modal.remote_type,
+            (modal.focus_field == RemoteField::Type).then_some(modal.input),
+            theme,
+        );
+
+        // Picker, the backend's options, or the path
+        if Self::shows_picker(modal) {
+            Self::render_providers(f, chunks[2], modal, theme);
+        } else if modal.picks_upstream() {
+            Self::render_upstreams(f, chunks[2], modal, theme);
+        } else if modal.has_options() {
+            Self::render_options(f, chunks[2], modal, theme);
+        } else {
+            Self::render_field(
+                f,
+                chunks[2],
+                "Path",
+                &modal.path,
+                (modal.focus_field == RemoteField::Path).then_some(modal.input),
+                theme,
+            );
+        }
+
+        let detail = match (modal.focused_option(), modal.provider()) {
+            (Some(option), _) => option.help.as_str(),
+            (None, Some(provider)) if !Self::shows_picker(modal) => provider.description.as_str(),
+            _ => "",
+        };
+        let detail = Paragraph::new(detail)
+            .style(Style::default().fg(theme.dim))
+            .wrap(Wrap { trim: true });
+        f.render_widget(detail, chunks[3]);
+
+        // Error or help text (single line, smaller font)
+        let help_text = if modal.waiting {
+            "Saving... | Esc: Close".to_string()
+        } else if let Some(ref error) = modal.error {
+            error.clone()
+        } else if Self::shows_picker(modal) || modal.picks_upstream() {
+            "Up/Down: Choose | Tab: Complete | Enter: Save | Esc: Cancel".to_string()
+        } else if modal.has_options() {
+            "Up/Down: Example | Tab: Next | ^A: Advanced | Enter: Save | Esc: Cancel".to_string()
+        } else {
+            "Tab: Next | Enter: Save | Esc: Cancel".to_string()
+        };
+
+        let style = if modal.waiting {
+            Style::default().fg(theme.warning)
+        } else if modal.error.is_some() {
+            Style::default().fg(theme.error)
+        } else {
+            Style::default().fg(theme.dim)
+        };
+
+        let help = Paragraph::new(help_text).style(style);
+        f.render_widget(help, chunks[4]);
+    }
+
+    /// Whether the body lists backends for the type field.
+    fn shows_picker(modal: &CreateRemoteModal) -> bool {
+        modal.mode == CreateRemoteMode::Create
+            && modal.focus_field == RemoteField::Type
+            && !modal.providers.is_empty()
+    }
+
+    /// Lists the backends matching the type field.
+    fn render_providers(f: &mut Frame, area: Rect, modal: &CreateRemoteModal, theme: &Theme) {
+        let matches = modal.type_matches();
+        let offset = Self::offset(modal.type_selected, area);
+        let lines: Vec<Line> = matches
+            .iter()
+            .enumerate()
+            .skip(offset)
+            .take(usize::from(area.height))
+            .map(|(i, provider)| {
+                let name = format!("{:<width$}", provider.name, width = PROVIDER_NAME_WIDTH);
+                if i == modal.type_selected {
+                    Line::styled(
+                        format!(
+                            "{}{} {}",
+                            theme.highlight_symbol(),
+                            name,
+                            provider.description
+                        ),
+                        theme.selected(),
+                    )
+                } else {
+                    Line::from(vec![
+                        Span::raw(name),
+                        Span::styled(
+                            format!(" {}", provider.description),
+                            Style::default().fg(theme.dim),
+                        ),
+                    ])
+                }
+            })
+            .collect();
+        f.render_widget(Paragraph::new(lines), area);
+    }
+
+    /// Shows the upstream option being edited, scrolled to the
+    /// cursor, above the remotes or folders it can complete to.
+    fn render_upstreams(f: &mut Frame, area: Rect, modal: &CreateRemoteModal, theme: &Theme) {
+        let Some(option) = modal.focused_option() else {
+            return;
+        };
+        let value = modal.value(&option.name);
+        let [field, list] =
+            Layout::vertical([Constraint::Length(2), Constraint::Min(0)]).areas(area);
+        Self::render_field(f, field, &option.name, value, Some(modal.input), theme);
+
+        let offset = Self::offset(modal.upstream_selected, list);
+        let lines: Vec<Line> = modal
+            .upstream_matches()
+            .into_iter()
+            .enumerate()
+            .skip(offset)
+            .take(usize::from(list.height))
+            .map(|(i, upstream)| {
+                if i == modal.upstream_selected {
+                    Line::styled(
+                        format!("{}{}", theme.highlight_symbol(), upstream),
+                        theme.selected(),
+                    )
+                } else {
+                    Line::raw(upstream)
+                }
+            })
+            .collect();
+        f.render_widget(Paragraph::new(lines), list);
+    }
+
+    /// Lists the backend's options, one per row: required ones marked
+    /// with `*`, secrets masked, and defaults shown dimmed until a
+    /// value is typed.
+    fn render_options(f: &mut Frame, area: Rect, modal: &CreateRemoteModal, theme: &Theme) {
+        let focused = match modal.focus_field {
+            RemoteField::Setting(i) => Some(i),
+            _ => None,
+        };
+        let offset = Self::offset(focused.unwrap_or(0), area);
+        let lines: Vec<Line> = modal
+            .options()
+            .into_iter()
+            .enumerate()
+            .skip(offset)
+            .take(usize::from(area.height))
+            .map(|(i, option)| {
+                let marker = if option.required { "*" } else { "" };
+                let label = format!(
+                    "{:<width$}",
+                    format!("{}{}", option.name, marker),
+                    width = OPTION_LABEL_WIDTH
+                );
+                let text = modal.value(&option.name);
+                let value = if text.is_empty() {
+                    let placeholder =
+                        if option.is_secret() && modal.original.contains_key(&option.name) {
+                            "(unchanged)".to_string()
+                        } else if option.default.is_empty() {
+                            String::new()
+                        } else {
+                            format!("default: {}", option.default)
+                        };
+                    Span::styled(placeholder, Style::default().fg(theme.dim))
+                } else if option.is_secret() {
+                    Span::raw("*".repeat(text::graphemes(text)))
+                } else {
+                    Span::raw(text)
+                };
+                if focused == Some(i) {
+                    let shown = if option.is_secret() {
+                        "*".repeat(text::graphemes(text))
+                    } else {
+                        text.to_string()
+                    };
+                    let width = usize::from(area.width).saturating_sub(OPTION_LABEL_WIDTH + 1);
+                    let mut spans = vec![Span::raw(label), Span::raw(" ")];
+                    spans.extend(modal.input.spans(&shown, width));
+                    if text.is_empty() {
+                        spans.push(Span::raw(value.content));
+                    }
+                    Line::from(spans).style(theme.selected())
+                } else {
+                    Line::from(vec![Span::raw(label), Span::raw(" "), value])
+                }
+            })
+            .collect();
+        f.render_widget(Paragraph::new(lines), area);
+    }
+
+    /// Returns the first row to draw so that row `selected` stays
+    /// within `area`.
+    fn offset(selected: usize, area: Rect) -> usize {
+        selected.saturating_sub(usize::from(area.height).max(1) - 1)
+    }
+
+    /// Returns the field at a screen position, if any.
+    ///
+    /// `area` must be the same area passed to [`Self::render`], along
+    /// with the same modal.
+    pub fn field_at(
+        area: Rect,
+        modal: &CreateRemoteModal,
+        column: u16,
+        row: u16,
+    ) -> Option<RemoteField> {
+        let (_, chunks) = Self::layout(area);
+        let position = Position::new(column, row);
+        if chunks[0].contains(position) {
+            return Some(RemoteField::Name);
+        }
+        if chunks[1].contains(position) {
+            return Some(RemoteField::Type);
+        }
+        let body = chunks[2];
+        if !body.contains(position) || Self::shows_picker(modal) || modal.picks_upstream() {
+            return None;
+        }
+        if !modal.has_options() {
+            return (row < body.y + 2).then_some(RemoteField::Path);
+        }
+        let focused = match modal.focus_field {
+            RemoteField::Setting(i) => i,
+            _ => 0,
+        };
+        let index = Self::offset(focused, body) + usize::from(row - body.y);
+        (index < modal.options().len()).then_some(RemoteField::Setting(index))
+    }
+
+    /// Returns the modal area and its rows: name, type, a body for the
+    /// picker, options or path, the focused option's help, then help.
+    fn layout(area: Rect) -> (Rect, Rc<[Rect]>) {
+        let modal_area = modal_area(area, 76, 22);
+
+        let inner = Rect {
+            x: modal_area.x + 1,
+            y: modal_area.y + 1,
+            width: modal_area.width.saturating_sub(2),
+            height: modal_area.height.saturating_sub(2),
+        };
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(2),
+                Constraint::Length(2),
+                Constraint::Min(1),
+                Constraint::Length(3),
+                Constraint::Length(1),
+            ])
+            .split(inner);
+        (modal_area, chunks)
+    }
+
+    fn render_field(
+        f: &mut Frame,
+        area: Rect,
+        label: &str,
+        value: &str,
+        cursor: Option<Input>,
+        theme: &Theme,
+    ) {
+        let focused = cursor.is_some();
+        let prefix = format!("{}: ", label);
+        let mut spans = vec![Span::raw(prefix.clone())];
+        match cursor {
+            Some(input) => {
+                let width = usize::from(area.width).saturating_sub(text::width(&prefix));
+                spans.extend(input.spans(value, width));
+            }
+            None if text::width(value) > 30 => {
+                spans.push(Span::raw(format!("{}...", text::take_width(value, 27))));
+            }
+            None => spans.push(Span::raw(value.to_string())),
+        }
+
+        let paragraph = Paragraph::new(Line::from(spans)).style(if focused {
+            Style::default().fg(theme.heading).bold()
+        } else {
+            Style::default()
+        });
+
+        f.render_widget(paragraph, area);
+
+        // Minimal bottom border for focused field
+        if focused && area.height > 1 {
+            let bottom_line = "─".repeat(area.width as usize);
+            let bottom = Paragraph::new(bottom_line).style(Style::default().fg(theme.border));
+            let bottom_area = Rect {
+                y: area.y + 1,
+                height: 1,
+                ..area
+            };
+            f.render_widget(bottom, bottom_area);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_field_at_rows() {
+        let area = Rect::new(0, 0, 80, 24);
+        let (modal_area, _) = CreateRemoteWidget::layout(area);
+        let modal = CreateRemoteModal::new(CreateRemoteMode::Create);
+        let column = modal_area.x + 2;
+
+        assert_eq!(
+            CreateRemoteWidget::field_at(area, &modal, column, modal_area.y + 1),
+            Some(RemoteField::Name)
+        );
+        assert_eq!(
+            CreateRemoteWidget::field_at(area, &modal, column, modal_area.y + 3),
+            Some(RemoteField::Type)
+        );
+        assert_eq!(
+            CreateRemoteWidget::field_at(area, &modal, column, modal_area.y + 6),
+            Some(RemoteField::Path)
+        );
+        assert_eq!(CreateRemoteWidget::field_at(area, &modal, 0, 0), None);
+    }
+}
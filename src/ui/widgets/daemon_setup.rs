@@ -0,0 +1,128 @@
+//! Dialog shown at startup when no rclone daemon answers: it offers to
+//! start `rclone rcd` for the session when rclone is installed, and
+//! says how to install it when it is not.
+
+use crate::daemon::install_commands;
+use crate::ui::Theme;
+use crate::ui::layout::modal_area;
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::Style,
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+};
+use std::path::PathBuf;
+
+/// Daemon setup dialog state.
+#[derive(Debug, Clone, Default)]
+pub struct DaemonSetup {
+    /// Address no daemon answered at.
+    pub address: String,
+    /// The rclone binary found on `PATH`, if any.
+    pub rclone: Option<PathBuf>,
+    /// Whether a daemon is being started.
+    pub starting: bool,
+    /// Why starting one failed, if it did.
+    pub error: Option<String>,
+}
+
+impl DaemonSetup {
+    /// Creates the dialog for `address`, with the rclone binary found
+    /// on `PATH`, if any.
+    pub fn new(address: String, rclone: Option<PathBuf>) -> Self {
+        Self {
+            address,
+            rclone,
+            ..Self::default()
+        }
+    }
+}
+
+/// Widget for the daemon setup dialog.
+pub struct DaemonSetupWidget;
+
+impl DaemonSetupWidget {
+    pub fn render(f: &mut Frame, area: Rect, setup: &DaemonSetup, theme: &Theme) {
+        f.render_widget(Clear, area);
+        f.render_widget(
+            Block::default().style(Style::default().bg(theme.backdrop)),
+            area,
+        );
+
+        let modal_area = modal_area(area, 72, 16);
+        f.render_widget(Clear, modal_area);
+        f.render_widget(
+            Block::default()
+                .title(" No rclone Daemon ")
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(theme.border)),
+            modal_area,
+        );
+
+        let inner = Rect {
+            x: modal_area.x + 1,
+            y: modal_area.y + 1,
+            width: modal_area.width.saturating_sub(2),
+            height: modal_area.height.saturating_sub(2),
+        };
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(3), Constraint::Length(1)])
+            .split(inner);
+
+        let mut lines = vec![
+            Line::raw(format!("No rclone daemon answers at {}.", setup.address)),
+            Line::default(),
+        ];
+        let help = match setup.rclone {
+            Some(ref rclone) => {
+                lines.push(Line::raw(format!(
+                    "rclone is installed at {}.",
+                    rclone.display()
+                )));
+                lines.push(Line::raw(
+                    "LazyFile can start `rclone rcd` for this session, on a free loopback \
+                     port behind a random login, and stop it when you quit.",
+                ));
+                "s/Enter: Start rclone rcd | r: Check again | Esc: Close | q: Quit"
+            }
+            None => {
+                lines.push(Line::raw(
+                    "rclone is not installed, or not on PATH. To install it:",
+                ));
+                lines.push(Line::default());
+                for (command, applies) in install_commands() {
+                    lines.push(Line::from(vec![
+                        Span::styled(format!("  {}", command), Style::default().fg(theme.accent)),
+                        Span::styled(format!("  ({})", applies), Style::default().fg(theme.dim)),
+                    ]));
+                }
+                lines.push(Line::default());
+                lines.push(Line::raw(
+                    "Then press r, or start `rclone rcd` yourself and LazyFile connects \
+                     once it answers.",
+                ));
+                "r: Check again | Esc: Close | q: Quit"
+            }
+        };
+        if setup.starting {
+            lines.push(Line::default());
+            lines.push(Line::styled(
+                "Starting rclone rcd...",
+                Style::default().fg(theme.warning),
+            ));
+        } else if let Some(ref error) = setup.error {
+            lines.push(Line::default());
+            lines.push(Line::styled(
+                format!("Could not start it: {}", error),
+                Style::default().fg(theme.error),
+            ));
+        }
+        f.render_widget(Paragraph::new(lines).wrap(Wrap { trim: false }), chunks[0]);
+        f.render_widget(
+            Paragraph::new(help).style(Style::default().fg(theme.dim)),
+            chunks[1],
+        );
+    }
+}
@@ -0,0 +1,284 @@
+//! Error modal shown when an rclone request fails mid-session.
+
+use crate::error::LazyFileError;
+use crate::ui::Theme;
+use crate::ui::layout::modal_area;
+use ratatui::{
+    Frame,
+    layout::{Alignment, Constraint, Direction, Layout, Position, Rect},
+    style::Style,
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+};
+
+/// Error modal state.
+#[derive(Debug, Clone)]
+pub struct ErrorModal {
+    /// One-line summary of what went wrong.
+    pub summary: String,
+    /// RC endpoint the failing request went to, if known.
+    pub endpoint: Option<&'static str>,
+    /// HTTP status the daemon answered with, if it answered.
+    pub status: Option<String>,
+    /// Response body or underlying error text.
+    pub details: String,
+    /// Highlighted button.
+    pub selected: ErrorChoice,
+}
+
+/// Buttons of the error modal.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ErrorChoice {
+    /// Run the failed operation again.
+    Retry,
+    /// Dismiss the error and carry on.
+    Ignore,
+}
+
+impl ErrorModal {
+    /// Builds the modal for `err`.
+    ///
+    /// For an HTTP error status the rclone `error` field becomes the
+    /// summary and the pretty-printed JSON body the details.
+    pub fn new(err: &LazyFileError) -> Self {
+        let (endpoint, status, summary, details) = match err {
+            LazyFileError::RcloneStatus {
+                endpoint,
+                status,
+                body,
+            } => {
+                let json = serde_json::from_str::<serde_json::Value>(body).ok();
+                let summary = json
+                    .as_ref()
+                    .and_then(|v| v.get("error"))
+                    .and_then(serde_json::Value::as_str)
+                    .map_or_else(|| format!("request to {} failed", endpoint), str::to_string);
+                let details = json
+                    .and_then(|v| serde_json::to_string_pretty(&v).ok())
+                    .unwrap_or_else(|| body.clone());
+                (Some(*endpoint), Some(status.to_string()), summary, details)
+            }
+            LazyFileError::RcloneApi { endpoint, message } => (
+                Some(*endpoint),
+                None,
+                format!("request to {} failed", endpoint),
+                message.clone(),
+            ),
+            other => (None, None, other.to_string(), String::new()),
+        };
+        Self {
+            summary,
+            endpoint,
+            status,
+            details,
+            selected: ErrorChoice::Retry,
+        }
+    }
+
+    /// Moves the highlight to the other button.
+    pub fn toggle(&mut self) {
+        self.selected = match self.selected {
+            ErrorChoice::Retry => ErrorChoice::Ignore,
+            ErrorChoice::Ignore => ErrorChoice::Retry,
+        };
+    }
+}
+
+/// Widget for the error modal.
+pub struct ErrorWidget;
+
+/// Screen areas of the error modal's parts.
+struct ErrorLayout {
+    modal: Rect,
+    body: Rect,
+    retry: Rect,
+    ignore: Rect,
+    help: Rect,
+}
+
+impl ErrorWidget {
+    /// Render the error modal centered in `area`.
+    ///
+    /// # Arguments
+    /// * `f` - Frame for rendering
+    /// * `area` - Area to center the modal in
+    /// * `modal` - Error to show
+    /// * `theme` - Colors to draw with
+    pub fn render(f: &mut Frame, area: Rect, modal: &ErrorModal, theme: &Theme) {
+        f.render_widget(Clear, area);
+        f.render_widget(
+            Block::default().style(Style::default().bg(theme.backdrop)),
+            area,
+        );
+
+        let layout = Self::layout(area);
+        f.render_widget(Clear, layout.modal);
+        f.render_widget(
+            Block::default()
+                .title(" Error ")
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(theme.error)),
+            layout.modal,
+        );
+
+        let label = Style::default().fg(theme.dim);
+        let mut lines = vec![
+            Line::styled(
+                modal.summary.clone(),
+                Style::default().fg(theme.error).bold(),
+            ),
+            Line::default(),
+        ];
+        if let Some(endpoint) = modal.endpoint {
+            lines.push(Line::from(vec![
+                Span::styled("Endpoint: ", label),
+                Span::raw(endpoint),
+            ]));
+        }
+        if let Some(ref status) = modal.status {
+            lines.push(Line::from(vec![
+                Span::styled("Status:   ", label),
+                Span::raw(status.clone()),
+            ]));
+        }
+        if !modal.details.is_empty() {
+            lines.push(Line::default());
+            lines.extend(modal.details.lines().map(|l| Line::raw(l.to_string())));
+        }
+        f.render_widget(
+            Paragraph::new(lines).wrap(Wrap { trim: false }),
+            layout.body,
+        );
+
+        let button = |text, choice, color| {
+            let style = if modal.selected == choice {
+                Style::default().fg(theme.selection_fg).bg(color).bold()
+            } else {
+                Style::default().fg(color)
+            };
+            Paragraph::new(theme.button(text, modal.selected == choice))
+                .style(style)
+                .alignment(Alignment::Center)
+        };
+        f.render_widget(
+            button("Retry", ErrorChoice::Retry, theme.heading),
+            layout.retry,
+        );
+        f.render_widget(
+            button("Ignore", ErrorChoice::Ignore, theme.dim),
+            layout.ignore,
+        );
+
+        let help = Paragraph::new("r: Retry | i/Esc: Ignore | Tab: Switch | Enter: Choose")
+            .style(Style::default().fg(theme.dim));
+        f.render_widget(help, layout.help);
+    }
+
+    /// Returns the button at a screen position, if any.
+    ///
+    /// `area` must be the same area passed to [`Self::render`].
+    pub fn choice_at(area: Rect, column: u16, row: u16) -> Option<ErrorChoice> {
+        let layout = Self::layout(area);
+        let position = Position::new(column, row);
+        if layout.retry.contains(position) {
+            Some(ErrorChoice::Retry)
+        } else if layout.ignore.contains(position) {
+            Some(ErrorChoice::Ignore)
+        } else {
+            None
+        }
+    }
+
+    fn layout(area: Rect) -> ErrorLayout {
+        let modal = modal_area(area, 70, 18);
+        let inner = Rect {
+            x: modal.x + 1,
+            y: modal.y + 1,
+            width: modal.width.saturating_sub(2),
+            height: modal.height.saturating_sub(2),
+        };
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Min(1),
+                Constraint::Length(2),
+                Constraint::Length(1),
+            ])
+            .split(inner);
+        let buttons = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+            .split(chunks[1]);
+
+        ErrorLayout {
+            modal,
+            body: chunks[0],
+            retry: buttons[0],
+            ignore: buttons[1],
+            help: chunks[2],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_status_error_uses_rclone_message() {
+        let err = LazyFileError::RcloneStatus {
+            endpoint: "operations/list",
+            status: reqwest::StatusCode::NOT_FOUND,
+            body: r#"{"error":"directory not found","status":404}"#.to_string(),
+        };
+        let modal = ErrorModal::new(&err);
+
+        assert_eq!(modal.summary, "directory not found");
+        assert_eq!(modal.endpoint, Some("operations/list"));
+        assert_eq!(modal.status.as_deref(), Some("404 Not Found"));
+        assert!(modal.details.contains("\"status\": 404"));
+    }
+
+    #[test]
+    fn test_non_json_body_is_kept_verbatim() {
+        let err = LazyFileError::RcloneStatus {
+            endpoint: "config/create",
+            status: reqwest::StatusCode::BAD_GATEWAY,
+            body: "upstream down".to_string(),
+        };
+        let modal = ErrorModal::new(&err);
+
+        assert_eq!(modal.summary, "request to config/create failed");
+        assert_eq!(modal.details, "upstream down");
+    }
+
+    #[test]
+    fn test_defaults_to_retry_and_toggles() {
+        let err = LazyFileError::InvalidInput {
+            field: "path",
+            reason: "contains '..'",
+        };
+        let mut modal = ErrorModal::new(&err);
+        assert_eq!(modal.selected, ErrorChoice::Retry);
+
+        modal.toggle();
+        assert_eq!(modal.selected, ErrorChoice::Ignore);
+    }
+
+    #[test]
+    fn test_choice_at_buttons() {
+        let area = Rect::new(0, 0, 80, 24);
+        let layout = ErrorWidget::layout(area);
+
+        assert_eq!(
+            ErrorWidget::choice_at(area, layout.retry.x, layout.retry.y),
+            Some(ErrorChoice::Retry)
+        );
+        assert_eq!(
+            ErrorWidget::choice_at(area, layout.ignore.x, layout.ignore.y),
+            Some(ErrorChoice::Ignore)
+        );
+        assert_eq!(ErrorWidget::choice_at(area, 0, 0), None);
+    }
+}
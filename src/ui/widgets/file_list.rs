@@ -1,12 +1,147 @@
 //! Files list widget.
+//!
+//! Only the rows on screen are built each frame, so drawing costs the
+//! same for a directory of ten entries as for one of a hundred
+//! thousand. Names are colored by their kind of file. With Nerd Font
+//! icons on, each carries a glyph for it; otherwise directories are
+//! bracketed. In the tree view, nested rows are indented below their
+//! directory.
 
-use crate::rclone::FileItem;
-use crate::ui::styles;
+use super::usage::format_size;
+use crate::app::Tree;
+use crate::app::flat::FlatListing;
+use crate::app::tree::{depth, leaf_name};
+use crate::config::{Column, FileListConfig, IconSet};
+use crate::rclone::{FileItem, Filter};
+use crate::ui::text::{self, pad, truncate};
+use crate::ui::{FileColors, Theme};
 use ratatui::{
     Frame,
-    layout::Rect,
-    widgets::{Block, Borders, List, ListItem},
+    layout::{Margin, Rect},
+    style::Style,
+    text::{Line, Span},
+    widgets::{
+        Block, Borders, List, ListItem, ListState, Scrollbar, ScrollbarOrientation, ScrollbarState,
+    },
 };
+use std::collections::BTreeSet;
+use std::ops::Range;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Narrowest the name column gets before columns on the right are
+/// dropped to make room.
+const MIN_NAME_WIDTH: usize = 12;
+
+/// Extensions of images.
+const IMAGES: &[&str] = &[
+    "png", "jpg", "jpeg", "gif", "webp", "bmp", "svg", "tif", "tiff", "heic", "ico",
+];
+
+/// Extensions of videos.
+const VIDEOS: &[&str] = &["mp4", "mkv", "mov", "avi", "webm", "m4v", "wmv"];
+
+/// Extensions of audio files.
+const AUDIO: &[&str] = &["mp3", "flac", "wav", "ogg", "m4a", "aac", "opus"];
+
+/// Extensions of archives.
+const ARCHIVES: &[&str] = &["zip", "tar", "gz", "tgz", "bz2", "xz", "zst", "7z", "rar"];
+
+/// Extensions of source code and structured text.
+const CODE: &[&str] = &[
+    "rs", "py", "js", "ts", "go", "c", "h", "cpp", "java", "rb", "sh", "toml", "json", "yaml",
+    "yml", "html", "css",
+];
+
+/// Extensions of documents.
+const DOCUMENTS: &[&str] = &[
+    "txt", "md", "doc", "docx", "odt", "rtf", "csv", "xls", "xlsx",
+];
+
+/// Kinds of file, each with its own icon and color.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileKind {
+    /// A directory.
+    Dir,
+    /// Pictures such as PNG or JPEG.
+    Image,
+    /// Videos such as MP4.
+    Video,
+    /// Audio such as MP3 or FLAC.
+    Audio,
+    /// Archives such as zip or tar.
+    Archive,
+    /// Source code and structured text.
+    Code,
+    /// Text documents and spreadsheets.
+    Document,
+    /// PDF documents.
+    Pdf,
+    /// Anything else.
+    Other,
+}
+
+impl FileKind {
+    /// Returns the kind of `item`, by its extension.
+    pub fn of(item: &FileItem) -> Self {
+        if item.is_dir() {
+            return Self::Dir;
+        }
+        let extension = item
+            .name()
+            .rsplit_once('.')
+            .map(|(_, extension)| extension.to_ascii_lowercase())
+            .unwrap_or_default();
+        [
+            (IMAGES, Self::Image),
+            (VIDEOS, Self::Video),
+            (AUDIO, Self::Audio),
+            (ARCHIVES, Self::Archive),
+            (CODE, Self::Code),
+            (DOCUMENTS, Self::Document),
+            (&["pdf"][..], Self::Pdf),
+        ]
+        .into_iter()
+        .find(|(extensions, _)| extensions.contains(&extension.as_str()))
+        .map_or(Self::Other, |(_, kind)| kind)
+    }
+
+    /// Returns the Nerd Font glyph.
+    pub fn nerd_icon(self) -> &'static str {
+        match self {
+            Self::Dir => "\u{f07b}",
+            Self::Image => "\u{f1c5}",
+            Self::Video => "\u{f1c8}",
+            Self::Audio => "\u{f1c7}",
+            Self::Archive => "\u{f1c6}",
+            Self::Code => "\u{f1c9}",
+            Self::Document => "\u{f15c}",
+            Self::Pdf => "\u{f1c1}",
+            Self::Other => "\u{f15b}",
+        }
+    }
+}
+
+/// Columns the files panel is drawn with.
+#[derive(Debug, Clone, Copy)]
+pub struct ColumnView<'a> {
+    /// Columns, their widths and the hash type.
+    pub config: &'a FileListConfig,
+    /// Whether sizes are exact bytes and times ISO 8601, instead of
+    /// `1.5 MiB` and `3h ago`.
+    pub exact: bool,
+}
+
+/// How names in the files panel are drawn.
+#[derive(Debug, Clone, Copy)]
+pub struct NameView<'a> {
+    /// Glyphs marking each kind of file.
+    pub icons: IconSet,
+    /// Colors of each kind of file.
+    pub colors: &'a FileColors,
+    /// Expanded subdirectories, if the panel is a tree; rows are then
+    /// indented by depth and directories carry `▸` or `▾`.
+    pub tree: Option<&'a Tree>,
+}
 
 /// Widget for displaying files and directories.
 pub struct FileListWidget;
@@ -18,40 +153,350 @@ impl FileListWidget {
     /// * `f` - Frame for rendering
     /// * `area` - Area to render in
     /// * `files` - List of navigation items
+    /// * `offset` - Index of the first visible row
     /// * `selected` - Index of selected item
+    /// * `marked` - Names of the marked items
+    /// * `filter` - Size and age limits the listing was made with
+    /// * `flat` - Order and pattern of the recursive listing, if the
+    ///   panel shows one
+    /// * `columns` - Columns after the name, or `None` for names only
+    /// * `names` - Icons and colors of the names
+    /// * `offline` - Changes queued while the daemon is unreachable,
+    ///   or `None` while it answers
     /// * `focused` - Whether this panel is focused
-    pub fn render(f: &mut Frame, area: Rect, files: &[FileItem], selected: usize, focused: bool) {
-        let items: Vec<ListItem> = files
+    /// * `theme` - Colors to draw with
+    #[expect(clippy::too_many_arguments)]
+    pub fn render(
+        f: &mut Frame,
+        area: Rect,
+        files: &[FileItem],
+        offset: usize,
+        selected: usize,
+        marked: &BTreeSet<String>,
+        filter: &Filter,
+        flat: Option<&FlatListing>,
+        columns: Option<ColumnView>,
+        names: NameView,
+        offline: Option<usize>,
+        focused: bool,
+        theme: &Theme,
+    ) {
+        // Panels have a one-row border above and below.
+        let rows = usize::from(area.height.saturating_sub(2));
+        let window = visible_window(files.len(), offset, rows);
+        // The selection marker, if any, takes cells from every row.
+        let row_width = usize::from(area.width.saturating_sub(2))
+            .saturating_sub(text::width(theme.highlight_symbol()));
+        let layout = columns.map_or_else(Vec::new, |view| fit_columns(view.config, row_width));
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |elapsed| elapsed.as_secs() as i64);
+        let name_width = row_width.saturating_sub(layout.iter().map(|(_, width)| width + 1).sum());
+        let items: Vec<ListItem> = files[window.clone()]
             .iter()
             .map(|item| {
-                let name = if item.is_dir() {
-                    format!("[{}]", item.name())
+                let kind = FileKind::of(item);
+                let leaf = leaf_name(item.name());
+                let mut name = match (names.icons, kind) {
+                    (IconSet::Nerd, _) => format!("{} {}", kind.nerd_icon(), leaf),
+                    (_, FileKind::Dir) => format!("[{}]", leaf),
+                    _ => leaf.to_string(),
+                };
+                if let Some(tree) = names.tree {
+                    let branch = match kind {
+                        FileKind::Dir if tree.is_expanded(item.name()) => "▾ ",
+                        FileKind::Dir => "▸ ",
+                        _ => "  ",
+                    };
+                    name = format!("{}{}{}", "  ".repeat(depth(item.name())), branch, name);
+                }
+                let (style, name_style) = if marked.contains(item.name()) {
+                    name = format!("* {}", name);
+                    (Style::new().fg(theme.accent).bold(), Style::new())
                 } else {
-                    item.name().to_string()
+                    (Style::new(), names.colors.style(item, kind, theme))
                 };
-                ListItem::new(name)
+                let Some(view) = columns.filter(|_| !layout.is_empty()) else {
+                    return ListItem::new(Line::styled(name, name_style)).style(style);
+                };
+                let mut spans = vec![Span::styled(pad(&name, name_width), name_style)];
+                for &(column, width) in &layout {
+                    let text = cell(item, column, view, now);
+                    let text = if column == Column::Size {
+                        format!(" {:>width$}", truncate(&text, width))
+                    } else {
+                        format!(" {}", pad(&text, width))
+                    };
+                    spans.push(Span::styled(text, Style::new().fg(theme.dim)));
+                }
+                ListItem::new(Line::from(spans)).style(style)
             })
             .collect();
 
         let border_style = if focused {
-            styles::focused_style()
+            theme.focused()
         } else {
-            styles::NORMAL_STYLE
+            Style::new()
         };
 
+        let mut title = " Files ".to_string();
+        if let Some(flat) = flat {
+            title.push_str(&format!("[{}] ", flat.describe()));
+        }
+        if !filter.is_empty() {
+            title.push_str(&format!("[{}] ", filter.describe()));
+        }
+        if !marked.is_empty() {
+            title.push_str(&format!("({} marked) ", marked.len()));
+        }
+        let mut title = vec![Span::raw(title)];
+        if let Some(queued) = offline {
+            let mut banner = "OFFLINE (cached) ".to_string();
+            if queued > 0 {
+                banner.push_str(&format!("{} queued ", queued));
+            }
+            title.push(Span::styled(banner, Style::new().fg(theme.warning).bold()));
+        }
         let list = List::new(items)
             .block(
                 Block::default()
                     .borders(Borders::ALL)
-                    .title(" Files ")
+                    .title(Line::from(title))
                     .border_style(border_style),
             )
-            .style(styles::NORMAL_STYLE)
-            .highlight_style(styles::selected_style());
-
-        let mut list_state = ratatui::widgets::ListState::default();
-        list_state.select(Some(selected));
+            .style(Style::new())
+            .highlight_style(theme.selected())
+            .highlight_symbol(theme.highlight_symbol());
 
+        let mut list_state = ListState::default().with_selected(
+            selected
+                .checked_sub(window.start)
+                .filter(|&row| row < window.len()),
+        );
         f.render_stateful_widget(list, area, &mut list_state);
+
+        if files.len() > rows {
+            let mut scrollbar = ScrollbarState::new(files.len() - rows + 1)
+                .viewport_content_length(rows)
+                .position(window.start.min(files.len() - rows));
+            f.render_stateful_widget(
+                Scrollbar::new(ScrollbarOrientation::VerticalRight)
+                    .begin_symbol(None)
+                    .end_symbol(None)
+                    .thumb_style(border_style),
+                area.inner(Margin {
+                    vertical: 1,
+                    horizontal: 0,
+                }),
+                &mut scrollbar,
+            );
+        }
+    }
+}
+
+/// Returns the configured columns with their widths, dropping those
+/// on the right that would leave the name less than
+/// [`MIN_NAME_WIDTH`] of the `width` cells.
+fn fit_columns(config: &FileListConfig, width: usize) -> Vec<(Column, usize)> {
+    let mut layout: Vec<(Column, usize)> = config
+        .columns
+        .iter()
+        .map(|&column| (column, usize::from(config.width(column))))
+        .collect();
+    while !layout.is_empty()
+        && layout.iter().map(|(_, w)| w + 1).sum::<usize>() + MIN_NAME_WIDTH > width
+    {
+        layout.pop();
+    }
+    layout
+}
+
+/// Returns the text of `column` for `item`, with times relative to
+/// `now` in Unix seconds unless the view is exact.
+fn cell(item: &FileItem, column: Column, view: ColumnView, now: i64) -> String {
+    match column {
+        Column::Size if item.is_dir() => String::new(),
+        Column::Size if item.size < 0 => "-".to_string(),
+        Column::Size if view.exact => item.size.to_string(),
+        Column::Size => format_size(item.size.unsigned_abs()),
+        Column::Modtime if view.exact => whole_seconds(&item.mod_time),
+        Column::Modtime => match unix_time(&item.mod_time) {
+            Some(then) => ago(then, now),
+            None => item.mod_time.clone(),
+        },
+        Column::Mime => item.mime_type.clone(),
+        Column::Hash => item
+            .hashes
+            .as_ref()
+            .and_then(|hashes| hashes.get(&view.config.hash))
+            .cloned()
+            .unwrap_or_default(),
+    }
+}
+
+/// Drops the fraction of a second from an RFC 3339 time, keeping the
+/// offset: `2024-01-31T12:00:00.5Z` becomes `2024-01-31T12:00:00Z`.
+fn whole_seconds(time: &str) -> String {
+    match time.get(19..) {
+        Some(rest) if rest.starts_with('.') => {
+            let offset = rest.trim_start_matches(|c: char| c == '.' || c.is_ascii_digit());
+            format!("{}{}", &time[..19], offset)
+        }
+        _ => time.to_string(),
+    }
+}
+
+/// Parses an RFC 3339 time such as rclone's `ModTime` into Unix
+/// seconds.
+fn unix_time(time: &str) -> Option<i64> {
+    let number = |range: Range<usize>| time.get(range)?.parse::<i64>().ok();
+    let (year, month, day) = (number(0..4)?, number(5..7)?, number(8..10)?);
+    let (hour, minute, second) = (number(11..13)?, number(14..16)?, number(17..19)?);
+    let rest = time
+        .get(19..)?
+        .trim_start_matches(|c: char| c == '.' || c.is_ascii_digit());
+    let offset = match rest.chars().next()? {
+        'Z' | 'z' => 0,
+        sign @ ('+' | '-') => {
+            let minutes =
+                rest.get(1..3)?.parse::<i64>().ok()? * 60 + rest.get(4..6)?.parse::<i64>().ok()?;
+            if sign == '-' {
+                -minutes * 60
+            } else {
+                minutes * 60
+            }
+        }
+        _ => return None,
+    };
+    // Days since 1970-01-01 in the proleptic Gregorian calendar, by
+    // counting from a March-based year so leap days come last.
+    let y = if month <= 2 { year - 1 } else { year };
+    let era = y.div_euclid(400);
+    let year_of_era = y - era * 400;
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    let days = era * 146_097 + day_of_era - 719_468;
+    Some(days * 86_400 + hour * 3_600 + minute * 60 + second - offset)
+}
+
+/// Describes how long before `now` the time `then` was, e.g. `3h ago`.
+pub(crate) fn ago(then: i64, now: i64) -> String {
+    let seconds = now - then;
+    let (count, unit) = match seconds {
+        ..0 => return "in the future".to_string(),
+        0..60 => return "just now".to_string(),
+        60..3_600 => (seconds / 60, "m"),
+        3_600..86_400 => (seconds / 3_600, "h"),
+        86_400..2_592_000 => (seconds / 86_400, "d"),
+        2_592_000..31_536_000 => (seconds / 2_592_000, "mo"),
+        _ => (seconds / 31_536_000, "y"),
+    };
+    format!("{}{} ago", count, unit)
+}
+
+/// Returns the indices of the items shown in `rows` rows starting at
+/// `offset`. The offset is used as it is, so rows line up with the
+/// ones mouse clicks are mapped to.
+pub fn visible_window(len: usize, offset: usize, rows: usize) -> Range<usize> {
+    let start = offset.min(len);
+    start..len.min(start.saturating_add(rows))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_window_covers_visible_rows() {
+        assert_eq!(visible_window(100_000, 500, 20), 500..520);
+        assert_eq!(visible_window(5, 0, 20), 0..5);
+        assert_eq!(visible_window(0, 3, 20), 0..0);
+    }
+
+    #[test]
+    fn test_columns_make_room_for_name() {
+        let config = FileListConfig {
+            columns: vec![Column::Size, Column::Modtime, Column::Mime],
+            ..FileListConfig::default()
+        };
+        assert_eq!(fit_columns(&config, 80).len(), 3);
+        assert_eq!(
+            fit_columns(&config, 60),
+            [(Column::Size, 12), (Column::Modtime, 25)]
+        );
+        assert!(fit_columns(&config, 20).is_empty());
+    }
+
+    #[test]
+    fn test_kinds_follow_extension() {
+        let file = |name: &str| FileItem {
+            name: name.to_string(),
+            ..Default::default()
+        };
+        assert_eq!(FileKind::of(&file("photo.JPG")), FileKind::Image);
+        assert_eq!(FileKind::of(&file("main.rs")), FileKind::Code);
+        assert_eq!(FileKind::of(&file("Makefile")), FileKind::Other);
+        let dir = FileItem {
+            is_dir: true,
+            ..file("docs")
+        };
+        assert_eq!(FileKind::of(&dir), FileKind::Dir);
+    }
+
+    #[test]
+    fn test_cells() {
+        let item = FileItem {
+            name: "a.txt".to_string(),
+            size: 2048,
+            mod_time: "2024-01-31T12:00:00.5Z".to_string(),
+            is_dir: false,
+            ..Default::default()
+        };
+        let config = FileListConfig::default();
+        let mut view = ColumnView {
+            config: &config,
+            exact: false,
+        };
+        let now = unix_time("2024-01-31T15:30:00Z").unwrap();
+        assert_eq!(cell(&item, Column::Size, view, now), "2.0 KiB");
+        assert_eq!(cell(&item, Column::Modtime, view, now), "3h ago");
+        assert_eq!(cell(&item, Column::Hash, view, now), "");
+
+        view.exact = true;
+        assert_eq!(cell(&item, Column::Size, view, now), "2048");
+        assert_eq!(
+            cell(&item, Column::Modtime, view, now),
+            "2024-01-31T12:00:00Z"
+        );
+        assert_eq!(pad("abcdef", 4), "abc…");
+        assert_eq!(pad("ab", 4), "ab  ");
+        assert_eq!(pad("写真.jpg", 4), "写… ");
+    }
+
+    #[test]
+    fn test_unix_time() {
+        assert_eq!(unix_time("1970-01-01T00:00:00Z"), Some(0));
+        assert_eq!(unix_time("2000-03-01T00:00:00Z"), Some(951_868_800));
+        assert_eq!(
+            unix_time("2024-01-31T13:00:00.123456789+01:00"),
+            unix_time("2024-01-31T12:00:00Z")
+        );
+        assert_eq!(unix_time("yesterday"), None);
+    }
+
+    #[test]
+    fn test_ago() {
+        assert_eq!(ago(0, 30), "just now");
+        assert_eq!(ago(0, 90), "1m ago");
+        assert_eq!(ago(0, 3 * 86_400), "3d ago");
+        assert_eq!(ago(0, 400 * 86_400), "1y ago");
+        assert_eq!(ago(10, 0), "in the future");
+    }
+
+    #[test]
+    fn test_window_stays_in_bounds() {
+        assert_eq!(visible_window(100, 95, 20), 95..100);
+        assert_eq!(visible_window(100, usize::MAX, 20), 100..100);
+        assert_eq!(visible_window(100, 10, 0), 10..10);
     }
 }
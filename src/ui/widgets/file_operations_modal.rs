@@ -1,9 +1,12 @@
 //! File operations modal widget.
 
+use crate::ui::Theme;
+use crate::ui::layout::modal_area;
+use crate::ui::text;
 use ratatui::{
     Frame,
     layout::{Constraint, Direction, Layout, Rect},
-    style::{Color, Style},
+    style::Style,
     widgets::{Block, Borders, Clear, Paragraph},
 };
 
@@ -30,6 +33,10 @@ pub struct FileOperationsModal {
     pub current_path: String,
     pub input: String,
     pub error: Option<String>,
+    /// Whether the operation is in flight.
+    pub waiting: bool,
+    /// Whether a delete needs the name typed before it goes ahead.
+    pub typed_confirm: bool,
 }
 
 impl FileOperationsModal {
@@ -41,6 +48,8 @@ impl FileOperationsModal {
             current_path: String::new(),
             input: String::new(),
             error: None,
+            waiting: false,
+            typed_confirm: false,
         }
     }
 
@@ -52,6 +61,8 @@ impl FileOperationsModal {
             current_path: String::new(),
             input: String::new(),
             error: None,
+            waiting: false,
+            typed_confirm: false,
         }
     }
 
@@ -63,6 +74,8 @@ impl FileOperationsModal {
             current_path,
             input: String::new(),
             error: None,
+            waiting: false,
+            typed_confirm: false,
         }
     }
 
@@ -74,6 +87,8 @@ impl FileOperationsModal {
             current_path,
             input: String::new(),
             error: None,
+            waiting: false,
+            typed_confirm: false,
         }
     }
 
@@ -85,9 +100,17 @@ impl FileOperationsModal {
             current_path,
             input: String::new(),
             error: None,
+            waiting: false,
+            typed_confirm: false,
         }
     }
 
+    /// Requires the name to be typed before a delete goes ahead.
+    pub fn with_typed_confirm(mut self, typed_confirm: bool) -> Self {
+        self.typed_confirm = typed_confirm;
+        self
+    }
+
     pub fn input_char(&mut self, c: char) {
         // Bracketed paste can deliver control characters as Char
         // events; they are never valid in a path segment.
@@ -99,12 +122,14 @@ impl FileOperationsModal {
     }
 
     pub fn backspace(&mut self) {
-        self.input.pop();
+        text::pop_grapheme(&mut self.input);
     }
 
     pub fn is_valid(&self) -> bool {
         match self.operation {
-            FileOperationType::DeleteFile | FileOperationType::DeleteDirectory => true,
+            FileOperationType::DeleteFile | FileOperationType::DeleteDirectory => {
+                !self.typed_confirm || self.input == self.file_name
+            }
             FileOperationType::Mkdir | FileOperationType::Copy | FileOperationType::Move => {
                 !self.input.is_empty()
             }
@@ -121,13 +146,31 @@ impl FileOperationsModal {
         }
     }
 
+    /// Returns why the modal cannot be submitted yet.
+    pub fn invalid_reason(&self) -> String {
+        match self.operation {
+            FileOperationType::DeleteFile | FileOperationType::DeleteDirectory => {
+                format!("Type '{}' to confirm", self.file_name)
+            }
+            _ => "Input is required".to_string(),
+        }
+    }
+
     pub fn get_message(&self) -> String {
+        let typed = if self.typed_confirm {
+            format!("\nType '{}' to confirm.", self.file_name)
+        } else {
+            String::new()
+        };
         match self.operation {
             FileOperationType::DeleteFile => {
-                format!("Delete file '{}'?", self.file_name)
+                format!("Delete file '{}'?{}", self.file_name, typed)
             }
             FileOperationType::DeleteDirectory => {
-                format!("Delete directory '{}' and all contents?", self.file_name)
+                format!(
+                    "Delete directory '{}' and all contents?{}",
+                    self.file_name, typed
+                )
             }
             FileOperationType::Mkdir => "Enter directory name:".to_string(),
             FileOperationType::Copy => {
@@ -139,43 +182,62 @@ impl FileOperationsModal {
         }
     }
 
+    /// Returns the notification shown once the operation succeeded.
+    /// Describes the operation before it ran, e.g. when it is queued.
+    pub fn pending_message(&self) -> String {
+        match self.operation {
+            FileOperationType::DeleteFile => format!("deleting '{}'", self.file_name),
+            FileOperationType::DeleteDirectory => {
+                format!("deleting directory '{}'", self.file_name)
+            }
+            FileOperationType::Mkdir => format!("creating directory '{}'", self.input),
+            FileOperationType::Copy => format!("copying '{}' to '{}'", self.file_name, self.input),
+            FileOperationType::Move => format!("moving '{}' to '{}'", self.file_name, self.input),
+        }
+    }
+
+    pub fn done_message(&self) -> String {
+        match self.operation {
+            FileOperationType::DeleteFile => format!("Deleted '{}'", self.file_name),
+            FileOperationType::DeleteDirectory => {
+                format!("Deleted directory '{}'", self.file_name)
+            }
+            FileOperationType::Mkdir => format!("Created directory '{}'", self.input),
+            FileOperationType::Copy => format!("Copied '{}' to '{}'", self.file_name, self.input),
+            FileOperationType::Move => format!("Moved '{}' to '{}'", self.file_name, self.input),
+        }
+    }
+
     pub fn needs_input(&self) -> bool {
-        matches!(
-            self.operation,
-            FileOperationType::Mkdir | FileOperationType::Copy | FileOperationType::Move
-        )
+        match self.operation {
+            FileOperationType::DeleteFile | FileOperationType::DeleteDirectory => {
+                self.typed_confirm
+            }
+            FileOperationType::Mkdir | FileOperationType::Copy | FileOperationType::Move => true,
+        }
     }
 }
 
 pub struct FileOperationsWidget;
 
 impl FileOperationsWidget {
-    pub fn render(f: &mut Frame, area: Rect, modal: &FileOperationsModal) {
+    pub fn render(f: &mut Frame, area: Rect, modal: &FileOperationsModal, theme: &Theme) {
         // Render backdrop
         f.render_widget(Clear, area);
         f.render_widget(
-            Block::default().style(Style::default().bg(Color::DarkGray)),
+            Block::default().style(Style::default().bg(theme.backdrop)),
             area,
         );
 
         // Calculate modal size
-        let modal_width = 55.min(area.width.saturating_sub(4));
         let modal_height = if modal.needs_input() { 11 } else { 9 };
-        let x = (area.width.saturating_sub(modal_width)) / 2 + area.x;
-        let y = (area.height.saturating_sub(modal_height)) / 2 + area.y;
-
-        let modal_area = Rect {
-            x,
-            y,
-            width: modal_width,
-            height: modal_height,
-        };
+        let modal_area = modal_area(area, 55, modal_height);
 
         // Clear and draw modal border
         f.render_widget(Clear, modal_area);
         let border_color = match modal.operation {
-            FileOperationType::DeleteFile | FileOperationType::DeleteDirectory => Color::Red,
-            _ => Color::Cyan,
+            FileOperationType::DeleteFile | FileOperationType::DeleteDirectory => theme.error,
+            _ => theme.border,
         };
         let block = Block::default()
             .title(format!(" {} ", modal.get_title()))
@@ -209,7 +271,7 @@ impl FileOperationsWidget {
         // Input field (if needed)
         let mut message_idx = 1;
         if modal.needs_input() {
-            let input_style = Style::default().fg(Color::Cyan).bg(Color::Black);
+            let input_style = Style::default().fg(theme.accent);
             let input_text = format!(" {} ", modal.input);
             let input = Paragraph::new(input_text)
                 .style(input_style)
@@ -220,17 +282,22 @@ impl FileOperationsWidget {
 
         // Error message
         if let Some(error) = &modal.error {
-            let error_para = Paragraph::new(error.as_str()).style(Style::default().fg(Color::Red));
+            let error_para = Paragraph::new(error.as_str()).style(Style::default().fg(theme.error));
             f.render_widget(error_para, chunks[message_idx]);
         }
 
         // Help text
-        let help_text = if modal.needs_input() {
-            "Type: input | Backspace: delete char | Enter: confirm | Esc: cancel"
+        let (help_text, help_color) = if modal.waiting {
+            ("Working... | Esc: close", theme.warning)
+        } else if modal.needs_input() {
+            (
+                "Type: input | Backspace: delete char | Enter: confirm | Esc: cancel",
+                theme.dim,
+            )
         } else {
-            "Enter: confirm | Esc: cancel"
+            ("Enter: confirm | Esc: cancel", theme.dim)
         };
-        let help = Paragraph::new(help_text).style(Style::default().fg(Color::Gray));
+        let help = Paragraph::new(help_text).style(Style::default().fg(help_color));
         f.render_widget(help, chunks[chunks.len() - 1]);
     }
 }
@@ -264,7 +331,7 @@ mod tests {
     #[test]
     fn test_file_operation_type_clone() {
         let op = FileOperationType::Copy;
-        let cloned = op.clone();
+        let cloned = Clone::clone(&op);
         assert_eq!(op, cloned);
     }
 
@@ -646,4 +713,15 @@ mod tests {
         modal.input_char('a');
         assert_eq!(modal.input, "a");
     }
+
+    #[test]
+    fn test_done_message_names_the_target() {
+        let mut modal = FileOperationsModal::copy("a.txt".to_string(), "/".to_string());
+        modal.input = "b.txt".to_string();
+        assert_eq!(modal.done_message(), "Copied 'a.txt' to 'b.txt'");
+        assert_eq!(
+            FileOperationsModal::delete_directory("old".to_string()).done_message(),
+            "Deleted directory 'old'"
+        );
+    }
 }
@@ -0,0 +1,198 @@
+//! Form for the size and age limits listings are filtered by.
+
+use crate::error::Result;
+use crate::rclone::Filter;
+use crate::ui::Theme;
+use crate::ui::layout::modal_area;
+use crate::ui::text;
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::Style,
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+};
+
+/// Fields of the filter form, top to bottom.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FilterField {
+    MinSize,
+    MaxSize,
+    ModifiedAfter,
+    ModifiedBefore,
+}
+
+impl FilterField {
+    pub const ALL: [Self; 4] = [
+        Self::MinSize,
+        Self::MaxSize,
+        Self::ModifiedAfter,
+        Self::ModifiedBefore,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::MinSize => "Min size:        ",
+            Self::MaxSize => "Max size:        ",
+            Self::ModifiedAfter => "Modified after:  ",
+            Self::ModifiedBefore => "Modified before: ",
+        }
+    }
+}
+
+/// Filter form state.
+#[derive(Debug, Clone)]
+pub struct FilterForm {
+    /// Text of each field, in [`FilterField::ALL`] order.
+    pub values: [String; 4],
+    pub focus: FilterField,
+    /// Why the limits were rejected.
+    pub error: Option<String>,
+}
+
+impl FilterForm {
+    /// Creates the form filled in with the limits of `filter`.
+    pub fn new(filter: &Filter) -> Self {
+        let text = |value: &Option<String>| value.clone().unwrap_or_default();
+        Self {
+            values: [
+                text(&filter.min_size),
+                text(&filter.max_size),
+                text(&filter.modified_after),
+                text(&filter.modified_before),
+            ],
+            focus: FilterField::MinSize,
+            error: None,
+        }
+    }
+
+    /// Moves the focus `delta` fields along, wrapping around.
+    pub fn step(&mut self, delta: isize) {
+        let len = FilterField::ALL.len() as isize;
+        let next = (self.index() as isize + delta).rem_euclid(len);
+        self.focus = FilterField::ALL[next as usize];
+    }
+
+    pub fn input_char(&mut self, c: char) {
+        if c.is_control() {
+            return;
+        }
+        self.values[self.index()].push(c);
+        self.error = None;
+    }
+
+    pub fn backspace(&mut self) {
+        text::pop_grapheme(&mut self.values[self.index()]);
+        self.error = None;
+    }
+
+    /// Empties every field.
+    pub fn clear(&mut self) {
+        self.values = Default::default();
+        self.error = None;
+    }
+
+    /// Returns the filter the fields describe.
+    ///
+    /// # Errors
+    /// Returns `InvalidInput` naming the first malformed field.
+    pub fn filter(&self) -> Result<Filter> {
+        let [min_size, max_size, after, before] = &self.values;
+        Filter::new(min_size, max_size, after, before)
+    }
+
+    fn index(&self) -> usize {
+        FilterField::ALL
+            .iter()
+            .position(|field| *field == self.focus)
+            .unwrap_or(0)
+    }
+}
+
+/// Widget for the filter form.
+pub struct FilterWidget;
+
+impl FilterWidget {
+    pub fn render(f: &mut Frame, area: Rect, form: &FilterForm, theme: &Theme) {
+        f.render_widget(Clear, area);
+        f.render_widget(
+            Block::default().style(Style::default().bg(theme.backdrop)),
+            area,
+        );
+
+        let modal_area = modal_area(area, 60, 12);
+        f.render_widget(Clear, modal_area);
+        f.render_widget(
+            Block::default()
+                .title(" Filter ")
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(theme.border)),
+            modal_area,
+        );
+
+        let inner = Rect {
+            x: modal_area.x + 1,
+            y: modal_area.y + 1,
+            width: modal_area.width.saturating_sub(2),
+            height: modal_area.height.saturating_sub(2),
+        };
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(5),
+                Constraint::Min(1),
+                Constraint::Length(1),
+            ])
+            .split(inner);
+
+        let mut lines = vec![Line::default()];
+        for (field, value) in FilterField::ALL.iter().zip(&form.values) {
+            let style = if form.focus == *field {
+                Style::default().fg(theme.accent).bold()
+            } else {
+                Style::default()
+            };
+            lines.push(Line::from(vec![
+                Span::styled(field.label(), Style::default().fg(theme.heading)),
+                Span::styled(value.clone(), style),
+            ]));
+        }
+        f.render_widget(Paragraph::new(lines), chunks[0]);
+
+        let (note, style) = match form.error {
+            Some(ref error) => (error.as_str(), Style::default().fg(theme.error)),
+            None => (
+                "Sizes like 500K or 1.5G; ages like 7d or 2024-01-31",
+                Style::default().fg(theme.dim),
+            ),
+        };
+        f.render_widget(
+            Paragraph::new(vec![Line::default(), Line::styled(note, style)]),
+            chunks[1],
+        );
+        f.render_widget(
+            Paragraph::new("Tab: Next | ctrl-x: Clear | Enter: Apply | Esc: Cancel")
+                .style(Style::default().fg(theme.dim)),
+            chunks[2],
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_form_round_trips_filter() {
+        let filter = Filter::new("10M", "", "7d", "").unwrap();
+        let mut form = FilterForm::new(&filter);
+        assert_eq!(form.filter().unwrap(), filter);
+
+        form.step(-1);
+        assert_eq!(form.focus, FilterField::ModifiedBefore);
+        form.input_char('x');
+        assert!(form.filter().is_err());
+        form.clear();
+        assert!(form.filter().unwrap().is_empty());
+    }
+}
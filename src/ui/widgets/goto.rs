@@ -0,0 +1,142 @@
+//! Prompt for a `remote:path` location to jump straight to.
+
+use super::compare::split_location;
+use crate::ui::Theme;
+use crate::ui::layout::modal_area;
+use crate::ui::text;
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::Style,
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+};
+
+/// Go-to prompt state.
+#[derive(Debug, Clone, Default)]
+pub struct GoToPrompt {
+    /// Location typed so far, as `remote:path`.
+    pub input: String,
+    /// Whether the location is being looked up.
+    pub waiting: bool,
+    /// Why the location is rejected, or why looking it up failed.
+    pub error: Option<String>,
+}
+
+impl GoToPrompt {
+    /// Creates the prompt starting from `location`, usually the one
+    /// on screen, so a nearby path takes a few keys.
+    pub fn new(location: String) -> Self {
+        Self {
+            input: location,
+            ..Self::default()
+        }
+    }
+
+    pub fn input_char(&mut self, c: char) {
+        if c.is_control() {
+            return;
+        }
+        self.input.push(c);
+        self.error = None;
+    }
+
+    pub fn backspace(&mut self) {
+        text::pop_grapheme(&mut self.input);
+        self.error = None;
+    }
+
+    /// Returns the remote and path typed, or why they cannot be used
+    /// given the configured `remotes`.
+    pub fn target(&self, remotes: &[String]) -> Result<(String, String), String> {
+        let (remote, path) = split_location(&self.input).map_err(str::to_string)?;
+        if !remotes.is_empty() && !remotes.contains(&remote) {
+            return Err(format!("No remote named '{}'", remote));
+        }
+        Ok((remote, path))
+    }
+}
+
+/// Widget for the go-to prompt.
+pub struct GoToWidget;
+
+impl GoToWidget {
+    pub fn render(f: &mut Frame, area: Rect, prompt: &GoToPrompt, theme: &Theme) {
+        f.render_widget(Clear, area);
+        f.render_widget(
+            Block::default().style(Style::default().bg(theme.backdrop)),
+            area,
+        );
+
+        let modal_area = modal_area(area, 64, 9);
+        f.render_widget(Clear, modal_area);
+        f.render_widget(
+            Block::default()
+                .title(" Go To ")
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(theme.border)),
+            modal_area,
+        );
+
+        let inner = Rect {
+            x: modal_area.x + 1,
+            y: modal_area.y + 1,
+            width: modal_area.width.saturating_sub(2),
+            height: modal_area.height.saturating_sub(2),
+        };
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(1),
+                Constraint::Length(3),
+                Constraint::Min(1),
+                Constraint::Length(1),
+            ])
+            .split(inner);
+
+        f.render_widget(Paragraph::new("Location (remote:path):"), chunks[0]);
+        f.render_widget(
+            Paragraph::new(format!(" {} ", prompt.input))
+                .style(Style::default().fg(theme.accent))
+                .block(Block::default().borders(Borders::ALL)),
+            chunks[1],
+        );
+        if let Some(ref error) = prompt.error {
+            f.render_widget(
+                Paragraph::new(error.as_str())
+                    .style(Style::default().fg(theme.error))
+                    .wrap(Wrap { trim: true }),
+                chunks[2],
+            );
+        }
+
+        let (help, style) = if prompt.waiting {
+            ("Looking up...", Style::default().fg(theme.warning))
+        } else {
+            ("Enter: Go | Esc: Cancel", Style::default().fg(theme.dim))
+        };
+        f.render_widget(Paragraph::new(help).style(style), chunks[3]);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_target_needs_a_known_remote() {
+        let remotes = vec!["gdrive".to_string()];
+        let mut prompt = GoToPrompt::new("gdrive:/docs/2024/".to_string());
+        assert_eq!(
+            prompt.target(&remotes),
+            Ok(("gdrive".to_string(), "docs/2024".to_string()))
+        );
+
+        prompt.input = "s3:backup".to_string();
+        assert_eq!(
+            prompt.target(&remotes),
+            Err("No remote named 's3'".to_string())
+        );
+        prompt.input = "docs".to_string();
+        assert!(prompt.target(&remotes).is_err());
+    }
+}
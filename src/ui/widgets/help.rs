@@ -1,9 +1,27 @@
-//! Help widget.
+//! Help widget: one line of the keys that work on the screen shown.
 
-use crate::ui::styles;
+use super::help_overlay::modal_keys;
+use crate::app::{Action, KeyContext, Keymap, Panel};
+use crate::ui::{Theme, text};
 use ratatui::{Frame, layout::Rect, widgets::Paragraph};
 
-/// Widget for displaying help text.
+/// Actions advertised at the end of the line whatever has focus.
+/// Everything else is listed in the help overlay.
+const HINTED: [Action; 3] = [Action::ShowHelp, Action::CommandPalette, Action::Quit];
+
+/// Separator between two hints.
+const SEPARATOR: &str = " | ";
+
+/// What the help line describes.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum HelpContext {
+    /// A focused panel, with no modal open.
+    Panel(Panel),
+    /// An open modal, by its heading in the help overlay.
+    Modal(&'static str),
+}
+
+/// Widget for the one-line hint of the keys in the current context.
 pub struct HelpWidget;
 
 impl HelpWidget {
@@ -12,23 +30,102 @@ impl HelpWidget {
     /// # Arguments
     /// * `f` - Frame for rendering
     /// * `area` - Area to render in
-    pub fn render(f: &mut Frame, area: Rect) {
-        let help_text = [
-            "j/k: Nav",
-            "a: Add",
-            "e: Edit",
-            "d: Del",
-            "x: Del File",
-            "n: Mkdir",
-            "c: Copy",
-            "m: Move",
-            "Enter: Open",
-            "Backspace: Back",
-            "Tab: Panel",
-            "q: Quit",
-        ]
-        .join(" | ");
-        let paragraph = Paragraph::new(help_text).style(styles::header_style());
-        f.render_widget(paragraph, area);
+    /// * `keymap` - Bindings the hints are read from
+    /// * `context` - What is focused or open
+    /// * `theme` - Colors to draw with
+    pub fn render(f: &mut Frame, area: Rect, keymap: &Keymap, context: HelpContext, theme: &Theme) {
+        let text = Self::text(keymap, context, usize::from(area.width));
+        f.render_widget(Paragraph::new(text).style(theme.header()), area);
+    }
+
+    /// Builds the hint line, at most `width` cells wide.
+    ///
+    /// For a panel these are the bindings of its own actions, in
+    /// keymap order, followed by [`HINTED`]; the panel's hints that do
+    /// not fit are left out, so help is always advertised. For a modal
+    /// they are the keys its handler reads.
+    fn text(keymap: &Keymap, context: HelpContext, width: usize) -> String {
+        let (hints, tail): (Vec<String>, String) = match context {
+            HelpContext::Panel(panel) => {
+                let mut seen = Vec::new();
+                let hints = keymap
+                    .bindings()
+                    .iter()
+                    .filter(|binding| {
+                        let context = binding.action.context();
+                        context != KeyContext::Global && context.applies_to(panel)
+                    })
+                    .filter(|binding| {
+                        let first = !seen.contains(&binding.action);
+                        seen.push(binding.action);
+                        first
+                    })
+                    .map(|binding| format!("{}: {}", binding.keys, binding.action.description()))
+                    .collect();
+                let tail = HINTED
+                    .into_iter()
+                    .filter_map(|action| {
+                        let keys = keymap.keys_for(action).next()?;
+                        Some(format!("{}: {}", keys, action.description()))
+                    })
+                    .collect::<Vec<_>>()
+                    .join(SEPARATOR);
+                (hints, tail)
+            }
+            HelpContext::Modal(title) => {
+                let hints = modal_keys(title)
+                    .iter()
+                    .map(|(keys, description)| format!("{}: {}", keys, description))
+                    .collect();
+                (hints, String::new())
+            }
+        };
+        let room = match text::width(&tail) {
+            0 => width,
+            used => width.saturating_sub(used + SEPARATOR.len()),
+        };
+        let mut line = String::new();
+        for hint in hints {
+            let extra = if line.is_empty() { 0 } else { SEPARATOR.len() };
+            if text::width(&line) + extra + text::width(&hint) > room {
+                break;
+            }
+            if extra > 0 {
+                line.push_str(SEPARATOR);
+            }
+            line.push_str(&hint);
+        }
+        match (line.is_empty(), tail.is_empty()) {
+            (true, _) => tail,
+            (false, true) => line,
+            (false, false) => format!("{}{}{}", line, SEPARATOR, tail),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hints_follow_the_context() {
+        let keymap = Keymap::default();
+        assert_eq!(
+            HelpWidget::text(&keymap, HelpContext::Panel(Panel::Files), 40),
+            "?: Help | :: Command palette | q: Quit"
+        );
+
+        let files = HelpWidget::text(&keymap, HelpContext::Panel(Panel::Files), 300);
+        assert!(files.contains(Action::DeleteFile.description()));
+        assert!(!files.contains(Action::AddRemote.description()));
+        assert!(files.ends_with("q: Quit"));
+        assert!(files.len() <= 300);
+
+        let remotes = HelpWidget::text(&keymap, HelpContext::Panel(Panel::Remotes), 300);
+        assert!(remotes.contains(Action::AddRemote.description()));
+
+        let jobs = HelpWidget::text(&keymap, HelpContext::Modal("Jobs"), 300);
+        assert!(jobs.starts_with("s: Stop the selected job"));
+        assert!(!jobs.contains("Quit"));
     }
 }
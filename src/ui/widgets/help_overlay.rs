@@ -0,0 +1,388 @@
+//! Full-screen help overlay listing every key binding.
+
+use crate::app::{Action, KeyContext, Keymap};
+use crate::ui::Theme;
+use ratatui::{
+    Frame,
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+};
+
+/// Width reserved for the keys column.
+const KEYS_COLUMN_WIDTH: usize = 18;
+
+/// Keys handled directly by modals rather than through the keymap,
+/// grouped by the modal they apply to. Keep in sync with the modal
+/// handlers in `app::handler`.
+const MODAL_KEYS: [(&str, &[(&str, &str)]); 25] = [
+    (
+        "Forms and confirmations",
+        &[
+            ("tab / shift-tab", "Next / previous field"),
+            ("left / right", "Toggle Yes / No"),
+            ("y / n", "Choose Yes / No"),
+            ("enter", "Confirm"),
+            ("esc", "Cancel"),
+        ],
+    ),
+    (
+        "Command palette",
+        &[
+            ("type", "Filter commands"),
+            ("down / ctrl-n", "Next match"),
+            ("up / ctrl-p", "Previous match"),
+            ("enter", "Run"),
+            ("esc", "Close"),
+        ],
+    ),
+    (
+        "Rename / duplicate remote",
+        &[
+            ("tab", "Toggle deleting the original"),
+            ("enter", "Save"),
+            ("esc", "Cancel"),
+        ],
+    ),
+    (
+        "Go to",
+        &[
+            ("type / paste", "remote:path of a directory or file"),
+            ("ctrl-u", "Clear"),
+            ("enter", "Go"),
+            ("esc", "Cancel"),
+        ],
+    ),
+    (
+        "Recent locations",
+        &[
+            ("type", "Fuzzy search"),
+            ("up / down", "Select"),
+            ("ctrl-x", "Forget location"),
+            ("enter", "Go"),
+            ("esc", "Cancel"),
+        ],
+    ),
+    (
+        "Filter",
+        &[
+            ("tab / up / down", "Next / previous field"),
+            ("ctrl-x", "Clear every limit"),
+            ("enter", "Apply and list again"),
+            ("esc", "Cancel"),
+        ],
+    ),
+    (
+        "Mark matching",
+        &[
+            ("* / ?", "Any run of characters / any one character"),
+            ("[a-z] / [!a-z]", "One character in / not in a set"),
+            ("enter", "Mark the matching entries"),
+            ("esc", "Cancel"),
+        ],
+    ),
+    (
+        "Change storage tier",
+        &[
+            ("up / down", "Pick one of the backend's tiers"),
+            ("enter", "Move the selection to the tier"),
+            ("esc", "Cancel"),
+        ],
+    ),
+    (
+        "Object metadata",
+        &[
+            ("j / k", "Select a key"),
+            (
+                "e / enter",
+                "Edit the value, where the backend writes metadata",
+            ),
+            ("a", "Add a key"),
+            ("tab", "Switch between key and value while editing"),
+            ("r", "Reload"),
+            ("esc / q", "Close, or cancel the edit"),
+        ],
+    ),
+    (
+        "File versions",
+        &[
+            ("j / k", "Select a version"),
+            ("d", "Download it to the current directory"),
+            ("r", "Restore it over the file"),
+            ("esc / q", "Close"),
+        ],
+    ),
+    (
+        "Copy URL",
+        &[
+            ("tab", "Switch between URL and file name"),
+            ("enter", "Have the daemon download the URL"),
+            ("esc", "Cancel"),
+        ],
+    ),
+    (
+        "Batch rename",
+        &[
+            ("tab", "Switch between find and replace"),
+            ("ctrl-r", "Toggle regex ($1 = capture group)"),
+            ("{n} / {n:3}", "Counter, optionally zero-padded"),
+            ("up / down", "Scroll the preview"),
+            ("enter", "Rename"),
+            ("esc", "Cancel"),
+        ],
+    ),
+    (
+        "File exists",
+        &[
+            ("s / o / r", "Skip / overwrite / rename"),
+            ("left / right", "Choose"),
+            ("a", "Apply to all conflicts this session"),
+            ("enter", "Confirm"),
+            ("esc", "Cancel"),
+        ],
+    ),
+    (
+        "Remote details",
+        &[
+            ("j / k", "Scroll"),
+            ("p", "Edit the path the remote opens at"),
+            ("i / esc / q", "Close"),
+        ],
+    ),
+    (
+        "Errors",
+        &[("r", "Retry the failed request"), ("i / esc", "Ignore")],
+    ),
+    (
+        "Mounts",
+        &[
+            ("n", "Mount a remote"),
+            ("u", "Unmount the selected mount"),
+            ("r", "Refresh"),
+            ("tab", "Next form field"),
+            ("esc / q", "Close"),
+        ],
+    ),
+    (
+        "Jobs",
+        &[
+            ("s", "Stop the selected job"),
+            ("e", "Show its error / output"),
+            ("r", "Refresh"),
+            ("J / esc", "Close"),
+        ],
+    ),
+    (
+        "Transfer history",
+        &[
+            ("f", "Show failed transfers only / all"),
+            ("r", "Reload"),
+            ("esc / q", "Close"),
+        ],
+    ),
+    (
+        "Audit log",
+        &[
+            ("f", "Show failed changes only / all"),
+            ("r", "Reload"),
+            ("esc / q", "Close"),
+        ],
+    ),
+    (
+        "rclone daemon",
+        &[
+            ("s / enter", "Start rclone rcd for this session"),
+            ("r", "Check again"),
+            ("esc", "Close"),
+            ("q", "Quit"),
+        ],
+    ),
+    (
+        "Disk usage",
+        &[
+            ("enter / l", "Open the selected directory"),
+            ("backspace / h", "Go up"),
+            ("r", "Size again"),
+            ("u / esc", "Close, stopping a listing in flight"),
+        ],
+    ),
+    (
+        "Largest files",
+        &[
+            ("j / k", "Select a file"),
+            ("enter", "Go to it in the files panel"),
+            ("D", "Delete it, after y to confirm"),
+            ("m", "Move it to a typed path"),
+            ("r", "Scan again"),
+            ("W / esc", "Close, stopping a scan in flight"),
+        ],
+    ),
+    (
+        "Compare",
+        &[
+            ("tab", "Next form field"),
+            ("a", "Show / hide matching files"),
+            ("n", "Compare other paths"),
+            ("r", "Compare again"),
+            ("esc / q", "Close"),
+        ],
+    ),
+    (
+        "Help and preview",
+        &[
+            ("j / k", "Scroll"),
+            ("ctrl-d / ctrl-u", "Scroll half a page"),
+            ("g / G", "Top / bottom"),
+            ("esc / q", "Close"),
+        ],
+    ),
+    (
+        "Mouse",
+        &[
+            ("click", "Focus panel and select row"),
+            ("double-click", "Open remote or directory"),
+            ("wheel", "Move through the list"),
+        ],
+    ),
+];
+
+/// Returns the keys of the modal group headed `title`, or none for a
+/// heading [`MODAL_KEYS`] does not have.
+pub(super) fn modal_keys(title: &str) -> &'static [(&'static str, &'static str)] {
+    MODAL_KEYS
+        .iter()
+        .find(|(heading, _)| *heading == title)
+        .map_or(&[], |(_, keys)| keys)
+}
+
+/// Full-screen, scrollable list of key bindings, grouped by context.
+///
+/// Keymap-driven bindings are read from the active [`Keymap`], so the
+/// overlay always matches what the keys actually do.
+pub struct HelpOverlayWidget;
+
+impl HelpOverlayWidget {
+    /// Render the help overlay over all of `area`.
+    ///
+    /// # Arguments
+    /// * `f` - Frame for rendering
+    /// * `area` - Area to cover
+    /// * `keymap` - Bindings to list
+    /// * `scroll` - Index of the first visible line
+    /// * `theme` - Colors to draw with
+    pub fn render(f: &mut Frame, area: Rect, keymap: &Keymap, scroll: usize, theme: &Theme) {
+        let lines = Self::lines(keymap, theme);
+        let total = lines.len();
+        let scroll = scroll.min(Self::max_scroll(keymap, area));
+
+        f.render_widget(Clear, area);
+        let title = format!(
+            " Help ({}/{}) - j/k scroll, Esc close ",
+            (scroll + 1).min(total),
+            total
+        );
+        let paragraph = Paragraph::new(lines)
+            .block(
+                Block::default()
+                    .title(title)
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(theme.border)),
+            )
+            .scroll((u16::try_from(scroll).unwrap_or(u16::MAX), 0));
+        f.render_widget(paragraph, area);
+    }
+
+    /// Returns the largest useful scroll offset when the overlay
+    /// covers `area`.
+    pub fn max_scroll(keymap: &Keymap, area: Rect) -> usize {
+        let rows = usize::from(area.height.saturating_sub(2));
+        Self::line_count(keymap).saturating_sub(rows)
+    }
+
+    /// Returns the number of lines the overlay has to show.
+    pub fn line_count(keymap: &Keymap) -> usize {
+        Self::lines(keymap, &Theme::default()).len()
+    }
+
+    fn lines(keymap: &Keymap, theme: &Theme) -> Vec<Line<'static>> {
+        let heading = Style::default()
+            .fg(theme.heading)
+            .add_modifier(Modifier::BOLD);
+        let key_style = Style::default().fg(theme.accent);
+        let entry = |keys: String, description: &'static str| {
+            Line::from(vec![
+                Span::styled(
+                    format!("  {:<width$}", keys, width = KEYS_COLUMN_WIDTH),
+                    key_style,
+                ),
+                Span::raw(description),
+            ])
+        };
+
+        let mut lines = Vec::new();
+        for context in [
+            KeyContext::Global,
+            KeyContext::Remotes,
+            KeyContext::Files,
+            KeyContext::Logs,
+        ] {
+            if !lines.is_empty() {
+                lines.push(Line::default());
+            }
+            lines.push(Line::from(Span::styled(context.label(), heading)));
+            for action in Action::ALL.into_iter().filter(|a| a.context() == context) {
+                let keys = keymap
+                    .keys_for(action)
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>()
+                    .join(" / ");
+                let keys = if keys.is_empty() {
+                    "(unbound)".to_string()
+                } else {
+                    keys
+                };
+                lines.push(entry(keys, action.description()));
+            }
+        }
+        for (title, keys) in MODAL_KEYS {
+            lines.push(Line::default());
+            lines.push(Line::from(Span::styled(title, heading)));
+            for (keys, description) in keys {
+                lines.push(entry(keys.to_string(), description));
+            }
+        }
+        lines
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lists_every_action() {
+        let text: String = HelpOverlayWidget::lines(&Keymap::default(), &Theme::default())
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join("\n");
+        for action in Action::ALL {
+            assert!(text.contains(action.description()), "{:?}", action);
+        }
+        assert!(text.contains("Command palette"));
+    }
+
+    #[test]
+    fn test_max_scroll_is_zero_when_everything_fits() {
+        let keymap = Keymap::default();
+        let tall = Rect::new(0, 0, 80, 500);
+        assert_eq!(HelpOverlayWidget::max_scroll(&keymap, tall), 0);
+
+        let short = Rect::new(0, 0, 80, 12);
+        assert_eq!(
+            HelpOverlayWidget::max_scroll(&keymap, short),
+            HelpOverlayWidget::line_count(&keymap) - 10
+        );
+    }
+}
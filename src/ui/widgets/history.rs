@@ -0,0 +1,280 @@
+//! Transfer history view: the copies and moves LazyFile ran, newest
+//! first, with the details of the selected one.
+
+use super::file_list::ago;
+use super::jobs::format_duration;
+use super::usage::format_size;
+use crate::config::{TransferKind, TransferRecord};
+use crate::error::Result;
+use crate::ui::Theme;
+use crate::ui::layout::modal_area;
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::Style,
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph, Wrap},
+};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Transfer history view state.
+#[derive(Debug, Clone, Default)]
+pub struct HistoryView {
+    /// Records, newest first.
+    pub records: Vec<TransferRecord>,
+    /// Index of the highlighted record among those shown.
+    pub selected: usize,
+    /// Whether only failed transfers are shown.
+    pub failed_only: bool,
+    /// Why the history could not be read, if it could not.
+    pub error: Option<String>,
+}
+
+impl HistoryView {
+    /// Creates the view over a loaded history.
+    pub fn new(result: Result<Vec<TransferRecord>>) -> Self {
+        let mut view = Self::default();
+        view.set_records(result);
+        view
+    }
+
+    /// Shows a freshly loaded history, starting from the newest
+    /// record.
+    pub fn set_records(&mut self, result: Result<Vec<TransferRecord>>) {
+        match result {
+            Ok(records) => {
+                self.records = records;
+                self.error = None;
+            }
+            Err(e) => self.error = Some(e.to_string()),
+        }
+        self.selected = 0;
+    }
+
+    /// Returns the records shown, newest first.
+    pub fn visible(&self) -> Vec<&TransferRecord> {
+        self.records
+            .iter()
+            .filter(|record| !self.failed_only || record.error.is_some())
+            .collect()
+    }
+
+    /// Returns the highlighted record.
+    pub fn selected_record(&self) -> Option<&TransferRecord> {
+        self.visible().get(self.selected).copied()
+    }
+
+    /// Shows only failures, or everything again.
+    pub fn toggle_failed_only(&mut self) {
+        self.failed_only = !self.failed_only;
+        self.selected = 0;
+    }
+
+    pub fn select_next(&mut self) {
+        if self.selected + 1 < self.visible().len() {
+            self.selected += 1;
+        }
+    }
+
+    pub fn select_prev(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+    }
+}
+
+/// Returns the label of a transfer's kind.
+fn kind_label(kind: TransferKind) -> &'static str {
+    match kind {
+        TransferKind::Copy => "copy",
+        TransferKind::Move => "move",
+    }
+}
+
+/// Returns the size moved, or `-` when it was not known.
+fn size_label(bytes: Option<u64>) -> String {
+    bytes.map_or_else(|| "-".to_string(), format_size)
+}
+
+/// Widget for the transfer history view.
+pub struct HistoryWidget;
+
+impl HistoryWidget {
+    pub fn render(f: &mut Frame, area: Rect, view: &HistoryView, theme: &Theme) {
+        f.render_widget(Clear, area);
+        f.render_widget(
+            Block::default().style(Style::default().bg(theme.backdrop)),
+            area,
+        );
+
+        let modal_area = modal_area(area, 100, 28);
+        f.render_widget(Clear, modal_area);
+        let title = if view.failed_only {
+            " Transfer History (failed) "
+        } else {
+            " Transfer History "
+        };
+        f.render_widget(
+            Block::default()
+                .title(title)
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(theme.border)),
+            modal_area,
+        );
+
+        let inner = Rect {
+            x: modal_area.x + 1,
+            y: modal_area.y + 1,
+            width: modal_area.width.saturating_sub(2),
+            height: modal_area.height.saturating_sub(2),
+        };
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Min(3),
+                Constraint::Length(5),
+                Constraint::Length(1),
+            ])
+            .split(inner);
+
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |elapsed| elapsed.as_secs() as i64);
+        Self::render_records(f, chunks[0], view, now, theme);
+        Self::render_detail(f, chunks[1], view, now, theme);
+
+        let help = format!(
+            "f: {} | r: Reload | Esc: Close",
+            if view.failed_only {
+                "Show all"
+            } else {
+                "Failed only"
+            }
+        );
+        f.render_widget(
+            Paragraph::new(help).style(Style::default().fg(theme.dim)),
+            chunks[2],
+        );
+    }
+
+    /// Draws one row per record, or why there are none to show.
+    fn render_records(f: &mut Frame, area: Rect, view: &HistoryView, now: i64, theme: &Theme) {
+        let records = view.visible();
+        let status = match view.error {
+            Some(ref error) => Some((error.as_str(), theme.error)),
+            None if view.records.is_empty() => Some(("No transfers recorded yet", theme.dim)),
+            None if records.is_empty() => Some(("No failed transfers", theme.dim)),
+            None => None,
+        };
+        if let Some((text, color)) = status {
+            f.render_widget(Paragraph::new(text).style(Style::default().fg(color)), area);
+            return;
+        }
+
+        let items: Vec<ListItem> = records
+            .iter()
+            .map(|record| {
+                let (result, color) = match record.error {
+                    Some(_) => ("failed", theme.error),
+                    None => ("done", theme.success),
+                };
+                ListItem::new(Line::from(vec![
+                    Span::styled(
+                        format!("{:<10}", ago(record.finished, now)),
+                        Style::default().fg(theme.dim),
+                    ),
+                    Span::raw(format!("{:<5}", kind_label(record.kind))),
+                    Span::styled(format!("{:<7}", result), Style::default().fg(color)),
+                    Span::raw(format!(
+                        "{:>10} {:>7}  {} → {}",
+                        size_label(record.bytes),
+                        format_duration(record.seconds),
+                        record.source,
+                        record.destination
+                    )),
+                ]))
+            })
+            .collect();
+        let mut state = ListState::default().with_selected(Some(view.selected));
+        f.render_stateful_widget(
+            List::new(items)
+                .highlight_style(theme.selected())
+                .highlight_symbol(theme.highlight_symbol()),
+            area,
+            &mut state,
+        );
+    }
+
+    /// Draws the highlighted record in full, since its row may be cut
+    /// off.
+    fn render_detail(f: &mut Frame, area: Rect, view: &HistoryView, now: i64, theme: &Theme) {
+        let Some(record) = view.selected_record() else {
+            return;
+        };
+        let outcome = match record.error {
+            Some(ref error) => Line::styled(
+                format!("Failed: {}", error),
+                Style::default().fg(theme.error),
+            ),
+            None => Line::raw(format!(
+                "{} {} in {}, {}",
+                if record.kind == TransferKind::Copy {
+                    "Copied"
+                } else {
+                    "Moved"
+                },
+                size_label(record.bytes),
+                format_duration(record.seconds),
+                ago(record.finished, now)
+            )),
+        };
+        let lines = vec![
+            Line::raw(format!("From: {}", record.source)),
+            Line::raw(format!("To:   {}", record.destination)),
+            outcome,
+        ];
+        f.render_widget(
+            Paragraph::new(lines).wrap(Wrap { trim: false }).block(
+                Block::default()
+                    .borders(Borders::TOP)
+                    .border_style(Style::default().fg(theme.border)),
+            ),
+            area,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(finished: i64, error: Option<&str>) -> TransferRecord {
+        TransferRecord {
+            finished,
+            kind: TransferKind::Move,
+            source: "gdrive:a.txt".to_string(),
+            destination: "s3:a.txt".to_string(),
+            bytes: None,
+            seconds: 1.0,
+            error: error.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn test_failed_only_filters_and_resets_selection() {
+        let mut view = HistoryView::new(Ok(vec![
+            record(3, None),
+            record(2, Some("quota exceeded")),
+            record(1, None),
+        ]));
+        view.select_next();
+        view.select_next();
+        view.select_next();
+        assert_eq!(view.selected_record().unwrap().finished, 1);
+
+        view.toggle_failed_only();
+        assert_eq!(view.visible().len(), 1);
+        assert_eq!(view.selected_record().unwrap().finished, 2);
+        view.select_next();
+        assert_eq!(view.selected, 0);
+        assert_eq!(size_label(None), "-");
+    }
+}
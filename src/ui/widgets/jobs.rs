@@ -0,0 +1,370 @@
+//! Jobs view: the daemon's running and recently finished jobs, the
+//! files being transferred, and the output or error of the selected
+//! job.
+
+use super::usage::format_size;
+use crate::error::Result;
+use crate::rclone::{Job, JobState, Transfer};
+use crate::ui::Theme;
+use crate::ui::layout::modal_area;
+use crate::ui::text::{pad, truncate_start};
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph, Wrap},
+};
+use std::time::Instant;
+
+/// Cells in a transfer's progress bar.
+const BAR_WIDTH: usize = 10;
+
+/// Most transfer rows shown at once.
+const MAX_TRANSFERS: usize = 4;
+
+/// Jobs view state.
+#[derive(Debug, Clone, Default)]
+pub struct JobsView {
+    /// Jobs, as of the last listing.
+    pub jobs: Vec<Job>,
+    /// Whether a listing is in flight.
+    pub loading: bool,
+    /// Index of the highlighted job.
+    pub selected: usize,
+    /// Why the last listing failed, if it did.
+    pub error: Option<String>,
+    /// Whether the detail pane shows the job's error rather than its
+    /// output.
+    pub show_error: bool,
+    /// Files being transferred, as of the last listing.
+    pub transfers: Vec<Transfer>,
+    /// When to list again, while anything is running.
+    pub next_refresh: Option<Instant>,
+}
+
+impl JobsView {
+    /// Creates the view while the first listing is in flight.
+    pub fn new() -> Self {
+        Self {
+            loading: true,
+            ..Self::default()
+        }
+    }
+
+    /// Shows a finished listing, keeping the selection on the same
+    /// job when it is still listed.
+    pub fn set_jobs(&mut self, result: Result<Vec<Job>>) {
+        self.loading = false;
+        match result {
+            Ok(jobs) => {
+                let id = self.selected_job().map(|job| job.id);
+                self.selected = id
+                    .and_then(|id| jobs.iter().position(|job| job.id == id))
+                    .unwrap_or(self.selected);
+                self.jobs = jobs;
+                self.error = None;
+            }
+            Err(e) => self.error = Some(e.to_string()),
+        }
+        self.selected = self.selected.min(self.jobs.len().saturating_sub(1));
+    }
+
+    /// Shows the files in flight. A failed lookup shows none; the
+    /// jobs listing reports the daemon's errors.
+    pub fn set_transfers(&mut self, result: Result<Vec<Transfer>>) {
+        self.transfers = result.unwrap_or_default();
+    }
+
+    /// Returns whether a job is running or a file is in flight, so
+    /// the view is worth refreshing.
+    pub fn is_busy(&self) -> bool {
+        !self.transfers.is_empty() || self.jobs.iter().any(|job| job.state() == JobState::Running)
+    }
+
+    /// Returns the highlighted job.
+    pub fn selected_job(&self) -> Option<&Job> {
+        self.jobs.get(self.selected)
+    }
+
+    pub fn select_next(&mut self) {
+        if self.selected + 1 < self.jobs.len() {
+            self.selected += 1;
+        }
+    }
+
+    pub fn select_prev(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+    }
+}
+
+/// Formats seconds as `42s`, `3m05s` or `1h02m`.
+pub fn format_duration(seconds: f64) -> String {
+    // Saturating float-to-int cast; durations are shown to the second.
+    let total = seconds.max(0.0) as u64;
+    match total {
+        0..60 => format!("{}s", total),
+        60..3600 => format!("{}m{:02}s", total / 60, total % 60),
+        _ => format!("{}h{:02}m", total / 3600, total % 3600 / 60),
+    }
+}
+
+/// Returns a bar of [`BAR_WIDTH`] cells filled to `percentage`.
+fn progress_bar(percentage: u8) -> String {
+    let filled = (usize::from(percentage.min(100)) * BAR_WIDTH + 50) / 100;
+    format!("{}{}", "█".repeat(filled), "░".repeat(BAR_WIDTH - filled))
+}
+
+/// Widget for the jobs view.
+pub struct JobsWidget;
+
+impl JobsWidget {
+    pub fn render(f: &mut Frame, area: Rect, view: &JobsView, theme: &Theme) {
+        f.render_widget(Clear, area);
+        f.render_widget(
+            Block::default().style(Style::default().bg(theme.backdrop)),
+            area,
+        );
+
+        let modal_area = modal_area(area, 80, 24);
+        f.render_widget(Clear, modal_area);
+        f.render_widget(
+            Block::default()
+                .title(" Jobs ")
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(theme.border)),
+            modal_area,
+        );
+
+        let inner = Rect {
+            x: modal_area.x + 1,
+            y: modal_area.y + 1,
+            width: modal_area.width.saturating_sub(2),
+            height: modal_area.height.saturating_sub(2),
+        };
+        let transfer_rows = match view.transfers.len() {
+            0 => 0,
+            len => len.min(MAX_TRANSFERS) + 1,
+        };
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Percentage(40),
+                Constraint::Length(u16::try_from(transfer_rows).unwrap_or(u16::MAX)),
+                Constraint::Min(3),
+                Constraint::Length(1),
+            ])
+            .split(inner);
+
+        Self::render_jobs(f, chunks[0], view, theme);
+        Self::render_transfers(f, chunks[1], view, theme);
+        Self::render_detail(f, chunks[2], view, theme);
+
+        let help = format!(
+            "s: Stop | e: Show {} | r: Refresh | J/Esc: Close",
+            if view.show_error { "output" } else { "error" }
+        );
+        f.render_widget(
+            Paragraph::new(help).style(Style::default().fg(theme.dim)),
+            chunks[3],
+        );
+    }
+
+    /// Returns the label and color of a job's state.
+    fn state(job: &Job, theme: &Theme) -> (&'static str, Color) {
+        match job.state() {
+            JobState::Running => ("running", theme.warning),
+            JobState::Succeeded => ("done", theme.success),
+            JobState::Failed => ("failed", theme.error),
+        }
+    }
+
+    /// Draws one row per job, or why there are none to show.
+    fn render_jobs(f: &mut Frame, area: Rect, view: &JobsView, theme: &Theme) {
+        let status = match (&view.error, view.loading) {
+            (Some(error), _) => Some((error.as_str(), theme.error)),
+            (None, true) if view.jobs.is_empty() => Some(("Loading jobs...", theme.dim)),
+            (None, _) if view.jobs.is_empty() => Some(("No jobs", theme.dim)),
+            _ => None,
+        };
+        if let Some((text, color)) = status {
+            f.render_widget(Paragraph::new(text).style(Style::default().fg(color)), area);
+            return;
+        }
+
+        let items: Vec<ListItem> = view
+            .jobs
+            .iter()
+            .map(|job| {
+                let (state, color) = Self::state(job, theme);
+                ListItem::new(Line::from(vec![
+                    Span::raw(format!("#{:<5}", job.id)),
+                    Span::styled(format!("{:<8}", state), Style::default().fg(color)),
+                    Span::raw(format!("{:>7} ", format_duration(job.duration))),
+                    Span::styled(job.group.as_str(), Style::default().fg(theme.dim)),
+                ]))
+            })
+            .collect();
+        let mut state = ListState::default().with_selected(Some(view.selected));
+        f.render_stateful_widget(
+            List::new(items)
+                .highlight_style(theme.selected())
+                .highlight_symbol(theme.highlight_symbol()),
+            area,
+            &mut state,
+        );
+    }
+
+    /// Draws one row per file in flight: its name, a progress bar,
+    /// speed and time left. Files past [`MAX_TRANSFERS`] are counted
+    /// in the title.
+    fn render_transfers(f: &mut Frame, area: Rect, view: &JobsView, theme: &Theme) {
+        if view.transfers.is_empty() {
+            return;
+        }
+        let title = match view.transfers.len() {
+            len if len > MAX_TRANSFERS => {
+                format!(" Transferring ({} more) ", len - MAX_TRANSFERS)
+            }
+            _ => " Transferring ".to_string(),
+        };
+        // Bar, percentage, speed and time left take a fixed width; the
+        // name gets the rest.
+        let name_width = usize::from(area.width)
+            .saturating_sub(BAR_WIDTH + 28)
+            .max(8);
+        let lines: Vec<Line> = view
+            .transfers
+            .iter()
+            .take(MAX_TRANSFERS)
+            .map(|transfer| {
+                let speed = format!("{}/s", format_size(transfer.speed.max(0.0) as u64));
+                let eta = transfer
+                    .eta
+                    .map_or_else(|| "-".to_string(), format_duration);
+                Line::from(vec![
+                    Span::raw(format!(
+                        "{} ",
+                        pad(&truncate_start(&transfer.name, name_width), name_width)
+                    )),
+                    Span::styled(
+                        progress_bar(transfer.percentage),
+                        Style::default().fg(theme.accent),
+                    ),
+                    Span::raw(format!(" {:>3}% {:>12} ", transfer.percentage, speed)),
+                    Span::styled(format!("{:>6}", eta), Style::default().fg(theme.dim)),
+                ])
+            })
+            .collect();
+        f.render_widget(
+            Paragraph::new(lines).block(
+                Block::default()
+                    .title(title)
+                    .borders(Borders::TOP)
+                    .border_style(Style::default().fg(theme.border)),
+            ),
+            area,
+        );
+    }
+
+    /// Draws the selected job's output, or its error.
+    fn render_detail(f: &mut Frame, area: Rect, view: &JobsView, theme: &Theme) {
+        let Some(job) = view.selected_job() else {
+            return;
+        };
+        let (title, text, style) = if view.show_error {
+            let text = match job.error.as_str() {
+                "" => "No error".to_string(),
+                error => error.to_string(),
+            };
+            (" Error ", text, Style::default().fg(theme.error))
+        } else {
+            let text = match job.output {
+                Some(ref output) if !output.is_null() => {
+                    serde_json::to_string_pretty(output).unwrap_or_default()
+                }
+                _ if job.finished => "No output".to_string(),
+                _ => format!("Running since {}", job.start_time),
+            };
+            (" Output ", text, Style::default())
+        };
+        f.render_widget(
+            Paragraph::new(text)
+                .style(style)
+                .wrap(Wrap { trim: false })
+                .block(
+                    Block::default()
+                        .title(title)
+                        .borders(Borders::TOP)
+                        .border_style(Style::default().fg(theme.border)),
+                ),
+            area,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn job(id: i64) -> Job {
+        Job {
+            id,
+            ..Job::default()
+        }
+    }
+
+    #[test]
+    fn test_formats_durations() {
+        assert_eq!(format_duration(0.4), "0s");
+        assert_eq!(format_duration(42.9), "42s");
+        assert_eq!(format_duration(185.0), "3m05s");
+        assert_eq!(format_duration(3720.0), "1h02m");
+    }
+
+    #[test]
+    fn test_draws_transfer_progress() {
+        assert_eq!(progress_bar(0), "░".repeat(BAR_WIDTH));
+        assert_eq!(
+            progress_bar(25),
+            format!("{}{}", "█".repeat(3), "░".repeat(7))
+        );
+        assert_eq!(progress_bar(100), "█".repeat(BAR_WIDTH));
+        assert_eq!(truncate_start("docs/a.iso", 20), "docs/a.iso");
+        assert_eq!(truncate_start("docs/2024/a.iso", 8), "…4/a.iso");
+    }
+
+    #[test]
+    fn test_busy_while_jobs_run_or_files_move() {
+        let mut view = JobsView::new();
+        view.set_jobs(Ok(vec![Job {
+            finished: true,
+            ..job(1)
+        }]));
+        assert!(!view.is_busy());
+        view.set_transfers(Ok(vec![Transfer::default()]));
+        assert!(view.is_busy());
+        view.set_transfers(Err(crate::error::LazyFileError::RcloneApi {
+            endpoint: "core/stats",
+            message: "down".to_string(),
+        }));
+        assert!(!view.is_busy());
+        view.set_jobs(Ok(vec![job(2)]));
+        assert!(view.is_busy());
+    }
+
+    #[test]
+    fn test_selection_follows_job_across_listings() {
+        let mut view = JobsView::new();
+        view.set_jobs(Ok(vec![job(1), job(2), job(3)]));
+        view.select_next();
+        view.select_next();
+        assert_eq!(view.selected_job().unwrap().id, 3);
+
+        view.set_jobs(Ok(vec![job(2), job(3)]));
+        assert_eq!(view.selected_job().unwrap().id, 3);
+
+        view.set_jobs(Ok(vec![job(1)]));
+        assert_eq!(view.selected, 0);
+    }
+}
@@ -0,0 +1,285 @@
+//! Largest files below a directory, biggest first, for finding what
+//! to clean up. Each can be deleted or moved straight from the list.
+
+use super::usage::format_size;
+use crate::rclone::{CancelToken, FileItem};
+use crate::ui::Theme;
+use crate::ui::layout::modal_area;
+use crate::ui::text;
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::Style,
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph},
+};
+use std::cmp::Reverse;
+
+/// How many of the largest files the view keeps.
+pub const LARGEST_COUNT: usize = 100;
+
+/// What is asked before the highlighted file is changed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LargestPrompt {
+    /// Whether to delete it.
+    Delete,
+    /// Where to move it, as a path within the remote typed so far.
+    Move(String),
+}
+
+/// Largest files view state.
+#[derive(Debug, Clone)]
+pub struct LargestView {
+    /// Remote scanned.
+    pub remote: String,
+    /// Directory scanned, within the remote.
+    pub root: String,
+    /// The largest files, biggest first and named by their path below
+    /// the directory, once scanned; `None` while scanning.
+    pub files: Option<Result<Vec<FileItem>, String>>,
+    /// How many files the scan found.
+    pub scanned: usize,
+    /// Index of the highlighted file.
+    pub selected: usize,
+    /// Cancels the scan in flight.
+    pub cancel: Option<CancelToken>,
+    /// What is being asked about the highlighted file, if anything.
+    pub prompt: Option<LargestPrompt>,
+    /// Path within the remote of the file being deleted or moved.
+    pub busy: Option<String>,
+}
+
+impl LargestView {
+    /// Creates the view for `root` on `remote`, waiting for the scan.
+    pub fn new(remote: String, root: String) -> Self {
+        Self {
+            remote,
+            root,
+            files: None,
+            scanned: 0,
+            selected: 0,
+            cancel: None,
+            prompt: None,
+            busy: None,
+        }
+    }
+
+    /// Keeps the [`LARGEST_COUNT`] largest of the files a scan found.
+    pub fn set_files(&mut self, files: Result<Vec<FileItem>, String>) {
+        self.cancel = None;
+        self.files = Some(files.map(|mut files| {
+            self.scanned = files.len();
+            files.sort_by_key(|item| Reverse(item.size));
+            files.truncate(LARGEST_COUNT);
+            files
+        }));
+        self.clamp_selection();
+    }
+
+    /// Returns the highlighted file, if any.
+    pub fn selected_file(&self) -> Option<&FileItem> {
+        match self.files {
+            Some(Ok(ref files)) => files.get(self.selected),
+            _ => None,
+        }
+    }
+
+    /// Returns the path within the remote of a file of the list.
+    pub fn path_of(&self, item: &FileItem) -> String {
+        if self.root.is_empty() {
+            item.name().to_string()
+        } else {
+            format!("{}/{}", self.root, item.name())
+        }
+    }
+
+    /// Drops the file at `path` within the remote from the list, once
+    /// it was deleted or moved away.
+    pub fn remove(&mut self, path: &str) {
+        let Some(Ok(ref files)) = self.files else {
+            return;
+        };
+        let kept: Vec<FileItem> = files
+            .iter()
+            .filter(|item| self.path_of(item) != path)
+            .cloned()
+            .collect();
+        self.files = Some(Ok(kept));
+        self.clamp_selection();
+    }
+
+    pub fn select_next(&mut self) {
+        let count = match self.files {
+            Some(Ok(ref files)) => files.len(),
+            _ => 0,
+        };
+        if self.selected + 1 < count {
+            self.selected += 1;
+        }
+    }
+
+    pub fn select_prev(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+    }
+
+    /// Types a character into the move destination.
+    pub fn input_char(&mut self, c: char) {
+        if let Some(LargestPrompt::Move(ref mut dst)) = self.prompt
+            && !c.is_control()
+        {
+            dst.push(c);
+        }
+    }
+
+    pub fn backspace(&mut self) {
+        if let Some(LargestPrompt::Move(ref mut dst)) = self.prompt {
+            text::pop_grapheme(dst);
+        }
+    }
+
+    fn clamp_selection(&mut self) {
+        let count = self
+            .files
+            .as_ref()
+            .map_or(0, |files| files.as_ref().map_or(0, Vec::len));
+        self.selected = self.selected.min(count.saturating_sub(1));
+    }
+}
+
+/// Widget for the largest files view.
+pub struct LargestWidget;
+
+impl LargestWidget {
+    pub fn render(f: &mut Frame, area: Rect, view: &LargestView, theme: &Theme) {
+        f.render_widget(Clear, area);
+        f.render_widget(
+            Block::default().style(Style::default().bg(theme.backdrop)),
+            area,
+        );
+
+        let modal_area = modal_area(area, 90, 24);
+        f.render_widget(Clear, modal_area);
+        f.render_widget(
+            Block::default()
+                .title(format!(" Largest files in {}:{} ", view.remote, view.root))
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(theme.border)),
+            modal_area,
+        );
+
+        let inner = Rect {
+            x: modal_area.x + 1,
+            y: modal_area.y + 1,
+            width: modal_area.width.saturating_sub(2),
+            height: modal_area.height.saturating_sub(2),
+        };
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Min(1),
+                Constraint::Length(1),
+                Constraint::Length(1),
+            ])
+            .split(inner);
+
+        match view.files {
+            None => f.render_widget(
+                Paragraph::new("Scanning...").style(Style::default().fg(theme.dim)),
+                chunks[0],
+            ),
+            Some(Err(ref error)) => f.render_widget(
+                Paragraph::new(error.as_str()).style(Style::default().fg(theme.error)),
+                chunks[0],
+            ),
+            Some(Ok(ref files)) if files.is_empty() => f.render_widget(
+                Paragraph::new("No files below this directory")
+                    .style(Style::default().fg(theme.dim)),
+                chunks[0],
+            ),
+            Some(Ok(ref files)) => {
+                let items: Vec<ListItem> = files
+                    .iter()
+                    .map(|item| {
+                        let size =
+                            u64::try_from(item.size).map_or_else(|_| "?".to_string(), format_size);
+                        ListItem::new(Line::from(vec![
+                            Span::styled(
+                                format!("{:>10}  ", size),
+                                Style::default().fg(theme.accent),
+                            ),
+                            Span::raw(item.name()),
+                        ]))
+                    })
+                    .collect();
+                let mut state = ListState::default().with_selected(Some(view.selected));
+                f.render_stateful_widget(
+                    List::new(items)
+                        .highlight_style(theme.selected())
+                        .highlight_symbol(theme.highlight_symbol()),
+                    chunks[0],
+                    &mut state,
+                );
+            }
+        }
+
+        let name = view.selected_file().map_or("", FileItem::name);
+        let (status, color) = match (&view.prompt, &view.busy) {
+            (_, Some(path)) => (format!("Changing {}...", path), theme.warning),
+            (Some(LargestPrompt::Delete), None) => {
+                (format!("Delete {}? y: Yes | n: No", name), theme.warning)
+            }
+            (Some(LargestPrompt::Move(dst)), None) => (format!("Move to: {}", dst), theme.accent),
+            (None, None) => match view.files {
+                Some(Ok(ref files)) => (
+                    format!("{} largest of {} files", files.len(), view.scanned),
+                    theme.dim,
+                ),
+                _ => (String::new(), theme.dim),
+            },
+        };
+        f.render_widget(
+            Paragraph::new(status).style(Style::default().fg(color)),
+            chunks[1],
+        );
+        let help = match view.prompt {
+            Some(LargestPrompt::Move(_)) => "Enter: Move | Esc: Cancel",
+            _ => "j/k: Select | Enter: Go to | D: Delete | m: Move | r: Rescan | Esc: Close",
+        };
+        f.render_widget(
+            Paragraph::new(help).style(Style::default().fg(theme.dim)),
+            chunks[2],
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn file(name: &str, size: i64) -> FileItem {
+        FileItem {
+            name: name.to_string(),
+            size,
+            ..FileItem::default()
+        }
+    }
+
+    #[test]
+    fn test_keeps_the_largest_files_first() {
+        let mut view = LargestView::new("s3".to_string(), "backup".to_string());
+        let files = (0..150).map(|i| file(&format!("f{}.bin", i), i)).collect();
+        view.set_files(Ok(files));
+        let Some(Ok(ref files)) = view.files else {
+            panic!("no files");
+        };
+        assert_eq!(files.len(), LARGEST_COUNT);
+        assert_eq!(files[0].name(), "f149.bin");
+        assert_eq!(view.scanned, 150);
+
+        view.select_next();
+        let path = view.path_of(view.selected_file().unwrap());
+        assert_eq!(path, "backup/f148.bin");
+        view.remove(&path);
+        assert_eq!(view.selected_file().unwrap().name(), "f147.bin");
+    }
+}
@@ -0,0 +1,94 @@
+//! Log pane widget.
+
+use crate::logs::LogBuffer;
+use crate::ui::Theme;
+use ratatui::{
+    Frame,
+    layout::Rect,
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, ListState},
+};
+use tracing::Level;
+
+/// Scroll position and flags the log pane is drawn with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LogView {
+    /// Index of the first visible line.
+    pub offset: usize,
+    /// Index of the selected line.
+    pub selected: usize,
+    /// Whether the pane is focused.
+    pub focused: bool,
+    /// Whether the pane follows the newest line.
+    pub follow: bool,
+}
+
+/// Widget showing recent log lines, colored by level.
+pub struct LogsWidget;
+
+impl LogsWidget {
+    /// Render the log pane.
+    ///
+    /// # Arguments
+    /// * `f` - Frame for rendering
+    /// * `area` - Area to render in
+    /// * `logs` - Captured log lines
+    /// * `view` - Scroll position and flags
+    /// * `theme` - Colors to draw with
+    pub fn render(f: &mut Frame, area: Rect, logs: &LogBuffer, view: LogView, theme: &Theme) {
+        // Only the visible window is copied out of the shared buffer.
+        let rows = usize::from(area.height.saturating_sub(2));
+        let items: Vec<ListItem> = logs
+            .lines(view.offset, rows)
+            .into_iter()
+            .map(|line| {
+                ListItem::new(Line::from(vec![
+                    Span::styled(
+                        format!("{:<5} ", line.level),
+                        Style::new().fg(level_color(line.level, theme)),
+                    ),
+                    Span::styled(format!("{} ", line.target), Style::new().fg(theme.dim)),
+                    Span::raw(line.message),
+                ]))
+            })
+            .collect();
+
+        let border_style = if view.focused {
+            theme.focused()
+        } else {
+            Style::new()
+        };
+        let title = if view.follow {
+            " Logs (following) "
+        } else {
+            " Logs "
+        };
+
+        let list = List::new(items)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title(title)
+                    .border_style(border_style),
+            )
+            .highlight_style(theme.selected())
+            .highlight_symbol(theme.highlight_symbol());
+
+        let selected = view
+            .selected
+            .checked_sub(view.offset)
+            .filter(|_| view.focused);
+        let mut list_state = ListState::default().with_selected(selected);
+        f.render_stateful_widget(list, area, &mut list_state);
+    }
+}
+
+fn level_color(level: Level, theme: &Theme) -> Color {
+    match level {
+        Level::ERROR => theme.error,
+        Level::WARN => theme.warning,
+        Level::INFO => theme.accent,
+        Level::DEBUG | Level::TRACE => theme.dim,
+    }
+}
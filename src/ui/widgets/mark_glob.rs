@@ -0,0 +1,253 @@
+//! Prompt for a glob such as `*.jpg`, marking every entry of the
+//! current directory whose name matches.
+
+use crate::ui::Theme;
+use crate::ui::layout::modal_area;
+use crate::ui::text;
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::Style,
+    widgets::{Block, Borders, Clear, Paragraph},
+};
+
+/// Glob prompt state.
+#[derive(Debug, Clone)]
+pub struct GlobPrompt {
+    /// Pattern typed so far.
+    pub pattern: String,
+    /// Names in the current directory, to count matches as the
+    /// pattern is typed.
+    pub names: Vec<String>,
+    /// Whether the pattern narrows the recursive listing to the paths
+    /// it matches, rather than marking entries.
+    pub narrow: bool,
+}
+
+impl GlobPrompt {
+    /// Creates the prompt for a directory with entries `names`.
+    pub fn new(names: Vec<String>) -> Self {
+        Self {
+            pattern: String::new(),
+            names,
+            narrow: false,
+        }
+    }
+
+    /// Creates the prompt narrowing a recursive listing of files at
+    /// `paths`, starting from the pattern it is narrowed by, if any.
+    pub fn narrowing(paths: Vec<String>, pattern: Option<String>) -> Self {
+        Self {
+            pattern: pattern.unwrap_or_default(),
+            names: paths,
+            narrow: true,
+        }
+    }
+
+    pub fn input_char(&mut self, c: char) {
+        if c.is_control() {
+            return;
+        }
+        self.pattern.push(c);
+    }
+
+    pub fn backspace(&mut self) {
+        text::pop_grapheme(&mut self.pattern);
+    }
+
+    /// Returns how many names the pattern matches. An empty pattern
+    /// narrows nothing, so it matches every path.
+    pub fn matches(&self) -> usize {
+        match self.narrow {
+            true if self.pattern.is_empty() => self.names.len(),
+            true => self
+                .names
+                .iter()
+                .filter(|path| path_match(&self.pattern, path))
+                .count(),
+            false => self
+                .names
+                .iter()
+                .filter(|name| glob_match(&self.pattern, name))
+                .count(),
+        }
+    }
+}
+
+/// Returns whether the file at relative `path` matches `pattern`: its
+/// name does, or with a `/` in the pattern, its whole path, as in
+/// rclone's filters.
+pub fn path_match(pattern: &str, path: &str) -> bool {
+    if pattern.contains('/') {
+        glob_match(pattern, path)
+    } else {
+        glob_match(pattern, path.rsplit('/').next().unwrap_or(path))
+    }
+}
+
+/// Returns whether `name` matches the shell-style `pattern`: `*` is
+/// any run of characters, `?` any one character, and `[a-z]` or
+/// `[!a-z]` one character in or not in a set. Anything else, a lone
+/// `[` included, matches itself.
+pub fn glob_match(pattern: &str, name: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let name: Vec<char> = name.chars().collect();
+    let (mut p, mut n) = (0, 0);
+    // Where to resume after the last `*` if the rest fails to match:
+    // the pattern just past it, and the name one further along.
+    let mut resume = None;
+    while n < name.len() {
+        let step = match pattern.get(p) {
+            Some('*') => {
+                resume = Some((p + 1, n + 1));
+                p += 1;
+                continue;
+            }
+            Some('?') => Some(1),
+            Some('[') => match class_match(&pattern[p..], name[n]) {
+                Some((true, len)) => Some(len),
+                Some((false, _)) => None,
+                None => (name[n] == '[').then_some(1),
+            },
+            Some(&c) => (c == name[n]).then_some(1),
+            None => None,
+        };
+        match (step, resume) {
+            (Some(len), _) => {
+                p += len;
+                n += 1;
+            }
+            (None, Some((after_star, next))) => {
+                p = after_star;
+                n = next;
+                resume = Some((after_star, next + 1));
+            }
+            (None, None) => return false,
+        }
+    }
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+/// Matches `c` against the class at the start of `pattern`, returning
+/// whether it matched and the class's length, or `None` if the `[` is
+/// not closed.
+fn class_match(pattern: &[char], c: char) -> Option<(bool, usize)> {
+    let mut i = 1;
+    let negated = matches!(pattern.get(i), Some('!' | '^'));
+    if negated {
+        i += 1;
+    }
+    let mut matched = false;
+    let mut first = true;
+    loop {
+        let &start = pattern.get(i)?;
+        if start == ']' && !first {
+            return Some((matched != negated, i + 1));
+        }
+        first = false;
+        if pattern.get(i + 1) == Some(&'-')
+            && let Some(&end) = pattern.get(i + 2)
+            && end != ']'
+        {
+            matched |= (start..=end).contains(&c);
+            i += 3;
+        } else {
+            matched |= start == c;
+            i += 1;
+        }
+    }
+}
+
+/// Widget for the glob prompt.
+pub struct GlobPromptWidget;
+
+impl GlobPromptWidget {
+    pub fn render(f: &mut Frame, area: Rect, prompt: &GlobPrompt, theme: &Theme) {
+        f.render_widget(Clear, area);
+        f.render_widget(
+            Block::default().style(Style::default().bg(theme.backdrop)),
+            area,
+        );
+
+        let modal_area = modal_area(area, 50, 8);
+        f.render_widget(Clear, modal_area);
+        f.render_widget(
+            Block::default()
+                .title(if prompt.narrow {
+                    " Narrow Listing "
+                } else {
+                    " Mark Matching "
+                })
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(theme.border)),
+            modal_area,
+        );
+
+        let inner = Rect {
+            x: modal_area.x + 1,
+            y: modal_area.y + 1,
+            width: modal_area.width.saturating_sub(2),
+            height: modal_area.height.saturating_sub(2),
+        };
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(3),
+                Constraint::Length(1),
+                Constraint::Min(0),
+                Constraint::Length(1),
+            ])
+            .split(inner);
+
+        f.render_widget(
+            Paragraph::new(format!(" {} ", prompt.pattern))
+                .style(Style::default().fg(theme.accent))
+                .block(Block::default().title(" Glob ").borders(Borders::ALL)),
+            chunks[0],
+        );
+        f.render_widget(
+            Paragraph::new(format!(
+                "{} of {} {} match",
+                prompt.matches(),
+                prompt.names.len(),
+                if prompt.narrow { "files" } else { "entries" }
+            ))
+            .style(Style::default().fg(theme.dim)),
+            chunks[1],
+        );
+        f.render_widget(
+            Paragraph::new(if prompt.narrow {
+                "Name, or path with / | Enter: Narrow, empty for all | Esc: Cancel"
+            } else {
+                "*, ? and [a-z] | Enter: Mark | Esc: Cancel"
+            })
+            .style(Style::default().fg(theme.dim)),
+            chunks[3],
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_glob_wildcards() {
+        assert!(glob_match("*.jpg", "a.jpg"));
+        assert!(!glob_match("*.jpg", "a.jpeg"));
+        assert!(glob_match("IMG_????.*", "IMG_0001.png"));
+        assert!(glob_match("*a*b*", "xxaxxbxx"));
+        assert!(!glob_match("*a*b", "xxbxxa"));
+        assert!(glob_match("*", ""));
+        assert!(!glob_match("", "a"));
+    }
+
+    #[test]
+    fn test_glob_classes() {
+        assert!(glob_match("[abc]*", "banana"));
+        assert!(glob_match("file[0-9].txt", "file7.txt"));
+        assert!(!glob_match("file[!0-9].txt", "file7.txt"));
+        assert!(glob_match("[]]", "]"));
+        assert!(glob_match("a[", "a["));
+    }
+}
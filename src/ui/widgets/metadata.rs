@@ -0,0 +1,368 @@
+//! Details of a file: its size, times and type, and the metadata its
+//! backend keeps, such as `content-type`, `cache-control` or custom
+//! headers. Keys can be set where the backend writes metadata.
+
+use super::usage::format_size;
+use crate::rclone::ObjectInfo;
+use crate::ui::layout::modal_area;
+use crate::ui::{Input, Theme};
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::Style,
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph},
+};
+
+/// Field of the metadata form being typed in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MetadataField {
+    /// The key, only for a new one.
+    Key,
+    /// The value.
+    Value,
+}
+
+/// A key being set.
+#[derive(Debug, Clone)]
+pub struct MetadataEdit {
+    /// Key typed so far, or the one being changed.
+    pub key: String,
+    /// Value typed so far.
+    pub value: String,
+    /// Whether the key is new, so it can be typed.
+    pub new: bool,
+    /// Field typed in.
+    pub field: MetadataField,
+    /// Cursor of the field typed in.
+    pub input: Input,
+}
+
+/// Metadata view state.
+#[derive(Debug, Clone)]
+pub struct MetadataView {
+    /// Remote the file is on.
+    pub remote: String,
+    /// Path of the file within the remote.
+    pub path: String,
+    /// The file and its metadata, once fetched; `None` while loading.
+    pub info: Option<Result<ObjectInfo, String>>,
+    /// Metadata keys and values, sorted by key.
+    pub metadata: Vec<(String, String)>,
+    /// Whether the backend writes metadata; `None` until known.
+    pub writable: Option<bool>,
+    /// Index of the highlighted key.
+    pub selected: usize,
+    /// Key being set, while the form is open.
+    pub edit: Option<MetadataEdit>,
+    /// Whether a change is being written.
+    pub saving: bool,
+    /// Why the last change failed, if it did.
+    pub error: Option<String>,
+}
+
+impl MetadataView {
+    /// Creates the view for the file at `path` on `remote`, waiting
+    /// for its details.
+    pub fn new(remote: String, path: String) -> Self {
+        Self {
+            remote,
+            path,
+            info: None,
+            metadata: Vec::new(),
+            writable: None,
+            selected: 0,
+            edit: None,
+            saving: false,
+            error: None,
+        }
+    }
+
+    /// Shows fetched details, keeping the highlighted key in range.
+    pub fn set_info(&mut self, info: Result<ObjectInfo, String>) {
+        self.metadata = info
+            .as_ref()
+            .ok()
+            .and_then(|info| info.metadata.clone())
+            .map(|metadata| metadata.into_iter().collect())
+            .unwrap_or_default();
+        self.selected = self.selected.min(self.metadata.len().saturating_sub(1));
+        self.info = Some(info);
+    }
+
+    /// Opens the form for the highlighted key, or for a new one.
+    pub fn start_edit(&mut self, new: bool) {
+        let (key, value) = match self.metadata.get(self.selected) {
+            Some((key, value)) if !new => (key.clone(), value.clone()),
+            _ if new => (String::new(), String::new()),
+            _ => return,
+        };
+        self.error = None;
+        self.edit = Some(MetadataEdit {
+            key,
+            value,
+            new,
+            field: if new {
+                MetadataField::Key
+            } else {
+                MetadataField::Value
+            },
+            input: Input::default(),
+        });
+    }
+
+    pub fn select_next(&mut self) {
+        if self.selected + 1 < self.metadata.len() {
+            self.selected += 1;
+        }
+    }
+
+    pub fn select_prev(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+    }
+}
+
+impl MetadataEdit {
+    /// Moves to the other field of a new key.
+    pub fn toggle_field(&mut self) {
+        if self.new {
+            self.field = match self.field {
+                MetadataField::Key => MetadataField::Value,
+                MetadataField::Value => MetadataField::Key,
+            };
+            self.input.end();
+        }
+    }
+
+    /// Returns the text of the field typed in.
+    pub fn text_mut(&mut self) -> &mut String {
+        match self.field {
+            MetadataField::Key => &mut self.key,
+            MetadataField::Value => &mut self.value,
+        }
+    }
+}
+
+/// Widget for the metadata view.
+pub struct MetadataWidget;
+
+impl MetadataWidget {
+    pub fn render(f: &mut Frame, area: Rect, view: &MetadataView, theme: &Theme) {
+        f.render_widget(Clear, area);
+        f.render_widget(
+            Block::default().style(Style::default().bg(theme.backdrop)),
+            area,
+        );
+
+        let modal_area = modal_area(area, 80, 24);
+        f.render_widget(Clear, modal_area);
+        f.render_widget(
+            Block::default()
+                .title(format!(" {}:{} ", view.remote, view.path))
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(theme.border)),
+            modal_area,
+        );
+
+        let inner = Rect {
+            x: modal_area.x + 1,
+            y: modal_area.y + 1,
+            width: modal_area.width.saturating_sub(2),
+            height: modal_area.height.saturating_sub(2),
+        };
+        let form_height = if view.edit.is_some() { 4 } else { 0 };
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(5),
+                Constraint::Min(3),
+                Constraint::Length(form_height),
+                Constraint::Length(1),
+                Constraint::Length(1),
+            ])
+            .split(inner);
+
+        let info = match view.info {
+            None => {
+                f.render_widget(
+                    Paragraph::new("Loading...").style(Style::default().fg(theme.dim)),
+                    chunks[0],
+                );
+                return;
+            }
+            Some(Err(ref error)) => {
+                f.render_widget(
+                    Paragraph::new(error.as_str()).style(Style::default().fg(theme.error)),
+                    chunks[0],
+                );
+                return;
+            }
+            Some(Ok(ref info)) => info,
+        };
+        let field = |label: &str, value: String| {
+            Line::from(vec![
+                Span::styled(format!("{:<14}", label), Style::default().fg(theme.dim)),
+                Span::raw(value),
+            ])
+        };
+        let size =
+            u64::try_from(info.item.size).map_or_else(|_| "unknown".to_string(), format_size);
+        let writable = match view.writable {
+            Some(true) => "yes",
+            Some(false) => "no",
+            None => "checking...",
+        };
+        f.render_widget(
+            Paragraph::new(vec![
+                field("Size", size),
+                field("Modified", info.item.mod_time.clone()),
+                field("MIME type", info.item.mime_type.clone()),
+                field("Writable", writable.to_string()),
+            ]),
+            chunks[0],
+        );
+
+        Self::render_metadata(f, chunks[1], view, theme);
+        if let Some(ref edit) = view.edit {
+            Self::render_form(f, chunks[2], edit, theme);
+        }
+
+        let status = if view.saving {
+            Line::styled("Saving...", Style::default().fg(theme.warning))
+        } else if let Some(ref error) = view.error {
+            Line::styled(error.as_str(), Style::default().fg(theme.error))
+        } else {
+            Line::default()
+        };
+        f.render_widget(Paragraph::new(status), chunks[3]);
+        let help = match (view.edit.is_some(), view.writable) {
+            (true, _) => "Tab: Key / value | Enter: Save | Esc: Cancel",
+            (false, Some(true)) => {
+                "j/k: Select | e: Edit value | a: Add key | r: Reload | Esc: Close"
+            }
+            (false, _) => "j/k: Select | r: Reload | Esc: Close",
+        };
+        f.render_widget(
+            Paragraph::new(help).style(Style::default().fg(theme.dim)),
+            chunks[4],
+        );
+    }
+
+    /// Draws one row per metadata key.
+    fn render_metadata(f: &mut Frame, area: Rect, view: &MetadataView, theme: &Theme) {
+        let block = Block::default()
+            .title(" Metadata ")
+            .borders(Borders::TOP)
+            .border_style(Style::default().fg(theme.border));
+        if view.metadata.is_empty() {
+            f.render_widget(
+                Paragraph::new("The backend keeps no metadata for this file")
+                    .style(Style::default().fg(theme.dim))
+                    .block(block),
+                area,
+            );
+            return;
+        }
+        let key_width = view
+            .metadata
+            .iter()
+            .map(|(key, _)| key.chars().count())
+            .max()
+            .unwrap_or(0)
+            .min(30);
+        let items: Vec<ListItem> = view
+            .metadata
+            .iter()
+            .map(|(key, value)| {
+                ListItem::new(Line::from(vec![
+                    Span::styled(
+                        format!("{:<width$}  ", key, width = key_width),
+                        Style::default().fg(theme.accent),
+                    ),
+                    Span::raw(value.as_str()),
+                ]))
+            })
+            .collect();
+        let mut state = ListState::default().with_selected(Some(view.selected));
+        f.render_stateful_widget(
+            List::new(items)
+                .block(block)
+                .highlight_style(theme.selected())
+                .highlight_symbol(theme.highlight_symbol()),
+            area,
+            &mut state,
+        );
+    }
+
+    /// Draws the key and value being set, the field typed in with its
+    /// cursor.
+    fn render_form(f: &mut Frame, area: Rect, edit: &MetadataEdit, theme: &Theme) {
+        let width = usize::from(area.width).saturating_sub(10);
+        let row = |label: &str, text: &str, field: MetadataField| {
+            let mut spans = vec![Span::styled(
+                format!("{:<8}", label),
+                Style::default().fg(theme.dim),
+            )];
+            if edit.field == field {
+                spans.extend(edit.input.spans(text, width));
+            } else {
+                spans.push(Span::raw(text.to_string()));
+            }
+            Line::from(spans)
+        };
+        f.render_widget(
+            Paragraph::new(vec![
+                row("Key", &edit.key, MetadataField::Key),
+                row("Value", &edit.value, MetadataField::Value),
+            ])
+            .block(
+                Block::default()
+                    .title(if edit.new {
+                        " Add Key "
+                    } else {
+                        " Edit Value "
+                    })
+                    .borders(Borders::TOP)
+                    .border_style(Style::default().fg(theme.border)),
+            ),
+            area,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn test_edits_the_highlighted_key_or_a_new_one() {
+        let mut view = MetadataView::new("s3".to_string(), "a.jpg".to_string());
+        view.start_edit(false);
+        assert!(view.edit.is_none());
+
+        let metadata = BTreeMap::from([
+            ("cache-control".to_string(), "no-cache".to_string()),
+            ("content-type".to_string(), "image/jpeg".to_string()),
+        ]);
+        view.set_info(Ok(ObjectInfo {
+            metadata: Some(metadata),
+            ..ObjectInfo::default()
+        }));
+        view.select_next();
+        view.start_edit(false);
+        let edit = view.edit.as_mut().unwrap();
+        assert_eq!(
+            (edit.key.as_str(), edit.value.as_str()),
+            ("content-type", "image/jpeg")
+        );
+        edit.toggle_field();
+        assert_eq!(edit.field, MetadataField::Value);
+
+        view.start_edit(true);
+        let edit = view.edit.as_mut().unwrap();
+        assert!(edit.key.is_empty());
+        edit.toggle_field();
+        assert_eq!(edit.field, MetadataField::Value);
+    }
+}
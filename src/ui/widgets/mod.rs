@@ -1,17 +1,81 @@
 //! UI widget components.
 
+pub mod audit;
+pub mod batch_rename;
+pub mod calls;
+pub mod command_palette;
+pub mod compare;
+pub mod config_password;
+pub mod config_wizard;
 pub mod confirm_modal;
+pub mod conflict;
+pub mod copy_remote;
+pub mod copy_url;
 pub mod create_remote;
+pub mod create_remote_options;
+pub mod create_remote_upstream;
+pub mod create_remote_widget;
+pub mod daemon_setup;
+pub mod error_modal;
 pub mod file_list;
 pub mod file_operations_modal;
+pub mod filter;
+pub mod goto;
 pub mod help;
+pub mod help_overlay;
+pub mod history;
+pub mod jobs;
+pub mod largest;
+pub mod log_pane;
+pub mod mark_glob;
+pub mod metadata;
+pub mod mounts;
+pub mod preview;
+pub mod quit;
+pub mod recent;
+pub mod remote_info;
 pub mod remote_list;
+pub mod spinner;
 pub mod status_bar;
+pub mod tier;
+pub mod usage;
+pub mod versions;
 
-pub use confirm_modal::{ConfirmModal, ConfirmWidget};
-pub use create_remote::{CreateRemoteModal, CreateRemoteMode, CreateRemoteWidget, RemoteField};
-pub use file_list::FileListWidget;
+pub use audit::{AuditView, AuditWidget};
+pub use batch_rename::{BatchRename, BatchRenameWidget, RenameField, RenamePair};
+pub use calls::CallsWidget;
+pub use command_palette::{CommandPalette, CommandPaletteWidget, PaletteItem};
+pub use compare::{CompareForm, CompareSide, CompareView, CompareWidget, DiffKind, DiffRow};
+pub use config_password::{ConfigPasswordModal, ConfigPasswordWidget};
+pub use config_wizard::{ConfigWizard, ConfigWizardWidget};
+pub use confirm_modal::{ConfirmChoice, ConfirmModal, ConfirmWidget};
+pub use conflict::{ConflictModal, ConflictWidget};
+pub use copy_remote::{CopyRemoteModal, CopyRemoteWidget};
+pub use copy_url::{CopyUrlField, CopyUrlPrompt, CopyUrlWidget};
+pub use create_remote::{CreateRemoteModal, CreateRemoteMode, RemoteField};
+pub use create_remote_widget::CreateRemoteWidget;
+pub use daemon_setup::{DaemonSetup, DaemonSetupWidget};
+pub use error_modal::{ErrorChoice, ErrorModal, ErrorWidget};
+pub use file_list::{ColumnView, FileKind, FileListWidget, NameView};
 pub use file_operations_modal::{FileOperationType, FileOperationsModal, FileOperationsWidget};
-pub use help::HelpWidget;
-pub use remote_list::RemoteListWidget;
-pub use status_bar::StatusBarWidget;
+pub use filter::{FilterField, FilterForm, FilterWidget};
+pub use goto::{GoToPrompt, GoToWidget};
+pub use help::{HelpContext, HelpWidget};
+pub use help_overlay::HelpOverlayWidget;
+pub use history::{HistoryView, HistoryWidget};
+pub use jobs::{JobsView, JobsWidget};
+pub use largest::{LargestPrompt, LargestView, LargestWidget};
+pub use log_pane::{LogView, LogsWidget};
+pub use mark_glob::{GlobPrompt, GlobPromptWidget};
+pub use metadata::{MetadataEdit, MetadataField, MetadataView, MetadataWidget};
+pub use mounts::{MountField, MountForm, MountsView, MountsWidget};
+pub use preview::{Preview, PreviewContent, PreviewWidget};
+pub use quit::{QuitChoice, QuitModal, QuitWidget};
+pub use recent::{RecentPicker, RecentWidget};
+pub use remote_info::{InfoRow, RemoteInfo, RemoteInfoWidget};
+pub use remote_list::{RemoteDetails, RemoteListWidget};
+pub use spinner::SpinnerWidget;
+pub use status_bar::{StatusBarWidget, StatusView};
+pub use tier::{TierPrompt, TierWidget};
+pub use usage::{UsageView, UsageWidget};
+pub use versions::{VersionsView, VersionsWidget};
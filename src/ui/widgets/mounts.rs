@@ -0,0 +1,358 @@
+//! Mounts view: the remotes the daemon has mounted on local
+//! directories, and a form to mount another.
+//!
+//! Mounts live on the daemon's machine, so the mount point is a
+//! directory there, which is not necessarily where LazyFile runs.
+
+use crate::error::Result;
+use crate::rclone::Mount;
+use crate::ui::Theme;
+use crate::ui::layout::modal_area;
+use crate::ui::text;
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::Style,
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph},
+};
+
+/// Field of the mount form that takes typed characters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MountField {
+    /// What to mount, as `remote:path`.
+    Source,
+    /// Local directory to mount it on.
+    MountPoint,
+}
+
+/// Form for a new mount.
+#[derive(Debug, Clone)]
+pub struct MountForm {
+    /// What to mount, as `remote:path`.
+    pub source: String,
+    /// Local directory on the daemon's machine.
+    pub mount_point: String,
+    /// Field being typed into.
+    pub focus: MountField,
+    /// Whether the mount request is in flight.
+    pub waiting: bool,
+    /// Why the form cannot be sent, or why mounting failed.
+    pub error: Option<String>,
+}
+
+impl MountForm {
+    /// Creates the form with `source` filled in and the mount point
+    /// focused.
+    pub fn new(source: String) -> Self {
+        let focus = if source.is_empty() {
+            MountField::Source
+        } else {
+            MountField::MountPoint
+        };
+        Self {
+            source,
+            mount_point: String::new(),
+            focus,
+            waiting: false,
+            error: None,
+        }
+    }
+
+    /// Moves the focus to the other field.
+    pub fn toggle_focus(&mut self) {
+        self.focus = match self.focus {
+            MountField::Source => MountField::MountPoint,
+            MountField::MountPoint => MountField::Source,
+        };
+    }
+
+    pub fn input_char(&mut self, c: char) {
+        // Bracketed paste can deliver control characters as Char
+        // events; they are never part of a path.
+        if c.is_control() {
+            return;
+        }
+        self.field_mut().push(c);
+        self.error = None;
+    }
+
+    pub fn backspace(&mut self) {
+        text::pop_grapheme(self.field_mut());
+        self.error = None;
+    }
+
+    /// Splits the source into the remote and the path within it.
+    ///
+    /// # Errors
+    /// Returns why the form cannot be sent: a source without a
+    /// remote, or no mount point.
+    pub fn target(&self) -> std::result::Result<(String, String), &'static str> {
+        let Some((remote, path)) = self.source.split_once(':') else {
+            return Err("Source must be remote:path");
+        };
+        if remote.is_empty() {
+            return Err("Source must name a remote");
+        }
+        if self.mount_point.is_empty() {
+            return Err("A mount point is required");
+        }
+        Ok((remote.to_string(), path.to_string()))
+    }
+
+    fn field_mut(&mut self) -> &mut String {
+        match self.focus {
+            MountField::Source => &mut self.source,
+            MountField::MountPoint => &mut self.mount_point,
+        }
+    }
+}
+
+/// Mounts view state.
+#[derive(Debug, Clone, Default)]
+pub struct MountsView {
+    /// Active mounts, as of the last listing.
+    pub mounts: Vec<Mount>,
+    /// Whether a listing is in flight.
+    pub loading: bool,
+    /// Index of the highlighted mount.
+    pub selected: usize,
+    /// Why the last listing failed, if it did.
+    pub error: Option<String>,
+    /// Form for a new mount, while it is open.
+    pub form: Option<MountForm>,
+}
+
+impl MountsView {
+    /// Creates the view while the first listing is in flight.
+    pub fn new() -> Self {
+        Self {
+            loading: true,
+            ..Self::default()
+        }
+    }
+
+    /// Shows a finished listing, keeping the selection in range.
+    pub fn set_mounts(&mut self, result: Result<Vec<Mount>>) {
+        self.loading = false;
+        match result {
+            Ok(mounts) => {
+                self.mounts = mounts;
+                self.error = None;
+            }
+            Err(e) => self.error = Some(e.to_string()),
+        }
+        self.selected = self.selected.min(self.mounts.len().saturating_sub(1));
+    }
+
+    /// Returns the highlighted mount.
+    pub fn selected_mount(&self) -> Option<&Mount> {
+        self.mounts.get(self.selected)
+    }
+
+    pub fn select_next(&mut self) {
+        if self.selected + 1 < self.mounts.len() {
+            self.selected += 1;
+        }
+    }
+
+    pub fn select_prev(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+    }
+}
+
+/// Widget for the mounts view.
+pub struct MountsWidget;
+
+impl MountsWidget {
+    pub fn render(f: &mut Frame, area: Rect, view: &MountsView, theme: &Theme) {
+        f.render_widget(Clear, area);
+        f.render_widget(
+            Block::default().style(Style::default().bg(theme.backdrop)),
+            area,
+        );
+
+        let modal_area = modal_area(area, 76, 20);
+        f.render_widget(Clear, modal_area);
+        f.render_widget(
+            Block::default()
+                .title(" Mounts ")
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(theme.border)),
+            modal_area,
+        );
+
+        let inner = Rect {
+            x: modal_area.x + 1,
+            y: modal_area.y + 1,
+            width: modal_area.width.saturating_sub(2),
+            height: modal_area.height.saturating_sub(2),
+        };
+        let form_height = if view.form.is_some() { 3 } else { 0 };
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Min(1),
+                Constraint::Length(form_height),
+                Constraint::Length(1),
+            ])
+            .split(inner);
+
+        Self::render_mounts(f, chunks[0], view, theme);
+        if let Some(ref form) = view.form {
+            Self::render_form(f, chunks[1], form, theme);
+        }
+
+        let (help, style) = match view.form {
+            Some(MountForm {
+                error: Some(ref error),
+                ..
+            }) => (error.as_str(), Style::default().fg(theme.error)),
+            Some(MountForm { waiting: true, .. }) => {
+                ("Mounting...", Style::default().fg(theme.warning))
+            }
+            Some(_) => (
+                "Tab: Next field | Enter: Mount | Esc: Back",
+                Style::default().fg(theme.dim),
+            ),
+            None => (
+                "n: New mount | u: Unmount | r: Refresh | Esc: Close",
+                Style::default().fg(theme.dim),
+            ),
+        };
+        f.render_widget(Paragraph::new(help).style(style), chunks[2]);
+    }
+
+    /// Draws the active mounts, or why there are none to show.
+    fn render_mounts(f: &mut Frame, area: Rect, view: &MountsView, theme: &Theme) {
+        let status = match (&view.error, view.loading) {
+            (Some(error), _) => Some((error.as_str(), theme.error)),
+            (None, true) if view.mounts.is_empty() => Some(("Loading mounts...", theme.dim)),
+            (None, _) if view.mounts.is_empty() => Some(("Nothing is mounted", theme.dim)),
+            _ => None,
+        };
+        if let Some((text, color)) = status {
+            f.render_widget(Paragraph::new(text).style(Style::default().fg(color)), area);
+            return;
+        }
+
+        let items: Vec<ListItem> = view
+            .mounts
+            .iter()
+            .map(|mount| {
+                ListItem::new(Line::from(vec![
+                    Span::styled(mount.mount_point.as_str(), Style::default().bold()),
+                    Span::raw(" ← "),
+                    Span::styled(mount.fs.as_str(), Style::default().fg(theme.accent)),
+                    Span::styled(
+                        format!(" {}", mount.mounted_on),
+                        Style::default().fg(theme.dim),
+                    ),
+                ]))
+            })
+            .collect();
+        let (highlight, symbol) = if view.form.is_some() {
+            (Style::default(), "")
+        } else {
+            (theme.selected(), theme.highlight_symbol())
+        };
+        let mut state = ListState::default().with_selected(Some(view.selected));
+        f.render_stateful_widget(
+            List::new(items)
+                .highlight_style(highlight)
+                .highlight_symbol(symbol),
+            area,
+            &mut state,
+        );
+    }
+
+    /// Draws the new mount form below the list.
+    fn render_form(f: &mut Frame, area: Rect, form: &MountForm, theme: &Theme) {
+        let field = |label: &'static str, value: &str, focused: bool| {
+            let style = if focused {
+                Style::default().fg(theme.accent).bold()
+            } else {
+                Style::default()
+            };
+            Line::from(vec![
+                Span::styled(label, Style::default().fg(theme.heading)),
+                Span::styled(value.to_string(), style),
+            ])
+        };
+        let lines = vec![
+            Line::default(),
+            field(
+                "Source:      ",
+                &form.source,
+                form.focus == MountField::Source,
+            ),
+            field(
+                "Mount point: ",
+                &form.mount_point,
+                form.focus == MountField::MountPoint,
+            ),
+        ];
+        f.render_widget(Paragraph::new(lines), area);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::LazyFileError;
+
+    fn mount(point: &str) -> Mount {
+        Mount {
+            fs: "gdrive:".to_string(),
+            mount_point: point.to_string(),
+            mounted_on: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_form_focuses_mount_point_when_source_is_known() {
+        let mut form = MountForm::new("gdrive:docs".to_string());
+        assert_eq!(form.focus, MountField::MountPoint);
+        for c in "/mnt/docs".chars() {
+            form.input_char(c);
+        }
+        assert_eq!(
+            form.target(),
+            Ok(("gdrive".to_string(), "docs".to_string()))
+        );
+        assert_eq!(form.mount_point, "/mnt/docs");
+    }
+
+    #[test]
+    fn test_form_rejects_incomplete_input() {
+        let mut form = MountForm::new(String::new());
+        assert_eq!(form.focus, MountField::Source);
+        form.input_char('x');
+        assert!(form.target().is_err());
+        form.input_char(':');
+        assert!(form.target().is_err());
+        form.toggle_focus();
+        form.input_char('/');
+        assert!(form.target().is_ok());
+    }
+
+    #[test]
+    fn test_failed_listing_keeps_previous_mounts() {
+        let mut view = MountsView::new();
+        view.set_mounts(Ok(vec![mount("/mnt/a"), mount("/mnt/b")]));
+        view.select_next();
+        view.select_next();
+        assert_eq!(view.selected, 1);
+
+        view.set_mounts(Err(LazyFileError::RcloneApi {
+            endpoint: "mount/listmounts",
+            message: "down".to_string(),
+        }));
+        assert_eq!(view.mounts.len(), 2);
+        assert!(view.error.is_some());
+
+        view.set_mounts(Ok(vec![mount("/mnt/a")]));
+        assert_eq!(view.selected, 0);
+        assert!(view.error.is_none());
+    }
+}
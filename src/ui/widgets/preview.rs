@@ -0,0 +1,316 @@
+//! Full-screen preview of a remote file, as text or a hex dump.
+
+use crate::ui::Theme;
+use crate::ui::highlight::{StyledLine, highlight};
+use ratatui::{
+    Frame,
+    layout::Rect,
+    style::Style,
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+};
+
+/// Bytes fetched for a preview. Larger files are cut off.
+pub const PREVIEW_LIMIT: u64 = 64 * 1024;
+
+/// Bytes shown per hex dump row.
+const HEX_ROW: usize = 16;
+
+/// Spaces a tab expands to in text previews.
+const TAB: &str = "    ";
+
+/// What the preview currently shows.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PreviewContent {
+    /// The file is still being fetched.
+    Loading,
+    /// Decoded text with no grammar to highlight it.
+    Text(String),
+    /// Text highlighted for its file type.
+    Highlighted(Vec<StyledLine>),
+    /// Raw bytes of a binary file, shown as a hex dump.
+    Binary(Vec<u8>),
+    /// The fetch failed, with the reason.
+    Failed(String),
+}
+
+impl PreviewContent {
+    /// Classifies fetched bytes as text or binary.
+    pub fn from_bytes(bytes: Vec<u8>) -> Self {
+        if is_binary(&bytes) {
+            Self::Binary(bytes)
+        } else {
+            Self::Text(String::from_utf8_lossy(&bytes).into_owned())
+        }
+    }
+
+    /// Classifies fetched bytes and highlights text for the file type
+    /// of `path` with the syntect scheme `syntax`. Highlighting is
+    /// CPU-bound; run it off the UI thread.
+    pub fn highlighted(bytes: Vec<u8>, path: &str, syntax: &str) -> Self {
+        match Self::from_bytes(bytes) {
+            Self::Text(text) => match highlight(&text, path, syntax) {
+                Some(lines) => Self::Highlighted(lines),
+                None => Self::Text(text),
+            },
+            other => other,
+        }
+    }
+}
+
+/// Preview modal state.
+#[derive(Debug, Clone)]
+pub struct Preview {
+    /// Remote the file lives on.
+    pub remote: String,
+    /// Path of the file within the remote.
+    pub path: String,
+    /// Full size of the file in bytes.
+    pub size: u64,
+    /// Fetched content.
+    pub content: PreviewContent,
+    /// Index of the first visible line.
+    pub scroll: usize,
+}
+
+impl Preview {
+    /// Create a preview waiting for its content.
+    pub fn new(remote: impl Into<String>, path: impl Into<String>, size: u64) -> Self {
+        Self {
+            remote: remote.into(),
+            path: path.into(),
+            size,
+            content: PreviewContent::Loading,
+            scroll: 0,
+        }
+    }
+
+    /// Stores fetched bytes, as text unless they look binary.
+    pub fn set_bytes(&mut self, bytes: Vec<u8>) {
+        self.content = PreviewContent::from_bytes(bytes);
+    }
+
+    /// Returns whether only the start of the file was fetched.
+    pub fn is_truncated(&self) -> bool {
+        self.size > PREVIEW_LIMIT
+    }
+
+    /// Returns the number of lines the preview has to show.
+    pub fn line_count(&self) -> usize {
+        match self.content {
+            PreviewContent::Text(ref text) => text.lines().count(),
+            PreviewContent::Highlighted(ref lines) => lines.len(),
+            PreviewContent::Binary(ref bytes) => bytes.len().div_ceil(HEX_ROW),
+            PreviewContent::Loading | PreviewContent::Failed(_) => 1,
+        }
+    }
+}
+
+/// Returns whether `bytes` should be shown as a hex dump: they hold
+/// a NUL byte or are not UTF-8. A multi-byte character cut off by
+/// [`PREVIEW_LIMIT`] at the very end still counts as text.
+pub fn is_binary(bytes: &[u8]) -> bool {
+    if bytes.contains(&0) {
+        return true;
+    }
+    match std::str::from_utf8(bytes) {
+        Ok(_) => false,
+        Err(e) => e.error_len().is_some(),
+    }
+}
+
+/// Formats one hex dump row: offset, sixteen hex bytes in two groups,
+/// and the printable ASCII.
+fn hex_row(offset: usize, chunk: &[u8]) -> (String, String, String) {
+    let mut hex = String::with_capacity(HEX_ROW * 3 + 1);
+    for i in 0..HEX_ROW {
+        if i == HEX_ROW / 2 {
+            hex.push(' ');
+        }
+        match chunk.get(i) {
+            Some(byte) => hex.push_str(&format!("{:02x} ", byte)),
+            None => hex.push_str("   "),
+        }
+    }
+    let ascii = chunk
+        .iter()
+        .map(|&b| {
+            if b.is_ascii_graphic() || b == b' ' {
+                char::from(b)
+            } else {
+                '.'
+            }
+        })
+        .collect();
+    (format!("{:08x}  ", offset), hex, ascii)
+}
+
+/// Widget drawing the preview over the whole screen.
+pub struct PreviewWidget;
+
+impl PreviewWidget {
+    /// Render the preview over all of `area`.
+    ///
+    /// # Arguments
+    /// * `f` - Frame for rendering
+    /// * `area` - Area to cover
+    /// * `preview` - File and content to show
+    /// * `theme` - Colors to draw with
+    pub fn render(f: &mut Frame, area: Rect, preview: &Preview, theme: &Theme) {
+        let rows = usize::from(area.height.saturating_sub(2));
+        let scroll = preview.scroll.min(Self::max_scroll(preview, area));
+        let lines = Self::lines(preview, scroll, rows, theme);
+
+        let mode = match preview.content {
+            PreviewContent::Binary(_) => " [hex]",
+            _ => "",
+        };
+        let truncated = if preview.is_truncated() {
+            " (first 64 KiB)"
+        } else {
+            ""
+        };
+        let title = format!(
+            " {}:{}{}{} - j/k scroll, Esc close ",
+            preview.remote, preview.path, mode, truncated
+        );
+
+        f.render_widget(Clear, area);
+        f.render_widget(
+            Paragraph::new(lines).block(
+                Block::default()
+                    .title(title)
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(theme.border)),
+            ),
+            area,
+        );
+    }
+
+    /// Returns the largest useful scroll offset when the preview
+    /// covers `area`.
+    pub fn max_scroll(preview: &Preview, area: Rect) -> usize {
+        let rows = usize::from(area.height.saturating_sub(2));
+        preview.line_count().saturating_sub(rows)
+    }
+
+    /// Builds only the visible lines, so long files stay cheap to
+    /// draw.
+    fn lines(preview: &Preview, scroll: usize, rows: usize, theme: &Theme) -> Vec<Line<'static>> {
+        match preview.content {
+            PreviewContent::Loading => {
+                vec![Line::styled("Loading...", Style::default().fg(theme.dim))]
+            }
+            PreviewContent::Failed(ref reason) => {
+                vec![Line::styled(
+                    reason.clone(),
+                    Style::default().fg(theme.error),
+                )]
+            }
+            PreviewContent::Text(ref text) => text
+                .lines()
+                .skip(scroll)
+                .take(rows)
+                .map(|line| Line::raw(line.replace('\t', TAB)))
+                .collect(),
+            PreviewContent::Highlighted(ref lines) => lines
+                .iter()
+                .skip(scroll)
+                .take(rows)
+                .map(|line| {
+                    Line::from(
+                        line.iter()
+                            .map(|(style, piece)| Span::styled(piece.clone(), *style))
+                            .collect::<Vec<_>>(),
+                    )
+                })
+                .collect(),
+            PreviewContent::Binary(ref bytes) => bytes
+                .chunks(HEX_ROW)
+                .enumerate()
+                .skip(scroll)
+                .take(rows)
+                .map(|(row, chunk)| {
+                    let (offset, hex, ascii) = hex_row(row * HEX_ROW, chunk);
+                    Line::from(vec![
+                        Span::styled(offset, Style::default().fg(theme.dim)),
+                        Span::raw(hex),
+                        Span::styled(format!("|{}|", ascii), Style::default().fg(theme.accent)),
+                    ])
+                })
+                .collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detects_binary() {
+        assert!(!is_binary(b"hello\nworld"));
+        assert!(!is_binary("héllo".as_bytes()));
+        assert!(is_binary(b"PK\x03\x04\x00\x00"));
+        assert!(is_binary(&[0xff, 0xfe, 0x41]));
+    }
+
+    #[test]
+    fn test_truncated_utf8_is_still_text() {
+        let bytes = "aé".as_bytes();
+        assert!(!is_binary(&bytes[..bytes.len() - 1]));
+    }
+
+    #[test]
+    fn test_hex_row_formats_offset_bytes_and_ascii() {
+        let (offset, hex, ascii) = hex_row(32, b"AB\x00");
+        assert_eq!(offset, "00000020  ");
+        assert!(hex.starts_with("41 42 00 "));
+        assert_eq!(hex.len(), HEX_ROW * 3 + 1);
+        assert_eq!(ascii, "AB.");
+    }
+
+    #[test]
+    fn test_line_count_by_content() {
+        let mut preview = Preview::new("r", "f", 40);
+        assert_eq!(preview.line_count(), 1);
+
+        preview.set_bytes(b"a\nb\nc".to_vec());
+        assert_eq!(preview.line_count(), 3);
+
+        preview.set_bytes(vec![0; 40]);
+        assert!(matches!(preview.content, PreviewContent::Binary(_)));
+        assert_eq!(preview.line_count(), 3);
+    }
+
+    #[test]
+    fn test_highlights_known_file_types() {
+        let source = b"fn main() {}\n".to_vec();
+        assert!(matches!(
+            PreviewContent::highlighted(source.clone(), "main.rs", "base16-ocean.dark"),
+            PreviewContent::Highlighted(_)
+        ));
+        assert!(matches!(
+            PreviewContent::highlighted(source, "notes.unknownext", "base16-ocean.dark"),
+            PreviewContent::Text(_)
+        ));
+        assert!(matches!(
+            PreviewContent::highlighted(vec![0; 4], "main.rs", "base16-ocean.dark"),
+            PreviewContent::Binary(_)
+        ));
+    }
+
+    #[test]
+    fn test_max_scroll_clamps_to_content() {
+        let mut preview = Preview::new("r", "f", 0);
+        preview.set_bytes(b"1\n2\n3\n4\n5\n6\n7\n8\n9\n10".to_vec());
+        assert_eq!(
+            PreviewWidget::max_scroll(&preview, Rect::new(0, 0, 80, 6)),
+            6
+        );
+        assert_eq!(
+            PreviewWidget::max_scroll(&preview, Rect::new(0, 0, 80, 50)),
+            0
+        );
+    }
+}
@@ -0,0 +1,246 @@
+//! Dialog shown when quitting while changes to files are still in
+//! flight or the daemon still runs jobs.
+
+use super::jobs::format_duration;
+use crate::rclone::Job;
+use crate::ui::Theme;
+use crate::ui::layout::modal_area;
+use ratatui::{
+    Frame,
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::Style,
+    text::Line,
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+};
+use std::time::Instant;
+
+/// What to do about the running jobs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QuitChoice {
+    /// Quit once every job has finished.
+    Wait,
+    /// Quit now and leave the jobs running on the daemon.
+    Detach,
+    /// Stop the jobs, then quit.
+    Cancel,
+}
+
+impl QuitChoice {
+    /// Returns the button text.
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Wait => "Wait",
+            Self::Detach => "Detach",
+            Self::Cancel => "Cancel jobs",
+        }
+    }
+}
+
+/// Buttons of the dialog, left to right.
+const CHOICES: [QuitChoice; 3] = [QuitChoice::Wait, QuitChoice::Detach, QuitChoice::Cancel];
+
+/// Buttons when LazyFile started the daemon, which stops with it:
+/// there is nothing to detach from.
+const OWNED_CHOICES: [QuitChoice; 2] = [QuitChoice::Wait, QuitChoice::Cancel];
+
+/// Quit dialog state.
+#[derive(Debug, Clone)]
+pub struct QuitModal {
+    /// Jobs still running, as of the last listing.
+    pub jobs: Vec<Job>,
+    /// Requests changing files that LazyFile has in flight; quitting
+    /// drops them.
+    pub transfers: usize,
+    /// Highlighted button.
+    pub selected: QuitChoice,
+    /// Whether LazyFile started the daemon itself.
+    pub owns_daemon: bool,
+    /// Whether LazyFile quits as soon as the jobs are done.
+    pub waiting: bool,
+    /// Whether the jobs were told to stop, so quitting need not wait
+    /// for LazyFile's own requests.
+    pub cancelling: bool,
+    /// Whether a listing is in flight.
+    pub loading: bool,
+    /// When to list the jobs again while waiting.
+    pub next_refresh: Option<Instant>,
+}
+
+impl QuitModal {
+    /// Creates the dialog for `jobs`, which are still running, and
+    /// `transfers` requests in flight.
+    pub fn new(jobs: Vec<Job>, transfers: usize, owns_daemon: bool) -> Self {
+        Self {
+            jobs,
+            transfers,
+            selected: QuitChoice::Wait,
+            owns_daemon,
+            waiting: false,
+            cancelling: false,
+            loading: false,
+            next_refresh: None,
+        }
+    }
+
+    /// Returns the buttons on offer, left to right.
+    pub fn choices(&self) -> &'static [QuitChoice] {
+        if self.owns_daemon {
+            &OWNED_CHOICES
+        } else {
+            &CHOICES
+        }
+    }
+
+    /// Moves the highlight `delta` buttons along, wrapping around.
+    pub fn step(&mut self, delta: isize) {
+        let choices = self.choices();
+        let current = choices
+            .iter()
+            .position(|choice| *choice == self.selected)
+            .unwrap_or(0);
+        let next = (current as isize + delta).rem_euclid(choices.len() as isize);
+        self.selected = choices[next as usize];
+    }
+}
+
+/// Widget for the quit dialog.
+pub struct QuitWidget;
+
+impl QuitWidget {
+    /// Render the quit dialog centered in `area`.
+    ///
+    /// # Arguments
+    /// * `f` - Frame for rendering
+    /// * `area` - Area to center the dialog in
+    /// * `modal` - Running jobs and the highlighted button
+    /// * `theme` - Colors to draw with
+    pub fn render(f: &mut Frame, area: Rect, modal: &QuitModal, theme: &Theme) {
+        f.render_widget(Clear, area);
+        f.render_widget(
+            Block::default().style(Style::default().bg(theme.backdrop)),
+            area,
+        );
+
+        let modal_area = modal_area(area, 60, 16);
+        f.render_widget(Clear, modal_area);
+        f.render_widget(
+            Block::default()
+                .title(" Quit ")
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(theme.warning)),
+            modal_area,
+        );
+
+        let inner = Rect {
+            x: modal_area.x + 1,
+            y: modal_area.y + 1,
+            width: modal_area.width.saturating_sub(2),
+            height: modal_area.height.saturating_sub(2),
+        };
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Min(1),
+                Constraint::Length(2),
+                Constraint::Length(1),
+            ])
+            .split(inner);
+
+        let dim = Style::default().fg(theme.dim);
+        let mut lines = Vec::new();
+        if !modal.jobs.is_empty() {
+            lines.push(Line::raw(format!(
+                "{} job(s) still running on the daemon:",
+                modal.jobs.len()
+            )));
+            lines.extend(modal.jobs.iter().map(|job| {
+                Line::raw(format!(
+                    "  #{:<5} {:<12} {:>7}",
+                    job.id,
+                    job.group,
+                    format_duration(job.duration)
+                ))
+            }));
+            lines.push(Line::default());
+        }
+        if modal.transfers > 0 {
+            lines.push(Line::raw(format!(
+                "{} change(s) to files still in flight; quitting drops them.",
+                modal.transfers
+            )));
+            lines.push(Line::default());
+        }
+        if modal.cancelling {
+            lines.push(Line::styled(
+                "Stopping the jobs; LazyFile quits once they have stopped.",
+                Style::default().fg(theme.warning),
+            ));
+        } else if modal.waiting {
+            lines.push(Line::styled(
+                "Waiting for them to finish; LazyFile quits once they are done.",
+                Style::default().fg(theme.warning),
+            ));
+        } else if modal.owns_daemon {
+            lines.push(Line::styled(
+                "The daemon LazyFile started stops when it quits, and so do its jobs.",
+                dim,
+            ));
+        } else {
+            lines.push(Line::styled(
+                "Detach quits and leaves them running on the daemon.",
+                dim,
+            ));
+        }
+        f.render_widget(Paragraph::new(lines).wrap(Wrap { trim: false }), chunks[0]);
+
+        let choices = modal.choices();
+        let buttons = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints(vec![
+                Constraint::Ratio(1, choices.len() as u32);
+                choices.len()
+            ])
+            .split(chunks[1]);
+        for (choice, area) in choices.iter().zip(buttons.iter()) {
+            let style = if modal.selected == *choice {
+                Style::default()
+                    .fg(theme.selection_fg)
+                    .bg(theme.heading)
+                    .bold()
+            } else {
+                Style::default().fg(theme.heading)
+            };
+            f.render_widget(
+                Paragraph::new(theme.button(choice.label(), modal.selected == *choice))
+                    .style(style)
+                    .alignment(Alignment::Center),
+                *area,
+            );
+        }
+
+        let help = if modal.owns_daemon {
+            "w: Wait | c: Cancel jobs | Enter: Choose | Esc: Back"
+        } else {
+            "w: Wait | d: Detach | c: Cancel jobs | Enter: Choose | Esc: Back"
+        };
+        f.render_widget(Paragraph::new(help).style(dim), chunks[2]);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_step_skips_detach_from_an_owned_daemon() {
+        let mut modal = QuitModal::new(Vec::new(), 0, false);
+        modal.step(1);
+        assert_eq!(modal.selected, QuitChoice::Detach);
+
+        let mut modal = QuitModal::new(Vec::new(), 0, true);
+        modal.step(1);
+        assert_eq!(modal.selected, QuitChoice::Cancel);
+        modal.step(1);
+        assert_eq!(modal.selected, QuitChoice::Wait);
+    }
+}
@@ -0,0 +1,196 @@
+//! Fuzzy-searchable list of recently visited locations.
+
+use crate::app::fuzzy;
+use crate::ui::Theme;
+use crate::ui::layout::modal_area;
+use crate::ui::text;
+use ratatui::{
+    Frame,
+    layout::Rect,
+    style::Style,
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph},
+};
+
+/// Maximum number of locations shown at once.
+const MAX_VISIBLE: usize = 12;
+
+/// Recent locations picker state.
+#[derive(Debug, Clone, Default)]
+pub struct RecentPicker {
+    /// Text typed so far.
+    pub query: String,
+    /// Index into the current matches.
+    pub selected: usize,
+    /// Locations as `remote:path`, most recent first.
+    pub locations: Vec<String>,
+}
+
+impl RecentPicker {
+    /// Creates the picker over `locations`, most recent first.
+    pub fn new(locations: Vec<String>) -> Self {
+        Self {
+            locations,
+            ..Self::default()
+        }
+    }
+
+    /// Appends a character to the query and selects the best match.
+    pub fn push_char(&mut self, c: char) {
+        self.query.push(c);
+        self.selected = 0;
+    }
+
+    /// Removes the last query character and selects the best match.
+    pub fn pop_char(&mut self) {
+        text::pop_grapheme(&mut self.query);
+        self.selected = 0;
+    }
+
+    /// Selects the next match, wrapping to the first.
+    pub fn select_next(&mut self) {
+        let len = self.matches().len();
+        if len > 0 {
+            self.selected = (self.selected + 1) % len;
+        }
+    }
+
+    /// Selects the previous match, wrapping to the last.
+    pub fn select_prev(&mut self) {
+        let len = self.matches().len();
+        if len > 0 {
+            self.selected = (self.selected + len - 1) % len;
+        }
+    }
+
+    /// Returns the locations matching the query, best first. Ties
+    /// keep the most recent first.
+    pub fn matches(&self) -> Vec<&str> {
+        let mut scored: Vec<(i64, &str)> = self
+            .locations
+            .iter()
+            .filter_map(|location| {
+                fuzzy::score(&self.query, location).map(|score| (score, location.as_str()))
+            })
+            .collect();
+        scored.sort_by_key(|(score, _)| std::cmp::Reverse(*score));
+        scored.into_iter().map(|(_, location)| location).collect()
+    }
+
+    /// Returns the highlighted location, if anything matches.
+    pub fn selected_location(&self) -> Option<String> {
+        self.matches()
+            .get(self.selected)
+            .map(|location| location.to_string())
+    }
+
+    /// Drops `location` from the list, keeping the selection in
+    /// range.
+    pub fn remove(&mut self, location: &str) {
+        self.locations.retain(|l| l != location);
+        self.selected = self.selected.min(self.matches().len().saturating_sub(1));
+    }
+}
+
+/// Widget for the recent locations picker.
+pub struct RecentWidget;
+
+impl RecentWidget {
+    pub fn render(f: &mut Frame, area: Rect, picker: &RecentPicker, theme: &Theme) {
+        let matches = picker.matches();
+        let list_rows = matches.len().clamp(1, MAX_VISIBLE);
+        let wanted_height = u16::try_from(list_rows + 4).unwrap_or(u16::MAX);
+        // Near the top, so the list grows downwards as matches appear.
+        let mut modal_area = modal_area(area, 64, wanted_height);
+        modal_area.y = (area.y + area.height / 6).min(area.bottom() - modal_area.height);
+
+        f.render_widget(Clear, modal_area);
+        let block = Block::default()
+            .title(" Recent Locations ")
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme.border));
+        let inner = block.inner(modal_area);
+        f.render_widget(block, modal_area);
+        if inner.height < 3 {
+            return;
+        }
+
+        let prompt = Line::from(vec![
+            Span::styled("> ", Style::default().fg(theme.heading)),
+            Span::raw(picker.query.as_str()),
+            Span::styled("_", Style::default().fg(theme.heading)),
+        ]);
+        f.render_widget(Paragraph::new(prompt), Rect { height: 1, ..inner });
+        let list_area = Rect {
+            y: inner.y + 1,
+            height: inner.height - 2,
+            ..inner
+        };
+        let help_area = Rect {
+            y: inner.y + inner.height - 1,
+            height: 1,
+            ..inner
+        };
+        f.render_widget(
+            Paragraph::new("Enter: Go | Ctrl-x: Forget | Esc: Cancel")
+                .style(Style::default().fg(theme.dim)),
+            help_area,
+        );
+
+        if matches.is_empty() {
+            let message = if picker.locations.is_empty() {
+                "No recent locations yet"
+            } else {
+                "No matching locations"
+            };
+            f.render_widget(
+                Paragraph::new(Span::styled(message, Style::default().fg(theme.dim))),
+                list_area,
+            );
+            return;
+        }
+
+        let items: Vec<ListItem> = matches.iter().map(|l| ListItem::new(*l)).collect();
+        let list = List::new(items)
+            .highlight_style(theme.selected())
+            .highlight_symbol(theme.highlight_symbol());
+        let offset = picker
+            .selected
+            .saturating_sub(usize::from(list_area.height.max(1)) - 1);
+        let mut state = ListState::default()
+            .with_offset(offset)
+            .with_selected(Some(picker.selected));
+        f.render_stateful_widget(list, list_area, &mut state);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_query_ranks_and_ties_keep_recency() {
+        let mut picker = RecentPicker::new(vec![
+            "s3:backup/2024".to_string(),
+            "gdrive:docs/reports".to_string(),
+            "gdrive:docs".to_string(),
+        ]);
+        assert_eq!(
+            picker.selected_location().as_deref(),
+            Some("s3:backup/2024")
+        );
+
+        for c in "docs".chars() {
+            picker.push_char(c);
+        }
+        assert_eq!(picker.matches(), ["gdrive:docs/reports", "gdrive:docs"]);
+        picker.select_prev();
+        assert_eq!(picker.selected, 1);
+        picker.remove("gdrive:docs");
+        assert_eq!(picker.selected, 0);
+        assert_eq!(
+            picker.selected_location().as_deref(),
+            Some("gdrive:docs/reports")
+        );
+    }
+}
@@ -0,0 +1,310 @@
+//! View of a remote: its backend, the path it opens at, every
+//! configured setting with secrets redacted, and what the backend
+//! supports. Only the start path can be edited here.
+
+use super::create_remote::SECRET_WORDS;
+use crate::rclone::{FsInfo, Provider};
+use crate::ui::Theme;
+use crate::ui::layout::modal_area;
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+};
+use std::collections::HashMap;
+
+/// Shown instead of a secret's value.
+const REDACTED: &str = "********";
+
+/// Features worth knowing about when choosing how to transfer, with
+/// rclone's name for each.
+const FEATURES: [(&str, &str); 7] = [
+    ("Copy", "Server-side copy"),
+    ("Move", "Server-side move"),
+    ("DirMove", "Server-side directory move"),
+    ("Purge", "Fast directory delete"),
+    ("PublicLink", "Public links"),
+    ("About", "Quota info"),
+    ("CanHaveEmptyDirectories", "Empty directories"),
+];
+
+/// One line of the view.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InfoRow {
+    /// Section title.
+    Heading(&'static str),
+    /// A label and its value.
+    Field(String, String),
+    /// Why part of the details is missing.
+    Error(String),
+    /// Space between sections.
+    Blank,
+}
+
+/// Remote details state.
+#[derive(Debug, Clone)]
+pub struct RemoteInfo {
+    /// Remote shown.
+    pub name: String,
+    /// Backend type, e.g. `drive`.
+    pub remote_type: String,
+    /// The backend's description, e.g. "Google Drive", if known.
+    pub description: Option<String>,
+    /// Settings other than the type, by name, with secrets redacted.
+    pub settings: Vec<(String, String)>,
+    /// What the backend supports, or why it could not be fetched.
+    pub info: Result<FsInfo, String>,
+    /// Path the remote opens at; empty for its root.
+    pub start_path: String,
+    /// Start path being typed, while editing it.
+    pub editing: Option<String>,
+    /// First row shown.
+    pub scroll: usize,
+}
+
+impl RemoteInfo {
+    /// Builds the view from a remote's `config`. The backends in
+    /// `providers` decide which settings are secret; settings they do
+    /// not describe are redacted if their name suggests a secret.
+    pub fn new(
+        name: String,
+        mut config: HashMap<String, String>,
+        info: Result<FsInfo, String>,
+        providers: &[Provider],
+    ) -> Self {
+        let remote_type = config.remove("type").unwrap_or_default();
+        let provider = providers.iter().find(|p| p.name == remote_type);
+        let schema = provider.map_or(&[][..], Provider::options);
+        let mut settings: Vec<(String, String)> = config
+            .into_iter()
+            .map(|(key, value)| {
+                let secret = match schema.iter().find(|option| option.name == key) {
+                    Some(option) => option.is_secret(),
+                    None => SECRET_WORDS.iter().any(|word| key.contains(word)),
+                };
+                let value = if secret && !value.is_empty() {
+                    REDACTED.to_string()
+                } else {
+                    value
+                };
+                (key, value)
+            })
+            .collect();
+        settings.sort();
+        Self {
+            name,
+            remote_type,
+            description: provider.map(|p| p.description.clone()),
+            settings,
+            info,
+            start_path: String::new(),
+            editing: None,
+            scroll: 0,
+        }
+    }
+
+    /// Returns the lines of the view, top to bottom.
+    pub fn rows(&self) -> Vec<InfoRow> {
+        let backend = match self.description {
+            Some(ref description) if !description.is_empty() => {
+                format!("{} ({})", self.remote_type, description)
+            }
+            _ => self.remote_type.clone(),
+        };
+        let start_path = if self.start_path.is_empty() {
+            format!("{}: (root)", self.name)
+        } else {
+            format!("{}:{}", self.name, self.start_path)
+        };
+        let mut rows = vec![
+            InfoRow::Field("Type".to_string(), backend),
+            InfoRow::Field("Start path".to_string(), start_path),
+            InfoRow::Blank,
+            InfoRow::Heading("Settings"),
+        ];
+        if self.settings.is_empty() {
+            rows.push(InfoRow::Field("(none)".to_string(), String::new()));
+        }
+        rows.extend(
+            self.settings
+                .iter()
+                .map(|(key, value)| InfoRow::Field(key.clone(), value.clone())),
+        );
+        rows.push(InfoRow::Blank);
+        rows.push(InfoRow::Heading("Features"));
+        match self.info {
+            Ok(ref info) => {
+                rows.extend(FEATURES.iter().map(|(name, label)| {
+                    let value = if info.supports(name) { "yes" } else { "no" };
+                    InfoRow::Field(label.to_string(), value.to_string())
+                }));
+                let hashes = match info.hashes() {
+                    [] => "none".to_string(),
+                    hashes => hashes.join(", "),
+                };
+                rows.push(InfoRow::Field("Hashes".to_string(), hashes));
+            }
+            Err(ref e) => rows.push(InfoRow::Error(format!("Could not fetch: {}", e))),
+        }
+        rows
+    }
+
+    /// Scrolls to row `to`, kept within the view.
+    pub fn scroll_to(&mut self, to: usize) {
+        self.scroll = to.min(self.rows().len().saturating_sub(1));
+    }
+}
+
+/// Widget for the remote details view.
+pub struct RemoteInfoWidget;
+
+impl RemoteInfoWidget {
+    pub fn render(f: &mut Frame, area: Rect, view: &RemoteInfo, theme: &Theme) {
+        f.render_widget(Clear, area);
+        f.render_widget(
+            Block::default().style(Style::default().bg(theme.backdrop)),
+            area,
+        );
+
+        let modal_area = modal_area(area, 72, 24);
+        f.render_widget(Clear, modal_area);
+        f.render_widget(
+            Block::default()
+                .title(format!(" {} ", view.name))
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(theme.border)),
+            modal_area,
+        );
+
+        let inner = Rect {
+            x: modal_area.x + 1,
+            y: modal_area.y + 1,
+            width: modal_area.width.saturating_sub(2),
+            height: modal_area.height.saturating_sub(2),
+        };
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(1), Constraint::Length(1)])
+            .split(inner);
+
+        let rows = view.rows();
+        let width = rows
+            .iter()
+            .filter_map(|row| match row {
+                InfoRow::Field(label, _) => Some(label.chars().count()),
+                _ => None,
+            })
+            .max()
+            .unwrap_or(0);
+        let lines: Vec<Line> = rows
+            .into_iter()
+            .skip(view.scroll)
+            .map(|row| match row {
+                InfoRow::Heading(title) => Line::from(Span::styled(
+                    title,
+                    Style::default()
+                        .fg(theme.accent)
+                        .add_modifier(Modifier::BOLD),
+                )),
+                InfoRow::Field(label, value) => Line::from(vec![
+                    Span::styled(
+                        format!("{:<width$}  ", label, width = width),
+                        Style::default().fg(theme.dim),
+                    ),
+                    Span::raw(value),
+                ]),
+                InfoRow::Error(text) => {
+                    Line::from(Span::styled(text, Style::default().fg(theme.error)))
+                }
+                InfoRow::Blank => Line::default(),
+            })
+            .collect();
+        f.render_widget(Paragraph::new(lines), chunks[0]);
+
+        let footer = match view.editing {
+            Some(ref input) => Line::from(vec![
+                Span::styled(
+                    format!("Start path: {}:", view.name),
+                    Style::default().fg(theme.heading),
+                ),
+                Span::raw(input.as_str()),
+                Span::styled("_", Style::default().fg(theme.heading)),
+                Span::styled(
+                    "  Enter: Save | Esc: Cancel",
+                    Style::default().fg(theme.dim),
+                ),
+            ]),
+            None => Line::from(Span::styled(
+                "j/k: Scroll | p: Start path | i/Esc: Close",
+                Style::default().fg(theme.dim),
+            )),
+        };
+        f.render_widget(Paragraph::new(footer), chunks[1]);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rclone::ConfigOption;
+
+    fn drive() -> Provider {
+        Provider {
+            name: "drive".to_string(),
+            description: "Google Drive".to_string(),
+            hide: false,
+            options: Some(vec![ConfigOption {
+                name: "client_secret".to_string(),
+                is_password: true,
+                ..ConfigOption::default()
+            }]),
+        }
+    }
+
+    #[test]
+    fn test_redacts_secrets() {
+        let config = HashMap::from([
+            ("type".to_string(), "drive".to_string()),
+            ("client_id".to_string(), "abc".to_string()),
+            ("client_secret".to_string(), "obscured".to_string()),
+            ("token".to_string(), r#"{"access_token":"x"}"#.to_string()),
+            ("team_drive".to_string(), String::new()),
+        ]);
+        let details = RemoteInfo::new(
+            "gdrive".to_string(),
+            config,
+            Ok(FsInfo::default()),
+            &[drive()],
+        );
+
+        assert_eq!(details.description.as_deref(), Some("Google Drive"));
+        assert_eq!(
+            details.settings,
+            vec![
+                ("client_id".to_string(), "abc".to_string()),
+                ("client_secret".to_string(), REDACTED.to_string()),
+                ("team_drive".to_string(), String::new()),
+                ("token".to_string(), REDACTED.to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_shows_why_features_are_missing() {
+        let config = HashMap::from([("type".to_string(), "s3".to_string())]);
+        let mut details =
+            RemoteInfo::new("s3".to_string(), config, Err("timeout".to_string()), &[]);
+
+        let rows = details.rows();
+        assert_eq!(
+            rows.first(),
+            Some(&InfoRow::Field("Type".to_string(), "s3".to_string()))
+        );
+        assert!(matches!(rows.last(), Some(InfoRow::Error(e)) if e.contains("timeout")));
+
+        details.scroll_to(usize::MAX);
+        assert_eq!(details.scroll, rows.len() - 1);
+    }
+}
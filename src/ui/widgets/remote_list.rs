@@ -1,11 +1,146 @@
 //! Remotes list widget.
+//!
+//! Each remote is marked with a colored icon for the kind of backend
+//! it uses, followed by the backend type, so a Drive, an S3 bucket
+//! and an SFTP server tell apart at a glance. The `icons` key picks
+//! shapes, Nerd Font glyphs or letters. Favorites carry a star.
+//! Remotes with a quota end in a usage bar, yellow or red past the
+//! `[quota]` thresholds. Collapsed to a strip, the panel shows just
+//! the icon and the first letter of each name.
 
-use crate::ui::styles;
+use crate::config::{IconSet, QuotaConfig, QuotaLevel};
+use crate::rclone::Quota;
+use crate::ui::layout::STRIP_WIDTH;
+use crate::ui::{Theme, text};
 use ratatui::{
     Frame,
     layout::Rect,
+    style::{Color, Style},
+    text::{Line, Span},
     widgets::{Block, Borders, List, ListItem},
 };
+use std::collections::HashMap;
+
+/// Consumer cloud drive backends.
+const DRIVES: &[&str] = &[
+    "drive",
+    "onedrive",
+    "dropbox",
+    "box",
+    "pcloud",
+    "mega",
+    "jottacloud",
+    "koofr",
+    "yandex",
+    "mailru",
+    "pikpak",
+    "protondrive",
+    "putio",
+    "premiumizeme",
+    "seafile",
+    "sharefile",
+    "opendrive",
+    "zoho",
+    "hidrive",
+    "iclouddrive",
+    "filefabric",
+    "quatrix",
+    "googlephotos",
+    "gofile",
+];
+
+/// Object store backends.
+const OBJECT_STORES: &[&str] = &[
+    "s3",
+    "b2",
+    "google cloud storage",
+    "azureblob",
+    "azurefiles",
+    "swift",
+    "qingstor",
+    "oracleobjectstorage",
+    "storj",
+    "sia",
+    "netstorage",
+];
+
+/// Backends that talk to a server over a network protocol.
+const SERVERS: &[&str] = &["sftp", "ftp", "smb", "webdav", "http", "hdfs"];
+
+/// Backends layered over other remotes.
+const LAYERED: &[&str] = &[
+    "alias", "union", "combine", "crypt", "chunker", "compress", "hasher", "cache",
+];
+
+/// Cells of the usage bar, between its brackets.
+const BAR_WIDTH: usize = 8;
+
+/// Kinds of backend, each drawn with its own icon and color.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RemoteKind {
+    /// Consumer cloud drives such as Google Drive or Dropbox.
+    Drive,
+    /// Object stores such as S3 or Azure Blob.
+    Object,
+    /// Servers reached over a network protocol such as SFTP.
+    Server,
+    /// The local filesystem.
+    Local,
+    /// Remotes layered over other remotes, such as crypt or union.
+    Virtual,
+    /// Anything else, or a type not fetched yet.
+    Other,
+}
+
+impl RemoteKind {
+    /// Returns the kind of backend `remote_type` is.
+    pub fn of(remote_type: &str) -> Self {
+        [
+            (DRIVES, Self::Drive),
+            (OBJECT_STORES, Self::Object),
+            (SERVERS, Self::Server),
+            (&["local"][..], Self::Local),
+            (LAYERED, Self::Virtual),
+        ]
+        .into_iter()
+        .find(|(types, _)| types.contains(&remote_type))
+        .map_or(Self::Other, |(_, kind)| kind)
+    }
+
+    /// Returns the icon from `icons` and its color in `theme`.
+    pub fn icon(self, icons: IconSet, theme: &Theme) -> (&'static str, Color) {
+        let glyphs = match icons {
+            IconSet::Unicode => ["●", "■", "▶", "◆", "◇", "○"],
+            IconSet::Nerd => [
+                "\u{f0c2}", "\u{f1c0}", "\u{f233}", "\u{f0a0}", "\u{f0c1}", "\u{f128}",
+            ],
+            IconSet::Ascii => ["D", "O", "S", "L", "V", "?"],
+        };
+        match self {
+            Self::Drive => (glyphs[0], theme.accent),
+            Self::Object => (glyphs[1], theme.warning),
+            Self::Server => (glyphs[2], theme.success),
+            Self::Local => (glyphs[3], theme.heading),
+            Self::Virtual => (glyphs[4], theme.dim),
+            Self::Other => (glyphs[5], theme.dim),
+        }
+    }
+}
+
+/// What the panel shows about each remote besides its name.
+#[derive(Debug, Clone, Copy)]
+pub struct RemoteDetails<'a> {
+    /// Backend type of each remote, by name.
+    pub types: &'a HashMap<String, String>,
+    /// Names of the starred remotes.
+    pub favorites: &'a [String],
+    /// Glyphs marking the kind of each remote.
+    pub icons: IconSet,
+    /// Space each remote has and uses, by name.
+    pub quotas: &'a HashMap<String, Quota>,
+    /// When usage bars turn yellow or red.
+    pub quota: &'a QuotaConfig,
+}
 
 /// Widget for displaying list of remotes.
 pub struct RemoteListWidget;
@@ -17,30 +152,181 @@ impl RemoteListWidget {
     /// * `f` - Frame for rendering
     /// * `area` - Area to render in
     /// * `remotes` - List of remote names
+    /// * `details` - Types and favorites of the remotes
+    /// * `offset` - Index of the first visible row
     /// * `selected` - Index of selected remote
     /// * `focused` - Whether this panel is focused
-    pub fn render(f: &mut Frame, area: Rect, remotes: &[String], selected: usize, focused: bool) {
-        let items: Vec<ListItem> = remotes.iter().map(|r| ListItem::new(r.as_str())).collect();
+    /// * `theme` - Colors to draw with
+    #[expect(clippy::too_many_arguments, reason = "mirrors the panel's state")]
+    pub fn render(
+        f: &mut Frame,
+        area: Rect,
+        remotes: &[String],
+        details: RemoteDetails,
+        offset: usize,
+        selected: usize,
+        focused: bool,
+        theme: &Theme,
+    ) {
+        let strip = area.width <= STRIP_WIDTH;
+        let row_width = usize::from(area.width.saturating_sub(2))
+            .saturating_sub(text::width(theme.highlight_symbol()));
+        let items: Vec<ListItem> = remotes
+            .iter()
+            .map(|name| {
+                let remote_type = details.types.get(name).map_or("", String::as_str);
+                let (icon, color) = RemoteKind::of(remote_type).icon(details.icons, theme);
+                if strip {
+                    let initial: String = name.chars().take(1).collect();
+                    return ListItem::new(Line::from(vec![
+                        Span::styled(icon, Style::new().fg(color)),
+                        Span::raw(initial),
+                    ]));
+                }
+                let star = if details.favorites.contains(name) {
+                    let star = if details.icons == IconSet::Ascii {
+                        "* "
+                    } else {
+                        "★ "
+                    };
+                    Span::styled(star, Style::new().fg(theme.warning))
+                } else {
+                    Span::raw("")
+                };
+                let mut spans = vec![
+                    Span::styled(icon, Style::new().fg(color)),
+                    Span::raw(" "),
+                    star,
+                    Span::raw(name.as_str()),
+                    Span::styled(format!(" {}", remote_type), Style::new().fg(theme.dim)),
+                ];
+                let percent = details.quotas.get(name).and_then(Quota::percent_used);
+                if let Some(percent) = percent {
+                    let level = details.quota.level(name, percent);
+                    let bar = usage_bar(percent, level, details.icons, theme);
+                    let used: usize = spans.iter().chain(&bar).map(Span::width).sum();
+                    // The bar goes at the right edge, if it fits at all.
+                    if used < row_width {
+                        spans.push(Span::raw(" ".repeat(row_width - used)));
+                        spans.extend(bar);
+                    }
+                }
+                ListItem::new(Line::from(spans))
+            })
+            .collect();
 
         let border_style = if focused {
-            styles::focused_style()
+            theme.focused()
         } else {
-            styles::NORMAL_STYLE
+            Style::new()
         };
 
         let list = List::new(items)
             .block(
                 Block::default()
                     .borders(Borders::ALL)
-                    .title(" Remotes ")
+                    .title(if strip { "" } else { " Remotes " })
                     .border_style(border_style),
             )
-            .style(styles::NORMAL_STYLE)
-            .highlight_style(styles::selected_style());
+            .style(Style::new())
+            .highlight_style(theme.selected())
+            .highlight_symbol(theme.highlight_symbol());
 
-        let mut list_state = ratatui::widgets::ListState::default();
-        list_state.select(Some(selected));
+        let mut list_state = ratatui::widgets::ListState::default()
+            .with_offset(offset)
+            .with_selected(Some(selected));
 
         f.render_stateful_widget(list, area, &mut list_state);
     }
 }
+
+/// Returns the bar showing `percent` used, colored by `level`. With
+/// `symbols` on, the level also shows as `!` or `!!`, for when the
+/// color cannot be told apart.
+fn usage_bar(percent: u8, level: QuotaLevel, icons: IconSet, theme: &Theme) -> Vec<Span<'static>> {
+    let (full, empty) = match icons {
+        IconSet::Ascii => ('#', '-'),
+        IconSet::Unicode | IconSet::Nerd => ('█', '░'),
+    };
+    let filled = (usize::from(percent) * BAR_WIDTH).div_ceil(100);
+    let (color, marker) = match level {
+        QuotaLevel::Normal => (theme.success, "  "),
+        QuotaLevel::Warning => (theme.warning, "! "),
+        QuotaLevel::Critical => (theme.error, "!!"),
+    };
+    let marker = if theme.symbols { marker } else { "" };
+    vec![
+        Span::raw(" ["),
+        Span::styled(
+            format!(
+                "{}{}",
+                full.to_string().repeat(filled),
+                empty.to_string().repeat(BAR_WIDTH - filled)
+            ),
+            Style::new().fg(color),
+        ),
+        Span::raw("]"),
+        Span::styled(format!("{:>4}%{}", percent, marker), Style::new().fg(color)),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_remote_kinds() {
+        assert_eq!(RemoteKind::of("drive"), RemoteKind::Drive);
+        assert_eq!(RemoteKind::of("s3"), RemoteKind::Object);
+        assert_eq!(RemoteKind::of("sftp"), RemoteKind::Server);
+        assert_eq!(RemoteKind::of("crypt"), RemoteKind::Virtual);
+        assert_eq!(RemoteKind::of(""), RemoteKind::Other);
+    }
+
+    #[test]
+    fn test_usage_bar_marks_levels() {
+        let text = |percent, level, theme: &Theme| -> String {
+            usage_bar(percent, level, IconSet::Ascii, theme)
+                .iter()
+                .map(|span| span.content.as_ref())
+                .collect()
+        };
+        let theme = Theme::default();
+        assert_eq!(text(0, QuotaLevel::Normal, &theme), " [--------]   0%");
+        assert_eq!(text(50, QuotaLevel::Normal, &theme), " [####----]  50%");
+        assert_eq!(text(100, QuotaLevel::Critical, &theme), " [########] 100%");
+
+        let symbols = Theme {
+            symbols: true,
+            ..Theme::default()
+        };
+        assert_eq!(
+            text(81, QuotaLevel::Warning, &symbols),
+            " [#######-]  81%! "
+        );
+        assert_eq!(
+            text(96, QuotaLevel::Critical, &symbols),
+            " [########]  96%!!"
+        );
+    }
+
+    #[test]
+    fn test_kinds_have_distinct_icons() {
+        let theme = Theme::default();
+        let kinds = [
+            RemoteKind::Drive,
+            RemoteKind::Object,
+            RemoteKind::Server,
+            RemoteKind::Local,
+            RemoteKind::Virtual,
+            RemoteKind::Other,
+        ];
+        for icons in [IconSet::Unicode, IconSet::Nerd, IconSet::Ascii] {
+            let glyphs: std::collections::HashSet<_> = kinds
+                .iter()
+                .map(|kind| kind.icon(icons, &theme).0)
+                .collect();
+            assert_eq!(glyphs.len(), kinds.len());
+        }
+    }
+}
@@ -0,0 +1,66 @@
+//! Activity spinner drawn on a panel border.
+
+use crate::ui::{Theme, text};
+use ratatui::{
+    Frame,
+    layout::Rect,
+    style::Style,
+    widgets::{Clear, Paragraph},
+};
+use std::time::Duration;
+
+/// Animation frames, one per tick.
+const FRAMES: [&str; 10] = ["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
+
+/// How long each frame is shown.
+pub const FRAME_TIME: Duration = Duration::from_millis(80);
+
+/// Spinner with a label, drawn into the top-right of a bordered panel.
+pub struct SpinnerWidget;
+
+impl SpinnerWidget {
+    /// Returns the frame to show after `elapsed`.
+    pub fn frame(elapsed: Duration) -> &'static str {
+        let tick = elapsed.as_millis() / FRAME_TIME.as_millis();
+        // The modulo keeps the index below FRAMES.len().
+        FRAMES[usize::try_from(tick % FRAMES.len() as u128).unwrap_or(0)]
+    }
+
+    /// Render the spinner on the top border of `area`.
+    ///
+    /// # Arguments
+    /// * `f` - Frame for rendering
+    /// * `area` - Panel whose border the spinner sits on
+    /// * `elapsed` - Time since the activity started
+    /// * `label` - Text after the spinner
+    /// * `theme` - Colors to draw with
+    pub fn render(f: &mut Frame, area: Rect, elapsed: Duration, label: &str, theme: &Theme) {
+        let text = format!(" {} {} ", Self::frame(elapsed), label);
+        let width = u16::try_from(text::width(&text))
+            .unwrap_or(u16::MAX)
+            .min(area.width.saturating_sub(2));
+        let spot = Rect {
+            x: area.right().saturating_sub(width + 1),
+            y: area.y,
+            width,
+            height: 1.min(area.height),
+        };
+        f.render_widget(Clear, spot);
+        f.render_widget(
+            Paragraph::new(text).style(Style::default().fg(theme.accent)),
+            spot,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_frame_advances_and_wraps() {
+        assert_eq!(SpinnerWidget::frame(Duration::ZERO), FRAMES[0]);
+        assert_eq!(SpinnerWidget::frame(FRAME_TIME), FRAMES[1]);
+        assert_eq!(SpinnerWidget::frame(FRAME_TIME * 10), FRAMES[0]);
+    }
+}
@@ -1,7 +1,85 @@
-//! Status bar widget.
+//! Status bar widget, built from the segments `[status_bar]` lists.
 
-use crate::ui::styles;
-use ratatui::{Frame, layout::Rect, widgets::Paragraph};
+use super::usage::format_size;
+use crate::config::Segment;
+use crate::ui::Theme;
+use ratatui::{
+    Frame,
+    layout::Rect,
+    style::Style,
+    text::{Line, Span},
+    widgets::Paragraph,
+};
+use std::time::Duration;
+
+/// What the status bar can show.
+#[derive(Debug, Clone, Copy)]
+pub struct StatusView<'a> {
+    /// Currently selected remote.
+    pub remote: Option<&'a str>,
+    /// Current path within the remote.
+    pub path: &'a str,
+    /// Connection status.
+    pub connected: bool,
+    /// Time until the next reconnect attempt, if one is scheduled.
+    pub retry_in: Option<Duration>,
+    /// Retry a failed request is on, and how many it gets, while one
+    /// is being retried.
+    pub retrying: Option<(u32, u32)>,
+    /// Number of marked entries.
+    pub marked: usize,
+    /// Combined transfer speed in bytes per second, if polled.
+    pub speed: Option<f64>,
+    /// Jobs running on the daemon, if polled.
+    pub jobs: Option<usize>,
+}
+
+/// Returns the text of `segment` and its color, or None when there
+/// is nothing to show, such as no marks or no jobs.
+fn segment_text(segment: Segment, view: &StatusView, theme: &Theme) -> Option<Span<'static>> {
+    let text = match segment {
+        Segment::Location => match view.remote {
+            Some(remote) => format!("{}:{}", remote, view.path),
+            None => "Select a remote".to_string(),
+        },
+        Segment::Remote => view.remote.unwrap_or("Select a remote").to_string(),
+        Segment::Path => {
+            view.remote?;
+            format!("/{}", view.path)
+        }
+        Segment::Connection => {
+            let (status, color) = match (view.connected, view.retry_in) {
+                (true, _) => match view.retrying {
+                    Some((retry, retries)) => (
+                        format!("● Connected, retrying request ({}/{})", retry, retries),
+                        theme.warning,
+                    ),
+                    None => ("● Connected".to_string(), theme.success),
+                },
+                (false, Some(wait)) => (
+                    format!("○ Disconnected, retrying in {}s", wait.as_secs_f64().ceil()),
+                    theme.error,
+                ),
+                (false, None) => ("○ Reconnecting...".to_string(), theme.warning),
+            };
+            return Some(Span::styled(status, Style::default().fg(color)));
+        }
+        Segment::Selection => match view.marked {
+            0 => return None,
+            marked => format!("{} marked", marked),
+        },
+        Segment::Speed => match view.speed {
+            Some(speed) if speed > 0.0 => format!("{}/s", format_size(speed as u64)),
+            _ => return None,
+        },
+        Segment::Jobs => match view.jobs {
+            Some(0) | None => return None,
+            Some(1) => "1 job".to_string(),
+            Some(jobs) => format!("{} jobs", jobs),
+        },
+    };
+    Some(Span::raw(text))
+}
 
 /// Widget for displaying application status.
 pub struct StatusBarWidget;
@@ -12,28 +90,63 @@ impl StatusBarWidget {
     /// # Arguments
     /// * `f` - Frame for rendering
     /// * `area` - Area to render in
-    /// * `remote` - Currently selected remote
-    /// * `path` - Current path within remote
-    /// * `connected` - Connection status
-    pub fn render(f: &mut Frame, area: Rect, remote: Option<&str>, path: &str, connected: bool) {
-        let status = if connected {
-            "Connected"
-        } else {
-            "Disconnected"
-        };
-
-        let display_path = if let Some(r) = remote {
-            if path.is_empty() {
-                format!("{}:", r)
-            } else {
-                format!("{}:{}", r, path)
+    /// * `segments` - Segments to show, left to right
+    /// * `view` - State the segments are drawn from
+    /// * `theme` - Colors to draw with
+    pub fn render(
+        f: &mut Frame,
+        area: Rect,
+        segments: &[Segment],
+        view: &StatusView,
+        theme: &Theme,
+    ) {
+        let mut spans = vec![Span::raw("  ")];
+        for span in segments
+            .iter()
+            .filter_map(|segment| segment_text(*segment, view, theme))
+        {
+            if spans.len() > 1 {
+                spans.push(Span::raw(" | "));
             }
-        } else {
-            "Select a remote".to_string()
+            spans.push(span);
+        }
+        let paragraph = Paragraph::new(Line::from(spans)).style(theme.status_bar());
+        f.render_widget(paragraph, area);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_skips_empty_segments() {
+        let theme = Theme::default();
+        let mut view = StatusView {
+            remote: Some("s3"),
+            path: "docs",
+            connected: true,
+            retry_in: None,
+            retrying: None,
+            marked: 0,
+            speed: Some(0.0),
+            jobs: None,
+        };
+        let text = |segment, view: &StatusView| {
+            segment_text(segment, view, &theme).map(|span| span.content.into_owned())
         };
 
-        let text = format!("  {} | {}  ", display_path, status);
-        let paragraph = Paragraph::new(text).style(styles::status_bar_style());
-        f.render_widget(paragraph, area);
+        assert_eq!(text(Segment::Location, &view).as_deref(), Some("s3:docs"));
+        assert_eq!(text(Segment::Path, &view).as_deref(), Some("/docs"));
+        assert_eq!(text(Segment::Selection, &view), None);
+        assert_eq!(text(Segment::Speed, &view), None);
+        assert_eq!(text(Segment::Jobs, &view), None);
+
+        view.marked = 3;
+        view.speed = Some(2048.0);
+        view.jobs = Some(2);
+        assert_eq!(text(Segment::Selection, &view).as_deref(), Some("3 marked"));
+        assert_eq!(text(Segment::Speed, &view).as_deref(), Some("2.0 KiB/s"));
+        assert_eq!(text(Segment::Jobs, &view).as_deref(), Some("2 jobs"));
     }
 }
@@ -0,0 +1,200 @@
+//! Prompt for the storage tier or class to move the selection to, on
+//! object stores that have them.
+
+use crate::ui::Theme;
+use crate::ui::layout::modal_area;
+use crate::ui::text;
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::Style,
+    text::Line,
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph},
+};
+
+/// Tier prompt state.
+#[derive(Debug, Clone)]
+pub struct TierPrompt {
+    /// Remote the entries are on.
+    pub remote: String,
+    /// Paths of the entries within the remote, with whether each is a
+    /// directory.
+    pub entries: Vec<(String, bool)>,
+    /// Tier typed or picked so far.
+    pub tier: String,
+    /// Tiers the remote's backend offers, if it is one LazyFile knows.
+    pub choices: &'static [&'static str],
+    /// Index of the picked choice, if one is.
+    pub selected: Option<usize>,
+}
+
+impl TierPrompt {
+    /// Creates the prompt for `entries` on `remote`, a remote of type
+    /// `remote_type`.
+    pub fn new(remote: String, remote_type: &str, entries: Vec<(String, bool)>) -> Self {
+        Self {
+            remote,
+            entries,
+            tier: String::new(),
+            choices: tiers(remote_type),
+            selected: None,
+        }
+    }
+
+    pub fn input_char(&mut self, c: char) {
+        if c.is_control() || c.is_whitespace() {
+            return;
+        }
+        self.tier.push(c);
+        self.selected = None;
+    }
+
+    pub fn backspace(&mut self) {
+        text::pop_grapheme(&mut self.tier);
+        self.selected = None;
+    }
+
+    /// Picks the choice `delta` rows from the current one, wrapping
+    /// around, and fills it in.
+    pub fn step(&mut self, delta: isize) {
+        let count = self.choices.len();
+        if count == 0 {
+            return;
+        }
+        let next = match self.selected {
+            Some(index) => (index as isize + delta).rem_euclid(count as isize) as usize,
+            None if delta < 0 => count - 1,
+            None => 0,
+        };
+        self.selected = Some(next);
+        self.tier = self.choices[next].to_string();
+    }
+}
+
+/// Returns the tiers or storage classes of backend `remote_type`, as
+/// rclone names them; empty for backends LazyFile does not know.
+pub fn tiers(remote_type: &str) -> &'static [&'static str] {
+    match remote_type {
+        "s3" => &[
+            "STANDARD",
+            "STANDARD_IA",
+            "ONEZONE_IA",
+            "INTELLIGENT_TIERING",
+            "GLACIER_IR",
+            "GLACIER",
+            "DEEP_ARCHIVE",
+        ],
+        "azureblob" => &["Hot", "Cool", "Cold", "Archive"],
+        "google cloud storage" => &["STANDARD", "NEARLINE", "COLDLINE", "ARCHIVE"],
+        "oracleobjectstorage" => &["Standard", "InfrequentAccess", "Archive"],
+        _ => &[],
+    }
+}
+
+/// Widget for the tier prompt.
+pub struct TierWidget;
+
+impl TierWidget {
+    pub fn render(f: &mut Frame, area: Rect, prompt: &TierPrompt, theme: &Theme) {
+        f.render_widget(Clear, area);
+        f.render_widget(
+            Block::default().style(Style::default().bg(theme.backdrop)),
+            area,
+        );
+
+        let height = 8 + prompt.choices.len().min(8) as u16;
+        let modal_area = modal_area(area, 56, height);
+        f.render_widget(Clear, modal_area);
+        f.render_widget(
+            Block::default()
+                .title(" Change Storage Tier ")
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(theme.border)),
+            modal_area,
+        );
+
+        let inner = Rect {
+            x: modal_area.x + 1,
+            y: modal_area.y + 1,
+            width: modal_area.width.saturating_sub(2),
+            height: modal_area.height.saturating_sub(2),
+        };
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(3),
+                Constraint::Length(1),
+                Constraint::Min(0),
+                Constraint::Length(1),
+            ])
+            .split(inner);
+
+        f.render_widget(
+            Paragraph::new(format!(" {} ", prompt.tier))
+                .style(Style::default().fg(theme.accent))
+                .block(Block::default().title(" Tier ").borders(Borders::ALL)),
+            chunks[0],
+        );
+        let count = match prompt.entries.len() {
+            1 => "1 entry".to_string(),
+            n => format!("{} entries", n),
+        };
+        f.render_widget(
+            Paragraph::new(format!("For {} on {}:", count, prompt.remote))
+                .style(Style::default().fg(theme.dim)),
+            chunks[1],
+        );
+
+        if prompt.choices.is_empty() {
+            f.render_widget(
+                Paragraph::new("Type the tier as the backend names it")
+                    .style(Style::default().fg(theme.dim)),
+                chunks[2],
+            );
+        } else {
+            let items: Vec<ListItem> = prompt
+                .choices
+                .iter()
+                .map(|tier| ListItem::new(Line::raw(*tier)))
+                .collect();
+            let mut state = ListState::default().with_selected(prompt.selected);
+            f.render_stateful_widget(
+                List::new(items)
+                    .highlight_style(theme.selected())
+                    .highlight_symbol(theme.highlight_symbol()),
+                chunks[2],
+                &mut state,
+            );
+        }
+
+        f.render_widget(
+            Paragraph::new("Up/Down: Pick | Enter: Change | Esc: Cancel")
+                .style(Style::default().fg(theme.dim)),
+            chunks[3],
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_picking_wraps_and_typing_clears_the_pick() {
+        let mut prompt = TierPrompt::new("s3".to_string(), "azureblob", Vec::new());
+        prompt.step(-1);
+        assert_eq!(prompt.tier, "Archive");
+        prompt.step(1);
+        assert_eq!(prompt.tier, "Hot");
+        prompt.backspace();
+        assert_eq!((prompt.tier.as_str(), prompt.selected), ("Ho", None));
+
+        let mut prompt = TierPrompt::new("nas".to_string(), "sftp", Vec::new());
+        prompt.step(1);
+        assert!(prompt.tier.is_empty());
+        for c in "COLD LINE".chars() {
+            prompt.input_char(c);
+        }
+        assert_eq!(prompt.tier, "COLDLINE");
+    }
+}
@@ -0,0 +1,399 @@
+//! Usage view: what takes up space below a directory, largest first,
+//! in the manner of ncdu.
+//!
+//! The directory is listed recursively once; moving into and out of
+//! subdirectories then only walks the sizes summed from that listing.
+
+use crate::error::Result;
+use crate::rclone::{CancelToken, TreeEntry};
+use crate::ui::Theme;
+use crate::ui::layout::modal_area;
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::Style,
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph},
+};
+use std::collections::BTreeMap;
+
+/// Width of the bar showing an entry's share of its directory.
+const BAR_WIDTH: usize = 20;
+
+/// Sizes summed over a directory and everything below it.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DirUsage {
+    /// Bytes in the directory and below.
+    pub size: u64,
+    /// Files in the directory and below.
+    pub files: u64,
+    /// Subdirectories, by name.
+    pub dirs: BTreeMap<String, DirUsage>,
+    /// Files directly in the directory, with their sizes.
+    pub file_sizes: Vec<(String, u64)>,
+}
+
+/// One entry of a directory, as listed by the view.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UsageRow {
+    pub name: String,
+    /// Bytes, summed over everything below for a directory.
+    pub size: u64,
+    pub is_dir: bool,
+}
+
+impl DirUsage {
+    /// Sums the sizes of a recursive listing. Sizes a backend cannot
+    /// tell count as zero.
+    pub fn from_entries(entries: &[TreeEntry]) -> Self {
+        let mut root = Self::default();
+        for entry in entries {
+            let parts: Vec<&str> = entry.path.split('/').filter(|p| !p.is_empty()).collect();
+            let size = u64::try_from(entry.size).unwrap_or(0);
+            root.insert(&parts, size, entry.is_dir);
+        }
+        root
+    }
+
+    fn insert(&mut self, parts: &[&str], size: u64, is_dir: bool) {
+        let Some((first, rest)) = parts.split_first() else {
+            return;
+        };
+        if !is_dir {
+            self.size += size;
+            self.files += 1;
+        }
+        if rest.is_empty() && !is_dir {
+            self.file_sizes.push((first.to_string(), size));
+        } else {
+            self.dirs
+                .entry(first.to_string())
+                .or_default()
+                .insert(rest, size, is_dir);
+        }
+    }
+
+    /// Returns the directory's entries, largest first.
+    pub fn rows(&self) -> Vec<UsageRow> {
+        let dirs = self.dirs.iter().map(|(name, dir)| UsageRow {
+            name: name.clone(),
+            size: dir.size,
+            is_dir: true,
+        });
+        let files = self.file_sizes.iter().map(|(name, size)| UsageRow {
+            name: name.clone(),
+            size: *size,
+            is_dir: false,
+        });
+        let mut rows: Vec<UsageRow> = dirs.chain(files).collect();
+        rows.sort_by(|a, b| b.size.cmp(&a.size).then_with(|| a.name.cmp(&b.name)));
+        rows
+    }
+}
+
+/// Usage view state.
+#[derive(Debug, Clone, Default)]
+pub struct UsageView {
+    /// Remote holding the sized directory.
+    pub remote: String,
+    /// Directory that was listed.
+    pub root: String,
+    /// Sizes below `root`, once the listing arrived.
+    pub tree: Option<DirUsage>,
+    /// Whether the listing is in flight.
+    pub loading: bool,
+    /// Drops the listing in flight when the view closes.
+    pub cancel: Option<CancelToken>,
+    /// Why the listing failed, if it did.
+    pub error: Option<String>,
+    /// Subdirectories moved into below `root`, outermost first.
+    pub trail: Vec<String>,
+    /// Index of the highlighted entry.
+    pub selected: usize,
+}
+
+impl UsageView {
+    /// Creates the view for `root` on `remote` while it is listed.
+    pub fn new(remote: String, root: String) -> Self {
+        Self {
+            remote,
+            root,
+            loading: true,
+            ..Self::default()
+        }
+    }
+
+    /// Shows a finished listing, starting again from `root`.
+    pub fn set_entries(&mut self, result: Result<Vec<TreeEntry>>) {
+        self.loading = false;
+        self.cancel = None;
+        match result {
+            Ok(entries) => {
+                self.tree = Some(DirUsage::from_entries(&entries));
+                self.error = None;
+                self.trail.clear();
+                self.selected = 0;
+            }
+            Err(e) => self.error = Some(e.to_string()),
+        }
+    }
+
+    /// Returns the directory shown.
+    pub fn current(&self) -> Option<&DirUsage> {
+        self.trail
+            .iter()
+            .try_fold(self.tree.as_ref()?, |dir, name| dir.dirs.get(name))
+    }
+
+    /// Returns the entries of the directory shown, largest first.
+    pub fn rows(&self) -> Vec<UsageRow> {
+        self.current().map(DirUsage::rows).unwrap_or_default()
+    }
+
+    /// Returns the directory shown, as `remote:path`.
+    pub fn location(&self) -> String {
+        let path = std::iter::once(self.root.as_str())
+            .chain(self.trail.iter().map(String::as_str))
+            .filter(|part| !part.is_empty())
+            .collect::<Vec<_>>()
+            .join("/");
+        format!("{}:{}", self.remote, path)
+    }
+
+    /// Moves into the highlighted directory.
+    pub fn enter(&mut self) {
+        if let Some(row) = self.rows().get(self.selected)
+            && row.is_dir
+        {
+            self.trail.push(row.name.clone());
+            self.selected = 0;
+        }
+    }
+
+    /// Moves back out to the parent directory, highlighting the one
+    /// just left.
+    pub fn leave(&mut self) {
+        if let Some(name) = self.trail.pop() {
+            self.selected = self
+                .rows()
+                .iter()
+                .position(|row| row.is_dir && row.name == name)
+                .unwrap_or(0);
+        }
+    }
+
+    pub fn select_next(&mut self) {
+        if self.selected + 1 < self.rows().len() {
+            self.selected += 1;
+        }
+    }
+
+    pub fn select_prev(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+    }
+}
+
+/// Formats a byte count in binary units, e.g. `1.5 GiB`.
+pub fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 6] = ["B", "KiB", "MiB", "GiB", "TiB", "PiB"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit + 1 < UNITS.len() {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{} B", bytes)
+    } else {
+        format!("{:.1} {}", value, UNITS[unit])
+    }
+}
+
+/// Returns a bar of [`BAR_WIDTH`] cells filled in proportion to
+/// `size` out of `total`.
+fn bar(size: u64, total: u64) -> String {
+    let filled = if total == 0 {
+        0
+    } else {
+        // Rounded share of the bar; the cast is exact below BAR_WIDTH.
+        ((size as f64 / total as f64) * BAR_WIDTH as f64).round() as usize
+    };
+    let filled = filled.min(BAR_WIDTH);
+    format!("{}{}", "█".repeat(filled), "░".repeat(BAR_WIDTH - filled))
+}
+
+/// Widget for the usage view.
+pub struct UsageWidget;
+
+impl UsageWidget {
+    pub fn render(f: &mut Frame, area: Rect, view: &UsageView, theme: &Theme) {
+        f.render_widget(Clear, area);
+        f.render_widget(
+            Block::default().style(Style::default().bg(theme.backdrop)),
+            area,
+        );
+
+        let modal_area = modal_area(area, 80, 24);
+        f.render_widget(Clear, modal_area);
+        f.render_widget(
+            Block::default()
+                .title(format!(" Usage: {} ", view.location()))
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(theme.border)),
+            modal_area,
+        );
+
+        let inner = Rect {
+            x: modal_area.x + 1,
+            y: modal_area.y + 1,
+            width: modal_area.width.saturating_sub(2),
+            height: modal_area.height.saturating_sub(2),
+        };
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(1),
+                Constraint::Min(1),
+                Constraint::Length(1),
+            ])
+            .split(inner);
+
+        let status = match (&view.error, view.current()) {
+            (Some(error), _) => Some((error.clone(), theme.error)),
+            (None, _) if view.loading => Some((
+                format!(
+                    "Sizing {}... this lists everything below it",
+                    view.location()
+                ),
+                theme.dim,
+            )),
+            (None, Some(dir)) if dir.dirs.is_empty() && dir.file_sizes.is_empty() => {
+                Some(("Empty directory".to_string(), theme.dim))
+            }
+            _ => None,
+        };
+        if let Some(dir) = view.current() {
+            let total = format!("Total {} in {} files", format_size(dir.size), dir.files);
+            f.render_widget(
+                Paragraph::new(total).style(Style::default().fg(theme.accent)),
+                chunks[0],
+            );
+        }
+        match (status, view.current()) {
+            (Some((text, color)), _) => {
+                f.render_widget(
+                    Paragraph::new(text).style(Style::default().fg(color)),
+                    chunks[1],
+                );
+            }
+            (None, Some(dir)) => Self::render_rows(f, chunks[1], view, dir.size, theme),
+            (None, None) => {}
+        }
+
+        f.render_widget(
+            Paragraph::new("enter: Open | backspace: Up | r: Refresh | u/Esc: Close")
+                .style(Style::default().fg(theme.dim)),
+            chunks[2],
+        );
+    }
+
+    /// Draws one row per entry: its size, its share of `total` and
+    /// its name.
+    fn render_rows(f: &mut Frame, area: Rect, view: &UsageView, total: u64, theme: &Theme) {
+        let items: Vec<ListItem> = view
+            .rows()
+            .into_iter()
+            .map(|row| {
+                let name = if row.is_dir {
+                    Span::styled(format!("{}/", row.name), Style::default().fg(theme.accent))
+                } else {
+                    Span::raw(row.name)
+                };
+                ListItem::new(Line::from(vec![
+                    Span::raw(format!("{:>10} ", format_size(row.size))),
+                    Span::styled(bar(row.size, total), Style::default().fg(theme.warning)),
+                    Span::raw(" "),
+                    name,
+                ]))
+            })
+            .collect();
+        let mut state = ListState::default().with_selected(Some(view.selected));
+        f.render_stateful_widget(
+            List::new(items)
+                .highlight_style(theme.selected())
+                .highlight_symbol(theme.highlight_symbol()),
+            area,
+            &mut state,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(path: &str, size: i64, is_dir: bool) -> TreeEntry {
+        TreeEntry {
+            path: path.to_string(),
+            size,
+            is_dir,
+            hashes: None,
+        }
+    }
+
+    fn view() -> UsageView {
+        let mut view = UsageView::new("gdrive".to_string(), "backup".to_string());
+        view.set_entries(Ok(vec![
+            entry("notes.txt", 10, false),
+            entry("photos", -1, true),
+            entry("photos/2023", -1, true),
+            entry("photos/2023/a.jpg", 300, false),
+            entry("photos/b.jpg", 200, false),
+            entry("empty", -1, true),
+            entry("doc.gdoc", -1, false),
+        ]));
+        view
+    }
+
+    #[test]
+    fn test_sums_sizes_up_the_tree() {
+        let view = view();
+        let root = view.current().unwrap();
+        assert_eq!(root.size, 510);
+        assert_eq!(root.files, 4);
+        assert_eq!(root.dirs["photos"].size, 500);
+        assert_eq!(root.dirs["photos"].dirs["2023"].size, 300);
+
+        let names: Vec<String> = view.rows().into_iter().map(|row| row.name).collect();
+        assert_eq!(names, ["photos", "notes.txt", "doc.gdoc", "empty"]);
+    }
+
+    #[test]
+    fn test_enter_and_leave_keep_place() {
+        let mut view = view();
+        view.enter();
+        assert_eq!(view.location(), "gdrive:backup/photos");
+        assert_eq!(view.rows()[0].name, "2023");
+
+        view.select_next();
+        view.enter();
+        assert_eq!(view.trail, ["photos"], "files cannot be entered");
+
+        view.leave();
+        assert_eq!(view.location(), "gdrive:backup");
+        assert_eq!(view.selected, 0);
+    }
+
+    #[test]
+    fn test_formats_binary_units() {
+        assert_eq!(format_size(512), "512 B");
+        assert_eq!(format_size(1536), "1.5 KiB");
+        assert_eq!(format_size(3 * 1024 * 1024 * 1024), "3.0 GiB");
+    }
+
+    #[test]
+    fn test_bar_fills_share() {
+        assert_eq!(bar(1, 2).chars().filter(|&c| c == '█').count(), 10);
+        assert_eq!(bar(0, 0), "░".repeat(BAR_WIDTH));
+    }
+}
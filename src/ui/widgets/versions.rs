@@ -0,0 +1,195 @@
+//! Prior versions of a file on backends that keep them, with their
+//! upload times and sizes.
+
+use super::usage::format_size;
+use crate::rclone::Version;
+use crate::ui::Theme;
+use crate::ui::layout::modal_area;
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::Style,
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph},
+};
+
+/// Versions view state.
+#[derive(Debug, Clone)]
+pub struct VersionsView {
+    /// Remote the file is on.
+    pub remote: String,
+    /// Path of the file within the remote.
+    pub path: String,
+    /// Versions, newest first, once listed; `None` while loading.
+    pub versions: Option<Result<Vec<Version>, String>>,
+    /// Index of the highlighted version.
+    pub selected: usize,
+    /// What is being done with a version, while it is.
+    pub busy: Option<String>,
+}
+
+impl VersionsView {
+    /// Creates the view for the file at `path` on `remote`, waiting
+    /// for its versions.
+    pub fn new(remote: String, path: String) -> Self {
+        Self {
+            remote,
+            path,
+            versions: None,
+            selected: 0,
+            busy: None,
+        }
+    }
+
+    /// Shows listed versions, keeping the highlighted one in range.
+    pub fn set_versions(&mut self, versions: Result<Vec<Version>, String>) {
+        let count = versions.as_ref().map_or(0, Vec::len);
+        self.selected = self.selected.min(count.saturating_sub(1));
+        self.versions = Some(versions);
+    }
+
+    /// Returns the highlighted version, if any.
+    pub fn selected_version(&self) -> Option<&Version> {
+        match self.versions {
+            Some(Ok(ref versions)) => versions.get(self.selected),
+            _ => None,
+        }
+    }
+
+    pub fn select_next(&mut self) {
+        let count = match self.versions {
+            Some(Ok(ref versions)) => versions.len(),
+            _ => 0,
+        };
+        if self.selected + 1 < count {
+            self.selected += 1;
+        }
+    }
+
+    pub fn select_prev(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+    }
+}
+
+/// Returns whether backend `remote_type` keeps prior versions of files
+/// that rclone can list.
+pub fn keeps_versions(remote_type: &str) -> bool {
+    matches!(remote_type, "s3" | "b2")
+}
+
+/// Widget for the versions view.
+pub struct VersionsWidget;
+
+impl VersionsWidget {
+    pub fn render(f: &mut Frame, area: Rect, view: &VersionsView, theme: &Theme) {
+        f.render_widget(Clear, area);
+        f.render_widget(
+            Block::default().style(Style::default().bg(theme.backdrop)),
+            area,
+        );
+
+        let modal_area = modal_area(area, 80, 20);
+        f.render_widget(Clear, modal_area);
+        f.render_widget(
+            Block::default()
+                .title(format!(" Versions of {}:{} ", view.remote, view.path))
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(theme.border)),
+            modal_area,
+        );
+
+        let inner = Rect {
+            x: modal_area.x + 1,
+            y: modal_area.y + 1,
+            width: modal_area.width.saturating_sub(2),
+            height: modal_area.height.saturating_sub(2),
+        };
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Min(1),
+                Constraint::Length(1),
+                Constraint::Length(1),
+            ])
+            .split(inner);
+
+        match view.versions {
+            None => f.render_widget(
+                Paragraph::new("Loading...").style(Style::default().fg(theme.dim)),
+                chunks[0],
+            ),
+            Some(Err(ref error)) => f.render_widget(
+                Paragraph::new(error.as_str()).style(Style::default().fg(theme.error)),
+                chunks[0],
+            ),
+            Some(Ok(ref versions)) if versions.is_empty() => f.render_widget(
+                Paragraph::new("No prior versions of this file")
+                    .style(Style::default().fg(theme.dim)),
+                chunks[0],
+            ),
+            Some(Ok(ref versions)) => {
+                let items: Vec<ListItem> = versions
+                    .iter()
+                    .map(|version| {
+                        let size = u64::try_from(version.size)
+                            .map_or_else(|_| "?".to_string(), format_size);
+                        ListItem::new(Line::from(vec![
+                            Span::raw(format!("{} UTC  ", version.time)),
+                            Span::styled(
+                                format!("{:>10}  ", size),
+                                Style::default().fg(theme.accent),
+                            ),
+                            Span::styled(version.name.as_str(), Style::default().fg(theme.dim)),
+                        ]))
+                    })
+                    .collect();
+                let mut state = ListState::default().with_selected(Some(view.selected));
+                f.render_stateful_widget(
+                    List::new(items)
+                        .highlight_style(theme.selected())
+                        .highlight_symbol(theme.highlight_symbol()),
+                    chunks[0],
+                    &mut state,
+                );
+            }
+        }
+
+        if let Some(ref busy) = view.busy {
+            f.render_widget(
+                Paragraph::new(busy.as_str()).style(Style::default().fg(theme.warning)),
+                chunks[1],
+            );
+        }
+        f.render_widget(
+            Paragraph::new(
+                "j/k: Select | d: Download to current directory | r: Restore | Esc: Close",
+            )
+            .style(Style::default().fg(theme.dim)),
+            chunks[2],
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_selection_stays_within_the_versions() {
+        let mut view = VersionsView::new("b2".to_string(), "a.txt".to_string());
+        view.select_next();
+        assert!(view.selected_version().is_none());
+
+        let version = |time: &str| Version {
+            time: time.to_string(),
+            ..Version::default()
+        };
+        view.set_versions(Ok(vec![version("2024-03-02"), version("2024-03-01")]));
+        view.select_next();
+        view.select_next();
+        assert_eq!(view.selected_version().unwrap().time, "2024-03-01");
+
+        view.set_versions(Ok(vec![version("2024-03-03")]));
+        assert_eq!(view.selected, 0);
+    }
+}
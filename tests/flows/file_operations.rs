@@ -29,6 +29,7 @@ async fn test_flow_mkdir_via_modal() {
     Handler::handle_key(&mut app, create_key_event(KeyCode::Enter))
         .await
         .unwrap();
+    Handler::settle(&mut app).await;
 
     Handler::handle_key(&mut app, create_key_event(KeyCode::Char('n')))
         .await
@@ -45,6 +46,8 @@ async fn test_flow_mkdir_via_modal() {
         .await
         .unwrap();
 
+    Handler::settle(&mut app).await;
+
     assert!(app.file_operations_modal().is_none());
     assert!(test_dir.join("new_folder").exists());
     assert!(test_dir.join("new_folder").is_dir());
@@ -78,11 +81,12 @@ async fn test_flow_delete_file_via_modal() {
     Handler::handle_key(&mut app, create_key_event(KeyCode::Enter))
         .await
         .unwrap();
+    Handler::settle(&mut app).await;
 
     assert_eq!(app.files.len(), 1);
     assert_eq!(app.files[0].name(), "to_delete.txt");
 
-    Handler::handle_key(&mut app, create_key_event(KeyCode::Char('x')))
+    Handler::handle_key(&mut app, create_key_event(KeyCode::Char('D')))
         .await
         .unwrap();
     assert!(app.file_operations_modal().is_some());
@@ -91,6 +95,8 @@ async fn test_flow_delete_file_via_modal() {
         .await
         .unwrap();
 
+    Handler::settle(&mut app).await;
+
     assert!(!test_dir.join("to_delete.txt").exists());
     assert!(app.files.is_empty());
 
@@ -123,6 +129,7 @@ async fn test_flow_copy_file_via_modal() {
     Handler::handle_key(&mut app, create_key_event(KeyCode::Enter))
         .await
         .unwrap();
+    Handler::settle(&mut app).await;
 
     Handler::handle_key(&mut app, create_key_event(KeyCode::Char('c')))
         .await
@@ -139,6 +146,8 @@ async fn test_flow_copy_file_via_modal() {
         .await
         .unwrap();
 
+    Handler::settle(&mut app).await;
+
     assert!(test_dir.join("source.txt").exists());
     assert!(test_dir.join("destination.txt").exists());
 
@@ -175,6 +184,7 @@ async fn test_flow_move_file_via_modal() {
     Handler::handle_key(&mut app, create_key_event(KeyCode::Enter))
         .await
         .unwrap();
+    Handler::settle(&mut app).await;
 
     Handler::handle_key(&mut app, create_key_event(KeyCode::Char('m')))
         .await
@@ -191,6 +201,8 @@ async fn test_flow_move_file_via_modal() {
         .await
         .unwrap();
 
+    Handler::settle(&mut app).await;
+
     assert!(!test_dir.join("source.txt").exists());
     assert!(test_dir.join("destination.txt").exists());
 
@@ -224,11 +236,12 @@ async fn test_flow_purge_directory_via_modal() {
     Handler::handle_key(&mut app, create_key_event(KeyCode::Enter))
         .await
         .unwrap();
+    Handler::settle(&mut app).await;
 
     assert_eq!(app.files.len(), 1);
     assert!(app.files[0].is_dir());
 
-    Handler::handle_key(&mut app, create_key_event(KeyCode::Char('x')))
+    Handler::handle_key(&mut app, create_key_event(KeyCode::Char('D')))
         .await
         .unwrap();
     assert!(app.file_operations_modal().is_some());
@@ -237,6 +250,8 @@ async fn test_flow_purge_directory_via_modal() {
         .await
         .unwrap();
 
+    Handler::settle(&mut app).await;
+
     assert!(!test_dir.join("to_purge").exists());
     assert!(app.files.is_empty());
 
@@ -267,6 +282,7 @@ async fn test_flow_multiple_operations() {
     Handler::handle_key(&mut app, create_key_event(KeyCode::Enter))
         .await
         .unwrap();
+    Handler::settle(&mut app).await;
 
     Handler::handle_key(&mut app, create_key_event(KeyCode::Char('n')))
         .await
@@ -279,15 +295,19 @@ async fn test_flow_multiple_operations() {
     Handler::handle_key(&mut app, create_key_event(KeyCode::Enter))
         .await
         .unwrap();
+    Handler::settle(&mut app).await;
     assert!(test_dir.join("mydir").exists());
 
     Handler::handle_key(&mut app, create_key_event(KeyCode::Enter))
         .await
         .unwrap();
+
+    Handler::settle(&mut app).await;
     assert_eq!(app.current_path, "mydir");
 
     fs::write(test_dir.join("mydir/test.txt"), "test content").unwrap();
-    app.load_files().await.unwrap();
+    app.reload_files();
+    Handler::settle(&mut app).await;
     assert_eq!(app.files.len(), 1);
 
     Handler::handle_key(&mut app, create_key_event(KeyCode::Char('c')))
@@ -301,17 +321,20 @@ async fn test_flow_multiple_operations() {
     Handler::handle_key(&mut app, create_key_event(KeyCode::Enter))
         .await
         .unwrap();
+    Handler::settle(&mut app).await;
     assert!(test_dir.join("mydir/test_copy.txt").exists());
 
     app.files_selected = 0;
-    Handler::handle_key(&mut app, create_key_event(KeyCode::Char('x')))
+    Handler::handle_key(&mut app, create_key_event(KeyCode::Char('D')))
         .await
         .unwrap();
     Handler::handle_key(&mut app, create_key_event(KeyCode::Enter))
         .await
         .unwrap();
+    Handler::settle(&mut app).await;
 
-    app.load_files().await.unwrap();
+    app.reload_files();
+    Handler::settle(&mut app).await;
     assert_eq!(app.files.len(), 1);
     assert_eq!(app.files[0].name(), "test_copy.txt");
 
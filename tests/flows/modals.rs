@@ -29,6 +29,7 @@ async fn test_flow_cancel_modal_with_escape() {
     Handler::handle_key(&mut app, create_key_event(KeyCode::Enter))
         .await
         .unwrap();
+    Handler::settle(&mut app).await;
 
     Handler::handle_key(&mut app, create_key_event(KeyCode::Char('n')))
         .await
@@ -75,6 +76,7 @@ async fn test_flow_modal_validation_error() {
     Handler::handle_key(&mut app, create_key_event(KeyCode::Enter))
         .await
         .unwrap();
+    Handler::settle(&mut app).await;
 
     Handler::handle_key(&mut app, create_key_event(KeyCode::Char('n')))
         .await
@@ -84,6 +86,8 @@ async fn test_flow_modal_validation_error() {
         .await
         .unwrap();
 
+    Handler::settle(&mut app).await;
+
     assert!(app.file_operations_modal().is_some());
     let modal = app.file_operations_modal().unwrap();
     assert!(modal.error.is_some());
@@ -96,6 +100,7 @@ async fn test_flow_modal_validation_error() {
     Handler::handle_key(&mut app, create_key_event(KeyCode::Enter))
         .await
         .unwrap();
+    Handler::settle(&mut app).await;
 
     assert!(app.file_operations_modal().is_none());
     assert!(test_dir.join("valid_dir").exists());
@@ -127,6 +132,7 @@ async fn test_flow_modal_backspace_clears_input() {
     Handler::handle_key(&mut app, create_key_event(KeyCode::Enter))
         .await
         .unwrap();
+    Handler::settle(&mut app).await;
 
     Handler::handle_key(&mut app, create_key_event(KeyCode::Char('n')))
         .await
@@ -142,6 +148,7 @@ async fn test_flow_modal_backspace_clears_input() {
         Handler::handle_key(&mut app, create_key_event(KeyCode::Backspace))
             .await
             .unwrap();
+        Handler::settle(&mut app).await;
     }
 
     for c in "correct".chars() {
@@ -154,6 +161,8 @@ async fn test_flow_modal_backspace_clears_input() {
         .await
         .unwrap();
 
+    Handler::settle(&mut app).await;
+
     assert!(!test_dir.join("wrong").exists());
     assert!(test_dir.join("correct").exists());
 
@@ -186,6 +195,7 @@ async fn test_flow_copy_modal_with_existing_file() {
     Handler::handle_key(&mut app, create_key_event(KeyCode::Enter))
         .await
         .unwrap();
+    Handler::settle(&mut app).await;
 
     Handler::handle_key(&mut app, create_key_event(KeyCode::Char('c')))
         .await
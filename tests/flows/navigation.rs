@@ -41,6 +41,8 @@ async fn test_flow_create_remote_and_browse() {
         .await
         .unwrap();
 
+    Handler::settle(&mut app).await;
+
     assert_eq!(app.focused_panel, Panel::Files);
     assert_eq!(app.current_remote, Some(remote_name.clone()));
     assert_eq!(app.files.len(), 3);
@@ -55,6 +57,7 @@ async fn test_flow_create_remote_and_browse() {
     Handler::handle_key(&mut app, create_key_event(KeyCode::Enter))
         .await
         .unwrap();
+    Handler::settle(&mut app).await;
 
     assert_eq!(app.current_path, "subdir");
     assert_eq!(app.files.len(), 1);
@@ -62,12 +65,16 @@ async fn test_flow_create_remote_and_browse() {
     Handler::handle_key(&mut app, create_key_event(KeyCode::Backspace))
         .await
         .unwrap();
+
+    Handler::settle(&mut app).await;
     assert_eq!(app.current_path, "");
     assert_eq!(app.files.len(), 3);
 
     Handler::handle_key(&mut app, create_key_event(KeyCode::Backspace))
         .await
         .unwrap();
+
+    Handler::settle(&mut app).await;
     assert_eq!(app.focused_panel, Panel::Remotes);
     assert!(app.current_remote.is_none());
 
@@ -101,11 +108,13 @@ async fn test_flow_deep_navigation() {
     Handler::handle_key(&mut app, create_key_event(KeyCode::Enter))
         .await
         .unwrap();
+    Handler::settle(&mut app).await;
 
     for dir in &["a", "b", "c", "d"] {
         Handler::handle_key(&mut app, create_key_event(KeyCode::Enter))
             .await
             .unwrap();
+        Handler::settle(&mut app).await;
         assert!(app.current_path.ends_with(dir));
     }
 
@@ -116,12 +125,15 @@ async fn test_flow_deep_navigation() {
         Handler::handle_key(&mut app, create_key_event(KeyCode::Backspace))
             .await
             .unwrap();
+        Handler::settle(&mut app).await;
     }
     assert_eq!(app.current_path, "");
 
     Handler::handle_key(&mut app, create_key_event(KeyCode::Backspace))
         .await
         .unwrap();
+
+    Handler::settle(&mut app).await;
     assert_eq!(app.focused_panel, Panel::Remotes);
 
     client.delete_remote(&remote_name).await.unwrap();
@@ -166,6 +178,7 @@ async fn test_flow_panel_switching() {
     Handler::handle_key(&mut app, create_key_event(KeyCode::Enter))
         .await
         .unwrap();
+    Handler::settle(&mut app).await;
 
     assert_eq!(app.focused_panel, Panel::Files);
     assert_eq!(app.files.len(), 1);
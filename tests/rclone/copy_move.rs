@@ -187,3 +187,27 @@ async fn test_move_nonexistent_file() {
 
     cleanup_test_dir("move_nonexistent");
 }
+
+#[tokio::test]
+async fn test_upload_stream_writes_everything_read() {
+    let client = create_test_client();
+    setup_test_remote(&client).await;
+    let test_dir = get_test_dir("upload_stream");
+    let remote_path = get_remote_path("upload_stream");
+
+    let content = "line\n".repeat(100_000);
+    let reader = std::io::Cursor::new(content.clone().into_bytes());
+    let dst_path = format!("{}/dump.sql", remote_path);
+    let sent = client
+        .upload_stream(TEST_REMOTE, &dst_path, reader)
+        .await
+        .expect("upload_stream should succeed");
+
+    assert_eq!(sent, content.len() as u64);
+    assert_eq!(
+        fs::read_to_string(test_dir.join("dump.sql")).unwrap(),
+        content
+    );
+
+    cleanup_test_dir("upload_stream");
+}